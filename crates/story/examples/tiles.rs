@@ -53,6 +53,12 @@ impl StoryTiles {
 
         cx.subscribe(&dock_area, |this, dock_area, ev: &DockEvent, cx| match ev {
             DockEvent::LayoutChanged => this.save_layout(dock_area, cx),
+            DockEvent::InvalidPanel { panel_name, .. } => {
+                eprintln!(
+                    "panel `{}` is not registered, show a placeholder",
+                    panel_name
+                );
+            }
         })
         .detach();
 
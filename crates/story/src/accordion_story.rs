@@ -12,6 +12,7 @@ pub struct AccordionStory {
     size: Size,
     bordered: bool,
     disabled: bool,
+    multiple: bool,
     focus_handle: FocusHandle,
 }
 
@@ -36,6 +37,7 @@ impl AccordionStory {
             open_ixs: Vec::new(),
             size: Size::default(),
             disabled: false,
+            multiple: false,
             focus_handle: cx.focus_handle(),
         }
     }
@@ -108,6 +110,15 @@ impl Render for AccordionStory {
                                 cx.notify();
                             })),
                     )
+                    .child(
+                        Checkbox::new("multiple")
+                            .label("Multiple")
+                            .checked(self.multiple)
+                            .on_click(cx.listener(|this, checked, cx| {
+                                this.multiple = *checked;
+                                cx.notify();
+                            })),
+                    )
                     .child(
                         Checkbox::new("bordered")
                             .label("Bordered")
@@ -123,15 +134,15 @@ impl Render for AccordionStory {
                     .bordered(self.bordered)
                     .with_size(self.size)
                     .disabled(self.disabled)
+                    .multiple(self.multiple)
+                    .open_indices(self.open_ixs.clone())
                     .item(|this|
-                        this.open(self.open_ixs.contains(&0))
-                            .icon(IconName::Info)
+                        this.icon(IconName::Info)
                             .title("This is first accordion")
                             .content("Hello")
                     )
                    .item(|this|
-                        this.open(self.open_ixs.contains(&1))
-                            .icon(IconName::Inbox)
+                        this.icon(IconName::Inbox)
                             .title("This is second accordion")
                             .content(
                                 v_flex()
@@ -144,13 +155,19 @@ impl Render for AccordionStory {
                             )
                     )
                      .item(|this|
-                        this.open(self.open_ixs.contains(&2))
-                            .icon(IconName::Moon)
+                        this.icon(IconName::Moon)
                             .title("This is third accordion")
                             .content(
                                 "This is the third accordion content. It can be any view, like a text view or a button."
                             )
-                    ) .on_toggle_click(cx.listener(|this, open_ixs: &[usize], cx| {
+                    )
+                    .item(|this|
+                        this.disabled(true)
+                            .icon(IconName::Info)
+                            .title("This is a disabled accordion")
+                            .content("The disabled accordion can't be toggled.")
+                    )
+                    .on_toggle_click(cx.listener(|this, open_ixs: &[usize], cx| {
                         this.toggle_accordion(open_ixs.to_vec(), cx);
                     })),
             )
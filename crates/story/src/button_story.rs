@@ -1,5 +1,5 @@
 use gpui::{
-    px, ClickEvent, FocusableView, IntoElement, ParentElement as _, Render, Styled as _, View,
+    div, px, ClickEvent, FocusableView, IntoElement, ParentElement as _, Render, Styled as _, View,
     ViewContext, VisualContext as _, WindowContext,
 };
 
@@ -9,8 +9,11 @@ use ui::{
     checkbox::Checkbox,
     h_flex,
     prelude::FluentBuilder,
+    switch::Switch,
+    tab::Tab,
     theme::{ActiveTheme, Theme},
-    v_flex, Disableable as _, Icon, IconName, Selectable as _, Sizable as _,
+    v_flex, AutomationId as _, Disableable as _, Icon, IconName, RovingFocus, Selectable as _,
+    Sizable as _, Size,
 };
 
 use crate::section;
@@ -22,6 +25,7 @@ pub struct ButtonStory {
     selected: bool,
     compact: bool,
     toggle_multiple: bool,
+    roving_focus: RovingFocus,
 }
 
 impl ButtonStory {
@@ -33,6 +37,7 @@ impl ButtonStory {
             selected: false,
             compact: false,
             toggle_multiple: false,
+            roving_focus: RovingFocus::new(),
         })
     }
 
@@ -134,6 +139,7 @@ impl Render for ButtonStory {
                         section("Normal Button", cx)
                             .child(
                                 Button::new("button-1")
+                                    .automation_id("primary-button")
                                     .primary()
                                     .label("Primary Button")
                                     .disabled(disabled)
@@ -473,6 +479,7 @@ impl Render for ButtonStory {
                     .child(
                         ButtonGroup::new("button-group")
                             .small()
+                            .roving_focus(&self.roving_focus)
                             .disabled(disabled)
                             .child(
                                 Button::new("button-one")
@@ -667,5 +674,56 @@ impl Render for ButtonStory {
                             .when(compact, |this| this.compact()),
                     ),
             )
+            .child(
+                section("Size Matrix", cx).child(
+                    v_flex().w_full().gap_3().children(
+                        [
+                            ("XSmall", Size::XSmall),
+                            ("Small", Size::Small),
+                            ("Medium", Size::Medium),
+                            ("Large", Size::Large),
+                            ("XLarge", Size::XLarge),
+                        ]
+                        .into_iter()
+                        .enumerate()
+                        .map(|(ix, (name, size))| {
+                            h_flex()
+                                .gap_4()
+                                .items_center()
+                                .child(div().w_16().text_sm().child(name))
+                                .child(
+                                    Button::new(("size-matrix-button", ix))
+                                        .icon(IconName::Heart)
+                                        .label("Button")
+                                        .with_size(size),
+                                )
+                                .child(
+                                    Button::new(("size-matrix-icon", ix))
+                                        .icon(IconName::Search)
+                                        .label("Search")
+                                        .icon_only(true)
+                                        .with_size(size),
+                                )
+                                .child(
+                                    Switch::new(("size-matrix-switch", ix))
+                                        .checked(true)
+                                        .label("Switch")
+                                        .with_size(size),
+                                )
+                                .child(
+                                    Checkbox::new(("size-matrix-checkbox", ix))
+                                        .checked(true)
+                                        .label("Checkbox")
+                                        .with_size(size),
+                                )
+                                .child(
+                                    Tab::new(("size-matrix-tab", ix), "Tab")
+                                        .selected(true)
+                                        .with_size(size),
+                                )
+                        }),
+                    ),
+                ),
+            )
     }
 }
@@ -1,4 +1,4 @@
-use chrono::{Days, Duration, Utc};
+use chrono::{Days, Duration, Utc, Weekday};
 use gpui::{
     px, IntoElement, ParentElement as _, Render, Styled as _, View, ViewContext,
     VisualContext as _, WindowContext,
@@ -88,6 +88,8 @@ impl CalendarStory {
             DatePicker::new("date_picker_large", cx)
                 .large()
                 .date_format("%Y-%m-%d")
+                .first_day_of_week(Weekday::Mon)
+                .week_number(true)
                 .width(px(300.))
         });
         let date_picker_small = cx.new_view(|cx| {
@@ -175,7 +177,8 @@ impl Render for CalendarStory {
                 Button::new("change-size")
                     .label(format!("size: {:?}", self.size))
                     .on_click(cx.listener(|this, _, cx| match this.size {
-                        Size::Small => this.change_size(Size::Medium, cx),
+                        Size::XSmall => this.change_size(Size::Medium, cx),
+                        Size::Small => this.change_size(Size::XSmall, cx),
                         Size::Large => this.change_size(Size::Small, cx),
                         _ => this.change_size(Size::Large, cx),
                     })),
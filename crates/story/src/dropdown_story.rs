@@ -1,12 +1,14 @@
 use gpui::{
-    actions, px, AppContext, InteractiveElement, IntoElement, KeyBinding, ParentElement, Render,
-    SharedString, Styled, View, ViewContext, VisualContext, WindowContext,
+    actions, px, AppContext, InteractiveElement, IntoElement, ParentElement, Render, SharedString,
+    Styled, View, ViewContext, VisualContext, WindowContext,
 };
 
 use ui::{
+    cascader::{Cascader, CascaderOption},
     checkbox::Checkbox,
     dropdown::{Dropdown, DropdownEvent, DropdownItem, SearchableVec},
     h_flex,
+    keymap::{self, KeymapEntry},
     theme::ActiveTheme,
     v_flex, FocusableCycle, IconName, Sizable,
 };
@@ -15,10 +17,13 @@ actions!(dropdown_story, [Tab, TabPrev]);
 
 const CONTEXT: &str = "DropdownStory";
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([
-        KeyBinding::new("shift-tab", TabPrev, Some(CONTEXT)),
-        KeyBinding::new("tab", Tab, Some(CONTEXT)),
-    ])
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("shift-tab", TabPrev, Some(CONTEXT)),
+            KeymapEntry::new("tab", Tab, Some(CONTEXT)),
+        ],
+    )
 }
 
 struct Country {
@@ -55,6 +60,8 @@ pub struct DropdownStory {
     simple_dropdown2: View<Dropdown<SearchableVec<SharedString>>>,
     simple_dropdown3: View<Dropdown<Vec<SharedString>>>,
     disabled_dropdown: View<Dropdown<Vec<SharedString>>>,
+    multiple_dropdown: View<Dropdown<Vec<SharedString>>>,
+    cascader: View<Cascader>,
 }
 
 impl super::Story for DropdownStory {
@@ -112,12 +119,45 @@ impl DropdownStory {
                 .menu_width(px(320.))
         });
 
+        let locations = vec![
+            CascaderOption::new("asia", "Asia").children([
+                CascaderOption::new("cn", "China").children([
+                    CascaderOption::new("bj", "Beijing"),
+                    CascaderOption::new("sh", "Shanghai"),
+                    CascaderOption::new("sz", "Shenzhen"),
+                ]),
+                CascaderOption::new("jp", "Japan").children([
+                    CascaderOption::new("tokyo", "Tokyo"),
+                    CascaderOption::new("osaka", "Osaka"),
+                ]),
+                CascaderOption::new("sg", "Singapore"),
+            ]),
+            CascaderOption::new("america", "America").children([
+                CascaderOption::new("us", "United States").children([
+                    CascaderOption::new("ny", "New York"),
+                    CascaderOption::new("sf", "San Francisco"),
+                ]),
+                CascaderOption::new("ca", "Canada").children([
+                    CascaderOption::new("toronto", "Toronto"),
+                    CascaderOption::new("vancouver", "Vancouver"),
+                ]),
+            ]),
+        ];
+        let cascader = cx.new_view(|cx| {
+            Cascader::new("cascader-location", locations, cx)
+                .searchable(true, cx)
+                .cleanable()
+                .placeholder("Location")
+                .width(px(320.))
+        });
+
         cx.new_view(|cx| {
             cx.subscribe(&country_dropdown, Self::on_dropdown_event)
                 .detach();
 
             Self {
                 disabled: false,
+                cascader,
                 country_dropdown,
                 fruit_dropdown,
                 simple_dropdown1: cx.new_view(|cx| {
@@ -139,6 +179,9 @@ impl DropdownStory {
                             "Go".into(),
                             "C++".into(),
                             "JavaScript".into(),
+                            "中国平安".into(),
+                            "招商银行".into(),
+                            "贵州茅台".into(),
                         ]),
                         None,
                         cx,
@@ -163,6 +206,25 @@ impl DropdownStory {
                         .small()
                         .disabled(true)
                 }),
+                multiple_dropdown: cx.new_view(|cx| {
+                    Dropdown::new(
+                        "multiple-dropdown",
+                        vec![
+                            "Rust".into(),
+                            "Go".into(),
+                            "C++".into(),
+                            "JavaScript".into(),
+                            "Python".into(),
+                            "Swift".into(),
+                        ],
+                        None,
+                        cx,
+                    )
+                    .multiple(true)
+                    .cleanable()
+                    .placeholder("Languages")
+                    .width(px(320.))
+                }),
             }
         })
     }
@@ -179,6 +241,9 @@ impl DropdownStory {
     ) {
         match event {
             DropdownEvent::Confirm(value) => println!("Selected country: {:?}", value),
+            DropdownEvent::SelectionChanged(values) => {
+                println!("Selected countries: {:?}", values)
+            }
         }
     }
 
@@ -204,6 +269,10 @@ impl DropdownStory {
             .update(cx, |this, _| this.set_disabled(disabled));
         self.simple_dropdown3
             .update(cx, |this, _| this.set_disabled(disabled));
+        self.multiple_dropdown
+            .update(cx, |this, _| this.set_disabled(disabled));
+        self.cascader
+            .update(cx, |this, _| this.set_disabled(disabled));
     }
 }
 
@@ -218,6 +287,8 @@ impl FocusableCycle for DropdownStory {
             self.simple_dropdown1.focus_handle(cx),
             self.simple_dropdown2.focus_handle(cx),
             self.simple_dropdown3.focus_handle(cx),
+            self.multiple_dropdown.focus_handle(cx),
+            self.cascader.focus_handle(cx),
         ]
     }
 }
@@ -244,7 +315,9 @@ impl Render for DropdownStory {
                     .items_center()
                     .gap_4()
                     .child(self.country_dropdown.clone())
-                    .child(self.fruit_dropdown.clone()),
+                    .child(self.fruit_dropdown.clone())
+                    .child(self.multiple_dropdown.clone())
+                    .child(self.cascader.clone()),
             )
             .child(
                 v_flex()
@@ -272,6 +345,14 @@ impl Render for DropdownStory {
                         "Language: {:?}",
                         self.simple_dropdown2.read(cx).selected_value()
                     ))
+                    .child(format!(
+                        "Languages: {:?}",
+                        self.multiple_dropdown.read(cx).selected_values()
+                    ))
+                    .child(format!(
+                        "Location: {:?}",
+                        self.cascader.read(cx).selected_values()
+                    ))
                     .child("This is other text."),
             )
             .child(
@@ -1,5 +1,9 @@
 use gpui::{px, ParentElement as _, Render, Styled, View, VisualContext as _, WindowContext};
-use ui::{h_flex, v_flex, SvgImg};
+use ui::{
+    h_flex,
+    image::{Image, ObjectFit},
+    v_flex, SvgImg,
+};
 
 const GOOGLE_LOGO: &str = include_str!("./fixtures/google.svg");
 const PIE_JSON: &str = include_str!("./fixtures/pie.json");
@@ -61,5 +65,24 @@ impl Render for ImageStory {
             )
             .child(self.inbox_img.clone().flex_shrink_0().size(px(64.)))
             .child(self.pie_chart.clone().flex_shrink_0().w_full().h(px(400.)))
+            .child(
+                h_flex()
+                    .gap_4()
+                    .children(
+                        [ObjectFit::Cover, ObjectFit::Contain, ObjectFit::Fill].map(|fit| {
+                            Image::new("https://picsum.photos/id/10/640/360")
+                                .object_fit(fit)
+                                .w(px(200.))
+                                .h(px(160.))
+                                .rounded_md()
+                        }),
+                    )
+                    .child(
+                        Image::new("https://example.invalid/missing.png")
+                            .w(px(200.))
+                            .h(px(160.))
+                            .rounded_md(),
+                    ),
+            )
     }
 }
@@ -1,16 +1,22 @@
 use gpui::{
-    actions, div, px, AppContext, FocusHandle, InteractiveElement, IntoElement, KeyBinding,
-    ParentElement as _, Render, SharedString, Styled, View, ViewContext, VisualContext,
+    actions, div, px, AppContext, FocusHandle, InteractiveElement, IntoElement, ParentElement as _,
+    Render, SharedString, Styled, Subscription, Task, Timer, View, ViewContext, VisualContext,
     WindowContext,
 };
 use regex::Regex;
+use std::time::Duration;
 
 use crate::section;
 use ui::{
+    binding::Binding,
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
     h_flex,
-    input::{InputEvent, OtpInput, TextInput},
+    input::{
+        self, Completion, CompletionProvider, InputEvent, InputOptEvent, MaskPattern, OtpInput,
+        TagInput, TextDecoration, TextInput, ValidationState,
+    },
+    keymap::{self, KeymapEntry},
     number_input::{NumberInput, NumberInputEvent},
     prelude::FluentBuilder as _,
     v_flex, FocusableCycle, IconName, Sizable,
@@ -18,13 +24,60 @@ use ui::{
 
 actions!(input_story, [Tab, TabPrev]);
 
+/// Highlight the "GPUI" and mark the "a some" as a typo in the textarea.
+fn decorate_textarea(input: &mut TextInput, cx: &mut ViewContext<TextInput>) {
+    let text = input.text();
+    let highlights = text.match_indices("GPUI").map(|(ix, word)| {
+        TextDecoration::background(ix..ix + word.len(), ui::yellow_500().opacity(0.3))
+    });
+    let typos = text
+        .match_indices("a some")
+        .map(|(ix, word)| TextDecoration::squiggle(ix..ix + word.len(), ui::red_500()));
+    let decorations = highlights.chain(typos).collect::<Vec<_>>();
+    input.set_decorations(decorations, cx);
+}
+
+/// Complete the mentions of the users after `@`.
+struct MentionProvider;
+
+impl CompletionProvider for MentionProvider {
+    fn trigger_characters(&self) -> &[char] {
+        &['@']
+    }
+
+    fn completions(&self, token: &str, _: &mut WindowContext) -> Task<Vec<Completion>> {
+        let query = token.trim_start_matches('@').to_lowercase();
+        let users = [
+            ("alice", "Alice Smith"),
+            ("bob", "Bob Brown"),
+            ("carol", "Carol White"),
+            ("dave", "Dave Green"),
+        ];
+
+        Task::ready(
+            users
+                .iter()
+                .filter(|(name, _)| name.starts_with(&query))
+                .map(|(name, full_name)| {
+                    Completion::new(*name)
+                        .insert_text(format!("@{} ", name))
+                        .detail(*full_name)
+                })
+                .collect(),
+        )
+    }
+}
+
 const CONTEXT: &str = "InputStory";
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([
-        KeyBinding::new("shift-tab", TabPrev, Some(CONTEXT)),
-        KeyBinding::new("tab", Tab, Some(CONTEXT)),
-    ])
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("shift-tab", TabPrev, Some(CONTEXT)),
+            KeymapEntry::new("tab", Tab, Some(CONTEXT)),
+        ],
+    )
 }
 
 pub struct InputStory {
@@ -35,7 +88,11 @@ pub struct InputStory {
     number_input1: View<NumberInput>,
     number_input2: View<NumberInput>,
     number_input2_value: u64,
+    number_input3: View<NumberInput>,
     mash_input: View<TextInput>,
+    validate_input: View<TextInput>,
+    phone_input: View<TextInput>,
+    currency_input: View<TextInput>,
     disabled_input: View<TextInput>,
     prefix_input1: View<TextInput>,
     suffix_input1: View<TextInput>,
@@ -47,7 +104,9 @@ pub struct InputStory {
     otp_value: Option<SharedString>,
     otp_input_small: View<OtpInput>,
     otp_input_large: View<OtpInput>,
+    tag_input: View<TagInput>,
     opt_input_sized: View<OtpInput>,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl super::Story for InputStory {
@@ -80,13 +139,19 @@ impl InputStory {
         });
         cx.subscribe(&input1, Self::on_input_event).detach();
 
-        let input2 = cx.new_view(|cx| TextInput::new(cx).placeholder("Enter text here..."));
+        let input2 = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .placeholder("Type @ to mention someone...")
+                .completion_provider(MentionProvider)
+        });
         cx.subscribe(&input2, Self::on_input_event).detach();
 
         let textarea = cx.new_view(|cx| {
             let mut input = TextInput::new(cx)
                 .multi_line()
                 .rows(10)
+                .line_number(true)
+                .highlight_current_line(true)
                 .placeholder("Enter text here...");
             input.set_text(
                 unindent::unindent(
@@ -111,9 +176,16 @@ impl InputStory {
                 ),
                 cx,
             );
+            decorate_textarea(&mut input, cx);
             input
         });
         cx.subscribe(&textarea, Self::on_input_event).detach();
+        cx.subscribe(&textarea, |_, textarea, event: &InputEvent, cx| {
+            if let InputEvent::Change(_) = event {
+                textarea.update(cx, decorate_textarea);
+            }
+        })
+        .detach();
 
         let number_input1_value = 1;
         let number_input1 = cx.new_view(|cx| {
@@ -134,6 +206,13 @@ impl InputStory {
         cx.subscribe(&number_input2, Self::on_number_input2_event)
             .detach();
 
+        let number_input3 = cx.new_view(|cx| {
+            NumberInput::new(cx)
+                .placeholder("Number Input with 万/亿", cx)
+                .cjk_units(true, cx)
+                .small(cx)
+        });
+
         let mask_input = cx.new_view(|cx| {
             let mut input = TextInput::new(cx).cleanable();
             input.set_masked(true, cx);
@@ -141,6 +220,47 @@ impl InputStory {
             input
         });
 
+        let validate_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .placeholder("Username, `admin` has been taken.")
+                .validate_async(|text, cx| {
+                    cx.background_executor().spawn(async move {
+                        // Simulate to check the username from the server.
+                        Timer::after(Duration::from_millis(500)).await;
+                        if text.as_ref() == "admin" {
+                            Err(format!("The username `{}` has been taken.", text).into())
+                        } else {
+                            Ok(())
+                        }
+                    })
+                })
+        });
+
+        let phone_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .mask_pattern(MaskPattern::new("###-####-####"))
+                .placeholder("Phone number: ###-####-####")
+        });
+        // The phone input is only enabled when the username is valid.
+        let _subscriptions = Binding::new(
+            |this: &Self, cx| {
+                this.validate_input.read(cx).validation_state() != &ValidationState::Valid
+            },
+            |this, disabled, cx| {
+                this.phone_input
+                    .update(cx, |input, cx| input.set_disabled(*disabled, cx))
+            },
+        )
+        .observe(&validate_input, cx)
+        .build(cx);
+        let currency_input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .mask_pattern(MaskPattern::number(Some(2)))
+                .prefix(|_| div().child("$").ml_3())
+                .suffix(|_| div().child("USD").mr_3())
+                .placeholder("Amount")
+        });
+
         let prefix_input1 = cx.new_view(|cx| {
             TextInput::new(cx)
                 .prefix(|_| div().child(IconName::Search).ml_3())
@@ -163,12 +283,12 @@ impl InputStory {
         });
 
         let otp_input = cx.new_view(|cx| OtpInput::new(6, cx).masked(true));
-        cx.subscribe(&otp_input, |this, _, ev: &InputEvent, cx| match ev {
-            InputEvent::Change(text) => {
-                this.otp_value = Some(text.clone());
-                cx.notify();
-            }
-            _ => {}
+        cx.subscribe(&otp_input, |this, _, ev: &InputOptEvent, cx| {
+            this.otp_value = match ev {
+                InputOptEvent::Change(_) => None,
+                InputOptEvent::Complete(text) => Some(text.clone()),
+            };
+            cx.notify();
         })
         .detach();
 
@@ -180,7 +300,11 @@ impl InputStory {
             number_input1_value,
             number_input2,
             number_input2_value: 0,
+            number_input3,
             mash_input: mask_input,
+            validate_input,
+            phone_input,
+            currency_input,
             disabled_input: cx.new_view(|cx| {
                 let mut input = TextInput::new(cx);
                 input.set_text("This is disabled input", cx);
@@ -207,6 +331,12 @@ impl InputStory {
                     .small()
                     .groups(1)
             }),
+            tag_input: cx.new_view(|cx| {
+                let mut input = TagInput::new(cx);
+                input.set_tags(["Rust", "GPUI"], cx);
+                input.set_placeholder("Type and press Enter or comma to add tag", cx);
+                input
+            }),
             otp_input_large: cx.new_view(|cx| {
                 OtpInput::new(6, cx)
                     .groups(3)
@@ -221,6 +351,7 @@ impl InputStory {
                     .default_value("654321")
                     .with_size(px(55.))
             }),
+            _subscriptions,
         }
     }
 
@@ -243,6 +374,8 @@ impl InputStory {
             InputEvent::PressEnter => println!("PressEnter"),
             InputEvent::Focus => println!("Focus"),
             InputEvent::Blur => println!("Blur"),
+            InputEvent::CompositionChange(text) => println!("CompositionChange: {:?}", text),
+            InputEvent::HistoryChange(state) => println!("HistoryChange: {:?}", state),
         };
     }
 
@@ -258,6 +391,8 @@ impl InputStory {
                 InputEvent::PressEnter => println!("PressEnter"),
                 InputEvent::Focus => println!("Focus"),
                 InputEvent::Blur => println!("Blur"),
+                InputEvent::CompositionChange(text) => println!("CompositionChange: {:?}", text),
+                InputEvent::HistoryChange(state) => println!("HistoryChange: {:?}", state),
             },
             NumberInputEvent::Step(step_action) => match step_action {
                 ui::number_input::StepAction::Decrement => {
@@ -288,6 +423,8 @@ impl InputStory {
                 InputEvent::PressEnter => println!("PressEnter"),
                 InputEvent::Focus => println!("Focus"),
                 InputEvent::Blur => println!("Blur"),
+                InputEvent::CompositionChange(text) => println!("CompositionChange: {:?}", text),
+                InputEvent::HistoryChange(state) => println!("HistoryChange: {:?}", state),
             },
             NumberInputEvent::Step(step_action) => match step_action {
                 ui::number_input::StepAction::Decrement => {
@@ -357,6 +494,46 @@ impl Render for InputStory {
             .p_4()
             .justify_start()
             .gap_3()
+            .child(
+                // The buttons are disabled when the focused element can't handle the actions.
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new("undo")
+                            .ghost()
+                            .small()
+                            .label("Undo")
+                            .action(Box::new(input::Undo)),
+                    )
+                    .child(
+                        Button::new("redo")
+                            .ghost()
+                            .small()
+                            .label("Redo")
+                            .action(Box::new(input::Redo)),
+                    )
+                    .child(
+                        Button::new("cut")
+                            .ghost()
+                            .small()
+                            .label("Cut")
+                            .action(Box::new(input::Cut)),
+                    )
+                    .child(
+                        Button::new("copy")
+                            .ghost()
+                            .small()
+                            .label("Copy")
+                            .action(Box::new(input::Copy)),
+                    )
+                    .child(
+                        Button::new("paste")
+                            .ghost()
+                            .small()
+                            .label("Paste")
+                            .action(Box::new(input::Paste)),
+                    ),
+            )
             .child(
                 h_flex()
                     .gap_3()
@@ -371,14 +548,18 @@ impl Render for InputStory {
                                     .w_full()
                                     .child("Number Input")
                                     .child(self.number_input1.clone())
-                                    .child(self.number_input2.clone()),
+                                    .child(self.number_input2.clone())
+                                    .child(self.number_input3.clone()),
                             ),
                     )
                     .child(section("Textarea", cx).child(self.textarea.clone()))
                     .child(
                         section("Input State", cx)
                             .child(self.disabled_input.clone())
-                            .child(self.mash_input.clone()),
+                            .child(self.mash_input.clone())
+                            .child(self.validate_input.clone())
+                            .child(self.phone_input.clone())
+                            .child(self.currency_input.clone()),
                     ),
             )
             .child(
@@ -395,7 +576,8 @@ impl Render for InputStory {
                         section("Input Size", cx)
                             .child(self.large_input.clone())
                             .child(self.small_input.clone()),
-                    ),
+                    )
+                    .child(section("Tag Input", cx).child(self.tag_input.clone())),
             )
             .child(
                 section(
@@ -309,6 +309,16 @@ impl Panel for StoryContainer {
         self.name.clone().into_any_element()
     }
 
+    fn drag_preview(&self, _cx: &WindowContext) -> Option<AnyElement> {
+        Some(
+            v_flex()
+                .gap_1()
+                .child(Label::new(self.name.clone()).text_sm())
+                .child(self.description.clone())
+                .into_any_element(),
+        )
+    }
+
     fn title_style(&self, cx: &AppContext) -> Option<TitleStyle> {
         if let Some(bg) = self.title_bg {
             Some(TitleStyle {
@@ -361,6 +371,19 @@ impl Panel for StoryContainer {
         ]
     }
 
+    fn accept_file_drop(&self, _cx: &AppContext) -> bool {
+        true
+    }
+
+    fn on_file_drop(&mut self, paths: &[std::path::PathBuf], cx: &mut ViewContext<Self>) {
+        let names = paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        cx.push_notification(format!("Dropped on {}: {}", self.name, names));
+    }
+
     fn dump(&self, _cx: &AppContext) -> PanelState {
         let mut state = PanelState::new(self);
         let story_state = StoryState {
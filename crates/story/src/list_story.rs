@@ -3,14 +3,15 @@ use core::time;
 use fake::Fake;
 use gpui::{
     actions, div, px, relative, AnyElement, AppContext, ElementId, FocusHandle, FocusableView,
-    InteractiveElement, IntoElement, ParentElement, Render, RenderOnce, Styled, Task, Timer, View,
-    ViewContext, VisualContext, WindowContext,
+    InteractiveElement, IntoElement, ParentElement, Render, RenderOnce, SharedString, Styled, Task,
+    Timer, View, ViewContext, VisualContext, WindowContext,
 };
 
 use ui::{
     h_flex,
     label::Label,
     list::{List, ListDelegate, ListItem},
+    scroll::Overscroll,
     theme::{hsl, ActiveTheme},
     v_flex,
 };
@@ -133,6 +134,8 @@ impl RenderOnce for CompanyListItem {
 struct CompanyListDelegate {
     companies: Vec<Company>,
     matched_companies: Vec<Company>,
+    /// The sections of the matched companies grouped by the first letter, with the items count.
+    sections: Vec<(SharedString, usize)>,
     selected_index: usize,
     confirmed_index: Option<usize>,
 }
@@ -155,10 +158,40 @@ impl ListDelegate for CompanyListDelegate {
             .filter(|company| company.name.to_lowercase().contains(&query.to_lowercase()))
             .cloned()
             .collect();
+        self.update_sections();
 
         Task::ready(())
     }
 
+    fn sections_count(&self, _: &AppContext) -> usize {
+        self.sections.len()
+    }
+
+    fn section_items_count(&self, section: usize, _: &AppContext) -> usize {
+        self.sections.get(section).map_or(0, |(_, count)| *count)
+    }
+
+    fn render_section_header(
+        &self,
+        section: usize,
+        cx: &mut ViewContext<List<Self>>,
+    ) -> Option<impl IntoElement> {
+        let (title, _) = self.sections.get(section)?;
+
+        Some(
+            h_flex()
+                .h(px(52.))
+                .px_3()
+                .items_end()
+                .pb_1()
+                .text_sm()
+                .text_color(cx.theme().muted_foreground)
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .child(title.clone()),
+        )
+    }
+
     fn confirm(&mut self, ix: Option<usize>, cx: &mut ViewContext<List<Self>>) {
         self.confirmed_index = ix;
         if let Some(_) = ix {
@@ -206,6 +239,41 @@ impl ListDelegate for CompanyListDelegate {
         }
     }
 
+    fn can_move_item(&self, _: usize, _: &AppContext) -> bool {
+        true
+    }
+
+    fn item_label(&self, ix: usize, _: &AppContext) -> Option<SharedString> {
+        self.matched_companies
+            .get(ix)
+            .map(|company| company.name.clone().into())
+    }
+
+    fn move_item(&mut self, from: usize, to: usize, _: &mut ViewContext<List<Self>>) {
+        let company = self.matched_companies.remove(from);
+        self.matched_companies.insert(to, company);
+        self.update_sections();
+    }
+
+    fn can_load_more(&self, _: &AppContext) -> bool {
+        self.companies.len() < 2_000
+    }
+
+    fn load_more(&mut self, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        cx.spawn(|view, mut cx| async move {
+            // Simulate loading the next page from the server.
+            Timer::after(time::Duration::from_secs(1)).await;
+            let _ = view.update(&mut cx, |view, cx| {
+                let delegate = view.delegate_mut();
+                let companies = (0..200).map(|_| random_company()).collect::<Vec<_>>();
+                delegate.companies.extend(companies.iter().cloned());
+                delegate.matched_companies.extend(companies);
+                delegate.update_sections();
+                cx.notify();
+            });
+        })
+    }
+
     fn render_item(&self, ix: usize, _cx: &mut ViewContext<List<Self>>) -> Option<Self::Item> {
         let selected = ix == self.selected_index || Some(ix) == self.confirmed_index;
         if let Some(company) = self.matched_companies.get(ix) {
@@ -217,6 +285,25 @@ impl ListDelegate for CompanyListDelegate {
 }
 
 impl CompanyListDelegate {
+    /// Group the matched companies (sorted by name) by the first letter.
+    fn update_sections(&mut self) {
+        self.sections.clear();
+        for company in self.matched_companies.iter() {
+            let letter: SharedString = company
+                .name
+                .chars()
+                .next()
+                .map(|c| c.to_ascii_uppercase().to_string())
+                .unwrap_or_default()
+                .into();
+
+            match self.sections.last_mut() {
+                Some((title, count)) if *title == letter => *count += 1,
+                _ => self.sections.push((letter, 1)),
+            }
+        }
+    }
+
     fn selected_company(&self) -> Option<Company> {
         self.companies.get(self.selected_index).cloned()
     }
@@ -248,20 +335,24 @@ impl ListStory {
     }
 
     fn new(cx: &mut ViewContext<Self>) -> Self {
-        let companies = (0..1_000)
+        let mut companies = (0..1_000)
             .map(|_| random_company())
             .collect::<Vec<Company>>();
+        companies.sort_by(|a, b| a.name.cmp(&b.name));
 
         let company_list = cx.new_view(|cx| {
-            List::new(
-                CompanyListDelegate {
-                    matched_companies: companies.clone(),
-                    companies,
-                    selected_index: 0,
-                    confirmed_index: None,
-                },
-                cx,
-            )
+            let mut delegate = CompanyListDelegate {
+                matched_companies: companies.clone(),
+                companies,
+                sections: vec![],
+                selected_index: 0,
+                confirmed_index: None,
+            };
+            delegate.update_sections();
+
+            List::new(delegate, cx)
+                .overscroll(Overscroll::platform())
+                .edge_fades(true)
         });
 
         // Spawn a background to random refresh the list
@@ -10,13 +10,21 @@ use story::{
     TooltipStory,
 };
 use ui::{
+    breakpoints::Breakpoints,
     button::{Button, ButtonVariants as _},
     color_picker::{ColorPicker, ColorPickerEvent},
-    dock::{DockArea, DockAreaState, DockEvent, DockItem, DockPlacement},
+    dock::{
+        write_atomic, Autosave, AutosaveHandle, DockArea, DockAreaState, DockEvent, DockItem,
+        DockPlacement, LayoutMetrics, LayoutSuggestion,
+    },
     h_flex,
+    keymap::{self, Keymap, KeymapEntry},
+    menu_bar::{MenuContext, MenuDef},
     popup_menu::PopupMenuExt,
     scroll::ScrollbarShow,
-    theme::{ActiveTheme, Theme},
+    theme::{ActiveTheme, ChartPalette, Theme, ThemeVariant},
+    unsaved::{UnsavedItem, UnsavedProviderHandle},
+    window_state::WindowState,
     ContextModal, IconName, Root, Sizable, TitleBar,
 };
 
@@ -25,12 +33,21 @@ const MAIN_DOCK_AREA: DockAreaTab = DockAreaTab {
     version: 5,
 };
 
+const MAIN_WINDOW_ID: &str = "main-window";
+const LAYOUT_FILE: &str = "target/layout.json";
+
 #[derive(Clone, PartialEq, Eq, Deserialize)]
 struct SelectScrollbarShow(ScrollbarShow);
 
 #[derive(Clone, PartialEq, Eq, Deserialize)]
 struct SelectLocale(SharedString);
 
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct SelectChartPalette(ChartPalette);
+
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+struct SelectThemeVariant(ThemeVariant);
+
 #[derive(Clone, PartialEq, Eq, Deserialize)]
 struct SelectFont(usize);
 
@@ -47,20 +64,70 @@ impl_actions!(
         SelectFont,
         AddPanel,
         SelectScrollbarShow,
+        SelectChartPalette,
+        SelectThemeVariant,
         TogglePanelVisible
     ]
 );
 
-actions!(main_menu, [Quit]);
-actions!(workspace, [Open, CloseWindow]);
+actions!(
+    main_menu,
+    [
+        Quit,
+        ToggleTranslucent,
+        ToggleLite,
+        ToggleChartPatterns,
+        ReloadKeymap
+    ]
+);
+actions!(workspace, [Open, CloseWindow, SuggestLayout]);
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([KeyBinding::new("cmd-q", Quit, None)]);
+    keymap::bind_keys(cx, [KeymapEntry::new("cmd-q", Quit, None)]);
 
     cx.on_action(|_action: &Open, _cx: &mut AppContext| {});
+    cx.on_action(|_: &ReloadKeymap, cx: &mut AppContext| load_user_keymap(cx));
 
     ui::init(cx);
+    ui::about_info!()
+        .icon("icons/layout-dashboard.svg")
+        .link("GitHub", "https://github.com/longbridgeapp/gpui-component")
+        .copyright("Copyright © Longbridge")
+        .set_global(cx);
     story::init(cx);
+    load_user_keymap(cx);
+    load_layout_metrics(cx);
+}
+
+/// Load the user keymap to override the default key bindings.
+fn load_user_keymap(cx: &mut AppContext) {
+    let fname = "target/keymap.json";
+    let Ok(content) = std::fs::read_to_string(fname) else {
+        Keymap::clear_user_keymap(cx);
+        return;
+    };
+
+    match Keymap::load_user_keymap(&content, cx) {
+        Ok(_) => println!("load keymap success"),
+        Err(err) => eprintln!("load keymap error: {:?}", err),
+    }
+}
+
+/// Enable the layout metrics, and load the metrics recorded in the previous sessions.
+fn load_layout_metrics(cx: &mut AppContext) {
+    LayoutMetrics::set_enabled(true, cx);
+
+    let Ok(json) = std::fs::read_to_string("target/layout_metrics.json") else {
+        return;
+    };
+    if let Err(err) = LayoutMetrics::load(&json, cx) {
+        eprintln!("load layout metrics error: {:?}", err);
+    }
+}
+
+fn save_layout_metrics(json: &str) -> Result<()> {
+    write_atomic("target/layout_metrics.json", json)?;
+    Ok(())
 }
 
 pub struct StoryWorkspace {
@@ -69,8 +136,10 @@ pub struct StoryWorkspace {
     locale_selector: View<LocaleSelector>,
     font_size_selector: View<FontSizeSelector>,
     theme_color_picker: View<ColorPicker>,
-    last_layout_state: Option<DockAreaState>,
-    _save_layout_task: Option<Task<()>>,
+    _autosave: AutosaveHandle,
+    _unsaved_provider: Option<UnsavedProviderHandle>,
+    _save_window_state_task: Option<Task<()>>,
+    _save_metrics_task: Option<Task<()>>,
 }
 
 struct DockAreaTab {
@@ -102,8 +171,18 @@ impl StoryWorkspace {
             }
         };
 
-        cx.subscribe(&dock_area, |this, dock_area, ev: &DockEvent, cx| match ev {
-            DockEvent::LayoutChanged => this.save_layout(dock_area, cx),
+        let _autosave = Autosave::new(LAYOUT_FILE)
+            .debounce(Duration::from_secs(10))
+            .watch(&dock_area, cx);
+
+        cx.subscribe(&dock_area, |this, _, ev: &DockEvent, cx| match ev {
+            DockEvent::LayoutChanged => this.save_metrics(cx),
+            DockEvent::InvalidPanel { panel_name, .. } => {
+                eprintln!(
+                    "panel `{}` is not registered, show a placeholder",
+                    panel_name
+                );
+            }
         })
         .detach();
 
@@ -111,14 +190,47 @@ impl StoryWorkspace {
             let dock_area = dock_area.clone();
             move |cx| {
                 let state = dock_area.read(cx).dump(cx);
+                let metrics = LayoutMetrics::export(cx);
                 cx.background_executor().spawn(async move {
                     // Save layout before quitting
                     Self::save_state(&state).unwrap();
+                    if let Ok(metrics) = metrics {
+                        _ = save_layout_metrics(&metrics);
+                    }
                 })
             }
         })
         .detach();
 
+        cx.observe_window_bounds(|this, cx| this.save_window_state(cx))
+            .detach();
+
+        // Close the docks one by one in the small window.
+        Breakpoints::new()
+            .dock(dock_area.downgrade(), DockPlacement::Right, px(1100.))
+            .dock(dock_area.downgrade(), DockPlacement::Left, px(800.))
+            .dock(dock_area.downgrade(), DockPlacement::Bottom, px(560.))
+            .observe(cx)
+            .detach();
+
+        // Ask to save the dirty panels before closing the window,
+        // defer to make sure the window root view is ready.
+        cx.defer({
+            let dock_area = dock_area.downgrade();
+            move |this, cx| {
+                let provider = Root::register_unsaved_provider(cx, move |cx| {
+                    dock_area
+                        .upgrade()
+                        .map(|dock_area| dock_area.read(cx).dirty_panels(cx))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(UnsavedItem::panel)
+                        .collect()
+                });
+                this._unsaved_provider = Some(provider);
+            }
+        });
+
         let locale_selector = cx.new_view(LocaleSelector::new);
         let font_size_selector = cx.new_view(FontSizeSelector::new);
 
@@ -146,8 +258,10 @@ impl StoryWorkspace {
             locale_selector,
             font_size_selector,
             theme_color_picker,
-            last_layout_state: None,
-            _save_layout_task: None,
+            _autosave,
+            _unsaved_provider: None,
+            _save_window_state_task: None,
+            _save_metrics_task: None,
         }
     }
 
@@ -166,42 +280,61 @@ impl StoryWorkspace {
             false => ui::theme::ThemeMode::Dark,
         };
 
+        let from = *cx.theme().colors();
         Theme::change(mode, cx);
         self.set_theme_color(self.theme_color, cx);
+        Theme::animate_from(from, cx);
     }
 
-    fn save_layout(&mut self, dock_area: View<DockArea>, cx: &mut ViewContext<Self>) {
-        self._save_layout_task = Some(cx.spawn(|this, mut cx| async move {
-            Timer::after(Duration::from_secs(10)).await;
+    fn save_state(state: &DockAreaState) -> Result<()> {
+        println!("Save layout...");
+        let json = serde_json::to_string_pretty(state)?;
+        write_atomic(LAYOUT_FILE, json)?;
+        Ok(())
+    }
 
-            let _ = cx.update(|cx| {
-                let dock_area = dock_area.read(cx);
-                let state = dock_area.dump(cx);
+    fn save_window_state(&mut self, cx: &mut ViewContext<Self>) {
+        // Debounce, the bounds will be changed frequently when resizing or moving the window.
+        self._save_window_state_task = Some(cx.spawn(|_, mut cx| async move {
+            Timer::after(Duration::from_millis(500)).await;
 
-                let last_layout_state = this.upgrade().unwrap().read(cx).last_layout_state.clone();
-                if Some(&state) == last_layout_state.as_ref() {
-                    return;
+            _ = cx.update(|cx| {
+                let state = WindowState::new(MAIN_WINDOW_ID, cx);
+                if let Err(err) = Self::save_window_state_to_file(&state) {
+                    eprintln!("save window state error: {:?}", err);
                 }
+            });
+        }));
+    }
 
-                Self::save_state(&state).unwrap();
-                let _ = this.update(cx, |this, _| {
-                    this.last_layout_state = Some(state);
-                });
+    fn save_metrics(&mut self, cx: &mut ViewContext<Self>) {
+        // Debounce with the layout autosave, so the metrics are not lost if the app crashes.
+        self._save_metrics_task = Some(cx.spawn(|_, mut cx| async move {
+            Timer::after(Duration::from_secs(10)).await;
+
+            _ = cx.update(|cx| {
+                if let Ok(metrics) = LayoutMetrics::export(cx) {
+                    if let Err(err) = save_layout_metrics(&metrics) {
+                        eprintln!("save layout metrics error: {:?}", err);
+                    }
+                }
             });
         }));
     }
 
-    fn save_state(state: &DockAreaState) -> Result<()> {
-        println!("Save layout...");
+    fn save_window_state_to_file(state: &WindowState) -> Result<()> {
         let json = serde_json::to_string_pretty(state)?;
-        std::fs::write("target/layout.json", json)?;
+        write_atomic(format!("target/{}.json", state.id), json)?;
         Ok(())
     }
 
+    fn load_window_state(id: &str) -> Result<WindowState> {
+        let json = std::fs::read_to_string(format!("target/{}.json", id))?;
+        Ok(serde_json::from_str::<WindowState>(&json)?)
+    }
+
     fn load_layout(dock_area: View<DockArea>, cx: &mut WindowContext) -> Result<()> {
-        let fname = "target/layout.json";
-        let json = std::fs::read_to_string(fname)?;
-        let state = serde_json::from_str::<DockAreaState>(&json)?;
+        let state = Autosave::load(LAYOUT_FILE)?;
 
         // Check if the saved layout version is different from the current version
         // Notify the user and ask if they want to reset the layout to default.
@@ -338,11 +471,18 @@ impl StoryWorkspace {
             px(1200.0).min(display_size.height * 0.85),
         );
 
-        let window_bounds = Bounds::centered(None, window_size, cx);
+        let (window_bounds, display_id) = match Self::load_window_state(MAIN_WINDOW_ID) {
+            Ok(state) => (state.window_bounds(cx), state.display_id(cx)),
+            Err(_) => (
+                WindowBounds::Windowed(Bounds::centered(None, window_size, cx)),
+                None,
+            ),
+        };
 
         cx.spawn(|mut cx| async move {
             let options = WindowOptions {
-                window_bounds: Some(WindowBounds::Windowed(window_bounds)),
+                window_bounds: Some(window_bounds),
+                display_id,
                 titlebar: Some(TitlebarOptions {
                     title: None,
                     appears_transparent: true,
@@ -406,6 +546,51 @@ impl StoryWorkspace {
         });
     }
 
+    fn on_action_suggest_layout(&mut self, _: &SuggestLayout, cx: &mut ViewContext<Self>) {
+        let state = self.dock_area.read(cx).dump(cx);
+        let suggestions = LayoutMetrics::global(cx).suggestions(&state);
+        if suggestions.is_empty() {
+            cx.push_notification("There is no layout suggestion yet.");
+            return;
+        }
+
+        let message = suggestions
+            .iter()
+            .map(|suggestion| match suggestion {
+                LayoutSuggestion::ResizeDock { placement, size } => {
+                    format!("You always resize the {:?} dock to {}.", placement, size)
+                }
+                LayoutSuggestion::CloseDock { placement } => {
+                    format!("You always close the {:?} dock.", placement)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let answer = cx.prompt(
+            PromptLevel::Info,
+            "Apply the suggested layout?",
+            Some(&message),
+            &["Apply", "Cancel"],
+        );
+        let dock_area = self.dock_area.downgrade();
+        cx.spawn(|_, mut cx| async move {
+            if answer.await != Ok(0) {
+                return;
+            }
+
+            _ = cx.update(|cx| {
+                _ = dock_area.update(cx, |dock_area, cx| {
+                    let state = LayoutSuggestion::apply_all(&suggestions, state);
+                    if let Err(err) = dock_area.load(state, cx) {
+                        eprintln!("apply layout error: {:?}", err);
+                    }
+                });
+            });
+        })
+        .detach();
+    }
+
     fn on_action_toggle_panel_visible(
         &mut self,
         action: &TogglePanelVisible,
@@ -451,6 +636,7 @@ impl Render for StoryWorkspace {
             .id("story-workspace")
             .on_action(cx.listener(Self::on_action_add_panel))
             .on_action(cx.listener(Self::on_action_toggle_panel_visible))
+            .on_action(cx.listener(Self::on_action_suggest_layout))
             .relative()
             .size_full()
             .flex()
@@ -491,6 +677,8 @@ impl Render for StoryWorkspace {
                                             Box::new(AddPanel(DockPlacement::Bottom)),
                                         )
                                         .separator()
+                                        .menu("Suggest Layout", Box::new(SuggestLayout))
+                                        .separator()
                                         .menu_with_check(
                                             "Sidebar",
                                             !invisible_panels
@@ -659,6 +847,37 @@ impl FontSizeSelector {
         Theme::global_mut(cx).scrollbar_show = show.0;
         cx.refresh();
     }
+
+    fn on_toggle_translucent(&mut self, _: &ToggleTranslucent, cx: &mut ViewContext<Self>) {
+        let translucent = !cx.theme().translucent;
+        Theme::set_translucent(translucent, cx);
+    }
+
+    fn on_toggle_lite(&mut self, _: &ToggleLite, cx: &mut ViewContext<Self>) {
+        let lite = !cx.theme().lite;
+        Theme::set_lite(lite, cx);
+    }
+
+    fn on_select_chart_palette(
+        &mut self,
+        palette: &SelectChartPalette,
+        cx: &mut ViewContext<Self>,
+    ) {
+        Theme::set_chart_palette(palette.0, cx);
+    }
+
+    fn on_select_theme_variant(
+        &mut self,
+        variant: &SelectThemeVariant,
+        cx: &mut ViewContext<Self>,
+    ) {
+        Theme::set_variant(variant.0, cx);
+    }
+
+    fn on_toggle_chart_patterns(&mut self, _: &ToggleChartPatterns, cx: &mut ViewContext<Self>) {
+        let patterns = !cx.theme().chart_patterns;
+        Theme::set_chart_patterns(patterns, cx);
+    }
 }
 
 impl Render for FontSizeSelector {
@@ -666,12 +885,22 @@ impl Render for FontSizeSelector {
         let focus_handle = self.focus_handle.clone();
         let font_size = cx.theme().font_size as i32;
         let scroll_show = cx.theme().scrollbar_show;
+        let translucent = cx.theme().translucent;
+        let lite = cx.theme().lite;
+        let chart_palette = cx.theme().chart_palette;
+        let chart_patterns = cx.theme().chart_patterns;
+        let variant = cx.theme().variant;
 
         div()
             .id("font-size-selector")
             .track_focus(&focus_handle)
             .on_action(cx.listener(Self::on_select_font))
             .on_action(cx.listener(Self::on_select_scrollbar_show))
+            .on_action(cx.listener(Self::on_toggle_translucent))
+            .on_action(cx.listener(Self::on_toggle_lite))
+            .on_action(cx.listener(Self::on_select_chart_palette))
+            .on_action(cx.listener(Self::on_toggle_chart_patterns))
+            .on_action(cx.listener(Self::on_select_theme_variant))
             .child(
                 Button::new("btn")
                     .small()
@@ -686,6 +915,8 @@ impl Render for FontSizeSelector {
                         .menu_with_check("Font Default", font_size == 16, Box::new(SelectFont(16)))
                         .menu_with_check("Font Small", font_size == 14, Box::new(SelectFont(14)))
                         .separator()
+                        .menu("Reload Keymap", Box::new(ReloadKeymap))
+                        .separator()
                         .menu_with_check(
                             "Scrolling to show Scrollbar",
                             scroll_show == ScrollbarShow::Scrolling,
@@ -696,42 +927,98 @@ impl Render for FontSizeSelector {
                             scroll_show == ScrollbarShow::Hover,
                             Box::new(SelectScrollbarShow(ScrollbarShow::Hover)),
                         )
+                        .menu_with_check(
+                            "Always show Scrollbar",
+                            scroll_show == ScrollbarShow::Always,
+                            Box::new(SelectScrollbarShow(ScrollbarShow::Always)),
+                        )
+                        .separator()
+                        .menu_with_check("Lite Mode", lite, Box::new(ToggleLite))
+                        .separator()
+                        .menu_with_check(
+                            "Default Colors",
+                            variant == ThemeVariant::Default,
+                            Box::new(SelectThemeVariant(ThemeVariant::Default)),
+                        )
+                        .menu_with_check(
+                            "High Contrast Colors",
+                            variant == ThemeVariant::HighContrast,
+                            Box::new(SelectThemeVariant(ThemeVariant::HighContrast)),
+                        )
+                        .menu_with_check(
+                            "Color-blind Safe Colors",
+                            variant == ThemeVariant::ColorBlind,
+                            Box::new(SelectThemeVariant(ThemeVariant::ColorBlind)),
+                        )
+                        .separator()
+                        .menu_with_check(
+                            "Default Chart Colors",
+                            chart_palette == ChartPalette::Default,
+                            Box::new(SelectChartPalette(ChartPalette::Default)),
+                        )
+                        .menu_with_check(
+                            "Okabe-Ito Chart Colors",
+                            chart_palette == ChartPalette::OkabeIto,
+                            Box::new(SelectChartPalette(ChartPalette::OkabeIto)),
+                        )
+                        .menu_with_check(
+                            "Tol Bright Chart Colors",
+                            chart_palette == ChartPalette::TolBright,
+                            Box::new(SelectChartPalette(ChartPalette::TolBright)),
+                        )
+                        .menu_with_check(
+                            "Chart Patterns",
+                            chart_patterns,
+                            Box::new(ToggleChartPatterns),
+                        )
+                        .when(Theme::is_translucent_supported(), |this| {
+                            this.separator().menu_with_check(
+                                "Translucent Window",
+                                translucent,
+                                Box::new(ToggleTranslucent),
+                            )
+                        })
                     })
                     .anchor(Corner::TopRight),
             )
     }
 }
 
-fn main() {
-    use ui::input::{Copy, Cut, Paste, Redo, Undo};
+/// The native application menus, the same definitions can be used by the `MenuBar` in the window.
+const APP_MENUS: &str = r#"[
+    {
+        "name": "GPUI App",
+        "items": [
+            { "type": "action", "label": "About GPUI App", "action": "about::ShowAbout" },
+            { "type": "separator" },
+            { "type": "action", "label": "Quit", "action": "main_menu::Quit" }
+        ]
+    },
+    {
+        "name": "Edit",
+        "items": [
+            { "type": "action", "label": "Undo", "action": "input::Undo", "os_action": "undo" },
+            { "type": "action", "label": "Redo", "action": "input::Redo", "os_action": "redo" },
+            { "type": "separator" },
+            { "type": "action", "label": "Cut", "action": "input::Cut", "os_action": "cut" },
+            { "type": "action", "label": "Copy", "action": "input::Copy", "os_action": "copy" },
+            { "type": "action", "label": "Paste", "action": "input::Paste", "os_action": "paste" }
+        ]
+    },
+    { "name": "Window", "items": [] }
+]"#;
 
+fn main() {
     let app = App::new().with_assets(Assets);
 
     app.run(move |cx| {
         init(cx);
 
         cx.on_action(quit);
-        cx.set_menus(vec![
-            Menu {
-                name: "GPUI App".into(),
-                items: vec![MenuItem::action("Quit", Quit)],
-            },
-            Menu {
-                name: "Edit".into(),
-                items: vec![
-                    MenuItem::os_action("Undo", Undo, gpui::OsAction::Undo),
-                    MenuItem::os_action("Redo", Redo, gpui::OsAction::Redo),
-                    MenuItem::separator(),
-                    MenuItem::os_action("Cut", Cut, gpui::OsAction::Cut),
-                    MenuItem::os_action("Copy", Copy, gpui::OsAction::Copy),
-                    MenuItem::os_action("Paste", Paste, gpui::OsAction::Paste),
-                ],
-            },
-            Menu {
-                name: "Window".into(),
-                items: vec![],
-            },
-        ]);
+        let menus: Vec<MenuDef> =
+            serde_json::from_str(APP_MENUS).expect("failed to parse the app menus");
+        ui::menu_bar::set_menus(&menus, &MenuContext::new(), cx)
+            .expect("failed to set the app menus");
         cx.activate(true);
 
         open_new(cx, |_workspace, _cx| {
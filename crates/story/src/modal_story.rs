@@ -11,11 +11,12 @@ use ui::{
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
     date_picker::DatePicker,
+    draft::Drafts,
     dropdown::Dropdown,
     h_flex,
     input::TextInput,
     list::{List, ListDelegate, ListItem},
-    notification::{Notification, NotificationType},
+    notification::{DoNotDisturb, Notification, NotificationSettings, NotificationType},
     theme::ActiveTheme as _,
     v_flex,
     webview::WebView,
@@ -296,6 +297,8 @@ impl ModalStory {
             this.placement(placement)
                 .overlay(overlay)
                 .size(px(400.))
+                .min_size(px(200.))
+                .max_size(px(800.))
                 .title("Drawer Title")
                 .gap_4()
                 .child(input.clone())
@@ -321,7 +324,20 @@ impl ModalStory {
                         ))
                         .child(Button::new("cancel").label("Cancel").on_click(|_, cx| {
                             cx.close_drawer();
-                        })),
+                        }))
+                        .child(
+                            Button::new("nested")
+                                .label("Open Nested Drawer...")
+                                .on_click(move |_, cx| {
+                                    cx.open_drawer(move |this, _| {
+                                        this.placement(placement)
+                                            .overlay(overlay)
+                                            .size(px(300.))
+                                            .title("Nested Drawer")
+                                            .child("This drawer is opened above the previous one.")
+                                    })
+                                }),
+                        ),
                 )
         });
     }
@@ -403,6 +419,55 @@ impl ModalStory {
         self.input1.focus_handle(cx).focus(cx);
     }
 
+    fn show_draft_modal(&mut self, cx: &mut ViewContext<Self>) {
+        const FLOW_ID: &str = "modal-story-feedback";
+
+        // Restore the draft if the modal was dismissed before, otherwise create a new form.
+        let restored = Drafts::contains(FLOW_ID, cx);
+        let input = Drafts::get::<View<TextInput>>(FLOW_ID, cx).unwrap_or_else(|| {
+            cx.new_view(|cx| {
+                TextInput::new(cx)
+                    .multi_line()
+                    .rows(4)
+                    .placeholder("Dismiss the modal and open again, the text will be restored.")
+            })
+        });
+        let view = cx.view().clone();
+
+        cx.open_modal(move |modal, _| {
+            modal
+                .title("Feedback")
+                .on_close({
+                    let input = input.clone();
+                    move |_, cx| Drafts::save(FLOW_ID, input.clone(), cx)
+                })
+                .child(input.clone())
+                .footer(
+                    h_flex()
+                        .gap_6()
+                        .items_center()
+                        .child(Button::new("submit").primary().label("Submit").on_click({
+                            let view = view.clone();
+                            let input = input.clone();
+                            move |_, cx| {
+                                Drafts::discard(FLOW_ID, cx);
+                                cx.close_modal();
+                                let text = input.read(cx).text();
+                                view.update(cx, |view, _| view.selected_value = Some(text));
+                            }
+                        }))
+                        .when(restored, |this| {
+                            this.child(Button::new("discard").label("Discard Draft").on_click(
+                                |_, cx| {
+                                    Drafts::discard(FLOW_ID, cx);
+                                    cx.close_modal();
+                                },
+                            ))
+                        }),
+                )
+        });
+    }
+
     fn on_action_test_action(&mut self, _: &TestAction, cx: &mut ViewContext<Self>) {
         cx.push_notification("You have clicked the TestAction.");
     }
@@ -556,6 +621,11 @@ impl Render for ModalStory {
                             .label("Open Modal...")
                             .on_click(cx.listener(|this, _, cx| this.show_modal(cx))),
                     )
+                    .child(
+                        Button::new("show-draft-modal")
+                            .label("Open Modal with Draft...")
+                            .on_click(cx.listener(|this, _, cx| this.show_draft_modal(cx))),
+                    )
                     .child(
                         h_flex()
                             .gap_3()
@@ -622,6 +692,37 @@ impl Render for ModalStory {
                                         ),
                                     )
                                     })),
+                            )
+                            .child(
+                                Button::new("show-notify-source")
+                                    .label("Notification from Downloads")
+                                    .on_click(cx.listener(|_, _, cx| {
+                                        let name: String = fake::faker::lorem::en::Word().fake();
+                                        cx.push_notification(
+                                            Notification::success(format!(
+                                                "{}.zip has been downloaded.",
+                                                name
+                                            ))
+                                            .source("Downloads"),
+                                        )
+                                    })),
+                            )
+                            .child(
+                                Checkbox::new("do-not-disturb")
+                                    .label("Do Not Disturb")
+                                    .checked(NotificationSettings::do_not_disturb(cx).is_some())
+                                    .on_click(cx.listener(|_, checked: &bool, cx| {
+                                        // Do not disturb all day, the errors still be shown.
+                                        let dnd = checked.then(|| {
+                                            DoNotDisturb::new(
+                                                chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                                                chrono::NaiveTime::from_hms_opt(23, 59, 59)
+                                                    .unwrap(),
+                                            )
+                                        });
+                                        NotificationSettings::set_do_not_disturb(dnd, cx);
+                                        cx.notify();
+                                    })),
                             ),
                     ),
             )
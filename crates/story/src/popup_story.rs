@@ -1,8 +1,7 @@
 use gpui::{
     actions, div, impl_actions, px, AppContext, Corner, DismissEvent, Element, EventEmitter,
-    FocusHandle, FocusableView, InteractiveElement, IntoElement, KeyBinding, MouseButton,
-    ParentElement as _, Render, SharedString, Styled as _, View, ViewContext, VisualContext,
-    WindowContext,
+    FocusHandle, FocusableView, InteractiveElement, IntoElement, MouseButton, ParentElement as _,
+    Render, SharedString, Styled as _, View, ViewContext, VisualContext, WindowContext,
 };
 use serde::Deserialize;
 use ui::{
@@ -11,6 +10,8 @@ use ui::{
     divider::Divider,
     h_flex,
     input::TextInput,
+    keymap::{self, KeymapEntry},
+    menu_bar::{MenuBar, MenuContext, MenuDef},
     popover::{Popover, PopoverContent},
     popup_menu::PopupMenuExt,
     switch::Switch,
@@ -21,6 +22,35 @@ use ui::{
 #[derive(Clone, PartialEq, Deserialize)]
 struct Info(usize);
 
+const MENU_BAR: &str = r#"[
+    {
+        "name": "Edit",
+        "items": [
+            { "type": "action", "label": "Cut", "action": "popover_story::Cut" },
+            { "type": "action", "label": "Copy", "action": "popover_story::Copy" },
+            { "type": "action", "label": "Paste", "action": "popover_story::Paste" },
+            { "type": "separator" },
+            { "type": "action", "label": "Search All", "action": "popover_story::SearchAll" }
+        ]
+    },
+    {
+        "name": "View",
+        "items": [
+            { "type": "action", "label": "Toggle Window Mode", "action": "popover_story::ToggleWindowMode" },
+            { "type": "separator" },
+            {
+                "type": "submenu",
+                "label": "Info",
+                "items": [
+                    { "type": "action", "label": "Info 0", "action": "popover_story::Info", "args": 0 },
+                    { "type": "action", "label": "Info 1 (Window Mode)", "action": "popover_story::Info", "args": 1, "enabled_when": "window_mode" },
+                    { "type": "action", "label": "Info 2 (Not Window Mode)", "action": "popover_story::Info", "args": 2, "enabled_when": "!window_mode" }
+                ]
+            }
+        ]
+    }
+]"#;
+
 actions!(
     popover_story,
     [Copy, Paste, Cut, SearchAll, ToggleWindowMode]
@@ -28,24 +58,27 @@ actions!(
 impl_actions!(popover_story, [Info]);
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-c", Copy, None),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-c", Copy, None),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-v", Paste, None),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-v", Paste, None),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-x", Cut, None),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-x", Cut, None),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-shift-f", SearchAll, None),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-shift-f", SearchAll, None),
-    ])
+    keymap::bind_keys(
+        cx,
+        [
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-c", Copy, None),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-c", Copy, None),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-v", Paste, None),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-v", Paste, None),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-x", Cut, None),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-x", Cut, None),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-shift-f", SearchAll, None),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-shift-f", SearchAll, None),
+        ],
+    )
 }
 
 struct Form {
@@ -90,6 +123,7 @@ pub struct PopupStory {
     form: View<Form>,
     message: String,
     window_mode: bool,
+    menus: Vec<MenuDef>,
 }
 
 impl super::Story for PopupStory {
@@ -118,6 +152,7 @@ impl PopupStory {
             focus_handle: cx.focus_handle(),
             message: "".to_string(),
             window_mode: false,
+            menus: serde_json::from_str(MENU_BAR).expect("failed to parse the menu bar"),
         }
     }
 
@@ -195,6 +230,11 @@ impl Render for PopupStory {
                 }
             })
             .gap_6()
+            .child(
+                MenuBar::new("menu-bar")
+                    .menus(self.menus.clone())
+                    .context(MenuContext::new().set("window_mode", window_mode)),
+            )
             .child(
                 Switch::new("switch-window-mode")
                     .checked(window_mode)
@@ -235,6 +275,7 @@ impl Render for PopupStory {
                     .child(
                         Popover::new("info-top-right")
                             .anchor(Corner::TopRight)
+                            .arrow(true)
                             .trigger(Button::new("info-top-right").label("Top Right"))
                             .content(|cx| {
                                 cx.new_view(|cx| {
@@ -259,6 +300,12 @@ impl Render for PopupStory {
             .child(
                 h_flex()
                     .gap_3()
+                    .child(
+                        Button::new("search-all")
+                            .icon(IconName::Search)
+                            .tooltip_with_action("Search All", &SearchAll)
+                            .on_click(|_, cx| cx.dispatch_action(Box::new(SearchAll))),
+                    )
                     .child(
                         Button::new("popup-menu-1")
                             .icon(IconName::Ellipsis)
@@ -1,15 +1,21 @@
+use std::time::Duration;
+
 use gpui::{
-    div, px, IntoElement, ParentElement, Render, Styled, View, ViewContext, VisualContext,
-    WindowContext,
+    div, px, Hsla, IntoElement, ParentElement, Render, SharedString, Styled, View, ViewContext,
+    VisualContext, WindowContext,
 };
 use ui::{
     button::Button,
+    carousel::{Carousel, CarouselEvent},
+    compare_slider::CompareSlider,
     divider::Divider,
     h_flex,
     indicator::Indicator,
-    progress::Progress,
+    pattern::{pattern_fill, Pattern},
+    progress::{Progress, ProgressCircle},
     skeleton::Skeleton,
-    slider::{Slider, SliderEvent},
+    slider::{RangeSlider, Slider, SliderEvent},
+    theme::ActiveTheme as _,
     v_flex, IconName, Sizable,
 };
 
@@ -20,6 +26,30 @@ pub struct ProgressStory {
     slider1_value: f32,
     slider2: View<Slider>,
     slider2_value: f32,
+    range_slider: View<RangeSlider>,
+    range_value: (f32, f32),
+    compare_slider: View<CompareSlider>,
+    carousel: View<Carousel>,
+    carousel_ix: usize,
+}
+
+struct CarouselSlide {
+    title: SharedString,
+    color: Hsla,
+}
+
+impl Render for CarouselSlide {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(self.color)
+            .text_color(ui::white())
+            .text_xl()
+            .child(self.title.clone())
+    }
 }
 
 impl super::Story for ProgressStory {
@@ -50,6 +80,7 @@ impl ProgressStory {
                 this.slider1_value = *value;
                 cx.notify();
             }
+            _ => {}
         })
         .detach();
 
@@ -59,11 +90,88 @@ impl ProgressStory {
                 this.slider2_value = *value;
                 cx.notify();
             }
+            _ => {}
+        })
+        .detach();
+
+        let range_slider = cx.new_view(|cx| {
+            RangeSlider::new(cx)
+                .min(0.)
+                .max(100.)
+                .step(5.)
+                .min_gap(10.)
+                .default_range(20., 80.)
+                .marks([(0., "0"), (25., ""), (50., "50"), (75., ""), (100., "100")])
+        });
+        cx.subscribe(&range_slider, |this, _, event: &SliderEvent, cx| {
+            if let SliderEvent::RangeChange(start, end) = event {
+                this.range_value = (*start, *end);
+                cx.notify();
+            }
         })
         .detach();
 
+        // Compare the same content in light and dark colors.
+        let compare_slider = cx.new_view(|_| {
+            CompareSlider::new()
+                .before(|_| {
+                    div()
+                        .size_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(ui::gray_100())
+                        .text_color(ui::gray_900())
+                        .child("Light")
+                })
+                .after(|_| {
+                    div()
+                        .size_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(ui::gray_900())
+                        .text_color(ui::gray_100())
+                        .child("Dark")
+                })
+        });
+
+        let carousel = cx.new_view(|cx| {
+            let slides = [
+                ("Welcome", ui::blue_500()),
+                ("Build with GPUI", ui::violet_500()),
+                ("Get Started", ui::green_500()),
+            ]
+            .map(|(title, color)| {
+                cx.new_view(|_| CarouselSlide {
+                    title: title.into(),
+                    color,
+                })
+            });
+
+            Carousel::new()
+                .children(slides)
+                .looped(true)
+                .autoplay(Duration::from_secs(5))
+        });
+        cx.subscribe(
+            &carousel,
+            |this, _, event: &CarouselEvent, cx| match event {
+                CarouselEvent::Change(ix) => {
+                    this.carousel_ix = *ix;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
         Self {
             focus_handle: cx.focus_handle(),
+            carousel,
+            carousel_ix: 0,
+            range_slider,
+            range_value: (20., 80.),
+            compare_slider,
             value: 50.,
             slider1_value: 15.,
             slider2_value: 1.,
@@ -113,6 +221,24 @@ impl Render for ProgressStory {
                     ))),
             )
             .child(div().w_1_2().child(Progress::new().value(self.value)))
+            .child(div().w_1_2().child(Progress::new().indeterminate(true)))
+            .child(
+                h_flex()
+                    .gap_x_4()
+                    .child(
+                        ProgressCircle::new()
+                            .value(self.value)
+                            .label(format!("{}%", self.value)),
+                    )
+                    .child(
+                        ProgressCircle::new()
+                            .size(px(32.))
+                            .stroke_width(px(3.))
+                            .value(self.value)
+                            .color(ui::green_500()),
+                    )
+                    .child(ProgressCircle::new().size(px(32.)).indeterminate(true)),
+            )
             .child(
                 h_flex()
                     .gap_x_2()
@@ -160,6 +286,58 @@ impl Render for ProgressStory {
                     .child(self.slider2.clone())
                     .child(format!("Slider 2: {}", self.slider2_value)),
             )
+            .child(
+                v_flex()
+                    .gap_3()
+                    .w(px(400.))
+                    .child(self.range_slider.clone())
+                    .child(format!(
+                        "Range: {} - {}",
+                        self.range_value.0, self.range_value.1
+                    )),
+            )
+            .child(
+                Divider::horizontal()
+                    .mt_10()
+                    .label("Compare Slider")
+                    .color(ui::gray_300()),
+            )
+            .child(
+                div()
+                    .w(px(400.))
+                    .h(px(200.))
+                    .child(self.compare_slider.clone()),
+            )
+            .child(
+                Divider::horizontal()
+                    .mt_10()
+                    .label("Carousel")
+                    .color(ui::gray_300()),
+            )
+            .child(
+                v_flex()
+                    .gap_2()
+                    .child(div().w(px(400.)).h(px(200.)).child(self.carousel.clone()))
+                    .child(format!("Active: {}", self.carousel_ix + 1)),
+            )
+            .child(
+                Divider::horizontal()
+                    .mt_10()
+                    .label("Chart Palette")
+                    .color(ui::gray_300()),
+            )
+            .child(h_flex().gap_2().children((0..8).map(|ix| {
+                div()
+                    .relative()
+                    .size_10()
+                    .rounded_md()
+                    .overflow_hidden()
+                    .child(
+                        pattern_fill(Pattern::for_series(ix), cx.theme().chart_color(ix))
+                            .absolute()
+                            .size_full(),
+                    )
+            })))
             .child(
                 h_flex()
                     .mt_5()
@@ -172,5 +350,25 @@ impl Render for ProgressStory {
                             .child(Skeleton::new().w(px(240.)).h_4()),
                     ),
             )
+            .child(
+                h_flex()
+                    .w(px(600.))
+                    .items_start()
+                    .gap_6()
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .gap_4()
+                            .child(Skeleton::avatar_with_text())
+                            .child(Skeleton::paragraph(3)),
+                    )
+                    .child(
+                        v_flex()
+                            .flex_1()
+                            .gap_2()
+                            .child(Skeleton::new().shimmer().h_8())
+                            .child(Skeleton::table(3, 3)),
+                    ),
+            )
     }
 }
@@ -58,7 +58,11 @@ impl ResizableStory {
                         .child(
                             resizable_panel()
                                 .size(px(300.))
-                                .content(|cx| panel_box("Left 1 (Min 120px)", cx)),
+                                .min_size(px(120.))
+                                .collapsible(true)
+                                .content(|cx| {
+                                    panel_box("Left 1 (Min 120px, Double-click to collapse)", cx)
+                                }),
                             cx,
                         )
                         .child(
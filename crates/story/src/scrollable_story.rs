@@ -2,14 +2,14 @@ use std::cell::Cell;
 use std::rc::Rc;
 
 use gpui::{
-    div, px, size, Entity, InteractiveElement, ParentElement, Pixels, Render, ScrollHandle,
-    SharedString, Size, StatefulInteractiveElement as _, Styled, View, ViewContext, VisualContext,
-    WindowContext,
+    canvas, div, prelude::FluentBuilder as _, px, size, Bounds, Entity, InteractiveElement,
+    ParentElement, Pixels, Render, ScrollHandle, SharedString, Size,
+    StatefulInteractiveElement as _, Styled, View, ViewContext, VisualContext, WindowContext,
 };
 use ui::button::Button;
 use ui::divider::Divider;
 use ui::label::Label;
-use ui::scroll::{Scrollbar, ScrollbarAxis, ScrollbarState};
+use ui::scroll::{scroll_into_view, Overscroll, Scrollbar, ScrollbarAxis, ScrollbarState};
 use ui::theme::ActiveTheme;
 use ui::{h_flex, v_flex, v_virtual_list, StyledExt as _};
 
@@ -23,8 +23,13 @@ pub struct ScrollableStory {
     test_width: Pixels,
     axis: ScrollbarAxis,
     message: SharedString,
+    scroll_view_handle: ScrollHandle,
+    /// The bounds of the target item to scroll into view.
+    target_bounds: Rc<Cell<Bounds<Pixels>>>,
 }
 
+const TARGET_ITEM: usize = 250;
+
 const ITEM_HEIGHT: Pixels = px(30.);
 
 impl ScrollableStory {
@@ -46,6 +51,8 @@ impl ScrollableStory {
             test_width,
             axis: ScrollbarAxis::Both,
             message: SharedString::default(),
+            scroll_view_handle: ScrollHandle::new(),
+            target_bounds: Rc::new(Cell::new(Bounds::default())),
         }
     }
 
@@ -85,6 +92,11 @@ impl ScrollableStory {
         cx.notify();
     }
 
+    fn scroll_to_target(&mut self, cx: &mut ViewContext<Self>) {
+        scroll_into_view(&self.scroll_view_handle, self.target_bounds.get());
+        cx.notify();
+    }
+
     fn set_message(&mut self, msg: &str, cx: &mut ViewContext<Self>) {
         self.message = SharedString::from(msg.to_string());
         cx.notify();
@@ -166,6 +178,12 @@ impl Render for ScrollableStory {
                                     .on_click(cx.listener(|view, _, cx| {
                                         view.change_axis(ScrollbarAxis::Horizontal, cx)
                                     })),
+                            )
+                            .child(Divider::vertical().px_2())
+                            .child(
+                                Button::new("test-scroll-into-view")
+                                    .label(format!("Scroll to Item {}", TARGET_ITEM))
+                                    .on_click(cx.listener(|view, _, cx| view.scroll_to_target(cx))),
                             ),
                     )
                     .child(Label::new(self.message.clone())),
@@ -262,20 +280,35 @@ impl Render for ScrollableStory {
                     .child(
                         v_flex()
                             .id("test-1")
-                            .scrollable(cx.view().entity_id(), ScrollbarAxis::Vertical)
+                            .scrollable(cx.view().entity_id(), self.axis)
+                            .track_scroll(&self.scroll_view_handle)
+                            .overscroll(Overscroll::platform())
+                            .edge_fades(true)
                             .focusable()
                             .p_3()
                             .w(self.test_width)
                             .gap_1()
                             .child("Hello world")
-                            .children(self.items.iter().take(500).map(|item| {
+                            .children(self.items.iter().take(500).enumerate().map(|(ix, item)| {
                                 div()
+                                    .relative()
                                     .h(ITEM_HEIGHT)
                                     .bg(cx.theme().background)
                                     .items_center()
                                     .justify_center()
                                     .text_sm()
                                     .child(item.to_string())
+                                    .when(ix == TARGET_ITEM, |this| {
+                                        let target_bounds = self.target_bounds.clone();
+                                        this.text_color(cx.theme().primary).child(
+                                            canvas(
+                                                move |bounds, _| target_bounds.set(bounds),
+                                                |_, _, _| {},
+                                            )
+                                            .absolute()
+                                            .size_full(),
+                                        )
+                                    })
                             })),
                     )
             })
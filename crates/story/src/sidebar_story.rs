@@ -11,7 +11,8 @@ use ui::{
     popup_menu::PopupMenuExt,
     prelude::FluentBuilder,
     sidebar::{
-        Sidebar, SidebarFooter, SidebarGroup, SidebarHeader, SidebarMenu, SidebarToggleButton,
+        Sidebar, SidebarFooter, SidebarGroup, SidebarHeader, SidebarMenu, SidebarState,
+        SidebarToggleButton,
     },
     theme::ActiveTheme,
     v_flex, Collapsible, Icon, IconName,
@@ -26,6 +27,7 @@ pub struct SidebarStory {
     active_item: Item,
     active_subitem: Option<SubItem>,
     is_collapsed: bool,
+    sidebar_state: SidebarState,
     focus_handle: gpui::FocusHandle,
 }
 
@@ -39,6 +41,7 @@ impl SidebarStory {
             active_item: Item::Playground,
             active_subitem: None,
             is_collapsed: false,
+            sidebar_state: SidebarState::default(),
             focus_handle: cx.focus_handle(),
         }
     }
@@ -274,47 +277,60 @@ impl Render for SidebarStory {
                                 )
                             }),
                     )
-                    .child(SidebarGroup::new("Platform").child(SidebarMenu::new().map(
-                        |mut menu| {
-                            for item in groups[0].iter() {
-                                let item = *item;
-                                menu = menu.submenu(
-                                    item.label(),
-                                    Some(item.icon().into()),
-                                    self.active_item == item,
-                                    |mut submenu| {
-                                        for subitem in item.items() {
-                                            submenu = submenu.menu(
-                                                subitem.label(),
-                                                None,
-                                                self.active_subitem == Some(subitem),
-                                                cx.listener(subitem.handler(&item)),
-                                            );
-                                        }
-                                        submenu
-                                    },
-                                    cx.listener(move |this, _, cx| {
-                                        this.active_item = item;
-                                        cx.notify();
-                                    }),
-                                );
-                            }
-                            menu
-                        },
-                    )))
-                    .child(SidebarGroup::new("Projects").child(SidebarMenu::new().map(
-                        |mut menu| {
-                            for item in groups[1].iter() {
-                                menu = menu.menu(
-                                    item.label(),
-                                    Some(item.icon().into()),
-                                    self.active_item == *item,
-                                    cx.listener(item.handler()),
-                                );
-                            }
-                            menu
-                        },
-                    ))),
+                    .child(
+                        SidebarGroup::new("Platform")
+                            .expanded(self.sidebar_state.is_group_expanded("Platform"))
+                            .on_toggle_click(cx.listener(|this, expanded: &bool, cx| {
+                                this.sidebar_state.set_group_expanded("Platform", *expanded);
+                                cx.notify();
+                            }))
+                            .child(SidebarMenu::new().map(|mut menu| {
+                                for item in groups[0].iter() {
+                                    let item = *item;
+                                    menu = menu.submenu(
+                                        item.label(),
+                                        Some(item.icon().into()),
+                                        self.active_item == item,
+                                        |mut submenu| {
+                                            for subitem in item.items() {
+                                                submenu = submenu.menu(
+                                                    subitem.label(),
+                                                    None,
+                                                    self.active_subitem == Some(subitem),
+                                                    cx.listener(subitem.handler(&item)),
+                                                );
+                                            }
+                                            submenu
+                                        },
+                                        cx.listener(move |this, _, cx| {
+                                            this.active_item = item;
+                                            cx.notify();
+                                        }),
+                                    );
+                                }
+                                menu
+                            })),
+                    )
+                    .child(
+                        SidebarGroup::new("Projects")
+                            .badge(groups[1].len())
+                            .expanded(self.sidebar_state.is_group_expanded("Projects"))
+                            .on_toggle_click(cx.listener(|this, expanded: &bool, cx| {
+                                this.sidebar_state.set_group_expanded("Projects", *expanded);
+                                cx.notify();
+                            }))
+                            .child(SidebarMenu::new().map(|mut menu| {
+                                for item in groups[1].iter() {
+                                    menu = menu.menu(
+                                        item.label(),
+                                        Some(item.icon().into()),
+                                        self.active_item == *item,
+                                        cx.listener(item.handler()),
+                                    );
+                                }
+                                menu
+                            })),
+                    ),
             )
             .child(
                 v_flex()
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use gpui::{
     Div, IntoElement, ParentElement, Render, SharedString, Styled, View, ViewContext,
     VisualContext as _, WindowContext,
@@ -13,6 +15,7 @@ pub struct SwitchStory {
     switch1: bool,
     switch2: bool,
     switch3: bool,
+    switch4: bool,
 }
 
 impl super::Story for SwitchStory {
@@ -40,6 +43,7 @@ impl SwitchStory {
             switch1: true,
             switch2: false,
             switch3: true,
+            switch4: false,
         }
     }
 }
@@ -138,6 +142,26 @@ impl Render for SwitchStory {
                             cx.notify();
                         })),
                     )
+                )
+                .child(
+                    card(cx).v_flex()
+                        .items_start().child(title("Async Switch")).child(
+                        Switch::new("switch4").checked(self.switch4).label("Sync to Cloud").on_change({
+                            let view = cx.view().downgrade();
+                            move |checked, cx| {
+                                let checked = *checked;
+                                let view = view.clone();
+                                cx.spawn(|mut cx| async move {
+                                    cx.background_executor().timer(Duration::from_secs(1)).await;
+                                    view.update(&mut cx, |view, cx| {
+                                        view.switch4 = checked;
+                                        cx.notify();
+                                    })
+                                    .is_ok()
+                                })
+                            }
+                        }),
+                    ),
                 ),
             )
         )
@@ -2,21 +2,25 @@ use std::time::{self, Duration};
 
 use fake::{Fake, Faker};
 use gpui::{
-    div, impl_actions, px, AnyElement, AppContext, Edges, InteractiveElement, IntoElement,
-    ParentElement, Pixels, Render, SharedString, Styled, Timer, View, ViewContext,
+    div, impl_actions, px, AnyElement, AppContext, ClickEvent, Edges, InteractiveElement,
+    IntoElement, ParentElement, Pixels, Render, SharedString, Styled, Timer, View, ViewContext,
     VisualContext as _, WindowContext,
 };
 use serde::Deserialize;
 use ui::{
     button::{Button, ButtonVariants},
     checkbox::Checkbox,
+    format::{format_number, format_percent},
     h_flex,
     indicator::Indicator,
     input::{InputEvent, TextInput},
     label::Label,
     popup_menu::{PopupMenu, PopupMenuExt},
     prelude::FluentBuilder as _,
-    table::{ColFixed, ColSort, Table, TableDelegate, TableEvent},
+    table::{
+        self, BarCell, ColFixed, ColSort, DrawerHost, Table, TableDelegate, TableDetail,
+        TableEvent, TableGroup,
+    },
     theme::ActiveTheme as _,
     v_flex, Selectable, Size, StyleSized as _,
 };
@@ -177,6 +181,7 @@ struct StockTableDelegate {
     col_selection: bool,
     loading: bool,
     fixed_cols: bool,
+    group_rows: bool,
     is_eof: bool,
 }
 
@@ -245,6 +250,7 @@ impl StockTableDelegate {
             col_sort: true,
             col_selection: true,
             fixed_cols: false,
+            group_rows: false,
             loading: false,
             is_eof: false,
         }
@@ -265,7 +271,7 @@ impl StockTableDelegate {
         let this = div()
             .h_full()
             .table_cell_size(self.size)
-            .child(format!("{:.3}", val));
+            .child(format_number(val, 3));
         // Val is a 0.0 .. n.0
         // 30% to red, 30% to green, others to default
         let right_num = ((val - val.floor()) * 1000.).floor() as i32;
@@ -311,6 +317,11 @@ impl TableDelegate for StockTableDelegate {
         }
     }
 
+    fn can_hide_col(&self, col_ix: usize, _: &AppContext) -> bool {
+        // Keep the ID column always visible.
+        col_ix > 0
+    }
+
     fn col_padding(&self, col_ix: usize, _: &AppContext) -> Option<Edges<Pixels>> {
         if col_ix >= 3 && col_ix <= 10 {
             Some(Edges::all(px(0.)))
@@ -371,8 +382,17 @@ impl TableDelegate for StockTableDelegate {
             "symbol" => stock.symbol.clone().into_any_element(),
             "price" => self.render_value_cell(stock.price, cx),
             "change" => self.render_value_cell(stock.change, cx),
-            "change_percent" => self.render_value_cell(stock.change_percent, cx),
-            "volume" => self.render_value_cell(stock.volume, cx),
+            "change_percent" => BarCell::new(stock.change_percent)
+                .min(-1.)
+                .max(1.)
+                .color(cx.theme().positive)
+                .negative_color(cx.theme().negative)
+                .percent_label(2)
+                .into_any_element(),
+            "volume" => BarCell::new(stock.volume)
+                .max(1000.)
+                .number_label(3)
+                .into_any_element(),
             "turnover" => self.render_value_cell(stock.turnover, cx),
             "market_cap" => self.render_value_cell(stock.market_cap, cx),
             "ttm" => self.render_value_cell(stock.ttm, cx),
@@ -420,10 +440,90 @@ impl TableDelegate for StockTableDelegate {
         }
     }
 
+    fn export_value(&self, row_ix: usize, col_ix: usize, _: &AppContext) -> SharedString {
+        let (Some(stock), Some(col)) = (self.stocks.get(row_ix), self.columns.get(col_ix)) else {
+            return SharedString::default();
+        };
+
+        match col.id.as_ref() {
+            "id" => stock.id.to_string(),
+            "name" => stock.name.clone(),
+            "symbol" => stock.symbol.clone(),
+            "price" => stock.price.to_string(),
+            "change" => stock.change.to_string(),
+            "change_percent" => stock.change_percent.to_string(),
+            "volume" => stock.volume.to_string(),
+            "turnover" => stock.turnover.to_string(),
+            "market_cap" => stock.market_cap.to_string(),
+            "ttm" => stock.ttm.to_string(),
+            "five_mins_ranking" => stock.five_mins_ranking.to_string(),
+            "th60_days_ranking" => stock.th60_days_ranking.to_string(),
+            "year_change_percent" => stock.year_change_percent.to_string(),
+            "bid" => stock.bid.to_string(),
+            "bid_volume" => stock.bid_volume.to_string(),
+            "ask" => stock.ask.to_string(),
+            "ask_volume" => stock.ask_volume.to_string(),
+            "open" => stock.open.to_string(),
+            "prev_close" => stock.prev_close.to_string(),
+            "high" => stock.high.to_string(),
+            "low" => stock.low.to_string(),
+            "turnover_rate" => stock.turnover_rate.to_string(),
+            "rise_rate" => stock.rise_rate.to_string(),
+            "amplitude" => stock.amplitude.to_string(),
+            "pe_status" => stock.pe_status.to_string(),
+            "pb_status" => stock.pb_status.to_string(),
+            "volume_ratio" => stock.volume_ratio.to_string(),
+            "bid_ask_ratio" => stock.bid_ask_ratio.to_string(),
+            "latest_pre_close" => stock.latest_pre_close.to_string(),
+            "latest_post_close" => stock.latest_post_close.to_string(),
+            "pre_market_cap" => stock.pre_market_cap.to_string(),
+            "pre_market_percent" => stock.pre_market_percent.to_string(),
+            "pre_market_change" => stock.pre_market_change.to_string(),
+            "post_market_cap" => stock.post_market_cap.to_string(),
+            "post_market_percent" => stock.post_market_percent.to_string(),
+            "post_market_change" => stock.post_market_change.to_string(),
+            "float_cap" => stock.float_cap.to_string(),
+            "shares" => stock.shares.to_string(),
+            "shares_float" => stock.shares_float.to_string(),
+            "day_5_ranking" => stock.day_5_ranking.to_string(),
+            "day_10_ranking" => stock.day_10_ranking.to_string(),
+            "day_30_ranking" => stock.day_30_ranking.to_string(),
+            "day_120_ranking" => stock.day_120_ranking.to_string(),
+            "day_250_ranking" => stock.day_250_ranking.to_string(),
+            _ => "--".to_string(),
+        }
+        .into()
+    }
+
     fn can_loop_select(&self, _: &AppContext) -> bool {
         self.loop_selection
     }
 
+    fn group_key(&self, row_ix: usize, _: &AppContext) -> Option<SharedString> {
+        if !self.group_rows {
+            return None;
+        }
+
+        // The stocks are sorted by id, group every 20 stocks.
+        let stock = self.stocks.get(row_ix)?;
+        Some(format!("Batch {}", stock.id / 20 + 1).into())
+    }
+
+    fn group_aggregates(
+        &self,
+        group: &TableGroup,
+        _: &AppContext,
+    ) -> Vec<(SharedString, SharedString)> {
+        let stocks = &self.stocks[group.rows.clone()];
+        let volume = stocks.iter().map(|stock| stock.volume).sum::<f64>();
+        let avg_price = stocks.iter().map(|stock| stock.price).sum::<f64>() / stocks.len() as f64;
+
+        vec![
+            ("Volume".into(), format_number(volume, 0).into()),
+            ("Avg Price".into(), format_number(avg_price, 2).into()),
+        ]
+    }
+
     fn can_move_col(&self, _: usize, _: &AppContext) -> bool {
         self.col_order
     }
@@ -484,12 +584,50 @@ impl TableDelegate for StockTableDelegate {
     }
 }
 
+/// The detail of the selected stock, shown in a drawer.
+struct StockDetail {
+    stock: Stock,
+}
+
+impl StockDetail {
+    fn set_stock(&mut self, stock: Stock, cx: &mut ViewContext<Self>) {
+        self.stock = stock;
+        cx.notify();
+    }
+}
+
+impl Render for StockDetail {
+    fn render(&mut self, _: &mut ViewContext<Self>) -> impl IntoElement {
+        let stock = &self.stock;
+
+        v_flex().gap_2().children(
+            [
+                ("Symbol", stock.symbol.clone()),
+                ("Name", stock.name.clone()),
+                ("Price", format_number(stock.price, 3)),
+                ("Change", format_number(stock.change, 3)),
+                ("Change %", format_percent(stock.change_percent, 2)),
+                ("Volume", format_number(stock.volume, 0)),
+                ("Market Cap", format_number(stock.market_cap, 0)),
+            ]
+            .into_iter()
+            .map(|(label, value)| {
+                h_flex()
+                    .justify_between()
+                    .child(Label::new(label))
+                    .child(value)
+            }),
+        )
+    }
+}
+
 pub struct TableStory {
     table: View<Table<StockTableDelegate>>,
     num_stocks_input: View<TextInput>,
     stripe: bool,
     refresh_data: bool,
     size: Size,
+    detail: Option<TableDetail<StockDetail>>,
 }
 
 impl super::Story for TableStory {
@@ -573,6 +711,7 @@ impl TableStory {
             stripe: false,
             refresh_data: false,
             size: Size::default(),
+            detail: None,
         }
     }
 
@@ -633,6 +772,14 @@ impl TableStory {
         });
     }
 
+    fn toggle_group_rows(&mut self, checked: &bool, cx: &mut ViewContext<Self>) {
+        self.table.update(cx, |table, cx| {
+            table.delegate_mut().group_rows = *checked;
+            table.refresh(cx);
+            cx.notify();
+        });
+    }
+
     fn toggle_stripe(&mut self, checked: &bool, cx: &mut ViewContext<Self>) {
         self.stripe = *checked;
         let stripe = self.stripe;
@@ -663,6 +810,40 @@ impl TableStory {
         cx.notify();
     }
 
+    fn toggle_detail(&mut self, checked: &bool, cx: &mut ViewContext<Self>) {
+        self.detail = checked.then(|| {
+            let table = self.table.clone();
+            let stock = move |row_ix: usize, cx: &WindowContext| {
+                table.read(cx).delegate().stocks[row_ix].clone()
+            };
+
+            TableDetail::new(
+                &self.table,
+                {
+                    let stock = stock.clone();
+                    move |row_ix, cx| {
+                        let stock = stock(row_ix, cx);
+                        cx.new_view(|_| StockDetail { stock })
+                    }
+                },
+                move |detail, row_ix, cx| detail.set_stock(stock(row_ix, cx), cx),
+                DrawerHost::new(|drawer, _| drawer.title("Stock Detail")),
+                cx,
+            )
+        });
+        cx.notify();
+    }
+
+    fn export_csv(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        let path = std::env::temp_dir().join("stocks.csv");
+        let result = std::fs::File::create(&path)
+            .and_then(|mut file| table::export_csv(self.table.read(cx).delegate(), &mut file, cx));
+        match result {
+            Ok(_) => println!("Exported to {}", path.display()),
+            Err(err) => println!("Failed to export: {}", err),
+        }
+    }
+
     fn on_table_event(
         &mut self,
         _: View<Table<StockTableDelegate>>,
@@ -675,9 +856,19 @@ impl TableStory {
             }
             TableEvent::SelectCol(ix) => println!("Select col: {}", ix),
             TableEvent::SelectRow(ix) => println!("Select row: {}", ix),
+            TableEvent::ClearSelection => println!("Clear selection"),
             TableEvent::MoveCol(origin_idx, target_idx) => {
                 println!("Move col index: {} -> {}", origin_idx, target_idx);
             }
+            TableEvent::ColVisibilityChanged(ix, visible) => {
+                println!("Col {} visible: {}", ix, visible);
+            }
+            TableEvent::GroupCollapsed(group, collapsed) => {
+                println!("Group {} collapsed: {}", group.key, collapsed);
+            }
+            TableEvent::RowHeightChanged(row_height) => {
+                println!("Row height changed: {:?}", row_height);
+            }
         }
     }
 }
@@ -726,6 +917,13 @@ impl Render for TableStory {
                                 )
                             }),
                     )
+                    .child(
+                        Button::new("export-csv")
+                            .compact()
+                            .outline()
+                            .label("Export CSV")
+                            .on_click(cx.listener(Self::export_csv)),
+                    )
                     .child(
                         Checkbox::new("loop-selection")
                             .label("Loop Selection")
@@ -768,6 +966,18 @@ impl Render for TableStory {
                             .selected(delegate.fixed_cols)
                             .on_click(cx.listener(Self::toggle_fixed_cols)),
                     )
+                    .child(
+                        Checkbox::new("group-rows")
+                            .label("Group Rows")
+                            .selected(delegate.group_rows)
+                            .on_click(cx.listener(Self::toggle_group_rows)),
+                    )
+                    .child(
+                        Checkbox::new("detail")
+                            .label("Detail Drawer")
+                            .selected(self.detail.is_some())
+                            .on_click(cx.listener(Self::toggle_detail)),
+                    )
                     .child(
                         Checkbox::new("refresh-data")
                             .label("Refresh Data")
@@ -1,22 +1,59 @@
 use gpui::{
-    div, px, rems, IntoElement, ParentElement, Render, Styled, View, ViewContext,
-    VisualContext as _, WindowContext,
+    div, px, rems, InteractiveElement as _, IntoElement, ParentElement, Render, ScrollHandle,
+    StatefulInteractiveElement as _, Styled, View, ViewContext, VisualContext as _, WindowContext,
 };
 
 use ui::{
+    avatar::{Avatar, AvatarGroup, AvatarStatus},
     badge::Badge,
     button::{Button, ButtonVariant, ButtonVariants as _},
     checkbox::Checkbox,
+    clamped_text::ClampedText,
     clipboard::Clipboard,
+    code_block::{CodeBlock, JsonHighlighter},
     h_flex,
     label::Label,
     link::Link,
+    markdown::Markdown,
     radio::Radio,
-    v_flex, Disableable as _, IconName, Sizable, StyledExt,
+    tag::Tag,
+    theme::ActiveTheme as _,
+    toc::{Toc, TocItem},
+    v_flex, Disableable as _, Icon, IconName, Sizable, StyledExt,
 };
 
 use crate::section;
 
+const CODE_BLOCK_JSON: &str = r#"{
+  "symbol": "AAPL.US",
+  "name": "Apple Inc.",
+  "last_done": 189.84,
+  "change_rate": -0.0123,
+  "trading": true,
+  "halted_reason": null,
+  "tags": ["Technology", "Consumer Electronics", "The quote payload is long enough to scroll horizontally in the code block"]
+}"#;
+
+const MARKDOWN_RELEASE_NOTES: &str = r#"## Release Notes
+
+This release adds the **Markdown** element, the *emphasis*, ~~strikethrough~~ and `inline code` are supported.
+
+1. Render the headings, lists and tables.
+2. Open the [links](https://github.com/longbridgeapp/gpui-component) with the system browser.
+   - Nested lists are indented.
+
+| Component | Status |
+|-----------|--------|
+| Markdown  | New    |
+| CodeBlock | Stable |
+
+> The quote is rendered with a muted color.
+
+```json
+{"version": "0.2.0", "stable": true}
+```
+"#;
+
 pub struct TextStory {
     focus_handle: gpui::FocusHandle,
     check1: bool,
@@ -25,8 +62,19 @@ pub struct TextStory {
     radio_check1: bool,
     radio_check2: bool,
     masked: bool,
+    toc: View<Toc>,
+    toc_scroll_handle: ScrollHandle,
 }
 
+const TOC_SECTIONS: [(&str, usize); 6] = [
+    ("General", 0),
+    ("Appearance", 1),
+    ("Theme", 1),
+    ("Font", 1),
+    ("Account", 0),
+    ("About", 0),
+];
+
 impl super::Story for TextStory {
     fn title() -> &'static str {
         "Text"
@@ -43,6 +91,8 @@ impl super::Story for TextStory {
 
 impl TextStory {
     pub(crate) fn new(cx: &mut WindowContext) -> Self {
+        let toc_scroll_handle = ScrollHandle::new();
+
         Self {
             focus_handle: cx.focus_handle(),
             check1: false,
@@ -51,6 +101,15 @@ impl TextStory {
             radio_check1: false,
             radio_check2: true,
             masked: false,
+            toc: cx.new_view(|_| {
+                Toc::new(&toc_scroll_handle).items(
+                    TOC_SECTIONS
+                        .iter()
+                        .enumerate()
+                        .map(|(ix, (label, level))| TocItem::new(*label, ix).level(*level)),
+                )
+            }),
+            toc_scroll_handle,
         }
     }
 
@@ -246,6 +305,31 @@ impl Render for TextStory {
                         )
                 ),
             )
+            .child(
+                section("Code Block", cx).child(
+                    CodeBlock::new("code-block", CODE_BLOCK_JSON)
+                        .highlighter(JsonHighlighter)
+                        .w_full()
+                        .max_h(px(240.)),
+                ),
+            )
+            .child(
+                section("Clamped Text", cx).child(
+                    ClampedText::new(
+                        "clamped-text",
+                        "GPUI is a fast, productive UI framework for Rust from the creators of Zed. \
+                        It is a hybrid immediate and retained mode, GPU accelerated, UI framework for Rust, \
+                        designed to support a wide variety of applications. The long description is limited \
+                        to 2 lines, click the Show more to expand the full text, and the Show less to collapse it.",
+                    )
+                    .lines(2)
+                    .w(px(420.)),
+                ),
+            )
+            .child(
+                section("Markdown", cx)
+                    .child(Markdown::new("markdown", MARKDOWN_RELEASE_NOTES).w_full()),
+            )
             .child(
                 section("Badge", cx)
                     .child(
@@ -264,6 +348,85 @@ impl Render for TextStory {
                             .child(Badge::destructive().child("Destructive"))
                             .child(Badge::custom(ui::yellow_500(), ui::yellow_800(), ui::yellow_500()).child("Custom"))
                     )
+                    .child(
+                        h_flex().gap_4()
+                            .child(Badge::destructive().count(3).attach(Icon::new(IconName::Bell)))
+                            .child(Badge::destructive().count(120).attach(Button::new("inbox").icon(IconName::Inbox).ghost()))
+                            .child(Badge::custom(ui::green_500(), ui::white(), ui::green_500()).dot().attach(Icon::new(IconName::CircleUser)))
+                    )
+            )
+            .child(
+                section("Tag", cx)
+                    .child(Tag::new("tag-secondary", "Secondary"))
+                    .child(Tag::new("tag-primary", "Primary").primary())
+                    .child(Tag::new("tag-outline", "Outline").outline())
+                    .child(Tag::new("tag-destructive", "Destructive").destructive())
+                    .child(Tag::new("tag-blue", "Blue").color(ui::blue_500()))
+                    .child(
+                        Tag::new("tag-removable", "Removable")
+                            .color(ui::orange_500())
+                            .on_remove(|_, _| println!("Remove tag")),
+                    )
+            )
+            .child(
+                section("Avatar", cx)
+                    .child(
+                        h_flex()
+                            .gap_3()
+                            .child(Avatar::new("Jason Lee").xsmall())
+                            .child(Avatar::new("Floyd Wang").small().status(AvatarStatus::Online))
+                            .child(
+                                Avatar::new("GPUI")
+                                    .src("https://github.com/longbridgeapp.png")
+                                    .status(AvatarStatus::Away),
+                            )
+                            .child(Avatar::new("张三").large().status(AvatarStatus::Busy))
+                            .child(Avatar::new("Huacnlee").xlarge()),
+                    )
+                    .child(
+                        AvatarGroup::new()
+                            .children(
+                                ["Jason Lee", "Floyd Wang", "Huacnlee", "张三", "Sunny", "Bob"]
+                                    .into_iter()
+                                    .map(Avatar::new),
+                            )
+                            .limit(4),
+                    ),
+            )
+            .child(
+                section("Toc", cx)
+                    .child(
+                        h_flex()
+                            .w_full()
+                            .h(px(300.))
+                            .items_start()
+                            .gap_4()
+                            .child(div().w(px(160.)).child(self.toc.clone()))
+                            .child(
+                                v_flex()
+                                    .id("toc-content")
+                                    .flex_1()
+                                    .h_full()
+                                    .overflow_y_scroll()
+                                    .track_scroll(&self.toc_scroll_handle)
+                                    .children(TOC_SECTIONS.iter().map(|(label, level)| {
+                                        v_flex()
+                                            .gap_2()
+                                            .pb_6()
+                                            .child(
+                                                Label::new(*label)
+                                                    .font_semibold()
+                                                    .text_size(if *level == 0 { px(18.) } else { px(15.) }),
+                                            )
+                                            .child(
+                                                div()
+                                                    .text_sm()
+                                                    .text_color(cx.theme().muted_foreground)
+                                                    .child("Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur."),
+                                            )
+                                    })),
+                            ),
+                    ),
             )
     }
 }
@@ -1,19 +1,21 @@
 use gpui::{
     div, ClickEvent, FocusHandle, FocusableView, IntoElement, ParentElement as _, Render,
-    Styled as _, View, ViewContext, VisualContext as _, WindowContext,
+    SharedString, Styled as _, View, ViewContext, VisualContext as _, WindowContext,
 };
 use ui::{
     h_flex,
     input::{InputEvent, TextInput},
+    prelude::FluentBuilder as _,
     theme::ActiveTheme,
     v_flex,
-    webview::WebView,
+    webview::{WebView, WebViewEvent},
 };
 
 pub struct WebViewStory {
     focus_handle: FocusHandle,
     webview: View<WebView>,
     address_input: View<TextInput>,
+    title: Option<SharedString>,
 }
 
 impl super::Story for WebViewStory {
@@ -31,10 +33,9 @@ impl WebViewStory {
         let focus_handle = cx.focus_handle();
 
         let webview = cx.new_view(|cx| {
-            let webview = ui::wry::WebViewBuilder::new()
-                .build_as_child(&cx.raw_window_handle())
-                .unwrap();
-            WebView::new(cx, webview)
+            WebView::build(ui::wry::WebViewBuilder::new(), cx)
+                .unwrap()
+                .on_message(|message, _| println!("Message from page: {}", message))
         });
 
         let address_input = cx.new_view(|cx| {
@@ -44,8 +45,8 @@ impl WebViewStory {
         });
 
         let url = address_input.read(cx).text();
-        webview.update(cx, |view, _| {
-            view.load_url(&url);
+        webview.update(cx, |view, cx| {
+            view.load_url(&url, cx);
         });
 
         cx.new_view(|cx| {
@@ -53,15 +54,29 @@ impl WebViewStory {
                 focus_handle,
                 webview,
                 address_input: address_input.clone(),
+                title: None,
             };
 
+            cx.subscribe(&this.webview, |this, _, event: &WebViewEvent, cx| {
+                match event {
+                    WebViewEvent::Navigated(url) => {
+                        this.address_input
+                            .update(cx, |input, cx| input.set_text(url.clone(), cx));
+                    }
+                    WebViewEvent::TitleChanged(title) => this.title = Some(title.clone()),
+                    WebViewEvent::LoadError(err) => println!("Failed to load: {}", err),
+                }
+                cx.notify();
+            })
+            .detach();
+
             cx.subscribe(
                 &address_input,
                 |this: &mut Self, input, event: &InputEvent, cx| match event {
                     InputEvent::PressEnter => {
                         let url = input.read(cx).text();
-                        this.webview.update(cx, |view, _| {
-                            view.load_url(&url);
+                        this.webview.update(cx, |view, cx| {
+                            view.load_url(&url, cx);
                         });
                     }
                     _ => {}
@@ -79,8 +94,8 @@ impl WebViewStory {
 
     #[allow(unused)]
     fn go_back(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
-        self.webview.update(cx, |webview, _| {
-            webview.back().unwrap();
+        self.webview.update(cx, |webview, cx| {
+            webview.back(cx);
         });
     }
 }
@@ -101,6 +116,7 @@ impl Render for WebViewStory {
             .gap_3()
             .size_full()
             .child(h_flex().gap_2().items_center().child(address_input.clone()))
+            .when_some(self.title.clone(), |this, title| this.child(title))
             .child(
                 div()
                     .flex_1()
@@ -0,0 +1,295 @@
+use anyhow::Result;
+use gpui::{
+    actions, div, img, prelude::FluentBuilder as _, px, AppContext, Global,
+    InteractiveElement as _, IntoElement, ParentElement as _, SharedString,
+    StatefulInteractiveElement as _, Styled as _, WindowContext,
+};
+use rust_i18n::t;
+use serde::Deserialize;
+
+use crate::{h_flex, link::Link, theme::ActiveTheme as _, v_flex, ContextModal as _};
+
+actions!(about, [ShowAbout]);
+
+pub fn init(cx: &mut AppContext) {
+    cx.on_action(|_: &ShowAbout, cx: &mut AppContext| {
+        let Some(window) = cx.active_window() else {
+            return;
+        };
+        _ = window.update(cx, |_, cx| open_about(cx));
+    });
+}
+
+/// A third-party dependency to list in the About dialog.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct License {
+    pub name: SharedString,
+    #[serde(default)]
+    pub version: Option<SharedString>,
+    /// The SPDX license expression, e.g.: `MIT OR Apache-2.0`.
+    #[serde(default)]
+    pub license: Option<SharedString>,
+    #[serde(default)]
+    pub repository: Option<SharedString>,
+}
+
+/// Parse the licenses from the JSON generated by `cargo license --json`, e.g.:
+///
+/// ```json
+/// [{ "name": "gpui", "version": "0.1.0", "license": "Apache-2.0", "repository": "https://github.com/zed-industries/zed" }]
+/// ```
+///
+/// The other fields are ignored, the result is sorted by the name and version, and deduplicated.
+pub fn parse_licenses(json: &str) -> Result<Vec<License>> {
+    let mut licenses: Vec<License> = serde_json::from_str(json)?;
+    licenses.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+    licenses.dedup();
+    Ok(licenses)
+}
+
+/// Create the [`AboutInfo`] with the name and version of the calling crate from the Cargo metadata.
+///
+/// ```ignore
+/// about_info!()
+///     .icon("icons/app.svg")
+///     .licenses(parse_licenses(include_str!("../licenses.json"))?)
+///     .set_global(cx);
+/// ```
+#[macro_export]
+macro_rules! about_info {
+    () => {
+        $crate::about::AboutInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+            .description(env!("CARGO_PKG_DESCRIPTION"))
+            .homepage(env!("CARGO_PKG_HOMEPAGE"))
+    };
+}
+
+/// The info to display in the About dialog, set it as global to show by the [`ShowAbout`] action,
+/// so the dialog can be opened from the app menu.
+#[derive(Clone, Default)]
+pub struct AboutInfo {
+    name: SharedString,
+    version: SharedString,
+    icon: Option<SharedString>,
+    build: Option<SharedString>,
+    description: Option<SharedString>,
+    copyright: Option<SharedString>,
+    links: Vec<(SharedString, SharedString)>,
+    licenses: Vec<License>,
+}
+
+impl Global for AboutInfo {}
+
+impl AboutInfo {
+    pub fn new(name: impl Into<SharedString>, version: impl Into<SharedString>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the path of the app icon in the assets.
+    pub fn icon(mut self, icon: impl Into<SharedString>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the build info, e.g.: the commit hash and the build date.
+    pub fn build(mut self, build: impl Into<SharedString>) -> Self {
+        self.build = Some(build.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<SharedString>) -> Self {
+        let description = description.into();
+        self.description = (!description.is_empty()).then_some(description);
+        self
+    }
+
+    pub fn copyright(mut self, copyright: impl Into<SharedString>) -> Self {
+        self.copyright = Some(copyright.into());
+        self
+    }
+
+    /// Add a link, e.g.: the homepage, the privacy policy.
+    pub fn link(mut self, label: impl Into<SharedString>, url: impl Into<SharedString>) -> Self {
+        self.links.push((label.into(), url.into()));
+        self
+    }
+
+    /// Add the homepage link, ignored if the `url` is empty.
+    pub fn homepage(self, url: impl Into<SharedString>) -> Self {
+        let url = url.into();
+        if url.is_empty() {
+            return self;
+        }
+        self.link(t!("About.Homepage").to_string(), url)
+    }
+
+    /// Set the third-party licenses, see [`parse_licenses`].
+    pub fn licenses(mut self, licenses: Vec<License>) -> Self {
+        self.licenses = licenses;
+        self
+    }
+
+    pub fn name(&self) -> &SharedString {
+        &self.name
+    }
+
+    pub fn version(&self) -> &SharedString {
+        &self.version
+    }
+
+    /// Set as the global info of the app, to open by the [`ShowAbout`] action.
+    pub fn set_global(self, cx: &mut AppContext) {
+        cx.set_global(self);
+    }
+
+    /// Open the About dialog with this info.
+    pub fn open(&self, cx: &mut WindowContext) {
+        let info = self.clone();
+        cx.open_modal(move |modal, cx| {
+            modal
+                .width(px(420.))
+                .title(t!("About.Title", name = &info.name).to_string())
+                .child(info.render(cx))
+        });
+    }
+
+    fn render(&self, cx: &WindowContext) -> impl IntoElement {
+        v_flex()
+            .gap_4()
+            .child(
+                h_flex()
+                    .gap_4()
+                    .when_some(self.icon.clone(), |this, icon| {
+                        this.child(img(icon).size_16().flex_shrink_0())
+                    })
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .child(div().text_xl().font_semibold().child(self.name.clone()))
+                            .child(
+                                div().text_sm().child(
+                                    t!("About.Version", version = &self.version).to_string(),
+                                ),
+                            )
+                            .when_some(self.build.clone(), |this, build| {
+                                this.child(
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(build),
+                                )
+                            }),
+                    ),
+            )
+            .when_some(self.description.clone(), |this, description| {
+                this.child(div().text_sm().child(description))
+            })
+            .when(!self.links.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .gap_3()
+                        .text_sm()
+                        .children(self.links.iter().enumerate().map(|(ix, (label, url))| {
+                            Link::new(("about-link", ix))
+                                .href(url.clone())
+                                .child(label.clone())
+                        })),
+                )
+            })
+            .when(!self.licenses.is_empty(), |this| {
+                this.child(
+                    v_flex()
+                        .gap_1()
+                        .child(
+                            div()
+                                .text_sm()
+                                .font_semibold()
+                                .child(t!("About.Licenses").to_string()),
+                        )
+                        .child(
+                            v_flex()
+                                .id("about-licenses")
+                                .max_h(px(200.))
+                                .overflow_y_scroll()
+                                .p_2()
+                                .gap_1()
+                                .rounded(px(cx.theme().radius))
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .text_xs()
+                                .children(self.licenses.iter().map(|license| {
+                                    h_flex()
+                                        .justify_between()
+                                        .gap_2()
+                                        .child(
+                                            h_flex().gap_1().child(license.name.clone()).when_some(
+                                                license.version.clone(),
+                                                |this, version| {
+                                                    this.child(
+                                                        div()
+                                                            .text_color(cx.theme().muted_foreground)
+                                                            .child(version),
+                                                    )
+                                                },
+                                            ),
+                                        )
+                                        .child(div().text_color(cx.theme().muted_foreground).child(
+                                            license.license.clone().unwrap_or_else(|| {
+                                                t!("About.Unknown License").to_string().into()
+                                            }),
+                                        ))
+                                })),
+                        ),
+                )
+            })
+            .when_some(self.copyright.clone(), |this, copyright| {
+                this.child(
+                    div()
+                        .text_xs()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(copyright),
+                )
+            })
+    }
+}
+
+/// Open the About dialog with the global [`AboutInfo`], does nothing if it's not set.
+pub fn open_about(cx: &mut WindowContext) {
+    let Some(info) = cx.try_global::<AboutInfo>().cloned() else {
+        return;
+    };
+    info.open(cx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_licenses;
+
+    #[test]
+    fn test_parse_licenses() {
+        let json = r#"[
+            { "name": "serde", "version": "1.0.2", "license": "MIT OR Apache-2.0", "authors": "David" },
+            { "name": "anyhow", "version": "1.0.0", "license": "MIT OR Apache-2.0", "repository": "https://github.com/dtolnay/anyhow" },
+            { "name": "serde", "version": "1.0.2", "license": "MIT OR Apache-2.0" },
+            { "name": "private" }
+        ]"#;
+
+        let licenses = parse_licenses(json).unwrap();
+        assert_eq!(licenses.len(), 3);
+        assert_eq!(licenses[0].name, "anyhow");
+        assert_eq!(
+            licenses[0].repository.as_deref(),
+            Some("https://github.com/dtolnay/anyhow")
+        );
+        assert_eq!(licenses[1].name, "private");
+        assert_eq!(licenses[1].license, None);
+        assert_eq!(licenses[2].name, "serde");
+        assert_eq!(licenses[2].version.as_deref(), Some("1.0.2"));
+
+        assert!(parse_licenses("{}").is_err());
+    }
+}
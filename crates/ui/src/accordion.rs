@@ -1,12 +1,42 @@
-use std::{cell::Cell, rc::Rc, sync::Arc};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use gpui::{
-    div, prelude::FluentBuilder as _, rems, AnyElement, Div, ElementId, InteractiveElement as _,
-    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled,
-    WindowContext,
+    div, prelude::FluentBuilder as _, px, rems, Animation, AnyElement, Div, ElementId, Global,
+    InteractiveElement as _, IntoElement, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled, WindowContext,
+};
+
+use crate::{
+    animation::{cubic_bezier, TransitionExt as _},
+    h_flex,
+    theme::ActiveTheme as _,
+    v_flex, Icon, IconName, Sizable, Size,
 };
 
-use crate::{h_flex, theme::ActiveTheme as _, v_flex, Icon, IconName, Sizable, Size};
+const TOGGLE_DURATION: Duration = Duration::from_millis(200);
+/// The max height to animate the content, the content is not clipped after the animation.
+const ANIMATION_MAX_HEIGHT: f32 = 800.;
+
+/// The last collapsed accordion item, the content is kept rendering during the collapse animation.
+#[derive(Default)]
+struct AccordionCollapsing {
+    item: Option<(ElementId, usize, Instant)>,
+}
+
+impl Global for AccordionCollapsing {}
+
+impl AccordionCollapsing {
+    fn is_collapsing(id: &ElementId, ix: usize, cx: &WindowContext) -> bool {
+        cx.try_global::<Self>()
+            .and_then(|this| this.item.as_ref())
+            .map_or(false, |(item_id, item_ix, at)| {
+                item_id == id && *item_ix == ix && at.elapsed() < TOGGLE_DURATION
+            })
+    }
+}
 
 /// An AccordionGroup is a container for multiple Accordion elements.
 #[derive(IntoElement)]
@@ -18,7 +48,9 @@ pub struct Accordion {
     bordered: bool,
     disabled: bool,
     children: Vec<AccordionItem>,
+    open_indices: Option<Vec<usize>>,
     on_toggle_click: Option<Arc<dyn Fn(&[usize], &mut WindowContext) + Send + Sync>>,
+    on_toggle: Option<Arc<dyn Fn(usize, bool, &mut WindowContext) + Send + Sync>>,
 }
 
 impl Accordion {
@@ -31,10 +63,13 @@ impl Accordion {
             bordered: true,
             children: Vec::new(),
             disabled: false,
+            open_indices: None,
             on_toggle_click: None,
+            on_toggle: None,
         }
     }
 
+    /// Set to allow multiple items to be open at the same time, default is false.
     pub fn multiple(mut self, multiple: bool) -> Self {
         self.multiple = multiple;
         self
@@ -50,6 +85,13 @@ impl Accordion {
         self
     }
 
+    /// Set the indices of the open items, to control the open state by the parent view,
+    /// this overrides the [`AccordionItem::open`] of the items.
+    pub fn open_indices(mut self, open_indices: impl IntoIterator<Item = usize>) -> Self {
+        self.open_indices = Some(open_indices.into_iter().collect());
+        self
+    }
+
     pub fn item<F>(mut self, child: F) -> Self
     where
         F: FnOnce(AccordionItem) -> AccordionItem,
@@ -69,6 +111,17 @@ impl Accordion {
         self.on_toggle_click = Some(Arc::new(on_toggle_click));
         self
     }
+
+    /// Sets the callback when an item is toggled.
+    ///
+    /// The arguments are the index of the item and whether the item is open now.
+    pub fn on_toggle(
+        mut self,
+        on_toggle: impl Fn(usize, bool, &mut WindowContext) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_toggle = Some(Arc::new(on_toggle));
+        self
+    }
 }
 
 impl Sizable for Accordion {
@@ -79,72 +132,84 @@ impl Sizable for Accordion {
 }
 
 impl RenderOnce for Accordion {
-    fn render(self, _: &mut WindowContext) -> impl IntoElement {
-        let mut open_ixs: Vec<usize> = Vec::new();
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let open_ixs: Vec<usize> = match self.open_indices {
+            Some(open_indices) => open_indices,
+            None => self
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.open)
+                .map(|(ix, _)| ix)
+                .collect(),
+        };
         let multiple = self.multiple;
-        let state = Rc::new(Cell::new(None));
-
-        self.children
-            .iter()
-            .enumerate()
-            .for_each(|(ix, accordion)| {
-                if accordion.open {
-                    open_ixs.push(ix);
-                }
-            });
+        let lite = cx.theme().lite;
+        let id = self.id.clone();
 
         self.base
             .id(self.id)
-            .children(
-                self.children
-                    .into_iter()
-                    .enumerate()
-                    .map(|(ix, accordion)| {
-                        let state = Rc::clone(&state);
-                        accordion
-                            .with_size(self.size)
-                            .bordered(self.bordered)
-                            .when(self.disabled, |this| this.disabled(true))
-                            .on_toggle_click(move |_, _| {
-                                state.set(Some(ix));
-                            })
-                    }),
-            )
-            .when_some(
-                self.on_toggle_click.filter(|_| !self.disabled),
-                move |this, on_toggle_click| {
-                    this.on_click(move |_, cx| {
-                        let mut open_ixs = open_ixs.clone();
-                        if let Some(ix) = state.get() {
-                            if multiple {
-                                if let Some(pos) = open_ixs.iter().position(|&i| i == ix) {
-                                    open_ixs.remove(pos);
-                                } else {
-                                    open_ixs.push(ix);
-                                }
-                            } else {
-                                let was_open = open_ixs.iter().any(|&i| i == ix);
-                                open_ixs.clear();
-                                if !was_open {
-                                    open_ixs.push(ix);
-                                }
-                            }
+            .children(self.children.into_iter().enumerate().map(|(ix, item)| {
+                let open = open_ixs.contains(&ix);
+                let collapsing = !open && !lite && AccordionCollapsing::is_collapsing(&id, ix, cx);
+                let on_toggle_click = self.on_toggle_click.clone();
+                let on_toggle = self.on_toggle.clone();
+                let open_ixs = open_ixs.clone();
+                let id = id.clone();
+
+                item.ix(ix)
+                    .open(open)
+                    .collapsing(collapsing)
+                    .with_size(self.size)
+                    .bordered(self.bordered)
+                    .when(self.disabled, |this| this.disabled(true))
+                    .on_toggle_click(move |open, cx| {
+                        let open = *open;
+                        if !open {
+                            cx.default_global::<AccordionCollapsing>().item =
+                                Some((id.clone(), ix, Instant::now()));
                         }
 
-                        on_toggle_click(&open_ixs, cx);
+                        if let Some(on_toggle) = &on_toggle {
+                            on_toggle(ix, open, cx);
+                        }
+                        if let Some(on_toggle_click) = &on_toggle_click {
+                            on_toggle_click(&toggled_indices(&open_ixs, ix, multiple), cx);
+                        }
                     })
-                },
-            )
+            }))
+    }
+}
+
+/// Returns the open indices after toggling the `ix` item.
+fn toggled_indices(open_ixs: &[usize], ix: usize, multiple: bool) -> Vec<usize> {
+    let mut open_ixs = open_ixs.to_vec();
+    if multiple {
+        if let Some(pos) = open_ixs.iter().position(|&i| i == ix) {
+            open_ixs.remove(pos);
+        } else {
+            open_ixs.push(ix);
+        }
+    } else {
+        let was_open = open_ixs.contains(&ix);
+        open_ixs.clear();
+        if !was_open {
+            open_ixs.push(ix);
+        }
     }
+
+    open_ixs
 }
 
 /// An Accordion is a vertically stacked list of items, each of which can be expanded to reveal the content associated with it.
 #[derive(IntoElement)]
 pub struct AccordionItem {
+    ix: usize,
     icon: Option<Icon>,
     title: AnyElement,
     content: AnyElement,
     open: bool,
+    collapsing: bool,
     size: Size,
     bordered: bool,
     disabled: bool,
@@ -154,10 +219,12 @@ pub struct AccordionItem {
 impl AccordionItem {
     pub fn new() -> Self {
         Self {
+            ix: 0,
             icon: None,
             title: SharedString::default().into_any_element(),
             content: SharedString::default().into_any_element(),
             open: false,
+            collapsing: false,
             disabled: false,
             on_toggle_click: None,
             size: Size::default(),
@@ -195,6 +262,16 @@ impl AccordionItem {
         self
     }
 
+    fn ix(mut self, ix: usize) -> Self {
+        self.ix = ix;
+        self
+    }
+
+    fn collapsing(mut self, collapsing: bool) -> Self {
+        self.collapsing = collapsing;
+        self
+    }
+
     fn on_toggle_click(
         mut self,
         on_toggle_click: impl Fn(&bool, &mut WindowContext) + 'static,
@@ -216,6 +293,7 @@ impl RenderOnce for AccordionItem {
         let text_size = match self.size {
             Size::XSmall => rems(0.875),
             Size::Small => rems(0.875),
+            Size::XLarge => rems(1.125),
             _ => rems(1.0),
         };
 
@@ -226,6 +304,9 @@ impl RenderOnce for AccordionItem {
                 this.border_1().border_color(cx.theme().border).rounded_md()
             })
             .text_size(text_size)
+            .when(self.disabled, |this| {
+                this.text_color(cx.theme().muted_foreground)
+            })
             .child(
                 h_flex()
                     .id("accordion-title")
@@ -234,6 +315,7 @@ impl RenderOnce for AccordionItem {
                         Size::XSmall => this.py_0().px_1p5(),
                         Size::Small => this.py_0p5().px_2(),
                         Size::Large => this.py_1p5().px_4(),
+                        Size::XLarge => this.py_2().px_5(),
                         _ => this.py_1().px_3(),
                     })
                     .when(self.open, |this| {
@@ -262,7 +344,9 @@ impl RenderOnce for AccordionItem {
                     )
                     .when(!self.disabled, |this| {
                         this.cursor_pointer()
-                            .hover(|this| this.bg(cx.theme().accordion_hover))
+                            .when(!cx.theme().lite, |this| {
+                                this.hover(|this| this.bg(cx.theme().accordion_hover))
+                            })
                             .child(
                                 Icon::new(if self.open {
                                     IconName::ChevronUp
@@ -284,17 +368,57 @@ impl RenderOnce for AccordionItem {
                         },
                     ),
             )
-            .when(self.open, |this| {
+            .when(self.open || self.collapsing, |this| {
+                let open = self.open;
+                let content = div()
+                    .map(|this| match self.size {
+                        Size::XSmall => this.p_1p5(),
+                        Size::Small => this.p_2(),
+                        Size::Large => this.p_4(),
+                        Size::XLarge => this.p_5(),
+                        _ => this.p_3(),
+                    })
+                    .child(self.content);
+
+                // The content is removed when folded, so the animation will replay on toggling.
                 this.child(
-                    div()
-                        .map(|this| match self.size {
-                            Size::XSmall => this.p_1p5(),
-                            Size::Small => this.p_2(),
-                            Size::Large => this.p_4(),
-                            _ => this.p_3(),
-                        })
-                        .child(self.content),
+                    div().overflow_hidden().child(content).with_transition(
+                        ElementId::NamedInteger(
+                            if open {
+                                "accordion-expand"
+                            } else {
+                                "accordion-collapse"
+                            }
+                            .into(),
+                            self.ix,
+                        ),
+                        Animation::new(TOGGLE_DURATION).with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                        move |this, delta| {
+                            let delta = if open { delta } else { 1. - delta };
+                            if open && delta >= 1. {
+                                this
+                            } else {
+                                this.max_h(px(ANIMATION_MAX_HEIGHT * delta)).opacity(delta)
+                            }
+                        },
+                        cx,
+                    ),
                 )
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::toggled_indices;
+
+    #[test]
+    fn test_toggled_indices() {
+        assert_eq!(toggled_indices(&[], 1, false), vec![1]);
+        assert_eq!(toggled_indices(&[0], 1, false), vec![1]);
+        assert_eq!(toggled_indices(&[1], 1, false), Vec::<usize>::new());
+
+        assert_eq!(toggled_indices(&[0], 1, true), vec![0, 1]);
+        assert_eq!(toggled_indices(&[0, 1], 0, true), vec![1]);
+    }
+}
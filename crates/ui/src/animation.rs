@@ -1,3 +1,7 @@
+use gpui::{Animation, AnimationExt as _, AnyElement, ElementId, IntoElement, WindowContext};
+
+use crate::theme::ActiveTheme as _;
+
 /// A cubic bezier function like CSS `cubic-bezier`.
 ///
 /// Builder:
@@ -17,3 +21,28 @@ pub fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32) -> impl Fn(f32) -> f32 {
         y
     }
 }
+
+/// Extension to skip the transition animations in the lite rendering mode.
+pub(crate) trait TransitionExt: IntoElement + 'static {
+    /// Like the `with_animation`, but render the final frame directly
+    /// when the [`Theme::lite`](crate::theme::Theme::lite) is enabled.
+    fn with_transition(
+        self,
+        id: impl Into<ElementId>,
+        animation: Animation,
+        animator: impl Fn(Self, f32) -> Self + 'static,
+        cx: &WindowContext,
+    ) -> AnyElement
+    where
+        Self: Sized,
+    {
+        if cx.theme().lite {
+            animator(self, 1.0).into_any_element()
+        } else {
+            self.with_animation(id, animation, animator)
+                .into_any_element()
+        }
+    }
+}
+
+impl<E: IntoElement + 'static> TransitionExt for E {}
@@ -0,0 +1,294 @@
+use gpui::{
+    div, img, prelude::FluentBuilder as _, px, Div, Hsla, IntoElement, ParentElement, Pixels,
+    RenderOnce, SharedString, Styled, WindowContext,
+};
+
+use crate::{h_flex, theme::ActiveTheme as _, Sizable, Size};
+
+/// The background colors of the initials, picked by the hash of the name.
+const AVATAR_COLORS: [fn() -> Hsla; 10] = [
+    crate::red_500,
+    crate::orange_500,
+    crate::amber_500,
+    crate::green_500,
+    crate::teal_500,
+    crate::sky_500,
+    crate::blue_500,
+    crate::indigo_500,
+    crate::violet_500,
+    crate::pink_500,
+];
+
+/// The status dot displayed at the bottom right of the [`Avatar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AvatarStatus {
+    Online,
+    Away,
+    Busy,
+    Offline,
+}
+
+impl AvatarStatus {
+    fn color(&self, cx: &WindowContext) -> Hsla {
+        match self {
+            Self::Online => cx.theme().positive,
+            Self::Away => cx.theme().warning,
+            Self::Busy => cx.theme().negative,
+            Self::Offline => cx.theme().muted_foreground,
+        }
+    }
+}
+
+/// Returns the initials of the name, e.g.: "Jason Lee" -> "JL", "GPUI" -> "G".
+fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next().and_then(|word| word.chars().next());
+    let last = words.last().and_then(|word| word.chars().next());
+
+    match (first, last) {
+        (Some(first), Some(last)) => first.to_uppercase().chain(last.to_uppercase()).collect(),
+        (Some(first), None) => first.to_uppercase().collect(),
+        _ => "?".to_string(),
+    }
+}
+
+/// Returns the index of [`AVATAR_COLORS`] for the name, the same name always gets the same color.
+fn color_index(name: &str) -> usize {
+    let hash = name
+        .chars()
+        .fold(0u32, |hash, c| hash.wrapping_mul(31).wrapping_add(c as u32));
+
+    hash as usize % AVATAR_COLORS.len()
+}
+
+fn avatar_size(size: Size) -> Pixels {
+    match size {
+        Size::Size(size) => size,
+        Size::XSmall => px(20.),
+        Size::Small => px(24.),
+        Size::Medium => px(32.),
+        Size::Large => px(40.),
+        Size::XLarge => px(56.),
+    }
+}
+
+/// An image of the user, falls back to the colored initials of the name
+/// when the image is not set, loading or failed to load.
+///
+/// ```ignore
+/// Avatar::new("Jason Lee")
+///     .src("https://example.com/avatar.png")
+///     .status(AvatarStatus::Online)
+///     .large()
+/// ```
+#[derive(IntoElement)]
+pub struct Avatar {
+    base: Div,
+    name: SharedString,
+    src: Option<SharedString>,
+    status: Option<AvatarStatus>,
+    size: Size,
+}
+
+impl Avatar {
+    pub fn new(name: impl Into<SharedString>) -> Self {
+        Self {
+            base: div(),
+            name: name.into(),
+            src: None,
+            status: None,
+            size: Size::default(),
+        }
+    }
+
+    /// Set the image url or the file path of the avatar, the image is loaded asynchronously.
+    pub fn src(mut self, src: impl Into<SharedString>) -> Self {
+        self.src = Some(src.into());
+        self
+    }
+
+    /// Set the status dot of the avatar.
+    pub fn status(mut self, status: AvatarStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+}
+
+impl Sizable for Avatar {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for Avatar {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Avatar {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let size = avatar_size(self.size);
+        let color = AVATAR_COLORS[color_index(&self.name)]();
+
+        self.base
+            .relative()
+            .flex_shrink_0()
+            .size(size)
+            .rounded_full()
+            .child(
+                div()
+                    .relative()
+                    .size_full()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .rounded_full()
+                    .overflow_hidden()
+                    .bg(color)
+                    .text_color(crate::white())
+                    .text_size(size * 0.4)
+                    .child(initials(&self.name))
+                    // The initials are covered by the image after it is loaded.
+                    .when_some(self.src, |this, src| {
+                        this.child(img(src).absolute().top_0().left_0().size_full())
+                    }),
+            )
+            .when_some(self.status, |this, status| {
+                let dot_size = (size * 0.3).max(px(6.));
+
+                this.child(
+                    div()
+                        .absolute()
+                        .right_0()
+                        .bottom_0()
+                        .size(dot_size)
+                        .rounded_full()
+                        .border_2()
+                        .border_color(cx.theme().background)
+                        .bg(status.color(cx)),
+                )
+            })
+    }
+}
+
+/// A group of the [`Avatar`]s stacked horizontally, the avatars over the limit
+/// are displayed as a "+N" indicator.
+///
+/// ```ignore
+/// AvatarGroup::new()
+///     .children(members.iter().map(|member| Avatar::new(member.name.clone())))
+///     .limit(3)
+/// ```
+#[derive(IntoElement)]
+pub struct AvatarGroup {
+    base: Div,
+    avatars: Vec<Avatar>,
+    limit: Option<usize>,
+    size: Size,
+}
+
+impl AvatarGroup {
+    pub fn new() -> Self {
+        Self {
+            base: h_flex(),
+            avatars: Vec::new(),
+            limit: None,
+            size: Size::default(),
+        }
+    }
+
+    pub fn child(mut self, avatar: Avatar) -> Self {
+        self.avatars.push(avatar);
+        self
+    }
+
+    pub fn children(mut self, avatars: impl IntoIterator<Item = Avatar>) -> Self {
+        self.avatars.extend(avatars);
+        self
+    }
+
+    /// Set the max number of the avatars to display, default is None to display all.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+}
+
+impl Sizable for AvatarGroup {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for AvatarGroup {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for AvatarGroup {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let size = avatar_size(self.size);
+        let overlap = -(size * 0.25);
+        let limit = self.limit.unwrap_or(self.avatars.len());
+        let overflow = self.avatars.len().saturating_sub(limit);
+        let ring_color = cx.theme().background;
+
+        self.base
+            .children(
+                self.avatars
+                    .into_iter()
+                    .take(limit)
+                    .enumerate()
+                    .map(move |(ix, avatar)| {
+                        avatar
+                            .with_size(self.size)
+                            .border_2()
+                            .border_color(ring_color)
+                            .when(ix > 0, |this| this.ml(overlap))
+                    }),
+            )
+            .when(overflow > 0, |this| {
+                this.child(
+                    div()
+                        .flex()
+                        .flex_shrink_0()
+                        .items_center()
+                        .justify_center()
+                        .size(size)
+                        .rounded_full()
+                        .border_2()
+                        .border_color(ring_color)
+                        .when(limit > 0, |this| this.ml(overlap))
+                        .bg(cx.theme().muted)
+                        .text_color(cx.theme().muted_foreground)
+                        .text_size(size * 0.35)
+                        .child(format!("+{}", overflow)),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{color_index, initials, AVATAR_COLORS};
+
+    #[test]
+    fn test_initials() {
+        assert_eq!(initials("Jason Lee"), "JL");
+        assert_eq!(initials("  jason   van  lee "), "JL");
+        assert_eq!(initials("GPUI"), "G");
+        assert_eq!(initials("张三"), "张");
+        assert_eq!(initials(""), "?");
+    }
+
+    #[test]
+    fn test_color_index() {
+        assert_eq!(color_index("Jason Lee"), color_index("Jason Lee"));
+        assert!(color_index("Jason Lee") < AVATAR_COLORS.len());
+        assert_ne!(color_index("Jason Lee"), color_index("Floyd Wang"));
+    }
+}
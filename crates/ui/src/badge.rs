@@ -1,7 +1,7 @@
 use crate::{theme::ActiveTheme as _, Sizable, Size};
 use gpui::{
-    div, prelude::FluentBuilder as _, relative, Div, Hsla, InteractiveElement as _, IntoElement,
-    ParentElement, RenderOnce, Styled,
+    div, prelude::FluentBuilder as _, px, relative, AnyElement, Div, Hsla, InteractiveElement as _,
+    IntoElement, ParentElement, RenderOnce, Styled,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -49,14 +49,34 @@ impl BadgeVariant {
     }
 }
 
+/// Returns the label of the count, e.g.: "99+" if the count is greater than the `max`.
+fn count_label(count: usize, max: usize) -> String {
+    if count > max {
+        format!("{}+", max)
+    } else {
+        count.to_string()
+    }
+}
+
 /// Badge is a small status indicator for UI elements.
 ///
 /// Only support: Medium, Small
+///
+/// Use [`Badge::count`] or [`Badge::dot`] with [`Badge::attach`] to display
+/// the count or status at the top right corner of the icons and the buttons:
+///
+/// ```ignore
+/// Badge::destructive().count(unread).attach(Icon::new(IconName::Bell))
+/// ```
 #[derive(IntoElement)]
 pub struct Badge {
     base: Div,
     veriant: BadgeVariant,
     size: Size,
+    count: Option<usize>,
+    max: usize,
+    dot: bool,
+    target: Option<AnyElement>,
 }
 impl Badge {
     fn new() -> Self {
@@ -64,9 +84,37 @@ impl Badge {
             base: div().flex().items_center().rounded_md().border_1(),
             veriant: BadgeVariant::default(),
             size: Size::Medium,
+            count: None,
+            max: 99,
+            dot: false,
+            target: None,
         }
     }
 
+    /// Display the count as a pill, the badge is hidden if the count is 0.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Set the max count to display, the greater count is displayed as "99+", default is 99.
+    pub fn max(mut self, max: usize) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Display as a small dot without the content, e.g.: the online status.
+    pub fn dot(mut self) -> Self {
+        self.dot = true;
+        self
+    }
+
+    /// Attach the badge to the top right corner of the target element.
+    pub fn attach(mut self, target: impl IntoElement) -> Self {
+        self.target = Some(target.into_any_element());
+        self
+    }
+
     pub fn with_variant(mut self, variant: BadgeVariant) -> Self {
         self.veriant = variant;
         self
@@ -109,15 +157,70 @@ impl ParentElement for Badge {
 }
 impl RenderOnce for Badge {
     fn render(self, cx: &mut gpui::WindowContext) -> impl IntoElement {
-        self.base
+        let visible = self.count != Some(0);
+
+        let badge = self
+            .base
             .line_height(relative(1.3))
-            .map(|this| match self.size {
-                Size::XSmall | Size::Small => this.text_xs().px_1p5().py_0(),
-                _ => this.text_xs().px_2p5().py_0p5(),
+            .map(|this| {
+                if self.dot {
+                    this.size(px(8.)).rounded_full()
+                } else if let Some(count) = self.count {
+                    this.justify_center()
+                        .rounded_full()
+                        .min_w(px(16.))
+                        .h(px(16.))
+                        .px_1()
+                        .text_size(px(10.))
+                        .child(count_label(count, self.max))
+                } else {
+                    this.map(|this| match self.size {
+                        Size::XSmall | Size::Small => this.text_xs().px_1p5().py_0(),
+                        Size::XLarge => this.text_sm().px_3().py_0p5(),
+                        _ => this.text_xs().px_2p5().py_0p5(),
+                    })
+                    .hover(|this| this.opacity(0.9))
+                }
             })
             .bg(self.veriant.bg(cx))
             .text_color(self.veriant.fg(cx))
-            .border_color(self.veriant.border(cx))
-            .hover(|this| this.opacity(0.9))
+            .border_color(self.veriant.border(cx));
+
+        match self.target {
+            Some(target) => div()
+                .relative()
+                .flex_shrink_0()
+                .child(target)
+                .when(visible, |this| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .map(|this| {
+                                if self.count.is_some() {
+                                    this.top(px(-6.)).right(px(-8.))
+                                } else {
+                                    this.top_0().right_0()
+                                }
+                            })
+                            .child(badge),
+                    )
+                })
+                .into_any_element(),
+            None if visible => badge.into_any_element(),
+            None => div().into_any_element(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::count_label;
+
+    #[test]
+    fn test_count_label() {
+        assert_eq!(count_label(0, 99), "0");
+        assert_eq!(count_label(99, 99), "99");
+        assert_eq!(count_label(100, 99), "99+");
+        assert_eq!(count_label(12, 9), "9+");
     }
 }
@@ -0,0 +1,81 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{AppContext, Model, Subscription, View, ViewContext};
+
+/// A lightweight binding to derive a state (e.g.: visibility, disabled, options) of the fields
+/// from the values of other fields, and apply it automatically when the source fields changed.
+///
+/// The `apply` only be called when the computed value has been changed,
+/// so it's safe to update the source fields in the `apply`.
+///
+/// ```ignore
+/// let _subscriptions = Binding::new(
+///     |this: &Self, cx| this.username.read(cx).text().is_empty(),
+///     |this, disabled, cx| this.password.update(cx, |input, cx| input.set_disabled(*disabled, cx)),
+/// )
+/// .observe(&username, cx)
+/// .build(cx);
+/// ```
+pub struct Binding<V: 'static, T: PartialEq + 'static> {
+    compute: Rc<dyn Fn(&V, &AppContext) -> T>,
+    apply: Rc<dyn Fn(&mut V, &T, &mut ViewContext<V>)>,
+    last_value: Rc<RefCell<Option<T>>>,
+    subscriptions: Vec<Subscription>,
+}
+
+impl<V: 'static, T: PartialEq + 'static> Binding<V, T> {
+    /// Create a binding with the `compute` to derive the value, and the `apply` to update the fields.
+    pub fn new(
+        compute: impl Fn(&V, &AppContext) -> T + 'static,
+        apply: impl Fn(&mut V, &T, &mut ViewContext<V>) + 'static,
+    ) -> Self {
+        Self {
+            compute: Rc::new(compute),
+            apply: Rc::new(apply),
+            last_value: Rc::new(RefCell::new(None)),
+            subscriptions: Vec::new(),
+        }
+    }
+
+    /// Observe a source view, the binding will be re-evaluated when the view notified.
+    pub fn observe<S: 'static>(mut self, view: &View<S>, cx: &mut ViewContext<V>) -> Self {
+        let update = self.updater();
+        self.subscriptions
+            .push(cx.observe(view, move |this, _, cx| update(this, cx)));
+        self
+    }
+
+    /// Observe a source model, the binding will be re-evaluated when the model notified.
+    pub fn observe_model<S: 'static>(mut self, model: &Model<S>, cx: &mut ViewContext<V>) -> Self {
+        let update = self.updater();
+        self.subscriptions
+            .push(cx.observe(model, move |this, _, cx| update(this, cx)));
+        self
+    }
+
+    /// Apply the binding for the first time, and returns the subscriptions to keep the binding alive.
+    ///
+    /// The binding will be evaluated after the current update, so it can be built in the view's `new`.
+    pub fn build(self, cx: &mut ViewContext<V>) -> Vec<Subscription> {
+        let update = self.updater();
+        cx.defer(move |this, cx| update(this, cx));
+        self.subscriptions
+    }
+
+    fn updater(&self) -> impl Fn(&mut V, &mut ViewContext<V>) + 'static {
+        let compute = self.compute.clone();
+        let apply = self.apply.clone();
+        let last_value = self.last_value.clone();
+
+        move |this, cx| {
+            let value = compute(this, cx);
+            if last_value.borrow().as_ref() == Some(&value) {
+                return;
+            }
+
+            apply(this, &value, cx);
+            *last_value.borrow_mut() = Some(value);
+            cx.notify();
+        }
+    }
+}
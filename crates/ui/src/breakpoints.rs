@@ -0,0 +1,249 @@
+//! Keep the app shell usable in the small windows: enforce a minimum window size, and collapse
+//! the docks / sidebar to their compact modes one by one when the window is below the breakpoints.
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use gpui::{
+    size, Axis, Bounds, Pixels, Size, Subscription, ViewContext, WeakView, WindowBounds,
+    WindowOptions,
+};
+
+use crate::dock::{DockArea, DockPlacement};
+
+struct Step<V> {
+    axis: Axis,
+    size: Pixels,
+    on_change: Box<dyn Fn(&mut V, bool, &mut ViewContext<V>)>,
+}
+
+/// Returns the collapsed state of each step `(axis, breakpoint)` for the window `size`.
+///
+/// The steps of the same axis are collapsed in the order, so a step is never collapsed
+/// before the previous steps, even if its breakpoint is smaller.
+fn resolve_collapsed(size: Size<Pixels>, steps: &[(Axis, Pixels)]) -> Vec<bool> {
+    let mut collapsed = steps
+        .iter()
+        .map(|(axis, breakpoint)| size.along(*axis) < *breakpoint)
+        .collect::<Vec<_>>();
+
+    for ix in (0..steps.len()).rev() {
+        if !collapsed[ix] {
+            continue;
+        }
+        for prev_ix in 0..ix {
+            if steps[prev_ix].0 == steps[ix].0 {
+                collapsed[prev_ix] = true;
+            }
+        }
+    }
+    collapsed
+}
+
+/// The breakpoints of the app shell, usually created in the root view of the window.
+///
+/// ```ignore
+/// let breakpoints = Breakpoints::new()
+///     .min_size(size(px(640.), px(480.)))
+///     .dock(dock_area.downgrade(), DockPlacement::Right, px(1200.))
+///     .dock(dock_area.downgrade(), DockPlacement::Bottom, px(640.))
+///     .collapse_below_width(px(900.), |this: &mut Workspace, collapsed, cx| {
+///         this.sidebar_collapsed = collapsed;
+///         cx.notify();
+///     });
+///
+/// let options = breakpoints.window_options(options);
+/// // In the root view:
+/// self._subscriptions.push(breakpoints.observe(cx));
+/// ```
+pub struct Breakpoints<V> {
+    min_size: Option<Size<Pixels>>,
+    steps: Vec<Step<V>>,
+    collapsed: Vec<bool>,
+}
+
+impl<V: 'static> Default for Breakpoints<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: 'static> Breakpoints<V> {
+    pub fn new() -> Self {
+        Self {
+            min_size: None,
+            steps: Vec::new(),
+            collapsed: Vec::new(),
+        }
+    }
+
+    /// Set the minimum size of the window.
+    pub fn min_size(mut self, size: Size<Pixels>) -> Self {
+        self.min_size = Some(size);
+        self
+    }
+
+    /// Add a step to collapse when the window width is less than the `width`.
+    ///
+    /// The `on_change` is called with `true` to collapse and `false` to expand, when the state changes.
+    /// The steps are collapsed in the adding order when the window becomes narrower,
+    /// and expanded in the reverse order.
+    pub fn collapse_below_width(
+        self,
+        width: Pixels,
+        on_change: impl Fn(&mut V, bool, &mut ViewContext<V>) + 'static,
+    ) -> Self {
+        self.step(Axis::Horizontal, width, on_change)
+    }
+
+    /// Add a step to collapse when the window height is less than the `height`, see [`Breakpoints::collapse_below_width`].
+    pub fn collapse_below_height(
+        self,
+        height: Pixels,
+        on_change: impl Fn(&mut V, bool, &mut ViewContext<V>) + 'static,
+    ) -> Self {
+        self.step(Axis::Vertical, height, on_change)
+    }
+
+    /// Add a step to close the dock at the `placement` of the [`DockArea`], when the window
+    /// width (the height for the bottom dock) is less than the `size`.
+    ///
+    /// The dock is reopened after the window grows, only if it was closed by the breakpoint.
+    pub fn dock(
+        self,
+        dock_area: WeakView<DockArea>,
+        placement: DockPlacement,
+        size: Pixels,
+    ) -> Self {
+        let axis = match placement {
+            DockPlacement::Bottom => Axis::Vertical,
+            _ => Axis::Horizontal,
+        };
+        let closed_by_breakpoint = Cell::new(false);
+
+        self.step(axis, size, move |_, collapsed, cx| {
+            _ = dock_area.update(cx, |dock_area, cx| {
+                if collapsed {
+                    if dock_area.is_dock_open(placement, cx) {
+                        dock_area.set_dock_open(placement, false, cx);
+                        closed_by_breakpoint.set(true);
+                    }
+                } else if closed_by_breakpoint.replace(false) {
+                    dock_area.set_dock_open(placement, true, cx);
+                }
+            });
+        })
+    }
+
+    fn step(
+        mut self,
+        axis: Axis,
+        size: Pixels,
+        on_change: impl Fn(&mut V, bool, &mut ViewContext<V>) + 'static,
+    ) -> Self {
+        self.steps.push(Step {
+            axis,
+            size,
+            on_change: Box::new(on_change),
+        });
+        self.collapsed.push(false);
+        self
+    }
+
+    /// Apply the minimum size to the [`WindowOptions`], the restored bounds smaller than
+    /// the minimum size are enlarged.
+    pub fn window_options(&self, mut options: WindowOptions) -> WindowOptions {
+        let Some(min_size) = self.min_size else {
+            return options;
+        };
+
+        options.window_min_size = Some(min_size);
+        options.window_bounds = options.window_bounds.map(|window_bounds| {
+            let enlarge = |bounds: Bounds<Pixels>| {
+                let size = size(
+                    bounds.size.width.max(min_size.width),
+                    bounds.size.height.max(min_size.height),
+                );
+                Bounds::new(bounds.origin, size)
+            };
+            match window_bounds {
+                WindowBounds::Windowed(bounds) => WindowBounds::Windowed(enlarge(bounds)),
+                WindowBounds::Maximized(bounds) => WindowBounds::Maximized(enlarge(bounds)),
+                WindowBounds::Fullscreen(bounds) => WindowBounds::Fullscreen(enlarge(bounds)),
+            }
+        });
+        options
+    }
+
+    /// Collapse or expand the steps by the current size of the window.
+    pub fn apply(&mut self, view: &mut V, cx: &mut ViewContext<V>) {
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| (step.axis, step.size))
+            .collect::<Vec<_>>();
+        let collapsed = resolve_collapsed(cx.viewport_size(), &steps);
+
+        // Collapse in the order, expand in the reverse order.
+        for ix in 0..self.steps.len() {
+            if collapsed[ix] && !self.collapsed[ix] {
+                self.collapsed[ix] = true;
+                (self.steps[ix].on_change)(view, true, cx);
+            }
+        }
+        for ix in (0..self.steps.len()).rev() {
+            if !collapsed[ix] && self.collapsed[ix] {
+                self.collapsed[ix] = false;
+                (self.steps[ix].on_change)(view, false, cx);
+            }
+        }
+    }
+
+    /// Apply the breakpoints now and every time the window is resized.
+    pub fn observe(self, cx: &mut ViewContext<V>) -> Subscription {
+        let this = Rc::new(RefCell::new(self));
+
+        cx.defer({
+            let this = this.clone();
+            move |view, cx| this.borrow_mut().apply(view, cx)
+        });
+        cx.observe_window_bounds(move |view, cx| this.borrow_mut().apply(view, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{px, size, Axis};
+
+    use super::resolve_collapsed;
+
+    #[test]
+    fn test_resolve_collapsed() {
+        let steps = [
+            (Axis::Horizontal, px(1200.)),
+            (Axis::Vertical, px(600.)),
+            (Axis::Horizontal, px(900.)),
+            // Smaller than the previous, but collapsed after it.
+            (Axis::Horizontal, px(1000.)),
+        ];
+
+        assert_eq!(
+            resolve_collapsed(size(px(1600.), px(900.)), &steps),
+            vec![false, false, false, false]
+        );
+        assert_eq!(
+            resolve_collapsed(size(px(1100.), px(900.)), &steps),
+            vec![true, false, false, false]
+        );
+        assert_eq!(
+            resolve_collapsed(size(px(950.), px(500.)), &steps),
+            vec![true, true, true, true]
+        );
+        assert_eq!(
+            resolve_collapsed(size(px(1600.), px(500.)), &steps),
+            vec![false, true, false, false]
+        );
+    }
+}
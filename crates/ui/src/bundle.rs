@@ -0,0 +1,469 @@
+use std::{cell::RefCell, collections::BTreeMap, path::Path, rc::Rc};
+
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{px, AppContext, ParentElement as _, SharedString, Styled, WindowContext};
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    checkbox::Checkbox,
+    dock::{write_atomic, DockAreaState},
+    h_flex,
+    keymap::Keymap,
+    notification::Notification,
+    profile::{Profile, ProfileTheme, Profiles},
+    table::TableState,
+    theme::{ActiveTheme as _, Theme},
+    v_flex, ContextModal as _,
+};
+
+/// The version of the [`ConfigBundle`] format, bump it on the incompatible changes.
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// All the UI configuration of the app in a single versioned file: the theme, the keymap,
+/// the dock layouts, the table states and the settings.
+///
+/// This is used to deploy a standardized workspace, e.g.: export the bundle on a configured
+/// machine, then import it on the others, the conflicts are resolved by the user.
+///
+/// ```ignore
+/// ConfigBundle::capture(cx).export("workspace.json")?;
+///
+/// // On another machine.
+/// bundle::import_bundle("workspace.json", |cx| println!("imported"), cx)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    /// The [`BUNDLE_VERSION`] of the bundle when it is exported.
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ProfileTheme>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keymap: Option<String>,
+    #[serde(default)]
+    pub layouts: BTreeMap<String, DockAreaState>,
+    #[serde(default)]
+    pub tables: BTreeMap<String, TableState>,
+    #[serde(default)]
+    pub settings: BTreeMap<String, serde_json::Value>,
+}
+
+impl Default for ConfigBundle {
+    fn default() -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            theme: None,
+            keymap: None,
+            layouts: BTreeMap::new(),
+            tables: BTreeMap::new(),
+            settings: BTreeMap::new(),
+        }
+    }
+}
+
+/// The kind of the configuration in the [`ConfigBundle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigKind {
+    Theme,
+    Keymap,
+    Layout,
+    Table,
+    Setting,
+}
+
+impl ConfigKind {
+    fn label(&self) -> SharedString {
+        match self {
+            Self::Theme => t!("Bundle.Theme"),
+            Self::Keymap => t!("Bundle.Keymap"),
+            Self::Layout => t!("Bundle.Layout"),
+            Self::Table => t!("Bundle.Table"),
+            Self::Setting => t!("Bundle.Setting"),
+        }
+        .to_string()
+        .into()
+    }
+}
+
+/// A configuration exists in both the current and the imported bundles with different values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigConflict {
+    pub kind: ConfigKind,
+    /// The key of the layout, table or setting, `None` for the theme and the keymap.
+    pub key: Option<String>,
+}
+
+impl ConfigConflict {
+    fn new(kind: ConfigKind, key: Option<&str>) -> Self {
+        Self {
+            kind,
+            key: key.map(Into::into),
+        }
+    }
+
+    fn label(&self) -> SharedString {
+        match &self.key {
+            Some(key) => format!("{}: {}", self.kind.label(), key).into(),
+            None => self.kind.label(),
+        }
+    }
+}
+
+impl ConfigBundle {
+    /// Create the bundle from the profile.
+    pub fn from_profile(profile: &Profile) -> Self {
+        Self {
+            version: BUNDLE_VERSION,
+            theme: Some(profile.theme.clone()),
+            keymap: profile.keymap.clone(),
+            layouts: profile.layouts.clone(),
+            tables: profile.tables.clone(),
+            settings: profile.state.clone(),
+        }
+    }
+
+    /// Create the bundle from the active profile with the current theme.
+    pub fn capture(cx: &AppContext) -> Self {
+        let mut bundle = Self::from_profile(Profiles::global(cx).active());
+        bundle.theme = Some(ProfileTheme::from_theme(Theme::global(cx)));
+        bundle
+    }
+
+    /// Save the bundle into the file atomically.
+    pub fn export(&self, path: impl AsRef<Path>) -> Result<()> {
+        write_atomic(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Read the bundle from the file, and validate it by [`ConfigBundle::validate`].
+    pub fn read(path: impl AsRef<Path>, cx: &AppContext) -> Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        let bundle = serde_json::from_str::<Self>(&json).context("invalid configuration bundle")?;
+        bundle.validate(cx)?;
+        Ok(bundle)
+    }
+
+    /// Returns error if the bundle is exported by a newer version, or the keymap is invalid.
+    pub fn validate(&self, cx: &AppContext) -> Result<()> {
+        check_version(self.version)?;
+        if let Some(keymap) = &self.keymap {
+            Keymap::validate_user_keymap(keymap, cx)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the configurations of the `incoming` bundle that exist in this bundle with different values.
+    pub fn conflicts(&self, incoming: &Self) -> Vec<ConfigConflict> {
+        let mut conflicts = vec![];
+        if is_conflict(&self.theme, &incoming.theme) {
+            conflicts.push(ConfigConflict::new(ConfigKind::Theme, None));
+        }
+        if is_conflict(&self.keymap, &incoming.keymap) {
+            conflicts.push(ConfigConflict::new(ConfigKind::Keymap, None));
+        }
+        map_conflicts(
+            ConfigKind::Layout,
+            &self.layouts,
+            &incoming.layouts,
+            &mut conflicts,
+        );
+        map_conflicts(
+            ConfigKind::Table,
+            &self.tables,
+            &incoming.tables,
+            &mut conflicts,
+        );
+        map_conflicts(
+            ConfigKind::Setting,
+            &self.settings,
+            &incoming.settings,
+            &mut conflicts,
+        );
+        conflicts
+    }
+
+    /// Merge the `incoming` bundle into this bundle.
+    ///
+    /// The configurations that don't exist in this bundle are added, and the conflicts are
+    /// replaced only if the `replace` returns true.
+    pub fn merge(&mut self, incoming: Self, replace: impl Fn(&ConfigConflict) -> bool) {
+        if is_conflict(&self.theme, &incoming.theme) {
+            if replace(&ConfigConflict::new(ConfigKind::Theme, None)) {
+                self.theme = incoming.theme;
+            }
+        } else if incoming.theme.is_some() {
+            self.theme = incoming.theme;
+        }
+        if is_conflict(&self.keymap, &incoming.keymap) {
+            if replace(&ConfigConflict::new(ConfigKind::Keymap, None)) {
+                self.keymap = incoming.keymap;
+            }
+        } else if incoming.keymap.is_some() {
+            self.keymap = incoming.keymap;
+        }
+        merge_map(
+            ConfigKind::Layout,
+            &mut self.layouts,
+            incoming.layouts,
+            &replace,
+        );
+        merge_map(
+            ConfigKind::Table,
+            &mut self.tables,
+            incoming.tables,
+            &replace,
+        );
+        merge_map(
+            ConfigKind::Setting,
+            &mut self.settings,
+            incoming.settings,
+            &replace,
+        );
+    }
+
+    /// Replace the configurations of the active profile with this bundle, and apply the theme and the keymap.
+    ///
+    /// The DockAreas and the tables should observe the [`Profiles`] global to load the states.
+    pub fn apply(self, cx: &mut AppContext) -> Result<()> {
+        self.validate(cx)?;
+        Profiles::update_active(cx, |profile| {
+            if let Some(theme) = self.theme {
+                profile.theme = theme;
+            }
+            profile.keymap = self.keymap;
+            profile.layouts = self.layouts;
+            profile.tables = self.tables;
+            profile.state = self.settings;
+        });
+
+        let profile = Profiles::global(cx).active().clone();
+        Profiles::apply(&profile, cx)
+    }
+}
+
+fn check_version(version: u32) -> Result<()> {
+    if version == 0 || version > BUNDLE_VERSION {
+        return Err(anyhow!(
+            "unsupported configuration bundle version {}, expected 1 to {}",
+            version,
+            BUNDLE_VERSION
+        ));
+    }
+    Ok(())
+}
+
+fn is_conflict<V: PartialEq>(current: &Option<V>, incoming: &Option<V>) -> bool {
+    matches!((current, incoming), (Some(current), Some(incoming)) if current != incoming)
+}
+
+fn map_conflicts<V: PartialEq>(
+    kind: ConfigKind,
+    current: &BTreeMap<String, V>,
+    incoming: &BTreeMap<String, V>,
+    conflicts: &mut Vec<ConfigConflict>,
+) {
+    conflicts.extend(
+        incoming
+            .iter()
+            .filter(|(key, value)| current.get(*key).is_some_and(|current| current != *value))
+            .map(|(key, _)| ConfigConflict::new(kind, Some(key))),
+    );
+}
+
+fn merge_map<V: PartialEq>(
+    kind: ConfigKind,
+    current: &mut BTreeMap<String, V>,
+    incoming: BTreeMap<String, V>,
+    replace: &impl Fn(&ConfigConflict) -> bool,
+) {
+    for (key, value) in incoming {
+        let is_new = match current.get(&key) {
+            Some(current) => current != &value && replace(&ConfigConflict::new(kind, Some(&key))),
+            None => true,
+        };
+        if is_new {
+            current.insert(key, value);
+        }
+    }
+}
+
+/// Import the bundle from the file into the active profile.
+///
+/// If there are conflicts with the current configuration, a dialog is opened to choose the ones
+/// to replace, the `on_imported` will be called after the bundle is applied.
+///
+/// Returns error if the file is not a valid bundle.
+pub fn import_bundle(
+    path: impl AsRef<Path>,
+    on_imported: impl Fn(&mut WindowContext) + 'static,
+    cx: &mut WindowContext,
+) -> Result<()> {
+    let incoming = ConfigBundle::read(path, cx)?;
+    let current = ConfigBundle::capture(cx);
+    let conflicts = current.conflicts(&incoming);
+
+    if conflicts.is_empty() {
+        let mut bundle = current;
+        bundle.merge(incoming, |_| false);
+        bundle.apply(cx)?;
+        on_imported(cx);
+        return Ok(());
+    }
+
+    confirm_conflicts(current, incoming, conflicts, on_imported, cx);
+    Ok(())
+}
+
+/// Open a modal to list the conflicts, the checked ones are replaced by the imported.
+fn confirm_conflicts(
+    current: ConfigBundle,
+    incoming: ConfigBundle,
+    conflicts: Vec<ConfigConflict>,
+    on_imported: impl Fn(&mut WindowContext) + 'static,
+    cx: &mut WindowContext,
+) {
+    let selected = Rc::new(RefCell::new(vec![true; conflicts.len()]));
+    let on_imported = Rc::new(on_imported);
+
+    cx.open_modal(move |modal, cx| {
+        let all_selected = selected.borrow().iter().all(|checked| *checked);
+
+        modal
+            .width(px(480.))
+            .title(t!("Bundle.Title").to_string())
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(t!("Bundle.Message").to_string())
+                    .child(
+                        Checkbox::new("bundle-select-all")
+                            .label(t!("Bundle.Select All").to_string())
+                            .checked(all_selected)
+                            .on_click({
+                                let selected = selected.clone();
+                                move |checked, cx| {
+                                    selected.borrow_mut().fill(*checked);
+                                    cx.refresh();
+                                }
+                            }),
+                    )
+                    .child(
+                        v_flex()
+                            .gap_1()
+                            .pl_4()
+                            .text_color(cx.theme().muted_foreground)
+                            .children(conflicts.iter().enumerate().map(|(ix, conflict)| {
+                                Checkbox::new(("bundle-conflict", ix))
+                                    .label(conflict.label())
+                                    .checked(selected.borrow()[ix])
+                                    .on_click({
+                                        let selected = selected.clone();
+                                        move |checked, cx| {
+                                            selected.borrow_mut()[ix] = *checked;
+                                            cx.refresh();
+                                        }
+                                    })
+                            })),
+                    ),
+            )
+            .footer(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("bundle-cancel")
+                            .label(t!("Bundle.Cancel").to_string())
+                            .on_click(|_, cx| cx.close_modal()),
+                    )
+                    .child(
+                        Button::new("bundle-import")
+                            .primary()
+                            .label(t!("Bundle.Import").to_string())
+                            .on_click({
+                                let current = current.clone();
+                                let incoming = incoming.clone();
+                                let conflicts = conflicts.clone();
+                                let selected = selected.clone();
+                                let on_imported = on_imported.clone();
+                                move |_, cx| {
+                                    let selected = selected.borrow();
+                                    let mut bundle = current.clone();
+                                    bundle.merge(incoming.clone(), |conflict| {
+                                        conflicts
+                                            .iter()
+                                            .position(|it| it == conflict)
+                                            .is_some_and(|ix| selected[ix])
+                                    });
+
+                                    match bundle.apply(cx) {
+                                        Ok(()) => {
+                                            cx.close_modal();
+                                            on_imported(cx);
+                                        }
+                                        Err(err) => {
+                                            cx.push_notification(Notification::error(
+                                                err.to_string(),
+                                            ));
+                                        }
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{check_version, ConfigBundle, ConfigConflict, ConfigKind, BUNDLE_VERSION};
+    use crate::table::TableState;
+
+    #[test]
+    fn test_check_version() {
+        assert!(check_version(BUNDLE_VERSION).is_ok());
+        assert!(check_version(0).is_err());
+        assert!(check_version(BUNDLE_VERSION + 1).is_err());
+        assert!(serde_json::from_str::<ConfigBundle>("{}").is_err());
+    }
+
+    #[test]
+    fn test_conflicts_and_merge() {
+        let mut current = ConfigBundle::default();
+        current.keymap = Some("[]".into());
+        current.settings.insert("font".into(), json!("Inter"));
+        current.settings.insert("zoom".into(), json!(1.0));
+        current
+            .tables
+            .insert("orders".into(), TableState::default());
+
+        let mut incoming = ConfigBundle::default();
+        incoming.keymap = Some("{}".into());
+        incoming.settings.insert("font".into(), json!("Inter"));
+        incoming.settings.insert("zoom".into(), json!(1.5));
+        incoming.settings.insert("locale".into(), json!("en"));
+
+        let conflicts = current.conflicts(&incoming);
+        assert_eq!(
+            conflicts,
+            vec![
+                ConfigConflict::new(ConfigKind::Keymap, None),
+                ConfigConflict::new(ConfigKind::Setting, Some("zoom")),
+            ]
+        );
+
+        let mut merged = current.clone();
+        merged.merge(incoming.clone(), |conflict| {
+            conflict.kind == ConfigKind::Setting
+        });
+        assert_eq!(merged.keymap.as_deref(), Some("[]"));
+        assert_eq!(merged.settings["zoom"], json!(1.5));
+        assert_eq!(merged.settings["locale"], json!("en"));
+        assert!(merged.tables.contains_key("orders"));
+
+        let json = serde_json::to_string(&merged).unwrap();
+        assert_eq!(serde_json::from_str::<ConfigBundle>(&json).unwrap(), merged);
+    }
+}
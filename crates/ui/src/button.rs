@@ -3,12 +3,15 @@ use crate::{
     indicator::Indicator,
     theme::{ActiveTheme, Colorize as _},
     tooltip::Tooltip,
-    Disableable, Icon, Selectable, Sizable, Size,
+    AutomationExt as _, AutomationId, Disableable, Icon, Selectable, Sizable, Size, StyledExt as _,
 };
+use std::rc::Rc;
+
 use gpui::{
-    div, prelude::FluentBuilder as _, px, relative, AnyElement, ClickEvent, Corners, Div, Edges,
-    ElementId, Hsla, InteractiveElement, IntoElement, MouseButton, ParentElement, Pixels,
-    RenderOnce, SharedString, StatefulInteractiveElement as _, Styled, WindowContext,
+    div, prelude::FluentBuilder as _, px, relative, Action, AnyElement, ClickEvent, Corners, Div,
+    Edges, ElementId, FocusHandle, Hsla, InteractiveElement, IntoElement, MouseButton,
+    ParentElement, Pixels, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled,
+    WindowContext,
 };
 
 pub enum ButtonRounded {
@@ -166,11 +169,15 @@ pub struct Button {
     border_edges: Edges<bool>,
     size: Size,
     compact: bool,
+    icon_only: bool,
     tooltip: Option<SharedString>,
+    tooltip_action: Option<Rc<dyn Action>>,
+    action: Option<Rc<dyn Action>>,
     on_click: Option<Box<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>>,
     pub(crate) stop_propagation: bool,
     loading: bool,
     loading_icon: Option<Icon>,
+    focus_handle: Option<FocusHandle>,
 }
 
 impl From<Button> for AnyElement {
@@ -194,12 +201,16 @@ impl Button {
             border_edges: Edges::all(true),
             size: Size::Medium,
             tooltip: None,
+            tooltip_action: None,
+            action: None,
             on_click: None,
             stop_propagation: true,
             loading: false,
             compact: false,
+            icon_only: false,
             children: Vec::new(),
             loading_icon: None,
+            focus_handle: None,
         }
     }
 
@@ -233,12 +244,32 @@ impl Button {
         self
     }
 
+    /// Set to show the icon only, default is false.
+    ///
+    /// The label is hidden and used as the tooltip if no tooltip is set,
+    /// so the Icon Button is still described to the user.
+    pub fn icon_only(mut self, icon_only: bool) -> Self {
+        self.icon_only = icon_only;
+        self
+    }
+
     /// Set the tooltip of the button.
     pub fn tooltip(mut self, tooltip: impl Into<SharedString>) -> Self {
         self.tooltip = Some(tooltip.into());
         self
     }
 
+    /// Set the tooltip of the button, with the current keystrokes of the action.
+    pub fn tooltip_with_action(
+        mut self,
+        tooltip: impl Into<SharedString>,
+        action: &dyn Action,
+    ) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self.tooltip_action = Some(Rc::from(action.boxed_clone()));
+        self
+    }
+
     /// Set true to show the loading indicator.
     pub fn loading(mut self, loading: bool) -> Self {
         self.loading = loading;
@@ -251,6 +282,15 @@ impl Button {
         self
     }
 
+    /// Bind an action to the button, the action will be dispatched on click if there is no `on_click`.
+    ///
+    /// The button will be disabled automatically when the action is not available in the current focus,
+    /// e.g.: the focused view has no listener of the action.
+    pub fn action(mut self, action: Box<dyn Action>) -> Self {
+        self.action = Some(Rc::from(action));
+        self
+    }
+
     pub fn on_click(mut self, handler: impl Fn(&ClickEvent, &mut WindowContext) + 'static) -> Self {
         self.on_click = Some(Box::new(handler));
         self
@@ -265,6 +305,19 @@ impl Button {
         self.loading_icon = Some(icon.into());
         self
     }
+
+    /// Track the focus of the button, the focus ring will be shown when the button is focused.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+}
+
+impl AutomationId for Button {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.base = self.base.when_automation_id(Some(&id.into()));
+        self
+    }
 }
 
 impl Disableable for Button {
@@ -318,22 +371,45 @@ impl InteractiveElement for Button {
 }
 
 impl RenderOnce for Button {
-    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+    fn render(mut self, cx: &mut WindowContext) -> impl IntoElement {
+        if let Some(action) = self.action.clone() {
+            if !cx.is_action_available(action.as_ref()) {
+                self.disabled = true;
+            }
+
+            if self.on_click.is_none() {
+                self.on_click = Some(Box::new(move |_, cx| {
+                    cx.dispatch_action(action.boxed_clone());
+                }));
+            }
+        }
+
+        if self.icon_only && self.icon.is_some() {
+            if let Some(label) = self.label.take() {
+                self.tooltip.get_or_insert(label);
+            }
+        }
+
         let style: ButtonVariant = self.variant;
         let normal_style = style.normal(cx);
         let icon_size = match self.size {
             Size::Size(v) => Size::Size(v * 0.75),
             _ => self.size,
         };
+        let focus_handle = self.focus_handle.clone();
+        let is_focused = focus_handle
+            .as_ref()
+            .map_or(false, |handle| handle.is_focused(cx));
 
-        self.base
+        let button = self
+            .base
             .id(self.id)
             .flex()
             .items_center()
             .justify_center()
             .cursor_pointer()
             .overflow_hidden()
-            .when(cx.theme().shadow && normal_style.shadow, |this| {
+            .when(cx.theme().has_shadow() && normal_style.shadow, |this| {
                 this.shadow_sm()
             })
             .when(!style.no_padding(), |this| {
@@ -344,6 +420,7 @@ impl RenderOnce for Button {
                         Size::XSmall => this.size_5(),
                         Size::Small => this.size_6(),
                         Size::Large | Size::Medium => this.size_8(),
+                        Size::XLarge => this.size_12(),
                     }
                 } else {
                     // Normal Button
@@ -351,7 +428,10 @@ impl RenderOnce for Button {
                         Size::Size(size) => this.px(size * 0.2),
                         Size::XSmall => this.h_5().px_1(),
                         Size::Small => this.h_6().px_3().when(self.compact, |this| this.px_1p5()),
-                        _ => this.h_8().px_4().when(self.compact, |this| this.px_2()),
+                        Size::Large | Size::Medium => {
+                            this.h_8().px_4().when(self.compact, |this| this.px_2())
+                        }
+                        Size::XLarge => this.h_12().px_6().when(self.compact, |this| this.px_3()),
                     }
                 }
             })
@@ -390,11 +470,13 @@ impl RenderOnce for Button {
                 this.border_color(normal_style.border)
                     .bg(normal_style.bg)
                     .when(normal_style.underline, |this| this.text_decoration_1())
-                    .hover(|this| {
-                        let hover_style = style.hovered(cx);
-                        this.bg(hover_style.bg)
-                            .border_color(hover_style.border)
-                            .text_color(crate::red_400())
+                    .when(!cx.theme().lite, |this| {
+                        this.hover(|this| {
+                            let hover_style = style.hovered(cx);
+                            this.bg(hover_style.bg)
+                                .border_color(hover_style.border)
+                                .text_color(crate::red_400())
+                        })
                     })
                     .active(|this| {
                         let active_style = style.active(cx);
@@ -434,6 +516,7 @@ impl RenderOnce for Button {
                     .map(|this| match self.size {
                         Size::XSmall => this.gap_1().text_xs(),
                         Size::Small => this.gap_1().text_sm(),
+                        Size::XLarge => this.gap_2().text_lg(),
                         _ => this.gap_2().text_base(),
                     })
                     .when(!self.loading, |this| {
@@ -455,8 +538,20 @@ impl RenderOnce for Button {
             })
             .when(self.loading, |this| this.bg(normal_style.bg.opacity(0.8)))
             .when_some(self.tooltip.clone(), |this, tooltip| {
-                this.tooltip(move |cx| Tooltip::new(tooltip.clone(), cx))
+                let action = self.tooltip_action.clone();
+                this.tooltip(move |cx| match action.as_ref() {
+                    Some(action) => {
+                        Tooltip::with_action(tooltip.clone(), action.as_ref(), None, cx)
+                    }
+                    None => Tooltip::new(tooltip.clone(), cx),
+                })
             })
+            .focus_ring(is_focused, cx);
+
+        match focus_handle {
+            Some(focus_handle) => button.track_focus(&focus_handle).into_any_element(),
+            None => button.into_any_element(),
+        }
     }
 }
 
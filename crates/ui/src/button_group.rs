@@ -1,12 +1,12 @@
 use gpui::{
-    div, prelude::FluentBuilder as _, Corners, Div, Edges, ElementId, InteractiveElement,
+    div, prelude::FluentBuilder as _, Axis, Corners, Div, Edges, ElementId, InteractiveElement,
     IntoElement, ParentElement, RenderOnce, StatefulInteractiveElement as _, Styled, WindowContext,
 };
 use std::{cell::Cell, rc::Rc};
 
 use crate::{
     button::{Button, ButtonVariant, ButtonVariants},
-    Disableable, Sizable, Size,
+    Disableable, RovingFocus, Sizable, Size,
 };
 
 /// A ButtonGroup element, to wrap multiple buttons in a group.
@@ -22,6 +22,7 @@ pub struct ButtonGroup {
     compact: Option<bool>,
     variant: Option<ButtonVariant>,
     size: Option<Size>,
+    roving_focus: Option<RovingFocus>,
 
     on_click: Option<Box<dyn Fn(&Vec<usize>, &mut WindowContext) + 'static>>,
 }
@@ -45,6 +46,7 @@ impl ButtonGroup {
             compact: None,
             multiple: false,
             disabled: false,
+            roving_focus: None,
             on_click: None,
         }
     }
@@ -67,6 +69,12 @@ impl ButtonGroup {
         self
     }
 
+    /// Use the arrow keys to move focus between the buttons, and `tab` to leave the group.
+    pub fn roving_focus(mut self, roving_focus: &RovingFocus) -> Self {
+        self.roving_focus = Some(roving_focus.clone());
+        self
+    }
+
     /// Sets the on_click handler for the ButtonGroup.
     ///
     /// The handler first argument is a vector of the selected button indices.
@@ -97,8 +105,12 @@ impl ButtonVariants for ButtonGroup {
 }
 
 impl RenderOnce for ButtonGroup {
-    fn render(self, _cx: &mut WindowContext) -> impl IntoElement {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
         let children_len = self.children.len();
+        let roving_focus = self.roving_focus.clone();
+        if let Some(roving_focus) = roving_focus.as_ref() {
+            roving_focus.truncate(children_len);
+        }
         let mut selected_ixs: Vec<usize> = Vec::new();
         let state = Rc::new(Cell::new(None));
 
@@ -165,6 +177,9 @@ impl RenderOnce for ButtonGroup {
                         .when_some(self.size, |this, size| this.with_size(size))
                         .when_some(self.variant, |this, variant| this.with_variant(variant))
                         .when_some(self.compact, |this, _| this.compact())
+                        .when_some(roving_focus.as_ref(), |this, roving_focus| {
+                            this.track_focus(&roving_focus.focus_handle(child_index, cx))
+                        })
                         .on_click(move |_, _| {
                             state.set(Some(child_index));
                         });
@@ -172,6 +187,9 @@ impl RenderOnce for ButtonGroup {
                         child
                     }),
             )
+            .when_some(roving_focus, |this, roving_focus| {
+                this.on_key_down(roving_focus.on_key_down(Axis::Horizontal))
+            })
             .when_some(
                 self.on_click.filter(|_| !self.disabled),
                 move |this, on_click| {
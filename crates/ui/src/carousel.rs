@@ -0,0 +1,396 @@
+use std::time::Duration;
+
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, Animation, AnyView, Bounds, DragMoveEvent,
+    ElementId, EntityId, EventEmitter, InteractiveElement as _, IntoElement, MouseButton,
+    ParentElement as _, Pixels, Point, Render, StatefulInteractiveElement as _, Styled, Task,
+    ViewContext, VisualContext as _,
+};
+use smol::Timer;
+
+use crate::{
+    animation::{cubic_bezier, TransitionExt as _},
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    theme::ActiveTheme as _,
+    Disableable as _, IconName, Sizable as _,
+};
+
+/// The ratio of the width to drag for swiping to the next item.
+const SWIPE_THRESHOLD: f32 = 0.2;
+
+#[derive(Clone, Render)]
+pub struct DragCarousel(EntityId);
+
+pub enum CarouselEvent {
+    /// The active item has been changed, with the index of the item.
+    Change(usize),
+}
+
+/// Returns the index after moving by `delta`, wraps around if `looped`, otherwise stops at the edges.
+fn step_index(ix: usize, len: usize, delta: isize, looped: bool) -> usize {
+    if len == 0 {
+        return 0;
+    }
+
+    let ix = ix as isize + delta;
+    if looped {
+        ix.rem_euclid(len as isize) as usize
+    } else {
+        ix.clamp(0, len as isize - 1) as usize
+    }
+}
+
+/// Returns the moving direction of the swipe, dragging to right (positive offset) goes to the previous item.
+fn swipe_delta(offset: f32, width: f32) -> isize {
+    if width <= 0. || offset.abs() < width * SWIPE_THRESHOLD {
+        0
+    } else if offset > 0. {
+        -1
+    } else {
+        1
+    }
+}
+
+/// A Carousel to display the views one by one, with the swipe (drag) navigation,
+/// previous / next buttons, dot indicators and the optional autoplay.
+///
+/// ```ignore
+/// let carousel = cx.new_view(|_| {
+///     Carousel::new()
+///         .children([welcome_view, features_view, finish_view])
+///         .autoplay(Duration::from_secs(5))
+/// });
+/// cx.subscribe(&carousel, |_, _, event: &CarouselEvent, _| match event {
+///     CarouselEvent::Change(ix) => println!("Slide to {}", ix),
+/// });
+/// ```
+pub struct Carousel {
+    items: Vec<AnyView>,
+    active_ix: usize,
+    looped: bool,
+    autoplay: Option<Duration>,
+    bounds: Bounds<Pixels>,
+    hovered: bool,
+    /// The mouse down position of the swipe, `None` if not dragging.
+    dragging: Option<Point<Pixels>>,
+    /// The horizontal distance dragged from the `dragging` position.
+    drag_offset: Pixels,
+    /// The position of the track when the active item changed, to animate from.
+    slide_from: Option<Pixels>,
+    /// The number of the changes, used as the id of the slide animation.
+    slides: usize,
+    _autoplay_task: Option<Task<()>>,
+}
+
+impl Carousel {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            active_ix: 0,
+            looped: false,
+            autoplay: None,
+            bounds: Bounds::default(),
+            hovered: false,
+            dragging: None,
+            drag_offset: px(0.),
+            slide_from: None,
+            slides: 0,
+            _autoplay_task: None,
+        }
+    }
+
+    /// Add a view to the Carousel.
+    pub fn child(mut self, view: impl Into<AnyView>) -> Self {
+        self.items.push(view.into());
+        self
+    }
+
+    /// Add the views to the Carousel.
+    pub fn children(mut self, views: impl IntoIterator<Item = impl Into<AnyView>>) -> Self {
+        self.items.extend(views.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set to go back to the first item after the last one, default: false
+    pub fn looped(mut self, looped: bool) -> Self {
+        self.looped = looped;
+        self
+    }
+
+    /// Set the interval to switch to the next item automatically,
+    /// the autoplay is paused when the mouse hovers the Carousel.
+    pub fn autoplay(mut self, interval: Duration) -> Self {
+        self.autoplay = Some(interval);
+        self
+    }
+
+    /// Set the autoplay interval, `None` to stop the autoplay.
+    pub fn set_autoplay(&mut self, interval: Option<Duration>, cx: &mut ViewContext<Self>) {
+        self.autoplay = interval;
+        self.start_autoplay(cx);
+    }
+
+    /// Returns the number of the items.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true if there is no item.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns the index of the active item.
+    pub fn active_index(&self) -> usize {
+        self.active_ix
+    }
+
+    /// Slide to the item at the index.
+    pub fn set_active_index(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix >= self.items.len() {
+            return;
+        }
+
+        self.slide_to(ix, cx);
+        // Restart the autoplay timer after the manual navigation.
+        self.start_autoplay(cx);
+    }
+
+    /// Slide to the previous item.
+    pub fn prev(&mut self, cx: &mut ViewContext<Self>) {
+        let ix = step_index(self.active_ix, self.items.len(), -1, self.looped);
+        self.set_active_index(ix, cx);
+    }
+
+    /// Slide to the next item.
+    pub fn next(&mut self, cx: &mut ViewContext<Self>) {
+        let ix = step_index(self.active_ix, self.items.len(), 1, self.looped);
+        self.set_active_index(ix, cx);
+    }
+
+    fn track_left(&self) -> Pixels {
+        let offset = self.dragging.map_or(px(0.), |_| self.drag_offset);
+        -(self.bounds.size.width * self.active_ix as f32) + offset
+    }
+
+    fn slide_to(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let changed = ix != self.active_ix;
+
+        self.slide_from = Some(self.track_left());
+        self.slides += 1;
+        self.active_ix = ix;
+        self.dragging = None;
+        self.drag_offset = px(0.);
+        if changed {
+            cx.emit(CarouselEvent::Change(ix));
+        }
+        cx.notify();
+    }
+
+    fn start_autoplay(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(interval) = self.autoplay else {
+            self._autoplay_task = None;
+            return;
+        };
+
+        self._autoplay_task = Some(cx.spawn(|view, mut cx| async move {
+            loop {
+                Timer::after(interval).await;
+                let result = view.update(&mut cx, |this, cx| {
+                    if this.hovered || this.dragging.is_some() {
+                        return;
+                    }
+
+                    // The autoplay always goes back to the first item after the last one.
+                    let ix = step_index(this.active_ix, this.items.len(), 1, true);
+                    this.slide_to(ix, cx);
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// End the swipe, the mouse up without dragging (e.g.: click the buttons) is ignored.
+    fn on_drag_end(&mut self, cx: &mut ViewContext<Self>) {
+        if self.dragging.is_none() {
+            return;
+        }
+
+        let delta = swipe_delta(self.drag_offset.0, self.bounds.size.width.0);
+        let ix = step_index(self.active_ix, self.items.len(), delta, self.looped);
+        self.set_active_index(ix, cx);
+    }
+
+    fn render_track(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let width = self.bounds.size.width;
+        let target = -(width * self.active_ix as f32);
+
+        let track = h_flex().absolute().top_0().h_full().children(
+            self.items
+                .iter()
+                .map(|item| div().flex_shrink_0().w(width).h_full().child(item.clone())),
+        );
+
+        match (self.dragging, self.slide_from) {
+            (Some(_), _) => track.left(target + self.drag_offset).into_any_element(),
+            (None, Some(from)) => track.with_transition(
+                ElementId::NamedInteger("slide".into(), self.slides),
+                Animation::new(Duration::from_secs_f64(0.3))
+                    .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                move |this, delta| this.left(from + (target - from) * delta),
+                cx,
+            ),
+            (None, None) => track.left(target).into_any_element(),
+        }
+    }
+
+    fn render_indicators(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .absolute()
+            .bottom_2()
+            .left_0()
+            .right_0()
+            .justify_center()
+            .gap_1p5()
+            .children((0..self.items.len()).map(|ix| {
+                let active = ix == self.active_ix;
+
+                div()
+                    .id(("indicator", ix))
+                    .h(px(6.))
+                    .w(if active { px(16.) } else { px(6.) })
+                    .rounded_full()
+                    .cursor_pointer()
+                    .bg(cx.theme().primary.opacity(if active { 1. } else { 0.3 }))
+                    .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+                    .on_click(cx.listener(move |this, _, cx| this.set_active_index(ix, cx)))
+            }))
+    }
+}
+
+impl EventEmitter<CarouselEvent> for Carousel {}
+
+impl Render for Carousel {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        if self.autoplay.is_some() && self._autoplay_task.is_none() {
+            self.start_autoplay(cx);
+        }
+
+        let view = cx.view().clone();
+        let entity_id = cx.entity_id();
+        let len = self.items.len();
+        let has_prev = self.looped || self.active_ix > 0;
+        let has_next = self.looped || self.active_ix + 1 < len;
+
+        div()
+            .id("carousel")
+            .group("carousel")
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .on_hover(cx.listener(|this, hovered: &bool, _| this.hovered = *hovered))
+            .on_drag(DragCarousel(entity_id), {
+                let view = view.clone();
+                // Called after the mouse moved enough, with the mouse down position in the Carousel.
+                move |drag, position, cx| {
+                    view.update(cx, |this, _| {
+                        this.dragging = Some(this.bounds.origin + position);
+                        this.drag_offset = px(0.);
+                    });
+                    cx.stop_propagation();
+                    cx.new_view(|_| drag.clone())
+                }
+            })
+            .on_drag_move(
+                cx.listener(move |this, e: &DragMoveEvent<DragCarousel>, cx| {
+                    if e.drag(cx).0 != entity_id {
+                        return;
+                    }
+
+                    if let Some(start) = this.dragging {
+                        this.drag_offset = e.event.position.x - start.x;
+                        cx.notify();
+                    }
+                }),
+            )
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _, cx| this.on_drag_end(cx)),
+            )
+            .on_mouse_up_out(
+                MouseButton::Left,
+                cx.listener(|this, _, cx| this.on_drag_end(cx)),
+            )
+            .child(self.render_track(cx))
+            .when(len > 1, |this| {
+                this.child(
+                    Button::new("prev")
+                        .icon(IconName::ChevronLeft)
+                        .ghost()
+                        .small()
+                        .absolute()
+                        .left_2()
+                        .top_1_2()
+                        .mt(px(-12.))
+                        .disabled(!has_prev)
+                        .on_click(cx.listener(|this, _, cx| this.prev(cx))),
+                )
+                .child(
+                    Button::new("next")
+                        .icon(IconName::ChevronRight)
+                        .ghost()
+                        .small()
+                        .absolute()
+                        .right_2()
+                        .top_1_2()
+                        .mt(px(-12.))
+                        .disabled(!has_next)
+                        .on_click(cx.listener(|this, _, cx| this.next(cx))),
+                )
+                .child(self.render_indicators(cx))
+            })
+            .child({
+                // Render again when resized, the track and the swipe depend on the width.
+                canvas(
+                    move |bounds, cx| {
+                        view.update(cx, |r, cx| {
+                            if r.bounds != bounds {
+                                r.bounds = bounds;
+                                cx.notify();
+                            }
+                        })
+                    },
+                    |_, _, _| {},
+                )
+                .absolute()
+                .size_full()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{step_index, swipe_delta};
+
+    #[test]
+    fn test_step_index() {
+        assert_eq!(step_index(0, 3, 1, false), 1);
+        assert_eq!(step_index(2, 3, 1, false), 2);
+        assert_eq!(step_index(0, 3, -1, false), 0);
+        assert_eq!(step_index(2, 3, 1, true), 0);
+        assert_eq!(step_index(0, 3, -1, true), 2);
+        assert_eq!(step_index(0, 0, 1, true), 0);
+    }
+
+    #[test]
+    fn test_swipe_delta() {
+        assert_eq!(swipe_delta(10., 400.), 0);
+        assert_eq!(swipe_delta(-79., 400.), 0);
+        assert_eq!(swipe_delta(100., 400.), -1);
+        assert_eq!(swipe_delta(-100., 400.), 1);
+        assert_eq!(swipe_delta(100., 0.), 0);
+    }
+}
@@ -0,0 +1,797 @@
+use gpui::{
+    actions, canvas, deferred, div, prelude::FluentBuilder, px, rems, AppContext, Bounds,
+    ClickEvent, Div, ElementId, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
+    IntoElement, Length, ParentElement, Pixels, Render, SharedString, Stateful,
+    StatefulInteractiveElement, Styled, Subscription, View, ViewContext, VisualContext,
+    WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{
+    h_flex,
+    input::{ClearButton, InputEvent, TextInput},
+    keymap::{self, KeymapEntry},
+    matcher::match_text,
+    primitives::placement::{anchored_to, Align, FloatingPlacement, Side},
+    theme::ActiveTheme,
+    v_flex, Disableable, Icon, IconName, Sizable, Size, StyleSized, StyledExt,
+};
+
+actions!(cascader, [Up, Down, Left, Right, Enter, Escape]);
+
+const CONTEXT: &str = "Cascader";
+/// The separator between the labels of the levels, e.g.: `Asia / China / Beijing`.
+const SEPARATOR: &str = " / ";
+
+pub fn init(cx: &mut AppContext) {
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("up", Up, Some(CONTEXT)),
+            KeymapEntry::new("down", Down, Some(CONTEXT)),
+            KeymapEntry::new("left", Left, Some(CONTEXT)),
+            KeymapEntry::new("right", Right, Some(CONTEXT)),
+            KeymapEntry::new("enter", Enter, Some(CONTEXT)),
+            KeymapEntry::new("escape", Escape, Some(CONTEXT)),
+        ],
+    )
+}
+
+/// An option of the [`Cascader`], the options with children are the parent levels.
+#[derive(Debug, Clone)]
+pub struct CascaderOption {
+    pub value: SharedString,
+    pub label: SharedString,
+    pub children: Vec<CascaderOption>,
+}
+
+impl CascaderOption {
+    pub fn new(value: impl Into<SharedString>, label: impl Into<SharedString>) -> Self {
+        Self {
+            value: value.into(),
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    /// Set the children options of the next level.
+    pub fn children(mut self, children: impl IntoIterator<Item = CascaderOption>) -> Self {
+        self.children = children.into_iter().collect();
+        self
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+/// Returns the option at the `path` of the indices, one index per level.
+fn option_at<'a>(options: &'a [CascaderOption], path: &[usize]) -> Option<&'a CascaderOption> {
+    let (first, rest) = path.split_first()?;
+    let option = options.get(*first)?;
+    if rest.is_empty() {
+        Some(option)
+    } else {
+        option_at(&option.children, rest)
+    }
+}
+
+/// Returns the options of the columns to display for the `path`, the first column is the root options,
+/// and each option in the path with children opens the next column.
+fn columns<'a>(options: &'a [CascaderOption], path: &[usize]) -> Vec<&'a [CascaderOption]> {
+    let mut columns = vec![options];
+    let mut current = options;
+    for ix in path {
+        match current.get(*ix) {
+            Some(option) if !option.is_leaf() => {
+                current = &option.children;
+                columns.push(current);
+            }
+            _ => break,
+        }
+    }
+    columns
+}
+
+/// Returns the paths of all the leaf options, in the display order.
+fn leaf_paths(options: &[CascaderOption]) -> Vec<Vec<usize>> {
+    let mut paths = vec![];
+    for (ix, option) in options.iter().enumerate() {
+        if option.is_leaf() {
+            paths.push(vec![ix]);
+        } else {
+            for mut path in leaf_paths(&option.children) {
+                path.insert(0, ix);
+                paths.push(path);
+            }
+        }
+    }
+    paths
+}
+
+/// Returns the labels of the options at the `path` joined by the separator.
+fn path_title(options: &[CascaderOption], path: &[usize]) -> String {
+    (1..=path.len())
+        .map_while(|len| option_at(options, &path[..len]).map(|option| &*option.label))
+        .collect::<Vec<_>>()
+        .join(SEPARATOR)
+}
+
+/// Returns the leaf paths that the joined labels match the `query`.
+fn search_paths(options: &[CascaderOption], query: &str) -> Vec<Vec<usize>> {
+    leaf_paths(options)
+        .into_iter()
+        .filter(|path| match_text(&path_title(options, path), query).is_some())
+        .collect()
+}
+
+/// Returns the index after moving by `delta` in `len` items, stops at the edges.
+fn step(ix: usize, len: usize, delta: isize) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    (ix as isize + delta).clamp(0, len as isize - 1) as usize
+}
+
+pub enum CascaderEvent {
+    /// The selection has been changed, with the values of the selected path, empty if cleared.
+    Change(Vec<SharedString>),
+}
+
+/// A Cascader to pick an option from the hierarchical options, e.g.: region > country > city.
+///
+/// Selecting a parent option opens a column of its children, only the leaf options can be selected
+/// by default, use [`Cascader::change_on_select`] to allow selecting any level.
+///
+/// ```ignore
+/// let cascader = cx.new_view(|cx| {
+///     Cascader::new(
+///         "location",
+///         vec![CascaderOption::new("asia", "Asia").children([
+///             CascaderOption::new("cn", "China").children([
+///                 CascaderOption::new("bj", "Beijing"),
+///                 CascaderOption::new("sh", "Shanghai"),
+///             ]),
+///         ])],
+///         cx,
+///     )
+///     .searchable(true, cx)
+/// });
+/// ```
+pub struct Cascader {
+    id: ElementId,
+    focus_handle: FocusHandle,
+    options: Vec<CascaderOption>,
+    selected_path: Vec<usize>,
+    /// The highlighted option of each column while the menu is open.
+    active_path: Vec<usize>,
+    search_input: Option<View<TextInput>>,
+    query: SharedString,
+    matches: Vec<Vec<usize>>,
+    match_ix: usize,
+    change_on_select: bool,
+    placeholder: Option<SharedString>,
+    size: Size,
+    width: Length,
+    open: bool,
+    cleanable: bool,
+    disabled: bool,
+    /// The bounds of the input to place the menu.
+    bounds: Bounds<Pixels>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl Cascader {
+    pub fn new(
+        id: impl Into<ElementId>,
+        options: Vec<CascaderOption>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+        let _subscriptions = vec![cx.on_blur(&focus_handle, Self::on_blur)];
+
+        Self {
+            id: id.into(),
+            focus_handle,
+            options,
+            selected_path: Vec::new(),
+            active_path: Vec::new(),
+            search_input: None,
+            query: SharedString::default(),
+            matches: Vec::new(),
+            match_ix: 0,
+            change_on_select: false,
+            placeholder: None,
+            size: Size::Medium,
+            width: Length::Auto,
+            open: false,
+            cleanable: false,
+            disabled: false,
+            bounds: Bounds::default(),
+            _subscriptions,
+        }
+    }
+
+    /// Set to search the options by the labels of the leaf paths, default: false
+    pub fn searchable(mut self, searchable: bool, cx: &mut ViewContext<Self>) -> Self {
+        if !searchable {
+            self.search_input = None;
+            return self;
+        }
+
+        let input = cx.new_view(|cx| {
+            TextInput::new(cx)
+                .appearance(false)
+                .placeholder(t!("Cascader.search_placeholder"))
+        });
+        self._subscriptions.push(cx.subscribe(
+            &input,
+            |this, _, event: &InputEvent, cx| match event {
+                InputEvent::Change(text) => this.search(text.clone(), cx),
+                InputEvent::PressEnter => this.enter(&Enter, cx),
+                InputEvent::Blur => this.on_blur(cx),
+                _ => {}
+            },
+        ));
+        self.search_input = Some(input);
+        self
+    }
+
+    /// Set to select the option of any level, default: false
+    ///
+    /// By default, only the leaf options can be selected, and the parent options just open the next column.
+    pub fn change_on_select(mut self, change_on_select: bool) -> Self {
+        self.change_on_select = change_on_select;
+        self
+    }
+
+    /// Set the placeholder for display when the selection is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<SharedString>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Set the width of the cascader input, default: Length::Auto
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    /// Set true to show the clear button when the selection is not empty.
+    pub fn cleanable(mut self) -> Self {
+        self.cleanable = true;
+        self
+    }
+
+    /// Set the disable state for the cascader.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+    }
+
+    /// Replace the options, the selection is cleared.
+    pub fn set_options(&mut self, options: Vec<CascaderOption>, cx: &mut ViewContext<Self>) {
+        self.options = options;
+        self.selected_path.clear();
+        self.active_path.clear();
+        self.matches.clear();
+        cx.notify();
+    }
+
+    /// Returns the indices of the selected options, one per level.
+    pub fn selected_path(&self) -> &[usize] {
+        &self.selected_path
+    }
+
+    /// Returns the values of the selected options, one per level.
+    pub fn selected_values(&self) -> Vec<SharedString> {
+        (1..=self.selected_path.len())
+            .filter_map(|len| option_at(&self.options, &self.selected_path[..len]))
+            .map(|option| option.value.clone())
+            .collect()
+    }
+
+    /// Select the options by the values of each level, the selection is cleared if not found.
+    pub fn set_selected_values(&mut self, values: &[SharedString], cx: &mut ViewContext<Self>) {
+        let mut path = Vec::with_capacity(values.len());
+        let mut options = self.options.as_slice();
+        for value in values {
+            let Some(ix) = options.iter().position(|option| &option.value == value) else {
+                path.clear();
+                break;
+            };
+            path.push(ix);
+            options = &options[ix].children;
+        }
+
+        self.selected_path = path;
+        cx.notify();
+    }
+
+    pub fn focus(&self, cx: &mut WindowContext) {
+        self.focus_handle.focus(cx);
+    }
+
+    fn is_searching(&self) -> bool {
+        self.search_input.is_some() && !self.query.is_empty()
+    }
+
+    fn set_open(&mut self, open: bool, cx: &mut ViewContext<Self>) {
+        self.open = open;
+        if open {
+            self.active_path = if self.selected_path.is_empty() {
+                vec![0]
+            } else {
+                self.selected_path.clone()
+            };
+            if let Some(input) = self.search_input.as_ref() {
+                input.update(cx, |input, cx| {
+                    input.set_text("", cx);
+                    input.focus(cx);
+                });
+            }
+        } else {
+            self.query = SharedString::default();
+            self.matches.clear();
+            self.focus(cx);
+        }
+        cx.notify();
+    }
+
+    fn on_blur(&mut self, cx: &mut ViewContext<Self>) {
+        let input_focused = self
+            .search_input
+            .as_ref()
+            .map_or(false, |input| input.focus_handle(cx).is_focused(cx));
+        if input_focused || self.focus_handle.is_focused(cx) || !self.open {
+            return;
+        }
+
+        self.open = false;
+        cx.notify();
+    }
+
+    fn search(&mut self, query: SharedString, cx: &mut ViewContext<Self>) {
+        self.matches = search_paths(&self.options, &query);
+        self.match_ix = 0;
+        self.query = query;
+        cx.notify();
+    }
+
+    fn select(&mut self, path: Vec<usize>, close: bool, cx: &mut ViewContext<Self>) {
+        self.selected_path = path;
+        cx.emit(CascaderEvent::Change(self.selected_values()));
+        if close {
+            self.set_open(false, cx);
+        }
+        cx.notify();
+    }
+
+    /// Activate the option in the column at `depth`, select it if it's a leaf.
+    fn activate(&mut self, depth: usize, ix: usize, cx: &mut ViewContext<Self>) {
+        self.active_path.truncate(depth);
+        self.active_path.push(ix);
+
+        let is_leaf = option_at(&self.options, &self.active_path).map_or(true, |o| o.is_leaf());
+        if is_leaf {
+            self.select(self.active_path.clone(), true, cx);
+        } else if self.change_on_select {
+            // Keep the menu open to continue to the next level.
+            self.select(self.active_path.clone(), false, cx);
+        }
+        cx.notify();
+    }
+
+    fn move_active(&mut self, delta: isize, cx: &mut ViewContext<Self>) {
+        if self.is_searching() {
+            self.match_ix = step(self.match_ix, self.matches.len(), delta);
+        } else if let Some(ix) = self.active_path.pop() {
+            let len = columns(&self.options, &self.active_path)
+                .last()
+                .map_or(0, |column| column.len());
+            self.active_path.push(step(ix, len, delta));
+        }
+        cx.notify();
+    }
+
+    fn up(&mut self, _: &Up, cx: &mut ViewContext<Self>) {
+        if !self.open {
+            return;
+        }
+        self.move_active(-1, cx);
+    }
+
+    fn down(&mut self, _: &Down, cx: &mut ViewContext<Self>) {
+        if !self.open {
+            self.set_open(true, cx);
+            return;
+        }
+        self.move_active(1, cx);
+    }
+
+    fn left(&mut self, _: &Left, cx: &mut ViewContext<Self>) {
+        if !self.open || self.is_searching() {
+            cx.propagate();
+            return;
+        }
+
+        if self.active_path.len() > 1 {
+            self.active_path.pop();
+            cx.notify();
+        }
+    }
+
+    fn right(&mut self, _: &Right, cx: &mut ViewContext<Self>) {
+        if !self.open || self.is_searching() {
+            cx.propagate();
+            return;
+        }
+
+        let has_children =
+            option_at(&self.options, &self.active_path).map_or(false, |option| !option.is_leaf());
+        if has_children {
+            self.active_path.push(0);
+            cx.notify();
+        }
+    }
+
+    fn enter(&mut self, _: &Enter, cx: &mut ViewContext<Self>) {
+        if !self.open {
+            // Propagate the event to the parent view, for example to the Modal to support ENTER to confirm.
+            cx.propagate();
+            self.set_open(true, cx);
+            return;
+        }
+
+        if self.is_searching() {
+            if let Some(path) = self.matches.get(self.match_ix).cloned() {
+                self.select(path, true, cx);
+            }
+            return;
+        }
+
+        let Some(option) = option_at(&self.options, &self.active_path) else {
+            return;
+        };
+        if option.is_leaf() || self.change_on_select {
+            self.select(self.active_path.clone(), true, cx);
+        } else {
+            self.active_path.push(0);
+            cx.notify();
+        }
+    }
+
+    fn escape(&mut self, _: &Escape, cx: &mut ViewContext<Self>) {
+        // Propagate the event to the parent view, for example to the Modal to support ESC to close.
+        cx.propagate();
+
+        if self.open {
+            self.set_open(false, cx);
+        }
+    }
+
+    fn toggle_menu(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        cx.stop_propagation();
+        self.set_open(!self.open, cx);
+    }
+
+    fn clean(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        cx.stop_propagation();
+        self.select(vec![], false, cx);
+    }
+
+    fn render_item(
+        &self,
+        id: impl Into<ElementId>,
+        label: impl IntoElement,
+        active: bool,
+        has_children: bool,
+        cx: &WindowContext,
+    ) -> Stateful<Div> {
+        h_flex()
+            .id(id)
+            .justify_between()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .rounded(px(cx.theme().radius))
+            .cursor_pointer()
+            .whitespace_nowrap()
+            .input_text_size(self.size)
+            .hover(|this| this.bg(cx.theme().accent.opacity(0.5)))
+            .when(active, |this| {
+                this.bg(cx.theme().accent)
+                    .text_color(cx.theme().accent_foreground)
+            })
+            .child(label)
+            .when(has_children, |this| {
+                this.child(
+                    Icon::new(IconName::ChevronRight)
+                        .xsmall()
+                        .text_color(cx.theme().muted_foreground),
+                )
+            })
+    }
+
+    fn render_columns(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let columns = columns(&self.options, &self.active_path);
+        let last_depth = columns.len().saturating_sub(1);
+
+        h_flex()
+            .items_start()
+            .children(columns.into_iter().enumerate().map(|(depth, column)| {
+                v_flex()
+                    .id(("column", depth))
+                    .min_w(px(120.))
+                    .max_h(rems(20.))
+                    .overflow_y_scroll()
+                    .p_1()
+                    .when(depth < last_depth, |this| {
+                        this.border_r_1().border_color(cx.theme().border)
+                    })
+                    .children(column.iter().enumerate().map(|(ix, option)| {
+                        let active = self.active_path.get(depth) == Some(&ix);
+                        self.render_item(ix, option.label.clone(), active, !option.is_leaf(), cx)
+                            .on_click(cx.listener(move |this, _, cx| this.activate(depth, ix, cx)))
+                    }))
+            }))
+    }
+
+    fn render_matches(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .id("matches")
+            .min_w(px(240.))
+            .max_h(rems(20.))
+            .overflow_y_scroll()
+            .p_1()
+            .when(self.matches.is_empty(), |this| {
+                this.child(
+                    h_flex()
+                        .justify_center()
+                        .py_6()
+                        .text_color(cx.theme().muted_foreground.opacity(0.6))
+                        .child(Icon::new(IconName::Inbox).size(px(28.))),
+                )
+            })
+            .children(self.matches.iter().enumerate().map(|(ix, path)| {
+                let label = path_title(&self.options, path);
+                let path = path.clone();
+                self.render_item(ix, label, ix == self.match_ix, false, cx)
+                    .on_click(cx.listener(move |this, _, cx| this.select(path.clone(), true, cx)))
+            }))
+    }
+
+    fn display_title(&self, cx: &WindowContext) -> impl IntoElement {
+        let title = path_title(&self.options, &self.selected_path);
+
+        div()
+            .whitespace_nowrap()
+            .overflow_hidden()
+            .map(|this| {
+                if title.is_empty() {
+                    this.text_color(cx.theme().accent_foreground).child(
+                        self.placeholder
+                            .clone()
+                            .unwrap_or_else(|| t!("Cascader.placeholder").into()),
+                    )
+                } else {
+                    this.child(title)
+                }
+            })
+            .when(self.disabled, |this| {
+                this.cursor_not_allowed()
+                    .text_color(cx.theme().muted_foreground)
+            })
+    }
+}
+
+impl Sizable for Cascader {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl EventEmitter<CascaderEvent> for Cascader {}
+impl FocusableView for Cascader {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for Cascader {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let is_focused = self.focus_handle.is_focused(cx);
+        let show_clean = self.cleanable && !self.selected_path.is_empty();
+        let allow_open = !(self.open || self.disabled);
+        let outline_visible = self.open || is_focused && !self.disabled;
+        let view = cx.view().clone();
+        let bounds = self.bounds;
+
+        div()
+            .id(self.id.clone())
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::up))
+            .on_action(cx.listener(Self::down))
+            .on_action(cx.listener(Self::left))
+            .on_action(cx.listener(Self::right))
+            .on_action(cx.listener(Self::enter))
+            .on_action(cx.listener(Self::escape))
+            .size_full()
+            .relative()
+            .input_text_size(self.size)
+            .child(
+                h_flex()
+                    .id("cascader-input")
+                    .relative()
+                    .justify_between()
+                    .gap_1()
+                    .bg(cx.theme().background)
+                    .border_1()
+                    .border_color(cx.theme().input)
+                    .rounded(px(cx.theme().radius))
+                    .when(cx.theme().has_shadow(), |this| this.shadow_sm())
+                    .map(|this| {
+                        if self.disabled {
+                            this.cursor_not_allowed()
+                        } else {
+                            this.cursor_pointer()
+                        }
+                    })
+                    .overflow_hidden()
+                    .map(|this| match self.width {
+                        Length::Definite(l) => this.flex_none().w(l),
+                        Length::Auto => this.w_full(),
+                    })
+                    .when(outline_visible, |this| this.outline(cx))
+                    .input_size(self.size)
+                    .when(allow_open, |this| {
+                        this.on_click(cx.listener(Self::toggle_menu))
+                    })
+                    .child(
+                        div()
+                            .w_full()
+                            .overflow_hidden()
+                            .child(self.display_title(cx)),
+                    )
+                    .map(|this| {
+                        if show_clean {
+                            this.child(ClearButton::new(cx).map(|this| {
+                                if self.disabled {
+                                    this.disabled(true)
+                                } else {
+                                    this.on_click(cx.listener(Self::clean))
+                                }
+                            }))
+                        } else {
+                            this.child(
+                                Icon::new(if self.open {
+                                    IconName::ChevronUp
+                                } else {
+                                    IconName::ChevronDown
+                                })
+                                .xsmall()
+                                .text_color(cx.theme().muted_foreground),
+                            )
+                        }
+                    })
+                    .child(
+                        canvas(
+                            move |bounds, cx| view.update(cx, |r, _| r.bounds = bounds),
+                            |_, _, _| {},
+                        )
+                        .absolute()
+                        .size_full(),
+                    ),
+            )
+            .when(self.open, |this| {
+                let placement = FloatingPlacement::new(Side::Bottom, Align::Start);
+                this.child(
+                    deferred(
+                        anchored_to(bounds, placement).child(
+                            v_flex()
+                                .occlude()
+                                .bg(cx.theme().background)
+                                .border_1()
+                                .border_color(cx.theme().border)
+                                .rounded(px(cx.theme().radius))
+                                .when(cx.theme().has_shadow(), |this| this.shadow_md())
+                                .when_some(self.search_input.clone(), |this, input| {
+                                    this.child(
+                                        div()
+                                            .px_2()
+                                            .border_b_1()
+                                            .border_color(cx.theme().border)
+                                            .child(input),
+                                    )
+                                })
+                                .map(|this| {
+                                    if self.is_searching() {
+                                        this.child(self.render_matches(cx))
+                                    } else {
+                                        this.child(self.render_columns(cx))
+                                    }
+                                })
+                                .on_mouse_down_out(cx.listener(|this, _, cx| {
+                                    this.escape(&Escape, cx);
+                                })),
+                        ),
+                    )
+                    .with_priority(1),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> Vec<CascaderOption> {
+        vec![
+            CascaderOption::new("asia", "Asia").children([
+                CascaderOption::new("cn", "China").children([
+                    CascaderOption::new("bj", "Beijing"),
+                    CascaderOption::new("sh", "Shanghai"),
+                ]),
+                CascaderOption::new("jp", "Japan"),
+            ]),
+            CascaderOption::new("europe", "Europe").children([CascaderOption::new("fr", "France")
+                .children([CascaderOption::new("paris", "Paris")])]),
+        ]
+    }
+
+    #[test]
+    fn test_option_at() {
+        let options = options();
+        assert_eq!(&*option_at(&options, &[0, 0, 1]).unwrap().value, "sh");
+        assert_eq!(&*option_at(&options, &[1]).unwrap().value, "europe");
+        assert!(option_at(&options, &[]).is_none());
+        assert!(option_at(&options, &[0, 5]).is_none());
+    }
+
+    #[test]
+    fn test_columns() {
+        let options = options();
+        assert_eq!(columns(&options, &[]).len(), 1);
+        assert_eq!(columns(&options, &[0]).len(), 2);
+        assert_eq!(columns(&options, &[0, 0]).len(), 3);
+        // The leaf doesn't open a column.
+        assert_eq!(columns(&options, &[0, 1]).len(), 2);
+        assert_eq!(columns(&options, &[0, 0, 1]).len(), 3);
+    }
+
+    #[test]
+    fn test_leaf_paths() {
+        assert_eq!(
+            leaf_paths(&options()),
+            vec![vec![0, 0, 0], vec![0, 0, 1], vec![0, 1], vec![1, 0, 0]]
+        );
+    }
+
+    #[test]
+    fn test_search_paths() {
+        let options = options();
+        assert_eq!(path_title(&options, &[0, 0, 1]), "Asia / China / Shanghai");
+        assert_eq!(search_paths(&options, "shang"), vec![vec![0, 0, 1]]);
+        assert_eq!(
+            search_paths(&options, "china"),
+            vec![vec![0, 0, 0], vec![0, 0, 1]]
+        );
+        assert_eq!(search_paths(&options, "europe / fr"), vec![vec![1, 0, 0]]);
+        assert_eq!(search_paths(&options, "berlin"), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn test_step() {
+        assert_eq!(step(0, 3, 1), 1);
+        assert_eq!(step(2, 3, 1), 2);
+        assert_eq!(step(0, 3, -1), 0);
+        assert_eq!(step(0, 0, 1), 0);
+    }
+}
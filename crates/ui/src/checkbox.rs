@@ -1,8 +1,13 @@
-use crate::{h_flex, theme::ActiveTheme, v_flex, Disableable, IconName, Selectable};
+use std::rc::Rc;
+
+use crate::{
+    h_flex, theme::ActiveTheme, v_flex, AutomationExt as _, AutomationId, Disableable, GroupLayout,
+    IconName, Selectable, Sizable, Size,
+};
 use gpui::{
-    div, prelude::FluentBuilder as _, relative, svg, ElementId, InteractiveElement, IntoElement,
-    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled as _,
-    WindowContext,
+    div, prelude::FluentBuilder as _, px, relative, svg, ElementId, InteractiveElement,
+    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _,
+    Styled as _, WindowContext,
 };
 
 /// A Checkbox element.
@@ -12,6 +17,8 @@ pub struct Checkbox {
     label: Option<SharedString>,
     checked: bool,
     disabled: bool,
+    size: Size,
+    automation_id: Option<SharedString>,
     on_click: Option<Box<dyn Fn(&bool, &mut WindowContext) + 'static>>,
 }
 
@@ -22,6 +29,8 @@ impl Checkbox {
             label: None,
             checked: false,
             disabled: false,
+            size: Size::Medium,
+            automation_id: None,
             on_click: None,
         }
     }
@@ -42,6 +51,13 @@ impl Checkbox {
     }
 }
 
+impl AutomationId for Checkbox {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.automation_id = Some(id.into());
+        self
+    }
+}
+
 impl Disableable for Checkbox {
     fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -49,6 +65,13 @@ impl Disableable for Checkbox {
     }
 }
 
+impl Sizable for Checkbox {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
 impl Selectable for Checkbox {
     fn element_id(&self) -> &ElementId {
         &self.id
@@ -69,10 +92,25 @@ impl RenderOnce for Checkbox {
         } else {
             (cx.theme().primary, cx.theme().primary_foreground)
         };
+        let box_size = match self.size {
+            Size::XSmall => px(12.),
+            Size::Small => px(14.),
+            Size::Medium => px(16.),
+            Size::Large => px(20.),
+            Size::XLarge => px(24.),
+            Size::Size(size) => size,
+        };
 
         h_flex()
             .id(self.id)
-            .gap_2()
+            .when_automation_id(self.automation_id.as_ref())
+            .map(|this| match self.size {
+                Size::XSmall => this.gap_1().text_xs(),
+                Size::Small => this.gap_1p5().text_sm(),
+                Size::Large => this.gap_2().text_base(),
+                Size::XLarge => this.gap_2p5().text_lg(),
+                _ => this.gap_2(),
+            })
             .items_center()
             .line_height(relative(1.))
             .child(
@@ -81,7 +119,7 @@ impl RenderOnce for Checkbox {
                     .border_1()
                     .border_color(color)
                     .rounded_sm()
-                    .size_4()
+                    .size(box_size)
                     .flex_shrink_0()
                     .map(|this| match self.checked {
                         false => this.bg(cx.theme().transparent),
@@ -92,7 +130,7 @@ impl RenderOnce for Checkbox {
                             .absolute()
                             .top_px()
                             .left_px()
-                            .size_3()
+                            .size(box_size - px(4.))
                             .text_color(icon_color)
                             .map(|this| match self.checked {
                                 true => this.path(IconName::Check.path()),
@@ -129,3 +167,152 @@ impl RenderOnce for Checkbox {
             )
     }
 }
+
+/// Returns the sorted indices after the checkbox at `ix` is checked or unchecked.
+fn toggle_index(selected: &[usize], ix: usize, checked: bool) -> Vec<usize> {
+    let mut selected = selected
+        .iter()
+        .copied()
+        .filter(|&it| it != ix)
+        .collect::<Vec<_>>();
+    if checked {
+        selected.push(ix);
+        selected.sort_unstable();
+    }
+    selected
+}
+
+/// A group of the [`Checkbox`]es with the multiple selection.
+///
+/// The `on_click` and the `checked` of the checkboxes are managed by the group.
+///
+/// ```ignore
+/// CheckboxGroup::new("markets")
+///     .layout(GroupLayout::Grid(3))
+///     .children(["US", "HK", "CN"].map(|market| Checkbox::new(market).label(market)))
+///     .selected_indices(self.markets.clone())
+///     .on_change(cx.listener(|this, indices: &Vec<usize>, cx| {
+///         this.markets = indices.clone();
+///         cx.notify();
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct CheckboxGroup {
+    id: ElementId,
+    checkboxes: Vec<Checkbox>,
+    layout: GroupLayout,
+    selected: Vec<usize>,
+    disabled: bool,
+    size: Option<Size>,
+    on_change: Option<Rc<dyn Fn(&Vec<usize>, &mut WindowContext) + 'static>>,
+}
+
+impl CheckboxGroup {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            checkboxes: Vec::new(),
+            layout: GroupLayout::default(),
+            selected: Vec::new(),
+            disabled: false,
+            size: None,
+            on_change: None,
+        }
+    }
+
+    pub fn child(mut self, checkbox: Checkbox) -> Self {
+        self.checkboxes.push(checkbox);
+        self
+    }
+
+    pub fn children(mut self, checkboxes: impl IntoIterator<Item = Checkbox>) -> Self {
+        self.checkboxes.extend(checkboxes);
+        self
+    }
+
+    /// Set the layout of the checkboxes, default: [`GroupLayout::Vertical`]
+    pub fn layout(mut self, layout: GroupLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the indices of the checked checkboxes.
+    pub fn selected_indices(mut self, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.selected = indices.into_iter().collect();
+        self.selected.sort_unstable();
+        self.selected.dedup();
+        self
+    }
+
+    /// Set the handler to call with the sorted indices of the checked checkboxes when changed.
+    pub fn on_change(
+        mut self,
+        handler: impl Fn(&Vec<usize>, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Disableable for CheckboxGroup {
+    fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+}
+
+impl Sizable for CheckboxGroup {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = Some(size.into());
+        self
+    }
+}
+
+impl RenderOnce for CheckboxGroup {
+    fn render(self, _: &mut WindowContext) -> impl IntoElement {
+        let layout = self.layout;
+        let disabled = self.disabled;
+        let size = self.size;
+        let selected = Rc::new(self.selected);
+        let on_change = self.on_change;
+
+        layout
+            .container()
+            .id(self.id)
+            .children(
+                self.checkboxes
+                    .into_iter()
+                    .enumerate()
+                    .map(|(ix, checkbox)| {
+                        let checkbox = Checkbox {
+                            checked: selected.contains(&ix),
+                            disabled: disabled || checkbox.disabled,
+                            size: size.unwrap_or(checkbox.size),
+                            on_click: on_change.clone().map(|on_change| {
+                                let selected = selected.clone();
+                                Box::new(move |checked: &bool, cx: &mut WindowContext| {
+                                    on_change(&toggle_index(&selected, ix, *checked), cx);
+                                })
+                                    as Box<dyn Fn(&bool, &mut WindowContext)>
+                            }),
+                            ..checkbox
+                        };
+                        layout.item(checkbox)
+                    }),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::toggle_index;
+
+    #[test]
+    fn test_toggle_index() {
+        assert_eq!(toggle_index(&[], 1, true), vec![1]);
+        assert_eq!(toggle_index(&[0, 2], 1, true), vec![0, 1, 2]);
+        assert_eq!(toggle_index(&[0, 1, 2], 1, false), vec![0, 2]);
+        assert_eq!(toggle_index(&[0, 1], 1, true), vec![0, 1]);
+        assert_eq!(toggle_index(&[0], 3, false), vec![0]);
+    }
+}
@@ -0,0 +1,229 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, AnyElement, Div, Element, ElementId,
+    GlobalElementId, IntoElement, LayoutId, ParentElement, Pixels, Refineable as _, SharedString,
+    Styled, WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{
+    animation::cubic_bezier,
+    button::{Button, ButtonVariants as _},
+    theme::ActiveTheme as _,
+    v_flex, Sizable as _,
+};
+
+const TOGGLE_DURATION: Duration = Duration::from_millis(200);
+
+/// A text limited to the number of lines, with a "Show more" / "Show less" toggle
+/// when the text is overflowing, e.g.: the news summaries and the long descriptions in the cards.
+///
+/// ```ignore
+/// ClampedText::new("summary", summary).lines(3)
+/// ```
+pub struct ClampedText {
+    id: ElementId,
+    base: Div,
+    text: SharedString,
+    lines: usize,
+    on_toggle: Option<Rc<dyn Fn(&bool, &mut WindowContext)>>,
+}
+
+impl ClampedText {
+    pub fn new(id: impl Into<ElementId>, text: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            base: v_flex().items_start(),
+            text: text.into(),
+            lines: 3,
+            on_toggle: None,
+        }
+    }
+
+    /// Set the max lines of the text when it is collapsed, default is 3.
+    pub fn lines(mut self, lines: usize) -> Self {
+        self.lines = lines.max(1);
+        self
+    }
+
+    /// Set the callback when the text is expanded or collapsed, the argument is the expanded state.
+    pub fn on_toggle(mut self, handler: impl Fn(&bool, &mut WindowContext) + 'static) -> Self {
+        self.on_toggle = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Styled for ClampedText {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl IntoElement for ClampedText {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct ClampedTextState {
+    expanded: Rc<Cell<bool>>,
+    /// The height of the whole text, measured in the last frame.
+    full_height: Rc<Cell<Pixels>>,
+    toggled_at: Rc<Cell<Option<Instant>>>,
+}
+
+/// Returns the height of the text box during the toggle animation, the `progress` is in `0..=1`.
+fn animated_height(clamped: f32, full: f32, expanded: bool, progress: f32) -> f32 {
+    let (from, to) = if expanded {
+        (clamped, full)
+    } else {
+        (full, clamped)
+    };
+
+    from + (to - from) * progress.clamp(0., 1.)
+}
+
+impl Element for ClampedText {
+    type RequestLayoutState = AnyElement;
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<ElementId> {
+        Some(self.id.clone())
+    }
+
+    fn request_layout(
+        &mut self,
+        global_id: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        cx.with_element_state::<ClampedTextState, _>(global_id.unwrap(), |state, cx| {
+            let state = state.unwrap_or_default();
+            let view_id = cx.parent_view_id();
+
+            let mut text_style = cx.text_style();
+            if let Some(refinement) = self.base.text_style() {
+                text_style.refine(refinement);
+            }
+            let line_height = text_style.line_height_in_pixels(cx.rem_size());
+            let clamped_height = line_height * self.lines as f32;
+            let full_height = state.full_height.get();
+            let overflows = full_height > clamped_height + px(1.);
+            let expanded = state.expanded.get();
+
+            let progress = state
+                .toggled_at
+                .get()
+                .filter(|_| !cx.theme().lite)
+                .map(|at| at.elapsed().as_secs_f32() / TOGGLE_DURATION.as_secs_f32())
+                .filter(|progress| *progress < 1.);
+            let max_height = match progress {
+                Some(progress) => {
+                    cx.request_animation_frame();
+                    let ease = cubic_bezier(0.4, 0., 0.2, 1.);
+                    Some(px(animated_height(
+                        clamped_height.0,
+                        full_height.0,
+                        expanded,
+                        ease(progress),
+                    )))
+                }
+                None if expanded => None,
+                None => Some(clamped_height),
+            };
+
+            let measured_height = state.full_height.clone();
+            let mut element = std::mem::replace(&mut self.base, div())
+                .child(
+                    div()
+                        .w_full()
+                        .overflow_hidden()
+                        .when_some(max_height, |this, max_height| this.max_h(max_height))
+                        .child(
+                            div().relative().child(self.text.clone()).child(
+                                canvas(
+                                    move |bounds, cx| {
+                                        let height = bounds.size.height;
+                                        if measured_height.replace(height) != height {
+                                            cx.notify(view_id);
+                                        }
+                                    },
+                                    |_, _, _| {},
+                                )
+                                .absolute()
+                                .size_full(),
+                            ),
+                        ),
+                )
+                .when(overflows, |this| {
+                    let on_toggle = self.on_toggle.clone();
+                    let expanded_state = state.expanded.clone();
+                    let toggled_at = state.toggled_at.clone();
+
+                    this.child(
+                        Button::new("toggle")
+                            .link()
+                            .xsmall()
+                            .label(if expanded {
+                                t!("ClampedText.ShowLess")
+                            } else {
+                                t!("ClampedText.ShowMore")
+                            })
+                            .on_click(move |_, cx| {
+                                expanded_state.set(!expanded);
+                                toggled_at.set(Some(Instant::now()));
+                                if let Some(on_toggle) = &on_toggle {
+                                    on_toggle(&!expanded, cx);
+                                }
+                                cx.notify(view_id);
+                            }),
+                    )
+                })
+                .into_any_element();
+
+            ((element.request_layout(cx), element), state)
+        })
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: gpui::Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) {
+        element.prepaint(cx);
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: gpui::Bounds<Pixels>,
+        element: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        element.paint(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::animated_height;
+
+    #[test]
+    fn test_animated_height() {
+        assert_eq!(animated_height(40., 100., true, 0.), 40.);
+        assert_eq!(animated_height(40., 100., true, 0.5), 70.);
+        assert_eq!(animated_height(40., 100., true, 1.), 100.);
+        assert_eq!(animated_height(40., 100., false, 0.), 100.);
+        assert_eq!(animated_height(40., 100., false, 1.5), 40.);
+    }
+}
@@ -0,0 +1,276 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{EventEmitter, Model, Subscription, ViewContext};
+use smol::Timer;
+
+/// The default interval between the updates, about one frame.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(16);
+
+/// How to keep the events received between the updates.
+pub enum CoalesceStrategy<E> {
+    /// Keep all the events in the receiving order, drop the oldest ones if more than `max`.
+    All { max: usize },
+    /// Keep the latest event only, drop the others.
+    Latest,
+    /// Merge the new event into the pending one, e.g.: sum the volumes, keep the last price.
+    Merge(Rc<dyn Fn(&mut E, E)>),
+}
+
+impl<E> Clone for CoalesceStrategy<E> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::All { max } => Self::All { max: *max },
+            Self::Latest => Self::Latest,
+            Self::Merge(merge) => Self::Merge(merge.clone()),
+        }
+    }
+}
+
+struct Pending<E> {
+    events: VecDeque<E>,
+    strategy: CoalesceStrategy<E>,
+    scheduled: bool,
+    last_flush: Option<Instant>,
+}
+
+impl<E> Pending<E> {
+    fn new(strategy: CoalesceStrategy<E>) -> Self {
+        Self {
+            events: VecDeque::new(),
+            strategy,
+            scheduled: false,
+            last_flush: None,
+        }
+    }
+
+    fn push(&mut self, event: E) {
+        match &self.strategy {
+            CoalesceStrategy::All { max } => {
+                if self.events.len() >= (*max).max(1) {
+                    self.events.pop_front();
+                }
+                self.events.push_back(event);
+            }
+            CoalesceStrategy::Latest => {
+                self.events.clear();
+                self.events.push_back(event);
+            }
+            CoalesceStrategy::Merge(merge) => match self.events.back_mut() {
+                Some(pending) => merge(pending, event),
+                None => self.events.push_back(event),
+            },
+        }
+    }
+
+    fn take(&mut self, now: Instant) -> Vec<E> {
+        self.scheduled = false;
+        self.last_flush = Some(now);
+        self.events.drain(..).collect()
+    }
+}
+
+/// Returns the delay before the next update, to keep the `interval` since the last update.
+fn flush_delay(last_flush: Option<Instant>, now: Instant, interval: Duration) -> Duration {
+    last_flush.map_or(Duration::ZERO, |last_flush| {
+        interval.saturating_sub(now.saturating_duration_since(last_flush))
+    })
+}
+
+/// Coalesce the high frequency events of a Model (e.g.: 1k ticks per second of the quotes)
+/// into at most one update of the View per `interval`, so the streaming data doesn't starve the render loop.
+///
+/// The View is notified after the events are applied, so only one render per interval.
+///
+/// ```ignore
+/// let subscription = Coalesce::merge(|pending: &mut Tick, tick| {
+///     pending.volume += tick.volume;
+///     pending.price = tick.price;
+/// })
+/// .interval(Duration::from_millis(100))
+/// .subscribe(&quotes, cx, |this, ticks, cx| this.apply_ticks(ticks, cx));
+/// ```
+pub struct Coalesce<E> {
+    strategy: CoalesceStrategy<E>,
+    interval: Duration,
+}
+
+impl<E> Coalesce<E>
+where
+    E: Clone + 'static,
+{
+    pub fn new(strategy: CoalesceStrategy<E>) -> Self {
+        Self {
+            strategy,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+
+    /// Keep all the events, drop the oldest ones if more than `max` events between the updates.
+    pub fn all(max: usize) -> Self {
+        Self::new(CoalesceStrategy::All { max })
+    }
+
+    /// Keep the latest event only.
+    pub fn latest() -> Self {
+        Self::new(CoalesceStrategy::Latest)
+    }
+
+    /// Merge the events between the updates into one event.
+    pub fn merge(merge: impl Fn(&mut E, E) + 'static) -> Self {
+        Self::new(CoalesceStrategy::Merge(Rc::new(merge)))
+    }
+
+    /// Set the min interval between the updates of the View, default: 16ms
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Subscribe to the events of the `model`, the `on_events` is called with the coalesced events
+    /// at most once per interval, then the View is notified.
+    ///
+    /// The events are dropped once the returned [`Subscription`] is dropped.
+    pub fn subscribe<V, M>(
+        self,
+        model: &Model<M>,
+        cx: &mut ViewContext<V>,
+        on_events: impl Fn(&mut V, Vec<E>, &mut ViewContext<V>) + 'static,
+    ) -> Subscription
+    where
+        V: 'static,
+        M: EventEmitter<E>,
+    {
+        let interval = self.interval;
+        let pending = Rc::new(RefCell::new(Pending::new(self.strategy)));
+        let on_events = Rc::new(on_events);
+
+        cx.subscribe(model, move |_, _, event: &E, cx| {
+            let mut state = pending.borrow_mut();
+            state.push(event.clone());
+            if state.scheduled {
+                return;
+            }
+            state.scheduled = true;
+
+            let delay = flush_delay(state.last_flush, Instant::now(), interval);
+            let pending = Rc::downgrade(&pending);
+            let on_events = on_events.clone();
+            cx.spawn(|view, mut cx| async move {
+                if !delay.is_zero() {
+                    Timer::after(delay).await;
+                }
+
+                // The subscription has been dropped.
+                let Some(pending) = pending.upgrade() else {
+                    return;
+                };
+                _ = view.update(&mut cx, |view, cx| {
+                    let events = pending.borrow_mut().take(Instant::now());
+                    if !events.is_empty() {
+                        on_events(view, events, cx);
+                        cx.notify();
+                    }
+                });
+            })
+            .detach();
+        })
+    }
+}
+
+/// Observe the `model` and notify the View at most once per `interval`, instead of on every change of the Model.
+///
+/// The `on_notify` is called before the View is notified, e.g.: to read the latest state of the Model.
+pub fn observe_coalesced<V, M>(
+    model: &Model<M>,
+    interval: Duration,
+    cx: &mut ViewContext<V>,
+    on_notify: impl Fn(&mut V, Model<M>, &mut ViewContext<V>) + 'static,
+) -> Subscription
+where
+    V: 'static,
+    M: 'static,
+{
+    let pending = Rc::new(RefCell::new(Pending::<()>::new(CoalesceStrategy::Latest)));
+    let on_notify = Rc::new(on_notify);
+
+    cx.observe(model, move |_, model, cx| {
+        let mut state = pending.borrow_mut();
+        if state.scheduled {
+            return;
+        }
+        state.scheduled = true;
+
+        let delay = flush_delay(state.last_flush, Instant::now(), interval);
+        let pending = Rc::downgrade(&pending);
+        let on_notify = on_notify.clone();
+        cx.spawn(|view, mut cx| async move {
+            if !delay.is_zero() {
+                Timer::after(delay).await;
+            }
+
+            let Some(pending) = pending.upgrade() else {
+                return;
+            };
+            _ = view.update(&mut cx, |view, cx| {
+                pending.borrow_mut().take(Instant::now());
+                on_notify(view, model, cx);
+                cx.notify();
+            });
+        })
+        .detach();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        rc::Rc,
+        time::{Duration, Instant},
+    };
+
+    use super::{flush_delay, CoalesceStrategy, Pending};
+
+    #[test]
+    fn test_pending_strategies() {
+        let mut pending = Pending::new(CoalesceStrategy::All { max: 3 });
+        for i in 1..=5 {
+            pending.push(i);
+        }
+        assert_eq!(pending.take(Instant::now()), vec![3, 4, 5]);
+        assert_eq!(pending.take(Instant::now()), Vec::<i32>::new());
+
+        let mut pending = Pending::new(CoalesceStrategy::Latest);
+        for i in 1..=5 {
+            pending.push(i);
+        }
+        assert_eq!(pending.take(Instant::now()), vec![5]);
+
+        let mut pending = Pending::new(CoalesceStrategy::Merge(Rc::new(|a: &mut i32, b| *a += b)));
+        for i in 1..=5 {
+            pending.push(i);
+        }
+        assert_eq!(pending.take(Instant::now()), vec![15]);
+        pending.push(1);
+        assert_eq!(pending.take(Instant::now()), vec![1]);
+    }
+
+    #[test]
+    fn test_flush_delay() {
+        let now = Instant::now();
+        let interval = Duration::from_millis(16);
+        assert_eq!(flush_delay(None, now, interval), Duration::ZERO);
+        assert_eq!(
+            flush_delay(Some(now), now + Duration::from_millis(10), interval),
+            Duration::from_millis(6)
+        );
+        assert_eq!(
+            flush_delay(Some(now), now + Duration::from_millis(100), interval),
+            Duration::ZERO
+        );
+    }
+}
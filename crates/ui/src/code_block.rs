@@ -0,0 +1,326 @@
+//! A read-only code block with the syntax highlighting, line numbers and a copy button.
+
+use std::{ops::Range, rc::Rc};
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, Div, ElementId, HighlightStyle, InteractiveElement as _,
+    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _, Styled,
+    StyledText, WindowContext,
+};
+
+use crate::{clipboard::Clipboard, h_flex, theme::ActiveTheme, v_flex};
+
+/// The highlighter used to highlight the code in the [`CodeBlock`],
+/// implement it to plug in a syntax highlighting engine, e.g.: syntect.
+pub trait Highlighter: 'static {
+    /// Returns the highlighted ranges of the code, the ranges are the byte ranges of the whole code,
+    /// so the highlighter can keep the state across lines, e.g.: the multi-line comments.
+    fn highlight(&self, code: &str, cx: &WindowContext) -> Vec<(Range<usize>, HighlightStyle)>;
+}
+
+/// The built-in highlighter for the JSON payloads.
+pub struct JsonHighlighter;
+
+impl Highlighter for JsonHighlighter {
+    fn highlight(&self, code: &str, cx: &WindowContext) -> Vec<(Range<usize>, HighlightStyle)> {
+        json_tokens(code)
+            .into_iter()
+            .map(|(range, token)| {
+                let color = match token {
+                    JsonToken::Key => cx.theme().primary,
+                    JsonToken::String => crate::green_600(),
+                    JsonToken::Number => crate::orange_500(),
+                    JsonToken::Keyword => crate::blue_500(),
+                };
+
+                (
+                    range,
+                    HighlightStyle {
+                        color: Some(color),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonToken {
+    Key,
+    String,
+    Number,
+    /// The `true`, `false` and `null`.
+    Keyword,
+}
+
+/// Tokenize the JSON for highlighting, the invalid JSON is tokenized as much as possible.
+fn json_tokens(code: &str) -> Vec<(Range<usize>, JsonToken)> {
+    let bytes = code.as_bytes();
+    let mut tokens = vec![];
+    let mut ix = 0;
+    while ix < bytes.len() {
+        let start = ix;
+        match bytes[ix] {
+            b'"' => {
+                ix += 1;
+                while ix < bytes.len() && bytes[ix] != b'"' {
+                    if bytes[ix] == b'\\' {
+                        ix += 1;
+                    }
+                    ix += 1;
+                }
+                ix = (ix + 1).min(bytes.len());
+
+                let is_key = bytes[ix..]
+                    .iter()
+                    .find(|b| !b.is_ascii_whitespace())
+                    .map_or(false, |b| *b == b':');
+                let token = if is_key {
+                    JsonToken::Key
+                } else {
+                    JsonToken::String
+                };
+                tokens.push((start..ix, token));
+            }
+            b'-' | b'0'..=b'9' => {
+                while ix < bytes.len()
+                    && matches!(bytes[ix], b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')
+                {
+                    ix += 1;
+                }
+                tokens.push((start..ix, JsonToken::Number));
+            }
+            b'a'..=b'z' => {
+                while ix < bytes.len() && bytes[ix].is_ascii_alphabetic() {
+                    ix += 1;
+                }
+                if matches!(&code[start..ix], "true" | "false" | "null") {
+                    tokens.push((start..ix, JsonToken::Keyword));
+                }
+            }
+            _ => ix += 1,
+        }
+    }
+
+    tokens
+}
+
+/// Returns the highlights in the line, the ranges are relative to the line start.
+fn line_highlights<T: Clone>(
+    line: &Range<usize>,
+    highlights: &[(Range<usize>, T)],
+) -> Vec<(Range<usize>, T)> {
+    highlights
+        .iter()
+        .filter(|(range, _)| range.start < line.end && range.end > line.start)
+        .map(|(range, style)| {
+            let start = range.start.max(line.start) - line.start;
+            let end = range.end.min(line.end) - line.start;
+            (start..end, style.clone())
+        })
+        .filter(|(range, _)| !range.is_empty())
+        .collect()
+}
+
+/// Returns the byte ranges of the lines, without the line breaks.
+fn line_ranges(code: &str) -> Vec<Range<usize>> {
+    let mut start = 0;
+    code.split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            let range = start..start + line.len();
+            start = range.end + 1;
+            if code.as_bytes().get(range.end) == Some(&b'\r') {
+                start += 1;
+            }
+            range
+        })
+        .collect()
+}
+
+fn mono_font_family() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "Menlo"
+    } else if cfg!(target_os = "windows") {
+        "Consolas"
+    } else {
+        "DejaVu Sans Mono"
+    }
+}
+
+/// A read-only code block, e.g.: to display the JSON payloads and the logs in the panels.
+///
+/// ```ignore
+/// CodeBlock::new("payload", json).highlighter(JsonHighlighter)
+/// ```
+#[derive(IntoElement)]
+pub struct CodeBlock {
+    id: ElementId,
+    base: Div,
+    code: SharedString,
+    highlighter: Option<Rc<dyn Highlighter>>,
+    line_numbers: bool,
+    copyable: bool,
+}
+
+impl CodeBlock {
+    pub fn new(id: impl Into<ElementId>, code: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            base: div(),
+            code: code.into(),
+            highlighter: None,
+            line_numbers: true,
+            copyable: true,
+        }
+    }
+
+    /// Set the highlighter of the code, default is `None` to render the plain text.
+    pub fn highlighter(mut self, highlighter: impl Highlighter) -> Self {
+        self.highlighter = Some(Rc::new(highlighter));
+        self
+    }
+
+    /// Set to show the line numbers, default is true.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Set to show the copy button, default is true.
+    pub fn copyable(mut self, copyable: bool) -> Self {
+        self.copyable = copyable;
+        self
+    }
+}
+
+impl Styled for CodeBlock {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for CodeBlock {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let mut highlights = self
+            .highlighter
+            .as_ref()
+            .map(|highlighter| highlighter.highlight(&self.code, cx))
+            .unwrap_or_default();
+        highlights.sort_by_key(|(range, _)| range.start);
+
+        let mut text_style = cx.text_style();
+        text_style.font_family = mono_font_family().into();
+
+        let lines = line_ranges(&self.code);
+        let line_number_color = cx.theme().muted_foreground;
+
+        self.base
+            .id(self.id)
+            .relative()
+            .overflow_hidden()
+            .font_family(mono_font_family())
+            .text_sm()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(px(cx.theme().radius))
+            .bg(cx.theme().secondary)
+            .child(
+                h_flex()
+                    .id("lines")
+                    .size_full()
+                    .items_start()
+                    .overflow_y_scroll()
+                    .when(self.line_numbers, |this| {
+                        this.child(
+                            v_flex()
+                                .flex_shrink_0()
+                                .items_end()
+                                .py_2()
+                                .px_2()
+                                .border_r_1()
+                                .border_color(cx.theme().border)
+                                .text_color(line_number_color)
+                                .children((1..=lines.len()).map(|n| div().child(n.to_string()))),
+                        )
+                    })
+                    .child(div().id("code").flex_1().overflow_x_scroll().child(
+                        v_flex().py_2().px_3().children(lines.iter().map(|line| {
+                            let text = &self.code[line.clone()];
+                            let text: SharedString = if text.is_empty() {
+                                " ".into()
+                            } else {
+                                text.to_string().into()
+                            };
+
+                            div().whitespace_nowrap().child(
+                                StyledText::new(text).with_highlights(
+                                    &text_style,
+                                    line_highlights(line, &highlights),
+                                ),
+                            )
+                        })),
+                    )),
+            )
+            .when(self.copyable, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_1()
+                        .right_1()
+                        .child(Clipboard::new("copy").value(self.code.clone())),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{json_tokens, line_highlights, line_ranges, JsonToken};
+
+    #[test]
+    fn test_json_tokens() {
+        let code = r#"{"name": "GPUI", "stars": -1.5e3, "ok": true, "tag": null, "s": "a\"b"}"#;
+        let tokens = json_tokens(code)
+            .into_iter()
+            .map(|(range, token)| (&code[range], token))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            tokens,
+            vec![
+                ("\"name\"", JsonToken::Key),
+                ("\"GPUI\"", JsonToken::String),
+                ("\"stars\"", JsonToken::Key),
+                ("-1.5e3", JsonToken::Number),
+                ("\"ok\"", JsonToken::Key),
+                ("true", JsonToken::Keyword),
+                ("\"tag\"", JsonToken::Key),
+                ("null", JsonToken::Keyword),
+                ("\"s\"", JsonToken::Key),
+                ("\"a\\\"b\"", JsonToken::String),
+            ]
+        );
+
+        // Unterminated string
+        assert_eq!(json_tokens("\"abc"), vec![(0..4, JsonToken::String)]);
+        assert_eq!(json_tokens("\"abc\\"), vec![(0..5, JsonToken::String)]);
+    }
+
+    #[test]
+    fn test_line_highlights() {
+        let code = "ab\r\ncde\n\nf";
+        let lines = line_ranges(code);
+        assert_eq!(lines, vec![0..2, 4..7, 8..8, 9..10]);
+
+        let highlights = [(1..5, 'a'), (6..10, 'b')];
+        assert_eq!(line_highlights(&lines[0], &highlights), vec![(1..2, 'a')]);
+        assert_eq!(
+            line_highlights(&lines[1], &highlights),
+            vec![(0..1, 'a'), (2..3, 'b')]
+        );
+        assert_eq!(line_highlights(&lines[2], &highlights), vec![]);
+        assert_eq!(line_highlights(&lines[3], &highlights), vec![(0..1, 'b')]);
+    }
+}
@@ -1,7 +1,7 @@
 use gpui::{
-    anchored, canvas, deferred, div, prelude::FluentBuilder as _, px, relative, AppContext, Bounds,
-    Corner, ElementId, EventEmitter, FocusHandle, FocusableView, Hsla, InteractiveElement as _,
-    IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point, Render, SharedString,
+    canvas, deferred, div, prelude::FluentBuilder as _, px, relative, AppContext, Bounds, Corner,
+    ElementId, EventEmitter, FocusHandle, FocusableView, Hsla, InteractiveElement as _,
+    IntoElement, MouseButton, ParentElement, Pixels, Render, SharedString,
     StatefulInteractiveElement as _, Styled, View, ViewContext, VisualContext,
 };
 
@@ -9,7 +9,9 @@ use crate::{
     divider::Divider,
     h_flex,
     input::{InputEvent, TextInput},
+    keymap::{self, KeymapEntry},
     popover::Escape,
+    primitives::placement::{anchored_to, FloatingPlacement},
     theme::{ActiveTheme as _, Colorize},
     tooltip::Tooltip,
     v_flex, ColorExt as _, Sizable, Size, StyleSized,
@@ -18,7 +20,7 @@ use crate::{
 const KEY_CONTEXT: &'static str = "ColorPicker";
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([KeyBinding::new("escape", Escape, Some(KEY_CONTEXT))])
+    keymap::bind_keys(cx, [KeymapEntry::new("escape", Escape, Some(KEY_CONTEXT))])
 }
 
 #[derive(Clone)]
@@ -259,15 +261,6 @@ impl ColorPicker {
                 )
             })
     }
-
-    fn resolved_corner(&self, bounds: Bounds<Pixels>) -> Point<Pixels> {
-        bounds.corner(match self.anchor {
-            Corner::TopLeft => Corner::BottomLeft,
-            Corner::TopRight => Corner::BottomRight,
-            Corner::BottomLeft => Corner::TopLeft,
-            Corner::BottomRight => Corner::TopRight,
-        })
-    }
 }
 
 impl Sizable for ColorPicker {
@@ -315,7 +308,7 @@ impl Render for ColorPicker {
                             .border_color(cx.theme().input)
                             .rounded(px(cx.theme().radius))
                             .bg(cx.theme().background)
-                            .shadow_sm()
+                            .when(cx.theme().has_shadow(), |this| this.shadow_sm())
                             .overflow_hidden()
                             .size_with(self.size)
                             .when_some(self.value, |this, value| {
@@ -337,24 +330,17 @@ impl Render for ColorPicker {
             .when(self.open, |this| {
                 this.child(
                     deferred(
-                        anchored()
-                            .anchor(self.anchor)
-                            .snap_to_window_with_margin(px(8.))
-                            .position(self.resolved_corner(self.bounds))
+                        anchored_to(self.bounds, FloatingPlacement::from_corner(self.anchor))
                             .child(
                                 div()
                                     .occlude()
-                                    .map(|this| match self.anchor {
-                                        Corner::TopLeft | Corner::TopRight => this.mt_1p5(),
-                                        Corner::BottomLeft | Corner::BottomRight => this.mb_1p5(),
-                                    })
                                     .w_72()
                                     .overflow_hidden()
                                     .rounded_lg()
                                     .p_3()
                                     .border_1()
                                     .border_color(cx.theme().border)
-                                    .shadow_lg()
+                                    .when(cx.theme().has_shadow(), |this| this.shadow_lg())
                                     .rounded_lg()
                                     .bg(cx.theme().background)
                                     .on_mouse_up_out(
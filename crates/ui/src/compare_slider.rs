@@ -0,0 +1,180 @@
+use std::rc::Rc;
+
+use gpui::{
+    canvas, div, prelude::FluentBuilder as _, px, relative, AnyElement, Bounds, DragMoveEvent,
+    EntityId, EventEmitter, InteractiveElement as _, IntoElement, MouseButton, MouseDownEvent,
+    ParentElement as _, Pixels, Point, Render, StatefulInteractiveElement as _, Styled,
+    ViewContext, VisualContext as _, WindowContext,
+};
+
+use crate::{h_flex, theme::ActiveTheme as _, Icon, IconName, Sizable as _};
+
+#[derive(Clone, Render)]
+pub struct DragDivider(EntityId);
+
+pub enum CompareSliderEvent {
+    Change(f32),
+}
+
+/// A CompareSlider to overlay two elements with a draggable divider,
+/// the `before` element is revealed on the left of the divider, and the `after` on the right.
+///
+/// ```ignore
+/// let slider = cx.new_view(|_| {
+///     CompareSlider::new()
+///         .before(|_| img("light.png"))
+///         .after(|_| img("dark.png"))
+/// });
+/// ```
+pub struct CompareSlider {
+    value: f32,
+    before: Option<Rc<dyn Fn(&mut WindowContext) -> AnyElement>>,
+    after: Option<Rc<dyn Fn(&mut WindowContext) -> AnyElement>>,
+    bounds: Bounds<Pixels>,
+}
+
+impl CompareSlider {
+    pub fn new() -> Self {
+        Self {
+            value: 0.5,
+            before: None,
+            after: None,
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Set the element on the left of the divider.
+    pub fn before<E>(mut self, f: impl Fn(&mut WindowContext) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.before = Some(Rc::new(move |cx| f(cx).into_any_element()));
+        self
+    }
+
+    /// Set the element on the right of the divider.
+    pub fn after<E>(mut self, f: impl Fn(&mut WindowContext) -> E + 'static) -> Self
+    where
+        E: IntoElement,
+    {
+        self.after = Some(Rc::new(move |cx| f(cx).into_any_element()));
+        self
+    }
+
+    /// Set the default position of the divider, range of 0.0..1.0, default: 0.5
+    pub fn default_value(mut self, value: f32) -> Self {
+        self.value = value.clamp(0., 1.);
+        self
+    }
+
+    /// Set the position of the divider, range of 0.0..1.0
+    pub fn set_value(&mut self, value: f32, cx: &mut ViewContext<Self>) {
+        self.value = value.clamp(0., 1.);
+        cx.notify();
+    }
+
+    /// Return the position of the divider, range of 0.0..1.0
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    fn update_value_by_position(&mut self, position: Point<Pixels>, cx: &mut ViewContext<Self>) {
+        if self.bounds.size.width <= px(0.) {
+            return;
+        }
+
+        let value = (position.x - self.bounds.left()) / self.bounds.size.width;
+        self.value = value.clamp(0., 1.);
+        cx.emit(CompareSliderEvent::Change(self.value));
+        cx.notify();
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        self.update_value_by_position(event.position, cx);
+    }
+
+    fn render_divider(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entity_id = cx.entity_id();
+
+        div()
+            .id("divider")
+            .absolute()
+            .top_0()
+            .bottom_0()
+            .left(relative(self.value))
+            .w(px(2.))
+            .ml(px(-1.))
+            .bg(cx.theme().background)
+            .cursor_col_resize()
+            .child(
+                h_flex()
+                    .id("divider-handle")
+                    .absolute()
+                    .top_1_2()
+                    .left(px(-11.))
+                    .mt(px(-12.))
+                    .size_6()
+                    .justify_center()
+                    .rounded_full()
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().background)
+                    .when(cx.theme().has_shadow(), |this| this.shadow_md())
+                    .text_color(cx.theme().muted_foreground)
+                    .child(Icon::new(IconName::ChevronLeft).xsmall())
+                    .child(Icon::new(IconName::ChevronRight).xsmall()),
+            )
+            .on_drag(DragDivider(entity_id), |drag, _, cx| {
+                cx.stop_propagation();
+                cx.new_view(|_| drag.clone())
+            })
+            .on_drag_move(
+                cx.listener(move |view, e: &DragMoveEvent<DragDivider>, cx| {
+                    if e.drag(cx).0 != entity_id {
+                        return;
+                    }
+
+                    view.update_value_by_position(e.event.position, cx)
+                }),
+            )
+    }
+}
+
+impl EventEmitter<CompareSliderEvent> for CompareSlider {}
+
+impl Render for CompareSlider {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let width = self.bounds.size.width;
+        let before = self.before.clone().map(|f| f(cx));
+        let after = self.after.clone().map(|f| f(cx));
+
+        div()
+            .id("compare-slider")
+            .relative()
+            .size_full()
+            .overflow_hidden()
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .child(div().absolute().size_full().children(after))
+            .child(
+                // Clip the `before` element by the divider, keep the element in full width.
+                div()
+                    .absolute()
+                    .top_0()
+                    .left_0()
+                    .h_full()
+                    .w(relative(self.value))
+                    .overflow_hidden()
+                    .child(div().h_full().w(width).children(before)),
+            )
+            .child(self.render_divider(cx))
+            .child({
+                let view = cx.view().clone();
+                canvas(
+                    move |bounds, cx| view.update(cx, |r, _| r.bounds = bounds),
+                    |_, _, _| {},
+                )
+                .absolute()
+                .size_full()
+            })
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, IntoElement, ParentElement, RenderOnce, SharedString, Styled,
+    WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{h_flex, theme::ActiveTheme as _};
+
+/// The state of a realtime connection, e.g.: the [`WebSocket`](crate::websocket::WebSocket).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionState {
+    Connecting,
+    Connected,
+    /// The connection is lost, waiting `delay` to reconnect, `attempt` starts from 1.
+    Reconnecting {
+        attempt: u32,
+        delay: Duration,
+    },
+    Disconnected,
+}
+
+/// A dot with the label to display the [`ConnectionState`], e.g.: in the status bar.
+///
+/// ```ignore
+/// ConnectionIndicator::new(*self.socket.read(cx).state())
+/// ```
+#[derive(IntoElement)]
+pub struct ConnectionIndicator {
+    state: ConnectionState,
+    label: Option<SharedString>,
+    show_label: bool,
+}
+
+impl ConnectionIndicator {
+    pub fn new(state: ConnectionState) -> Self {
+        Self {
+            state,
+            label: None,
+            show_label: true,
+        }
+    }
+
+    /// Set the label instead of the default label of the state.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set false to show the dot only, default: true
+    pub fn show_label(mut self, show_label: bool) -> Self {
+        self.show_label = show_label;
+        self
+    }
+}
+
+impl RenderOnce for ConnectionIndicator {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let (color, label) = match self.state {
+            ConnectionState::Connecting => {
+                (cx.theme().warning, t!("Connection.connecting").to_string())
+            }
+            ConnectionState::Connected => {
+                (cx.theme().positive, t!("Connection.connected").to_string())
+            }
+            ConnectionState::Reconnecting { attempt, .. } => (
+                cx.theme().warning,
+                t!("Connection.reconnecting", attempt = attempt).to_string(),
+            ),
+            ConnectionState::Disconnected => (
+                cx.theme().muted_foreground,
+                t!("Connection.disconnected").to_string(),
+            ),
+        };
+
+        h_flex()
+            .gap_1p5()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(div().flex_shrink_0().size_2().rounded_full().bg(color))
+            .when(self.show_label, |this| {
+                this.child(self.label.unwrap_or_else(|| label.into()))
+            })
+    }
+}
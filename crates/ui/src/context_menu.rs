@@ -1,13 +1,16 @@
 use std::{cell::RefCell, rc::Rc};
 
 use gpui::{
-    anchored, deferred, div, prelude::FluentBuilder, px, relative, AnyElement, Corner,
-    DismissEvent, DispatchPhase, Element, ElementId, Focusable, GlobalElementId,
-    InteractiveElement, IntoElement, MouseButton, MouseDownEvent, ParentElement, Pixels, Point,
-    Position, Stateful, Style, View, ViewContext, WindowContext,
+    deferred, div, prelude::FluentBuilder, px, relative, AnyElement, Bounds, Corner, DismissEvent,
+    DispatchPhase, Element, ElementId, Focusable, GlobalElementId, InteractiveElement, IntoElement,
+    MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Position, Size, Stateful, Style,
+    View, ViewContext, WindowContext,
 };
 
-use crate::popup_menu::PopupMenu;
+use crate::{
+    popup_menu::PopupMenu,
+    primitives::placement::{anchored_to, FloatingPlacement},
+};
 
 pub trait ContextMenuExt: ParentElement + Sized {
     fn context_menu(
@@ -123,16 +126,16 @@ impl Element for ContextMenu {
 
                 if has_menu_item {
                     let mut menu_element = deferred(
-                        anchored()
-                            .position(*position)
-                            .snap_to_window_with_margin(px(8.))
-                            .anchor(anchor)
-                            .when_some(menu_view, |this, menu| {
-                                // Focus the menu, so that can be handle the action.
-                                menu.focus_handle(cx).focus(cx);
-
-                                this.child(div().occlude().child(menu.clone()))
-                            }),
+                        anchored_to(
+                            Bounds::new(*position, Size::default()),
+                            FloatingPlacement::from_corner(anchor).offset(px(0.)),
+                        )
+                        .when_some(menu_view, |this, menu| {
+                            // Focus the menu, so that can be handle the action.
+                            menu.focus_handle(cx).focus(cx);
+
+                            this.child(div().occlude().child(menu.clone()))
+                        }),
                     )
                     .with_priority(1)
                     .into_any();
@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AnyElement, EntityId, InteractiveElement as _,
+    IntoElement, ParentElement as _, StatefulInteractiveElement as _, Styled, ViewContext,
+};
+
+use crate::{
+    h_flex, primitives::cycle_index, theme::ActiveTheme as _, v_flex, Sizable as _, StyledExt as _,
+};
+
+use super::{DockArea, PanelView};
+
+/// The max number of the panels to keep in the [`ActivationHistory`].
+const MAX_HISTORY: usize = 50;
+
+/// The panels in the order of the recent activation, the most recent one is the first.
+///
+/// This is generic over the panel id for testing.
+#[derive(Debug, Clone)]
+pub(super) struct ActivationHistory<H> {
+    items: Vec<H>,
+}
+
+impl<H: Clone + PartialEq> ActivationHistory<H> {
+    pub(super) fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// Move the `item` to the front, returns true if the front item is changed.
+    pub(super) fn record(&mut self, item: H) -> bool {
+        if self.items.first() == Some(&item) {
+            return false;
+        }
+
+        self.items.retain(|it| it != &item);
+        self.items.insert(0, item);
+        self.items.truncate(MAX_HISTORY);
+        true
+    }
+
+    /// Remove the items that are not matched, e.g.: the closed panels.
+    pub(super) fn retain(&mut self, f: impl FnMut(&H) -> bool) {
+        self.items.retain(f);
+    }
+
+    pub(super) fn items(&self) -> &[H] {
+        &self.items
+    }
+}
+
+/// The state of the overlay to select a recent panel, like the `ctrl-tab` in the editors.
+pub(super) struct PanelSwitcher {
+    /// The panels in the order of the recent activation.
+    pub(super) panels: Vec<Arc<dyn PanelView>>,
+    pub(super) selected_ix: usize,
+}
+
+impl PanelSwitcher {
+    /// Create the switcher with the previous panel selected, or the last one if `forward` is false.
+    pub(super) fn new(panels: Vec<Arc<dyn PanelView>>, forward: bool) -> Self {
+        let mut this = Self {
+            panels,
+            selected_ix: 0,
+        };
+        this.select(forward);
+        this
+    }
+
+    /// Select the previous (older) panel if `forward`, otherwise the next (newer) one.
+    pub(super) fn select(&mut self, forward: bool) {
+        if self.panels.is_empty() {
+            return;
+        }
+        self.selected_ix = cycle_index(Some(self.selected_ix), self.panels.len(), forward);
+    }
+
+    pub(super) fn selected(&self) -> Option<Arc<dyn PanelView>> {
+        self.panels.get(self.selected_ix).cloned()
+    }
+
+    pub(super) fn render(&self, cx: &mut ViewContext<DockArea>) -> AnyElement {
+        div()
+            .id("panel-switcher")
+            .absolute()
+            .top_0()
+            .left_0()
+            .size_full()
+            .flex()
+            .justify_center()
+            .pt_16()
+            .child(
+                v_flex()
+                    .id("panel-switcher-list")
+                    .occlude()
+                    .w(px(320.))
+                    .max_h(px(400.))
+                    .overflow_y_scroll()
+                    .p_1()
+                    .gap_0p5()
+                    .popover_style(cx)
+                    .children(self.panels.iter().enumerate().map(|(ix, panel)| {
+                        let selected = ix == self.selected_ix;
+                        h_flex()
+                            .id(("panel-switcher-item", ix))
+                            .gap_2()
+                            .px_2()
+                            .py_1()
+                            .rounded(px(cx.theme().radius))
+                            .text_sm()
+                            .when(selected, |this| {
+                                this.bg(cx.theme().accent)
+                                    .text_color(cx.theme().accent_foreground)
+                            })
+                            .children(panel.icon(cx).map(|icon| icon.small()))
+                            .child(panel.title(cx))
+                            .on_click(cx.listener(move |this, _, cx| {
+                                this.select_switcher_panel(ix, cx);
+                            }))
+                    })),
+            )
+            .into_any_element()
+    }
+}
+
+/// Returns the id to record the panel in the [`ActivationHistory`].
+pub(super) fn panel_id(panel: &Arc<dyn PanelView>) -> EntityId {
+    panel.view().entity_id()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ActivationHistory, MAX_HISTORY};
+
+    #[test]
+    fn test_activation_history() {
+        let mut history = ActivationHistory::new();
+        assert!(history.record(1));
+        assert!(history.record(2));
+        assert!(history.record(3));
+        assert!(!history.record(3));
+        assert_eq!(history.items(), &[3, 2, 1]);
+
+        assert!(history.record(1));
+        assert_eq!(history.items(), &[1, 3, 2]);
+
+        history.retain(|item| *item != 3);
+        assert_eq!(history.items(), &[1, 2]);
+
+        for i in 0..100 {
+            history.record(i);
+        }
+        assert_eq!(history.items().len(), MAX_HISTORY);
+        assert_eq!(history.items()[0], 99);
+    }
+}
@@ -0,0 +1,349 @@
+use std::{
+    cell::RefCell,
+    fs,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::{Arc, Mutex, MutexGuard, Once, Weak},
+    time::Duration,
+};
+
+use anyhow::Result;
+use gpui::{AppContext, Subscription, Task, View, ViewContext};
+use serde::{de::DeserializeOwned, Serialize};
+use smol::Timer;
+
+use super::{DockArea, DockAreaState, DockEvent};
+
+/// The default delay to save after the last layout change.
+const DEFAULT_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// Write the `contents` to the `path` atomically.
+///
+/// The contents are written to a temporary file in the same directory and synced to the disk,
+/// then renamed to the `path`, so the `path` has either the old or the new contents, never a
+/// partial one if the app crashes or the power is lost while writing.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let tmp_path = tmp_path(path);
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Returns the temporary file path next to the `path`, e.g.: `layout.json.tmp`.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".tmp");
+    path.with_file_name(file_name)
+}
+
+/// The latest contents of a file, shared with the panic hook to save it if it's not saved yet.
+#[derive(Default)]
+struct Snapshot {
+    json: String,
+    saved: bool,
+}
+
+impl Snapshot {
+    /// Returns true if the `json` is changed.
+    fn update(&mut self, json: String) -> bool {
+        if self.json == json {
+            return false;
+        }
+
+        self.json = json;
+        self.saved = false;
+        true
+    }
+
+    /// Save the snapshot if not saved, returns true if written.
+    fn save(&mut self, path: &Path) -> io::Result<bool> {
+        if self.saved || self.json.is_empty() {
+            return Ok(false);
+        }
+
+        write_atomic(path, &self.json)?;
+        self.saved = true;
+        Ok(true)
+    }
+}
+
+/// The files of an [`Autosave`], the layout is the first one, then the session if any.
+type Files = Vec<(PathBuf, Snapshot)>;
+
+const LAYOUT: usize = 0;
+const SESSION: usize = 1;
+
+fn save_files(files: &mut Files) {
+    for (path, snapshot) in files.iter_mut() {
+        if let Err(err) = snapshot.save(path) {
+            log::error!("Failed to save {:?}: {:?}", path, err);
+        }
+    }
+}
+
+type SessionDump = Rc<dyn Fn(&AppContext) -> serde_json::Value>;
+
+/// Save the [`DockAreaState`] to a file on every [`DockEvent::LayoutChanged`] with debounce,
+/// the file is written by [`write_atomic`], so a crash while saving doesn't corrupt the layout.
+///
+/// The session state of the app (e.g.: the opened symbols) can be saved to another file
+/// together, see [`Autosave::session`].
+///
+/// A panic hook is installed to save the files if they are still waiting for the debounce,
+/// so the changes before the crash are not lost.
+///
+/// ```ignore
+/// self._autosave = Autosave::new("target/layout.json")
+///     .debounce(Duration::from_secs(2))
+///     .session("target/session.json", |cx| serde_json::json!({ "symbols": symbols(cx) }))
+///     .watch(&dock_area, cx);
+///
+/// // On the next launch.
+/// let state = Autosave::load("target/layout.json")?;
+/// dock_area.update(cx, |dock_area, cx| dock_area.load(state, cx))?;
+/// let session: Session = Autosave::load_session("target/session.json")?;
+/// ```
+pub struct Autosave {
+    path: PathBuf,
+    debounce: Duration,
+    session: Option<(PathBuf, SessionDump)>,
+}
+
+impl Autosave {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            debounce: DEFAULT_DEBOUNCE,
+            session: None,
+        }
+    }
+
+    /// Set the delay to save after the last layout change, default: 1s
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Save the session state returned by `dump` to the `path`, it's dumped on every layout change
+    /// and [`AutosaveHandle::save_session`].
+    pub fn session(
+        mut self,
+        path: impl Into<PathBuf>,
+        dump: impl Fn(&AppContext) -> serde_json::Value + 'static,
+    ) -> Self {
+        self.session = Some((path.into(), Rc::new(dump)));
+        self
+    }
+
+    /// Load the layout saved by the [`Autosave`].
+    pub fn load(path: impl AsRef<Path>) -> Result<DockAreaState> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Load the session state saved by the [`Autosave::session`].
+    pub fn load_session<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Start to save the layout of the `dock_area`, until the returned [`AutosaveHandle`] is dropped.
+    pub fn watch<V: 'static>(
+        self,
+        dock_area: &View<DockArea>,
+        cx: &mut ViewContext<V>,
+    ) -> AutosaveHandle {
+        let mut files = vec![(self.path, Snapshot::default())];
+        let session = self.session.map(|(path, dump)| {
+            files.push((path, Snapshot::default()));
+            dump
+        });
+        let files = Arc::new(Mutex::new(files));
+        register_panic_hook(&files);
+
+        let inner = Rc::new(AutosaveInner {
+            files,
+            debounce: self.debounce,
+            session,
+            save_task: RefCell::new(None),
+        });
+
+        let subscription = cx.subscribe(dock_area, {
+            let inner = inner.clone();
+            move |_, dock_area, event: &DockEvent, cx| {
+                let DockEvent::LayoutChanged = event else {
+                    return;
+                };
+
+                let layout = dock_area.read(cx).dump(cx);
+                inner.update(LAYOUT, &layout, cx);
+                if let Some(dump) = inner.session.clone() {
+                    inner.update(SESSION, &dump(cx), cx);
+                }
+            }
+        });
+
+        AutosaveHandle {
+            inner,
+            _subscription: subscription,
+        }
+    }
+}
+
+struct AutosaveInner {
+    files: Arc<Mutex<Files>>,
+    debounce: Duration,
+    session: Option<SessionDump>,
+    save_task: RefCell<Option<Task<()>>>,
+}
+
+impl AutosaveInner {
+    /// Update the file at `ix`, and save the files after the debounce if it's changed.
+    fn update(&self, ix: usize, value: &impl Serialize, cx: &AppContext) {
+        let json = match serde_json::to_string_pretty(value) {
+            Ok(json) => json,
+            Err(err) => {
+                log::error!("Failed to serialize the autosave state: {:?}", err);
+                return;
+            }
+        };
+        if !lock(&self.files)[ix].1.update(json) {
+            return;
+        }
+
+        let files = self.files.clone();
+        let debounce = self.debounce;
+        let task = cx.background_executor().spawn(async move {
+            Timer::after(debounce).await;
+            save_files(&mut lock(&files));
+        });
+        // Replace the pending save to debounce.
+        self.save_task.borrow_mut().replace(task);
+    }
+}
+
+/// The handle returned by [`Autosave::watch`], the pending changes are saved when dropped.
+pub struct AutosaveHandle {
+    inner: Rc<AutosaveInner>,
+    _subscription: Subscription,
+}
+
+impl AutosaveHandle {
+    /// Save the session state after the debounce, call it when the session state is changed.
+    pub fn save_session(&self, cx: &AppContext) {
+        if let Some(dump) = self.inner.session.clone() {
+            self.inner.update(SESSION, &dump(cx), cx);
+        }
+    }
+}
+
+impl Drop for AutosaveHandle {
+    fn drop(&mut self) {
+        self.inner.save_task.borrow_mut().take();
+        save_files(&mut lock(&self.inner.files));
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// The files of all the watching autosaves, to save on panic.
+static PANIC_FILES: Mutex<Vec<Weak<Mutex<Files>>>> = Mutex::new(Vec::new());
+
+/// Install the panic hook once to save the pending files, the previous hook is called after saved.
+fn register_panic_hook(files: &Arc<Mutex<Files>>) {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let prev_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            // Use `try_lock` to avoid the deadlock if the panic occurs while saving.
+            if let Ok(panic_files) = PANIC_FILES.try_lock() {
+                for files in panic_files.iter().filter_map(Weak::upgrade) {
+                    if let Ok(mut files) = files.try_lock() {
+                        save_files(&mut files);
+                    }
+                }
+            }
+            prev_hook(info);
+        }));
+    });
+
+    let mut panic_files = lock(&PANIC_FILES);
+    panic_files.retain(|files| files.strong_count() > 0);
+    panic_files.push(Arc::downgrade(files));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{save_files, tmp_path, write_atomic, Snapshot};
+
+    #[test]
+    fn test_tmp_path() {
+        assert_eq!(
+            tmp_path(Path::new("target/layout.json")),
+            Path::new("target/layout.json.tmp")
+        );
+    }
+
+    #[test]
+    fn test_write_atomic() {
+        let dir = std::env::temp_dir().join(format!("autosave-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("layout.json");
+
+        write_atomic(&path, "{}").unwrap();
+        write_atomic(&path, "{\"version\":1}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"version\":1}");
+        assert!(!tmp_path(&path).exists());
+
+        let mut snapshot = Snapshot::default();
+        assert!(!snapshot.save(&path).unwrap());
+        assert!(snapshot.update("{\"version\":2}".into()));
+        assert!(!snapshot.update("{\"version\":2}".into()));
+        assert!(snapshot.save(&path).unwrap());
+        assert!(!snapshot.save(&path).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"version\":2}");
+
+        // Failed to write into a missing directory, the target is untouched.
+        assert!(write_atomic(dir.join("missing/layout.json"), "{}").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_save_files() {
+        let dir = std::env::temp_dir().join(format!("autosave-files-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let layout_path = dir.join("layout.json");
+        let session_path = dir.join("session.json");
+
+        let mut files = vec![
+            (layout_path.clone(), Snapshot::default()),
+            (session_path.clone(), Snapshot::default()),
+        ];
+        files[1].1.update("{\"symbols\":[]}".into());
+        save_files(&mut files);
+        assert!(!layout_path.exists());
+        assert_eq!(
+            fs::read_to_string(&session_path).unwrap(),
+            "{\"symbols\":[]}"
+        );
+        assert!(files[1].1.saved);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -16,7 +16,7 @@ use crate::{
     AxisExt as _, StyledExt,
 };
 
-use super::{DockArea, DockItem, PanelView, TabPanel};
+use super::{DockArea, DockItem, LayoutMetrics, PanelView, TabPanel};
 
 #[derive(Clone, Render)]
 struct ResizePanel;
@@ -219,7 +219,9 @@ impl Dock {
     }
 
     pub fn toggle_open(&mut self, cx: &mut ViewContext<Self>) {
-        self.set_open(!self.open, cx);
+        let (placement, open) = (self.placement, !self.open);
+        LayoutMetrics::record(cx, |metrics| metrics.record_dock_toggled(placement, open));
+        self.set_open(open, cx);
     }
 
     /// Returns the size of the Dock, the size is means the width or height of
@@ -359,7 +361,11 @@ impl Dock {
         cx.notify();
     }
 
-    fn done_resizing(&mut self, _: &mut ViewContext<Self>) {
+    fn done_resizing(&mut self, cx: &mut ViewContext<Self>) {
+        if self.is_resizing {
+            let (placement, size) = (self.placement, self.size);
+            LayoutMetrics::record(cx, |metrics| metrics.record_dock_resized(placement, size));
+        }
         self.is_resizing = false;
     }
 }
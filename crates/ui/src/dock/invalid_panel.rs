@@ -1,26 +1,76 @@
+use std::sync::Arc;
+
 use gpui::{
-    AppContext, EventEmitter, FocusHandle, FocusableView, ParentElement as _, Render, SharedString,
-    Styled as _, WindowContext,
+    prelude::FluentBuilder as _, px, AppContext, EventEmitter, FocusHandle, FocusableView,
+    ParentElement as _, Render, SharedString, Styled as _, ViewContext, WeakView, WindowContext,
 };
+use rust_i18n::t;
 
-use crate::theme::ActiveTheme as _;
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    theme::ActiveTheme as _,
+    v_flex, Sizable as _,
+};
 
-use super::{Panel, PanelEvent, PanelState};
+use super::{DockArea, Panel, PanelEvent, PanelState, PanelView};
 
+/// The placeholder of the panel that is not registered in the [`PanelRegistry`](super::PanelRegistry),
+/// the state is kept to dump, so the layout is not lost.
 pub(crate) struct InvalidPanel {
-    name: SharedString,
+    pub(super) name: SharedString,
     focus_handle: FocusHandle,
-    old_state: PanelState,
+    pub(super) old_state: PanelState,
+    pub(super) dock_area: WeakView<DockArea>,
+    retry_failed: bool,
 }
 
 impl InvalidPanel {
-    pub(crate) fn new(name: &str, state: PanelState, cx: &mut WindowContext) -> Self {
+    pub(crate) fn new(
+        name: &str,
+        state: PanelState,
+        dock_area: WeakView<DockArea>,
+        cx: &mut WindowContext,
+    ) -> Self {
         Self {
             focus_handle: cx.focus_handle(),
             name: SharedString::from(name.to_owned()),
             old_state: state,
+            dock_area,
+            retry_failed: false,
         }
     }
+
+    fn remove(&mut self, cx: &mut ViewContext<Self>) {
+        let panel: Arc<dyn PanelView> = Arc::new(cx.view().clone());
+        let dock_area = self.dock_area.clone();
+        // Defer to the next cycle, the TabPanel can't be updated while this panel is updating.
+        cx.spawn(|_, mut cx| async move {
+            _ = cx.update(|cx| {
+                _ = dock_area.update(cx, |dock_area, cx| dock_area.remove_panel(panel, cx));
+            });
+        })
+        .detach();
+    }
+
+    fn retry(&mut self, cx: &mut ViewContext<Self>) {
+        let dock_area = self.dock_area.clone();
+        cx.spawn(|view, mut cx| async move {
+            _ = cx.update(|cx| {
+                let Some(this) = view.upgrade() else {
+                    return;
+                };
+                let rebuilt = dock_area
+                    .update(cx, |dock_area, cx| dock_area.retry_invalid_panel(&this, cx))
+                    .unwrap_or(false);
+                this.update(cx, |this, cx| {
+                    this.retry_failed = !rebuilt;
+                    cx.notify();
+                });
+            });
+        })
+        .detach();
+    }
 }
 impl Panel for InvalidPanel {
     fn panel_name(&self) -> &'static str {
@@ -46,10 +96,54 @@ impl Render for InvalidPanel {
             .flex_col()
             .items_center()
             .justify_center()
-            .text_color(cx.theme().muted_foreground)
-            .child(format!(
-                "The `{}` panel type is not registered in PanelRegistry.",
-                self.name.clone()
-            ))
+            .child(
+                v_flex()
+                    .max_w_96()
+                    .p_4()
+                    .gap_3()
+                    .rounded(px(cx.theme().radius))
+                    .border_1()
+                    .border_color(cx.theme().border)
+                    .bg(cx.theme().background)
+                    .child(
+                        gpui::div()
+                            .font_semibold()
+                            .child(t!("Dock.Missing Panel").to_string()),
+                    )
+                    .child(
+                        gpui::div()
+                            .text_sm()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                t!("Dock.Missing Panel Message", name = self.name.clone())
+                                    .to_string(),
+                            ),
+                    )
+                    .when(self.retry_failed, |this| {
+                        this.child(
+                            gpui::div()
+                                .text_sm()
+                                .text_color(cx.theme().destructive)
+                                .child(t!("Dock.Missing Panel Retry Failed").to_string()),
+                        )
+                    })
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .child(
+                                Button::new("retry")
+                                    .small()
+                                    .primary()
+                                    .label(t!("Dock.Retry").to_string())
+                                    .on_click(cx.listener(|this, _, cx| this.retry(cx))),
+                            )
+                            .child(
+                                Button::new("remove")
+                                    .small()
+                                    .label(t!("Dock.Remove from Layout").to_string())
+                                    .on_click(cx.listener(|this, _, cx| this.remove(cx))),
+                            ),
+                    ),
+            )
     }
 }
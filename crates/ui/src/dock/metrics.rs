@@ -0,0 +1,310 @@
+//! Opt-in layout usage metrics, used to propose an optimized default layout.
+//!
+//! All the data are kept in memory, the application can export them
+//! to persist locally, and load them back in the next session.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context as _, Result};
+use gpui::{px, AppContext, Global, Pixels};
+use serde::{Deserialize, Serialize};
+
+use super::{DockAreaState, DockPlacement, DockState};
+
+/// The minimum number of the samples before the suggestions are made.
+const MIN_SAMPLES: usize = 3;
+/// The size difference less than this will not be suggested to resize.
+const MIN_SIZE_DELTA: Pixels = px(20.);
+
+/// The usage of a panel, grouped by the `panel_name`.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PanelMetrics {
+    /// The times of the panel activated by the user.
+    pub activations: usize,
+}
+
+/// The usage of a dock.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DockMetrics {
+    /// The times of the dock resized by the user.
+    pub resizes: usize,
+    /// The sum of the sizes after each resize, used to get the average size.
+    pub total_size: f32,
+    /// The times of the dock opened by the user.
+    pub opens: usize,
+    /// The times of the dock closed by the user.
+    pub closes: usize,
+}
+
+impl DockMetrics {
+    /// Returns the average size after resizing, if the dock has been resized.
+    pub fn average_size(&self) -> Option<Pixels> {
+        if self.resizes == 0 {
+            return None;
+        }
+
+        Some(px(self.total_size / self.resizes as f32))
+    }
+}
+
+/// The aggregate of the layout usage.
+///
+/// The recording is disabled by default, use [`LayoutMetrics::set_enabled`] to opt-in.
+///
+/// ```ignore
+/// LayoutMetrics::set_enabled(true, cx);
+///
+/// let state = dock_area.read(cx).dump(cx);
+/// let suggestions = LayoutMetrics::global(cx).suggestions(&state);
+/// if !suggestions.is_empty() {
+///     // "You always shrink the right dock — apply this layout?"
+///     let state = LayoutSuggestion::apply_all(&suggestions, state);
+///     dock_area.update(cx, |dock_area, cx| dock_area.load(state, cx))?;
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutMetrics {
+    #[serde(default)]
+    pub panels: BTreeMap<String, PanelMetrics>,
+    #[serde(default)]
+    pub left_dock: DockMetrics,
+    #[serde(default)]
+    pub bottom_dock: DockMetrics,
+    #[serde(default)]
+    pub right_dock: DockMetrics,
+}
+
+#[derive(Default)]
+struct LayoutMetricsState {
+    enabled: bool,
+    metrics: LayoutMetrics,
+}
+
+impl Global for LayoutMetricsState {}
+
+/// A suggestion to change the layout, made by the [`LayoutMetrics`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutSuggestion {
+    /// The user always resize the dock to the size.
+    ResizeDock {
+        placement: DockPlacement,
+        size: Pixels,
+    },
+    /// The user always close the dock.
+    CloseDock { placement: DockPlacement },
+}
+
+impl LayoutSuggestion {
+    /// Apply the suggestion to the layout state.
+    pub fn apply(&self, state: &mut DockAreaState) {
+        match *self {
+            Self::ResizeDock { placement, size } => {
+                if let Some(dock) = dock_state_mut(state, placement) {
+                    dock.size = size;
+                }
+            }
+            Self::CloseDock { placement } => {
+                if let Some(dock) = dock_state_mut(state, placement) {
+                    dock.open = false;
+                }
+            }
+        }
+    }
+
+    /// Apply all the suggestions, and return the new layout state.
+    pub fn apply_all(suggestions: &[Self], mut state: DockAreaState) -> DockAreaState {
+        for suggestion in suggestions {
+            suggestion.apply(&mut state);
+        }
+        state
+    }
+}
+
+fn dock_state_mut(state: &mut DockAreaState, placement: DockPlacement) -> Option<&mut DockState> {
+    match placement {
+        DockPlacement::Left => state.left_dock.as_mut(),
+        DockPlacement::Bottom => state.bottom_dock.as_mut(),
+        DockPlacement::Right => state.right_dock.as_mut(),
+        DockPlacement::Center => None,
+    }
+}
+
+impl LayoutMetrics {
+    /// Enable or disable the recording, the recorded metrics are kept when disabled.
+    pub fn set_enabled(enabled: bool, cx: &mut AppContext) {
+        cx.default_global::<LayoutMetricsState>().enabled = enabled;
+    }
+
+    /// Returns true if the recording is enabled.
+    pub fn is_enabled(cx: &AppContext) -> bool {
+        cx.try_global::<LayoutMetricsState>()
+            .map(|state| state.enabled)
+            .unwrap_or(false)
+    }
+
+    /// Returns the recorded metrics.
+    pub fn global(cx: &AppContext) -> LayoutMetrics {
+        cx.try_global::<LayoutMetricsState>()
+            .map(|state| state.metrics.clone())
+            .unwrap_or_default()
+    }
+
+    /// Load the metrics exported by [`LayoutMetrics::export`], the current metrics will be replaced.
+    pub fn load(json: &str, cx: &mut AppContext) -> Result<()> {
+        let metrics =
+            serde_json::from_str::<LayoutMetrics>(json).context("invalid layout metrics")?;
+        cx.default_global::<LayoutMetricsState>().metrics = metrics;
+        Ok(())
+    }
+
+    /// Export the metrics as JSON, to persist it locally.
+    pub fn export(cx: &AppContext) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::global(cx))?)
+    }
+
+    /// Clear all the recorded metrics.
+    pub fn reset(cx: &mut AppContext) {
+        cx.default_global::<LayoutMetricsState>().metrics = LayoutMetrics::default();
+    }
+
+    /// Record the metrics if the recording is enabled.
+    pub(crate) fn record(cx: &mut AppContext, f: impl FnOnce(&mut LayoutMetrics)) {
+        if !Self::is_enabled(cx) {
+            return;
+        }
+
+        f(&mut cx.global_mut::<LayoutMetricsState>().metrics);
+    }
+
+    pub(crate) fn record_panel_activated(&mut self, panel_name: &str) {
+        self.panels
+            .entry(panel_name.to_string())
+            .or_default()
+            .activations += 1;
+    }
+
+    pub(crate) fn record_dock_resized(&mut self, placement: DockPlacement, size: Pixels) {
+        if let Some(dock) = self.dock_mut(placement) {
+            dock.resizes += 1;
+            dock.total_size += size.0;
+        }
+    }
+
+    pub(crate) fn record_dock_toggled(&mut self, placement: DockPlacement, open: bool) {
+        if let Some(dock) = self.dock_mut(placement) {
+            if open {
+                dock.opens += 1;
+            } else {
+                dock.closes += 1;
+            }
+        }
+    }
+
+    /// Returns the metrics of the dock.
+    pub fn dock(&self, placement: DockPlacement) -> Option<&DockMetrics> {
+        match placement {
+            DockPlacement::Left => Some(&self.left_dock),
+            DockPlacement::Bottom => Some(&self.bottom_dock),
+            DockPlacement::Right => Some(&self.right_dock),
+            DockPlacement::Center => None,
+        }
+    }
+
+    fn dock_mut(&mut self, placement: DockPlacement) -> Option<&mut DockMetrics> {
+        match placement {
+            DockPlacement::Left => Some(&mut self.left_dock),
+            DockPlacement::Bottom => Some(&mut self.bottom_dock),
+            DockPlacement::Right => Some(&mut self.right_dock),
+            DockPlacement::Center => None,
+        }
+    }
+
+    /// Returns the panel names and the activations, the most used first.
+    pub fn most_used_panels(&self) -> Vec<(&str, usize)> {
+        let mut panels = self
+            .panels
+            .iter()
+            .map(|(name, metrics)| (name.as_str(), metrics.activations))
+            .collect::<Vec<_>>();
+        panels.sort_by(|a, b| b.1.cmp(&a.1));
+        panels
+    }
+
+    /// Returns the suggestions to change the layout state, based on the recorded metrics.
+    ///
+    /// - If the dock is resized at least 3 times, suggest the average size.
+    /// - If the dock is closed at least 3 times more than opened, suggest to close it.
+    pub fn suggestions(&self, state: &DockAreaState) -> Vec<LayoutSuggestion> {
+        let mut suggestions = vec![];
+
+        for (placement, dock) in [
+            (DockPlacement::Left, &state.left_dock),
+            (DockPlacement::Bottom, &state.bottom_dock),
+            (DockPlacement::Right, &state.right_dock),
+        ] {
+            let (Some(dock), Some(metrics)) = (dock, self.dock(placement)) else {
+                continue;
+            };
+
+            if metrics.resizes >= MIN_SAMPLES {
+                if let Some(size) = metrics.average_size() {
+                    if (size - dock.size).abs() >= MIN_SIZE_DELTA {
+                        suggestions.push(LayoutSuggestion::ResizeDock { placement, size });
+                    }
+                }
+            }
+
+            if dock.open && metrics.closes >= metrics.opens + MIN_SAMPLES {
+                suggestions.push(LayoutSuggestion::CloseDock { placement });
+            }
+        }
+
+        suggestions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::*;
+
+    #[test]
+    fn test_layout_suggestions() {
+        let json = include_str!("../../tests/fixtures/layout.json");
+        let state: DockAreaState = serde_json::from_str(json).unwrap();
+
+        let mut metrics = LayoutMetrics::default();
+        assert_eq!(metrics.suggestions(&state), vec![]);
+
+        for size in [240., 250., 260.] {
+            metrics.record_dock_resized(DockPlacement::Right, px(size));
+        }
+        // The size is close to the current size.
+        metrics.record_dock_resized(DockPlacement::Left, px(340.));
+        for _ in 0..3 {
+            metrics.record_dock_toggled(DockPlacement::Bottom, false);
+        }
+        metrics.record_panel_activated("StoryContainer");
+
+        let suggestions = metrics.suggestions(&state);
+        assert_eq!(
+            suggestions,
+            vec![
+                LayoutSuggestion::CloseDock {
+                    placement: DockPlacement::Bottom
+                },
+                LayoutSuggestion::ResizeDock {
+                    placement: DockPlacement::Right,
+                    size: px(250.)
+                },
+            ]
+        );
+        assert_eq!(metrics.most_used_panels(), vec![("StoryContainer", 1)]);
+
+        let state = LayoutSuggestion::apply_all(&suggestions, state);
+        assert_eq!(state.bottom_dock.as_ref().unwrap().open, false);
+        assert_eq!(state.right_dock.as_ref().unwrap().size, px(250.));
+        assert_eq!(metrics.suggestions(&state), vec![]);
+    }
+}
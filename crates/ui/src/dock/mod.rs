@@ -1,32 +1,84 @@
+mod activation;
+mod autosave;
 mod dock;
 mod invalid_panel;
+mod metrics;
 mod panel;
+mod panel_window;
 mod stack_panel;
 mod state;
 mod tab_panel;
 mod tiles;
 
+use activation::{panel_id, ActivationHistory, PanelSwitcher};
 use anyhow::Result;
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, AnyElement, AnyView, AppContext, Axis, Bounds,
-    Edges, Entity as _, EntityId, EventEmitter, InteractiveElement as _, IntoElement,
-    ParentElement as _, Pixels, Render, SharedString, Styled, Subscription, View, ViewContext,
-    VisualContext, WeakView, WindowContext,
+    actions, canvas, div, impl_actions, prelude::FluentBuilder, AnyElement, AnyView, AppContext,
+    Axis, Bounds, Edges, Entity as _, EntityId, EventEmitter, InteractiveElement as _, IntoElement,
+    KeyDownEvent, ModifiersChangedEvent, ParentElement as _, Pixels, Render, SharedString, Styled,
+    Subscription, View, ViewContext, VisualContext, WeakView, WindowContext,
 };
+use invalid_panel::InvalidPanel;
+use serde::Deserialize;
 use std::sync::Arc;
 
+pub use autosave::*;
 pub use dock::*;
+pub use metrics::*;
 pub use panel::*;
 pub use stack_panel::*;
 pub use state::*;
 pub use tab_panel::*;
 pub use tiles::*;
 
+use crate::keymap::{self, KeymapEntry};
+
+const CONTEXT: &str = "DockArea";
+
 pub fn init(cx: &mut AppContext) {
     cx.set_global(PanelRegistry::new());
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("ctrl-tab", ActivatePreviousPanel, Some(CONTEXT)),
+            KeymapEntry::new("ctrl-shift-tab", ActivateNextPanel, Some(CONTEXT)),
+        ],
+    );
 }
 
-actions!(dock, [ToggleZoom, ClosePanel]);
+actions!(
+    dock,
+    [
+        ToggleZoom,
+        ClosePanel,
+        TogglePin,
+        OpenInWindow,
+        CloseAllTabs,
+        ActivatePreviousPanel,
+        ActivateNextPanel
+    ]
+);
+
+/// Activate the panel at the given index in the [`TabPanel`](TabPanel).
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct ActivatePanel(pub usize);
+
+/// Close the tab at the given index in the [`TabPanel`](TabPanel).
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct CloseTab(pub usize);
+
+/// Close all the tabs except the one at the given index, the pinned tabs are kept.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct CloseOtherTabs(pub usize);
+
+/// Close the tabs at the right of the given index, the pinned tabs are kept.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct CloseTabsToRight(pub usize);
+
+impl_actions!(
+    dock,
+    [ActivatePanel, CloseTab, CloseOtherTabs, CloseTabsToRight]
+);
 
 pub enum DockEvent {
     /// The layout of the dock has changed, subscribers this to save the layout.
@@ -34,6 +86,15 @@ pub enum DockEvent {
     /// This event is emitted when every time the layout of the dock has changed,
     /// So it emits may be too frequently, you may want to debounce the event.
     LayoutChanged,
+    /// The loaded layout has a panel that is not registered in the [`PanelRegistry`],
+    /// a placeholder is displayed instead, and the `state` is kept to dump.
+    ///
+    /// Subscribe this to log or repair the layout, the placeholder can be removed by
+    /// [`DockArea::remove_panel`], and it's rebuilt once the panel is registered by [`register_panel`].
+    InvalidPanel {
+        panel_name: SharedString,
+        state: PanelState,
+    },
 }
 
 /// The main area of the dock.
@@ -64,6 +125,13 @@ pub struct DockArea {
     /// The panel style, default is [`PanelStyle::Default`](PanelStyle::Default).
     pub(crate) panel_style: PanelStyle,
 
+    /// The panels in the order of the recent activation.
+    activation_history: ActivationHistory<EntityId>,
+    /// The overlay to select a recent panel, shown while holding the modifiers.
+    switcher: Option<PanelSwitcher>,
+    /// The panels opened in the separate windows, see [`OpenInWindow`].
+    detached_panels: Vec<Arc<dyn PanelView>>,
+
     _subscriptions: Vec<Subscription>,
 }
 
@@ -355,6 +423,17 @@ impl DockItem {
         }
     }
 
+    /// Returns the active panel, the active panel of the left-most and top-most TabPanel for the split layout.
+    pub(crate) fn active_panel(&self, cx: &AppContext) -> Option<Arc<dyn PanelView>> {
+        match self {
+            DockItem::Panel { view } => Some(view.clone()),
+            DockItem::Tabs { .. } | DockItem::Split { .. } => self
+                .left_top_tab_panel(cx)
+                .and_then(|tab_panel| tab_panel.read(cx).active_panel(cx)),
+            DockItem::Tiles { .. } => None,
+        }
+    }
+
     /// Recursively traverses to find the left-most and top-most TabPanel.
     pub(crate) fn left_top_tab_panel(&self, cx: &AppContext) -> Option<View<TabPanel>> {
         match self {
@@ -403,10 +482,18 @@ impl DockArea {
             bottom_dock: None,
             is_locked: false,
             panel_style: PanelStyle::Default,
+            activation_history: ActivationHistory::new(),
+            switcher: None,
+            detached_panels: vec![],
             _subscriptions: vec![],
         };
 
         this.subscribe_panel(&stack_panel, cx);
+        // Rebuild the placeholders once the missing panels are registered, e.g.: a plugin is loaded.
+        this._subscriptions
+            .push(cx.observe_global::<PanelRegistry>(|this, cx| {
+                this.retry_invalid_panels(cx);
+            }));
 
         this
     }
@@ -608,6 +695,21 @@ impl DockArea {
         }
     }
 
+    /// Open or close the dock at the placement, unlike [`DockArea::toggle_dock`],
+    /// this is not recorded as the user action in the [`LayoutMetrics`].
+    pub fn set_dock_open(&self, placement: DockPlacement, open: bool, cx: &mut WindowContext) {
+        let dock = match placement {
+            DockPlacement::Left => &self.left_dock,
+            DockPlacement::Bottom => &self.bottom_dock,
+            DockPlacement::Right => &self.right_dock,
+            DockPlacement::Center => return,
+        };
+
+        if let Some(dock) = dock {
+            dock.update(cx, |dock, cx| dock.set_open(open, cx));
+        }
+    }
+
     /// Add a panel item to the dock area at the given placement.
     ///
     /// If the left, bottom, right dock is not present, it will set the dock at the placement.
@@ -680,11 +782,277 @@ impl DockArea {
             self.bottom_dock = Some(bottom_dock_state.to_dock(weak_self.clone(), cx));
         }
 
-        self.items = state.center.to_item(weak_self, cx);
+        self.items = state.center.to_item(weak_self.clone(), cx);
+        // The panels were in the separate windows when saved, add them back to the center.
+        for detached in state.detached {
+            if let DockItem::Tabs { items, .. } = detached.to_item(weak_self.clone(), cx) {
+                for panel in items {
+                    self.add_panel(panel, DockPlacement::Center, cx);
+                }
+            }
+        }
         self.update_toggle_button_tab_panels(cx);
+
+        for view in self.invalid_panels(cx) {
+            let panel = view.read(cx);
+            let event = DockEvent::InvalidPanel {
+                panel_name: panel.name.clone(),
+                state: panel.old_state.clone(),
+            };
+            cx.emit(event);
+        }
         Ok(())
     }
 
+    /// Returns the root panels of the center and the docks.
+    fn root_panels(&self, cx: &AppContext) -> Vec<Arc<dyn PanelView>> {
+        let mut panels = vec![self.items.view()];
+        for dock in [&self.left_dock, &self.bottom_dock, &self.right_dock]
+            .into_iter()
+            .flatten()
+        {
+            panels.push(dock.read(cx).panel.view());
+        }
+        panels
+    }
+
+    fn invalid_panels(&self, cx: &AppContext) -> Vec<View<InvalidPanel>> {
+        let mut panels = vec![];
+        for root in self.root_panels(cx) {
+            each_panel(&root, cx, &mut |panel| {
+                if let Ok(view) = panel.view().downcast::<InvalidPanel>() {
+                    panels.push(view);
+                }
+            });
+        }
+        panels
+    }
+
+    /// Remove the `panel` from the TabPanel it belongs to.
+    pub fn remove_panel(&self, panel: Arc<dyn PanelView>, cx: &mut WindowContext) {
+        let tab_panel = self
+            .root_panels(cx)
+            .iter()
+            .find_map(|root| find_tab_panel(root, &panel, cx));
+        if let Some(tab_panel) = tab_panel {
+            tab_panel.update(cx, |tab_panel, cx| tab_panel.remove_panel(panel, cx));
+        }
+    }
+
+    /// Return the `panel` from the separate window to the `tab_panel` at `ix`,
+    /// or to the center if the `tab_panel` is no longer in the layout.
+    pub(crate) fn return_panel(
+        &mut self,
+        panel: Arc<dyn PanelView>,
+        tab_panel: WeakView<TabPanel>,
+        ix: usize,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.detached_panels.retain(|p| p.view() != panel.view());
+        let tab_panel = tab_panel.upgrade().filter(|tab_panel| {
+            let view: AnyView = tab_panel.clone().into();
+            self.root_panels(cx)
+                .iter()
+                .any(|root| contains_view(root, &view, cx))
+        });
+
+        match tab_panel {
+            Some(tab_panel) => tab_panel.update(cx, |tab_panel, cx| {
+                let ix = ix.min(tab_panel.panels.len());
+                tab_panel.insert_panel_at(panel, ix, cx);
+            }),
+            None => self.add_panel(panel, DockPlacement::Center, cx),
+        }
+        cx.emit(DockEvent::LayoutChanged);
+    }
+
+    /// Record the `panel` moved into a separate window, to keep it in the [`DockArea::dump`].
+    pub(crate) fn detach_panel(&mut self, panel: Arc<dyn PanelView>) {
+        self.detached_panels.push(panel);
+    }
+
+    /// Record the `panel` as the most recently activated, called when the panel is activated or clicked.
+    pub(crate) fn record_activation(&mut self, panel: &Arc<dyn PanelView>) {
+        self.activation_history.record(panel_id(panel));
+    }
+
+    /// Returns the panels in the order of the recent activation, the closed panels are removed.
+    pub fn recent_panels(&mut self, cx: &AppContext) -> Vec<Arc<dyn PanelView>> {
+        let mut panels = std::collections::HashMap::new();
+        for root in self.root_panels(cx) {
+            each_panel(&root, cx, &mut |panel| {
+                panels.insert(panel_id(panel), panel.clone());
+            });
+        }
+
+        self.activation_history.retain(|id| panels.contains_key(id));
+        self.activation_history
+            .items()
+            .iter()
+            .filter_map(|id| panels.get(id).cloned())
+            .collect()
+    }
+
+    /// Activate the `panel` and focus it, the dock of the panel will be opened if closed.
+    pub fn activate_panel(&mut self, panel: &Arc<dyn PanelView>, cx: &mut ViewContext<Self>) {
+        let view = panel.view();
+        for placement in [
+            DockPlacement::Left,
+            DockPlacement::Bottom,
+            DockPlacement::Right,
+        ] {
+            let dock = match placement {
+                DockPlacement::Left => &self.left_dock,
+                DockPlacement::Bottom => &self.bottom_dock,
+                _ => &self.right_dock,
+            };
+            let Some(dock) = dock else {
+                continue;
+            };
+            if contains_view(&dock.read(cx).panel.view(), &view, cx)
+                && !self.is_dock_open(placement, cx)
+            {
+                self.set_dock_open(placement, true, cx);
+            }
+        }
+
+        let tab_panel = self
+            .root_panels(cx)
+            .iter()
+            .find_map(|root| find_tab_panel(root, panel, cx));
+        match tab_panel {
+            Some(tab_panel) => tab_panel.update(cx, |tab_panel, cx| {
+                tab_panel.activate_panel(panel, cx);
+            }),
+            None => panel.focus_handle(cx).focus(cx),
+        }
+        self.record_activation(panel);
+    }
+
+    /// Switch to the previous activated panel, like the `ctrl-tab` in the editors.
+    ///
+    /// While holding the modifiers, a list of the recent panels is shown, press again to select
+    /// the older one, and release the modifiers to activate the selected panel.
+    pub fn activate_previous_panel(&mut self, cx: &mut ViewContext<Self>) {
+        self.cycle_recent_panels(true, cx);
+    }
+
+    /// Same as [`DockArea::activate_previous_panel`], but select the newer one.
+    pub fn activate_next_panel(&mut self, cx: &mut ViewContext<Self>) {
+        self.cycle_recent_panels(false, cx);
+    }
+
+    fn cycle_recent_panels(&mut self, forward: bool, cx: &mut ViewContext<Self>) {
+        if let Some(switcher) = self.switcher.as_mut() {
+            switcher.select(forward);
+            cx.notify();
+            return;
+        }
+
+        let panels = self.recent_panels(cx);
+        if panels.len() < 2 {
+            return;
+        }
+
+        let switcher = PanelSwitcher::new(panels, forward);
+        // Activate directly if the modifiers are not holding, e.g.: called from the menu.
+        if !cx.modifiers().modified() {
+            if let Some(panel) = switcher.selected() {
+                self.activate_panel(&panel, cx);
+            }
+            return;
+        }
+
+        self.switcher = Some(switcher);
+        cx.notify();
+    }
+
+    fn confirm_switcher(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(switcher) = self.switcher.take() else {
+            return;
+        };
+
+        if let Some(panel) = switcher.selected() {
+            self.activate_panel(&panel, cx);
+        }
+        cx.notify();
+    }
+
+    pub(super) fn select_switcher_panel(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(switcher) = self.switcher.as_mut() {
+            switcher.selected_ix = ix;
+        }
+        self.confirm_switcher(cx);
+    }
+
+    fn on_action_activate_previous_panel(
+        &mut self,
+        _: &ActivatePreviousPanel,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.activate_previous_panel(cx);
+    }
+
+    fn on_action_activate_next_panel(&mut self, _: &ActivateNextPanel, cx: &mut ViewContext<Self>) {
+        self.activate_next_panel(cx);
+    }
+
+    fn on_modifiers_changed(&mut self, event: &ModifiersChangedEvent, cx: &mut ViewContext<Self>) {
+        if self.switcher.is_some() && !event.modifiers.modified() {
+            self.confirm_switcher(cx);
+        }
+    }
+
+    fn on_key_down(&mut self, event: &KeyDownEvent, cx: &mut ViewContext<Self>) {
+        if self.switcher.is_some() && event.keystroke.key == "escape" {
+            self.switcher = None;
+            cx.stop_propagation();
+            cx.notify();
+        }
+    }
+
+    /// Rebuild the placeholders of the unregistered panels, e.g.: after the plugin that
+    /// provides the panels is loaded.
+    ///
+    /// Returns the number of the panels rebuilt, the panels still not registered are kept.
+    pub fn retry_invalid_panels(&self, cx: &mut ViewContext<Self>) -> usize {
+        let mut count = 0;
+        for view in self.invalid_panels(cx) {
+            if self.retry_invalid_panel(&view, cx) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub(crate) fn retry_invalid_panel(
+        &self,
+        view: &View<InvalidPanel>,
+        cx: &mut WindowContext,
+    ) -> bool {
+        let old: Arc<dyn PanelView> = Arc::new(view.clone());
+        let Some(tab_panel) = self
+            .root_panels(cx)
+            .iter()
+            .find_map(|root| find_tab_panel(root, &old, cx))
+        else {
+            return false;
+        };
+
+        let state = view.read(cx).old_state.clone();
+        let state = cx
+            .global::<PanelRegistry>()
+            .migrate(&state)
+            .unwrap_or(state);
+        let Some(panel) = state.build_panel(view.read(cx).dock_area.clone(), cx) else {
+            return false;
+        };
+
+        let panel: Arc<dyn PanelView> = panel.into();
+        tab_panel.update(cx, |tab_panel, cx| tab_panel.replace_panel(old, panel, cx));
+        true
+    }
+
     /// Dump the dock panels layout to PanelState.
     ///
     /// See also [DockArea::load].
@@ -711,6 +1079,11 @@ impl DockArea {
             left_dock,
             right_dock,
             bottom_dock,
+            detached: self
+                .detached_panels
+                .iter()
+                .map(|panel| panel.dump(cx))
+                .collect(),
         }
     }
 
@@ -792,6 +1165,7 @@ impl DockArea {
                 .detach();
                 cx.emit(DockEvent::LayoutChanged);
             }
+            PanelEvent::SizesChanged => {}
         });
 
         self._subscriptions.push(subscription);
@@ -802,6 +1176,19 @@ impl DockArea {
         self.id.clone()
     }
 
+    /// Returns all panels that have unsaved changes, see [`Panel::is_dirty`].
+    pub fn dirty_panels(&self, cx: &AppContext) -> Vec<Arc<dyn PanelView>> {
+        let mut panels = vec![];
+        for root in self.root_panels(cx) {
+            each_panel(&root, cx, &mut |panel| {
+                if panel.is_dirty(cx) {
+                    panels.push(panel.clone());
+                }
+            });
+        }
+        panels
+    }
+
     pub fn set_zoomed_in<P: Panel>(&mut self, panel: View<P>, cx: &mut ViewContext<Self>) {
         self.zoom_view = Some(panel.into());
         cx.notify();
@@ -842,13 +1229,109 @@ impl DockArea {
             .map(|view| view.entity_id());
     }
 }
+/// Call `f` with all the leaf panels in the `panel`, the StackPanel, TabPanel and Tiles are walked into.
+fn each_panel(
+    panel: &Arc<dyn PanelView>,
+    cx: &AppContext,
+    f: &mut impl FnMut(&Arc<dyn PanelView>),
+) {
+    let view = panel.view();
+    if let Ok(stack_panel) = view.clone().downcast::<StackPanel>() {
+        for panel in stack_panel.read(cx).panels.iter() {
+            each_panel(panel, cx, f);
+        }
+    } else if let Ok(tab_panel) = view.clone().downcast::<TabPanel>() {
+        for panel in tab_panel.read(cx).panels.iter() {
+            each_panel(panel, cx, f);
+        }
+    } else if let Ok(tiles) = view.downcast::<Tiles>() {
+        for item in tiles.read(cx).panels.iter() {
+            each_panel(&item.panel, cx, f);
+        }
+    } else {
+        f(panel);
+    }
+}
+
+/// Returns true if the `view` is the `panel` or in the `panel`.
+fn contains_view(panel: &Arc<dyn PanelView>, view: &AnyView, cx: &AppContext) -> bool {
+    let panel_view = panel.view();
+    if &panel_view == view {
+        return true;
+    }
+
+    if let Ok(stack_panel) = panel_view.clone().downcast::<StackPanel>() {
+        stack_panel
+            .read(cx)
+            .panels
+            .iter()
+            .any(|panel| contains_view(panel, view, cx))
+    } else if let Ok(tab_panel) = panel_view.clone().downcast::<TabPanel>() {
+        tab_panel
+            .read(cx)
+            .panels
+            .iter()
+            .any(|panel| contains_view(panel, view, cx))
+    } else if let Ok(tiles) = panel_view.downcast::<Tiles>() {
+        tiles
+            .read(cx)
+            .panels
+            .iter()
+            .any(|item| contains_view(&item.panel, view, cx))
+    } else {
+        false
+    }
+}
+
+/// Find the TabPanel that contains the `target` panel in the `panel`.
+fn find_tab_panel(
+    panel: &Arc<dyn PanelView>,
+    target: &Arc<dyn PanelView>,
+    cx: &AppContext,
+) -> Option<View<TabPanel>> {
+    let view = panel.view();
+    if let Ok(stack_panel) = view.clone().downcast::<StackPanel>() {
+        stack_panel
+            .read(cx)
+            .panels
+            .iter()
+            .find_map(|panel| find_tab_panel(panel, target, cx))
+    } else if let Ok(tab_panel) = view.clone().downcast::<TabPanel>() {
+        let target_view = target.view();
+        if tab_panel
+            .read(cx)
+            .panels
+            .iter()
+            .any(|panel| panel.view() == target_view)
+        {
+            Some(tab_panel)
+        } else {
+            None
+        }
+    } else if let Ok(tiles) = view.downcast::<Tiles>() {
+        tiles
+            .read(cx)
+            .panels
+            .iter()
+            .find_map(|item| find_tab_panel(&item.panel, target, cx))
+    } else {
+        None
+    }
+}
+
 impl EventEmitter<DockEvent> for DockArea {}
 impl Render for DockArea {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let view = cx.view().clone();
+        let switcher = self.switcher.as_ref().map(|switcher| switcher.render(cx));
 
         div()
             .id("dock-area")
+            .key_context(CONTEXT)
+            .on_action(cx.listener(Self::on_action_activate_previous_panel))
+            .on_action(cx.listener(Self::on_action_activate_next_panel))
+            .on_modifiers_changed(cx.listener(Self::on_modifiers_changed))
+            .capture_key_down(cx.listener(Self::on_key_down))
             .relative()
             .size_full()
             .overflow_hidden()
@@ -908,5 +1391,6 @@ impl Render for DockArea {
                     }
                 }
             })
+            .children(switcher)
     }
 }
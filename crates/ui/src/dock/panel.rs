@@ -1,9 +1,10 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
-use crate::{button::Button, popup_menu::PopupMenu};
+use crate::{button::Button, popup_menu::PopupMenu, Icon};
+use anyhow::Result;
 use gpui::{
     AnyElement, AnyView, AppContext, EventEmitter, FocusHandle, FocusableView, Global, Hsla,
-    IntoElement, SharedString, View, ViewContext, WeakView, WindowContext,
+    IntoElement, SharedString, Task, View, ViewContext, WeakView, WindowContext,
 };
 
 use rust_i18n::t;
@@ -14,6 +15,9 @@ pub enum PanelEvent {
     ZoomIn,
     ZoomOut,
     LayoutChanged,
+    /// The sizes of the panels in the [`StackPanel`](super::StackPanel) have been changed,
+    /// by the user resizing or the [`StackPanel::set_panel_size`](super::StackPanel::set_panel_size).
+    SizesChanged,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,16 +43,39 @@ pub trait Panel: EventEmitter<PanelEvent> + FocusableView {
     /// Once you have defined a panel name, this must not be changed.
     fn panel_name(&self) -> &'static str;
 
+    /// The version of the panel state, saved in the [`PanelState::version`], default is `None`.
+    ///
+    /// Increase it when the info of the [`Panel::dump`] is changed, and register a migration
+    /// by [`register_panel_migration`] to upgrade the states saved by the old versions.
+    fn panel_version(&self) -> Option<usize> {
+        None
+    }
+
     /// The title of the panel
     fn title(&self, cx: &WindowContext) -> AnyElement {
         SharedString::from(t!("Dock.Unnamed")).into_any_element()
     }
 
+    /// The miniature of the panel content to show in the drag preview when dragging the tab,
+    /// and in the hover preview of the collapsed bottom dock,
+    /// default is `None` to show the icon as the placeholder.
+    ///
+    /// The preview is clipped into a small thumbnail, so render a lightweight summary of the content,
+    /// the panel view itself can't be rendered twice.
+    fn drag_preview(&self, cx: &WindowContext) -> Option<AnyElement> {
+        None
+    }
+
     /// The theme of the panel title, default is `None`.
     fn title_style(&self, cx: &AppContext) -> Option<TitleStyle> {
         None
     }
 
+    /// The icon of the panel, used to show the compact tab when the panel is pinned, default is `None`.
+    fn icon(&self, cx: &WindowContext) -> Option<Icon> {
+        None
+    }
+
     /// Whether the panel can be closed, default is `true`.
     ///
     /// This method called in Panel render, we should make sure it is fast.
@@ -85,6 +112,14 @@ pub trait Panel: EventEmitter<PanelEvent> + FocusableView {
     /// Only current Panel will touch this method.
     fn set_zoomed(&self, zoomed: bool, cx: &ViewContext<Self>) {}
 
+    /// Prefetch the content of the panel before it is activated.
+    ///
+    /// This method will be called when the user hovers the tab of the inactive panel for a while,
+    /// the data-heavy panel can start loading here to feel instant when the tab is clicked.
+    ///
+    /// It may be called multiple times, the panel should avoid loading the data repeatedly.
+    fn prefetch(&mut self, cx: &mut ViewContext<Self>) {}
+
     /// The addition popup menu of the panel, default is `None`.
     fn popup_menu(&self, this: PopupMenu, cx: &WindowContext) -> PopupMenu {
         this
@@ -99,6 +134,26 @@ pub trait Panel: EventEmitter<PanelEvent> + FocusableView {
     fn dump(&self, cx: &AppContext) -> PanelState {
         PanelState::new(self)
     }
+
+    /// Return true if the panel has unsaved changes, default is `false`.
+    ///
+    /// A dirty panel will ask to save the changes before closing the panel or the window.
+    fn is_dirty(&self, cx: &AppContext) -> bool {
+        false
+    }
+
+    /// Save the changes of the panel, this will be called when user choose to save in the confirm dialog.
+    fn save(&mut self, cx: &mut ViewContext<Self>) -> Task<Result<()>> {
+        Task::ready(Ok(()))
+    }
+
+    /// Return true to accept the files dropped from the OS on the panel content, default is `false`.
+    fn accept_file_drop(&self, cx: &AppContext) -> bool {
+        false
+    }
+
+    /// Handle the files dropped from the OS, only called when [`Panel::accept_file_drop`] returns true.
+    fn on_file_drop(&mut self, paths: &[PathBuf], cx: &mut ViewContext<Self>) {}
 }
 
 /// The PanelView trait used to define the panel view.
@@ -107,16 +162,23 @@ pub trait PanelView: 'static + Send + Sync {
     fn panel_name(&self, cx: &AppContext) -> &'static str;
     fn title(&self, cx: &WindowContext) -> AnyElement;
     fn title_style(&self, cx: &AppContext) -> Option<TitleStyle>;
+    fn drag_preview(&self, cx: &WindowContext) -> Option<AnyElement>;
+    fn icon(&self, cx: &WindowContext) -> Option<Icon>;
     fn closable(&self, cx: &AppContext) -> bool;
     fn zoomable(&self, cx: &AppContext) -> bool;
     fn visible(&self, cx: &AppContext) -> bool;
     fn set_active(&self, active: bool, cx: &mut WindowContext);
     fn set_zoomed(&self, zoomed: bool, cx: &mut WindowContext);
+    fn prefetch(&self, cx: &mut WindowContext);
     fn popup_menu(&self, menu: PopupMenu, cx: &WindowContext) -> PopupMenu;
     fn toolbar_buttons(&self, cx: &WindowContext) -> Vec<Button>;
     fn view(&self) -> AnyView;
     fn focus_handle(&self, cx: &AppContext) -> FocusHandle;
     fn dump(&self, cx: &AppContext) -> PanelState;
+    fn is_dirty(&self, cx: &AppContext) -> bool;
+    fn save(&self, cx: &mut WindowContext) -> Task<Result<()>>;
+    fn accept_file_drop(&self, cx: &AppContext) -> bool;
+    fn on_file_drop(&self, paths: &[PathBuf], cx: &mut WindowContext);
 }
 
 impl<T: Panel> PanelView for View<T> {
@@ -132,6 +194,14 @@ impl<T: Panel> PanelView for View<T> {
         self.read(cx).title_style(cx)
     }
 
+    fn drag_preview(&self, cx: &WindowContext) -> Option<AnyElement> {
+        self.read(cx).drag_preview(cx)
+    }
+
+    fn icon(&self, cx: &WindowContext) -> Option<Icon> {
+        self.read(cx).icon(cx)
+    }
+
     fn closable(&self, cx: &AppContext) -> bool {
         self.read(cx).closable(cx)
     }
@@ -156,6 +226,12 @@ impl<T: Panel> PanelView for View<T> {
         })
     }
 
+    fn prefetch(&self, cx: &mut WindowContext) {
+        self.update(cx, |this, cx| {
+            this.prefetch(cx);
+        })
+    }
+
     fn popup_menu(&self, menu: PopupMenu, cx: &WindowContext) -> PopupMenu {
         self.read(cx).popup_menu(menu, cx)
     }
@@ -175,6 +251,22 @@ impl<T: Panel> PanelView for View<T> {
     fn dump(&self, cx: &AppContext) -> PanelState {
         self.read(cx).dump(cx)
     }
+
+    fn is_dirty(&self, cx: &AppContext) -> bool {
+        self.read(cx).is_dirty(cx)
+    }
+
+    fn save(&self, cx: &mut WindowContext) -> Task<Result<()>> {
+        self.update(cx, |this, cx| this.save(cx))
+    }
+
+    fn accept_file_drop(&self, cx: &AppContext) -> bool {
+        self.read(cx).accept_file_drop(cx)
+    }
+
+    fn on_file_drop(&self, paths: &[PathBuf], cx: &mut WindowContext) {
+        self.update(cx, |this, cx| this.on_file_drop(paths, cx))
+    }
 }
 
 impl From<&dyn PanelView> for AnyView {
@@ -207,17 +299,65 @@ pub struct PanelRegistry {
             ) -> Box<dyn PanelView>,
         >,
     >,
+    pub(super) migrations: HashMap<String, Arc<dyn Fn(PanelState) -> PanelState>>,
 }
 impl PanelRegistry {
     pub fn new() -> Self {
         Self {
             items: HashMap::new(),
+            migrations: HashMap::new(),
+        }
+    }
+
+    pub fn global(cx: &AppContext) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Returns true if the `panel_name` is registered by [`register_panel`].
+    pub fn is_registered(&self, panel_name: &str) -> bool {
+        self.items.contains_key(panel_name)
+    }
+
+    /// Returns the registered panel names in alphabetical order, e.g.: to build the "Add Panel" menu.
+    ///
+    /// The registry is changed at runtime by [`register_panel`] and [`unregister_panel`],
+    /// use `cx.observe_global::<PanelRegistry>` to update the UI.
+    pub fn panel_names(&self) -> Vec<SharedString> {
+        let mut names = self
+            .items
+            .keys()
+            .map(|name| SharedString::from(name.clone()))
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    /// Apply the migrations registered for the panel name of the `state`,
+    /// returns `None` if there is no migration for it.
+    ///
+    /// The migrations are chained if the panel is renamed, each migration is applied once at most.
+    pub(super) fn migrate(&self, state: &PanelState) -> Option<PanelState> {
+        let migrate = self.migrations.get(&state.panel_name)?;
+        let mut applied = vec![state.panel_name.clone()];
+        let mut state = migrate(state.clone());
+
+        while let Some(migrate) = self.migrations.get(&state.panel_name) {
+            if applied.contains(&state.panel_name) {
+                break;
+            }
+            applied.push(state.panel_name.clone());
+            state = migrate(state);
         }
+
+        Some(state)
     }
 }
 impl Global for PanelRegistry {}
 
 /// Register the Panel init by panel_name to global registry.
+///
+/// This can be called at runtime, e.g.: after a plugin is loaded, the placeholders of the
+/// unregistered panels in the opened [`DockArea`]s are rebuilt with the new registered panel.
 pub fn register_panel<F>(cx: &mut AppContext, panel_name: &str, deserialize: F)
 where
     F: Fn(WeakView<DockArea>, &PanelState, &PanelInfo, &mut WindowContext) -> Box<dyn PanelView>
@@ -231,3 +371,57 @@ where
         .items
         .insert(panel_name.to_string(), Arc::new(deserialize));
 }
+
+/// Unregister the Panel by panel_name from the global registry, e.g.: the plugin that provides the panel is unloaded.
+///
+/// The panels already in the layout are kept, they are loaded as the placeholder
+/// next time, until the panel is registered again.
+///
+/// Returns false if the panel is not registered.
+pub fn unregister_panel(cx: &mut AppContext, panel_name: &str) -> bool {
+    if !cx
+        .try_global::<PanelRegistry>()
+        .map_or(false, |registry| registry.is_registered(panel_name))
+    {
+        return false;
+    }
+
+    cx.global_mut::<PanelRegistry>()
+        .items
+        .remove(panel_name)
+        .is_some()
+}
+
+/// Register a migration to upgrade the saved [`PanelState`] of the `panel_name` before loading it,
+/// e.g.: the panel is renamed, or the info of the panel is changed.
+///
+/// The migration is called for all the states of the `panel_name`, check the [`PanelState::version`]
+/// to decide how to upgrade, and return the state unchanged if it is up to date.
+///
+/// ```ignore
+/// register_panel_migration(cx, "OldEditor", |mut state| {
+///     state.panel_name = "Editor".to_string();
+///     state
+/// });
+/// register_panel_migration(cx, "Editor", |mut state| {
+///     if state.version.is_none() {
+///         if let PanelInfo::Panel(value) = &mut state.info {
+///             value["path"] = value["file"].take();
+///         }
+///         state.version = Some(1);
+///     }
+///     state
+/// });
+/// ```
+pub fn register_panel_migration<F>(cx: &mut AppContext, panel_name: &str, migrate: F)
+where
+    F: Fn(PanelState) -> PanelState + 'static,
+{
+    if let None = cx.try_global::<PanelRegistry>() {
+        cx.set_global(PanelRegistry::new());
+    }
+
+    cx.global_mut::<PanelRegistry>()
+        .migrations
+        .insert(panel_name.to_string(), Arc::new(migrate));
+}
@@ -0,0 +1,122 @@
+//! Show a panel of the DockArea in a separate window, and return it back when the window is closed.
+
+use std::sync::Arc;
+
+use gpui::{
+    px, AnyWindowHandle, AppContext, Bounds, FocusHandle, FocusableView, IntoElement,
+    ParentElement as _, Pixels, Render, Size, Styled as _, ViewContext, VisualContext as _,
+    WeakView, WindowBounds, WindowContext, WindowOptions,
+};
+
+use crate::{theme::ActiveTheme as _, v_flex, Root};
+
+use super::{DockArea, PanelView, TabPanel};
+
+/// The default size of the panel window.
+const WINDOW_SIZE: Size<Pixels> = Size {
+    width: px(800.),
+    height: px(600.),
+};
+
+/// Where the panel is from, to return the panel back.
+struct Origin {
+    window: AnyWindowHandle,
+    dock_area: WeakView<DockArea>,
+    tab_panel: WeakView<TabPanel>,
+    ix: usize,
+}
+
+pub(crate) struct PanelWindow {
+    panel: Option<Arc<dyn PanelView>>,
+    focus_handle: FocusHandle,
+}
+
+impl PanelWindow {
+    /// Open the `panel` (has been removed from the `tab_panel`) in a new window,
+    /// the panel is inserted back to the `tab_panel` at `ix` when the window is closed.
+    pub(crate) fn open(
+        panel: Arc<dyn PanelView>,
+        dock_area: WeakView<DockArea>,
+        tab_panel: WeakView<TabPanel>,
+        ix: usize,
+        cx: &mut WindowContext,
+    ) {
+        let origin = Origin {
+            window: cx.window_handle(),
+            dock_area,
+            tab_panel,
+            ix,
+        };
+        let options = WindowOptions {
+            window_bounds: Some(WindowBounds::Windowed(Bounds::centered(
+                None,
+                WINDOW_SIZE,
+                cx,
+            ))),
+            ..Default::default()
+        };
+
+        let result = cx.open_window(options, |cx| {
+            let view = cx.new_view(|cx| Self::new(panel, origin, cx));
+            cx.new_view(|cx| Root::new(view.into(), cx))
+        });
+        if let Ok(window) = result {
+            _ = window.update(cx, |_, cx| cx.activate_window());
+        }
+    }
+
+    fn new(panel: Arc<dyn PanelView>, origin: Origin, cx: &mut ViewContext<Self>) -> Self {
+        panel.focus_handle(cx).focus(cx);
+
+        cx.on_release(move |this: &mut Self, _, cx| {
+            if let Some(panel) = this.panel.take() {
+                // Return the panel in the next cycle, the window is being closed.
+                cx.defer(move |cx| origin.restore(panel, cx));
+            }
+        })
+        .detach();
+
+        Self {
+            panel: Some(panel),
+            focus_handle: cx.focus_handle(),
+        }
+    }
+}
+
+impl Origin {
+    fn restore(self, panel: Arc<dyn PanelView>, cx: &mut AppContext) {
+        let Self {
+            window,
+            dock_area,
+            tab_panel,
+            ix,
+        } = self;
+
+        _ = window.update(cx, |_, cx| {
+            let Some(dock_area) = dock_area.upgrade() else {
+                return;
+            };
+            dock_area.update(cx, |dock_area, cx| {
+                dock_area.return_panel(panel, tab_panel, ix, cx);
+            });
+        });
+    }
+}
+
+impl FocusableView for PanelWindow {
+    fn focus_handle(&self, cx: &AppContext) -> FocusHandle {
+        self.panel
+            .as_ref()
+            .map(|panel| panel.focus_handle(cx))
+            .unwrap_or_else(|| self.focus_handle.clone())
+    }
+}
+
+impl Render for PanelWindow {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        v_flex()
+            .size_full()
+            .bg(cx.theme().background)
+            .children(self.panel.as_ref().map(|panel| panel.view()))
+    }
+}
@@ -60,10 +60,13 @@ impl StackPanel {
         });
 
         // Bubble up the resize event.
-        let _subscriptions = vec![cx
-            .subscribe(&panel_group, |_, _, _: &ResizablePanelEvent, cx| {
-                cx.emit(PanelEvent::LayoutChanged)
-            })];
+        let _subscriptions =
+            vec![
+                cx.subscribe(&panel_group, |_, _, _: &ResizablePanelEvent, cx| {
+                    cx.emit(PanelEvent::SizesChanged);
+                    cx.emit(PanelEvent::LayoutChanged)
+                }),
+            ];
 
         Self {
             axis,
@@ -99,6 +102,33 @@ impl StackPanel {
         self.panels.len()
     }
 
+    /// Returns the sizes of the panels.
+    pub fn sizes(&self, cx: &AppContext) -> Vec<Pixels> {
+        self.panel_group.read(cx).sizes()
+    }
+
+    /// Set the size of the panel at the index, the other panels are resized in proportion.
+    ///
+    /// The [`PanelEvent::SizesChanged`] is emitted after resized.
+    pub fn set_panel_size(&mut self, ix: usize, size: Pixels, cx: &mut ViewContext<Self>) {
+        self.panel_group
+            .update(cx, |view, cx| view.set_panel_size(ix, size, cx));
+    }
+
+    /// Set all the panels to the same size, e.g.: to reset the layout.
+    pub fn equalize_sizes(&mut self, cx: &mut ViewContext<Self>) {
+        self.panel_group
+            .update(cx, |view, cx| view.equalize_sizes(cx));
+    }
+
+    /// Set the sizes of the panels by the ratios of the stack size, e.g.: `&[0.3, 0.7]`.
+    ///
+    /// The ratios are normalized to sum to 1, the missing ones share the rest equally.
+    pub fn set_ratios(&mut self, ratios: &[f32], cx: &mut ViewContext<Self>) {
+        self.panel_group
+            .update(cx, |view, cx| view.set_ratios(ratios, cx));
+    }
+
     /// Return the index of the panel.
     pub(crate) fn index_of_panel(&self, panel: Arc<dyn PanelView>) -> Option<usize> {
         self.panels.iter().position(|p| p == &panel)
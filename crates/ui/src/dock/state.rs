@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use super::{
     invalid_panel::InvalidPanel, Dock, DockArea, DockItem, DockPlacement, Panel, PanelRegistry,
+    PanelView,
 };
 
 /// Used to serialize and deserialize the DockArea
@@ -24,15 +25,18 @@ pub struct DockAreaState {
     pub right_dock: Option<DockState>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bottom_dock: Option<DockState>,
+    /// The panels in the separate windows, they are added back to the center when loaded.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub detached: Vec<PanelState>,
 }
 
 /// Used to serialize and deserialize the Dock
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DockState {
     panel: PanelState,
-    placement: DockPlacement,
-    size: Pixels,
-    open: bool,
+    pub(super) placement: DockPlacement,
+    pub(super) size: Pixels,
+    pub(super) open: bool,
 }
 
 impl DockState {
@@ -67,6 +71,9 @@ impl DockState {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PanelState {
     pub panel_name: String,
+    /// The [`Panel::panel_version`] of the panel when it is saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<usize>,
     pub children: Vec<PanelState>,
     pub info: PanelInfo,
 }
@@ -103,7 +110,12 @@ pub enum PanelInfo {
         axis: usize, // 0 for horizontal, 1 for vertical
     },
     #[serde(rename = "tabs")]
-    Tabs { active_index: usize },
+    Tabs {
+        active_index: usize,
+        /// The number of the pinned tabs, the pinned tabs are always at the left.
+        #[serde(default)]
+        pinned_count: usize,
+    },
     #[serde(rename = "panel")]
     Panel(serde_json::Value),
     #[serde(rename = "tiles")]
@@ -119,7 +131,17 @@ impl PanelInfo {
     }
 
     pub fn tabs(active_index: usize) -> Self {
-        Self::Tabs { active_index }
+        Self::Tabs {
+            active_index,
+            pinned_count: 0,
+        }
+    }
+
+    pub fn tabs_with_pinned(active_index: usize, pinned_count: usize) -> Self {
+        Self::Tabs {
+            active_index,
+            pinned_count,
+        }
     }
 
     pub fn panel(info: serde_json::Value) -> Self {
@@ -150,7 +172,14 @@ impl PanelInfo {
 
     pub fn active_index(&self) -> Option<usize> {
         match self {
-            Self::Tabs { active_index } => Some(*active_index),
+            Self::Tabs { active_index, .. } => Some(*active_index),
+            _ => None,
+        }
+    }
+
+    pub fn pinned_count(&self) -> Option<usize> {
+        match self {
+            Self::Tabs { pinned_count, .. } => Some(*pinned_count),
             _ => None,
         }
     }
@@ -160,6 +189,7 @@ impl Default for PanelState {
     fn default() -> Self {
         Self {
             panel_name: "".to_string(),
+            version: None,
             children: Vec::new(),
             info: PanelInfo::Panel(serde_json::Value::Null),
         }
@@ -170,6 +200,7 @@ impl PanelState {
     pub fn new<P: Panel>(panel: &P) -> Self {
         Self {
             panel_name: panel.panel_name().to_string(),
+            version: panel.panel_version(),
             ..Default::default()
         }
     }
@@ -179,6 +210,30 @@ impl PanelState {
     }
 
     pub fn to_item(&self, dock_area: WeakView<DockArea>, cx: &mut WindowContext) -> DockItem {
+        // Upgrade the state saved by the old version first, see `register_panel_migration`.
+        if let Some(state) = cx.global::<PanelRegistry>().migrate(self) {
+            return state.build_item(dock_area, cx);
+        }
+
+        self.build_item(dock_area, cx)
+    }
+
+    /// Build the panel by the registered [`register_panel`](super::register_panel),
+    /// returns `None` if the panel is not registered.
+    pub(super) fn build_panel(
+        &self,
+        dock_area: WeakView<DockArea>,
+        cx: &mut WindowContext,
+    ) -> Option<Box<dyn PanelView>> {
+        let f = cx
+            .global::<PanelRegistry>()
+            .items
+            .get(&self.panel_name)
+            .cloned()?;
+        Some(f(dock_area, self, &self.info, cx))
+    }
+
+    fn build_item(&self, dock_area: WeakView<DockArea>, cx: &mut WindowContext) -> DockItem {
         let info = self.info.clone();
 
         let items: Vec<DockItem> = self
@@ -197,9 +252,16 @@ impl PanelState {
                 let sizes = sizes.iter().map(|s| Some(*s)).collect_vec();
                 DockItem::split_with_sizes(axis, items, sizes, &dock_area, cx)
             }
-            PanelInfo::Tabs { active_index } => {
+            PanelInfo::Tabs {
+                active_index,
+                pinned_count,
+            } => {
                 if items.len() == 1 {
-                    return items[0].clone();
+                    let item = items[0].clone();
+                    if let DockItem::Tabs { view, .. } = &item {
+                        view.update(cx, |view, cx| view.set_pinned_count(pinned_count, cx));
+                    }
+                    return item;
                 }
 
                 let items = items
@@ -213,22 +275,19 @@ impl PanelState {
                     })
                     .collect_vec();
 
-                DockItem::tabs(items, Some(active_index), &dock_area, cx)
+                let item = DockItem::tabs(items, Some(active_index), &dock_area, cx);
+                if let DockItem::Tabs { view, .. } = &item {
+                    view.update(cx, |view, cx| view.set_pinned_count(pinned_count, cx));
+                }
+                item
             }
             PanelInfo::Panel(_) => {
-                let view = if let Some(f) = cx
-                    .global::<PanelRegistry>()
-                    .items
-                    .get(&self.panel_name)
-                    .cloned()
-                {
-                    f(dock_area.clone(), self, &info, cx)
-                } else {
+                let view = self.build_panel(dock_area.clone(), cx).unwrap_or_else(|| {
                     // Show an invalid panel if the panel is not registered.
-                    Box::new(
-                        cx.new_view(|cx| InvalidPanel::new(&self.panel_name, self.clone(), cx)),
-                    )
-                };
+                    Box::new(cx.new_view(|cx| {
+                        InvalidPanel::new(&self.panel_name, self.clone(), dock_area.clone(), cx)
+                    }))
+                });
 
                 DockItem::tabs(vec![view.into()], None, &dock_area, cx)
             }
@@ -279,4 +338,60 @@ mod tests {
         assert_eq!(right_dock.panel.children.len(), 1);
         assert_eq!(right_dock.panel.children[0].panel_name, "StoryContainer");
     }
+
+    #[test]
+    fn test_deserialize_tabs_pinned_count() {
+        let info: PanelInfo = serde_json::from_str(r#"{ "tabs": { "active_index": 1 } }"#).unwrap();
+        assert_eq!(info.active_index(), Some(1));
+        assert_eq!(info.pinned_count(), Some(0));
+
+        let json = serde_json::to_string(&PanelInfo::tabs_with_pinned(1, 2)).unwrap();
+        let info: PanelInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(info.pinned_count(), Some(2));
+    }
+
+    #[test]
+    fn test_panel_migrations() {
+        let state: PanelState = serde_json::from_str(
+            r#"{ "panel_name": "OldEditor", "children": [], "info": { "panel": { "file": "a.rs" } } }"#,
+        )
+        .unwrap();
+        assert_eq!(state.version, None);
+
+        let mut registry = PanelRegistry::new();
+        assert_eq!(registry.migrate(&state), None);
+
+        registry.migrations.insert(
+            "OldEditor".to_string(),
+            std::sync::Arc::new(|mut state: PanelState| {
+                state.panel_name = "Editor".to_string();
+                state
+            }),
+        );
+        registry.migrations.insert(
+            "Editor".to_string(),
+            std::sync::Arc::new(|mut state: PanelState| {
+                if state.version.is_none() {
+                    if let PanelInfo::Panel(value) = &mut state.info {
+                        value["path"] = value["file"].take();
+                    }
+                    state.version = Some(1);
+                }
+                state
+            }),
+        );
+
+        let migrated = registry.migrate(&state).unwrap();
+        assert_eq!(migrated.panel_name, "Editor");
+        assert_eq!(migrated.version, Some(1));
+        assert_eq!(
+            migrated.info,
+            PanelInfo::panel(serde_json::json!({ "file": null, "path": "a.rs" }))
+        );
+        // The up to date state is unchanged.
+        assert_eq!(registry.migrate(&migrated), Some(migrated.clone()));
+
+        let json = serde_json::to_string(&migrated).unwrap();
+        assert!(json.contains(r#""version":1"#));
+    }
 }
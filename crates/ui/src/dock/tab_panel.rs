@@ -1,32 +1,42 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 
 use gpui::{
-    div, prelude::FluentBuilder, px, rems, AppContext, Corner, DefiniteLength, DismissEvent,
-    DragMoveEvent, Empty, Entity, EventEmitter, FocusHandle, FocusableView,
-    InteractiveElement as _, IntoElement, ParentElement, Pixels, Render, ScrollHandle,
-    SharedString, StatefulInteractiveElement, Styled, View, ViewContext, VisualContext as _,
-    WeakView, WindowContext,
+    div, prelude::FluentBuilder, px, rems, AppContext, Corner, DefiniteLength, DismissEvent, Div,
+    DragMoveEvent, Empty, Entity, EntityId, EventEmitter, FocusHandle, FocusableView,
+    InteractiveElement as _, IntoElement, KeyDownEvent, MouseButton, ParentElement, Pixels, Render,
+    ScrollHandle, SharedString, StatefulInteractiveElement, Styled, Task, View, ViewContext,
+    VisualContext as _, WeakView, WindowContext,
 };
 use rust_i18n::t;
+use smol::Timer;
 
 use crate::{
     button::{Button, ButtonVariants as _},
+    context_menu::ContextMenu,
     dock::PanelInfo,
+    drop_target::DropTargetExt as _,
     h_flex,
     popup_menu::{PopupMenu, PopupMenuExt},
     tab::{Tab, TabBar},
     theme::ActiveTheme,
-    v_flex, AxisExt, IconName, Placement, Selectable, Sizable,
+    unsaved::{confirm_unsaved, UnsavedItem},
+    v_flex, AxisExt, Icon, IconName, Placement, RovingFocus, Selectable, Sizable,
 };
 
 use super::{
-    ClosePanel, DockArea, DockPlacement, Panel, PanelEvent, PanelState, PanelStyle, PanelView,
-    StackPanel, ToggleZoom,
+    panel_window::PanelWindow, ActivatePanel, CloseAllTabs, CloseOtherTabs, ClosePanel, CloseTab,
+    CloseTabsToRight, DockArea, DockPlacement, LayoutMetrics, OpenInWindow, Panel, PanelEvent,
+    PanelState, PanelStyle, PanelView, StackPanel, TogglePin, ToggleZoom,
 };
 
+/// The delay of hovering the tab to prefetch the panel.
+const PREFETCH_DELAY: Duration = Duration::from_millis(200);
+
 #[derive(Clone, Copy)]
 struct TabState {
     closable: bool,
+    pinnable: bool,
+    pinned: bool,
     zoomable: bool,
     draggable: bool,
     droppable: bool,
@@ -46,24 +56,75 @@ impl DragPanel {
 
 impl Render for DragPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        div()
+        render_panel_card(&self.panel, cx)
             .id("drag-panel")
             .cursor_grab()
-            .py_1()
-            .px_3()
-            .w_24()
-            .overflow_hidden()
-            .whitespace_nowrap()
-            .border_1()
-            .border_color(cx.theme().border)
-            .rounded_md()
-            .text_color(cx.theme().tab_foreground)
-            .bg(cx.theme().tab_active)
-            .opacity(0.75)
-            .child(self.panel.title(cx))
+            .w_48()
+            .h_32()
+            .opacity(0.85)
     }
 }
 
+/// The read-only preview of the active panel in the collapsed bottom dock,
+/// shown when hovering the dock toggle button.
+struct DockPreview {
+    panel: Arc<dyn PanelView>,
+}
+
+impl Render for DockPreview {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        // Wrap in a child, to ensure the margin is applied like the tooltip.
+        div().child(render_panel_card(&self.panel, cx).m_3().w_80().h_56())
+    }
+}
+
+/// Render the panel title and the [`Panel::drag_preview`] as a card,
+/// the panel icon is used as the placeholder if there is no preview.
+fn render_panel_card(panel: &Arc<dyn PanelView>, cx: &WindowContext) -> Div {
+    let icon = panel.icon(cx);
+
+    v_flex()
+        .overflow_hidden()
+        .border_1()
+        .border_color(cx.theme().border)
+        .rounded_md()
+        .text_color(cx.theme().tab_foreground)
+        .bg(cx.theme().tab_active)
+        .when(cx.theme().has_shadow(), |this| this.shadow_md())
+        .child(
+            h_flex()
+                .flex_shrink_0()
+                .py_1()
+                .px_2()
+                .gap_1()
+                .overflow_hidden()
+                .whitespace_nowrap()
+                .text_sm()
+                .border_b_1()
+                .border_color(cx.theme().border)
+                .children(icon.clone().map(|icon| icon.small()))
+                .child(div().flex_1().overflow_hidden().child(panel.title(cx))),
+        )
+        .child(
+            div()
+                .flex_1()
+                .overflow_hidden()
+                .text_xs()
+                .bg(cx.theme().background)
+                .child(match panel.drag_preview(cx) {
+                    Some(preview) => div().size_full().p_2().child(preview),
+                    None => h_flex()
+                        .size_full()
+                        .justify_center()
+                        .text_color(cx.theme().muted_foreground)
+                        .child(
+                            icon.unwrap_or_else(|| Icon::new(IconName::LayoutDashboard))
+                                .large(),
+                        ),
+                }),
+        )
+}
+
 pub struct TabPanel {
     focus_handle: FocusHandle,
     dock_area: WeakView<DockArea>,
@@ -74,12 +135,19 @@ pub struct TabPanel {
     /// If this is true, the Panel closable will follow the active panel's closable,
     /// otherwise this TabPanel will not able to close
     pub(crate) closable: bool,
+    /// The pinned panels, the pinned panels are always at the left of the tabs.
+    pinned: HashSet<EntityId>,
 
     tab_bar_scroll_handle: ScrollHandle,
+    /// Move the focus between the tabs by the arrow keys.
+    tab_bar_focus: RovingFocus,
     is_zoomed: bool,
     is_collapsed: bool,
     /// When drag move, will get the placement of the panel to be split
     will_split_placement: Option<Placement>,
+    /// The index of the hovered tab and the task to prefetch its panel,
+    /// dropped to cancel when the hover of that tab is left.
+    prefetch_task: Option<(usize, Task<()>)>,
 }
 
 impl Panel for TabPanel {
@@ -133,7 +201,7 @@ impl Panel for TabPanel {
         let mut state = PanelState::new(self);
         for panel in self.panels.iter() {
             state.add_child(panel.dump(cx));
-            state.info = PanelInfo::tabs(self.active_ix);
+            state.info = PanelInfo::tabs_with_pinned(self.active_ix, self.pinned_count());
         }
         state
     }
@@ -152,10 +220,13 @@ impl TabPanel {
             panels: Vec::new(),
             active_ix: 0,
             tab_bar_scroll_handle: ScrollHandle::new(),
+            tab_bar_focus: RovingFocus::new(),
             will_split_placement: None,
             is_zoomed: false,
             is_collapsed: false,
             closable: true,
+            pinned: HashSet::new(),
+            prefetch_task: None,
         }
     }
 
@@ -189,6 +260,7 @@ impl TabPanel {
         self.active_ix = ix;
         self.tab_bar_scroll_handle.scroll_to_item(ix);
         self.focus_active_panel(cx);
+        self.record_activation(cx);
 
         // Sync the active state to all panels
         cx.spawn(|view, mut cx| async move {
@@ -209,6 +281,77 @@ impl TabPanel {
         cx.notify();
     }
 
+    /// Activate the `panel` and focus it, used by the [`DockArea::activate_panel`].
+    pub(super) fn activate_panel(
+        &mut self,
+        panel: &Arc<dyn PanelView>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if let Some(ix) = self.panels.iter().position(|p| p == panel) {
+            self.set_active_ix(ix, cx);
+            self.focus_active_panel(cx);
+        }
+    }
+
+    /// Record the active panel in the activation history of the [`DockArea`].
+    fn record_activation(&self, cx: &mut ViewContext<Self>) {
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+
+        let dock_area = self.dock_area.clone();
+        cx.defer(move |_, cx| {
+            _ = dock_area.update(cx, |dock_area, _| dock_area.record_activation(&panel));
+        });
+    }
+
+    /// Activate the panel by the user, and record it in the [`LayoutMetrics`].
+    fn activate_by_user(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if let Some(panel) = self.panels.get(ix) {
+            let panel_name = panel.panel_name(cx);
+            LayoutMetrics::record(cx, |metrics| metrics.record_panel_activated(panel_name));
+        }
+        self.set_active_ix(ix, cx);
+    }
+
+    /// Prefetch the inactive panel after hovering the tab for [`PREFETCH_DELAY`].
+    fn on_tab_hover(&mut self, ix: usize, hovered: bool, cx: &mut ViewContext<Self>) {
+        if !hovered {
+            // The hover of the next tab may come before the leave of this one.
+            if matches!(self.prefetch_task, Some((task_ix, _)) if task_ix == ix) {
+                self.prefetch_task = None;
+            }
+            return;
+        }
+        if ix == self.active_ix {
+            return;
+        }
+
+        let Some(panel) = self.panels.get(ix).cloned() else {
+            return;
+        };
+
+        let task = cx.spawn(|view, mut cx| async move {
+            Timer::after(PREFETCH_DELAY).await;
+
+            _ = cx.update(|cx| {
+                _ = view.update(cx, |view, cx| {
+                    view.prefetch_task = None;
+                    // Skip if the panel has been activated or removed during the delay.
+                    let is_inactive = view
+                        .panels
+                        .iter()
+                        .position(|p| p.view() == panel.view())
+                        .map_or(false, |ix| ix != view.active_ix);
+                    if is_inactive {
+                        panel.prefetch(cx);
+                    }
+                });
+            });
+        });
+        self.prefetch_task = Some((ix, task));
+    }
+
     /// Add a panel to the end of the tabs
     pub fn add_panel(&mut self, panel: Arc<dyn PanelView>, cx: &mut ViewContext<Self>) {
         self.add_panel_with_active(panel, true, cx);
@@ -266,7 +409,7 @@ impl TabPanel {
         cx.notify();
     }
 
-    fn insert_panel_at(
+    pub(super) fn insert_panel_at(
         &mut self,
         panel: Arc<dyn PanelView>,
         ix: usize,
@@ -280,8 +423,18 @@ impl TabPanel {
             return;
         }
 
+        let entity_id = panel.view().entity_id();
         self.panels.insert(ix, panel);
         self.set_active_ix(ix, cx);
+        // Keep the pinned tabs at the left, the inserted panel may be moved.
+        self.sort_pinned(cx);
+        if let Some(ix) = self
+            .panels
+            .iter()
+            .position(|p| p.view().entity_id() == entity_id)
+        {
+            self.set_active_ix(ix, cx);
+        }
         cx.emit(PanelEvent::LayoutChanged);
         cx.notify();
     }
@@ -294,8 +447,67 @@ impl TabPanel {
         cx.emit(PanelEvent::LayoutChanged);
     }
 
+    /// Close the panels, ask to save the dirty ones first.
+    pub fn close_panels(&mut self, panels: Vec<Arc<dyn PanelView>>, cx: &mut ViewContext<Self>) {
+        if panels.is_empty() {
+            return;
+        }
+
+        let dirty_items = panels
+            .iter()
+            .filter(|panel| panel.is_dirty(cx))
+            .cloned()
+            .map(UnsavedItem::panel)
+            .collect::<Vec<_>>();
+        if dirty_items.is_empty() {
+            self.remove_panels(panels, cx);
+            return;
+        }
+
+        let view = cx.view().downgrade();
+        confirm_unsaved(
+            dirty_items,
+            move |cx| {
+                _ = view.update(cx, |view, cx| view.remove_panels(panels.clone(), cx));
+            },
+            cx,
+        );
+    }
+
+    fn remove_panels(&mut self, panels: Vec<Arc<dyn PanelView>>, cx: &mut ViewContext<Self>) {
+        for panel in panels {
+            self.detach_panel(panel, cx);
+        }
+        self.remove_self_if_empty(cx);
+        cx.emit(PanelEvent::ZoomOut);
+        cx.emit(PanelEvent::LayoutChanged);
+        cx.notify();
+    }
+
+    /// Replace the `old` panel with the `new` one at the same position, and activate it.
+    pub(super) fn replace_panel(
+        &mut self,
+        old: Arc<dyn PanelView>,
+        new: Arc<dyn PanelView>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let old_view = old.view();
+        let Some(ix) = self.panels.iter().position(|p| p.view() == old_view) else {
+            return;
+        };
+
+        if self.pinned.remove(&old_view.entity_id()) {
+            self.pinned.insert(new.view().entity_id());
+        }
+        self.panels[ix] = new;
+        self.set_active_ix(ix, cx);
+        cx.emit(PanelEvent::LayoutChanged);
+        cx.notify();
+    }
+
     fn detach_panel(&mut self, panel: Arc<dyn PanelView>, cx: &mut ViewContext<Self>) {
         let panel_view = panel.view();
+        self.pinned.remove(&panel_view.entity_id());
         self.panels.retain(|p| p.view() != panel_view);
         if self.active_ix >= self.panels.len() {
             self.set_active_ix(self.panels.len().saturating_sub(1), cx)
@@ -316,6 +528,105 @@ impl TabPanel {
         }
     }
 
+    /// Return true if the tab of the panel can be closed by the user, the pinned tabs are not closable.
+    fn is_tab_closable(&self, panel: &Arc<dyn PanelView>, cx: &AppContext) -> bool {
+        self.closable && !self.is_pinned(panel) && panel.closable(cx)
+    }
+
+    /// Return the closable panels in the range of the tabs.
+    fn closable_tabs(
+        &self,
+        range: impl IntoIterator<Item = usize>,
+        cx: &AppContext,
+    ) -> Vec<Arc<dyn PanelView>> {
+        range
+            .into_iter()
+            .filter_map(|ix| self.panels.get(ix))
+            .filter(|panel| self.is_tab_closable(panel, cx))
+            .cloned()
+            .collect()
+    }
+
+    /// Return true if the panel is pinned.
+    ///
+    /// The pinned tab is rendered compact (icon only), always stay at the left,
+    /// and can't be closed by the user until unpinned.
+    pub fn is_pinned(&self, panel: &Arc<dyn PanelView>) -> bool {
+        self.pinned.contains(&panel.view().entity_id())
+    }
+
+    /// Return the number of the pinned panels.
+    pub fn pinned_count(&self) -> usize {
+        self.pinned.len()
+    }
+
+    /// Pin or unpin the panel, the pinned panels will be moved to the left of the tabs.
+    pub fn set_pinned(
+        &mut self,
+        panel: &Arc<dyn PanelView>,
+        pinned: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let entity_id = panel.view().entity_id();
+        if !self
+            .panels
+            .iter()
+            .any(|p| p.view().entity_id() == entity_id)
+        {
+            return;
+        }
+
+        let changed = if pinned {
+            self.pinned.insert(entity_id)
+        } else {
+            self.pinned.remove(&entity_id)
+        };
+        if !changed {
+            return;
+        }
+
+        self.sort_pinned(cx);
+        cx.emit(PanelEvent::LayoutChanged);
+        cx.notify();
+    }
+
+    /// Pin the first `count` panels, used to restore the pinned panels from the [`PanelState`].
+    pub(super) fn set_pinned_count(&mut self, count: usize, cx: &mut ViewContext<Self>) {
+        self.pinned = self
+            .panels
+            .iter()
+            .take(count)
+            .map(|panel| panel.view().entity_id())
+            .collect();
+        cx.notify();
+    }
+
+    /// Move the pinned panels to the left, and keep the order of the others,
+    /// the active panel will be kept.
+    fn sort_pinned(&mut self, cx: &mut ViewContext<Self>) {
+        let active_id = self
+            .panels
+            .get(self.active_ix)
+            .map(|panel| panel.view().entity_id());
+
+        let (mut panels, unpinned): (Vec<_>, Vec<_>) = self
+            .panels
+            .drain(..)
+            .partition(|panel| self.pinned.contains(&panel.view().entity_id()));
+        panels.extend(unpinned);
+        self.panels = panels;
+
+        if let Some(ix) = active_id.and_then(|id| {
+            self.panels
+                .iter()
+                .position(|panel| panel.view().entity_id() == id)
+        }) {
+            self.active_ix = ix;
+            self.tab_bar_scroll_handle.scroll_to_item(ix);
+        }
+        cx.notify();
+    }
+
     pub(super) fn set_collapsed(&mut self, collapsed: bool, cx: &mut ViewContext<Self>) {
         self.is_collapsed = collapsed;
         cx.notify();
@@ -421,6 +732,18 @@ impl TabPanel {
                                 };
                                 this.separator().menu(name, Box::new(ToggleZoom))
                             })
+                            .when(state.draggable && !is_zoomed, |this| {
+                                this.separator()
+                                    .menu(t!("Dock.Open in New Window"), Box::new(OpenInWindow))
+                            })
+                            .when(state.pinnable, |this| {
+                                let name = if state.pinned {
+                                    t!("Dock.Unpin")
+                                } else {
+                                    t!("Dock.Pin")
+                                };
+                                this.separator().menu(name, Box::new(TogglePin))
+                            })
                             .when(state.closable, |this| {
                                 this.separator()
                                     .menu(t!("Dock.Close"), Box::new(ClosePanel))
@@ -465,6 +788,23 @@ impl TabPanel {
         }
 
         let is_open = dock_area.is_dock_open(placement, cx);
+        // Peek the active panel of the collapsed bottom dock by hovering the toggle button.
+        let preview_panel = if placement.is_bottom() && !is_open {
+            // The TabPanel may be this one, it can't be read while rendering.
+            dock_area
+                .bottom_dock
+                .as_ref()
+                .and_then(|dock| dock.read(cx).panel.left_top_tab_panel(cx))
+                .and_then(|tab_panel| {
+                    if tab_panel.entity_id() == view_entity_id {
+                        self.active_panel(cx)
+                    } else {
+                        tab_panel.read(cx).active_panel(cx)
+                    }
+                })
+        } else {
+            None
+        };
 
         let icon = match placement {
             DockPlacement::Left => {
@@ -491,24 +831,38 @@ impl TabPanel {
             DockPlacement::Center => unreachable!(),
         };
 
-        Some(
-            Button::new(SharedString::from(format!("toggle-dock:{:?}", placement)))
-                .icon(icon)
-                .xsmall()
-                .ghost()
-                .tooltip(match is_open {
+        let button = Button::new(SharedString::from(format!("toggle-dock:{:?}", placement)))
+            .icon(icon)
+            .xsmall()
+            .ghost()
+            .when(preview_panel.is_none(), |this| {
+                this.tooltip(match is_open {
                     true => t!("Dock.Collapse"),
                     false => t!("Dock.Expand"),
                 })
-                .on_click(cx.listener({
-                    let dock_area = self.dock_area.clone();
-                    move |_, _, cx| {
-                        _ = dock_area.update(cx, |dock_area, cx| {
-                            dock_area.toggle_dock(placement, cx);
-                        });
-                    }
-                })),
-        )
+            })
+            .on_click(cx.listener({
+                let dock_area = self.dock_area.clone();
+                move |_, _, cx| {
+                    _ = dock_area.update(cx, |dock_area, cx| {
+                        dock_area.toggle_dock(placement, cx);
+                    });
+                }
+            }));
+
+        Some(match preview_panel {
+            Some(panel) => div()
+                .id("dock-preview")
+                .tooltip(move |cx| {
+                    cx.new_view(|_| DockPreview {
+                        panel: panel.clone(),
+                    })
+                    .into()
+                })
+                .child(button)
+                .into_any_element(),
+            None => button.into_any_element(),
+        })
     }
 
     fn render_title_bar(&self, state: TabState, cx: &mut ViewContext<Self>) -> impl IntoElement {
@@ -591,9 +945,37 @@ impl TabPanel {
         }
 
         let tabs_count = self.panels.len();
+        // The index of the visible tab, the hidden panels have no focus handle.
+        let mut focus_ix = 0;
 
         TabBar::new("tab-bar")
             .track_scroll(self.tab_bar_scroll_handle.clone())
+            .roving_focus(&self.tab_bar_focus)
+            .menu({
+                let panels = self.panels.clone();
+                let active_ix = self.active_ix;
+                move |mut menu, cx| {
+                    for (ix, panel) in panels.iter().enumerate() {
+                        if !panel.visible(cx) {
+                            continue;
+                        }
+
+                        let panel = panel.clone();
+                        menu = menu.menu_with_element(
+                            move |cx| {
+                                h_flex()
+                                    .gap_x_1p5()
+                                    .child(div().size_3p5().when(ix == active_ix, |this| {
+                                        this.child(Icon::new(IconName::Check).small())
+                                    }))
+                                    .child(panel.title(cx))
+                            },
+                            Box::new(ActivatePanel(ix)),
+                        );
+                    }
+                    menu.scrollable()
+                }
+            })
             .when(
                 left_dock_button.is_some() || bottom_dock_button.is_some(),
                 |this| {
@@ -627,14 +1009,46 @@ impl TabPanel {
                     active = false;
                 }
 
+                let pinned = self.is_pinned(panel);
+                let closable = self.is_tab_closable(panel, cx);
+                let tabs_len = self.panels.len();
+                let has_others = !self
+                    .closable_tabs((0..tabs_len).filter(|i| *i != ix), cx)
+                    .is_empty();
+                let has_right = !self.closable_tabs(ix + 1..tabs_len, cx).is_empty();
+                let has_any = !self.closable_tabs(0..tabs_len, cx).is_empty();
+                let label = if pinned {
+                    panel
+                        .icon(cx)
+                        .unwrap_or(Icon::new(IconName::Pin))
+                        .small()
+                        .into_any_element()
+                } else {
+                    panel.title(cx)
+                };
+                let focus_handle = self.tab_bar_focus.focus_handle(focus_ix, cx);
+                focus_ix += 1;
+
                 Some(
-                    Tab::new(("tab", ix), panel.title(cx))
+                    Tab::new(("tab", ix), label)
+                        .track_focus(&focus_handle)
+                        .relative()
                         .py_2()
+                        .when(pinned, |this| this.px_2())
                         .selected(active)
                         .disabled(disabled)
                         .when(!disabled, |this| {
                             this.on_click(cx.listener(move |view, _, cx| {
-                                view.set_active_ix(ix, cx);
+                                view.activate_by_user(ix, cx);
+                            }))
+                            .on_key_down(cx.listener(move |view, event: &KeyDownEvent, cx| {
+                                if matches!(event.keystroke.key.as_str(), "enter" | "space") {
+                                    cx.stop_propagation();
+                                    view.activate_by_user(ix, cx);
+                                }
+                            }))
+                            .on_hover(cx.listener(move |view, hovered, cx| {
+                                view.on_tab_hover(ix, *hovered, cx);
                             }))
                             .when(state.draggable, |this| {
                                 this.on_drag(
@@ -659,6 +1073,45 @@ impl TabPanel {
                                     },
                                 ))
                             })
+                            // The pinned tab can't be closed by middle-click or the menu.
+                            .when(closable, |this| {
+                                this.on_mouse_down(
+                                    MouseButton::Middle,
+                                    cx.listener(move |view, _, cx| {
+                                        cx.stop_propagation();
+                                        view.on_action_close_tab(&CloseTab(ix), cx);
+                                    }),
+                                )
+                            })
+                            .when(closable || has_any, |this| {
+                                this.suffix(
+                                    ContextMenu::new(("tab-context-menu", ix))
+                                        .menu(move |menu, _| {
+                                            menu.menu_with_disabled(
+                                                t!("Dock.Close"),
+                                                Box::new(CloseTab(ix)),
+                                                !closable,
+                                            )
+                                            .menu_with_disabled(
+                                                t!("Dock.Close Others"),
+                                                Box::new(CloseOtherTabs(ix)),
+                                                !has_others,
+                                            )
+                                            .menu_with_disabled(
+                                                t!("Dock.Close to the Right"),
+                                                Box::new(CloseTabsToRight(ix)),
+                                                !has_right,
+                                            )
+                                            .separator()
+                                            .menu_with_disabled(
+                                                t!("Dock.Close All"),
+                                                Box::new(CloseAllTabs),
+                                                !has_any,
+                                            )
+                                        })
+                                        .into_any_element(),
+                                )
+                            })
                         }),
                 )
             }))
@@ -709,12 +1162,18 @@ impl TabPanel {
 
         self.active_panel(cx)
             .map(|panel| {
+                let accept_file_drop = panel.accept_file_drop(cx);
+
                 div()
                     .id("tab-content")
                     .group("")
                     .overflow_y_scroll()
                     .overflow_x_hidden()
                     .flex_1()
+                    .when(accept_file_drop, |this| {
+                        let panel = panel.clone();
+                        this.on_file_drop(move |paths, cx| panel.on_file_drop(paths, cx))
+                    })
                     .child(panel.view())
                     .when(state.droppable, |this| {
                         this.on_drag_move(cx.listener(Self::on_panel_drag_move))
@@ -787,6 +1246,8 @@ impl TabPanel {
     ) {
         let panel = drag.panel.clone();
         let is_same_tab = drag.tab_panel == *cx.view();
+        // Keep the pinned state when reordering in the same tab.
+        let was_pinned = is_same_tab && self.is_pinned(&panel);
 
         // If target is same tab, and it is only one panel, do nothing.
         if is_same_tab && ix.is_none() {
@@ -816,11 +1277,17 @@ impl TabPanel {
         if let Some(placement) = self.will_split_placement {
             self.split_panel(panel, placement, None, cx);
         } else {
+            let entity_id = panel.view().entity_id();
             if let Some(ix) = ix {
                 self.insert_panel_at(panel, ix, cx)
             } else {
                 self.add_panel_with_active(panel, active, cx)
             }
+
+            if was_pinned {
+                self.pinned.insert(entity_id);
+                self.sort_pinned(cx);
+            }
         }
 
         self.remove_self_if_empty(cx);
@@ -955,10 +1422,88 @@ impl TabPanel {
         .detach();
     }
 
+    fn on_action_activate_panel(&mut self, action: &ActivatePanel, cx: &mut ViewContext<Self>) {
+        if self.panels.get(action.0).is_some() {
+            self.activate_by_user(action.0, cx);
+        }
+    }
+
+    fn on_action_toggle_pin(&mut self, _: &TogglePin, cx: &mut ViewContext<Self>) {
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+
+        let pinned = self.is_pinned(&panel);
+        self.set_pinned(&panel, !pinned, cx);
+    }
+
+    /// Move the active panel into a separate window, it's returned back when the window is closed.
+    fn on_action_open_in_window(&mut self, _: &OpenInWindow, cx: &mut ViewContext<Self>) {
+        if self.is_zoomed || !self.draggable(cx) {
+            return;
+        }
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+
+        let ix = self.active_ix;
+        let tab_panel = cx.view().downgrade();
+        // Record before removing, so the panel is kept in the layout saved on the change.
+        _ = self
+            .dock_area
+            .update(cx, |dock_area, _| dock_area.detach_panel(panel.clone()));
+        self.remove_panel(panel.clone(), cx);
+        PanelWindow::open(panel, self.dock_area.clone(), tab_panel, ix, cx);
+    }
+
+    fn on_action_close_tab(&mut self, action: &CloseTab, cx: &mut ViewContext<Self>) {
+        let Some(panel) = self.panels.get(action.0).cloned() else {
+            return;
+        };
+
+        if self.is_tab_closable(&panel, cx) {
+            self.close_panels(vec![panel], cx);
+        }
+    }
+
+    fn on_action_close_other_tabs(&mut self, action: &CloseOtherTabs, cx: &mut ViewContext<Self>) {
+        let ix = action.0;
+        let panels = self.closable_tabs((0..self.panels.len()).filter(|i| *i != ix), cx);
+        self.close_panels(panels, cx);
+    }
+
+    fn on_action_close_tabs_to_right(
+        &mut self,
+        action: &CloseTabsToRight,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let panels = self.closable_tabs(action.0 + 1..self.panels.len(), cx);
+        self.close_panels(panels, cx);
+    }
+
+    fn on_action_close_all_tabs(&mut self, _: &CloseAllTabs, cx: &mut ViewContext<Self>) {
+        let panels = self.closable_tabs(0..self.panels.len(), cx);
+        self.close_panels(panels, cx);
+    }
+
     fn on_action_close_panel(&mut self, _: &ClosePanel, cx: &mut ViewContext<Self>) {
-        if let Some(panel) = self.active_panel(cx) {
+        let Some(panel) = self.active_panel(cx) else {
+            return;
+        };
+
+        if !panel.is_dirty(cx) {
             self.remove_panel(panel, cx);
+            return;
         }
+
+        let view = cx.view().downgrade();
+        confirm_unsaved(
+            vec![UnsavedItem::panel(panel.clone())],
+            move |cx| {
+                _ = view.update(cx, |view, cx| view.remove_panel(panel.clone(), cx));
+            },
+            cx,
+        );
     }
 }
 
@@ -976,8 +1521,15 @@ impl EventEmitter<PanelEvent> for TabPanel {}
 impl Render for TabPanel {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl gpui::IntoElement {
         let focus_handle = self.focus_handle(cx);
+        let active_panel = self.active_panel(cx);
         let mut state = TabState {
             closable: self.closable(cx),
+            // Only the panels in the tab bar can be pinned.
+            pinnable: self.panels.len() > 1 || self.pinned_count() > 0,
+            pinned: active_panel
+                .as_ref()
+                .map(|panel| self.is_pinned(panel))
+                .unwrap_or(false),
             draggable: self.draggable(cx),
             droppable: self.droppable(cx),
             zoomable: self.zoomable(cx),
@@ -991,6 +1543,14 @@ impl Render for TabPanel {
             .track_focus(&focus_handle)
             .on_action(cx.listener(Self::on_action_toggle_zoom))
             .on_action(cx.listener(Self::on_action_close_panel))
+            .on_action(cx.listener(Self::on_action_close_tab))
+            .on_action(cx.listener(Self::on_action_close_other_tabs))
+            .on_action(cx.listener(Self::on_action_close_tabs_to_right))
+            .on_action(cx.listener(Self::on_action_close_all_tabs))
+            .on_action(cx.listener(Self::on_action_toggle_pin))
+            .on_action(cx.listener(Self::on_action_open_in_window))
+            .on_action(cx.listener(Self::on_action_activate_panel))
+            .capture_any_mouse_down(cx.listener(|view, _, cx| view.record_activation(cx)))
             .size_full()
             .overflow_hidden()
             .bg(cx.theme().background)
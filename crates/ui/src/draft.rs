@@ -0,0 +1,57 @@
+use std::{any::Any, collections::HashMap};
+
+use gpui::{AppContext, Global, SharedString};
+
+/// The drafts of the forms in Modal or Drawer in the current session, keyed by the flow id.
+///
+/// If the user dismissed the Modal accidentally (e.g.: click the overlay or press `escape`),
+/// save the form as a draft in the `on_close`, then reopen the same flow will restore the
+/// entered values. Call [`Drafts::discard`] when the form is submitted or discarded explicitly.
+///
+/// ```ignore
+/// let form = Drafts::get::<View<UserForm>>("new-user", cx)
+///     .unwrap_or_else(|| cx.new_view(UserForm::new));
+/// cx.open_modal(move |modal, _| {
+///     let form = form.clone();
+///     modal
+///         .child(form.clone())
+///         .on_close(move |_, cx| Drafts::save("new-user", form.clone(), cx))
+/// });
+/// ```
+#[derive(Default)]
+pub struct Drafts {
+    drafts: HashMap<SharedString, Box<dyn Any>>,
+}
+
+impl Global for Drafts {}
+
+impl Drafts {
+    /// Save the draft of the flow, the previous draft will be replaced.
+    pub fn save<T: 'static>(id: impl Into<SharedString>, draft: T, cx: &mut AppContext) {
+        cx.default_global::<Self>()
+            .drafts
+            .insert(id.into(), Box::new(draft));
+    }
+
+    /// Return the draft of the flow, returns `None` if there is no draft or the type is mismatched.
+    pub fn get<T: Clone + 'static>(id: &str, cx: &AppContext) -> Option<T> {
+        cx.try_global::<Self>()?
+            .drafts
+            .get(id)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    /// Return true if there is a draft of the flow.
+    pub fn contains(id: &str, cx: &AppContext) -> bool {
+        cx.try_global::<Self>()
+            .map_or(false, |drafts| drafts.drafts.contains_key(id))
+    }
+
+    /// Discard the draft of the flow, e.g.: the form has been submitted.
+    pub fn discard(id: &str, cx: &mut AppContext) {
+        if cx.has_global::<Self>() {
+            cx.global_mut::<Self>().drafts.remove(id);
+        }
+    }
+}
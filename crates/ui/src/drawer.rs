@@ -1,15 +1,18 @@
-use std::{rc::Rc, time::Duration};
+use std::{cell::Cell, rc::Rc, time::Duration};
 
 use gpui::{
-    actions, anchored, div, point, prelude::FluentBuilder as _, px, Animation, AnimationExt as _,
-    AnyElement, AppContext, ClickEvent, DefiniteLength, DismissEvent, Div, EventEmitter,
-    FocusHandle, InteractiveElement as _, IntoElement, KeyBinding, MouseButton, ParentElement,
-    Pixels, RenderOnce, Styled, WindowContext,
+    actions, anchored, div, point, prelude::FluentBuilder as _, px, Animation, AnyElement,
+    AppContext, ClickEvent, DefiniteLength, DismissEvent, Div, DragMoveEvent, EventEmitter,
+    FocusHandle, InteractiveElement as _, IntoElement, MouseButton, ParentElement, Pixels, Render,
+    RenderOnce, SharedString, StatefulInteractiveElement as _, Styled, VisualContext as _,
+    WindowContext,
 };
 
 use crate::{
+    animation::TransitionExt as _,
     button::{Button, ButtonVariants as _},
     h_flex,
+    keymap::{self, KeymapEntry},
     modal::overlay_color,
     root::ContextModal as _,
     scroll::ScrollbarAxis,
@@ -21,8 +24,14 @@ use crate::{
 actions!(drawer, [Escape]);
 
 const CONTEXT: &str = "Drawer";
+const HANDLE_SIZE: Pixels = px(4.);
+
+/// The drag marker for resizing the drawer, the value is the layer index of the drawer.
+#[derive(Clone, Render)]
+struct ResizeDrawer(usize);
+
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([KeyBinding::new("escape", Escape, Some(CONTEXT))])
+    keymap::bind_keys(cx, [KeymapEntry::new("escape", Escape, Some(CONTEXT))])
 }
 
 #[derive(IntoElement)]
@@ -30,6 +39,8 @@ pub struct Drawer {
     pub(crate) focus_handle: FocusHandle,
     placement: Placement,
     size: DefiniteLength,
+    min_size: Pixels,
+    max_size: Option<Pixels>,
     resizable: bool,
     on_close: Rc<dyn Fn(&ClickEvent, &mut WindowContext) + 'static>,
     title: Option<AnyElement>,
@@ -37,6 +48,12 @@ pub struct Drawer {
     content: Div,
     margin_top: Pixels,
     overlay: bool,
+    pub(crate) layer_ix: usize,
+    pub(crate) overlay_visible: bool,
+    /// The offset that the drawer pushed by the drawers above it.
+    pub(crate) stack_offset: Pixels,
+    /// The size of the drawer after resized by the user, kept by the Root.
+    pub(crate) resized_size: Rc<Cell<Option<Pixels>>>,
 }
 
 impl Drawer {
@@ -45,12 +62,18 @@ impl Drawer {
             focus_handle: cx.focus_handle(),
             placement: Placement::Right,
             size: DefiniteLength::Absolute(px(350.).into()),
+            min_size: px(100.),
+            max_size: None,
             resizable: true,
             title: None,
             footer: None,
             content: v_flex().px_4().py_3(),
             margin_top: TITLE_BAR_HEIGHT,
             overlay: true,
+            layer_ix: 0,
+            overlay_visible: true,
+            stack_offset: px(0.),
+            resized_size: Rc::new(Cell::new(None)),
             on_close: Rc::new(|_, _| {}),
         }
     }
@@ -73,6 +96,18 @@ impl Drawer {
         self
     }
 
+    /// Sets the min size of the drawer when resizing, default is 100px.
+    pub fn min_size(mut self, min_size: Pixels) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Sets the max size of the drawer when resizing, default is the size of the window.
+    pub fn max_size(mut self, max_size: Pixels) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
     /// Sets the margin top of the drawer, default is 0px.
     ///
     /// This is used to let Drawer be placed below a Windows Title, you can give the height of the title bar.
@@ -92,7 +127,7 @@ impl Drawer {
         self.placement = placement;
     }
 
-    /// Sets whether the drawer is resizable, default is `true`.
+    /// Sets whether the drawer is resizable by dragging the edge, default is `true`.
     pub fn resizable(mut self, resizable: bool) -> Self {
         self.resizable = resizable;
         self
@@ -132,6 +167,19 @@ impl RenderOnce for Drawer {
         let titlebar_height = self.margin_top;
         let size = cx.viewport_size();
         let on_close = self.on_close.clone();
+        let layer_ix = self.layer_ix;
+        let stack_offset = self.stack_offset;
+        let resized_size = self.resized_size.clone();
+        let drawer_size = match resized_size.get() {
+            Some(resized_size) => DefiniteLength::from(resized_size),
+            None => self.size,
+        };
+        let min_size = self.min_size;
+        let max_size = self.max_size.unwrap_or(if placement.is_horizontal() {
+            size.width
+        } else {
+            size.height - titlebar_height
+        });
 
         anchored()
             .position(point(px(0.), titlebar_height))
@@ -141,8 +189,10 @@ impl RenderOnce for Drawer {
                     .occlude()
                     .w(size.width)
                     .h(size.height - titlebar_height)
-                    .bg(overlay_color(self.overlay, cx))
-                    .when(self.overlay, |this| {
+                    .when(self.overlay_visible, |this| {
+                        this.bg(overlay_color(self.overlay, cx))
+                    })
+                    .when(self.overlay && self.overlay_visible, |this| {
                         this.on_mouse_down(MouseButton::Left, {
                             let on_close = self.on_close.clone();
                             move |_, cx| {
@@ -151,9 +201,26 @@ impl RenderOnce for Drawer {
                             }
                         })
                     })
+                    .when(self.resizable, |this| {
+                        this.on_drag_move(move |e: &DragMoveEvent<ResizeDrawer>, cx| {
+                            if e.drag(cx).0 != layer_ix {
+                                return;
+                            }
+
+                            let position = e.event.position;
+                            let new_size = match placement {
+                                Placement::Top => position.y - titlebar_height,
+                                Placement::Right => size.width - position.x,
+                                Placement::Bottom => size.height - position.y,
+                                Placement::Left => position.x,
+                            } - stack_offset;
+                            resized_size.set(Some(new_size.clamp(min_size, max_size)));
+                            cx.refresh();
+                        })
+                    })
                     .child(
                         v_flex()
-                            .id("drawer")
+                            .id(SharedString::from(format!("drawer-{layer_ix}")))
                             .key_context(CONTEXT)
                             .track_focus(&self.focus_handle)
                             .on_action({
@@ -167,13 +234,13 @@ impl RenderOnce for Drawer {
                             .occlude()
                             .bg(cx.theme().background)
                             .border_color(cx.theme().border)
-                            .shadow_xl()
+                            .when(cx.theme().has_shadow(), |this| this.shadow_xl())
                             .map(|this| {
                                 // Set the size of the drawer.
                                 if placement.is_horizontal() {
-                                    this.h_full().w(self.size)
+                                    this.h_full().w(drawer_size)
                                 } else {
-                                    this.w_full().h(self.size)
+                                    this.w_full().h(drawer_size)
                                 }
                             })
                             .map(|this| match self.placement {
@@ -184,6 +251,37 @@ impl RenderOnce for Drawer {
                                 }
                                 Placement::Left => this.top_0().left_0().bottom_0().border_r_1(),
                             })
+                            .when(self.resizable, |this| {
+                                this.child(
+                                    div()
+                                        .id("resize-handle")
+                                        .occlude()
+                                        .absolute()
+                                        .map(|this| match placement {
+                                            Placement::Top => this.bottom(-HANDLE_SIZE / 2.),
+                                            Placement::Right => this.left(-HANDLE_SIZE / 2.),
+                                            Placement::Bottom => this.top(-HANDLE_SIZE / 2.),
+                                            Placement::Left => this.right(-HANDLE_SIZE / 2.),
+                                        })
+                                        .map(|this| {
+                                            if placement.is_horizontal() {
+                                                this.top_0()
+                                                    .h_full()
+                                                    .w(HANDLE_SIZE)
+                                                    .cursor_col_resize()
+                                            } else {
+                                                this.left_0()
+                                                    .w_full()
+                                                    .h(HANDLE_SIZE)
+                                                    .cursor_row_resize()
+                                            }
+                                        })
+                                        .on_drag(ResizeDrawer(layer_ix), |drag, _, cx| {
+                                            cx.stop_propagation();
+                                            cx.new_view(|_| drag.clone())
+                                        }),
+                                )
+                            })
                             .child(
                                 // TitleBar
                                 h_flex()
@@ -225,11 +323,12 @@ impl RenderOnce for Drawer {
                                         .child(footer),
                                 )
                             })
-                            .with_animation(
+                            .with_transition(
                                 "slide",
                                 Animation::new(Duration::from_secs_f64(0.15)),
                                 move |this, delta| {
-                                    let y = px(-100.) + delta * px(100.);
+                                    // The drawer below the top drawer will be pushed by the `stack_offset`.
+                                    let y = px(-100.) + delta * px(100.) + stack_offset;
                                     this.map(|this| match placement {
                                         Placement::Top => this.top(y),
                                         Placement::Right => this.right(y),
@@ -237,6 +336,7 @@ impl RenderOnce for Drawer {
                                         Placement::Left => this.left(y),
                                     })
                                 },
+                                cx,
                             ),
                     ),
             )
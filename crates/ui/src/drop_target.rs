@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use gpui::{ExternalPaths, InteractiveElement, StyleRefinement, Styled as _, WindowContext};
+
+use crate::theme::ActiveTheme as _;
+
+/// Extension trait to accept the files dropped from the OS, e.g.: the Finder or the Explorer.
+///
+/// ```ignore
+/// div()
+///     .id("drop-area")
+///     .on_file_drop(|paths, cx| {
+///         for path in paths {
+///             println!("Dropped: {}", path.display());
+///         }
+///     })
+/// ```
+pub trait DropTargetExt: InteractiveElement + Sized {
+    /// Set the listener for the files dropped on the element,
+    /// the element is highlighted with the `drop_target` color of the theme while dragging over.
+    fn on_file_drop(self, listener: impl Fn(&[PathBuf], &mut WindowContext) + 'static) -> Self {
+        self.file_drag_over(|this, _, cx| this.bg(cx.theme().drop_target))
+            .on_drop(move |paths: &ExternalPaths, cx| listener(paths.paths(), cx))
+    }
+
+    /// Set the style of the element while the files are dragging over it,
+    /// use this after [`DropTargetExt::on_file_drop`] to override the default highlight.
+    fn file_drag_over(
+        self,
+        style: impl Fn(StyleRefinement, &[PathBuf], &WindowContext) -> StyleRefinement + 'static,
+    ) -> Self {
+        self.drag_over::<ExternalPaths>(move |this, paths, cx| style(this, paths.paths(), cx))
+    }
+}
+
+impl<E: InteractiveElement> DropTargetExt for E {}
@@ -1,30 +1,39 @@
 use gpui::{
-    actions, anchored, canvas, deferred, div, prelude::FluentBuilder, px, rems, AnyElement,
-    AppContext, Bounds, ClickEvent, DismissEvent, ElementId, EventEmitter, FocusHandle,
-    FocusableView, InteractiveElement, IntoElement, KeyBinding, Length, ParentElement, Pixels,
-    Render, SharedString, StatefulInteractiveElement, Styled, Task, View, ViewContext,
+    actions, canvas, deferred, div, prelude::FluentBuilder, px, rems, AnyElement, AppContext,
+    Bounds, ClickEvent, DismissEvent, ElementId, EventEmitter, FocusHandle, FocusableView,
+    FontWeight, HighlightStyle, InteractiveElement, IntoElement, Length, ParentElement, Pixels,
+    Render, SharedString, StatefulInteractiveElement, Styled, StyledText, Task, View, ViewContext,
     VisualContext, WeakView, WindowContext,
 };
 use rust_i18n::t;
 
 use crate::{
+    checkbox::Checkbox,
     h_flex,
     input::ClearButton,
+    keymap::{self, KeymapEntry},
     list::{self, List, ListDelegate, ListItem},
+    matcher::match_text,
+    primitives::placement::{anchored_to, Align, FloatingPlacement, Side},
+    tag::Tag,
     theme::ActiveTheme,
-    v_flex, Disableable, Icon, IconName, Sizable, Size, StyleSized, StyledExt,
+    v_flex, AutomationExt as _, AutomationId, Disableable, Icon, IconName, Sizable, Size,
+    StyleSized, StyledExt,
 };
 
 actions!(dropdown, [Up, Down, Enter, Escape]);
 
 const CONTEXT: &str = "Dropdown";
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([
-        KeyBinding::new("up", Up, Some(CONTEXT)),
-        KeyBinding::new("down", Down, Some(CONTEXT)),
-        KeyBinding::new("enter", Enter, Some(CONTEXT)),
-        KeyBinding::new("escape", Escape, Some(CONTEXT)),
-    ])
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("up", Up, Some(CONTEXT)),
+            KeymapEntry::new("down", Down, Some(CONTEXT)),
+            KeymapEntry::new("enter", Enter, Some(CONTEXT)),
+            KeymapEntry::new("escape", Escape, Some(CONTEXT)),
+        ],
+    )
 }
 
 /// A trait for items that can be displayed in a dropdown.
@@ -110,6 +119,8 @@ struct DropdownListDelegate<D: DropdownDelegate + 'static> {
     delegate: D,
     dropdown: WeakView<Dropdown<D>>,
     selected_index: Option<usize>,
+    /// The last search query, used to highlight the matched text.
+    query: SharedString,
 }
 
 impl<D> ListDelegate for DropdownListDelegate<D>
@@ -136,13 +147,45 @@ where
             .map_or(Size::Medium, |dropdown| dropdown.read(cx).size);
 
         if let Some(item) = self.delegate.get(ix) {
+            let title = item.title();
+            let highlights = match_text(&title, &self.query)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|range| {
+                    (
+                        range,
+                        HighlightStyle {
+                            color: Some(cx.theme().primary),
+                            font_weight: Some(FontWeight::SEMIBOLD),
+                            ..Default::default()
+                        },
+                    )
+                });
+            let title = StyledText::new(title).with_highlights(&cx.text_style(), highlights);
+            let checked = self.dropdown.upgrade().and_then(|dropdown| {
+                dropdown
+                    .read(cx)
+                    .checked
+                    .as_ref()
+                    .map(|checked| checked.contains(item.value()))
+            });
+
             let list_item = ListItem::new(("list-item", ix))
-                .check_icon(IconName::Check)
                 .cursor_pointer()
                 .selected(selected)
                 .input_text_size(size)
                 .list_size(size)
-                .child(div().whitespace_nowrap().child(item.title().to_string()));
+                .map(|this| match checked {
+                    Some(checked) => this.child(
+                        h_flex()
+                            .gap_2()
+                            .child(Checkbox::new(("check", ix)).checked(checked))
+                            .child(div().whitespace_nowrap().child(title)),
+                    ),
+                    None => this
+                        .check_icon(IconName::Check)
+                        .child(div().whitespace_nowrap().child(title)),
+                });
             Some(list_item)
         } else {
             None
@@ -162,6 +205,23 @@ where
     fn confirm(&mut self, ix: Option<usize>, cx: &mut ViewContext<List<Self>>) {
         self.selected_index = ix;
 
+        let multiple = self
+            .dropdown
+            .upgrade()
+            .map_or(false, |dropdown| dropdown.read(cx).checked.is_some());
+        if multiple {
+            // Keep the menu open to check more items.
+            let dropdown = self.dropdown.clone();
+            cx.defer(move |_, cx| {
+                _ = dropdown.update(cx, |this, cx| {
+                    if let Some(ix) = ix {
+                        this.toggle_selected(ix, cx);
+                    }
+                });
+            });
+            return;
+        }
+
         let selected_value = self
             .selected_index
             .and_then(|ix| self.delegate.get(ix))
@@ -179,6 +239,7 @@ where
     }
 
     fn perform_search(&mut self, query: &str, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        self.query = SharedString::from(query.to_string());
         self.dropdown.upgrade().map_or(Task::ready(()), |dropdown| {
             dropdown.update(cx, |_, cx| self.delegate.perform_search(query, cx))
         })
@@ -208,6 +269,75 @@ where
 
 pub enum DropdownEvent<D: DropdownDelegate + 'static> {
     Confirm(Option<<D::Item as DropdownItem>::Value>),
+    /// The checked items have been changed in the multiple mode, with the values in the checked order.
+    SelectionChanged(Vec<<D::Item as DropdownItem>::Value>),
+}
+
+/// The checked items in the multiple mode.
+///
+/// The items are kept by the value instead of the index, because the indices of the delegate
+/// are changed by the search, and the title is kept to display the chip of the filtered out item.
+struct CheckedItems<V> {
+    items: Vec<(V, SharedString)>,
+    eq: fn(&V, &V) -> bool,
+}
+
+impl<V: Clone> CheckedItems<V> {
+    fn new(eq: fn(&V, &V) -> bool) -> Self {
+        Self { items: vec![], eq }
+    }
+
+    fn position(&self, value: &V) -> Option<usize> {
+        self.items.iter().position(|(v, _)| (self.eq)(v, value))
+    }
+
+    fn contains(&self, value: &V) -> bool {
+        self.position(value).is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn values(&self) -> Vec<V> {
+        self.items.iter().map(|(value, _)| value.clone()).collect()
+    }
+
+    /// Check the item, or uncheck it if it's checked.
+    fn toggle(&mut self, value: &V, title: SharedString) {
+        match self.position(value) {
+            Some(pos) => {
+                self.items.remove(pos);
+            }
+            None => self.items.push((value.clone(), title)),
+        }
+    }
+
+    /// Returns true if all the `items` are checked, false if the `items` is empty.
+    fn contains_all(&self, items: &[(V, SharedString)]) -> bool {
+        !items.is_empty() && items.iter().all(|(value, _)| self.contains(value))
+    }
+
+    /// Check all the `items`, or uncheck them if all are checked, the others are kept.
+    fn toggle_all(&mut self, items: Vec<(V, SharedString)>) {
+        if self.contains_all(&items) {
+            let eq = self.eq;
+            self.items
+                .retain(|(v, _)| !items.iter().any(|(value, _)| eq(v, value)));
+        } else {
+            for (value, title) in items {
+                if !self.contains(&value) {
+                    self.items.push((value, title));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the number of the chips to display and the number of the rest items for `+N`.
+fn chips_overflow(len: usize, max_chips: usize) -> (usize, usize) {
+    let shown = len.min(max_chips);
+    (shown, len - shown)
 }
 
 /// A Dropdown element.
@@ -222,12 +352,16 @@ pub struct Dropdown<D: DropdownDelegate + 'static> {
     placeholder: Option<SharedString>,
     title_prefix: Option<SharedString>,
     selected_value: Option<<D::Item as DropdownItem>::Value>,
+    /// The checked items, `None` if not in the multiple mode.
+    checked: Option<CheckedItems<<D::Item as DropdownItem>::Value>>,
+    max_chips: usize,
     empty: Option<Box<dyn Fn(&WindowContext) -> AnyElement + 'static>>,
     width: Length,
     menu_width: Length,
     /// Store the bounds of the input
     bounds: Bounds<Pixels>,
     disabled: bool,
+    automation_id: Option<SharedString>,
 }
 
 pub struct SearchableVec<T> {
@@ -243,6 +377,15 @@ impl<T: DropdownItem + Clone> SearchableVec<T> {
             matched_items: items,
         }
     }
+
+    fn search(&mut self, query: &str) {
+        self.matched_items = self
+            .items
+            .iter()
+            .filter(|item| match_text(&item.title(), query).is_some())
+            .cloned()
+            .collect();
+    }
 }
 
 impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
@@ -275,13 +418,7 @@ impl<T: DropdownItem + Clone> DropdownDelegate for SearchableVec<T> {
     }
 
     fn perform_search(&mut self, query: &str, _cx: &mut ViewContext<Dropdown<Self>>) -> Task<()> {
-        self.matched_items = self
-            .items
-            .iter()
-            .filter(|item| item.title().to_lowercase().contains(&query.to_lowercase()))
-            .cloned()
-            .collect();
-
+        self.search(query);
         Task::ready(())
     }
 }
@@ -310,6 +447,7 @@ where
             delegate,
             dropdown: cx.view().downgrade(),
             selected_index,
+            query: SharedString::default(),
         };
 
         let searchable = delegate.delegate.can_search();
@@ -333,6 +471,8 @@ where
             size: Size::Medium,
             icon: None,
             selected_value: None,
+            checked: None,
+            max_chips: 3,
             open: false,
             cleanable: false,
             title_prefix: None,
@@ -341,6 +481,7 @@ where
             menu_width: Length::Auto,
             bounds: Bounds::default(),
             disabled: false,
+            automation_id: None,
         };
         this.set_selected_index(selected_index, cx);
         this
@@ -380,6 +521,25 @@ where
         self
     }
 
+    /// Set to check multiple items with the checkboxes, default: false
+    ///
+    /// The checked items are displayed as the removable chips, and the menu is kept open after checking,
+    /// use [`Dropdown::selected_values`] or [`DropdownEvent::SelectionChanged`] to get the checked items.
+    pub fn multiple(mut self, multiple: bool) -> Self
+    where
+        <<D as DropdownDelegate>::Item as DropdownItem>::Value: PartialEq,
+    {
+        self.checked = multiple.then(|| CheckedItems::new(PartialEq::eq));
+        self
+    }
+
+    /// Set the max number of the chips to display in the multiple mode,
+    /// the rest items are displayed as `+N`, default: 3
+    pub fn max_chips(mut self, max_chips: usize) -> Self {
+        self.max_chips = max_chips;
+        self
+    }
+
     /// Set true to show the clear button when the input field is not empty.
     pub fn cleanable(mut self) -> Self {
         self.cleanable = true;
@@ -443,6 +603,96 @@ where
         self.selected_value.as_ref()
     }
 
+    /// Returns the values of the checked items in the multiple mode, in the checked order.
+    pub fn selected_values(&self) -> Vec<<D::Item as DropdownItem>::Value> {
+        self.checked
+            .as_ref()
+            .map(|checked| checked.values())
+            .unwrap_or_default()
+    }
+
+    /// Set the checked items in the multiple mode, the values not in the items are ignored.
+    pub fn set_selected_values(
+        &mut self,
+        values: impl IntoIterator<Item = <D::Item as DropdownItem>::Value>,
+        cx: &mut ViewContext<Self>,
+    ) where
+        <<D as DropdownDelegate>::Item as DropdownItem>::Value: PartialEq,
+    {
+        let Some(checked) = self.checked.as_mut() else {
+            return;
+        };
+
+        let delegate = &self.list.read(cx).delegate().delegate;
+        checked.items.clear();
+        for value in values {
+            let title = delegate
+                .position(&value)
+                .and_then(|ix| delegate.get(ix))
+                .map(|item| item.title());
+            if let Some(title) = title {
+                if !checked.contains(&value) {
+                    checked.items.push((value, title));
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    /// Returns the values and titles of the matched items of the search.
+    fn matched_items(
+        &self,
+        cx: &AppContext,
+    ) -> Vec<(<D::Item as DropdownItem>::Value, SharedString)> {
+        let delegate = &self.list.read(cx).delegate().delegate;
+        (0..delegate.len())
+            .filter_map(|ix| delegate.get(ix))
+            .map(|item| (item.value().clone(), item.title()))
+            .collect()
+    }
+
+    fn emit_selection_changed(&mut self, cx: &mut ViewContext<Self>) {
+        cx.emit(DropdownEvent::SelectionChanged(self.selected_values()));
+        cx.notify();
+    }
+
+    fn toggle_selected(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some((value, title)) = self
+            .list
+            .read(cx)
+            .delegate()
+            .delegate
+            .get(ix)
+            .map(|item| (item.value().clone(), item.title()))
+        else {
+            return;
+        };
+
+        if let Some(checked) = self.checked.as_mut() {
+            checked.toggle(&value, title);
+        }
+        self.emit_selection_changed(cx);
+    }
+
+    fn remove_selected(&mut self, pos: usize, cx: &mut ViewContext<Self>) {
+        if let Some(checked) = self.checked.as_mut() {
+            if pos < checked.items.len() {
+                checked.items.remove(pos);
+            }
+        }
+        self.emit_selection_changed(cx);
+    }
+
+    /// Check all the matched items of the search, or uncheck them if all are checked.
+    fn toggle_all(&mut self, cx: &mut ViewContext<Self>) {
+        let items = self.matched_items(cx);
+        if let Some(checked) = self.checked.as_mut() {
+            checked.toggle_all(items);
+        }
+        self.emit_selection_changed(cx);
+        self.list.focus_handle(cx).focus(cx);
+    }
+
     pub fn focus(&self, cx: &mut WindowContext) {
         self.focus_handle.focus(cx);
     }
@@ -506,10 +756,43 @@ where
     }
 
     fn clean(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+        if let Some(checked) = self.checked.as_mut() {
+            checked.items.clear();
+            self.emit_selection_changed(cx);
+            return;
+        }
+
         self.set_selected_index(None, cx);
         cx.emit(DropdownEvent::Confirm(None));
     }
 
+    fn render_chips(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let items = self
+            .checked
+            .as_ref()
+            .map_or(&[][..], |checked| &checked.items[..]);
+        let (shown, rest) = chips_overflow(items.len(), self.max_chips);
+        let titles: Vec<(usize, SharedString)> = items[..shown]
+            .iter()
+            .enumerate()
+            .map(|(pos, (_, title))| (pos, title.clone()))
+            .collect();
+
+        h_flex()
+            .gap_1()
+            .overflow_hidden()
+            .children(titles.into_iter().map(|(ix, title)| {
+                Tag::new(("chip", ix), title)
+                    .xsmall()
+                    .when(!self.disabled, |this| {
+                        this.on_remove(cx.listener(move |this, _, cx| this.remove_selected(ix, cx)))
+                    })
+            }))
+            .when(rest > 0, |this| {
+                this.child(Tag::new("chip-rest", format!("+{}", rest)).xsmall())
+            })
+    }
+
     fn display_title(&self, cx: &WindowContext) -> impl IntoElement {
         let title = if let Some(selected_index) = &self.selected_index(cx) {
             let title = self
@@ -539,6 +822,16 @@ where
     }
 }
 
+impl<D> AutomationId for Dropdown<D>
+where
+    D: DropdownDelegate + 'static,
+{
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.automation_id = Some(id.into());
+        self
+    }
+}
+
 impl<D> Sizable for Dropdown<D>
 where
     D: DropdownDelegate + 'static,
@@ -570,7 +863,16 @@ where
 {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let is_focused = self.focus_handle.is_focused(cx);
-        let show_clean = self.cleanable && self.selected_index(cx).is_some();
+        let has_selection = if let Some(checked) = self.checked.as_ref() {
+            !checked.is_empty()
+        } else {
+            self.selected_index(cx).is_some()
+        };
+        let show_clean = self.cleanable && has_selection;
+        let all_selected = self.checked.as_ref().map_or(false, |checked| {
+            checked.contains_all(&self.matched_items(cx))
+        });
+        let multiple = self.checked.is_some();
         let view = cx.view().clone();
         let bounds = self.bounds;
         let allow_open = !(self.open || self.disabled);
@@ -584,6 +886,7 @@ where
 
         div()
             .id(self.id.clone())
+            .when_automation_id(self.automation_id.as_ref())
             .key_context(CONTEXT)
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::up))
@@ -604,7 +907,7 @@ where
                     .border_1()
                     .border_color(cx.theme().input)
                     .rounded(px(cx.theme().radius))
-                    .when(cx.theme().shadow, |this| this.shadow_sm())
+                    .when(cx.theme().has_shadow(), |this| this.shadow_sm())
                     .map(|this| {
                         if self.disabled {
                             this.cursor_not_allowed()
@@ -629,12 +932,13 @@ where
                             .items_center()
                             .justify_between()
                             .gap_1()
-                            .child(
-                                div()
-                                    .w_full()
-                                    .overflow_hidden()
-                                    .child(self.display_title(cx)),
-                            )
+                            .child(div().w_full().overflow_hidden().map(|this| {
+                                if multiple && has_selection {
+                                    this.child(self.render_chips(cx))
+                                } else {
+                                    this.child(self.display_title(cx))
+                                }
+                            }))
                             .when(show_clean, |this| {
                                 this.child(ClearButton::new(cx).map(|this| {
                                     if self.disabled {
@@ -677,9 +981,10 @@ where
                     ),
             )
             .when(self.open, |this| {
+                let placement = FloatingPlacement::new(Side::Bottom, Align::Start);
                 this.child(
                     deferred(
-                        anchored().snap_to_window_with_margin(px(8.)).child(
+                        anchored_to(bounds, placement).child(
                             div()
                                 .occlude()
                                 .map(|this| match self.menu_width {
@@ -689,15 +994,31 @@ where
                                 .child(
                                     v_flex()
                                         .occlude()
-                                        .mt_1p5()
                                         .bg(cx.theme().background)
                                         .border_1()
                                         .border_color(cx.theme().border)
                                         .rounded(px(cx.theme().radius))
-                                        .shadow_md()
+                                        .when(cx.theme().has_shadow(), |this| this.shadow_md())
                                         .on_mouse_down_out(|_, cx| {
                                             cx.dispatch_action(Box::new(Escape));
                                         })
+                                        .when(multiple, |this| {
+                                            this.child(
+                                                h_flex()
+                                                    .px_3()
+                                                    .py_1p5()
+                                                    .border_b_1()
+                                                    .border_color(cx.theme().border)
+                                                    .child(
+                                                        Checkbox::new("select-all")
+                                                            .label(t!("Dropdown.select_all"))
+                                                            .checked(all_selected)
+                                                            .on_click(cx.listener(
+                                                                |this, _, cx| this.toggle_all(cx),
+                                                            )),
+                                                    ),
+                                            )
+                                        })
                                         .child(self.list.clone()),
                                 )
                                 .on_mouse_down_out(cx.listener(|this, _, cx| {
@@ -710,3 +1031,71 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::SharedString;
+
+    use super::{chips_overflow, CheckedItems, DropdownDelegate, DropdownItem, SearchableVec};
+
+    fn matched_items(items: &SearchableVec<SharedString>) -> Vec<(SharedString, SharedString)> {
+        (0..items.len())
+            .filter_map(|ix| items.get(ix))
+            .map(|item| (item.clone(), item.title()))
+            .collect()
+    }
+
+    #[test]
+    fn test_checked_items_with_search() {
+        let mut items = SearchableVec::new(vec![
+            SharedString::from("Rust"),
+            SharedString::from("Go"),
+            SharedString::from("Ruby"),
+            SharedString::from("Python"),
+        ]);
+        let mut checked = CheckedItems::new(PartialEq::eq);
+
+        items.search("py");
+        let item = items.get(0).unwrap().clone();
+        checked.toggle(&item, item.title());
+        items.search("");
+
+        assert_eq!(checked.values(), vec![SharedString::from("Python")]);
+        let checked_ixs: Vec<usize> = (0..items.len())
+            .filter(|ix| checked.contains(items.get(*ix).unwrap()))
+            .collect();
+        assert_eq!(checked_ixs, vec![3]);
+
+        // Select all only checks the matched items.
+        items.search("ru");
+        checked.toggle_all(matched_items(&items));
+        assert!(checked.contains_all(&matched_items(&items)));
+        items.search("");
+        assert!(!checked.contains_all(&matched_items(&items)));
+        assert_eq!(
+            checked.values(),
+            vec![
+                SharedString::from("Python"),
+                SharedString::from("Rust"),
+                SharedString::from("Ruby")
+            ]
+        );
+
+        // Unselect all the matched items, the others are kept.
+        items.search("ru");
+        checked.toggle_all(matched_items(&items));
+        assert_eq!(checked.values(), vec![SharedString::from("Python")]);
+
+        let python = SharedString::from("Python");
+        checked.toggle(&python, python.clone());
+        assert!(checked.is_empty());
+    }
+
+    #[test]
+    fn test_chips_overflow() {
+        assert_eq!(chips_overflow(0, 3), (0, 0));
+        assert_eq!(chips_overflow(2, 3), (2, 0));
+        assert_eq!(chips_overflow(5, 3), (3, 2));
+        assert_eq!(chips_overflow(5, 0), (0, 5));
+    }
+}
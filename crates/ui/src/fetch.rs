@@ -0,0 +1,182 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    http_client::{AsyncBody, HttpClient as _},
+    AppContext, Global, SharedString, Task, ViewContext,
+};
+use serde::de::DeserializeOwned;
+use smol::io::AsyncReadExt as _;
+
+/// The default time to live of the cached responses.
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+struct CacheEntry {
+    body: Arc<[u8]>,
+    expires_at: Instant,
+}
+
+/// The cache of the response bodies by the url.
+#[derive(Default)]
+struct FetchCache {
+    entries: HashMap<SharedString, CacheEntry>,
+}
+
+impl Global for FetchCache {}
+
+impl FetchCache {
+    fn get(&mut self, url: &str, now: Instant) -> Option<Arc<[u8]>> {
+        match self.entries.get(url) {
+            Some(entry) if entry.expires_at > now => Some(entry.body.clone()),
+            Some(_) => {
+                self.entries.remove(url);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&mut self, url: SharedString, body: Arc<[u8]>, expires_at: Instant) {
+        // Remove the expired entries to keep the cache small.
+        let now = Instant::now();
+        self.entries.retain(|_, entry| entry.expires_at > now);
+        self.entries.insert(url, CacheEntry { body, expires_at });
+    }
+}
+
+/// Remove all the cached responses.
+pub fn clear_cache(cx: &mut AppContext) {
+    cx.default_global::<FetchCache>().entries.clear();
+}
+
+/// A GET request with the response cached for the `ttl`, with the [`HttpClient`](gpui::http_client::HttpClient) of the App.
+///
+/// The request is cancelled when the returned `Task` is dropped, so keep the task in the view
+/// to cancel it on the view dropped.
+///
+/// ```ignore
+/// self._load_task = Fetch::new("https://example.com/countries.json")
+///     .ttl(Duration::from_secs(300))
+///     .json_into(cx, |this, result: anyhow::Result<Vec<Country>>, cx| {
+///         if let Ok(countries) = result {
+///             this.dropdown.update(cx, |dropdown, cx| dropdown.set_items(countries, cx));
+///         }
+///     });
+/// ```
+pub struct Fetch {
+    url: SharedString,
+    ttl: Duration,
+}
+
+impl Fetch {
+    pub fn new(url: impl Into<SharedString>) -> Self {
+        Self {
+            url: url.into(),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Set the time to live of the cached response, `Duration::ZERO` to disable the cache, default: 60s
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Fetch the response body, returns the cached body if not expired.
+    pub fn bytes(self, cx: &mut AppContext) -> Task<anyhow::Result<Arc<[u8]>>> {
+        if let Some(body) = cx
+            .default_global::<FetchCache>()
+            .get(&self.url, Instant::now())
+        {
+            return Task::ready(Ok(body));
+        }
+
+        let client = cx.http_client();
+        let Self { url, ttl } = self;
+        cx.spawn(|mut cx| async move {
+            let mut response = client.get(&url, AsyncBody::default(), true).await?;
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to fetch {}, status: {}", url, response.status());
+            }
+
+            let mut body = Vec::new();
+            response.body_mut().read_to_end(&mut body).await?;
+            let body: Arc<[u8]> = body.into();
+
+            if !ttl.is_zero() {
+                let expires_at = Instant::now() + ttl;
+                let cached = body.clone();
+                cx.update(|cx| {
+                    cx.default_global::<FetchCache>()
+                        .insert(url, cached, expires_at)
+                })?;
+            }
+            Ok(body)
+        })
+    }
+
+    /// Fetch and decode the JSON response body on the background.
+    pub fn json<T>(self, cx: &mut AppContext) -> Task<anyhow::Result<T>>
+    where
+        T: DeserializeOwned + Send + 'static,
+    {
+        let bytes = self.bytes(cx);
+        cx.spawn(|cx| async move {
+            let bytes = bytes.await?;
+            cx.background_executor()
+                .spawn(async move { Ok(serde_json::from_slice(&bytes)?) })
+                .await
+        })
+    }
+
+    /// Fetch and decode the JSON response body, then update the view with the result.
+    ///
+    /// The `on_load` is not called if the view has been dropped.
+    pub fn json_into<V, T>(
+        self,
+        cx: &mut ViewContext<V>,
+        on_load: impl FnOnce(&mut V, anyhow::Result<T>, &mut ViewContext<V>) + 'static,
+    ) -> Task<()>
+    where
+        V: 'static,
+        T: DeserializeOwned + Send + 'static,
+    {
+        let task = self.json::<T>(cx);
+        cx.spawn(|view, mut cx| async move {
+            let result = task.await;
+            _ = view.update(&mut cx, |view, cx| on_load(view, result, cx));
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use super::FetchCache;
+
+    #[test]
+    fn test_fetch_cache() {
+        let mut cache = FetchCache::default();
+        let now = Instant::now();
+        let body: Arc<[u8]> = Arc::from(&b"[1, 2]"[..]);
+
+        cache.insert("a".into(), body.clone(), now + Duration::from_secs(60));
+        assert_eq!(cache.get("a", now), Some(body.clone()));
+        assert_eq!(cache.get("b", now), None);
+
+        // Expired entries are removed.
+        assert_eq!(cache.get("a", now + Duration::from_secs(61)), None);
+        assert!(cache.entries.is_empty());
+
+        cache.insert("a".into(), body.clone(), now - Duration::from_secs(1));
+        cache.insert("b".into(), body, now + Duration::from_secs(60));
+        assert_eq!(cache.entries.len(), 1);
+    }
+}
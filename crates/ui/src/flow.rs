@@ -0,0 +1,255 @@
+use std::{any::Any, time::Duration};
+
+use gpui::{
+    actions, div, prelude::FluentBuilder as _, relative, Animation, AnyView, AppContext, ElementId,
+    EventEmitter, FocusHandle, FocusableView, InteractiveElement as _, IntoElement,
+    ParentElement as _, Render, SharedString, Styled, ViewContext,
+};
+
+use crate::{
+    animation::{cubic_bezier, TransitionExt as _},
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    keymap::{self, KeymapEntry},
+    theme::ActiveTheme as _,
+    v_flex, IconName, Sizable as _, StyledExt as _,
+};
+
+actions!(flow, [Back]);
+
+const CONTEXT: &str = "Flow";
+
+pub fn init(cx: &mut AppContext) {
+    keymap::bind_keys(cx, [KeymapEntry::new("alt-left", Back, Some(CONTEXT))])
+}
+
+pub enum FlowEvent {
+    /// The current step has been changed, with the number of the steps in the stack.
+    Change(usize),
+}
+
+struct FlowStep {
+    title: SharedString,
+    view: AnyView,
+    /// The state of the step, e.g.: the form values to restore when going back.
+    state: Option<Box<dyn Any>>,
+}
+
+/// The direction of the slide transition.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Slide {
+    /// The new step slides in from the right.
+    Push,
+    /// The previous step slides in from the left.
+    Pop,
+}
+
+/// A stack of the views (steps) in one panel, like the navigation controller on mobile,
+/// to build the multi-screen flows, e.g.: the account opening.
+///
+/// Only the top step is rendered, the header with the back button is shown if there is any
+/// previous step, the `alt-left` key also goes back.
+///
+/// The step views can keep the [`WeakView`](gpui::WeakView) of the Flow to push the next step.
+///
+/// ```ignore
+/// let flow = cx.new_view(|cx| Flow::new("Account", profile_view, cx));
+///
+/// // In the profile step, after the form is submitted.
+/// self.flow.update(cx, |flow, cx| {
+///     flow.set_state(self.form.clone());
+///     flow.push("Documents", documents_view, cx);
+/// })?;
+/// ```
+pub struct Flow {
+    focus_handle: FocusHandle,
+    steps: Vec<FlowStep>,
+    slide: Option<Slide>,
+    /// The number of the changes, used as the id of the slide animation.
+    slides: usize,
+}
+
+impl Flow {
+    /// Create a Flow with the first step.
+    pub fn new(
+        title: impl Into<SharedString>,
+        view: impl Into<AnyView>,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            steps: vec![FlowStep {
+                title: title.into(),
+                view: view.into(),
+                state: None,
+            }],
+            slide: None,
+            slides: 0,
+        }
+    }
+
+    /// Returns the number of the steps in the stack.
+    pub fn depth(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if there is a previous step to go back.
+    pub fn can_go_back(&self) -> bool {
+        self.steps.len() > 1
+    }
+
+    /// Returns the view of the current step.
+    pub fn current(&self) -> AnyView {
+        self.current_step().view.clone()
+    }
+
+    /// Returns the title of the current step.
+    pub fn title(&self) -> SharedString {
+        self.current_step().title.clone()
+    }
+
+    fn current_step(&self) -> &FlowStep {
+        self.steps.last().expect("the flow has at least one step")
+    }
+
+    /// Push a step to the top of the stack, it slides in from the right.
+    pub fn push(
+        &mut self,
+        title: impl Into<SharedString>,
+        view: impl Into<AnyView>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.steps.push(FlowStep {
+            title: title.into(),
+            view: view.into(),
+            state: None,
+        });
+        self.changed(Slide::Push, cx);
+    }
+
+    /// Replace the current step without keeping it in the stack, e.g.: the finish step.
+    pub fn replace(
+        &mut self,
+        title: impl Into<SharedString>,
+        view: impl Into<AnyView>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.steps.pop();
+        self.push(title, view, cx);
+    }
+
+    /// Go back to the previous step, returns false if it's the first step.
+    pub fn pop(&mut self, cx: &mut ViewContext<Self>) -> bool {
+        if !self.can_go_back() {
+            return false;
+        }
+
+        self.steps.pop();
+        self.changed(Slide::Pop, cx);
+        true
+    }
+
+    /// Go back to the first step.
+    pub fn pop_to_root(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.can_go_back() {
+            return;
+        }
+
+        self.steps.truncate(1);
+        self.changed(Slide::Pop, cx);
+    }
+
+    /// Set the state of the current step, it's kept until the step is popped.
+    pub fn set_state<T: 'static>(&mut self, state: T) {
+        if let Some(step) = self.steps.last_mut() {
+            step.state = Some(Box::new(state));
+        }
+    }
+
+    /// Returns the state of the current step, `None` if not set or the type is mismatched.
+    pub fn state<T: 'static>(&self) -> Option<&T> {
+        self.state_at(self.steps.len() - 1)
+    }
+
+    /// Returns the state of the step at the index, the first step is `0`.
+    pub fn state_at<T: 'static>(&self, ix: usize) -> Option<&T> {
+        self.steps.get(ix)?.state.as_ref()?.downcast_ref()
+    }
+
+    fn changed(&mut self, slide: Slide, cx: &mut ViewContext<Self>) {
+        self.slide = Some(slide);
+        self.slides += 1;
+        cx.emit(FlowEvent::Change(self.steps.len()));
+        cx.notify();
+    }
+
+    fn on_action_back(&mut self, _: &Back, cx: &mut ViewContext<Self>) {
+        if !self.pop(cx) {
+            cx.propagate();
+        }
+    }
+
+    fn render_header(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .flex_shrink_0()
+            .gap_2()
+            .px_2()
+            .py_1()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(
+                Button::new("flow-back")
+                    .ghost()
+                    .small()
+                    .icon(IconName::ArrowLeft)
+                    .on_click(cx.listener(|this, _, cx| {
+                        this.pop(cx);
+                    })),
+            )
+            .child(div().text_sm().font_semibold().child(self.title()))
+    }
+}
+
+impl EventEmitter<FlowEvent> for Flow {}
+
+impl FocusableView for Flow {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for Flow {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let content = div().absolute().top_0().size_full().child(self.current());
+        let content = match self.slide {
+            Some(slide) => {
+                let from = if slide == Slide::Push { 1. } else { -1. };
+                content.with_transition(
+                    ElementId::NamedInteger("flow-slide".into(), self.slides),
+                    Animation::new(Duration::from_secs_f64(0.25))
+                        .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                    move |this, delta| this.left(relative(from * (1. - delta))),
+                    cx,
+                )
+            }
+            None => content.left_0().into_any_element(),
+        };
+
+        v_flex()
+            .key_context(CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::on_action_back))
+            .size_full()
+            .when(self.can_go_back(), |this| {
+                this.child(self.render_header(cx))
+            })
+            .child(
+                div()
+                    .relative()
+                    .flex_1()
+                    .w_full()
+                    .overflow_hidden()
+                    .child(content),
+            )
+    }
+}
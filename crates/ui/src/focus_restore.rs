@@ -0,0 +1,231 @@
+use gpui::{FocusHandle, WindowContext};
+
+use crate::Root;
+
+/// The overlay that interrupts the focus of the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverlayKind {
+    Drawer,
+    Modal,
+    /// The custom overlay, e.g.: a full screen preview.
+    Custom,
+}
+
+#[derive(Debug, Clone)]
+struct FocusEntry<H> {
+    kind: OverlayKind,
+    /// The focus handle of the overlay itself.
+    handle: H,
+    /// The focused element before the overlay opened.
+    previous: Option<H>,
+}
+
+/// The stack of the open overlays, in the order of opening.
+///
+/// This is generic over the handle for testing, `contains(overlay, handle)` tells
+/// whether the handle is inside the overlay, the handles inside the closed overlays are gone.
+#[derive(Debug, Clone)]
+struct FocusStack<H> {
+    entries: Vec<FocusEntry<H>>,
+}
+
+impl<H: Clone + PartialEq> FocusStack<H> {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, kind: OverlayKind, handle: H, previous: Option<H>) {
+        self.entries.push(FocusEntry {
+            kind,
+            handle,
+            previous,
+        });
+    }
+
+    /// Remove the closed overlays, returns the handle to focus.
+    ///
+    /// - If the top overlay is closed, the focus goes back to the element focused before it,
+    ///   or the nearest alive focus below it if that element is inside a closed overlay.
+    /// - If only the overlays under the top are closed, the focus is kept, but the overlays
+    ///   above them will restore to the nearest alive focus.
+    fn close(&mut self, closed: &[H], contains: impl Fn(&H, &H) -> bool) -> Option<H> {
+        let is_closed = |handle: &H| closed.contains(handle);
+        let top_closed = self
+            .entries
+            .last()
+            .map_or(false, |entry| is_closed(&entry.handle));
+        let is_alive = |handle: &H| {
+            !closed
+                .iter()
+                .any(|overlay| overlay == handle || contains(overlay, handle))
+        };
+
+        let mut alive_focus: Option<H> = None;
+        let mut entries = Vec::with_capacity(self.entries.len());
+        for mut entry in self.entries.drain(..) {
+            let previous = match entry.previous.take() {
+                Some(previous) if is_alive(&previous) => Some(previous),
+                _ => alive_focus.clone(),
+            };
+
+            if is_closed(&entry.handle) {
+                alive_focus = previous;
+            } else {
+                entry.previous = previous;
+                alive_focus = Some(entry.handle.clone());
+                entries.push(entry);
+            }
+        }
+        self.entries = entries;
+
+        if top_closed {
+            alive_focus
+        } else {
+            None
+        }
+    }
+
+    fn top(&self) -> Option<&FocusEntry<H>> {
+        self.entries.last()
+    }
+
+    fn session_focus(&self) -> Option<&H> {
+        self.entries
+            .first()
+            .and_then(|entry| entry.previous.as_ref())
+    }
+}
+
+/// The focus restore service of the window, it tracks the focus before the overlays
+/// (Drawer, Modal, or custom overlays) opened, and restores it after they closed.
+///
+/// The [`Root`] uses it for the Drawers and Modals, so the nested overlays (e.g.: a Modal opened
+/// from a Drawer) restore the focus correctly even if they are not closed in the opening order.
+///
+/// ```ignore
+/// // Open a custom overlay.
+/// FocusRestore::interrupt(OverlayKind::Custom, &overlay_focus_handle, cx);
+/// // Close it, the focus goes back to the element focused before it opened.
+/// FocusRestore::resume(&[overlay_focus_handle], cx);
+/// ```
+pub struct FocusRestore {
+    stack: FocusStack<FocusHandle>,
+}
+
+impl FocusRestore {
+    pub(crate) fn new() -> Self {
+        Self {
+            stack: FocusStack::new(),
+        }
+    }
+
+    pub(crate) fn push(&mut self, kind: OverlayKind, handle: FocusHandle, cx: &mut WindowContext) {
+        let previous = cx.focused();
+        self.stack.push(kind, handle.clone(), previous);
+        handle.focus(cx);
+    }
+
+    pub(crate) fn close(&mut self, closed: &[FocusHandle], cx: &mut WindowContext) {
+        let focus_handle = self
+            .stack
+            .close(closed, |overlay, handle| overlay.contains(handle, cx));
+        if let Some(focus_handle) = focus_handle {
+            cx.focus(&focus_handle);
+        }
+    }
+
+    /// Save the focused element and focus the overlay, call this when the overlay opened.
+    pub fn interrupt(kind: OverlayKind, handle: &FocusHandle, cx: &mut WindowContext) {
+        let handle = handle.clone();
+        Root::update(cx, move |root, cx| {
+            root.focus_restore.push(kind, handle, cx)
+        })
+    }
+
+    /// Restore the focus after the overlays closed, the overlays above the closed ones are kept.
+    pub fn resume(closed: &[FocusHandle], cx: &mut WindowContext) {
+        let closed = closed.to_vec();
+        Root::update(cx, move |root, cx| root.focus_restore.close(&closed, cx))
+    }
+
+    /// Focus back if the focus is lost, e.g.: after clicking the action of a Notification.
+    ///
+    /// The `previous` is the element focused before the action, it is used if there is no open overlay.
+    pub fn refocus(previous: Option<FocusHandle>, cx: &mut WindowContext) {
+        if cx.focused().is_some() {
+            return;
+        }
+
+        let focus_handle = Self::top_overlay(cx).map(|(_, handle)| handle).or(previous);
+        if let Some(focus_handle) = focus_handle {
+            cx.focus(&focus_handle);
+        }
+    }
+
+    /// Returns the kind and focus handle of the top open overlay.
+    pub fn top_overlay(cx: &WindowContext) -> Option<(OverlayKind, FocusHandle)> {
+        Root::read(cx)
+            .focus_restore
+            .stack
+            .top()
+            .map(|entry| (entry.kind, entry.handle.clone()))
+    }
+
+    /// Returns the element focused before the first overlay opened, `None` if no overlay is open.
+    pub fn session_focus(cx: &WindowContext) -> Option<FocusHandle> {
+        Root::read(cx).focus_restore.stack.session_focus().cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FocusStack, OverlayKind};
+
+    fn contains(overlay: &&str, handle: &&str) -> bool {
+        handle.starts_with(&format!("{}/", overlay))
+    }
+
+    #[test]
+    fn test_close_top() {
+        let mut stack = FocusStack::new();
+        stack.push(OverlayKind::Drawer, "drawer", Some("editor"));
+        stack.push(OverlayKind::Modal, "modal", Some("drawer/input"));
+
+        assert_eq!(stack.close(&["modal"], contains), Some("drawer/input"));
+        assert_eq!(stack.close(&["drawer"], contains), Some("editor"));
+        assert_eq!(stack.session_focus(), None);
+    }
+
+    #[test]
+    fn test_close_under_top() {
+        let mut stack = FocusStack::new();
+        stack.push(OverlayKind::Drawer, "drawer", Some("editor"));
+        stack.push(OverlayKind::Modal, "modal", Some("drawer/input"));
+
+        // Keep the focus in the top modal, but it will restore to the editor.
+        assert_eq!(stack.close(&["drawer"], contains), None);
+        assert_eq!(stack.session_focus(), Some(&"editor"));
+        assert_eq!(stack.close(&["modal"], contains), Some("editor"));
+    }
+
+    #[test]
+    fn test_close_nested() {
+        let mut stack = FocusStack::new();
+        stack.push(OverlayKind::Modal, "modal1", Some("editor"));
+        stack.push(OverlayKind::Modal, "modal2", Some("modal1/button"));
+        stack.push(OverlayKind::Modal, "modal3", Some("modal2"));
+
+        assert_eq!(
+            stack.close(&["modal2", "modal3"], contains),
+            Some("modal1/button")
+        );
+        assert_eq!(stack.close(&["modal1"], contains), Some("editor"));
+
+        // Nothing focused before the overlay opened.
+        stack.push(OverlayKind::Drawer, "drawer", None);
+        assert_eq!(stack.close(&["drawer"], contains), None);
+        assert_eq!(stack.close(&["unknown"], contains), None);
+    }
+}
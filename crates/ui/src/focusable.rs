@@ -1,4 +1,8 @@
-use gpui::{FocusHandle, ViewContext};
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{Axis, FocusHandle, KeyDownEvent, ViewContext, WindowContext};
+
+use crate::primitives::cycle_index;
 
 /// A trait for views that can cycle focus between its children.
 ///
@@ -20,21 +24,93 @@ pub trait FocusableCycle {
     {
         let focused_handle = cx.focused();
         let handles = self.cycle_focus_handles(cx);
-        let handles = if is_next {
-            handles
-        } else {
-            handles.into_iter().rev().collect()
-        };
-
-        let fallback_handle = handles[0].clone();
-        let target_focus_handle = handles
-            .into_iter()
-            .skip_while(|handle| Some(handle) != focused_handle.as_ref())
-            .skip(1)
-            .next()
-            .unwrap_or(fallback_handle);
-
-        target_focus_handle.focus(cx);
+        if handles.is_empty() {
+            return;
+        }
+
+        let focused_ix = handles
+            .iter()
+            .position(|handle| Some(handle) == focused_handle.as_ref());
+        let ix = cycle_index(focused_ix, handles.len(), is_next);
+
+        handles[ix].focus(cx);
         cx.stop_propagation();
     }
 }
+
+/// A handle to manage the roving focus of a group of items, e.g.: [`ButtonGroup`](crate::button_group::ButtonGroup), [`TabBar`](crate::tab::TabBar).
+///
+/// Like the `ScrollHandle`, you should keep it in your view state and pass it to the group every render.
+///
+/// Within the group, the arrow keys move the focus to the prev or next item,
+/// and the `tab` key leaves the group as normal.
+#[derive(Clone, Default)]
+pub struct RovingFocus {
+    handles: Rc<RefCell<Vec<FocusHandle>>>,
+}
+
+impl RovingFocus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the focus handle of the item at the given index, it will be created if not exists.
+    pub fn focus_handle(&self, ix: usize, cx: &mut WindowContext) -> FocusHandle {
+        let mut handles = self.handles.borrow_mut();
+        while handles.len() <= ix {
+            handles.push(cx.focus_handle());
+        }
+        handles[ix].clone()
+    }
+
+    /// Returns the index of the focused item.
+    pub fn focused_ix(&self, cx: &WindowContext) -> Option<usize> {
+        self.handles
+            .borrow()
+            .iter()
+            .position(|handle| handle.is_focused(cx))
+    }
+
+    /// Focus the item at the given index.
+    pub fn focus(&self, ix: usize, cx: &mut WindowContext) {
+        let handle = self.handles.borrow().get(ix).cloned();
+        if let Some(handle) = handle {
+            cx.focus(&handle);
+        }
+    }
+
+    /// Remove the focus handles that are out of the given length, call this when the items changed.
+    pub(crate) fn truncate(&self, len: usize) {
+        self.handles.borrow_mut().truncate(len);
+    }
+
+    /// Returns a key down listener to move the focus by the arrow keys along the axis.
+    pub(crate) fn on_key_down(
+        &self,
+        axis: Axis,
+    ) -> impl Fn(&KeyDownEvent, &mut WindowContext) + 'static {
+        let this = self.clone();
+        move |event, cx| {
+            let Some(ix) = this.focused_ix(cx) else {
+                return;
+            };
+            let len = this.handles.borrow().len();
+
+            let (prev_key, next_key) = match axis {
+                Axis::Horizontal => ("left", "right"),
+                Axis::Vertical => ("up", "down"),
+            };
+
+            let target_ix = match event.keystroke.key.as_str() {
+                key if key == prev_key => cycle_index(Some(ix), len, false),
+                key if key == next_key => cycle_index(Some(ix), len, true),
+                "home" => 0,
+                "end" => len - 1,
+                _ => return,
+            };
+
+            this.focus(target_ix, cx);
+            cx.stop_propagation();
+        }
+    }
+}
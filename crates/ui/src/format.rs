@@ -0,0 +1,336 @@
+/// The value of the Chinese numeral unit `万`.
+const WAN: f64 = 1e4;
+/// The value of the Chinese numeral unit `亿`.
+const YI: f64 = 1e8;
+
+/// The separators and the symbol positions to format the numbers in a locale.
+///
+/// The [`format_number`], [`format_currency`], [`format_percent`] and [`parse_number`] use the
+/// format of the current locale (see [`crate::set_locale`]), use this to format for a specific locale.
+///
+/// ```ignore
+/// use ui::format::NumberFormat;
+///
+/// let format = NumberFormat::for_locale("de");
+/// assert_eq!(format.format(1234.567, 2), "1.234,57");
+/// assert_eq!(format.format_currency(-1234.5, 2, "€"), "-1.234,50 €");
+/// assert_eq!(format.parse("1.234,5"), Some(1234.5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The decimal separator, e.g.: `.` in `1.5`.
+    pub decimal: char,
+    /// The thousand separator, e.g.: `,` in `1,234`.
+    pub group: char,
+    /// Put the currency symbol after the number, e.g.: `1.234,56 €`.
+    pub currency_after: bool,
+    /// Put a space between the number and `%`, e.g.: `12,5 %`.
+    pub percent_space: bool,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            decimal: '.',
+            group: ',',
+            currency_after: false,
+            percent_space: false,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Returns the format of the locale, e.g.: `en`, `de-DE`, `fr_FR`,
+    /// the unknown locales use the default format `1,234.56`.
+    pub fn for_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match language.as_str() {
+            "de" | "da" | "es" | "it" => Self {
+                decimal: ',',
+                group: '.',
+                currency_after: true,
+                percent_space: language != "it",
+            },
+            "nl" | "pt" | "id" | "tr" => Self {
+                decimal: ',',
+                group: '.',
+                ..Default::default()
+            },
+            "fr" | "ru" | "uk" | "pl" | "cs" | "sv" | "fi" | "nb" => Self {
+                decimal: ',',
+                group: '\u{a0}',
+                currency_after: true,
+                percent_space: true,
+            },
+            _ => Self::default(),
+        }
+    }
+
+    /// Returns the format of the current locale.
+    pub fn current() -> Self {
+        Self::for_locale(&crate::locale())
+    }
+
+    /// Format the number with the thousand separators and the fixed `precision` decimal places.
+    pub fn format(&self, value: f64, precision: usize) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let text = format!("{:.*}", precision, value.abs());
+        let digits = self.format_digits(&text);
+        // Avoid `-0.00` when the value is rounded to zero.
+        if value < 0. && text.bytes().any(|b| matches!(b, b'1'..=b'9')) {
+            format!("-{}", digits)
+        } else {
+            digits
+        }
+    }
+
+    /// Format the unsigned number text with `.` as the decimal point (e.g.: `1234.5`) in this format,
+    /// the text may be incomplete while typing, e.g.: `1234.`.
+    pub(crate) fn format_digits(&self, text: &str) -> String {
+        let (integer, fraction) = match text.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (text, None),
+        };
+
+        let mut result = String::with_capacity(text.len() + integer.len() / 3);
+        for (ix, c) in integer.chars().enumerate() {
+            if ix > 0 && (integer.len() - ix) % 3 == 0 {
+                result.push(self.group);
+            }
+            result.push(c);
+        }
+        if let Some(fraction) = fraction {
+            result.push(self.decimal);
+            result.push_str(fraction);
+        }
+        result
+    }
+
+    /// Format the number as the currency with the `symbol`, e.g.: `$1,234.56` or `1.234,56 €`.
+    pub fn format_currency(&self, value: f64, precision: usize, symbol: &str) -> String {
+        let number = self.format(value, precision);
+        if self.currency_after {
+            format!("{} {}", number, symbol)
+        } else {
+            match number.strip_prefix('-') {
+                Some(number) => format!("-{}{}", symbol, number),
+                None => format!("{}{}", symbol, number),
+            }
+        }
+    }
+
+    /// Format the ratio as the percentage, e.g.: `0.125` to `12.5%`.
+    pub fn format_percent(&self, ratio: f64, precision: usize) -> String {
+        let number = self.format(ratio * 100., precision);
+        if self.percent_space {
+            format!("{}\u{a0}%", number)
+        } else {
+            format!("{}%", number)
+        }
+    }
+
+    /// Parse the number formatted in this format, the thousand separators and spaces are ignored.
+    ///
+    /// Returns `None` if the text is not a valid number.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let text: String = text
+            .trim()
+            .chars()
+            .filter(|c| *c != self.group && !c.is_whitespace())
+            .map(|c| if c == self.decimal { '.' } else { c })
+            .collect();
+
+        if text.is_empty() {
+            return None;
+        }
+        text.parse::<f64>().ok().filter(|v| v.is_finite())
+    }
+}
+
+/// Format the number in the current locale, e.g.: `1,234.57` in `en` or `1.234,57` in `de`.
+pub fn format_number(value: f64, precision: usize) -> String {
+    NumberFormat::current().format(value, precision)
+}
+
+/// Format the number as the currency with the `symbol` in the current locale.
+pub fn format_currency(value: f64, precision: usize, symbol: &str) -> String {
+    NumberFormat::current().format_currency(value, precision, symbol)
+}
+
+/// Format the ratio as the percentage in the current locale, e.g.: `0.125` to `12.5%`.
+pub fn format_percent(ratio: f64, precision: usize) -> String {
+    NumberFormat::current().format_percent(ratio, precision)
+}
+
+/// Parse the number formatted in the current locale, returns `None` if the text is not a valid number.
+pub fn parse_number(text: &str) -> Option<f64> {
+    NumberFormat::current().parse(text)
+}
+
+/// Format the number with the Chinese numeral units `万` (10,000) and `亿` (100,000,000).
+///
+/// The `precision` is the max number of decimal places, the trailing zeros will be removed.
+///
+/// ```ignore
+/// use ui::format::format_cjk_number;
+///
+/// assert_eq!(format_cjk_number(15000., 2), "1.5万");
+/// assert_eq!(format_cjk_number(123456789., 2), "1.23亿");
+/// assert_eq!(format_cjk_number(999., 2), "999");
+/// ```
+pub fn format_cjk_number(value: f64, precision: usize) -> String {
+    const UNITS: [(f64, &str); 3] = [(1., ""), (WAN, "万"), (YI, "亿")];
+
+    let round = |ix: usize| format!("{:.*}", precision, value / UNITS[ix].0);
+    let mut ix = UNITS
+        .iter()
+        .rposition(|(base, _)| value.abs() >= *base)
+        .unwrap_or(0);
+    let mut text = round(ix);
+    // The value may be carried to the next unit by rounding, e.g.: `9999.9` to `1万`.
+    while ix + 1 < UNITS.len()
+        && text.parse::<f64>().map_or(false, |rounded| {
+            rounded.abs() * UNITS[ix].0 >= UNITS[ix + 1].0
+        })
+    {
+        ix += 1;
+        text = round(ix);
+    }
+
+    format!("{}{}", trim_zeros(text), UNITS[ix].1)
+}
+
+/// Parse the number that may contains the Chinese numeral units `万` and `亿`.
+///
+/// The thousand separators `,` will be ignored, returns `None` if the text is not a valid number.
+///
+/// ```ignore
+/// use ui::format::parse_cjk_number;
+///
+/// assert_eq!(parse_cjk_number("1.5万"), Some(15000.));
+/// assert_eq!(parse_cjk_number("1亿2000万"), Some(120000000.));
+/// assert_eq!(parse_cjk_number("1,234"), Some(1234.));
+/// ```
+pub fn parse_cjk_number(text: &str) -> Option<f64> {
+    let text = text.trim().replace([',', ' '], "");
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text.as_str()),
+    };
+
+    let value = match text.split_once('亿') {
+        Some((yi, rest)) => parse_wan_section(yi)? * YI + parse_optional_section(rest)?,
+        None => parse_wan_section(text)?,
+    };
+
+    Some(if negative { -value } else { value })
+}
+
+/// Parse a section under `亿`, e.g.: `1.5万`, `2000万5000`, `123`.
+fn parse_wan_section(text: &str) -> Option<f64> {
+    match text.split_once('万') {
+        Some((wan, rest)) => Some(parse_plain(wan)? * WAN + parse_optional_section(rest)?),
+        None => parse_plain(text),
+    }
+}
+
+fn parse_optional_section(text: &str) -> Option<f64> {
+    if text.is_empty() {
+        Some(0.)
+    } else {
+        parse_wan_section(text)
+    }
+}
+
+fn parse_plain(text: &str) -> Option<f64> {
+    if text.is_empty() || text.starts_with(['-', '+']) {
+        return None;
+    }
+
+    text.parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+fn trim_zeros(text: String) -> String {
+    if !text.contains('.') {
+        return text;
+    }
+
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_cjk_number() {
+        assert_eq!(format_cjk_number(0., 2), "0");
+        assert_eq!(format_cjk_number(9999., 2), "9999");
+        assert_eq!(format_cjk_number(10000., 2), "1万");
+        assert_eq!(format_cjk_number(15000., 2), "1.5万");
+        assert_eq!(format_cjk_number(-15000., 2), "-1.5万");
+        assert_eq!(format_cjk_number(12345678., 2), "1234.57万");
+        assert_eq!(format_cjk_number(150000000., 2), "1.5亿");
+        assert_eq!(format_cjk_number(1.2345, 2), "1.23");
+        assert_eq!(format_cjk_number(12000., 0), "1万");
+        assert_eq!(format_cjk_number(9999.9, 0), "1万");
+        assert_eq!(format_cjk_number(-9999.9, 0), "-1万");
+        assert_eq!(format_cjk_number(9999.9, 1), "9999.9");
+        assert_eq!(format_cjk_number(99999999.9, 2), "1亿");
+        assert_eq!(format_cjk_number(99994999., 2), "9999.5万");
+    }
+
+    #[test]
+    fn test_number_format() {
+        let en = NumberFormat::for_locale("en");
+        assert_eq!(en.format(0., 2), "0.00");
+        assert_eq!(en.format(999., 0), "999");
+        assert_eq!(en.format(1234.567, 2), "1,234.57");
+        assert_eq!(en.format(-1234567., 0), "-1,234,567");
+        assert_eq!(en.format(-0.001, 2), "0.00");
+        assert_eq!(en.format_currency(-1234.5, 2, "$"), "-$1,234.50");
+        assert_eq!(en.format_percent(0.125, 1), "12.5%");
+        assert_eq!(en.parse("1,234.5"), Some(1234.5));
+        assert_eq!(en.parse("-12"), Some(-12.));
+        assert_eq!(en.parse(""), None);
+        assert_eq!(en.parse("abc"), None);
+
+        let de = NumberFormat::for_locale("de-DE");
+        assert_eq!(de.format(1234567.891, 2), "1.234.567,89");
+        assert_eq!(de.format_currency(1234.5, 2, "€"), "1.234,50 €");
+        assert_eq!(de.format_percent(0.125, 1), "12,5\u{a0}%");
+        assert_eq!(de.parse("1.234,5"), Some(1234.5));
+
+        let fr = NumberFormat::for_locale("fr_FR");
+        assert_eq!(fr.format(1234.5, 1), "1\u{a0}234,5");
+        assert_eq!(fr.parse("1 234,5"), Some(1234.5));
+
+        assert_eq!(NumberFormat::for_locale("zh-CN"), NumberFormat::default());
+        assert_eq!(NumberFormat::for_locale("unknown"), NumberFormat::default());
+    }
+
+    #[test]
+    fn test_parse_cjk_number() {
+        assert_eq!(parse_cjk_number("1.5万"), Some(15000.));
+        assert_eq!(parse_cjk_number("-1.5万"), Some(-15000.));
+        assert_eq!(parse_cjk_number("2亿"), Some(200000000.));
+        assert_eq!(parse_cjk_number("1亿2000万"), Some(120000000.));
+        assert_eq!(parse_cjk_number("1亿2000万500"), Some(120000500.));
+        assert_eq!(parse_cjk_number("1万亿"), Some(1e12));
+        assert_eq!(parse_cjk_number(" 1,234.5 "), Some(1234.5));
+        assert_eq!(parse_cjk_number("123"), Some(123.));
+
+        assert_eq!(parse_cjk_number(""), None);
+        assert_eq!(parse_cjk_number("万"), None);
+        assert_eq!(parse_cjk_number("abc"), None);
+        assert_eq!(parse_cjk_number("1万-2"), None);
+    }
+}
@@ -0,0 +1,140 @@
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, AppContext, Corner, ElementId, Global, IntoElement,
+    ParentElement as _, RenderOnce, SharedString, Styled, VisualContext as _, WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    link::Link,
+    markdown::Markdown,
+    popover::{Popover, PopoverContent},
+    v_flex, IconName, Sizable as _, StyledExt as _,
+};
+
+/// The global switch to show or hide all the [`HelpMarker`]s.
+struct HelpMarkers {
+    visible: bool,
+}
+
+impl Global for HelpMarkers {}
+
+/// Show or hide all the [`HelpMarker`]s, e.g.: for the experienced users, default: visible
+pub fn set_help_markers_visible(visible: bool, cx: &mut AppContext) {
+    cx.set_global(HelpMarkers { visible });
+    cx.refresh();
+}
+
+/// Returns true if the [`HelpMarker`]s are visible.
+pub fn help_markers_visible(cx: &AppContext) -> bool {
+    cx.try_global::<HelpMarkers>()
+        .map_or(true, |markers| markers.visible)
+}
+
+/// A small `?` icon next to a complex setting, click to open a popover with the markdown docs.
+///
+/// ```ignore
+/// h_flex()
+///     .gap_1()
+///     .child("Margin ratio")
+///     .child(
+///         HelpMarker::new("margin-ratio-help", "The **margin ratio** is the equity divided by ...")
+///             .title("Margin ratio")
+///             .learn_more("https://example.com/docs/margin"),
+///     )
+/// ```
+#[derive(IntoElement)]
+pub struct HelpMarker {
+    id: ElementId,
+    title: Option<SharedString>,
+    content: SharedString,
+    links: Vec<(SharedString, SharedString)>,
+    anchor: Corner,
+}
+
+impl HelpMarker {
+    /// Create a HelpMarker with the markdown `content`.
+    pub fn new(id: impl Into<ElementId>, content: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            title: None,
+            content: content.into(),
+            links: Vec::new(),
+            anchor: Corner::TopLeft,
+        }
+    }
+
+    pub fn title(mut self, title: impl Into<SharedString>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Add a link at the bottom of the popover.
+    pub fn link(mut self, label: impl Into<SharedString>, url: impl Into<SharedString>) -> Self {
+        self.links.push((label.into(), url.into()));
+        self
+    }
+
+    /// Add a "Learn more" link at the bottom of the popover.
+    pub fn learn_more(self, url: impl Into<SharedString>) -> Self {
+        self.link(t!("HelpMarker.Learn more").to_string(), url)
+    }
+
+    /// Set the anchor corner of the popover, default: [`Corner::TopLeft`]
+    pub fn anchor(mut self, anchor: Corner) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+impl RenderOnce for HelpMarker {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        if !help_markers_visible(cx) {
+            return div().into_any_element();
+        }
+
+        let title = self.title;
+        let content = self.content;
+        let links = Rc::new(self.links);
+
+        Popover::new(self.id.clone())
+            .anchor(self.anchor)
+            .trigger(
+                Button::new(self.id)
+                    .ghost()
+                    .xsmall()
+                    .compact()
+                    .icon(IconName::CircleHelp),
+            )
+            .content(move |cx| {
+                let title = title.clone();
+                let content = content.clone();
+                let links = links.clone();
+                cx.new_view(|cx| {
+                    PopoverContent::new(cx, move |_| {
+                        v_flex()
+                            .gap_2()
+                            .w(px(320.))
+                            .when_some(title.clone(), |this, title| {
+                                this.child(div().font_semibold().child(title))
+                            })
+                            .child(Markdown::new("help-marker-content", content.clone()).text_sm())
+                            .when(!links.is_empty(), |this| {
+                                this.child(h_flex().gap_3().text_sm().children(
+                                    links.iter().enumerate().map(|(ix, (label, url))| {
+                                        Link::new(("help-marker-link", ix))
+                                            .href(url.clone())
+                                            .child(label.clone())
+                                    }),
+                                ))
+                            })
+                            .into_any()
+                    })
+                })
+            })
+            .into_any_element()
+    }
+}
@@ -8,6 +8,13 @@ pub trait HistoryItem: Clone {
     fn set_version(&mut self, version: usize);
 }
 
+/// The state of the [`History`] to enable or disable the Undo / Redo menu items or buttons.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryState {
+    pub can_undo: bool,
+    pub can_redo: bool,
+}
+
 /// The History is used to keep track of changes to a model and to allow undo and redo operations.
 ///
 /// This is now used in Input for undo/redo operations. You can also use this in
@@ -22,6 +29,11 @@ pub struct History<I: HistoryItem> {
     pub(crate) ignore: bool,
     max_undo: usize,
     group_interval: Option<Duration>,
+    /// The depth of the nested [`History::begin_grouping`].
+    grouping_depth: usize,
+    /// Start a new group on the next push, after the explicit grouping is ended.
+    new_group: bool,
+    last_state: HistoryState,
 }
 
 impl<I> History<I>
@@ -37,10 +49,15 @@ where
             version: 0,
             max_undo: 1000,
             group_interval: None,
+            grouping_depth: 0,
+            new_group: false,
+            last_state: HistoryState::default(),
         }
     }
 
     /// Set the maximum number of undo steps to keep, defaults to 1000.
+    ///
+    /// The oldest group is dropped as a whole when exceeded, so an undo never restores half of a group.
     pub fn max_undo(mut self, max_undo: usize) -> Self {
         self.max_undo = max_undo;
         self
@@ -53,10 +70,15 @@ where
     }
 
     /// Increment the version number if the last change was made more than `GROUP_INTERVAL` milliseconds ago.
+    ///
+    /// The version is kept in the explicit grouping.
     fn inc_version(&mut self) -> usize {
         let t = Instant::now();
-        if Some(self.last_changed_at.elapsed()) > self.group_interval {
+        if self.grouping_depth == 0
+            && (self.new_group || Some(self.last_changed_at.elapsed()) > self.group_interval)
+        {
             self.version += 1;
+            self.new_group = false;
         }
 
         self.last_changed_at = t;
@@ -68,11 +90,91 @@ where
         self.version
     }
 
+    /// Begin an explicit group, all the changes pushed until [`History::end_grouping`] are undone
+    /// and redone together, regardless of the `group_interval`.
+    ///
+    /// The grouping can be nested, the group is ended by the outermost `end_grouping`.
+    pub fn begin_grouping(&mut self) {
+        if self.grouping_depth == 0 {
+            self.version += 1;
+            self.new_group = false;
+        }
+        self.grouping_depth += 1;
+    }
+
+    /// End the group started by [`History::begin_grouping`], the next change starts a new group.
+    pub fn end_grouping(&mut self) {
+        if self.grouping_depth == 0 {
+            return;
+        }
+
+        self.grouping_depth -= 1;
+        if self.grouping_depth == 0 {
+            self.new_group = true;
+        }
+    }
+
+    /// Returns true if in the explicit grouping.
+    pub fn is_grouping(&self) -> bool {
+        self.grouping_depth > 0
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undos.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redos.is_empty()
+    }
+
+    pub fn state(&self) -> HistoryState {
+        HistoryState {
+            can_undo: self.can_undo(),
+            can_redo: self.can_redo(),
+        }
+    }
+
+    /// Returns the new state if changed since the last call, to emit an event to update the Undo / Redo UI.
+    ///
+    /// ```ignore
+    /// if let Some(state) = self.history.take_state_change() {
+    ///     cx.emit(MyEvent::HistoryChange(state));
+    /// }
+    /// ```
+    pub fn take_state_change(&mut self) -> Option<HistoryState> {
+        let state = self.state();
+        if state == self.last_state {
+            return None;
+        }
+
+        self.last_state = state;
+        Some(state)
+    }
+
+    /// Remove all the undo and redo steps.
+    pub fn clear(&mut self) {
+        self.undos.clear();
+        self.redos.clear();
+        self.new_group = true;
+    }
+
     pub fn push(&mut self, item: I) {
         let version = self.inc_version();
 
-        if self.undos.len() >= self.max_undo {
-            self.undos.remove(0);
+        if self.undos.len() >= self.max_undo.max(1) {
+            // Drop the oldest group as a whole.
+            let oldest = self.undos[0].version();
+            let len = self
+                .undos
+                .iter()
+                .take_while(|item| item.version() == oldest)
+                .count();
+            // Keep the group in progress, even if it's larger than the max.
+            if oldest != version {
+                self.undos.drain(..len);
+            } else {
+                self.undos.remove(0);
+            }
         }
 
         let mut item = item;
@@ -80,6 +182,11 @@ where
         self.undos.push(item);
     }
 
+    /// Returns the version of the group to be undone next.
+    pub(crate) fn undo_version(&self) -> Option<usize> {
+        self.undos.last().map(|item| item.version())
+    }
+
     pub fn undo(&mut self) -> Option<Vec<I>> {
         if let Some(first_change) = self.undos.pop() {
             let mut changes = vec![first_change.clone()];
@@ -194,4 +301,82 @@ mod tests {
 
         assert_eq!(history.undo().is_none(), true);
     }
+
+    #[test]
+    fn test_grouping() {
+        let mut history: History<TabIndex> = History::new().group_interval(Duration::from_secs(60));
+        // Grouped by the interval.
+        history.push(0.into());
+        history.push(1.into());
+        assert_eq!(history.version(), 0);
+
+        history.begin_grouping();
+        history.push(2.into());
+        history.begin_grouping();
+        history.push(3.into());
+        history.end_grouping();
+        assert!(history.is_grouping());
+        history.push(4.into());
+        history.end_grouping();
+        assert!(!history.is_grouping());
+        assert_eq!(history.version(), 1);
+
+        // A new group after the grouping ended, even in the interval.
+        history.push(5.into());
+        assert_eq!(history.version(), 2);
+
+        assert_eq!(history.undo().unwrap().len(), 1);
+        let changes = history.undo().unwrap();
+        assert_eq!(
+            changes.iter().map(|c| c.tab_index).collect::<Vec<_>>(),
+            vec![4, 3, 2]
+        );
+        assert_eq!(history.undo().unwrap().len(), 2);
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn test_max_undo_drops_group() {
+        let mut history: History<TabIndex> = History::new().max_undo(3);
+        history.begin_grouping();
+        history.push(0.into());
+        history.push(1.into());
+        history.end_grouping();
+        history.push(2.into());
+        history.push(3.into());
+
+        // The first group is dropped as a whole.
+        assert_eq!(history.undo().unwrap()[0].tab_index, 3);
+        assert_eq!(history.undo().unwrap()[0].tab_index, 2);
+        assert!(history.undo().is_none());
+    }
+
+    #[test]
+    fn test_state_change() {
+        let mut history: History<TabIndex> = History::new();
+        assert_eq!(history.take_state_change(), None);
+
+        history.push(0.into());
+        assert_eq!(
+            history.take_state_change(),
+            Some(HistoryState {
+                can_undo: true,
+                can_redo: false
+            })
+        );
+        history.push(1.into());
+        assert_eq!(history.take_state_change(), None);
+
+        history.undo();
+        assert_eq!(
+            history.take_state_change(),
+            Some(HistoryState {
+                can_undo: true,
+                can_redo: true
+            })
+        );
+
+        history.clear();
+        assert_eq!(history.take_state_change(), Some(HistoryState::default()));
+    }
 }
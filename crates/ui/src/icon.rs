@@ -13,6 +13,7 @@ pub enum IconName {
     ArrowUp,
     Asterisk,
     Bell,
+    BellOff,
     BookOpen,
     Bot,
     Calendar,
@@ -24,6 +25,7 @@ pub enum IconName {
     ChevronUp,
     ChevronsUpDown,
     CircleCheck,
+    CircleHelp,
     CircleUser,
     CircleX,
     Close,
@@ -38,6 +40,7 @@ pub enum IconName {
     GalleryVerticalEnd,
     GitHub,
     Globe,
+    GripVertical,
     Heart,
     HeartOff,
     Inbox,
@@ -60,6 +63,7 @@ pub enum IconName {
     PanelRight,
     PanelRightClose,
     PanelRightOpen,
+    Pin,
     Plus,
     ResizeCorner,
     Search,
@@ -90,6 +94,7 @@ impl IconName {
             Self::ArrowUp => "icons/arrow-up.svg",
             Self::Asterisk => "icons/asterisk.svg",
             Self::Bell => "icons/bell.svg",
+            Self::BellOff => "icons/bell-off.svg",
             Self::BookOpen => "icons/book-open.svg",
             Self::Bot => "icons/bot.svg",
             Self::Calendar => "icons/calendar.svg",
@@ -101,6 +106,7 @@ impl IconName {
             Self::ChevronUp => "icons/chevron-up.svg",
             Self::ChevronsUpDown => "icons/chevrons-up-down.svg",
             Self::CircleCheck => "icons/circle-check.svg",
+            Self::CircleHelp => "icons/circle-help.svg",
             Self::CircleUser => "icons/circle-user.svg",
             Self::CircleX => "icons/circle-x.svg",
             Self::Close => "icons/close.svg",
@@ -115,6 +121,7 @@ impl IconName {
             Self::GalleryVerticalEnd => "icons/gallery-vertical-end.svg",
             Self::GitHub => "icons/github.svg",
             Self::Globe => "icons/globe.svg",
+            Self::GripVertical => "icons/grip-vertical.svg",
             Self::Heart => "icons/heart.svg",
             Self::HeartOff => "icons/heart-off.svg",
             Self::Inbox => "icons/inbox.svg",
@@ -137,6 +144,7 @@ impl IconName {
             Self::PanelRight => "icons/panel-right.svg",
             Self::PanelRightClose => "icons/panel-right-close.svg",
             Self::PanelRightOpen => "icons/panel-right-open.svg",
+            Self::Pin => "icons/pin.svg",
             Self::Plus => "icons/plus.svg",
             Self::ResizeCorner => "icons/resize-corner.svg",
             Self::Search => "icons/search.svg",
@@ -288,6 +296,7 @@ impl RenderOnce for Icon {
                 Size::Small => this.size_3p5(),
                 Size::Medium => this.size_4(),
                 Size::Large => this.size_6(),
+                Size::XLarge => this.size_8(),
             })
             .path(self.path)
     }
@@ -312,6 +321,7 @@ impl Render for Icon {
                 Size::Small => this.size_3p5(),
                 Size::Medium => this.size_4(),
                 Size::Large => this.size_6(),
+                Size::XLarge => this.size_8(),
             })
             .path(self.path.clone())
             .when_some(self.rotation, |this, rotation| {
@@ -0,0 +1,310 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    sync::Arc,
+};
+
+use gpui::{
+    div, img, AnyElement, AppContext, Div, Global, IntoElement, ParentElement as _, RenderImage,
+    RenderOnce, SharedString, Styled, WindowContext,
+};
+use image::Frame;
+use smallvec::SmallVec;
+
+pub use gpui::ObjectFit;
+
+use crate::{fetch::Fetch, skeleton::Skeleton, theme::ActiveTheme as _, Icon, IconName};
+
+/// The default max number of the decoded images to keep in memory.
+const DEFAULT_CAPACITY: usize = 100;
+
+/// A cache that drops the least recently used entries when it's full.
+struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, V>,
+    /// The keys from the least to the most recently used.
+    order: VecDeque<K>,
+}
+
+impl<K: Hash + Eq + Clone, V: Clone> LruCache<K, V> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(ix) = self.order.iter().position(|k| k == key) {
+            self.order.remove(ix);
+        }
+        self.order.push_back(key.clone());
+    }
+
+    fn get(&mut self, key: &K) -> Option<V> {
+        let value = self.entries.get(key).cloned()?;
+        self.touch(key);
+        Some(value)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        self.touch(&key);
+        self.entries.insert(key, value);
+        self.evict();
+    }
+
+    fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            let Some(key) = self.order.pop_front() else {
+                break;
+            };
+            self.entries.remove(&key);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+}
+
+/// The loading state of a remote image.
+#[derive(Clone)]
+pub enum ImageState {
+    Loading,
+    Loaded(Arc<RenderImage>),
+    Failed(SharedString),
+}
+
+struct ImageCache {
+    images: LruCache<SharedString, Arc<RenderImage>>,
+    loading: HashSet<SharedString>,
+    failed: HashMap<SharedString, SharedString>,
+}
+
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self {
+            images: LruCache::new(DEFAULT_CAPACITY),
+            loading: HashSet::new(),
+            failed: HashMap::new(),
+        }
+    }
+}
+
+impl Global for ImageCache {}
+
+/// Set the max number of the decoded images to keep in memory, default: 100
+pub fn set_cache_capacity(capacity: usize, cx: &mut AppContext) {
+    cx.default_global::<ImageCache>()
+        .images
+        .set_capacity(capacity);
+}
+
+/// Remove all the cached images and the failures, the failed images will be loaded again.
+pub fn clear_cache(cx: &mut AppContext) {
+    let cache = cx.default_global::<ImageCache>();
+    cache.images.clear();
+    cache.failed.clear();
+}
+
+/// Returns the state of the remote image, the loading is started if not loaded.
+pub fn load_image(url: &SharedString, cx: &mut AppContext) -> ImageState {
+    let cache = cx.default_global::<ImageCache>();
+    if let Some(image) = cache.images.get(url) {
+        return ImageState::Loaded(image);
+    }
+    if let Some(err) = cache.failed.get(url) {
+        return ImageState::Failed(err.clone());
+    }
+    if !cache.loading.insert(url.clone()) {
+        return ImageState::Loading;
+    }
+
+    let url = url.clone();
+    let bytes = Fetch::new(url.clone())
+        .ttl(std::time::Duration::ZERO)
+        .bytes(cx);
+    cx.spawn(|mut cx| async move {
+        let result = match bytes.await {
+            Ok(bytes) => {
+                cx.background_executor()
+                    .spawn(async move { decode_image(&bytes) })
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+
+        _ = cx.update(|cx| {
+            let cache = cx.default_global::<ImageCache>();
+            cache.loading.remove(&url);
+            match result {
+                Ok(image) => cache.images.insert(url, image),
+                Err(err) => {
+                    cache.failed.insert(url, err.to_string().into());
+                }
+            }
+            cx.refresh();
+        });
+    })
+    .detach();
+
+    ImageState::Loading
+}
+
+/// Decode the image bytes (e.g.: PNG, JPEG, WebP) to the BGRA image to paint.
+fn decode_image(bytes: &[u8]) -> anyhow::Result<Arc<RenderImage>> {
+    let mut buffer = image::load_from_memory(bytes)?.into_rgba8();
+    // Convert from RGBA to BGRA.
+    for pixel in buffer.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+
+    Ok(Arc::new(RenderImage::new(SmallVec::from_elem(
+        Frame::new(buffer),
+        1,
+    ))))
+}
+
+fn is_remote(source: &str) -> bool {
+    source.starts_with("http://") || source.starts_with("https://")
+}
+
+/// A raster image element, the remote image (`http://` or `https://`) is loaded asynchronously,
+/// and the decoded image is kept in an LRU cache shared by the App.
+///
+/// The local images (the asset path or the file path) are loaded by the [`img`] of GPUI.
+///
+/// ```ignore
+/// Image::new("https://example.com/cover.png")
+///     .object_fit(ObjectFit::Cover)
+///     .w(px(320.))
+///     .h(px(180.))
+///     .rounded_md()
+/// ```
+#[derive(IntoElement)]
+pub struct Image {
+    base: Div,
+    source: SharedString,
+    object_fit: ObjectFit,
+    placeholder: Option<AnyElement>,
+    fallback: Option<AnyElement>,
+}
+
+impl Image {
+    pub fn new(source: impl Into<SharedString>) -> Self {
+        Self {
+            base: div(),
+            source: source.into(),
+            object_fit: ObjectFit::Contain,
+            placeholder: None,
+            fallback: None,
+        }
+    }
+
+    /// Set how the image fits the bounds, default: [`ObjectFit::Contain`]
+    pub fn object_fit(mut self, object_fit: ObjectFit) -> Self {
+        self.object_fit = object_fit;
+        self
+    }
+
+    /// Set the element to show while loading, default is a [`Skeleton`].
+    pub fn placeholder(mut self, placeholder: impl IntoElement) -> Self {
+        self.placeholder = Some(placeholder.into_any_element());
+        self
+    }
+
+    /// Set the element to show when the image failed to load, default is a warning icon.
+    pub fn fallback(mut self, fallback: impl IntoElement) -> Self {
+        self.fallback = Some(fallback.into_any_element());
+        self
+    }
+}
+
+impl Styled for Image {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Image {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let object_fit = self.object_fit;
+        let content = if !is_remote(&self.source) {
+            img(self.source)
+                .size_full()
+                .object_fit(object_fit)
+                .into_any_element()
+        } else {
+            match load_image(&self.source, cx) {
+                ImageState::Loaded(image) => img(image)
+                    .size_full()
+                    .object_fit(object_fit)
+                    .into_any_element(),
+                ImageState::Loading => self.placeholder.unwrap_or_else(|| {
+                    Skeleton::new()
+                        .size_full()
+                        .rounded_none()
+                        .into_any_element()
+                }),
+                ImageState::Failed(_) => self.fallback.unwrap_or_else(|| {
+                    div()
+                        .size_full()
+                        .flex()
+                        .items_center()
+                        .justify_center()
+                        .bg(cx.theme().muted)
+                        .text_color(cx.theme().muted_foreground)
+                        .child(Icon::new(IconName::TriangleAlert))
+                        .into_any_element()
+                }),
+            }
+        };
+
+        self.base.relative().overflow_hidden().child(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_remote, LruCache};
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        assert_eq!(cache.get(&"a"), Some(1));
+
+        // The `b` is the least recently used.
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(1));
+        assert_eq!(cache.get(&"c"), Some(3));
+
+        cache.insert("c", 4);
+        assert_eq!(cache.entries.len(), 2);
+        assert_eq!(cache.order.len(), 2);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.get(&"a"), None);
+        assert_eq!(cache.get(&"c"), Some(4));
+
+        cache.clear();
+        assert_eq!(cache.get(&"c"), None);
+    }
+
+    #[test]
+    fn test_is_remote() {
+        assert!(is_remote("https://example.com/a.png"));
+        assert!(is_remote("http://example.com/a.png"));
+        assert!(!is_remote("icons/a.png"));
+        assert!(!is_remote("/tmp/a.png"));
+    }
+}
@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use crate::{Icon, IconName, Sizable, Size};
+use crate::{theme::ActiveTheme as _, Icon, IconName, Sizable, Size};
 use gpui::{
     div, ease_in_out, percentage, prelude::FluentBuilder as _, Animation, AnimationExt as _, Hsla,
     IntoElement, ParentElement, RenderOnce, Styled as _, Transformation, WindowContext,
@@ -43,18 +43,25 @@ impl Sizable for Indicator {
 }
 
 impl RenderOnce for Indicator {
-    fn render(self, _: &mut WindowContext) -> impl IntoElement {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let icon = self
+            .icon
+            .with_size(self.size)
+            .when_some(self.color, |this, color| this.text_color(color));
+
         div()
-            .child(
-                self.icon
-                    .with_size(self.size)
-                    .when_some(self.color, |this, color| this.text_color(color))
-                    .with_animation(
+            .map(|this| {
+                // The spinning is disabled in the lite mode, show a static icon instead.
+                if cx.theme().lite {
+                    this.child(icon)
+                } else {
+                    this.child(icon.with_animation(
                         "circle",
                         Animation::new(self.speed).repeat().with_easing(ease_in_out),
                         |this, delta| this.transform(Transformation::rotate(percentage(delta))),
-                    ),
-            )
+                    ))
+                }
+            })
             .into_element()
     }
 }
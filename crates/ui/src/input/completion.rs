@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+use gpui::{SharedString, Task, WindowContext};
+
+use crate::primitives::listbox;
+
+/// A suggestion of the [`CompletionProvider`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    /// The text to display in the completion menu.
+    pub label: SharedString,
+    /// The text to replace the token, default is the `label`.
+    pub insert_text: SharedString,
+    /// The additional text to display at the right, e.g.: the name of the symbol.
+    pub detail: Option<SharedString>,
+}
+
+impl Completion {
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        let label = label.into();
+        Self {
+            insert_text: label.clone(),
+            label,
+            detail: None,
+        }
+    }
+
+    /// Set the text to replace the token, e.g.: `@alice ` for the mention.
+    pub fn insert_text(mut self, insert_text: impl Into<SharedString>) -> Self {
+        self.insert_text = insert_text.into();
+        self
+    }
+
+    /// Set the additional text to display at the right.
+    pub fn detail(mut self, detail: impl Into<SharedString>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Provide the completions for the token before the cursor of the [`TextInput`](super::TextInput),
+/// e.g.: the symbol lookup, the mentions.
+///
+/// ```ignore
+/// struct MentionProvider {
+///     users: Vec<SharedString>,
+/// }
+///
+/// impl CompletionProvider for MentionProvider {
+///     fn trigger_characters(&self) -> &[char] {
+///         &['@']
+///     }
+///
+///     fn completions(&self, token: &str, _: &mut WindowContext) -> Task<Vec<Completion>> {
+///         let query = token.trim_start_matches('@').to_lowercase();
+///         Task::ready(
+///             self.users
+///                 .iter()
+///                 .filter(|user| user.to_lowercase().starts_with(&query))
+///                 .map(|user| Completion::new(user.clone()).insert_text(format!("@{} ", user)))
+///                 .collect(),
+///         )
+///     }
+/// }
+///
+/// TextInput::new(cx).completion_provider(MentionProvider { users })
+/// ```
+pub trait CompletionProvider {
+    /// The chars to start the token, e.g.: `@` for the mentions, `$` for the symbols.
+    ///
+    /// Default is empty to complete any word before the cursor.
+    fn trigger_characters(&self) -> &[char] {
+        &[]
+    }
+
+    /// Returns the completions of the `token`, the token includes the trigger char if any.
+    fn completions(&self, token: &str, cx: &mut WindowContext) -> Task<Vec<Completion>>;
+}
+
+/// The state of the completion menu of the TextInput.
+pub(super) struct CompletionMenu {
+    /// The range of the token to replace when the menu is shown.
+    pub(super) range: Range<usize>,
+    pub(super) items: Vec<Completion>,
+    pub(super) selected_ix: usize,
+}
+
+impl CompletionMenu {
+    pub(super) fn new(range: Range<usize>, items: Vec<Completion>) -> Self {
+        Self {
+            range,
+            items,
+            selected_ix: 0,
+        }
+    }
+
+    pub(super) fn select_prev(&mut self) {
+        self.selected_ix =
+            listbox::prev_option(Some(self.selected_ix), self.items.len(), |_| false, true)
+                .unwrap_or(0);
+    }
+
+    pub(super) fn select_next(&mut self) {
+        self.selected_ix =
+            listbox::next_option(Some(self.selected_ix), self.items.len(), |_| false, true)
+                .unwrap_or(0);
+    }
+
+    pub(super) fn selected(&self) -> Option<&Completion> {
+        self.items.get(self.selected_ix)
+    }
+}
@@ -0,0 +1,135 @@
+use std::ops::Range;
+
+use gpui::Hsla;
+
+/// The style of a [`TextDecoration`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationStyle {
+    /// A wavy underline, e.g.: the spelling errors, the invalid ranges.
+    Squiggle,
+    /// A straight underline, e.g.: the mentions, the links.
+    Underline,
+    /// A background highlight under the text, e.g.: the search matches.
+    Background,
+    /// A line through the text, e.g.: the removed text.
+    Strikethrough,
+}
+
+/// A decoration of a range of the text in the [`TextInput`](super::TextInput).
+///
+/// The `range` is the byte range of the text, the decorations are not moved when
+/// the text is changed, so update them on the [`InputEvent::Change`](super::InputEvent::Change).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextDecoration {
+    pub range: Range<usize>,
+    pub style: DecorationStyle,
+    pub color: Hsla,
+}
+
+impl TextDecoration {
+    pub fn new(range: Range<usize>, style: DecorationStyle, color: impl Into<Hsla>) -> Self {
+        Self {
+            range,
+            style,
+            color: color.into(),
+        }
+    }
+
+    /// Create a wavy underline decoration.
+    pub fn squiggle(range: Range<usize>, color: impl Into<Hsla>) -> Self {
+        Self::new(range, DecorationStyle::Squiggle, color)
+    }
+
+    /// Create a straight underline decoration.
+    pub fn underline(range: Range<usize>, color: impl Into<Hsla>) -> Self {
+        Self::new(range, DecorationStyle::Underline, color)
+    }
+
+    /// Create a background highlight decoration.
+    pub fn background(range: Range<usize>, color: impl Into<Hsla>) -> Self {
+        Self::new(range, DecorationStyle::Background, color)
+    }
+
+    /// Create a strikethrough decoration.
+    pub fn strikethrough(range: Range<usize>, color: impl Into<Hsla>) -> Self {
+        Self::new(range, DecorationStyle::Strikethrough, color)
+    }
+}
+
+/// Clamp the range into the text, and move the bounds to the char boundaries.
+pub(super) fn clamp_range(text: &str, range: &Range<usize>) -> Range<usize> {
+    let floor = |mut offset: usize| {
+        offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    };
+
+    let start = floor(range.start);
+    start..floor(range.end).max(start)
+}
+
+/// Split the `0..len` into the segments by the bounds of the ranges,
+/// returns each segment with the indexes of the ranges that cover it.
+pub(super) fn decoration_segments(
+    len: usize,
+    ranges: &[Range<usize>],
+) -> Vec<(Range<usize>, Vec<usize>)> {
+    let mut bounds = vec![0, len];
+    for range in ranges {
+        bounds.push(range.start.min(len));
+        bounds.push(range.end.min(len));
+    }
+    bounds.sort_unstable();
+    bounds.dedup();
+
+    bounds
+        .windows(2)
+        .map(|pair| {
+            let segment = pair[0]..pair[1];
+            let covered = ranges
+                .iter()
+                .enumerate()
+                .filter(|(_, range)| range.start <= segment.start && segment.end <= range.end)
+                .map(|(ix, _)| ix)
+                .collect();
+            (segment, covered)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp_range, decoration_segments};
+
+    #[test]
+    fn test_decoration_segments() {
+        assert_eq!(decoration_segments(5, &[]), vec![(0..5, vec![])]);
+        assert_eq!(decoration_segments(0, &[]), vec![]);
+        assert_eq!(
+            decoration_segments(10, &[2..6, 4..8, 9..20]),
+            vec![
+                (0..2, vec![]),
+                (2..4, vec![0]),
+                (4..6, vec![0, 1]),
+                (6..8, vec![1]),
+                (8..9, vec![]),
+                (9..10, vec![2]),
+            ]
+        );
+        assert_eq!(
+            decoration_segments(4, &[0..4, 2..2]),
+            vec![(0..2, vec![0]), (2..4, vec![0])]
+        );
+    }
+
+    #[test]
+    fn test_clamp_range() {
+        let text = "Hello 世界";
+        assert_eq!(clamp_range(text, &(0..5)), 0..5);
+        assert_eq!(clamp_range(text, &(6..8)), 6..6);
+        assert_eq!(clamp_range(text, &(7..100)), 6..12);
+        assert_eq!(clamp_range(text, &(20..10)), 12..12);
+    }
+}
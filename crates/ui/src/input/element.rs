@@ -1,16 +1,20 @@
 use gpui::{
     fill, point, px, relative, size, Bounds, Corners, Element, ElementId, ElementInputHandler,
     GlobalElementId, IntoElement, LayoutId, MouseButton, MouseMoveEvent, PaintQuad, Path, Pixels,
-    Point, Style, TextRun, UnderlineStyle, View, WindowContext, WrappedLine,
+    Point, ShapedLine, SharedString, StrikethroughStyle, Style, TextRun, UnderlineStyle, View,
+    WindowContext, WrappedLine,
 };
 use smallvec::SmallVec;
 
-use crate::theme::ActiveTheme as _;
+use crate::{styled::text_run, theme::ActiveTheme as _};
 
+use super::decoration::{clamp_range, decoration_segments, DecorationStyle, TextDecoration};
 use super::TextInput;
 
 const RIGHT_MARGIN: Pixels = px(5.);
 const CURSOR_INSET: Pixels = px(0.5);
+/// The horizontal padding of the line numbers in the gutter.
+const GUTTER_PADDING: Pixels = px(8.);
 
 pub(super) struct TextElement {
     input: View<TextInput>,
@@ -43,8 +47,8 @@ impl TextElement {
         cx: &mut WindowContext,
     ) -> (Option<PaintQuad>, Point<Pixels>) {
         let input = self.input.read(cx);
-        let selected_range = &input.selected_range;
-        let cursor_offset = input.cursor_offset();
+        let selected_range = input.engine.selected_range();
+        let cursor_offset = input.engine.cursor_offset();
         let mut scroll_offset = input.scroll_handle.offset();
         let mut cursor = None;
 
@@ -113,7 +117,7 @@ impl TextElement {
                     scroll_offset.y
                 };
 
-                if input.selection_reversed {
+                if input.engine.is_selection_reversed() {
                     if scroll_offset.x + cursor_start.x < px(0.) {
                         // selection start is out of left
                         scroll_offset.x = -cursor_start.x;
@@ -162,7 +166,7 @@ impl TextElement {
         cx: &mut WindowContext,
     ) -> Option<Path<Pixels>> {
         let input = self.input.read(cx);
-        let selected_range = &input.selected_range;
+        let selected_range = input.engine.selected_range();
         if selected_range.is_empty() {
             return None;
         }
@@ -283,6 +287,11 @@ pub(super) struct PrepaintState {
     cursor_scroll_offset: Point<Pixels>,
     selection_path: Option<Path<Pixels>>,
     bounds: Bounds<Pixels>,
+    gutter_width: Pixels,
+    /// The line numbers of the visible lines, with the origin to paint.
+    line_numbers: Vec<(Point<Pixels>, ShapedLine)>,
+    /// The bounds of the line of the cursor to highlight.
+    current_line: Option<Bounds<Pixels>>,
 }
 
 impl IntoElement for TextElement {
@@ -356,9 +365,14 @@ impl Element for TextElement {
         let multi_line = self.input.read(cx).is_multi_line();
         let line_height = cx.line_height();
         let input = self.input.read(cx);
-        let text = input.text.clone();
+        let text = input.engine.text().clone();
         let placeholder = input.placeholder.clone();
         let style = cx.text_style();
+        let show_line_number = multi_line && input.line_number;
+        let soft_wrap = input.soft_wrap;
+        let highlight_current_line = multi_line && input.highlight_current_line;
+        let cursor_line = input.engine.line_for_offset(input.engine.cursor_offset());
+        let input_bounds = bounds;
         let mut bounds = bounds;
 
         let (display_text, text_color) = if text.is_empty() {
@@ -369,47 +383,90 @@ impl Element for TextElement {
                 cx.theme().foreground,
             )
         } else {
-            (text, cx.theme().foreground)
+            (text.clone(), cx.theme().foreground)
         };
 
-        let run = TextRun {
-            len: display_text.len(),
-            font: style.font(),
-            color: text_color,
-            background_color: None,
-            underline: None,
-            strikethrough: None,
+        let run = text_run(display_text.len(), &style, text_color);
+
+        let mut decorations = if text.is_empty() || input.masked {
+            vec![]
+        } else {
+            input
+                .decorations
+                .iter()
+                .map(|decoration| TextDecoration {
+                    range: clamp_range(&display_text, &decoration.range),
+                    ..decoration.clone()
+                })
+                .collect::<Vec<_>>()
         };
+        if let Some(marked_range) = input.engine.marked_range() {
+            decorations.push(TextDecoration::underline(
+                clamp_range(&display_text, &marked_range),
+                run.color,
+            ));
+        }
 
-        let runs = if let Some(marked_range) = input.marked_range.as_ref() {
-            vec![
-                TextRun {
-                    len: marked_range.start,
-                    ..run.clone()
-                },
-                TextRun {
-                    len: marked_range.end - marked_range.start,
-                    underline: Some(UnderlineStyle {
-                        color: Some(run.color),
-                        thickness: px(1.0),
-                        wavy: false,
-                    }),
-                    ..run.clone()
-                },
-                TextRun {
-                    len: display_text.len() - marked_range.end,
-                    ..run.clone()
-                },
-            ]
+        let ranges = decorations
+            .iter()
+            .map(|decoration| decoration.range.clone())
+            .collect::<Vec<_>>();
+        let runs = decoration_segments(display_text.len(), &ranges)
             .into_iter()
-            .filter(|run| run.len > 0)
-            .collect()
+            .map(|(segment, covered)| {
+                let mut run = TextRun {
+                    len: segment.len(),
+                    ..run.clone()
+                };
+                for ix in covered {
+                    let decoration = &decorations[ix];
+                    match decoration.style {
+                        DecorationStyle::Squiggle | DecorationStyle::Underline => {
+                            run.underline = Some(UnderlineStyle {
+                                color: Some(decoration.color),
+                                thickness: px(1.0),
+                                wavy: decoration.style == DecorationStyle::Squiggle,
+                            });
+                        }
+                        DecorationStyle::Background => {
+                            run.background_color = Some(decoration.color);
+                        }
+                        DecorationStyle::Strikethrough => {
+                            run.strikethrough = Some(StrikethroughStyle {
+                                color: Some(decoration.color),
+                                thickness: px(1.0),
+                            });
+                        }
+                    }
+                }
+                run
+            })
+            .collect::<Vec<_>>();
+
+        let font_size = style.font_size.to_pixels(cx.rem_size());
+        let number_run = |len: usize, color| text_run(len, &style, color);
+
+        // Reserve the gutter for the widest line number.
+        let gutter_width = if show_line_number {
+            let digits = (text.matches('\n').count() + 1).to_string().len();
+            let sample: SharedString = "8".repeat(digits).into();
+            let digits_width = cx
+                .text_system()
+                .shape_line(
+                    sample.clone(),
+                    font_size,
+                    &[number_run(sample.len(), text_color)],
+                )
+                .map(|line| line.width)
+                .unwrap_or_default();
+            digits_width + GUTTER_PADDING * 2.
         } else {
-            vec![run]
+            px(0.)
         };
+        bounds.origin.x += gutter_width;
+        bounds.size.width -= gutter_width;
 
-        let font_size = style.font_size.to_pixels(cx.rem_size());
-        let wrap_width = if multi_line {
+        let wrap_width = if multi_line && soft_wrap {
             Some(bounds.size.width - RIGHT_MARGIN)
         } else {
             None
@@ -456,12 +513,59 @@ impl Element for TextElement {
 
         let selection_path = self.layout_selections(&lines, line_height, &mut bounds, cx);
 
+        let mut line_numbers = vec![];
+        let mut current_line = None;
+        if show_line_number || highlight_current_line {
+            let mut offset_y = px(0.);
+            for (ix, line) in lines.iter().enumerate() {
+                let top = bounds.top() + offset_y;
+                let height = line.size(line_height).height;
+                offset_y += height;
+
+                if top + height < input_bounds.top() {
+                    continue;
+                }
+                if top > input_bounds.bottom() {
+                    break;
+                }
+
+                let is_current = ix == cursor_line;
+                if is_current && highlight_current_line {
+                    current_line = Some(Bounds::new(
+                        point(input_bounds.left(), top),
+                        size(input_bounds.size.width, height),
+                    ));
+                }
+
+                if show_line_number {
+                    let number: SharedString = (ix + 1).to_string().into();
+                    let color = if is_current {
+                        cx.theme().foreground
+                    } else {
+                        cx.theme().muted_foreground
+                    };
+                    if let Ok(shaped) = cx.text_system().shape_line(
+                        number.clone(),
+                        font_size,
+                        &[number_run(number.len(), color)],
+                    ) {
+                        // Right align the numbers in the gutter.
+                        let x = input_bounds.left() + gutter_width - GUTTER_PADDING - shaped.width;
+                        line_numbers.push((point(x, top), shaped));
+                    }
+                }
+            }
+        }
+
         PrepaintState {
             bounds,
             lines,
             cursor,
             cursor_scroll_offset,
             selection_path,
+            gutter_width,
+            line_numbers,
+            current_line,
         }
     }
 
@@ -476,13 +580,17 @@ impl Element for TextElement {
         let focus_handle = self.input.read(cx).focus_handle.clone();
         let focused = focus_handle.is_focused(cx);
         let bounds = prepaint.bounds;
-        let selected_range = self.input.read(cx).selected_range.clone();
+        let selected_range = self.input.read(cx).engine.selected_range();
 
         cx.handle_input(
             &focus_handle,
             ElementInputHandler::new(bounds, self.input.clone()),
         );
 
+        if let Some(current_line) = prepaint.current_line.take() {
+            cx.paint_quad(fill(current_line, cx.theme().accent.opacity(0.5)));
+        }
+
         // Paint selections
         if let Some(path) = prepaint.selection_path.take() {
             cx.paint_path(path, cx.theme().selection);
@@ -505,6 +613,20 @@ impl Element for TextElement {
             }
         }
 
+        // Paint the gutter over the text scrolled horizontally.
+        if prepaint.gutter_width > px(0.) {
+            cx.paint_quad(fill(
+                Bounds::new(
+                    input_bounds.origin,
+                    size(prepaint.gutter_width, input_bounds.size.height),
+                ),
+                cx.theme().background,
+            ));
+            for (origin, number) in prepaint.line_numbers.iter() {
+                _ = number.paint(*origin, line_height, cx);
+            }
+        }
+
         let width = prepaint
             .lines
             .iter()
@@ -517,12 +639,12 @@ impl Element for TextElement {
             .map(|l| l.size(line_height).height.0)
             .sum::<f32>();
 
-        let scroll_size = size(width, px(height));
+        let scroll_size = size(width + prepaint.gutter_width, px(height));
 
         self.input.update(cx, |input, _cx| {
             input.last_layout = Some(prepaint.lines.clone());
             input.last_bounds = Some(bounds);
-            input.last_cursor_offset = Some(input.cursor_offset());
+            input.last_cursor_offset = Some(input.engine.cursor_offset());
             input.last_line_height = line_height;
             input.input_bounds = input_bounds;
             input.last_selected_range = Some(selected_range);
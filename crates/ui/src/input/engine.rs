@@ -0,0 +1,557 @@
+use std::ops::Range;
+
+use gpui::SharedString;
+use unicode_segmentation::*;
+
+use super::change::Change;
+use crate::history::{History, HistoryState};
+
+/// The text editing engine without any view, shared by the text inputs.
+///
+/// It keeps the text, the selection, the IME marked range and the undo history,
+/// all the offsets are the UTF-8 byte offsets of the text, unless the method name has `utf16`.
+///
+/// The validation (pattern, mask) is not included, the input should check the
+/// pending text before calling the `replace_*` methods.
+pub struct TextEngine {
+    text: SharedString,
+    selected_range: Range<usize>,
+    selection_reversed: bool,
+    /// Range for save the selected word, use to keep word range when drag move.
+    selected_word_range: Option<Range<usize>>,
+    marked_range: Option<Range<usize>>,
+    history: History<Change>,
+}
+
+impl Default for TextEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextEngine {
+    pub fn new() -> Self {
+        Self {
+            text: "".into(),
+            selected_range: 0..0,
+            selection_reversed: false,
+            selected_word_range: None,
+            marked_range: None,
+            history: History::new().group_interval(std::time::Duration::from_secs(1)),
+        }
+    }
+
+    /// Return the text.
+    pub fn text(&self) -> &SharedString {
+        &self.text
+    }
+
+    /// Return the length of the text in bytes.
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Return the selected range, the start is always less than or equal to the end.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.selected_range.clone()
+    }
+
+    /// Return true if the cursor is at the start of the selection.
+    pub fn is_selection_reversed(&self) -> bool {
+        self.selection_reversed
+    }
+
+    /// Return the range of the IME composing text.
+    pub fn marked_range(&self) -> Option<Range<usize>> {
+        self.marked_range.clone()
+    }
+
+    /// Return the selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selected_range.clone()]
+    }
+
+    /// Return the text in the range.
+    pub fn text_for_range(&self, range: Range<usize>) -> &str {
+        &self.text[range]
+    }
+
+    /// Return the offset of the cursor.
+    pub fn cursor_offset(&self) -> usize {
+        if self.selection_reversed {
+            self.selected_range.start
+        } else {
+            self.selected_range.end
+        }
+    }
+
+    /// Set the text without history, the cursor will be moved to the start.
+    pub fn set_text(&mut self, text: impl Into<SharedString>) {
+        self.text = text.into();
+        self.selected_range = 0..0;
+        self.selection_reversed = false;
+        self.selected_word_range = None;
+        self.marked_range = None;
+    }
+
+    /// Move the cursor to the offset, and clear the selection.
+    pub fn move_to(&mut self, offset: usize) {
+        let offset = offset.min(self.text.len());
+        self.selected_range = offset..offset;
+        self.selection_reversed = false;
+    }
+
+    /// Extend the selection from the anchor to the offset.
+    pub fn select_to(&mut self, offset: usize) {
+        let offset = offset.min(self.text.len());
+        if self.selection_reversed {
+            self.selected_range.start = offset
+        } else {
+            self.selected_range.end = offset
+        };
+
+        if self.selected_range.end < self.selected_range.start {
+            self.selection_reversed = !self.selection_reversed;
+            self.selected_range = self.selected_range.end..self.selected_range.start;
+        }
+
+        // Ensure keep word selected range
+        if let Some(word_range) = self.selected_word_range.as_ref() {
+            if self.selected_range.start > word_range.start {
+                self.selected_range.start = word_range.start;
+            }
+            if self.selected_range.end < word_range.end {
+                self.selected_range.end = word_range.end;
+            }
+        }
+    }
+
+    /// Select all the text.
+    pub fn select_all(&mut self) {
+        self.move_to(0);
+        self.select_to(self.text.len());
+    }
+
+    /// Select the word at the offset, the word range will be kept when extending the selection,
+    /// until [`TextEngine::end_word_selection`] is called.
+    pub fn select_word(&mut self, offset: usize) {
+        let range = self.word_range(offset);
+        self.selected_range = range.clone();
+        self.selection_reversed = false;
+        self.selected_word_range = Some(range);
+    }
+
+    /// Stop keeping the word range of [`TextEngine::select_word`].
+    pub fn end_word_selection(&mut self) {
+        self.selected_word_range = None;
+    }
+
+    /// Clear the selection, and keep the cursor.
+    pub fn unselect(&mut self) {
+        let offset = self.cursor_offset();
+        self.selected_range = offset..offset;
+    }
+
+    /// Return the range of the word at the offset, the word is the alphanumeric chars and `_`.
+    pub fn word_range(&self, offset: usize) -> Range<usize> {
+        fn is_word(c: char) -> bool {
+            c.is_alphanumeric() || matches!(c, '_')
+        }
+
+        let offset = self.clip_offset(offset);
+        let start = self.text[..offset]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word(*c))
+            .last()
+            .map(|(ix, _)| ix)
+            .unwrap_or(offset);
+        let end = self.text[offset..]
+            .char_indices()
+            .find(|(_, c)| !is_word(*c))
+            .map(|(ix, _)| offset + ix)
+            .unwrap_or(self.text.len());
+
+        start..end
+    }
+
+    /// Return the range of the token before the offset to complete, that is the word chars before the offset,
+    /// with the leading trigger char if any, e.g.: `@` of the mentions.
+    ///
+    /// Returns `None` if the token is empty, or the token has no trigger char when the `triggers` is not empty.
+    pub fn token_before(&self, offset: usize, triggers: &[char]) -> Option<Range<usize>> {
+        let offset = self.clip_offset(offset);
+        let start = self.word_range(offset).start.min(offset);
+        let trigger = self.text[..start]
+            .chars()
+            .next_back()
+            .filter(|c| triggers.contains(c));
+
+        match trigger {
+            Some(c) => Some(start - c.len_utf8()..offset),
+            None if triggers.is_empty() && start < offset => Some(start..offset),
+            None => None,
+        }
+    }
+
+    /// Return the offset of the previous grapheme boundary.
+    pub fn previous_boundary(&self, offset: usize) -> usize {
+        self.text
+            .grapheme_indices(true)
+            .rev()
+            .find_map(|(idx, _)| (idx < offset).then_some(idx))
+            .unwrap_or(0)
+    }
+
+    /// Return the offset of the next grapheme boundary.
+    pub fn next_boundary(&self, offset: usize) -> usize {
+        self.text
+            .grapheme_indices(true)
+            .find_map(|(idx, _)| (idx > offset).then_some(idx))
+            .unwrap_or(self.text.len())
+    }
+
+    /// Return the offset of the start of the line at the offset.
+    pub fn start_of_line(&self, offset: usize) -> usize {
+        let offset = self.clip_offset(offset);
+        self.text[..offset]
+            .rfind('\n')
+            .map(|ix| ix + 1)
+            .unwrap_or(0)
+    }
+
+    /// Return the offset of the start of the `line` (starts from 0), or the start of the last line if out of range.
+    pub fn offset_for_line(&self, line: usize) -> usize {
+        self.text
+            .match_indices('\n')
+            .take(line)
+            .last()
+            .map(|(ix, _)| ix + 1)
+            .unwrap_or(0)
+    }
+
+    /// Return the line (starts from 0) of the offset.
+    pub fn line_for_offset(&self, offset: usize) -> usize {
+        let offset = self.clip_offset(offset);
+        self.text[..offset].matches('\n').count()
+    }
+
+    /// Return the offset of the end of the line at the offset, before the `\n`.
+    pub fn end_of_line(&self, offset: usize) -> usize {
+        let offset = self.clip_offset(offset);
+        self.text[offset..]
+            .find('\n')
+            .map(|ix| offset + ix)
+            .unwrap_or(self.text.len())
+    }
+
+    /// Clip the offset to the text length and the previous char boundary.
+    fn clip_offset(&self, offset: usize) -> usize {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        offset
+    }
+
+    pub fn offset_from_utf16(&self, offset: usize) -> usize {
+        offset_from_utf16(&self.text, offset)
+    }
+
+    pub fn offset_to_utf16(&self, offset: usize) -> usize {
+        let mut utf16_offset = 0;
+        let mut utf8_count = 0;
+
+        for ch in self.text.chars() {
+            if utf8_count >= offset {
+                break;
+            }
+            utf8_count += ch.len_utf8();
+            utf16_offset += ch.len_utf16();
+        }
+
+        utf16_offset
+    }
+
+    pub fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
+    }
+
+    pub fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
+        self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
+    }
+
+    /// Return the range to edit, the `range_utf16` if given, otherwise the marked range or the selected range.
+    pub fn edit_range(&self, range_utf16: Option<&Range<usize>>) -> Range<usize> {
+        range_utf16
+            .map(|range_utf16| self.range_from_utf16(range_utf16))
+            .or(self.marked_range.clone())
+            .unwrap_or(self.selected_range.clone())
+    }
+
+    /// Return the text after replacing the range with the `new_text`, without applying it.
+    pub fn pending_text(&self, range: &Range<usize>, new_text: &str) -> String {
+        self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]
+    }
+
+    /// Replace the text in the range with the `new_text`, and move the cursor after the new text.
+    pub fn replace_text_in_range(&mut self, range: Range<usize>, new_text: &str) {
+        let cursor = range.start + new_text.len();
+        let text = self.pending_text(&range, new_text);
+        self.push_history(&range, new_text);
+        self.text = text.into();
+        self.selected_range = cursor..cursor;
+        self.selection_reversed = false;
+        self.marked_range = None;
+    }
+
+    /// Replace the whole text, and move the cursor to the `cursor` offset.
+    ///
+    /// This is used when the change affects the text out of the edited range, e.g.: the mask pattern.
+    pub fn replace_all(&mut self, text: impl Into<SharedString>, cursor: usize) {
+        let text: SharedString = text.into();
+        self.push_history(&(0..self.text.len()), &text);
+        self.text = text;
+        let cursor = self.clip_offset(cursor);
+        self.selected_range = cursor..cursor;
+        self.selection_reversed = false;
+        self.marked_range = None;
+    }
+
+    /// Replace the text in the range with the IME composing text, and mark it.
+    ///
+    /// The `new_selected_range_utf16` is relative to the `new_text`.
+    pub fn replace_and_mark_text_in_range(
+        &mut self,
+        range: Range<usize>,
+        new_text: &str,
+        new_selected_range_utf16: Option<Range<usize>>,
+    ) {
+        let text = self.pending_text(&range, new_text);
+        self.push_history(&range, new_text);
+        self.text = text.into();
+        self.marked_range = Some(range.start..range.start + new_text.len());
+        self.selected_range = new_selected_range_utf16
+            .map(|range_utf16| {
+                range.start + offset_from_utf16(new_text, range_utf16.start)
+                    ..range.start + offset_from_utf16(new_text, range_utf16.end)
+            })
+            .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
+        self.selection_reversed = false;
+    }
+
+    /// Clear the IME marked range.
+    pub fn unmark_text(&mut self) {
+        self.marked_range = None;
+    }
+
+    fn push_history(&mut self, range: &Range<usize>, new_text: &str) {
+        if self.history.ignore {
+            return;
+        }
+
+        let old_text = self.text[range.clone()].to_string();
+        let new_range = range.start..range.start + new_text.len();
+        self.history
+            .push(Change::new(range.clone(), &old_text, new_range, new_text));
+    }
+
+    /// Group the following changes into one undo step, see [`History::begin_grouping`].
+    pub fn begin_grouping(&mut self) {
+        self.history.begin_grouping();
+    }
+
+    pub fn end_grouping(&mut self) {
+        self.history.end_grouping();
+    }
+
+    pub fn history_state(&self) -> HistoryState {
+        self.history.state()
+    }
+
+    /// Returns the new history state if changed since the last call.
+    pub fn take_history_change(&mut self) -> Option<HistoryState> {
+        self.history.take_state_change()
+    }
+
+    /// Undo the last changes, return true if the text has been changed.
+    pub fn undo(&mut self) -> bool {
+        let Some(changes) = self.history.undo() else {
+            return false;
+        };
+
+        self.history.ignore = true;
+        for change in changes {
+            self.replace_text_in_range(change.new_range.clone(), &change.old_text);
+        }
+        self.history.ignore = false;
+        true
+    }
+
+    /// Redo the last undone changes, return true if the text has been changed.
+    pub fn redo(&mut self) -> bool {
+        let Some(changes) = self.history.redo() else {
+            return false;
+        };
+
+        self.history.ignore = true;
+        for change in changes {
+            self.replace_text_in_range(change.old_range.clone(), &change.new_text);
+        }
+        self.history.ignore = false;
+        true
+    }
+}
+
+fn offset_from_utf16(text: &str, offset: usize) -> usize {
+    let mut utf8_offset = 0;
+    let mut utf16_count = 0;
+
+    for ch in text.chars() {
+        if utf16_count >= offset {
+            break;
+        }
+        utf16_count += ch.len_utf16();
+        utf8_offset += ch.len_utf8();
+    }
+
+    utf8_offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TextEngine;
+
+    fn engine(text: &str) -> TextEngine {
+        let mut engine = TextEngine::new();
+        engine.set_text(text.to_string());
+        engine
+    }
+
+    #[test]
+    fn test_utf16() {
+        let engine = engine("a中😀b");
+        assert_eq!(engine.offset_to_utf16(1), 1);
+        assert_eq!(engine.offset_to_utf16(4), 2);
+        assert_eq!(engine.offset_to_utf16(8), 4);
+        assert_eq!(engine.offset_from_utf16(4), 8);
+        assert_eq!(engine.range_from_utf16(&(1..4)), 1..8);
+        assert_eq!(engine.range_to_utf16(&(1..9)), 1..5);
+    }
+
+    #[test]
+    fn test_boundary_and_lines() {
+        let engine = engine("ab\n中c\n");
+        assert_eq!(engine.next_boundary(3), 6);
+        assert_eq!(engine.previous_boundary(6), 3);
+        assert_eq!(engine.start_of_line(7), 3);
+        assert_eq!(engine.start_of_line(3), 3);
+        assert_eq!(engine.start_of_line(2), 0);
+        assert_eq!(engine.end_of_line(0), 2);
+        assert_eq!(engine.end_of_line(2), 2);
+        assert_eq!(engine.end_of_line(4), 7);
+        assert_eq!(engine.end_of_line(8), 8);
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut engine = engine("hello world_1 中文");
+        engine.move_to(5);
+        engine.select_to(2);
+        assert_eq!(engine.selected_range(), 2..5);
+        assert!(engine.is_selection_reversed());
+        assert_eq!(engine.cursor_offset(), 2);
+        assert_eq!(engine.selected_text(), "llo");
+
+        engine.select_word(8);
+        assert_eq!(engine.selected_text(), "world_1");
+        // Keep the word range when extend the selection.
+        engine.select_to(9);
+        assert_eq!(engine.selected_text(), "world_1");
+        engine.end_word_selection();
+
+        engine.select_word(16);
+        assert_eq!(engine.selected_text(), "中文");
+
+        engine.select_all();
+        assert_eq!(engine.selected_range(), 0..engine.len());
+        engine.unselect();
+        assert_eq!(engine.selected_range(), engine.len()..engine.len());
+    }
+
+    #[test]
+    fn test_edit_and_history() {
+        let mut engine = engine("hello");
+        engine.move_to(5);
+        let range = engine.edit_range(None);
+        engine.replace_text_in_range(range, " 中文");
+        assert_eq!(engine.text().as_ref(), "hello 中文");
+        assert_eq!(engine.cursor_offset(), 12);
+
+        let range = engine.edit_range(Some(&(0..1)));
+        engine.replace_text_in_range(range, "H");
+        assert_eq!(engine.text().as_ref(), "Hello 中文");
+        assert_eq!(engine.cursor_offset(), 1);
+
+        // The changes in 1s are grouped.
+        assert!(engine.undo());
+        assert_eq!(engine.text().as_ref(), "hello");
+        assert!(!engine.undo());
+        assert!(engine.redo());
+        assert_eq!(engine.text().as_ref(), "Hello 中文");
+
+        engine.replace_all("123-4", 3);
+        assert_eq!(engine.text().as_ref(), "123-4");
+        assert_eq!(engine.cursor_offset(), 3);
+    }
+
+    #[test]
+    fn test_marked_text() {
+        let mut engine = engine("ab");
+        engine.move_to(1);
+        let range = engine.edit_range(None);
+        engine.replace_and_mark_text_in_range(range, "中文", Some(1..1));
+        assert_eq!(engine.text().as_ref(), "a中文b");
+        assert_eq!(engine.marked_range(), Some(1..7));
+        assert_eq!(engine.selected_range(), 4..4);
+
+        // Replace the marked text.
+        let range = engine.edit_range(None);
+        engine.replace_text_in_range(range, "字");
+        assert_eq!(engine.text().as_ref(), "a字b");
+        assert_eq!(engine.marked_range(), None);
+        assert_eq!(engine.cursor_offset(), 4);
+    }
+
+    #[test]
+    fn test_token_before() {
+        let engine = engine("hi @bo_b $AA中 x");
+        assert_eq!(engine.token_before(2, &[]), Some(0..2));
+        assert_eq!(engine.token_before(3, &[]), None);
+        assert_eq!(engine.token_before(6, &[]), Some(4..6));
+        assert_eq!(engine.token_before(6, &['@']), Some(3..6));
+        assert_eq!(engine.token_before(4, &['@']), Some(3..4));
+        assert_eq!(engine.token_before(2, &['@']), None);
+        assert_eq!(engine.token_before(15, &['@', '$']), Some(9..15));
+        assert_eq!(engine.token_before(17, &[]), Some(16..17));
+    }
+
+    #[test]
+    fn test_lines() {
+        let engine = engine("ab\n中c\n\nd");
+        assert_eq!(engine.offset_for_line(0), 0);
+        assert_eq!(engine.offset_for_line(1), 3);
+        assert_eq!(engine.offset_for_line(2), 8);
+        assert_eq!(engine.offset_for_line(3), 9);
+        assert_eq!(engine.offset_for_line(10), 9);
+        assert_eq!(engine.line_for_offset(0), 0);
+        assert_eq!(engine.line_for_offset(2), 0);
+        assert_eq!(engine.line_for_offset(3), 1);
+        assert_eq!(engine.line_for_offset(8), 2);
+        assert_eq!(engine.line_for_offset(100), 3);
+    }
+}
@@ -7,16 +7,15 @@ use smallvec::SmallVec;
 use std::cell::Cell;
 use std::ops::Range;
 use std::rc::Rc;
-use unicode_segmentation::*;
 
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    actions, div, point, px, AnyElement, AppContext, Bounds, ClickEvent, ClipboardItem,
-    Context as _, Entity, EventEmitter, FocusHandle, FocusableView, Half, InteractiveElement as _,
-    IntoElement, KeyBinding, KeyDownEvent, Model, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, ParentElement as _, Pixels, Point, Rems, Render, ScrollHandle, ScrollWheelEvent,
-    SharedString, Styled as _, UTF16Selection, ViewContext, ViewInputHandler, WindowContext,
-    WrappedLine,
+    actions, deferred, div, point, px, size, AnyElement, AppContext, Bounds, ClickEvent,
+    ClipboardItem, Context as _, Entity, EventEmitter, FocusHandle, FocusableView, Half,
+    InteractiveElement as _, IntoElement, KeyDownEvent, Model, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, ParentElement as _, Pixels, Point, Rems, Render, ScrollHandle,
+    ScrollWheelEvent, SharedString, StatefulInteractiveElement as _, Styled as _, Task,
+    UTF16Selection, ViewContext, ViewInputHandler, WindowContext, WrappedLine,
 };
 
 // TODO:
@@ -24,17 +23,26 @@ use gpui::{
 // - Move cursor to skip line eof empty chars.
 
 use super::blink_cursor::BlinkCursor;
-use super::change::Change;
+use super::completion::{CompletionMenu, CompletionProvider};
+use super::decoration::TextDecoration;
 use super::element::TextElement;
+use super::engine::TextEngine;
+use super::mask::MaskPattern;
+use super::validation::{AsyncValidator, ValidationState, DEFAULT_VALIDATION_DEBOUNCE};
 use super::ClearButton;
 
-use crate::history::History;
+use crate::history::HistoryState;
 use crate::indicator::Indicator;
+use crate::keymap::{self, KeymapEntry};
+use crate::primitives::placement::{anchored_to, Align, FloatingPlacement, Side};
 use crate::scroll::{Scrollbar, ScrollbarAxis, ScrollbarState};
 use crate::theme::ActiveTheme;
+use crate::tooltip::Tooltip;
 use crate::Size;
 use crate::StyledExt;
-use crate::{Sizable, StyleSized};
+use crate::{
+    h_flex, v_flex, AutomationExt as _, AutomationId, Icon, IconName, Sizable, StyleSized,
+};
 
 actions!(
     input,
@@ -66,6 +74,8 @@ actions!(
         MoveToStartOfLine,
         MoveToEndOfLine,
         TextChanged,
+        CompletionAccept,
+        CompletionDismiss,
     ]
 );
 
@@ -75,91 +85,100 @@ pub enum InputEvent {
     PressEnter,
     Focus,
     Blur,
+    /// The marked (composition) text of the IME is changed, `None` if the composition is ended.
+    ///
+    /// Use [`TextInput::marked_text_bounds`] to position the popover (e.g.: the autocomplete) by the composition.
+    CompositionChange(Option<SharedString>),
+    /// The undo / redo availability is changed, e.g.: to enable or disable the Undo / Redo buttons.
+    HistoryChange(HistoryState),
 }
 
 const CONTEXT: &str = "Input";
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([
-        KeyBinding::new("backspace", Backspace, Some(CONTEXT)),
-        KeyBinding::new("delete", Delete, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-backspace", DeleteToBeginningOfLine, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-delete", DeleteToEndOfLine, Some(CONTEXT)),
-        KeyBinding::new("enter", Enter, Some(CONTEXT)),
-        KeyBinding::new("up", Up, Some(CONTEXT)),
-        KeyBinding::new("down", Down, Some(CONTEXT)),
-        KeyBinding::new("left", Left, Some(CONTEXT)),
-        KeyBinding::new("right", Right, Some(CONTEXT)),
-        KeyBinding::new("shift-left", SelectLeft, Some(CONTEXT)),
-        KeyBinding::new("shift-right", SelectRight, Some(CONTEXT)),
-        KeyBinding::new("shift-up", SelectUp, Some(CONTEXT)),
-        KeyBinding::new("shift-down", SelectDown, Some(CONTEXT)),
-        KeyBinding::new("home", Home, Some(CONTEXT)),
-        KeyBinding::new("end", End, Some(CONTEXT)),
-        KeyBinding::new("shift-home", SelectToHome, Some(CONTEXT)),
-        KeyBinding::new("shift-end", SelectToEnd, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("ctrl-shift-a", SelectToHome, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("ctrl-shift-e", SelectToEnd, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("shift-cmd-left", SelectToHome, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("shift-cmd-right", SelectToEnd, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("ctrl-cmd-space", ShowCharacterPalette, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-a", SelectAll, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-a", SelectAll, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-c", Copy, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-c", Copy, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-x", Cut, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-x", Cut, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-v", Paste, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-v", Paste, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("ctrl-a", Home, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-left", Home, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("ctrl-e", End, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-right", End, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-z", Undo, Some(CONTEXT)),
-        #[cfg(target_os = "macos")]
-        KeyBinding::new("cmd-shift-z", Redo, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-z", Undo, Some(CONTEXT)),
-        #[cfg(not(target_os = "macos"))]
-        KeyBinding::new("ctrl-y", Redo, Some(CONTEXT)),
-    ]);
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("backspace", Backspace, Some(CONTEXT)),
+            KeymapEntry::new("delete", Delete, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-backspace", DeleteToBeginningOfLine, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-delete", DeleteToEndOfLine, Some(CONTEXT)),
+            KeymapEntry::new("enter", Enter, Some(CONTEXT)),
+            KeymapEntry::new("up", Up, Some(CONTEXT)),
+            KeymapEntry::new("down", Down, Some(CONTEXT)),
+            KeymapEntry::new("left", Left, Some(CONTEXT)),
+            KeymapEntry::new("right", Right, Some(CONTEXT)),
+            KeymapEntry::new("shift-left", SelectLeft, Some(CONTEXT)),
+            KeymapEntry::new("shift-right", SelectRight, Some(CONTEXT)),
+            KeymapEntry::new("shift-up", SelectUp, Some(CONTEXT)),
+            KeymapEntry::new("shift-down", SelectDown, Some(CONTEXT)),
+            KeymapEntry::new("home", Home, Some(CONTEXT)),
+            KeymapEntry::new("end", End, Some(CONTEXT)),
+            KeymapEntry::new("shift-home", SelectToHome, Some(CONTEXT)),
+            KeymapEntry::new("shift-end", SelectToEnd, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("ctrl-shift-a", SelectToHome, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("ctrl-shift-e", SelectToEnd, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("shift-cmd-left", SelectToHome, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("shift-cmd-right", SelectToEnd, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("ctrl-cmd-space", ShowCharacterPalette, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-a", SelectAll, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-a", SelectAll, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-c", Copy, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-c", Copy, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-x", Cut, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-x", Cut, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-v", Paste, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-v", Paste, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("ctrl-a", Home, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-left", Home, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("ctrl-e", End, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-right", End, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-z", Undo, Some(CONTEXT)),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-shift-z", Redo, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-z", Undo, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-y", Redo, Some(CONTEXT)),
+            // Propagated if the completion menu is not shown.
+            KeymapEntry::new("tab", CompletionAccept, Some(CONTEXT)),
+            KeymapEntry::new("escape", CompletionDismiss, Some(CONTEXT)),
+        ],
+    );
+
+    super::otp_input::init(cx);
 }
 
 pub struct TextInput {
     pub(super) focus_handle: FocusHandle,
-    pub(super) text: SharedString,
+    pub(super) engine: TextEngine,
     multi_line: bool,
-    pub(super) history: History<Change>,
     pub(super) blink_cursor: Model<BlinkCursor>,
     pub(super) prefix: Option<Box<dyn Fn(&mut ViewContext<Self>) -> AnyElement + 'static>>,
     pub(super) suffix: Option<Box<dyn Fn(&mut ViewContext<Self>) -> AnyElement + 'static>>,
     pub(super) loading: bool,
     pub(super) placeholder: SharedString,
-    pub(super) selected_range: Range<usize>,
-    /// Range for save the selected word, use to keep word range when drag move.
-    pub(super) selected_word_range: Option<Range<usize>>,
-    pub(super) selection_reversed: bool,
-    pub(super) marked_range: Option<Range<usize>>,
+    pub(super) decorations: Vec<TextDecoration>,
     pub(super) last_layout: Option<SmallVec<[WrappedLine; 1]>>,
     pub(super) last_cursor_offset: Option<usize>,
     /// The line_height of text layout, this will change will InputElement painted.
@@ -176,14 +195,26 @@ pub struct TextInput {
     pub(super) cleanable: bool,
     pub(super) size: Size,
     pub(super) rows: usize,
+    pub(super) line_number: bool,
+    pub(super) soft_wrap: bool,
+    pub(super) highlight_current_line: bool,
     pattern: Option<regex::Regex>,
     validate: Option<Box<dyn Fn(&str) -> bool + 'static>>,
+    mask_pattern: Option<MaskPattern>,
+    pub(super) async_validator: Option<AsyncValidator>,
+    pub(super) validation_debounce: std::time::Duration,
+    pub(super) validation_state: ValidationState,
+    pub(super) _validation_task: Option<Task<()>>,
     pub(crate) scroll_handle: ScrollHandle,
     scrollbar_state: Rc<Cell<ScrollbarState>>,
     /// The size of the scrollable content.
     pub(crate) scroll_size: gpui::Size<Pixels>,
     /// To remember the horizontal column (x-coordinate) of the cursor position.
     preferred_x_offset: Option<Pixels>,
+    automation_id: Option<SharedString>,
+    completion_provider: Option<Rc<dyn CompletionProvider>>,
+    completion_menu: Option<CompletionMenu>,
+    _completion_task: Option<Task<()>>,
 }
 
 impl EventEmitter<InputEvent> for TextInput {}
@@ -192,18 +223,13 @@ impl TextInput {
     pub fn new(cx: &mut ViewContext<Self>) -> Self {
         let focus_handle = cx.focus_handle();
         let blink_cursor = cx.new_model(|_| BlinkCursor::new());
-        let history = History::new().group_interval(std::time::Duration::from_secs(1));
         let input = Self {
             focus_handle: focus_handle.clone(),
-            text: "".into(),
+            engine: TextEngine::new(),
             multi_line: false,
             blink_cursor,
-            history,
             placeholder: "".into(),
-            selected_range: 0..0,
-            selected_word_range: None,
-            selection_reversed: false,
-            marked_range: None,
+            decorations: vec![],
             input_bounds: Bounds::default(),
             is_selecting: false,
             disabled: false,
@@ -216,7 +242,16 @@ impl TextInput {
             size: Size::Medium,
             pattern: None,
             validate: None,
+            mask_pattern: None,
+            async_validator: None,
+            validation_debounce: DEFAULT_VALIDATION_DEBOUNCE,
+            validation_state: ValidationState::None,
+            _validation_task: None,
+            automation_id: None,
             rows: 2,
+            line_number: false,
+            soft_wrap: true,
+            highlight_current_line: false,
             last_layout: None,
             last_bounds: None,
             last_selected_range: None,
@@ -226,6 +261,9 @@ impl TextInput {
             scrollbar_state: Rc::new(Cell::new(ScrollbarState::default())),
             scroll_size: gpui::size(px(0.), px(0.)),
             preferred_x_offset: None,
+            completion_provider: None,
+            completion_menu: None,
+            _completion_task: None,
         };
 
         // Observe the blink cursor to repaint the view when it changes.
@@ -259,7 +297,7 @@ impl TextInput {
     /// Called after moving the cursor. Updates preferred_x_offset if we know where the cursor now is.
     fn update_preferred_x_offset(&mut self, _cx: &mut ViewContext<Self>) {
         if let (Some(lines), Some(bounds)) = (&self.last_layout, &self.last_bounds) {
-            let offset = self.cursor_offset();
+            let offset = self.engine.cursor_offset();
             let line_height = self.last_line_height;
 
             // Find which line and sub-line the cursor is on and its position
@@ -297,6 +335,28 @@ impl TextInput {
         (0, 0, None)
     }
 
+    /// Return the bounds of the first visual line of the range, the `bounds` is the bounds of the text
+    /// (included the scroll offset), e.g.: to position the IME candidates below the composition.
+    fn bounds_for_offset_range(
+        &self,
+        range: Range<usize>,
+        bounds: Bounds<Pixels>,
+    ) -> Option<Bounds<Pixels>> {
+        let lines = self.last_layout.as_ref()?;
+        let line_height = self.last_line_height;
+        let (_, _, start) = self.line_and_position_for_offset(range.start, lines, line_height);
+        let (_, _, end) = self.line_and_position_for_offset(range.end, lines, line_height);
+        let start = start?;
+
+        // The range may be wrapped or cross the lines, only keep the part on the first visual line.
+        let width = match end {
+            Some(end) if end.y == start.y => (end.x - start.x).max(px(0.)),
+            _ => px(0.),
+        };
+
+        Some(Bounds::new(bounds.origin + start, size(width, line_height)))
+    }
+
     /// Move the cursor vertically by one line (up or down) while preserving the column if possible.
     /// direction: -1 for up, +1 for down
     fn move_vertical(&mut self, direction: i32, cx: &mut ViewContext<Self>) {
@@ -308,7 +368,7 @@ impl TextInput {
             return;
         };
 
-        let offset = self.cursor_offset();
+        let offset = self.engine.cursor_offset();
         let line_height = self.last_line_height;
         let (current_line_index, current_sub_line, current_pos) =
             self.line_and_position_for_offset(offset, lines, line_height);
@@ -369,8 +429,8 @@ impl TextInput {
             prev_lines_offset += l.len() + 1;
         }
 
-        let new_offset = (prev_lines_offset + new_local_index).min(self.text.len());
-        self.selected_range = new_offset..new_offset;
+        let new_offset = prev_lines_offset + new_local_index;
+        self.engine.move_to(new_offset);
         self.pause_blink_cursor(cx);
         cx.notify();
     }
@@ -395,25 +455,88 @@ impl TextInput {
         self
     }
 
+    /// Set true to show the line numbers in the gutter of the multi-line Textarea, default: false
+    pub fn line_number(mut self, line_number: bool) -> Self {
+        self.line_number = line_number;
+        self
+    }
+
+    /// Set the line numbers of the multi-line Textarea with reference.
+    pub fn set_line_number(&mut self, line_number: bool, cx: &mut ViewContext<Self>) {
+        self.line_number = line_number;
+        cx.notify();
+    }
+
+    /// Set false to disable the soft wrap of the multi-line Textarea, default: true
+    ///
+    /// The long lines are scrolled horizontally if the soft wrap is disabled.
+    pub fn soft_wrap(mut self, soft_wrap: bool) -> Self {
+        self.soft_wrap = soft_wrap;
+        self
+    }
+
+    /// Set the soft wrap of the multi-line Textarea with reference.
+    pub fn set_soft_wrap(&mut self, soft_wrap: bool, cx: &mut ViewContext<Self>) {
+        self.soft_wrap = soft_wrap;
+        self.preferred_x_offset = None;
+        cx.notify();
+    }
+
+    /// Set true to highlight the line of the cursor in the multi-line Textarea, default: false
+    pub fn highlight_current_line(mut self, highlight: bool) -> Self {
+        self.highlight_current_line = highlight;
+        self
+    }
+
+    /// Move the cursor to the start of the `line` (starts from 1, as the line numbers),
+    /// and scroll to make it visible.
+    ///
+    /// The cursor is moved to the last line if the `line` is out of range.
+    pub fn goto_line(&mut self, line: usize, cx: &mut ViewContext<Self>) {
+        let offset = self.engine.offset_for_line(line.saturating_sub(1));
+        self.move_to(offset, cx);
+    }
+
     /// Set the text of the input field.
     ///
     /// And the selection_range will be reset to 0..0.
     pub fn set_text(&mut self, text: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
-        self.history.ignore = true;
-        self.replace_text(text, cx);
-        self.history.ignore = false;
-        // Ensure cursor to start when set text
-        self.selected_range = 0..0;
+        let Some(text) = self.checked_text(text.into()) else {
+            return;
+        };
 
-        cx.notify();
+        self.engine.set_text(text);
+        self.on_text_changed(cx);
     }
 
     fn replace_text(&mut self, text: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
         let text: SharedString = text.into();
-        let range = 0..self.text.chars().map(|c| c.len_utf16()).sum();
+        let range = 0..self.engine.offset_to_utf16(self.engine.len());
         self.replace_text_in_range(Some(range), &text, cx);
     }
 
+    /// Returns true if there are changes to undo.
+    pub fn can_undo(&self) -> bool {
+        self.engine.history_state().can_undo
+    }
+
+    /// Returns true if there are undone changes to redo.
+    pub fn can_redo(&self) -> bool {
+        self.engine.history_state().can_redo
+    }
+
+    /// Begin a group of the changes to undo and redo together, e.g.: a multi-step edit by code.
+    ///
+    /// Must be paired with [`TextInput::end_grouping`], the grouping can be nested.
+    pub fn begin_grouping(&mut self) {
+        self.engine.begin_grouping();
+    }
+
+    /// End the group of the changes started by [`TextInput::begin_grouping`].
+    pub fn end_grouping(&mut self) {
+        self.engine.end_grouping();
+    }
+
     /// Set the disabled state of the input field.
     pub fn set_disabled(&mut self, disabled: bool, cx: &mut ViewContext<Self>) {
         self.disabled = disabled;
@@ -484,6 +607,30 @@ impl TextInput {
         self
     }
 
+    /// Set the decorations to render under the text, e.g.: the squiggles of the invalid ranges,
+    /// the highlights of the search matches.
+    ///
+    /// The decorations are not moved when the text is changed, and are ignored in the masked mode.
+    pub fn set_decorations(
+        &mut self,
+        decorations: impl IntoIterator<Item = TextDecoration>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.decorations = decorations.into_iter().collect();
+        cx.notify();
+    }
+
+    /// Remove all the decorations.
+    pub fn clear_decorations(&mut self, cx: &mut ViewContext<Self>) {
+        self.decorations.clear();
+        cx.notify();
+    }
+
+    /// Returns the decorations of the input field.
+    pub fn decorations(&self) -> &[TextDecoration] {
+        &self.decorations
+    }
+
     /// Set the placeholder text of the input field with reference.
     pub fn set_placeholder(&mut self, placeholder: impl Into<SharedString>) {
         self.placeholder = placeholder.into();
@@ -512,6 +659,39 @@ impl TextInput {
         self
     }
 
+    /// Set the provider to show the completion menu for the token before the cursor when typing.
+    ///
+    /// Press `up`, `down` to select, `enter` or `tab` to replace the token with the selected completion,
+    /// `escape` to dismiss the menu.
+    pub fn completion_provider(mut self, provider: impl CompletionProvider + 'static) -> Self {
+        self.completion_provider = Some(Rc::new(provider));
+        self
+    }
+
+    /// Set the mask pattern to format the input text, e.g.: `###-####` or number with thousand separator.
+    ///
+    /// The `pattern` and `validate` will be checked with the raw value,
+    /// use [`TextInput::raw_value`] to get the value without the mask.
+    pub fn mask_pattern(mut self, mask_pattern: MaskPattern) -> Self {
+        self.mask_pattern = Some(mask_pattern);
+        self
+    }
+
+    /// Set the mask pattern of the input field with reference.
+    pub fn set_mask_pattern(&mut self, mask_pattern: MaskPattern, cx: &mut ViewContext<Self>) {
+        let raw_value = self.raw_value();
+        self.mask_pattern = Some(mask_pattern);
+        self.set_text(raw_value, cx);
+    }
+
+    /// Return the value without the mask, if there is no mask pattern, it's the same as [`TextInput::text`].
+    pub fn raw_value(&self) -> SharedString {
+        match &self.mask_pattern {
+            Some(mask_pattern) => mask_pattern.unmask(self.engine.text()).into(),
+            None => self.engine.text().clone(),
+        }
+    }
+
     /// Set true to show indicator at the input right.
     pub fn set_loading(&mut self, loading: bool, cx: &mut ViewContext<Self>) {
         self.loading = loading;
@@ -520,7 +700,20 @@ impl TextInput {
 
     /// Return the text of the input field.
     pub fn text(&self) -> SharedString {
-        self.text.clone()
+        self.engine.text().clone()
+    }
+
+    /// Return the marked (composition) text of the IME, if composing.
+    pub fn marked_text(&self) -> Option<SharedString> {
+        self.engine
+            .marked_range()
+            .map(|range| self.engine.text_for_range(range).to_string().into())
+    }
+
+    /// Return the bounds of the marked (composition) text in the window, if composing.
+    pub fn marked_text_bounds(&self) -> Option<Bounds<Pixels>> {
+        let range = self.engine.marked_range()?;
+        self.bounds_for_offset_range(range, self.last_bounds?)
     }
 
     pub fn disabled(&self) -> bool {
@@ -534,24 +727,32 @@ impl TextInput {
 
     fn left(&mut self, _: &Left, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        if self.selected_range.is_empty() {
-            self.move_to(self.previous_boundary(self.cursor_offset()), cx);
+        let selected_range = self.engine.selected_range();
+        if selected_range.is_empty() {
+            self.move_to(self.engine.previous_boundary(selected_range.start), cx);
         } else {
-            self.move_to(self.selected_range.start, cx)
+            self.move_to(selected_range.start, cx)
         }
     }
 
     fn right(&mut self, _: &Right, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        if self.selected_range.is_empty() {
-            self.move_to(self.next_boundary(self.selected_range.end), cx);
+        let selected_range = self.engine.selected_range();
+        if selected_range.is_empty() {
+            self.move_to(self.engine.next_boundary(selected_range.end), cx);
         } else {
-            self.move_to(self.selected_range.end, cx)
+            self.move_to(selected_range.end, cx)
         }
     }
 
     fn up(&mut self, _: &Up, cx: &mut ViewContext<Self>) {
+        if let Some(menu) = self.completion_menu.as_mut() {
+            menu.select_prev();
+            cx.notify();
+            return;
+        }
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         self.pause_blink_cursor(cx);
@@ -559,7 +760,13 @@ impl TextInput {
     }
 
     fn down(&mut self, _: &Down, cx: &mut ViewContext<Self>) {
+        if let Some(menu) = self.completion_menu.as_mut() {
+            menu.select_next();
+            cx.notify();
+            return;
+        }
         if self.is_single_line() {
+            cx.propagate();
             return;
         }
         self.pause_blink_cursor(cx);
@@ -567,18 +774,20 @@ impl TextInput {
     }
 
     fn select_left(&mut self, _: &SelectLeft, cx: &mut ViewContext<Self>) {
-        self.select_to(self.previous_boundary(self.cursor_offset()), cx);
+        let offset = self.engine.previous_boundary(self.engine.cursor_offset());
+        self.select_to(offset, cx);
     }
 
     fn select_right(&mut self, _: &SelectRight, cx: &mut ViewContext<Self>) {
-        self.select_to(self.next_boundary(self.cursor_offset()), cx);
+        let offset = self.engine.next_boundary(self.engine.cursor_offset());
+        self.select_to(offset, cx);
     }
 
     fn select_up(&mut self, _: &SelectUp, cx: &mut ViewContext<Self>) {
         if self.is_single_line() {
             return;
         }
-        let offset = self.start_of_line(cx).saturating_sub(1);
+        let offset = self.start_of_line().saturating_sub(1);
         self.select_to(offset, cx);
     }
 
@@ -586,93 +795,68 @@ impl TextInput {
         if self.is_single_line() {
             return;
         }
-        let offset = (self.end_of_line(cx) + 1).min(self.text.len());
+        let offset = self.end_of_line() + 1;
         self.select_to(offset, cx);
     }
 
     fn select_all(&mut self, _: &SelectAll, cx: &mut ViewContext<Self>) {
-        self.move_to(0, cx);
-        self.select_to(self.text.len(), cx)
+        self.engine.select_all();
+        cx.notify()
     }
 
     fn home(&mut self, _: &Home, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        let offset = self.start_of_line(cx);
+        let offset = self.start_of_line();
         self.move_to(offset, cx);
     }
 
     fn end(&mut self, _: &End, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        let offset = self.end_of_line(cx);
+        let offset = self.end_of_line();
         self.move_to(offset, cx);
     }
 
     fn select_to_home(&mut self, _: &SelectToHome, cx: &mut ViewContext<Self>) {
-        let offset = self.start_of_line(cx);
+        let offset = self.start_of_line();
         self.select_to(offset, cx);
     }
 
     fn select_to_end(&mut self, _: &SelectToEnd, cx: &mut ViewContext<Self>) {
-        let offset = self.end_of_line(cx);
+        let offset = self.end_of_line();
         self.select_to(offset, cx);
     }
 
-    /// Get start of line
-    fn start_of_line(&mut self, cx: &mut ViewContext<Self>) -> usize {
+    /// Get start of the line at the cursor.
+    fn start_of_line(&self) -> usize {
         if self.is_single_line() {
             return 0;
         }
 
-        let offset = self.previous_boundary(self.cursor_offset());
-        let line = self
-            .text_for_range(self.range_to_utf16(&(0..offset + 1)), &mut None, cx)
-            .unwrap_or_default()
-            .rfind('\n')
-            .map(|i| i + 1)
-            .unwrap_or(0);
-        line
+        self.engine.start_of_line(self.engine.cursor_offset())
     }
 
-    /// Get end of line
-    fn end_of_line(&mut self, cx: &mut ViewContext<Self>) -> usize {
+    /// Get end of the line at the cursor.
+    fn end_of_line(&self) -> usize {
         if self.is_single_line() {
-            return self.text.len();
-        }
-
-        let offset = self.next_boundary(self.cursor_offset());
-        // ignore if offset is "\n"
-        if self
-            .text_for_range(self.range_to_utf16(&(offset - 1..offset)), &mut None, cx)
-            .unwrap_or_default()
-            .eq("\n")
-        {
-            return offset;
+            return self.engine.len();
         }
 
-        let line = self
-            .text_for_range(
-                self.range_to_utf16(&(offset..self.text.len())),
-                &mut None,
-                cx,
-            )
-            .unwrap_or_default()
-            .find('\n')
-            .map(|i| i + offset)
-            .unwrap_or(self.text.len());
-        line
+        self.engine.end_of_line(self.engine.cursor_offset())
     }
 
     fn backspace(&mut self, _: &Backspace, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.previous_boundary(self.cursor_offset()), cx)
+        if self.engine.selected_range().is_empty() {
+            let offset = self.engine.previous_boundary(self.engine.cursor_offset());
+            self.select_to(offset, cx)
         }
         self.replace_text_in_range(None, "", cx);
         self.pause_blink_cursor(cx);
     }
 
     fn delete(&mut self, _: &Delete, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
-            self.select_to(self.next_boundary(self.cursor_offset()), cx)
+        if self.engine.selected_range().is_empty() {
+            let offset = self.engine.next_boundary(self.engine.cursor_offset());
+            self.select_to(offset, cx)
         }
         self.replace_text_in_range(None, "", cx);
         self.pause_blink_cursor(cx);
@@ -683,31 +867,31 @@ impl TextInput {
         _: &DeleteToBeginningOfLine,
         cx: &mut ViewContext<Self>,
     ) {
-        let offset = self.start_of_line(cx);
-        self.replace_text_in_range(
-            Some(self.range_to_utf16(&(offset..self.cursor_offset()))),
-            "",
-            cx,
-        );
+        let offset = self.start_of_line();
+        let range = self
+            .engine
+            .range_to_utf16(&(offset..self.engine.cursor_offset()));
+        self.replace_text_in_range(Some(range), "", cx);
         self.pause_blink_cursor(cx);
     }
 
     fn delete_to_end_of_line(&mut self, _: &DeleteToEndOfLine, cx: &mut ViewContext<Self>) {
-        let offset = self.end_of_line(cx);
-        self.replace_text_in_range(
-            Some(self.range_to_utf16(&(self.cursor_offset()..offset))),
-            "",
-            cx,
-        );
+        let offset = self.end_of_line();
+        let range = self
+            .engine
+            .range_to_utf16(&(self.engine.cursor_offset()..offset));
+        self.replace_text_in_range(Some(range), "", cx);
         self.pause_blink_cursor(cx);
     }
 
     fn enter(&mut self, _: &Enter, cx: &mut ViewContext<Self>) {
+        if self.accept_completion(None, cx) {
+            return;
+        }
+
         if self.is_multi_line() {
+            // The cursor will be placed at the start of the next line.
             self.replace_text_in_range(None, "\n", cx);
-            // Move cursor to the start of the next line
-            // TODO: To be test this line is valid
-            self.move_to(self.next_boundary(self.cursor_offset()) - 1, cx);
         }
 
         cx.emit(InputEvent::PressEnter);
@@ -735,7 +919,7 @@ impl TextInput {
 
     fn on_mouse_up(&mut self, _: &MouseUpEvent, _: &mut ViewContext<Self>) {
         self.is_selecting = false;
-        self.selected_word_range = None;
+        self.engine.end_word_selection();
     }
 
     fn on_scroll_wheel(&mut self, event: &ScrollWheelEvent, _: &mut ViewContext<Self>) {
@@ -757,21 +941,20 @@ impl TextInput {
     }
 
     fn copy(&mut self, _: &Copy, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
+        if self.engine.selected_range().is_empty() {
             return;
         }
 
-        let selected_text = self.text[self.selected_range.clone()].to_string();
+        let selected_text = self.engine.selected_text().to_string();
         cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
     }
 
     fn cut(&mut self, _: &Cut, cx: &mut ViewContext<Self>) {
-        if self.selected_range.is_empty() {
+        if self.engine.selected_range().is_empty() {
             return;
         }
 
-        let range = self.range_from_utf16(&self.selected_range);
-        let selected_text = self.text[range].to_string();
+        let selected_text = self.engine.selected_text().to_string();
         cx.write_to_clipboard(ClipboardItem::new_string(selected_text));
         self.replace_text_in_range(None, "", cx);
     }
@@ -787,65 +970,37 @@ impl TextInput {
         }
     }
 
-    fn push_history(&mut self, range: &Range<usize>, new_text: &str, cx: &mut ViewContext<Self>) {
-        if self.history.ignore {
+    fn undo(&mut self, _: &Undo, cx: &mut ViewContext<Self>) {
+        if self.disabled {
             return;
         }
 
-        let old_text = self
-            .text_for_range(self.range_to_utf16(&range), &mut None, cx)
-            .unwrap_or("".to_string());
-
-        let new_range = range.start..range.start + new_text.len();
-
-        self.history.push(Change::new(
-            range.clone(),
-            &old_text,
-            new_range.clone(),
-            new_text,
-        ));
-    }
-
-    fn undo(&mut self, _: &Undo, cx: &mut ViewContext<Self>) {
-        self.history.ignore = true;
-        if let Some(changes) = self.history.undo() {
-            for change in changes {
-                let range_utf16 = self.range_to_utf16(&change.new_range);
-                self.replace_text_in_range(Some(range_utf16), &change.old_text, cx);
-            }
+        if self.engine.undo() {
+            self.on_text_changed(cx);
         }
-        self.history.ignore = false;
     }
 
     fn redo(&mut self, _: &Redo, cx: &mut ViewContext<Self>) {
-        self.history.ignore = true;
-        if let Some(changes) = self.history.redo() {
-            for change in changes {
-                let range_utf16 = self.range_to_utf16(&change.old_range);
-                self.replace_text_in_range(Some(range_utf16), &change.new_text, cx);
-            }
+        if self.disabled {
+            return;
+        }
+
+        if self.engine.redo() {
+            self.on_text_changed(cx);
         }
-        self.history.ignore = false;
     }
 
     fn move_to(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        self.selected_range = offset..offset;
+        self.hide_completion(cx);
+        self.engine.move_to(offset);
         self.pause_blink_cursor(cx);
         self.update_preferred_x_offset(cx);
         cx.notify()
     }
 
-    pub(super) fn cursor_offset(&self) -> usize {
-        if self.selection_reversed {
-            self.selected_range.start
-        } else {
-            self.selected_range.end
-        }
-    }
-
     fn index_for_mouse_position(&self, position: Point<Pixels>, _: &WindowContext) -> usize {
         // If the text is empty, always return 0
-        if self.text.is_empty() {
+        if self.engine.is_empty() {
             return 0;
         }
 
@@ -907,11 +1062,7 @@ impl TextInput {
             index += 1;
         }
 
-        if index > self.text.len() {
-            self.text.len()
-        } else {
-            index
-        }
+        index.min(self.engine.len())
     }
 
     /// Returns a y offsetted point for the line origin.
@@ -936,27 +1087,8 @@ impl TextInput {
     }
 
     fn select_to(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        if self.selection_reversed {
-            self.selected_range.start = offset
-        } else {
-            self.selected_range.end = offset
-        };
-
-        if self.selected_range.end < self.selected_range.start {
-            self.selection_reversed = !self.selection_reversed;
-            self.selected_range = self.selected_range.end..self.selected_range.start;
-        }
-
-        // Ensure keep word selected range
-        if let Some(word_range) = self.selected_word_range.as_ref() {
-            if self.selected_range.start > word_range.start {
-                self.selected_range.start = word_range.start;
-            }
-            if self.selected_range.end < word_range.end {
-                self.selected_range.end = word_range.end;
-            }
-        }
-        if self.selected_range.is_empty() {
+        self.engine.select_to(offset);
+        if self.engine.selected_range().is_empty() {
             self.update_preferred_x_offset(cx);
         }
         cx.notify()
@@ -964,101 +1096,15 @@ impl TextInput {
 
     /// Select the word at the given offset.
     fn select_word(&mut self, offset: usize, cx: &mut ViewContext<Self>) {
-        fn is_word(c: char) -> bool {
-            c.is_alphanumeric() || matches!(c, '_')
-        }
-
-        let mut start = self.offset_to_utf16(offset);
-        let mut end = start;
-        let prev_text = self
-            .text_for_range(0..start, &mut None, cx)
-            .unwrap_or_default();
-        let next_text = self
-            .text_for_range(end..self.text.len(), &mut None, cx)
-            .unwrap_or_default();
-
-        let prev_chars = prev_text.chars().rev().peekable();
-        let next_chars = next_text.chars().peekable();
-
-        for (_, c) in prev_chars.enumerate() {
-            if !is_word(c) {
-                break;
-            }
-
-            start -= c.len_utf16();
-        }
-
-        for (_, c) in next_chars.enumerate() {
-            if !is_word(c) {
-                break;
-            }
-
-            end += c.len_utf16();
-        }
-
-        self.selected_range = self.range_from_utf16(&(start..end));
-        self.selected_word_range = Some(self.selected_range.clone());
+        self.engine.select_word(offset);
         cx.notify()
     }
 
     fn unselect(&mut self, cx: &mut ViewContext<Self>) {
-        self.selected_range = self.cursor_offset()..self.cursor_offset();
+        self.engine.unselect();
         cx.notify()
     }
 
-    fn offset_from_utf16(&self, offset: usize) -> usize {
-        let mut utf8_offset = 0;
-        let mut utf16_count = 0;
-
-        for ch in self.text.chars() {
-            if utf16_count >= offset {
-                break;
-            }
-            utf16_count += ch.len_utf16();
-            utf8_offset += ch.len_utf8();
-        }
-
-        utf8_offset
-    }
-
-    fn offset_to_utf16(&self, offset: usize) -> usize {
-        let mut utf16_offset = 0;
-        let mut utf8_count = 0;
-
-        for ch in self.text.chars() {
-            if utf8_count >= offset {
-                break;
-            }
-            utf8_count += ch.len_utf8();
-            utf16_offset += ch.len_utf16();
-        }
-
-        utf16_offset
-    }
-
-    fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
-        self.offset_to_utf16(range.start)..self.offset_to_utf16(range.end)
-    }
-
-    fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
-        self.offset_from_utf16(range_utf16.start)..self.offset_from_utf16(range_utf16.end)
-    }
-
-    fn previous_boundary(&self, offset: usize) -> usize {
-        self.text
-            .grapheme_indices(true)
-            .rev()
-            .find_map(|(idx, _)| (idx < offset).then_some(idx))
-            .unwrap_or(0)
-    }
-
-    fn next_boundary(&self, offset: usize) -> usize {
-        self.text
-            .grapheme_indices(true)
-            .find_map(|(idx, _)| (idx > offset).then_some(idx))
-            .unwrap_or(self.text.len())
-    }
-
     /// Returns the true to let InputElement to render cursor, when Input is focused and current BlinkCursor is visible.
     pub(crate) fn show_cursor(&self, cx: &WindowContext) -> bool {
         self.focus_handle.is_focused(cx) && self.blink_cursor.read(cx).visible()
@@ -1072,6 +1118,7 @@ impl TextInput {
     }
 
     fn on_blur(&mut self, cx: &mut ViewContext<Self>) {
+        self.hide_completion(cx);
         self.unselect(cx);
         self.blink_cursor.update(cx, |cursor, cx| {
             cursor.stop(cx);
@@ -1089,8 +1136,137 @@ impl TextInput {
         self.pause_blink_cursor(cx)
     }
 
+    fn completion_accept(&mut self, _: &CompletionAccept, cx: &mut ViewContext<Self>) {
+        if self.completion_menu.is_none() {
+            cx.propagate();
+            return;
+        }
+
+        self.accept_completion(None, cx);
+    }
+
+    fn completion_dismiss(&mut self, _: &CompletionDismiss, cx: &mut ViewContext<Self>) {
+        if self.completion_menu.is_none() {
+            cx.propagate();
+            return;
+        }
+
+        self.hide_completion(cx);
+    }
+
+    /// Request the completions of the token before the cursor, the menu is hidden if no token.
+    fn update_completion(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(provider) = self.completion_provider.clone() else {
+            return;
+        };
+
+        let cursor = self.engine.cursor_offset();
+        let Some(range) = self
+            .engine
+            .token_before(cursor, provider.trigger_characters())
+        else {
+            self.hide_completion(cx);
+            return;
+        };
+
+        let token = self.engine.text_for_range(range.clone()).to_string();
+        let task = provider.completions(&token, cx);
+        self._completion_task = Some(cx.spawn(|view, mut cx| async move {
+            let items = task.await;
+            _ = view.update(&mut cx, |this, cx| {
+                this.completion_menu =
+                    (!items.is_empty()).then(|| CompletionMenu::new(range, items));
+                cx.notify();
+            });
+        }));
+    }
+
+    fn hide_completion(&mut self, cx: &mut ViewContext<Self>) {
+        self._completion_task = None;
+        if self.completion_menu.take().is_some() {
+            cx.notify();
+        }
+    }
+
+    /// Replace the token with the completion at `ix` or the selected one, returns false if the menu is not shown.
+    fn accept_completion(&mut self, ix: Option<usize>, cx: &mut ViewContext<Self>) -> bool {
+        let Some(menu) = self.completion_menu.take() else {
+            return false;
+        };
+        let Some(completion) = ix.map_or(menu.selected(), |ix| menu.items.get(ix)).cloned() else {
+            return false;
+        };
+
+        // The text may be changed after the completions requested, so find the token again.
+        let triggers = self
+            .completion_provider
+            .as_ref()
+            .map_or(&[][..], |provider| provider.trigger_characters());
+        let range = self
+            .engine
+            .token_before(self.engine.cursor_offset(), triggers)
+            .unwrap_or(menu.range);
+        let range_utf16 = self.engine.range_to_utf16(&range);
+
+        self.replace_text_in_range(Some(range_utf16), &completion.insert_text, cx);
+        self.hide_completion(cx);
+        true
+    }
+
+    fn render_completion_menu(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        let menu = self.completion_menu.as_ref()?;
+        let cursor = self.engine.cursor_offset();
+        let anchor = self.bounds_for_offset_range(cursor..cursor, self.last_bounds?)?;
+
+        Some(
+            deferred(
+                anchored_to(anchor, FloatingPlacement::new(Side::Bottom, Align::Start)).child(
+                    v_flex()
+                        .id("completion-menu")
+                        .occlude()
+                        .min_w(px(160.))
+                        .max_w(px(320.))
+                        .max_h(px(240.))
+                        .overflow_y_scroll()
+                        .p_1()
+                        .popover_style(cx)
+                        .children(menu.items.iter().enumerate().map(|(ix, completion)| {
+                            h_flex()
+                                .id(ix)
+                                .gap_2()
+                                .px_2()
+                                .py_1()
+                                .text_sm()
+                                .rounded(px(cx.theme().radius))
+                                .cursor_pointer()
+                                .hover(|this| this.bg(cx.theme().accent))
+                                .when(ix == menu.selected_ix, |this| {
+                                    this.bg(cx.theme().accent)
+                                        .text_color(cx.theme().accent_foreground)
+                                })
+                                .on_mouse_down(
+                                    MouseButton::Left,
+                                    cx.listener(move |this, _, cx| {
+                                        cx.stop_propagation();
+                                        this.accept_completion(Some(ix), cx);
+                                    }),
+                                )
+                                .child(div().flex_1().child(completion.label.clone()))
+                                .children(completion.detail.clone().map(|detail| {
+                                    div()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(detail)
+                                }))
+                        })),
+                ),
+            )
+            .with_priority(1),
+        )
+    }
+
     pub(super) fn on_drag_move(&mut self, event: &MouseMoveEvent, cx: &mut ViewContext<Self>) {
-        if self.text.is_empty() {
+        if self.engine.is_empty() {
             return;
         }
 
@@ -1126,6 +1302,40 @@ impl TextInput {
             .map(|p| p.is_match(new_text))
             .unwrap_or(true)
     }
+
+    /// Check the pending text with the validation and the mask pattern,
+    /// returns the text to apply, or `None` if the text is invalid.
+    fn checked_text(&self, pending_text: SharedString) -> Option<SharedString> {
+        match &self.mask_pattern {
+            Some(mask_pattern) => {
+                let raw_value = mask_pattern.unmask(&pending_text);
+                if !mask_pattern.is_valid(&raw_value) || !self.is_valid_input(&raw_value) {
+                    return None;
+                }
+
+                Some(mask_pattern.mask(&raw_value).into())
+            }
+            None => self.is_valid_input(&pending_text).then_some(pending_text),
+        }
+    }
+
+    fn on_text_changed(&mut self, cx: &mut ViewContext<Self>) {
+        self.update_preferred_x_offset(cx);
+        self.update_completion(cx);
+        cx.emit(InputEvent::Change(self.engine.text().clone()));
+        if let Some(state) = self.engine.take_history_change() {
+            cx.emit(InputEvent::HistoryChange(state));
+        }
+        self.validate_text_async(cx);
+        cx.notify();
+    }
+}
+
+impl AutomationId for TextInput {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.automation_id = Some(id.into());
+        self
+    }
 }
 
 impl Sizable for TextInput {
@@ -1142,9 +1352,9 @@ impl ViewInputHandler for TextInput {
         adjusted_range: &mut Option<Range<usize>>,
         _cx: &mut ViewContext<Self>,
     ) -> Option<String> {
-        let range = self.range_from_utf16(&range_utf16);
-        adjusted_range.replace(self.range_to_utf16(&range));
-        Some(self.text[range].to_string())
+        let range = self.engine.range_from_utf16(&range_utf16);
+        adjusted_range.replace(self.engine.range_to_utf16(&range));
+        Some(self.engine.text_for_range(range).to_string())
     }
 
     fn selected_text_range(
@@ -1153,19 +1363,22 @@ impl ViewInputHandler for TextInput {
         _cx: &mut ViewContext<Self>,
     ) -> Option<UTF16Selection> {
         Some(UTF16Selection {
-            range: self.range_to_utf16(&self.selected_range),
+            range: self.engine.range_to_utf16(&self.engine.selected_range()),
             reversed: false,
         })
     }
 
     fn marked_text_range(&self, _cx: &mut ViewContext<Self>) -> Option<Range<usize>> {
-        self.marked_range
-            .as_ref()
-            .map(|range| self.range_to_utf16(range))
+        self.engine
+            .marked_range()
+            .map(|range| self.engine.range_to_utf16(&range))
     }
 
-    fn unmark_text(&mut self, _cx: &mut ViewContext<Self>) {
-        self.marked_range = None;
+    fn unmark_text(&mut self, cx: &mut ViewContext<Self>) {
+        if self.engine.marked_range().is_some() {
+            self.engine.unmark_text();
+            cx.emit(InputEvent::CompositionChange(None));
+        }
     }
 
     fn replace_text_in_range(
@@ -1178,25 +1391,28 @@ impl ViewInputHandler for TextInput {
             return;
         }
 
-        let range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or(self.selected_range.clone());
-
-        let pending_text: SharedString =
-            (self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]).into();
-        if !self.is_valid_input(&pending_text) {
+        let range = self.engine.edit_range(range_utf16.as_ref());
+        let pending_text: SharedString = self.engine.pending_text(&range, new_text).into();
+        let Some(text) = self.checked_text(pending_text.clone()) else {
             return;
+        };
+        let was_composing = self.engine.marked_range().is_some();
+
+        if let Some(mask_pattern) = &self.mask_pattern {
+            // The mask may change the text out of the range, so replace the whole text,
+            // and keep the cursor after the same raw chars after the mask.
+            let cursor = range.start + new_text.len();
+            let raw_len = mask_pattern.unmask(&pending_text[..cursor]).chars().count();
+            let cursor = mask_pattern.offset_for_raw_len(&text, raw_len);
+            self.engine.replace_all(text, cursor);
+        } else {
+            self.engine.replace_text_in_range(range, new_text);
         }
-
-        self.push_history(&range, new_text, cx);
-        self.text = pending_text;
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
-        self.marked_range.take();
-        self.update_preferred_x_offset(cx);
-        cx.emit(InputEvent::Change(self.text.clone()));
-        cx.notify();
+        if was_composing {
+            self.engine.unmark_text();
+            cx.emit(InputEvent::CompositionChange(None));
+        }
+        self.on_text_changed(cx);
     }
 
     fn replace_and_mark_text_in_range(
@@ -1210,66 +1426,29 @@ impl ViewInputHandler for TextInput {
             return;
         }
 
-        let range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or(self.selected_range.clone());
-        let pending_text: SharedString =
-            (self.text[0..range.start].to_owned() + new_text + &self.text[range.end..]).into();
-        if !self.is_valid_input(&pending_text) {
+        let range = self.engine.edit_range(range_utf16.as_ref());
+        if !self.is_valid_input(&self.engine.pending_text(&range, new_text)) {
             return;
         }
 
-        self.push_history(&range, new_text, cx);
-        self.text = pending_text;
-        self.marked_range = Some(range.start..range.start + new_text.len());
-        self.selected_range = new_selected_range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .map(|new_range| new_range.start + range.start..new_range.end + range.end)
-            .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
-        cx.emit(InputEvent::Change(self.text.clone()));
+        self.engine
+            .replace_and_mark_text_in_range(range, new_text, new_selected_range_utf16);
+        cx.emit(InputEvent::CompositionChange(self.marked_text()));
+        cx.emit(InputEvent::Change(self.engine.text().clone()));
+        self.validate_text_async(cx);
         cx.notify();
     }
 
-    /// Used to position IME candidates.
-    /// TODO: Fix position of IME candidates in multi-line text input.
+    /// Used to position IME candidates, below the first visual line of the range,
+    /// for both the wrapped and the scrolled lines.
     fn bounds_for_range(
         &mut self,
         range_utf16: Range<usize>,
         bounds: Bounds<Pixels>,
         _: &mut ViewContext<Self>,
     ) -> Option<Bounds<Pixels>> {
-        let line_height = self.last_line_height;
-        let lines = self.last_layout.as_ref()?;
-        let range = self.range_from_utf16(&range_utf16);
-
-        let mut start_origin = None;
-        let mut end_origin = None;
-        let mut y_offset = px(0.);
-        let mut index_offset = 0;
-
-        for line in lines.iter() {
-            if let Some(p) = line.position_for_index(range.start - index_offset, line_height) {
-                start_origin = Some(p + point(px(0.), y_offset));
-            }
-            if let Some(p) = line.position_for_index(range.end - index_offset, line_height) {
-                end_origin = Some(p + point(px(0.), y_offset));
-            }
-
-            y_offset += line.size(line_height).height;
-            if start_origin.is_some() && end_origin.is_some() {
-                break;
-            }
-
-            index_offset += line.len();
-        }
-
-        Some(Bounds::from_corners(
-            bounds.origin + start_origin.unwrap_or_default(),
-            bounds.origin + end_origin.unwrap_or_default(),
-        ))
+        let range = self.engine.range_from_utf16(&range_utf16);
+        self.bounds_for_offset_range(range, bounds)
     }
 }
 
@@ -1290,6 +1469,7 @@ impl Render for TextInput {
         div()
             .flex()
             .id("input")
+            .when_automation_id(self.automation_id.as_ref())
             .key_context(CONTEXT)
             .track_focus(&self.focus_handle)
             .when(!self.disabled, |this| {
@@ -1303,10 +1483,11 @@ impl Render for TextInput {
             .on_action(cx.listener(Self::right))
             .on_action(cx.listener(Self::select_left))
             .on_action(cx.listener(Self::select_right))
+            // The `up`, `down` are also used to select the completion in the single line mode.
+            .on_action(cx.listener(Self::up))
+            .on_action(cx.listener(Self::down))
             .when(self.multi_line, |this| {
-                this.on_action(cx.listener(Self::up))
-                    .on_action(cx.listener(Self::down))
-                    .on_action(cx.listener(Self::select_up))
+                this.on_action(cx.listener(Self::select_up))
                     .on_action(cx.listener(Self::select_down))
             })
             .on_action(cx.listener(Self::select_all))
@@ -1322,6 +1503,8 @@ impl Render for TextInput {
             .on_action(cx.listener(Self::redo))
             .on_action(cx.listener(Self::redo))
             .on_key_down(cx.listener(Self::on_key_down_for_blink_cursor))
+            .on_action(cx.listener(Self::completion_accept))
+            .on_action(cx.listener(Self::completion_dismiss))
             .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_scroll_wheel(cx.listener(Self::on_scroll_wheel))
@@ -1340,8 +1523,11 @@ impl Render for TextInput {
                 .border_color(cx.theme().input)
                 .border_1()
                 .rounded(px(cx.theme().radius))
-                .when(cx.theme().shadow, |this| this.shadow_sm())
+                .when(cx.theme().has_shadow(), |this| this.shadow_sm())
                 .when(focused, |this| this.outline(cx))
+                .when(self.validation_state.error().is_some(), |this| {
+                    this.border_color(cx.theme().destructive)
+                })
                 .when(prefix.is_none(), |this| this.input_pl(self.size))
                 .when(suffix.is_none(), |this| this.input_pr(self.size))
             })
@@ -1355,14 +1541,26 @@ impl Render for TextInput {
                     .overflow_x_hidden()
                     .child(TextElement::new(cx.view().clone())),
             )
-            .when(self.loading, |this| {
-                this.child(Indicator::new().color(cx.theme().muted_foreground))
+            .when(
+                self.loading || self.validation_state.is_validating(),
+                |this| this.child(Indicator::new().color(cx.theme().muted_foreground)),
+            )
+            .when_some(self.validation_state.error().cloned(), |this, message| {
+                this.child(
+                    div()
+                        .id("validation-error")
+                        .flex_shrink_0()
+                        .text_color(cx.theme().destructive)
+                        .child(Icon::new(IconName::CircleX).small())
+                        .tooltip(move |cx| Tooltip::new(message.clone(), cx)),
+                )
             })
             .when(
-                self.cleanable && !self.loading && !self.text.is_empty() && self.is_single_line(),
+                self.cleanable && !self.loading && !self.engine.is_empty() && self.is_single_line(),
                 |this| this.child(ClearButton::new(cx).on_click(cx.listener(Self::clean))),
             )
             .children(suffix)
+            .children(self.render_completion_menu(cx))
             .when(self.is_multi_line(), |this| {
                 let entity_id = cx.view().entity_id();
                 if self.last_layout.is_some() {
@@ -0,0 +1,297 @@
+use gpui::SharedString;
+
+use crate::format::NumberFormat;
+
+/// The mask pattern of the [`TextInput`](super::TextInput), used to format the input text,
+/// and the unformatted value can be retrieved by [`TextInput::raw_value`](super::TextInput::raw_value).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MaskPattern {
+    /// The pattern to mask the text, e.g.: `###-####`.
+    ///
+    /// - `#` matches a digit.
+    /// - `A` matches a letter.
+    /// - `*` matches a digit or a letter.
+    ///
+    /// Other characters are literals that will be inserted automatically.
+    Pattern(SharedString),
+    /// The number with thousand separator, e.g.: `1,234,567.89`, `$1,234.00` or `12 %`.
+    ///
+    /// The raw value always uses `.` as the decimal point, e.g.: `1234.5` for `1.234,5` in `de`.
+    Number {
+        /// The thousand separator and the decimal point, default is `1,234.56`.
+        format: NumberFormat,
+        /// The max number of fraction digits, `None` means no limit.
+        fraction: Option<usize>,
+        /// The text shown before the number, e.g.: `$`, not included in the raw value.
+        prefix: SharedString,
+        /// The text shown after the number, e.g.: ` %`, not included in the raw value.
+        suffix: SharedString,
+    },
+}
+
+impl MaskPattern {
+    /// Create a pattern mask, e.g.: `###-####`.
+    pub fn new(pattern: impl Into<SharedString>) -> Self {
+        Self::Pattern(pattern.into())
+    }
+
+    /// Create a number mask with `,` as thousand separator and `.` as decimal point,
+    /// use [`MaskPattern::number_format`] for the other locales.
+    pub fn number(fraction: Option<usize>) -> Self {
+        Self::Number {
+            format: NumberFormat::default(),
+            fraction,
+            prefix: SharedString::default(),
+            suffix: SharedString::default(),
+        }
+    }
+
+    /// Set the separators of the number mask, e.g.: `NumberFormat::for_locale("de")` for `1.234,56`,
+    /// no effect for the pattern mask.
+    ///
+    /// The format is ignored if the thousand separator is same as the decimal point.
+    pub fn number_format(mut self, new_format: NumberFormat) -> Self {
+        if let Self::Number { format, .. } = &mut self {
+            if new_format.group != new_format.decimal {
+                *format = new_format;
+            }
+        }
+        self
+    }
+
+    /// Set the prefix of the number mask, e.g.: `$`, no effect for the pattern mask.
+    pub fn prefix(mut self, new_prefix: impl Into<SharedString>) -> Self {
+        if let Self::Number { prefix, .. } = &mut self {
+            *prefix = new_prefix.into();
+        }
+        self
+    }
+
+    /// Set the suffix of the number mask, e.g.: ` %`, no effect for the pattern mask.
+    pub fn suffix(mut self, new_suffix: impl Into<SharedString>) -> Self {
+        if let Self::Number { suffix, .. } = &mut self {
+            *suffix = new_suffix.into();
+        }
+        self
+    }
+
+    /// Returns true if the char is a part of the raw value, not a literal or separator.
+    fn is_raw_char(&self, c: char) -> bool {
+        match self {
+            Self::Pattern(pattern) => !pattern
+                .chars()
+                .any(|p| p == c && !matches!(p, '#' | 'A' | '*')),
+            // The prefix and suffix may be partially deleted, so only keep the number chars.
+            Self::Number { format, .. } => c.is_ascii_digit() || c == '-' || c == format.decimal,
+        }
+    }
+
+    /// Returns the char in the raw value, the decimal point of the number mask is `.` in the raw value.
+    fn raw_char(&self, c: char) -> char {
+        match self {
+            Self::Number { format, .. } if c == format.decimal => '.',
+            _ => c,
+        }
+    }
+
+    /// Returns the text without the prefix and suffix of the number mask.
+    fn strip_affixes<'a>(&self, text: &'a str) -> &'a str {
+        match self {
+            Self::Pattern(_) => text,
+            Self::Number { prefix, suffix, .. } => {
+                let text = text.strip_prefix(prefix.as_ref()).unwrap_or(text);
+                text.strip_suffix(suffix.as_ref()).unwrap_or(text)
+            }
+        }
+    }
+
+    /// Remove the literals, separators, prefix and suffix from the masked text.
+    pub fn unmask(&self, text: &str) -> String {
+        self.strip_affixes(text)
+            .chars()
+            .filter(|c| self.is_raw_char(*c))
+            .map(|c| self.raw_char(c))
+            .collect()
+    }
+
+    /// Returns true if the raw value is valid for the mask.
+    pub fn is_valid(&self, raw: &str) -> bool {
+        match self {
+            Self::Pattern(pattern) => {
+                let mut placeholders = pattern.chars().filter(|c| matches!(c, '#' | 'A' | '*'));
+                raw.chars().all(|c| match placeholders.next() {
+                    Some('#') => c.is_ascii_digit(),
+                    Some('A') => c.is_alphabetic(),
+                    Some('*') => c.is_alphanumeric(),
+                    _ => false,
+                })
+            }
+            Self::Number { fraction, .. } => {
+                let raw = raw.strip_prefix('-').unwrap_or(raw);
+                let (int, frac) = match raw.split_once('.') {
+                    Some((int, frac)) => (int, Some(frac)),
+                    None => (raw, None),
+                };
+
+                int.chars().all(|c| c.is_ascii_digit())
+                    && frac.map_or(true, |frac| {
+                        frac.chars().all(|c| c.is_ascii_digit())
+                            && fraction.map_or(true, |max| max > 0 && frac.len() <= max)
+                    })
+            }
+        }
+    }
+
+    /// Format the raw value with the mask.
+    pub fn mask(&self, raw: &str) -> String {
+        match self {
+            Self::Pattern(pattern) => {
+                let mut result = String::new();
+                let mut chars = raw.chars().peekable();
+                for p in pattern.chars() {
+                    if chars.peek().is_none() {
+                        break;
+                    }
+
+                    if matches!(p, '#' | 'A' | '*') {
+                        result.extend(chars.next());
+                    } else {
+                        result.push(p);
+                    }
+                }
+                result
+            }
+            Self::Number {
+                format,
+                prefix,
+                suffix,
+                ..
+            } => {
+                // Empty to show the placeholder.
+                if raw.is_empty() {
+                    return String::new();
+                }
+
+                let (sign, raw) = match raw.strip_prefix('-') {
+                    Some(raw) => ("-", raw),
+                    None => ("", raw),
+                };
+                format!("{}{}{}{}", prefix, sign, format.format_digits(raw), suffix)
+            }
+        }
+    }
+
+    /// Returns the byte offset in the masked text after the `raw_len` raw chars.
+    pub(super) fn offset_for_raw_len(&self, masked: &str, raw_len: usize) -> usize {
+        let value = self.strip_affixes(masked);
+        // The byte offset of the value in the masked text, after the prefix.
+        let start = masked.find(value).unwrap_or(0);
+        if raw_len == 0 {
+            return start;
+        }
+
+        let mut count = 0;
+        for (ix, c) in value.char_indices() {
+            if self.is_raw_char(c) {
+                count += 1;
+                if count == raw_len {
+                    return start + ix + c.len_utf8();
+                }
+            }
+        }
+        start + value.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MaskPattern;
+    use crate::format::NumberFormat;
+
+    #[test]
+    fn test_pattern_mask() {
+        let mask = MaskPattern::new("###-####");
+        assert_eq!(mask.mask("1234567"), "123-4567");
+        assert_eq!(mask.mask("123"), "123");
+        assert_eq!(mask.mask("1234"), "123-4");
+        assert_eq!(mask.unmask("123-4567"), "1234567");
+        assert!(mask.is_valid("1234567"));
+        assert!(!mask.is_valid("12345678"));
+        assert!(!mask.is_valid("12a"));
+        assert_eq!(mask.offset_for_raw_len("123-4567", 3), 3);
+        assert_eq!(mask.offset_for_raw_len("123-4567", 4), 5);
+
+        let mask = MaskPattern::new("AA-**");
+        assert_eq!(mask.mask("CNa1"), "CN-a1");
+        assert!(!mask.is_valid("C1"));
+    }
+
+    #[test]
+    fn test_number_mask() {
+        let mask = MaskPattern::number(Some(2));
+        assert_eq!(mask.mask("1234567.89"), "1,234,567.89");
+        assert_eq!(mask.mask("-1234"), "-1,234");
+        assert_eq!(mask.mask("123"), "123");
+        assert_eq!(mask.mask("1234."), "1,234.");
+        assert_eq!(mask.unmask("1,234,567.89"), "1234567.89");
+        assert!(mask.is_valid("1234.5"));
+        assert!(mask.is_valid("-"));
+        assert!(!mask.is_valid("1234.567"));
+        assert!(!mask.is_valid("12a"));
+        assert!(!MaskPattern::number(Some(0)).is_valid("1.2"));
+        assert_eq!(mask.offset_for_raw_len("1,234", 2), 3);
+    }
+
+    #[test]
+    fn test_number_mask_affixes() {
+        let mask = MaskPattern::number(Some(2)).prefix("$");
+        assert_eq!(mask.mask("1234"), "$1,234");
+        assert_eq!(mask.mask("-1234.5"), "$-1,234.5");
+        assert_eq!(mask.mask(""), "");
+        assert_eq!(mask.unmask("$1,234.00"), "1234.00");
+        // The prefix is deleted.
+        assert_eq!(mask.unmask("1,234"), "1234");
+        assert_eq!(mask.offset_for_raw_len("$1,234", 0), 1);
+        assert_eq!(mask.offset_for_raw_len("$1,234", 2), 4);
+        assert_eq!(mask.offset_for_raw_len("$1,234", 9), 6);
+
+        let mask = MaskPattern::number(None).suffix(" %");
+        assert_eq!(mask.mask("12"), "12 %");
+        assert_eq!(mask.unmask("12 %"), "12");
+        // The suffix is partially deleted.
+        assert_eq!(mask.unmask("12 "), "12");
+        assert_eq!(mask.offset_for_raw_len("12 %", 2), 2);
+        assert_eq!(mask.offset_for_raw_len("12 %", 3), 2);
+
+        // The `.` in the prefix is not a part of the value.
+        let mask = MaskPattern::number(Some(2)).prefix("Rs.");
+        assert_eq!(mask.mask("1234.5"), "Rs.1,234.5");
+        assert_eq!(mask.unmask("Rs.1,234.5"), "1234.5");
+        assert_eq!(mask.offset_for_raw_len("Rs.1,234.5", 1), 4);
+    }
+
+    #[test]
+    fn test_number_mask_format() {
+        let mask = MaskPattern::number(Some(2)).number_format(NumberFormat::for_locale("de"));
+        assert_eq!(mask.mask("1234567.89"), "1.234.567,89");
+        assert_eq!(mask.mask("1234."), "1.234,");
+        assert_eq!(mask.unmask("1.234.567,89"), "1234567.89");
+        // The `.` is the thousand separator, not a part of the value.
+        assert_eq!(mask.unmask("1.234.5"), "12345");
+        assert_eq!(mask.offset_for_raw_len("1.234,5", 5), 6);
+        assert!(mask.is_valid(&mask.unmask("1.234,56")));
+
+        let mask = MaskPattern::number(Some(2)).number_format(NumberFormat::for_locale("fr"));
+        assert_eq!(mask.mask("1234.5"), "1\u{a0}234,5");
+        assert_eq!(mask.unmask("1\u{a0}234,5"), "1234.5");
+
+        // The format with the same thousand separator and decimal point is ignored.
+        let format = NumberFormat {
+            group: ',',
+            decimal: ',',
+            ..Default::default()
+        };
+        let mask = MaskPattern::number(None).number_format(format);
+        assert_eq!(mask.mask("1234.5"), "1,234.5");
+    }
+}
@@ -1,10 +1,22 @@
 mod blink_cursor;
 mod change;
 mod clear_button;
+mod completion;
+mod decoration;
 mod element;
+mod engine;
 mod input;
+mod mask;
 mod otp_input;
+mod tag_input;
+mod validation;
 
 pub(crate) use clear_button::*;
+pub use completion::{Completion, CompletionProvider};
+pub use decoration::{DecorationStyle, TextDecoration};
+pub use engine::TextEngine;
 pub use input::*;
+pub use mask::MaskPattern;
 pub use otp_input::*;
+pub use tag_input::*;
+pub use validation::ValidationState;
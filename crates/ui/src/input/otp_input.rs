@@ -1,16 +1,34 @@
 use gpui::{
-    div, prelude::FluentBuilder, px, AnyElement, Context, EventEmitter, FocusHandle, FocusableView,
-    InteractiveElement, IntoElement, KeyDownEvent, Model, MouseButton, MouseDownEvent,
-    ParentElement as _, Render, SharedString, Styled as _, ViewContext,
+    div, prelude::FluentBuilder, px, AnyElement, AppContext, Context, EventEmitter, FocusHandle,
+    FocusableView, InteractiveElement, IntoElement, KeyDownEvent, Model, MouseButton,
+    MouseDownEvent, ParentElement as _, Render, SharedString, Styled as _, ViewContext,
 };
 
+use crate::keymap::{self, KeymapEntry};
 use crate::{h_flex, theme::ActiveTheme, v_flex, Icon, IconName, Sizable, Size};
 
-use super::{blink_cursor::BlinkCursor, InputEvent};
+use super::{blink_cursor::BlinkCursor, InputEvent, Paste};
+
+const CONTEXT: &str = "OtpInput";
+
+pub(super) fn init(cx: &mut AppContext) {
+    keymap::bind_keys(
+        cx,
+        [
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-v", Paste, Some(CONTEXT)),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-v", Paste, Some(CONTEXT)),
+        ],
+    );
+}
 
 pub enum InputOptEvent {
-    /// When all OTP input have filled, this event will be triggered.
+    /// When the value of the OTP input has been changed.
     Change(SharedString),
+    /// When all OTP input have filled, this event will be triggered,
+    /// e.g.: to submit the login form automatically.
+    Complete(SharedString),
 }
 
 /// A One Time Password (OTP) input element.
@@ -26,6 +44,7 @@ pub struct OtpInput {
     length: usize,
     number_of_groups: usize,
     masked: bool,
+    numeric: bool,
     value: SharedString,
     blink_cursor: Model<BlinkCursor>,
     size: Size,
@@ -41,6 +60,7 @@ impl OtpInput {
             number_of_groups: 2,
             value: SharedString::default(),
             masked: false,
+            numeric: true,
             blink_cursor: blink_cursor.clone(),
             size: Size::Medium,
         };
@@ -101,6 +121,20 @@ impl OtpInput {
         cx.notify();
     }
 
+    /// Set to false to accept letters as well as digits, default is true (only digits).
+    pub fn numeric(mut self, numeric: bool) -> Self {
+        self.numeric = numeric;
+        self
+    }
+
+    fn is_valid_char(&self, c: char) -> bool {
+        if self.numeric {
+            c.is_ascii_digit()
+        } else {
+            c.is_ascii_alphanumeric()
+        }
+    }
+
     pub fn focus(&self, cx: &mut ViewContext<Self>) {
         self.focus_handle.focus(cx);
     }
@@ -113,39 +147,77 @@ impl OtpInput {
         let mut chars: Vec<char> = self.value.chars().collect();
         let ix = chars.len();
 
-        let key = event.keystroke.key.as_str();
+        let keystroke = &event.keystroke;
+        if keystroke.modifiers.platform || keystroke.modifiers.control {
+            return;
+        }
 
-        match key {
+        match keystroke.key.as_str() {
             "backspace" => {
+                // Move back to the previous field and clear it.
                 if ix > 0 {
                     let ix = ix - 1;
                     chars.remove(ix);
                 }
-
-                cx.prevent_default();
-                cx.stop_propagation();
             }
-            _ => {
-                let c = key.chars().next().unwrap();
-                if !matches!(c, '0'..='9') {
+            key => {
+                let mut key_chars = key.chars();
+                let (Some(c), None) = (key_chars.next(), key_chars.next()) else {
+                    return;
+                };
+                if !self.is_valid_char(c) {
                     return;
                 }
                 if ix >= self.length {
                     return;
                 }
 
+                // Auto advance to the next field.
                 chars.push(c);
-
-                cx.prevent_default();
-                cx.stop_propagation();
             }
         }
 
+        cx.prevent_default();
+        cx.stop_propagation();
+        self.update_value(chars, cx);
+    }
+
+    /// Paste a full code (or a part of it) from the clipboard, the chars will be filled from
+    /// the current field. The separators (e.g.: spaces or `-`) in the code will be ignored.
+    fn paste(&mut self, _: &Paste, cx: &mut ViewContext<Self>) {
+        let Some(text) = cx.read_from_clipboard().and_then(|item| item.text()) else {
+            return;
+        };
+
+        let pasted: Vec<char> = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect();
+        if pasted.is_empty() || !pasted.iter().all(|c| self.is_valid_char(*c)) {
+            return;
+        }
+
+        let mut chars: Vec<char> = self.value.chars().collect();
+        if pasted.len() >= self.length {
+            chars.clear();
+        }
+        chars.extend(pasted);
+        chars.truncate(self.length);
+        self.update_value(chars, cx);
+    }
+
+    fn update_value(&mut self, chars: Vec<char>, cx: &mut ViewContext<Self>) {
         self.pause_blink_cursor(cx);
-        self.value = SharedString::from(chars.iter().collect::<String>());
+        let value = SharedString::from(chars.iter().collect::<String>());
+        if value == self.value {
+            return;
+        }
 
-        if self.value.chars().count() == self.length {
+        self.value = value;
+        cx.emit(InputOptEvent::Change(self.value.clone()));
+        if chars.len() == self.length {
             cx.emit(InputEvent::Change(self.value.clone()));
+            cx.emit(InputOptEvent::Complete(self.value.clone()));
         }
         cx.notify()
     }
@@ -184,6 +256,7 @@ impl FocusableView for OtpInput {
     }
 }
 impl EventEmitter<InputEvent> for OtpInput {}
+impl EventEmitter<InputOptEvent> for OtpInput {}
 
 impl Render for OtpInput {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
@@ -195,6 +268,7 @@ impl Render for OtpInput {
             Size::Small => px(14.),
             Size::Medium => px(16.),
             Size::Large => px(18.),
+            Size::XLarge => px(20.),
             Size::Size(v) => v * 0.5,
         };
 
@@ -220,7 +294,7 @@ impl Render for OtpInput {
                     .border_color(cx.theme().input)
                     .bg(cx.theme().background)
                     .when(is_input_focused, |this| this.border_color(cx.theme().ring))
-                    .when(cx.theme().shadow, |this| this.shadow_sm())
+                    .when(cx.theme().has_shadow(), |this| this.shadow_sm())
                     .items_center()
                     .justify_center()
                     .rounded_md()
@@ -230,6 +304,7 @@ impl Render for OtpInput {
                         Size::Small => this.w_6().h_6(),
                         Size::Medium => this.w_8().h_8(),
                         Size::Large => this.w_11().h_11(),
+                        Size::XLarge => this.w_12().h_12(),
                         Size::Size(px) => this.w(px).h(px),
                     })
                     .on_mouse_down(MouseButton::Left, cx.listener(Self::on_input_mouse_down))
@@ -260,7 +335,9 @@ impl Render for OtpInput {
         }
 
         v_flex()
+            .key_context(CONTEXT)
             .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::paste))
             .on_key_down(cx.listener(Self::on_key_down))
             .items_center()
             .child(
@@ -0,0 +1,201 @@
+use gpui::{
+    div, prelude::FluentBuilder as _, px, EventEmitter, FocusHandle, FocusableView,
+    InteractiveElement as _, IntoElement, MouseButton, ParentElement as _, Render, SharedString,
+    Styled as _, Subscription, View, ViewContext, VisualContext as _,
+};
+
+use super::{Backspace, InputEvent, TextInput};
+use crate::{tag::Tag, theme::ActiveTheme as _, Sizable, StyledExt as _};
+
+pub enum TagInputEvent {
+    /// The tags have been changed.
+    Change(Vec<SharedString>),
+}
+
+/// Split the typed text by the commas, returns the completed tags and the rest text to keep typing.
+fn split_tags(text: &str) -> (Vec<String>, &str) {
+    let Some(pos) = text.rfind(',') else {
+        return (vec![], text);
+    };
+
+    let tags = text[..pos]
+        .split(',')
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| tag.to_string())
+        .collect();
+
+    (tags, &text[pos + 1..])
+}
+
+/// An input to enter the tags, the tag is added by pressing `Enter` or typing a comma,
+/// and the last tag is removed by pressing `Backspace` in the empty input.
+pub struct TagInput {
+    input: View<TextInput>,
+    tags: Vec<SharedString>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<TagInputEvent> for TagInput {}
+
+impl TagInput {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        let input = cx.new_view(|cx| TextInput::new(cx).appearance(false).small());
+        let _subscriptions = vec![cx.subscribe(&input, Self::on_input_event)];
+
+        Self {
+            input,
+            tags: vec![],
+            _subscriptions,
+        }
+    }
+
+    /// Set the placeholder of the input.
+    pub fn set_placeholder(
+        &mut self,
+        placeholder: impl Into<SharedString>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.input.update(cx, |input, cx| {
+            input.set_placeholder(placeholder);
+            cx.notify();
+        });
+    }
+
+    /// Returns the tags.
+    pub fn tags(&self) -> &[SharedString] {
+        &self.tags
+    }
+
+    /// Set the tags, the duplicate tags are ignored.
+    pub fn set_tags(
+        &mut self,
+        tags: impl IntoIterator<Item = impl Into<SharedString>>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.tags.clear();
+        for tag in tags {
+            let tag = tag.into();
+            if !self.tags.contains(&tag) {
+                self.tags.push(tag);
+            }
+        }
+        cx.notify();
+    }
+
+    /// Add a tag, returns false if the tag is empty or already exists.
+    pub fn add_tag(&mut self, tag: impl Into<SharedString>, cx: &mut ViewContext<Self>) -> bool {
+        let tag: SharedString = tag.into();
+        let tag: SharedString = tag.trim().to_string().into();
+        if tag.is_empty() || self.tags.contains(&tag) {
+            return false;
+        }
+
+        self.tags.push(tag);
+        cx.emit(TagInputEvent::Change(self.tags.clone()));
+        cx.notify();
+        true
+    }
+
+    /// Remove the tag at the index.
+    pub fn remove_tag(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        if ix >= self.tags.len() {
+            return;
+        }
+
+        self.tags.remove(ix);
+        cx.emit(TagInputEvent::Change(self.tags.clone()));
+        cx.notify();
+    }
+
+    fn on_input_event(
+        &mut self,
+        input: View<TextInput>,
+        event: &InputEvent,
+        cx: &mut ViewContext<Self>,
+    ) {
+        match event {
+            InputEvent::Change(text) => {
+                let (tags, rest) = split_tags(text);
+                if tags.is_empty() && rest.len() == text.len() {
+                    return;
+                }
+
+                let rest = rest.trim_start().to_string();
+                for tag in tags {
+                    self.add_tag(tag, cx);
+                }
+                input.update(cx, |input, cx| input.set_text(rest, cx));
+            }
+            InputEvent::PressEnter => {
+                let text = input.read(cx).text();
+                if self.add_tag(text, cx) {
+                    input.update(cx, |input, cx| input.set_text("", cx));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Remove the last tag when pressing `Backspace` in the empty input,
+    /// this runs in the capture phase, so the input is not changed yet.
+    fn on_backspace(&mut self, _: &Backspace, cx: &mut ViewContext<Self>) {
+        if self.input.read(cx).text().is_empty() && !self.tags.is_empty() {
+            self.remove_tag(self.tags.len() - 1, cx);
+        }
+    }
+}
+
+impl FocusableView for TagInput {
+    fn focus_handle(&self, cx: &gpui::AppContext) -> FocusHandle {
+        self.input.focus_handle(cx)
+    }
+}
+
+impl Render for TagInput {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let focused = self.input.focus_handle(cx).contains_focused(cx);
+
+        div()
+            .id("tag-input")
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap_1()
+            .w_full()
+            .px_2()
+            .py_1()
+            .bg(cx.theme().background)
+            .border_1()
+            .border_color(cx.theme().input)
+            .rounded(px(cx.theme().radius))
+            .when(cx.theme().has_shadow(), |this| this.shadow_sm())
+            .when(focused, |this| this.outline(cx))
+            .capture_action(cx.listener(Self::on_backspace))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, cx| this.input.focus_handle(cx).focus(cx)),
+            )
+            .children(self.tags.iter().enumerate().map(|(ix, tag)| {
+                Tag::new(("tag", ix), tag.clone())
+                    .small()
+                    .on_remove(cx.listener(move |this, _, cx| this.remove_tag(ix, cx)))
+            }))
+            .child(div().flex_1().min_w(px(80.)).child(self.input.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_tags;
+
+    #[test]
+    fn test_split_tags() {
+        assert_eq!(split_tags("rust"), (vec![], "rust"));
+        assert_eq!(split_tags("rust,"), (vec!["rust".to_string()], ""));
+        assert_eq!(
+            split_tags(" rust , gpui,,ui"),
+            (vec!["rust".to_string(), "gpui".to_string()], "ui")
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::{rc::Rc, time::Duration};
+
+use gpui::{SharedString, Task, ViewContext};
+use smol::Timer;
+
+use super::TextInput;
+
+/// The default debounce duration before running the async validation.
+pub(super) const DEFAULT_VALIDATION_DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub(super) type AsyncValidator =
+    Rc<dyn Fn(SharedString, &mut ViewContext<TextInput>) -> Task<Result<(), SharedString>>>;
+
+/// The state of the async validation of the [`TextInput`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ValidationState {
+    /// No validation has been performed, or the input is empty.
+    #[default]
+    None,
+    /// The validation is in progress.
+    Validating,
+    /// The text is valid.
+    Valid,
+    /// The text is invalid, with the error message.
+    Invalid(SharedString),
+}
+
+impl ValidationState {
+    pub fn is_validating(&self) -> bool {
+        matches!(self, Self::Validating)
+    }
+
+    /// Returns the error message if the text is invalid.
+    pub fn error(&self) -> Option<&SharedString> {
+        match self {
+            Self::Invalid(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl TextInput {
+    /// Set an async validator of the input field, it will be called after the text changed,
+    /// e.g.: check the username availability from the server.
+    ///
+    /// The validator returns a `Task` with `Err(message)` if the text is invalid.
+    ///
+    /// - The validation will be debounced, see [`TextInput::validation_debounce`].
+    /// - A new validation will cancel the previous one that still in progress.
+    /// - A spinner will be displayed in the input when validating.
+    pub fn validate_async(
+        mut self,
+        f: impl Fn(SharedString, &mut ViewContext<Self>) -> Task<Result<(), SharedString>> + 'static,
+    ) -> Self {
+        self.async_validator = Some(Rc::new(f));
+        self
+    }
+
+    /// Set the debounce duration of the async validation, default is 300ms.
+    pub fn validation_debounce(mut self, duration: Duration) -> Self {
+        self.validation_debounce = duration;
+        self
+    }
+
+    /// Return the state of the async validation.
+    pub fn validation_state(&self) -> &ValidationState {
+        &self.validation_state
+    }
+
+    /// Run the async validation for the current text, the previous validation will be cancelled.
+    pub(super) fn validate_text_async(&mut self, cx: &mut ViewContext<Self>) {
+        let Some(validator) = self.async_validator.clone() else {
+            return;
+        };
+
+        if self.engine.text().is_empty() {
+            self.validation_state = ValidationState::None;
+            self._validation_task = None;
+            return;
+        }
+
+        let text = self.engine.text().clone();
+        let debounce = self.validation_debounce;
+        self.validation_state = ValidationState::Validating;
+        // Replace the task to drop the previous one, that will cancel the superseded validation.
+        self._validation_task = Some(cx.spawn(|view, mut cx| async move {
+            Timer::after(debounce).await;
+
+            let Ok(Ok(task)) = cx.update(|cx| view.update(cx, |_, cx| validator(text, cx))) else {
+                return;
+            };
+            let result = task.await;
+
+            _ = cx.update(|cx| {
+                _ = view.update(cx, |view, cx| {
+                    view.validation_state = match result {
+                        Ok(()) => ValidationState::Valid,
+                        Err(message) => ValidationState::Invalid(message),
+                    };
+                    cx.notify();
+                });
+            });
+        }));
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context as _, Result};
+use gpui::{
+    Action, AppContext, Global, KeyBinding, KeyBindingContextPredicate, Keystroke, SharedString,
+};
+use serde::Deserialize;
+
+/// A key binding entry of the [`Keymap`].
+pub struct KeymapEntry {
+    pub(crate) keystrokes: SharedString,
+    pub(crate) action: Box<dyn Action>,
+    pub(crate) context: Option<SharedString>,
+}
+
+impl Clone for KeymapEntry {
+    fn clone(&self) -> Self {
+        Self {
+            keystrokes: self.keystrokes.clone(),
+            action: self.action.boxed_clone(),
+            context: self.context.clone(),
+        }
+    }
+}
+
+impl KeymapEntry {
+    /// Create a key binding entry, the arguments are same as [`KeyBinding::new`].
+    pub fn new<A: Action>(keystrokes: &str, action: A, context: Option<&str>) -> Self {
+        Self {
+            keystrokes: SharedString::from(keystrokes.to_string()),
+            action: action.boxed_clone(),
+            context: context.map(|context| SharedString::from(context.to_string())),
+        }
+    }
+
+    fn to_key_binding(&self) -> Result<KeyBinding> {
+        let predicate = self
+            .context
+            .as_ref()
+            .map(|context| KeyBindingContextPredicate::parse(context))
+            .transpose()?;
+
+        KeyBinding::load(&self.keystrokes, self.action.boxed_clone(), predicate)
+    }
+}
+
+/// The keymap to aggregate the default key bindings of the components and the application,
+/// and the user overrides loaded from the JSON keymap file.
+///
+/// The user keymap file is a list of sections, the keys of a section are the keystrokes,
+/// and the value is the action name, or the action name with the arguments.
+///
+/// ```json
+/// [
+///     { "context": "Input", "cmd-d": "input::Delete" },
+///     { "ctrl-1": ["story::SelectFont", 18] }
+/// ]
+/// ```
+#[derive(Default)]
+pub struct Keymap {
+    defaults: Vec<KeymapEntry>,
+    /// The enabled shortcuts of the [`Shortcuts`](crate::shortcuts::Shortcuts).
+    shortcuts: Vec<KeymapEntry>,
+    user: Vec<KeymapEntry>,
+}
+
+impl Global for Keymap {}
+
+/// Bind the default keys, use this instead of `cx.bind_keys`
+/// to keep the bindings when the keymap has been rebuilt.
+///
+/// ```ignore
+/// keymap::bind_keys(cx, [KeymapEntry::new("escape", Escape, Some("Modal"))]);
+/// ```
+pub fn bind_keys(cx: &mut AppContext, entries: impl IntoIterator<Item = KeymapEntry>) {
+    let entries = entries.into_iter().collect::<Vec<_>>();
+    cx.bind_keys(
+        entries
+            .iter()
+            .map(|entry| entry.to_key_binding().expect("invalid key binding")),
+    );
+    cx.default_global::<Keymap>().defaults.extend(entries);
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeymapAction {
+    Name(String),
+    WithArgs(String, serde_json::Value),
+}
+
+#[derive(Debug, Deserialize)]
+struct KeymapSection {
+    #[serde(default)]
+    context: Option<String>,
+    #[serde(flatten)]
+    bindings: BTreeMap<String, KeymapAction>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeymapFile {
+    Sections(Vec<KeymapSection>),
+    Section(KeymapSection),
+}
+
+/// Parse the keymap file, returns the list of `(context, keystrokes, action, args)`.
+fn parse_keymap(
+    content: &str,
+) -> Result<Vec<(Option<String>, String, String, Option<serde_json::Value>)>> {
+    let sections = match serde_json::from_str::<KeymapFile>(content)
+        .map_err(|err| anyhow!("invalid keymap file: {}", err))?
+    {
+        KeymapFile::Sections(sections) => sections,
+        KeymapFile::Section(section) => vec![section],
+    };
+
+    Ok(sections
+        .into_iter()
+        .flat_map(|section| {
+            let context = section.context;
+            section
+                .bindings
+                .into_iter()
+                .map(move |(keystrokes, action)| match action {
+                    KeymapAction::Name(name) => (context.clone(), keystrokes, name, None),
+                    KeymapAction::WithArgs(name, args) => {
+                        (context.clone(), keystrokes, name, Some(args))
+                    }
+                })
+        })
+        .collect())
+}
+
+impl Keymap {
+    /// Load the user keymap from the JSON content, and rebuild the key bindings.
+    ///
+    /// The previous user keymap will be replaced, and the keymap keeps unchanged if there is any error.
+    pub fn load_user_keymap(content: &str, cx: &mut AppContext) -> Result<()> {
+        let entries = Self::parse_user_keymap(content, cx)?;
+        cx.default_global::<Keymap>().user = entries;
+        Self::rebuild(cx);
+        Ok(())
+    }
+
+    /// Check the user keymap without loading it, e.g.: before importing a keymap file.
+    pub fn validate_user_keymap(content: &str, cx: &AppContext) -> Result<()> {
+        Self::parse_user_keymap(content, cx).map(|_| ())
+    }
+
+    fn parse_user_keymap(content: &str, cx: &AppContext) -> Result<Vec<KeymapEntry>> {
+        let mut entries = vec![];
+        for (context, keystrokes, name, args) in parse_keymap(content)? {
+            let action = cx
+                .build_action(&name, args)
+                .with_context(|| format!("unknown action `{}` for `{}`", name, keystrokes))?;
+
+            let entry = KeymapEntry {
+                keystrokes: keystrokes.into(),
+                action,
+                context: context.map(Into::into),
+            };
+            entry
+                .to_key_binding()
+                .with_context(|| format!("invalid key binding `{}`", entry.keystrokes))?;
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+
+    /// Remove the user keymap, and rebuild the key bindings with the defaults.
+    pub fn clear_user_keymap(cx: &mut AppContext) {
+        cx.default_global::<Keymap>().user.clear();
+        Self::rebuild(cx);
+    }
+
+    /// Returns the default key bindings, bound by the [`bind_keys`].
+    pub(crate) fn defaults(&self) -> &[KeymapEntry] {
+        &self.defaults
+    }
+
+    /// Replace the key bindings of the shortcuts, and rebuild the key bindings.
+    pub(crate) fn set_shortcuts(entries: Vec<KeymapEntry>, cx: &mut AppContext) {
+        cx.default_global::<Keymap>().shortcuts = entries;
+        Self::rebuild(cx);
+    }
+
+    /// Rebuild all the key bindings of the app, the user keymap takes precedence over the defaults
+    /// and the shortcuts.
+    ///
+    /// NOTE: The key bindings not bound by the [`bind_keys`] will be removed.
+    pub fn rebuild(cx: &mut AppContext) {
+        let keymap = cx.default_global::<Keymap>();
+        let bindings = keymap
+            .defaults
+            .iter()
+            .chain(keymap.shortcuts.iter())
+            .chain(keymap.user.iter())
+            .filter_map(|entry| entry.to_key_binding().ok())
+            .collect::<Vec<_>>();
+
+        cx.clear_key_bindings();
+        cx.bind_keys(bindings);
+    }
+
+    /// Returns the current keystrokes bound to the action, e.g.: to show in the menus or tooltips.
+    ///
+    /// If the `context` is given, only the bindings in the context or without context are matched.
+    pub fn keystrokes_for_action(
+        action: &dyn Action,
+        context: Option<&str>,
+        cx: &AppContext,
+    ) -> Option<Vec<Keystroke>> {
+        let keymap = cx.try_global::<Keymap>()?;
+
+        keymap
+            .defaults
+            .iter()
+            .chain(keymap.shortcuts.iter())
+            .chain(keymap.user.iter())
+            .rev()
+            .find(|entry| {
+                entry.action.partial_eq(action)
+                    && (entry.context.is_none() || entry.context.as_deref() == context)
+            })
+            .and_then(|entry| {
+                entry
+                    .keystrokes
+                    .split_whitespace()
+                    .map(|keystroke| Keystroke::parse(keystroke).ok())
+                    .collect()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_keymap;
+
+    #[test]
+    fn test_parse_keymap() {
+        let entries = parse_keymap(
+            r#"[
+                { "context": "Input", "cmd-d": "input::Delete" },
+                { "ctrl-1": ["story::SelectFont", 18] }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0],
+            (
+                Some("Input".to_string()),
+                "cmd-d".to_string(),
+                "input::Delete".to_string(),
+                None
+            )
+        );
+        assert_eq!(
+            entries[1],
+            (
+                None,
+                "ctrl-1".to_string(),
+                "story::SelectFont".to_string(),
+                Some(serde_json::json!(18))
+            )
+        );
+
+        let entries = parse_keymap(r#"{ "context": "Input", "cmd-d": "input::Delete" }"#).unwrap();
+        assert_eq!(entries.len(), 1);
+
+        assert!(parse_keymap(r#"[{ "cmd-d": 1 }]"#).is_err());
+        assert!(parse_keymap("cmd-d").is_err());
+    }
+}
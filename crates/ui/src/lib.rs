@@ -1,5 +1,6 @@
 mod colors;
 mod event;
+mod focus_restore;
 mod focusable;
 mod icon;
 mod root;
@@ -8,53 +9,89 @@ mod svg_img;
 mod time;
 mod title_bar;
 
+pub mod about;
 pub mod accordion;
 pub mod animation;
+pub mod avatar;
 pub mod badge;
+pub mod binding;
 pub mod breadcrumb;
+pub mod breakpoints;
+pub mod bundle;
 pub mod button;
 pub mod button_group;
+pub mod carousel;
+pub mod cascader;
 pub mod checkbox;
+pub mod clamped_text;
 pub mod clipboard;
+pub mod coalesce;
+pub mod code_block;
 pub mod color_picker;
+pub mod compare_slider;
+pub mod connection;
 pub mod context_menu;
 pub mod divider;
 pub mod dock;
+pub mod draft;
 pub mod drawer;
+pub mod drop_target;
 pub mod dropdown;
+pub mod fetch;
+pub mod flow;
+pub mod format;
+pub mod help_marker;
 pub mod history;
+pub mod image;
 pub mod indicator;
 pub mod input;
+pub mod keymap;
 pub mod label;
 pub mod link;
 pub mod list;
+pub mod markdown;
+pub mod matcher;
+pub mod menu_bar;
 pub mod modal;
 pub mod notification;
 pub mod number_input;
+pub mod pattern;
 pub mod popover;
 pub mod popup_menu;
 pub mod prelude;
+pub mod primitives;
+pub mod profile;
 pub mod progress;
 pub mod radio;
+pub mod reorder;
 pub mod resizable;
 pub mod scroll;
+pub mod shortcuts;
 pub mod sidebar;
 pub mod skeleton;
 pub mod slider;
 pub mod switch;
 pub mod tab;
 pub mod table;
+pub mod tag;
 pub mod theme;
+pub mod toc;
 pub mod tooltip;
+pub mod undo_toast;
+pub mod unsaved;
 pub mod virtual_list;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 pub mod webview;
+pub mod window_state;
 
 // re-export
 pub use wry;
 
 pub use crate::Disableable;
 pub use event::InteractiveElementExt;
-pub use focusable::FocusableCycle;
+pub use focus_restore::{FocusRestore, OverlayKind};
+pub use focusable::{FocusableCycle, RovingFocus};
 pub use root::{ContextModal, Root};
 pub use styled::*;
 pub use time::*;
@@ -83,16 +120,21 @@ pub struct Assets;
 /// You can initialize the UI module at your application's entry point.
 pub fn init(cx: &mut gpui::AppContext) {
     theme::init(cx);
+    about::init(cx);
+    cascader::init(cx);
     date_picker::init(cx);
     dock::init(cx);
     drawer::init(cx);
     dropdown::init(cx);
+    flow::init(cx);
     input::init(cx);
     number_input::init(cx);
     list::init(cx);
-    modal::init(cx);
     popover::init(cx);
     popup_menu::init(cx);
+    profile::init(cx);
+    shortcuts::init(cx);
+    slider::init(cx);
     table::init(cx);
 }
 
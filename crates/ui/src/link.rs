@@ -3,7 +3,7 @@ use gpui::{
     RenderOnce, SharedString, Stateful, StatefulInteractiveElement, Styled,
 };
 
-use crate::theme::ActiveTheme as _;
+use crate::{theme::ActiveTheme as _, AutomationExt as _, AutomationId};
 
 /// A Link element like a `<a>` tag in HTML.
 #[derive(IntoElement)]
@@ -43,6 +43,13 @@ impl Link {
     }
 }
 
+impl AutomationId for Link {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.base = self.base.when_automation_id(Some(&id.into()));
+        self
+    }
+}
+
 impl Styled for Link {
     fn style(&mut self) -> &mut gpui::StyleRefinement {
         self.base.style()
@@ -1,32 +1,56 @@
 use std::time::Duration;
-use std::{cell::Cell, rc::Rc};
+use std::{cell::Cell, ops::Range, rc::Rc};
 
-use crate::Icon;
 use crate::{
+    h_flex,
+    indicator::Indicator,
     input::{InputEvent, TextInput},
-    scroll::{Scrollbar, ScrollbarState},
+    keymap::{self, KeymapEntry},
+    reorder::{self, DragReorder},
+    scroll::{
+        Overscroll, OverscrollBounce, OverscrollState, ScrollEdges, Scrollbar, ScrollbarAxis,
+        ScrollbarState,
+    },
     theme::ActiveTheme,
     v_flex, IconName, Size,
 };
+use crate::{Icon, Sizable as _};
 use gpui::{
-    actions, div, prelude::FluentBuilder, uniform_list, AnyElement, AppContext, Entity,
-    FocusHandle, FocusableView, InteractiveElement, IntoElement, KeyBinding, Length,
-    ListSizingBehavior, MouseButton, ParentElement, Render, SharedString, Styled, Task,
-    UniformListScrollHandle, View, ViewContext, VisualContext, WindowContext,
+    actions, div, prelude::FluentBuilder, uniform_list, AnyElement, AppContext, Div, DragMoveEvent,
+    Entity, EventEmitter, FocusHandle, FocusableView, InteractiveElement, IntoElement, Length,
+    ListSizingBehavior, MouseButton, ParentElement, Pixels, Render, SharedString,
+    StatefulInteractiveElement, Styled, Task, UniformListScrollHandle, View, ViewContext,
+    VisualContext, WindowContext,
 };
 use gpui::{px, ScrollStrategy};
 use smol::Timer;
 
 actions!(list, [Cancel, Confirm, SelectPrev, SelectNext]);
 
+/// The interval to auto scroll the list while dragging an item near the edge.
+const AUTO_SCROLL_INTERVAL: Duration = Duration::from_millis(16);
+
 pub fn init(cx: &mut AppContext) {
     let context: Option<&str> = Some("List");
-    cx.bind_keys([
-        KeyBinding::new("escape", Cancel, context),
-        KeyBinding::new("enter", Confirm, context),
-        KeyBinding::new("up", SelectPrev, context),
-        KeyBinding::new("down", SelectNext, context),
-    ]);
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("escape", Cancel, context),
+            KeymapEntry::new("enter", Confirm, context),
+            KeymapEntry::new("up", SelectPrev, context),
+            KeymapEntry::new("down", SelectNext, context),
+        ],
+    );
+}
+
+/// Returns true if the visible rows are near the end, in the `threshold` rows.
+fn is_near_end(visible_end: usize, rows_count: usize, threshold: usize) -> bool {
+    visible_end + threshold >= rows_count
+}
+
+/// Returns true if the visible rows are near the start, in the `threshold` rows.
+fn is_near_start(visible_start: usize, threshold: usize) -> bool {
+    visible_start < threshold
 }
 
 /// A delegate for the List.
@@ -43,6 +67,30 @@ pub trait ListDelegate: Sized + 'static {
     /// Return the number of items in the list.
     fn items_count(&self, cx: &AppContext) -> usize;
 
+    /// Return the number of sections in the list, default is 0 means no sections.
+    ///
+    /// The items are flattened in the order of the sections, so the item index is the same as
+    /// the list without sections, and the keyboard navigation will skip the section headers.
+    fn sections_count(&self, cx: &AppContext) -> usize {
+        0
+    }
+
+    /// Return the number of items in the section, the empty sections will not be rendered.
+    fn section_items_count(&self, section: usize, cx: &AppContext) -> usize {
+        0
+    }
+
+    /// Render the header of the section, the header will stick to the top while scrolling the section.
+    ///
+    /// The header must have the same height as the items.
+    fn render_section_header(
+        &self,
+        section: usize,
+        cx: &mut ViewContext<List<Self>>,
+    ) -> Option<impl IntoElement> {
+        None::<Div>
+    }
+
     /// Render the item at the given index.
     ///
     /// Return None will skip the item.
@@ -77,6 +125,72 @@ pub trait ListDelegate: Sized + 'static {
 
     /// Cancel the selection, e.g.: Pressed ESC.
     fn cancel(&mut self, cx: &mut ViewContext<List<Self>>) {}
+
+    /// Return true to show a drag handle on the item to reorder it, default is false.
+    fn can_move_item(&self, ix: usize, cx: &AppContext) -> bool {
+        false
+    }
+
+    /// Return the label of the item to show in the drag preview.
+    fn item_label(&self, ix: usize, cx: &AppContext) -> Option<SharedString> {
+        None
+    }
+
+    /// Move the item at the given `from` index to the `to` index,
+    /// the `to` is the index of the item after moving.
+    fn move_item(&mut self, from: usize, to: usize, cx: &mut ViewContext<List<Self>>) {}
+
+    /// Return true to load more items when scrolling to the bottom, e.g.: there are more pages.
+    ///
+    /// Default: false
+    fn can_load_more(&self, cx: &AppContext) -> bool {
+        false
+    }
+
+    /// Return true to load the previous items when scrolling to the top, e.g.: the older messages of a chat.
+    ///
+    /// Default: false
+    fn can_load_prev(&self, cx: &AppContext) -> bool {
+        false
+    }
+
+    /// Returns a threshold value (n rows), when scrolling to the bottom (or top),
+    /// the remaining number of rows triggers `load_more` (or `load_prev`).
+    ///
+    /// Default: 20 rows
+    fn load_more_threshold(&self) -> usize {
+        20
+    }
+
+    /// Load more items and append them to the end of the list.
+    ///
+    /// The loading indicator row is shown at the bottom until the task is finished,
+    /// and this is not called again in the meantime.
+    fn load_more(&mut self, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        Task::ready(())
+    }
+
+    /// Load the previous items and insert them to the start of the list.
+    ///
+    /// The loading indicator row is shown at the top until the task is finished, then the scroll
+    /// position is adjusted to keep the visible items still.
+    fn load_prev(&mut self, cx: &mut ViewContext<List<Self>>) -> Task<()> {
+        Task::ready(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListEvent {
+    /// The item has been moved by dragging, the `to` is the index of the item after moving.
+    Moved { from: usize, to: usize },
+}
+
+/// The row of the list, a section header, an item or the loading indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListRow {
+    Section(usize),
+    Item(usize),
+    Loading,
 }
 
 pub struct List<D: ListDelegate> {
@@ -90,11 +204,25 @@ pub struct List<D: ListDelegate> {
     enable_scrollbar: bool,
     vertical_scroll_handle: UniformListScrollHandle,
     scrollbar_state: Rc<Cell<ScrollbarState>>,
+    overscroll: Overscroll,
+    edge_fades: bool,
+    overscroll_state: Rc<Cell<OverscrollState>>,
 
     pub(crate) size: Size,
     selected_index: Option<usize>,
     right_clicked_index: Option<usize>,
+    rows: Vec<ListRow>,
+    /// The insertion position of the dragging item.
+    drop_ix: Option<usize>,
+    auto_scroll_delta: Pixels,
+    loading_more: bool,
+    loading_prev: bool,
+    /// The scroll offset of the last render, to know the scrolling direction.
+    last_scroll_y: Pixels,
     _search_task: Task<()>,
+    _drag_task: Option<Task<()>>,
+    _load_more_task: Task<()>,
+    _load_prev_task: Task<()>,
 }
 
 impl<D> List<D>
@@ -120,13 +248,25 @@ where
             last_query: None,
             selected_index: None,
             right_clicked_index: None,
+            rows: Vec::new(),
             vertical_scroll_handle: UniformListScrollHandle::new(),
             scrollbar_state: Rc::new(Cell::new(ScrollbarState::new())),
+            overscroll: Overscroll::None,
+            edge_fades: false,
+            overscroll_state: Rc::new(Cell::new(OverscrollState::default())),
             max_height: None,
             enable_scrollbar: true,
             loading: false,
             size: Size::default(),
+            drop_ix: None,
+            auto_scroll_delta: px(0.),
+            loading_more: false,
+            loading_prev: false,
+            last_scroll_y: px(0.),
             _search_task: Task::ready(()),
+            _drag_task: None,
+            _load_more_task: Task::ready(()),
+            _load_prev_task: Task::ready(()),
         }
     }
 
@@ -150,6 +290,18 @@ where
         self
     }
 
+    /// Set the feedback when scrolling beyond the edge, default is [`Overscroll::None`].
+    pub fn overscroll(mut self, overscroll: Overscroll) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    /// Set to show the fade shadows at the edges that have more items, default is false.
+    pub fn edge_fades(mut self, edge_fades: bool) -> Self {
+        self.edge_fades = edge_fades;
+        self
+    }
+
     pub fn no_query(mut self) -> Self {
         self.query_input = None;
         self
@@ -207,13 +359,196 @@ where
         ))
     }
 
+    fn render_scroll_edges(&self, cx: &mut ViewContext<Self>) -> Option<impl IntoElement> {
+        if !self.edge_fades && self.overscroll == Overscroll::None {
+            return None;
+        }
+
+        let scroll_size = self
+            .vertical_scroll_handle
+            .0
+            .borrow()
+            .last_item_size
+            .map(|size| size.contents)
+            .unwrap_or_default();
+
+        Some(
+            ScrollEdges::new(
+                cx.view().entity_id(),
+                self.overscroll_state.clone(),
+                ScrollbarAxis::Vertical,
+                self.vertical_scroll_handle.clone(),
+                scroll_size,
+            )
+            .overscroll(self.overscroll)
+            .fade(self.edge_fades),
+        )
+    }
+
     fn scroll_to_selected_item(&mut self, _cx: &mut ViewContext<Self>) {
         if let Some(ix) = self.selected_index {
+            let row_ix = self
+                .rows
+                .iter()
+                .position(|row| *row == ListRow::Item(ix))
+                .unwrap_or(ix);
             self.vertical_scroll_handle
-                .scroll_to_item(ix, ScrollStrategy::Top);
+                .scroll_to_item(row_ix, ScrollStrategy::Top);
+        }
+    }
+
+    /// Flatten the sections and items into rows.
+    fn prepare_rows(&mut self, cx: &AppContext) {
+        self.rows.clear();
+        if self.loading_prev {
+            self.rows.push(ListRow::Loading);
+        }
+
+        self.push_item_rows(cx);
+
+        if self.loading_more {
+            self.rows.push(ListRow::Loading);
+        }
+    }
+
+    fn push_item_rows(&mut self, cx: &AppContext) {
+        let sections_count = self.delegate.sections_count(cx);
+        if sections_count == 0 {
+            self.rows
+                .extend((0..self.delegate.items_count(cx)).map(ListRow::Item));
+            return;
+        }
+
+        let mut ix = 0;
+        for section in 0..sections_count {
+            let count = self.delegate.section_items_count(section, cx);
+            if count == 0 {
+                continue;
+            }
+
+            self.rows.push(ListRow::Section(section));
+            self.rows.extend((ix..ix + count).map(ListRow::Item));
+            ix += count;
+        }
+    }
+
+    /// Return the section of the first visible row, if the list has been scrolled.
+    fn sticky_section(&self) -> Option<usize> {
+        let state = self.vertical_scroll_handle.0.borrow();
+        let row_height = state.last_item_size?.item.height;
+        let offset_y = -state.base_handle.offset().y;
+        if row_height <= px(0.) || offset_y <= px(0.) || self.rows.is_empty() {
+            return None;
+        }
+
+        let first_row = ((offset_y / row_height).floor() as usize).min(self.rows.len() - 1);
+        self.rows[..=first_row]
+            .iter()
+            .rev()
+            .find_map(|row| match row {
+                ListRow::Section(section) => Some(*section),
+                ListRow::Item(_) | ListRow::Loading => None,
+            })
+    }
+
+    /// Start to load more or the previous items if the visible rows are near the edges,
+    /// this is called in the render, so it defers the loading after the render.
+    fn load_if_needed(&mut self, visible_range: Range<usize>, cx: &mut ViewContext<Self>) {
+        let offset_y = self
+            .vertical_scroll_handle
+            .0
+            .borrow()
+            .base_handle
+            .offset()
+            .y;
+        // The offset is increased when scrolling up.
+        let scrolling_up = offset_y > self.last_scroll_y;
+        self.last_scroll_y = offset_y;
+
+        let threshold = self.delegate.load_more_threshold();
+        let load_more = !self.loading_more
+            && self.delegate.can_load_more(cx)
+            && is_near_end(visible_range.end, self.rows.len(), threshold);
+        // Only load the previous items when the user scrolls up, to avoid loading on the first render.
+        let load_prev = !self.loading_prev
+            && scrolling_up
+            && self.delegate.can_load_prev(cx)
+            && is_near_start(visible_range.start, threshold);
+
+        if load_more {
+            self.loading_more = true;
+            cx.defer(|this, cx| this.load_more(cx));
+        }
+        if load_prev {
+            self.loading_prev = true;
+            cx.defer(|this, cx| this.load_prev(cx));
         }
     }
 
+    fn load_more(&mut self, cx: &mut ViewContext<Self>) {
+        let task = self.delegate.load_more(cx);
+        cx.notify();
+
+        self._load_more_task = cx.spawn(|this, mut cx| async move {
+            task.await;
+            let _ = this.update(&mut cx, |this, cx| {
+                this.loading_more = false;
+                cx.notify();
+            });
+        });
+    }
+
+    fn load_prev(&mut self, cx: &mut ViewContext<Self>) {
+        let items_count = self.delegate.items_count(cx);
+        let task = self.delegate.load_prev(cx);
+        cx.notify();
+
+        self._load_prev_task = cx.spawn(|this, mut cx| async move {
+            task.await;
+            let _ = this.update(&mut cx, |this, cx| {
+                // Keep the visible items still, the inserted rows (and the removed loading row)
+                // shift the rows below them.
+                let rows_count = this.rows.len();
+                this.loading_prev = false;
+                this.prepare_rows(cx);
+                let inserted_rows = this.rows.len() as f32 - rows_count as f32;
+                let inserted_items = this.delegate.items_count(cx).saturating_sub(items_count);
+
+                let state = this.vertical_scroll_handle.0.borrow();
+                if let Some(row_height) = state.last_item_size.map(|size| size.item.height) {
+                    let mut offset = state.base_handle.offset();
+                    offset.y -= row_height * inserted_rows;
+                    state.base_handle.set_offset(offset);
+                    this.last_scroll_y = offset.y;
+                }
+                drop(state);
+
+                this.selected_index = this.selected_index.map(|ix| ix + inserted_items);
+                cx.notify();
+            });
+        });
+    }
+
+    fn render_loading_row(&self, cx: &mut ViewContext<Self>) -> Div {
+        h_flex()
+            .w_full()
+            .h(self
+                .vertical_scroll_handle
+                .0
+                .borrow()
+                .last_item_size
+                .map_or(px(32.), |size| size.item.height))
+            .justify_center()
+            .child(Indicator::new().small().color(cx.theme().muted_foreground))
+    }
+
+    fn render_section_header(&self, section: usize, cx: &mut ViewContext<Self>) -> Div {
+        div()
+            .w_full()
+            .bg(cx.theme().background)
+            .children(self.delegate.render_section_header(section, cx))
+    }
+
     fn on_query_input_event(
         &mut self,
         _: View<TextInput>,
@@ -311,12 +646,179 @@ where
         cx.notify();
     }
 
+    /// Start the task to auto scroll while dragging, the drag state will be cleared when the drag ends.
+    fn ensure_drag_task(&mut self, cx: &mut ViewContext<Self>) {
+        if self._drag_task.is_some() {
+            return;
+        }
+
+        self._drag_task = Some(cx.spawn(|this, mut cx| async move {
+            loop {
+                Timer::after(AUTO_SCROLL_INTERVAL).await;
+                let dragging = this.update(&mut cx, |this, cx| {
+                    if !cx.has_active_drag() {
+                        this.drop_ix = None;
+                        this.auto_scroll_delta = px(0.);
+                        this._drag_task = None;
+                        cx.notify();
+                        return false;
+                    }
+
+                    if this.auto_scroll_delta != px(0.) {
+                        let state = this.vertical_scroll_handle.0.borrow();
+                        let viewport_height = state.base_handle.bounds().size.height;
+                        let max_y = state
+                            .last_item_size
+                            .map(|size| (size.contents.height - viewport_height).max(px(0.)))
+                            .unwrap_or_default();
+                        let mut offset = state.base_handle.offset();
+                        offset.y = (offset.y + this.auto_scroll_delta).clamp(-max_y, px(0.));
+                        state.base_handle.set_offset(offset);
+                        drop(state);
+                        cx.notify();
+                    }
+                    true
+                });
+
+                if !matches!(dragging, Ok(true)) {
+                    break;
+                }
+            }
+        }));
+    }
+
+    fn on_item_drag_move(
+        &mut self,
+        ix: usize,
+        event: &DragMoveEvent<DragReorder>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        if event.drag(cx).entity_id != cx.entity_id() {
+            return;
+        }
+        if !event.bounds.contains(&event.event.position) {
+            return;
+        }
+
+        let drop_ix = reorder::insertion_index(ix, event.bounds, event.event.position);
+        if self.drop_ix != Some(drop_ix) {
+            self.drop_ix = Some(drop_ix);
+            cx.notify();
+        }
+    }
+
+    fn on_item_drop(&mut self, drag: &DragReorder, cx: &mut ViewContext<Self>) {
+        if drag.entity_id != cx.entity_id() {
+            return;
+        }
+
+        let drop_ix = self.drop_ix.take();
+        self.auto_scroll_delta = px(0.);
+        cx.notify();
+
+        let Some(to) = drop_ix.and_then(|drop_ix| reorder::target_index(drag.ix, drop_ix)) else {
+            return;
+        };
+        self.move_item(drag.ix, to, cx);
+    }
+
+    /// Move the item at the given `from` index to the `to` index, and keep the selection on the same item.
+    pub fn move_item(&mut self, from: usize, to: usize, cx: &mut ViewContext<Self>) {
+        let items_count = self.delegate.items_count(cx);
+        if from == to || from >= items_count || to >= items_count {
+            return;
+        }
+
+        self.delegate.move_item(from, to, cx);
+        self.selected_index = self
+            .selected_index
+            .map(|ix| reorder::moved_index(ix, from, to));
+        self.right_clicked_index = None;
+
+        cx.emit(ListEvent::Moved { from, to });
+        cx.notify();
+    }
+
+    fn render_drag_handle(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let drag = DragReorder::new(cx.entity_id(), ix);
+        let drag = match self.delegate.item_label(ix, cx) {
+            Some(label) => drag.label(label),
+            None => drag,
+        };
+
+        h_flex()
+            .id(("list-item-drag-handle", ix))
+            .flex_shrink_0()
+            .h_full()
+            .px_1()
+            .cursor_grab()
+            .child(
+                Icon::new(IconName::GripVertical)
+                    .small()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            // Avoid to confirm the item when start dragging.
+            .on_mouse_down(MouseButton::Left, |_, cx| cx.stop_propagation())
+            .on_drag(drag, |drag, _, cx| {
+                cx.stop_propagation();
+                cx.new_view(|_| drag.clone())
+            })
+    }
+
     fn render_list_item(&mut self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let movable = self.delegate.can_move_item(ix, cx);
+        let is_last = ix + 1 == self.delegate.items_count(cx);
+
         div()
             .id("list-item")
             .w_full()
             .relative()
-            .children(self.delegate.render_item(ix, cx))
+            .map(|this| {
+                if movable {
+                    this.child(
+                        h_flex()
+                            .w_full()
+                            .child(self.render_drag_handle(ix, cx))
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .overflow_hidden()
+                                    .children(self.delegate.render_item(ix, cx)),
+                            ),
+                    )
+                } else {
+                    this.children(self.delegate.render_item(ix, cx))
+                }
+            })
+            .on_drag_move(
+                cx.listener(move |this, e: &DragMoveEvent<DragReorder>, cx| {
+                    this.on_item_drag_move(ix, e, cx)
+                }),
+            )
+            .on_drop(cx.listener(|this, drag: &DragReorder, cx| this.on_item_drop(drag, cx)))
+            // The drop indicator
+            .when(self.drop_ix == Some(ix), |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .right_0()
+                        .h(px(2.))
+                        .bg(cx.theme().drag_border),
+                )
+            })
+            .when(is_last && self.drop_ix == Some(ix + 1), |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .bottom_0()
+                        .left_0()
+                        .right_0()
+                        .h(px(2.))
+                        .bg(cx.theme().drag_border),
+                )
+            })
             .when_some(self.selected_index, |this, selected_index| {
                 this.when(ix == selected_index, |this| {
                     this.child(
@@ -362,6 +864,8 @@ where
     }
 }
 
+impl<D> EventEmitter<ListEvent> for List<D> where D: ListDelegate {}
+
 impl<D> FocusableView for List<D>
 where
     D: ListDelegate,
@@ -383,6 +887,9 @@ where
         let view = cx.view().clone();
         let vertical_scroll_handle = self.vertical_scroll_handle.clone();
         let items_count = self.delegate.items_count(cx);
+        self.prepare_rows(cx);
+        let rows_count = self.rows.len();
+        let sticky_section = self.sticky_section();
         let sizing_behavior = if self.max_height.is_some() {
             ListSizingBehavior::Infer
         } else {
@@ -428,6 +935,7 @@ where
                 } else {
                     this.child(
                         v_flex()
+                            .id("list-body")
                             .flex_grow()
                             .relative()
                             .when_some(self.max_height, |this, h| this.max_h(h))
@@ -437,20 +945,61 @@ where
                             })
                             .when(items_count > 0, |this| {
                                 this.child(
-                                    uniform_list(view, "uniform-list", items_count, {
+                                    uniform_list(view, "uniform-list", rows_count, {
                                         move |list, visible_range, cx| {
+                                            list.load_if_needed(visible_range.clone(), cx);
+
                                             visible_range
-                                                .map(|ix| list.render_list_item(ix, cx))
+                                                .map(|row_ix| match list.rows[row_ix] {
+                                                    ListRow::Section(section) => list
+                                                        .render_section_header(section, cx)
+                                                        .into_any_element(),
+                                                    ListRow::Item(ix) => list
+                                                        .render_list_item(ix, cx)
+                                                        .into_any_element(),
+                                                    ListRow::Loading => list
+                                                        .render_loading_row(cx)
+                                                        .into_any_element(),
+                                                })
                                                 .collect::<Vec<_>>()
                                         }
                                     })
                                     .flex_grow()
                                     .with_sizing_behavior(sizing_behavior)
                                     .track_scroll(vertical_scroll_handle)
-                                    .into_any_element(),
+                                    .map(|list| {
+                                        match self.overscroll {
+                                            Overscroll::Bounce => OverscrollBounce::new(
+                                                self.overscroll_state.clone(),
+                                                list,
+                                            )
+                                            .into_any_element(),
+                                            _ => list.into_any_element(),
+                                        }
+                                    }),
+                                )
+                            })
+                            .when_some(sticky_section, |this, section| {
+                                this.child(
+                                    self.render_section_header(section, cx)
+                                        .absolute()
+                                        .top_0()
+                                        .left_0(),
                                 )
                             })
-                            .children(self.render_scrollbar(cx)),
+                            .children(self.render_scroll_edges(cx))
+                            .children(self.render_scrollbar(cx))
+                            .on_drag_move(cx.listener(
+                                |this, e: &DragMoveEvent<DragReorder>, cx| {
+                                    if e.drag(cx).entity_id != cx.entity_id() {
+                                        return;
+                                    }
+
+                                    this.auto_scroll_delta =
+                                        reorder::auto_scroll_delta(e.bounds, e.event.position);
+                                    this.ensure_drag_task(cx);
+                                },
+                            )),
                     )
                 }
             })
@@ -463,3 +1012,24 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_near_end, is_near_start};
+
+    #[test]
+    fn test_is_near_end() {
+        assert!(!is_near_end(10, 100, 20));
+        assert!(is_near_end(80, 100, 20));
+        assert!(is_near_end(100, 100, 20));
+        assert!(is_near_end(5, 10, 20));
+    }
+
+    #[test]
+    fn test_is_near_start() {
+        assert!(is_near_start(0, 20));
+        assert!(is_near_start(19, 20));
+        assert!(!is_near_start(20, 20));
+        assert!(!is_near_start(0, 0));
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{h_flex, theme::ActiveTheme, Disableable, Icon, IconName, Selectable, Sizable as _};
+use crate::{
+    h_flex, theme::ActiveTheme, AutomationExt as _, AutomationId, Disableable, Icon, IconName,
+    Selectable, Sizable as _,
+};
 use gpui::{
     div, prelude::FluentBuilder as _, AnyElement, ClickEvent, Div, ElementId, InteractiveElement,
-    IntoElement, MouseButton, MouseMoveEvent, ParentElement, RenderOnce, Stateful,
+    IntoElement, MouseButton, MouseMoveEvent, ParentElement, RenderOnce, SharedString, Stateful,
     StatefulInteractiveElement as _, Styled, WindowContext,
 };
 use smallvec::SmallVec;
@@ -84,6 +87,13 @@ impl ListItem {
     }
 }
 
+impl AutomationId for ListItem {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.base = self.base.when_automation_id(Some(&id.into()));
+        self
+    }
+}
+
 impl Disableable for ListItem {
     fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
@@ -135,7 +145,7 @@ impl RenderOnce for ListItem {
                 }
             })
             .when(is_active, |this| this.bg(cx.theme().list_active))
-            .when(!is_active && !self.disabled, |this| {
+            .when(!is_active && !self.disabled && !cx.theme().lite, |this| {
                 this.hover(|this| this.bg(cx.theme().list_hover))
             })
             // Mouse enter
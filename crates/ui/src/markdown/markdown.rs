@@ -0,0 +1,280 @@
+use std::ops::Range;
+
+use gpui::{
+    div, img, prelude::FluentBuilder as _, px, AnyElement, Div, ElementId, FontStyle, FontWeight,
+    HighlightStyle, InteractiveElement as _, InteractiveText, IntoElement, ParentElement,
+    RenderOnce, SharedString, StatefulInteractiveElement as _, StrikethroughStyle, Styled,
+    StyledText, UnderlineStyle, WindowContext,
+};
+
+use super::parser::{parse_markdown, Block, Inline, TextSpan};
+use crate::{
+    code_block::{CodeBlock, JsonHighlighter},
+    divider::Divider,
+    h_flex,
+    theme::ActiveTheme,
+    tooltip::Tooltip,
+    v_flex, SvgImg,
+};
+
+/// The size to rasterize the SVG images from the assets, the SVG has no intrinsic pixel size.
+const SVG_IMAGE_SIZE: f32 = 320.;
+
+/// Render the CommonMark text, e.g.: the release notes and the help panels.
+///
+/// Supports the headings, emphasis, lists, tables, links, code blocks and images,
+/// the links are opened by the system browser when clicked.
+///
+/// ```ignore
+/// Markdown::new("release-notes", "# v0.2.0\n\n- Add **Markdown** element.")
+/// ```
+#[derive(IntoElement)]
+pub struct Markdown {
+    id: ElementId,
+    base: Div,
+    source: SharedString,
+}
+
+impl Markdown {
+    pub fn new(id: impl Into<ElementId>, source: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            base: v_flex(),
+            source: source.into(),
+        }
+    }
+}
+
+impl Styled for Markdown {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Markdown {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let blocks = parse_markdown(&self.source);
+        let mut next_id = 0;
+
+        self.base.id(self.id).gap_3().children(
+            blocks
+                .iter()
+                .map(|block| render_block(block, &mut next_id, cx)),
+        )
+    }
+}
+
+fn render_block(block: &Block, next_id: &mut usize, cx: &mut WindowContext) -> AnyElement {
+    match block {
+        Block::Heading { level, inlines } => div()
+            .font_weight(FontWeight::SEMIBOLD)
+            .map(|this| match level {
+                1 => this.text_2xl(),
+                2 => this.text_xl(),
+                3 => this.text_lg(),
+                _ => this.text_base(),
+            })
+            .child(render_inlines(inlines, next_id, cx))
+            .into_any_element(),
+        Block::Paragraph(inlines) => render_inlines(inlines, next_id, cx),
+        Block::List { start, items } => v_flex()
+            .gap_1()
+            .children(items.iter().enumerate().map(|(ix, item)| {
+                let marker = match start {
+                    Some(start) => format!("{}.", start + ix as u64),
+                    None => "•".to_string(),
+                };
+
+                h_flex()
+                    .items_start()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_shrink_0()
+                            .min_w_4()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(marker),
+                    )
+                    .child(
+                        v_flex().flex_1().gap_1().children(
+                            item.iter()
+                                .map(|block| render_block(block, next_id, cx))
+                                .collect::<Vec<_>>(),
+                        ),
+                    )
+            }))
+            .into_any_element(),
+        Block::Code { lang, code } => {
+            *next_id += 1;
+            CodeBlock::new(("markdown-code", *next_id), code.clone())
+                .line_numbers(false)
+                .when(lang.as_deref() == Some("json"), |this| {
+                    this.highlighter(JsonHighlighter)
+                })
+                .w_full()
+                .into_any_element()
+        }
+        Block::Table { head, rows } => v_flex()
+            .w_full()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded(px(cx.theme().radius))
+            .overflow_hidden()
+            .child(
+                render_table_row(head, next_id, cx)
+                    .font_weight(FontWeight::SEMIBOLD)
+                    .bg(cx.theme().table_head),
+            )
+            .children(
+                rows.iter()
+                    .map(|row| render_table_row(row, next_id, cx).border_t_1())
+                    .collect::<Vec<_>>(),
+            )
+            .into_any_element(),
+        Block::Quote(blocks) => v_flex()
+            .gap_2()
+            .pl_3()
+            .border_l_4()
+            .border_color(cx.theme().border)
+            .text_color(cx.theme().muted_foreground)
+            .children(
+                blocks
+                    .iter()
+                    .map(|block| render_block(block, next_id, cx))
+                    .collect::<Vec<_>>(),
+            )
+            .into_any_element(),
+        Block::Rule => Divider::horizontal().into_any_element(),
+    }
+}
+
+fn render_table_row(cells: &[Vec<Inline>], next_id: &mut usize, cx: &mut WindowContext) -> Div {
+    let border_color = cx.theme().border;
+
+    h_flex().w_full().border_color(border_color).children(
+        cells
+            .iter()
+            .enumerate()
+            .map(|(ix, inlines)| {
+                div()
+                    .flex_1()
+                    .px_2()
+                    .py_1()
+                    .when(ix > 0, |this| this.border_l_1().border_color(border_color))
+                    .child(render_inlines(inlines, next_id, cx))
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Render the inlines as the styled text, the images are rendered between the text.
+fn render_inlines(inlines: &[Inline], next_id: &mut usize, cx: &mut WindowContext) -> AnyElement {
+    let mut children: Vec<AnyElement> = vec![];
+    let mut spans: Vec<&TextSpan> = vec![];
+
+    for inline in inlines {
+        match inline {
+            Inline::Text(span) => spans.push(span),
+            Inline::Image { url, alt } => {
+                if !spans.is_empty() {
+                    children.push(render_text(&spans, next_id, cx));
+                    spans.clear();
+                }
+                children.push(render_image(url, alt, next_id));
+            }
+        }
+    }
+    if !spans.is_empty() {
+        children.push(render_text(&spans, next_id, cx));
+    }
+
+    if children.len() == 1 {
+        children.remove(0)
+    } else {
+        v_flex().gap_2().children(children).into_any_element()
+    }
+}
+
+fn render_text(spans: &[&TextSpan], next_id: &mut usize, cx: &mut WindowContext) -> AnyElement {
+    let mut text = String::new();
+    let mut highlights: Vec<(Range<usize>, HighlightStyle)> = vec![];
+    let mut links: Vec<(Range<usize>, String)> = vec![];
+
+    for span in spans {
+        let range = text.len()..text.len() + span.text.len();
+        text.push_str(&span.text);
+
+        let mut highlight = HighlightStyle::default();
+        if span.style.bold {
+            highlight.font_weight = Some(FontWeight::BOLD);
+        }
+        if span.style.italic {
+            highlight.font_style = Some(FontStyle::Italic);
+        }
+        if span.style.strikethrough {
+            highlight.strikethrough = Some(StrikethroughStyle {
+                thickness: px(1.),
+                color: None,
+            });
+        }
+        if span.style.code {
+            highlight.background_color = Some(cx.theme().secondary);
+        }
+        if let Some(link) = &span.link {
+            highlight.color = Some(cx.theme().link);
+            highlight.underline = Some(UnderlineStyle {
+                thickness: px(1.),
+                color: Some(cx.theme().link),
+                wavy: false,
+            });
+            links.push((range.clone(), link.clone()));
+        }
+
+        if highlight != HighlightStyle::default() {
+            highlights.push((range, highlight));
+        }
+    }
+
+    let text = StyledText::new(text).with_highlights(&cx.text_style(), highlights);
+    if links.is_empty() {
+        return div().child(text).into_any_element();
+    }
+
+    *next_id += 1;
+    let (ranges, urls): (Vec<_>, Vec<_>) = links.into_iter().unzip();
+    div()
+        .child(
+            InteractiveText::new(("markdown-text", *next_id), text).on_click(
+                ranges,
+                move |ix, cx| {
+                    cx.open_url(&urls[ix]);
+                },
+            ),
+        )
+        .into_any_element()
+}
+
+fn render_image(url: &str, alt: &str, next_id: &mut usize) -> AnyElement {
+    let is_remote = url.contains("://");
+    if !is_remote && url.ends_with(".svg") {
+        return SvgImg::new()
+            .source(
+                SharedString::from(url.to_string()),
+                px(SVG_IMAGE_SIZE),
+                px(SVG_IMAGE_SIZE),
+            )
+            .size(px(SVG_IMAGE_SIZE))
+            .into_any_element();
+    }
+
+    *next_id += 1;
+    div()
+        .id(("markdown-image", *next_id))
+        .max_w_full()
+        .child(img(SharedString::from(url.to_string())).max_w_full())
+        .when(!alt.is_empty(), |this| {
+            let alt = SharedString::from(alt.to_string());
+            this.tooltip(move |cx| Tooltip::new(alt.clone(), cx))
+        })
+        .into_any_element()
+}
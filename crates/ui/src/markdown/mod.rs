@@ -0,0 +1,4 @@
+mod markdown;
+mod parser;
+
+pub use markdown::*;
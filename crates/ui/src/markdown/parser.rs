@@ -0,0 +1,393 @@
+use std::iter::Peekable;
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+
+/// The style of a [`TextSpan`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SpanStyle {
+    pub(crate) bold: bool,
+    pub(crate) italic: bool,
+    pub(crate) strikethrough: bool,
+    pub(crate) code: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TextSpan {
+    pub(crate) text: String,
+    pub(crate) style: SpanStyle,
+    pub(crate) link: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Inline {
+    Text(TextSpan),
+    Image { url: String, alt: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Block {
+    Heading {
+        level: u8,
+        inlines: Vec<Inline>,
+    },
+    Paragraph(Vec<Inline>),
+    /// The `start` is the number of the first item for the ordered list.
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Code {
+        lang: Option<String>,
+        code: String,
+    },
+    Table {
+        head: Vec<Vec<Inline>>,
+        rows: Vec<Vec<Vec<Inline>>>,
+    },
+    Quote(Vec<Block>),
+    Rule,
+}
+
+/// Parse the CommonMark (with the tables and the strikethrough extensions) into the blocks.
+pub(crate) fn parse_markdown(source: &str) -> Vec<Block> {
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH;
+    let mut events = Parser::new_ext(source, options).peekable();
+    parse_blocks(&mut events, None)
+}
+
+fn is_inline(event: &Event) -> bool {
+    match event {
+        Event::Text(_)
+        | Event::Code(_)
+        | Event::SoftBreak
+        | Event::HardBreak
+        | Event::InlineHtml(_)
+        | Event::InlineMath(_)
+        | Event::FootnoteReference(_)
+        | Event::TaskListMarker(_) => true,
+        Event::Start(tag) => matches!(
+            tag,
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link { .. } | Tag::Image { .. }
+        ),
+        Event::End(tag) => matches!(
+            tag,
+            TagEnd::Emphasis
+                | TagEnd::Strong
+                | TagEnd::Strikethrough
+                | TagEnd::Link
+                | TagEnd::Image
+        ),
+        _ => false,
+    }
+}
+
+/// Parse the blocks until the `end` tag, or the end of the events if `end` is None.
+fn parse_blocks<'a>(
+    events: &mut Peekable<impl Iterator<Item = Event<'a>>>,
+    end: Option<TagEnd>,
+) -> Vec<Block> {
+    let mut blocks = vec![];
+    loop {
+        // The items of the tight list have the inline content without paragraph.
+        if events.peek().map_or(false, is_inline) {
+            blocks.push(Block::Paragraph(parse_inlines(events, None)));
+            continue;
+        }
+
+        let Some(event) = events.next() else {
+            break;
+        };
+
+        match event {
+            Event::End(tag) if Some(tag) == end => break,
+            Event::Start(Tag::Paragraph) => {
+                blocks.push(Block::Paragraph(parse_inlines(
+                    events,
+                    Some(TagEnd::Paragraph),
+                )));
+            }
+            Event::Start(Tag::Heading { level, .. }) => {
+                blocks.push(Block::Heading {
+                    level: level as u8,
+                    inlines: parse_inlines(events, Some(TagEnd::Heading(level))),
+                });
+            }
+            Event::Start(Tag::BlockQuote(kind)) => {
+                blocks.push(Block::Quote(parse_blocks(
+                    events,
+                    Some(TagEnd::BlockQuote(kind)),
+                )));
+            }
+            Event::Start(Tag::CodeBlock(kind)) => {
+                let lang = match kind {
+                    CodeBlockKind::Fenced(lang) if !lang.is_empty() => Some(lang.to_string()),
+                    _ => None,
+                };
+                let mut code = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(text) => code.push_str(&text),
+                        Event::End(TagEnd::CodeBlock) => break,
+                        _ => {}
+                    }
+                }
+                blocks.push(Block::Code {
+                    lang,
+                    code: code.trim_end_matches('\n').to_string(),
+                });
+            }
+            Event::Start(Tag::List(start)) => {
+                let mut items = vec![];
+                while let Some(event) = events.next() {
+                    match event {
+                        Event::Start(Tag::Item) => {
+                            items.push(parse_blocks(events, Some(TagEnd::Item)));
+                        }
+                        Event::End(TagEnd::List(_)) => break,
+                        _ => {}
+                    }
+                }
+                blocks.push(Block::List { start, items });
+            }
+            Event::Start(Tag::Table(_)) => {
+                let mut head = vec![];
+                let mut rows = vec![];
+                let mut row = vec![];
+                while let Some(event) = events.next() {
+                    match event {
+                        Event::Start(Tag::TableCell) => {
+                            row.push(parse_inlines(events, Some(TagEnd::TableCell)));
+                        }
+                        Event::End(TagEnd::TableHead) => head = std::mem::take(&mut row),
+                        Event::End(TagEnd::TableRow) => rows.push(std::mem::take(&mut row)),
+                        Event::End(TagEnd::Table) => break,
+                        _ => {}
+                    }
+                }
+                blocks.push(Block::Table { head, rows });
+            }
+            Event::Rule => blocks.push(Block::Rule),
+            Event::Html(html) => blocks.push(Block::Paragraph(vec![Inline::Text(TextSpan {
+                text: html.trim_end().to_string(),
+                style: SpanStyle::default(),
+                link: None,
+            })])),
+            // Skip the unsupported blocks, e.g.: the footnote definitions, keep their content.
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Push the text to the last span if it has the same style, otherwise push a new span.
+fn push_text(inlines: &mut Vec<Inline>, text: &str, style: SpanStyle, link: &Option<String>) {
+    if let Some(Inline::Text(last)) = inlines.last_mut() {
+        if last.style == style && last.link == *link {
+            last.text.push_str(text);
+            return;
+        }
+    }
+
+    inlines.push(Inline::Text(TextSpan {
+        text: text.to_string(),
+        style,
+        link: link.clone(),
+    }));
+}
+
+/// Parse the inline content until the `end` tag, or the next block if `end` is None.
+fn parse_inlines<'a>(
+    events: &mut Peekable<impl Iterator<Item = Event<'a>>>,
+    end: Option<TagEnd>,
+) -> Vec<Inline> {
+    let mut inlines: Vec<Inline> = vec![];
+    let mut style = SpanStyle::default();
+    let mut link: Option<String> = None;
+
+    loop {
+        if end.is_none() && !events.peek().map_or(false, is_inline) {
+            break;
+        }
+        let Some(event) = events.next() else {
+            break;
+        };
+
+        match event {
+            Event::End(tag) if Some(tag) == end => break,
+            Event::Text(text) | Event::InlineHtml(text) | Event::InlineMath(text) => {
+                push_text(&mut inlines, &text, style, &link)
+            }
+            Event::Code(text) => push_text(
+                &mut inlines,
+                &text,
+                SpanStyle {
+                    code: true,
+                    ..style
+                },
+                &link,
+            ),
+            Event::SoftBreak => push_text(&mut inlines, " ", style, &link),
+            Event::HardBreak => push_text(&mut inlines, "\n", style, &link),
+            Event::TaskListMarker(checked) => push_text(
+                &mut inlines,
+                if checked { "☑ " } else { "☐ " },
+                style,
+                &link,
+            ),
+            Event::Start(Tag::Emphasis) => style.italic = true,
+            Event::End(TagEnd::Emphasis) => style.italic = false,
+            Event::Start(Tag::Strong) => style.bold = true,
+            Event::End(TagEnd::Strong) => style.bold = false,
+            Event::Start(Tag::Strikethrough) => style.strikethrough = true,
+            Event::End(TagEnd::Strikethrough) => style.strikethrough = false,
+            Event::Start(Tag::Link { dest_url, .. }) => link = Some(dest_url.to_string()),
+            Event::End(TagEnd::Link) => link = None,
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                let mut alt = String::new();
+                for event in events.by_ref() {
+                    match event {
+                        Event::Text(text) | Event::Code(text) => alt.push_str(&text),
+                        Event::End(TagEnd::Image) => break,
+                        _ => {}
+                    }
+                }
+                inlines.push(Inline::Image {
+                    url: dest_url.to_string(),
+                    alt,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    inlines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_markdown, Block, Inline, SpanStyle, TextSpan};
+
+    fn text(text: &str) -> Inline {
+        Inline::Text(TextSpan {
+            text: text.to_string(),
+            style: SpanStyle::default(),
+            link: None,
+        })
+    }
+
+    #[test]
+    fn test_parse_blocks() {
+        let blocks = parse_markdown(
+            "# Release Notes\n\nHello\nworld\n\n---\n\n> Quote\n\n```json\n{\"a\": 1}\n```\n",
+        );
+        assert_eq!(
+            blocks,
+            vec![
+                Block::Heading {
+                    level: 1,
+                    inlines: vec![text("Release Notes")]
+                },
+                Block::Paragraph(vec![text("Hello world")]),
+                Block::Rule,
+                Block::Quote(vec![Block::Paragraph(vec![text("Quote")])]),
+                Block::Code {
+                    lang: Some("json".into()),
+                    code: "{\"a\": 1}".into()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_lists_and_tables() {
+        let blocks =
+            parse_markdown("3. One\n4. Two\n   - Nested\n\n| A | B |\n|---|---|\n| 1 | 2 |\n");
+        assert_eq!(
+            blocks,
+            vec![
+                Block::List {
+                    start: Some(3),
+                    items: vec![
+                        vec![Block::Paragraph(vec![text("One")])],
+                        vec![
+                            Block::Paragraph(vec![text("Two")]),
+                            Block::List {
+                                start: None,
+                                items: vec![vec![Block::Paragraph(vec![text("Nested")])]]
+                            }
+                        ],
+                    ]
+                },
+                Block::Table {
+                    head: vec![vec![text("A")], vec![text("B")]],
+                    rows: vec![vec![vec![text("1")], vec![text("2")]]],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_inlines() {
+        let blocks =
+            parse_markdown("**Bold** *it* ~~del~~ `code` [link](https://a.com) ![logo](logo.png)");
+        let Block::Paragraph(inlines) = &blocks[0] else {
+            panic!("expected paragraph");
+        };
+
+        let span = |text: &str, style: SpanStyle, link: Option<&str>| {
+            Inline::Text(TextSpan {
+                text: text.to_string(),
+                style,
+                link: link.map(|link| link.to_string()),
+            })
+        };
+        assert_eq!(
+            inlines,
+            &vec![
+                span(
+                    "Bold",
+                    SpanStyle {
+                        bold: true,
+                        ..Default::default()
+                    },
+                    None
+                ),
+                text(" "),
+                span(
+                    "it",
+                    SpanStyle {
+                        italic: true,
+                        ..Default::default()
+                    },
+                    None
+                ),
+                text(" "),
+                span(
+                    "del",
+                    SpanStyle {
+                        strikethrough: true,
+                        ..Default::default()
+                    },
+                    None
+                ),
+                text(" "),
+                span(
+                    "code",
+                    SpanStyle {
+                        code: true,
+                        ..Default::default()
+                    },
+                    None
+                ),
+                text(" "),
+                span("link", SpanStyle::default(), Some("https://a.com")),
+                text(" "),
+                Inline::Image {
+                    url: "logo.png".into(),
+                    alt: "logo".into()
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::ops::Range;
+
+/// Match the `query` in the `text`, returns the byte ranges of the matched characters in `text`,
+/// the ranges can be used to highlight the matched text.
+///
+/// - The match is case-insensitive, and the query must be a contiguous substring of the text.
+/// - If the `pinyin` feature is enabled, the Chinese characters can also be matched by the pinyin
+///   or the initials, e.g.: `zgpa`, `zhongguo` and `zhongguopa` all match `中国平安`.
+///   All the readings of a heteronym are matched, e.g.: `zsyh` matches `招商银行`.
+///
+/// Returns `None` if not matched.
+pub fn match_text(text: &str, query: &str) -> Option<Vec<Range<usize>>> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Some(vec![]);
+    }
+
+    let chars = text
+        .char_indices()
+        .map(|(ix, c)| CharCandidate {
+            range: ix..ix + c.len_utf8(),
+            text: c.to_lowercase().collect(),
+            pinyin: pinyin_of(c),
+        })
+        .collect::<Vec<_>>();
+
+    (0..chars.len()).find_map(|start| {
+        let end = match_from(&chars, start, &query)?;
+        Some(vec![chars[start].range.start..chars[end - 1].range.end])
+    })
+}
+
+struct CharCandidate {
+    range: Range<usize>,
+    text: String,
+    pinyin: Vec<&'static str>,
+}
+
+/// Match the query from the char at `ix`, returns the end (exclusive) char index if matched.
+fn match_from(chars: &[CharCandidate], ix: usize, query: &str) -> Option<usize> {
+    if query.is_empty() {
+        return Some(ix);
+    }
+
+    let candidate = chars.get(ix)?;
+    if let Some(rest) = query.strip_prefix(candidate.text.as_str()) {
+        if let Some(end) = match_from(chars, ix + 1, rest) {
+            return Some(end);
+        }
+    }
+
+    // Any prefix of any reading can match the char, prefer the longest one.
+    candidate.pinyin.iter().find_map(|pinyin| {
+        (1..=pinyin.len()).rev().find_map(|len| {
+            let rest = query.strip_prefix(&pinyin[..len])?;
+            match_from(chars, ix + 1, rest)
+        })
+    })
+}
+
+/// Returns the readings of the char without the tones, the heteronym has more than one.
+#[cfg(feature = "pinyin")]
+fn pinyin_of(c: char) -> Vec<&'static str> {
+    use pinyin::ToPinyinMulti as _;
+
+    let mut readings = vec![];
+    for pinyin in c.to_pinyin_multi().into_iter().flatten() {
+        let plain = pinyin.plain();
+        if !readings.contains(&plain) {
+            readings.push(plain);
+        }
+    }
+    readings
+}
+
+#[cfg(not(feature = "pinyin"))]
+fn pinyin_of(_: char) -> Vec<&'static str> {
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::match_text;
+
+    #[test]
+    fn test_match_text() {
+        assert_eq!(match_text("Hello World", ""), Some(vec![]));
+        assert_eq!(match_text("Hello World", "world"), Some(vec![6..11]));
+        assert_eq!(match_text("Hello World", "LO W"), Some(vec![3..7]));
+        assert_eq!(match_text("Hello World", "hw"), None);
+        assert_eq!(match_text("中国平安", "平安"), Some(vec![6..12]));
+        assert_eq!(match_text("中国平安", "zgpa"), pinyin_or_none(vec![0..12]));
+    }
+
+    #[cfg(feature = "pinyin")]
+    #[test]
+    fn test_match_pinyin() {
+        assert_eq!(match_text("中国平安", "zhongguo"), Some(vec![0..6]));
+        assert_eq!(match_text("中国平安", "zhongguopa"), Some(vec![0..12]));
+        assert_eq!(match_text("中国平安", "pingan"), Some(vec![6..12]));
+        assert_eq!(match_text("中国平安 A", "pa a"), Some(vec![6..14]));
+        assert_eq!(match_text("中国平安", "zgx"), None);
+        // The 行 is read as `xing` or `hang`.
+        assert_eq!(match_text("招商银行", "zsyh"), Some(vec![0..12]));
+        assert_eq!(match_text("招商银行", "yinhang"), Some(vec![6..12]));
+        assert_eq!(match_text("银行", "xing"), Some(vec![3..6]));
+    }
+
+    fn pinyin_or_none(ranges: Vec<std::ops::Range<usize>>) -> Option<Vec<std::ops::Range<usize>>> {
+        if cfg!(feature = "pinyin") {
+            Some(ranges)
+        } else {
+            None
+        }
+    }
+}
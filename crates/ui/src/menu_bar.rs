@@ -0,0 +1,455 @@
+use std::{collections::HashSet, rc::Rc};
+
+use anyhow::{Context as _, Result};
+use gpui::{
+    Action, AppContext, ElementId, InteractiveElement as _, IntoElement, Menu, MenuItem, OsAction,
+    ParentElement as _, RenderOnce, SharedString, ViewContext, WindowContext,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    h_flex,
+    popup_menu::{PopupMenu, PopupMenuExt as _},
+    Sizable as _,
+};
+
+/// The definition of a menu, can be loaded from the data (e.g.: JSON) to build
+/// both the native application menus and the in-window [`MenuBar`] or [`PopupMenu`].
+///
+/// ```json
+/// {
+///     "name": "Edit",
+///     "items": [
+///         { "type": "action", "label": "Undo", "action": "input::Undo", "os_action": "undo" },
+///         { "type": "separator" },
+///         { "type": "action", "label": "Delete", "action": "input::Delete", "enabled_when": "editable && has_selection" },
+///         { "type": "submenu", "label": "More", "items": [] }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MenuDef {
+    pub name: SharedString,
+    #[serde(default)]
+    pub items: Vec<MenuItemDef>,
+}
+
+/// The definition of a menu item, see [`MenuDef`].
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MenuItemDef {
+    Separator,
+    Action {
+        label: SharedString,
+        /// The name of the action, e.g.: `input::Undo`.
+        action: SharedString,
+        /// The arguments of the action, for the actions have data, e.g.: `SelectFont(16)`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        args: Option<serde_json::Value>,
+        /// The native edit action of the OS, only used by the native menus.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        os_action: Option<MenuOsAction>,
+        /// The expression to enable the item, see [`MenuContext::eval`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        enabled_when: Option<SharedString>,
+    },
+    Submenu {
+        label: SharedString,
+        #[serde(default)]
+        items: Vec<MenuItemDef>,
+    },
+}
+
+/// The serializable [`OsAction`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MenuOsAction {
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+    Undo,
+    Redo,
+}
+
+impl From<MenuOsAction> for OsAction {
+    fn from(action: MenuOsAction) -> Self {
+        match action {
+            MenuOsAction::Cut => OsAction::Cut,
+            MenuOsAction::Copy => OsAction::Copy,
+            MenuOsAction::Paste => OsAction::Paste,
+            MenuOsAction::SelectAll => OsAction::SelectAll,
+            MenuOsAction::Undo => OsAction::Undo,
+            MenuOsAction::Redo => OsAction::Redo,
+        }
+    }
+}
+
+/// The flags to evaluate the `enabled_when` expressions of the menu items.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MenuContext {
+    flags: HashSet<SharedString>,
+}
+
+impl MenuContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the flag to true or false.
+    pub fn set(mut self, flag: impl Into<SharedString>, value: bool) -> Self {
+        let flag = flag.into();
+        if value {
+            self.flags.insert(flag);
+        } else {
+            self.flags.remove(&flag);
+        }
+        self
+    }
+
+    /// Returns true if the flag is set.
+    pub fn is_set(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Evaluate the expression with the flags.
+    ///
+    /// The expression supports the flag names, `!`, `&&`, `||` and the parentheses,
+    /// e.g.: `editable && (has_selection || !empty)`.
+    ///
+    /// Returns false if the expression is invalid.
+    pub fn eval(&self, expr: &str) -> bool {
+        let tokens = match tokenize(expr) {
+            Some(tokens) => tokens,
+            None => return false,
+        };
+
+        let mut parser = ExprParser {
+            tokens: &tokens,
+            pos: 0,
+            context: self,
+        };
+        match parser.parse_or() {
+            Some(value) if parser.pos == tokens.len() => value,
+            _ => false,
+        }
+    }
+
+    fn is_enabled(&self, enabled_when: &Option<SharedString>) -> bool {
+        enabled_when
+            .as_ref()
+            .map_or(true, |expr| self.eval(expr.as_ref()))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Ident(&'a str),
+    Not,
+    And,
+    Or,
+    LeftParen,
+    RightParen,
+}
+
+fn tokenize(expr: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = vec![];
+    let mut rest = expr.trim_start();
+
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '!' => {
+                tokens.push(Token::Not);
+                1
+            }
+            '(' => {
+                tokens.push(Token::LeftParen);
+                1
+            }
+            ')' => {
+                tokens.push(Token::RightParen);
+                1
+            }
+            '&' if rest.starts_with("&&") => {
+                tokens.push(Token::And);
+                2
+            }
+            '|' if rest.starts_with("||") => {
+                tokens.push(Token::Or);
+                2
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+                    .unwrap_or(rest.len());
+                tokens.push(Token::Ident(&rest[..len]));
+                len
+            }
+            _ => return None,
+        };
+
+        rest = rest[len..].trim_start();
+    }
+
+    Some(tokens)
+}
+
+struct ExprParser<'a> {
+    tokens: &'a [Token<'a>],
+    pos: usize,
+    context: &'a MenuContext,
+}
+
+impl ExprParser<'_> {
+    fn next_is(&mut self, token: Token<'_>) -> bool {
+        if self.tokens.get(self.pos) == Some(&token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Option<bool> {
+        let mut value = self.parse_and()?;
+        while self.next_is(Token::Or) {
+            value = self.parse_and()? || value;
+        }
+        Some(value)
+    }
+
+    fn parse_and(&mut self) -> Option<bool> {
+        let mut value = self.parse_unary()?;
+        while self.next_is(Token::And) {
+            value = self.parse_unary()? && value;
+        }
+        Some(value)
+    }
+
+    fn parse_unary(&mut self) -> Option<bool> {
+        if self.next_is(Token::Not) {
+            return self.parse_unary().map(|value| !value);
+        }
+
+        if self.next_is(Token::LeftParen) {
+            let value = self.parse_or()?;
+            return self.next_is(Token::RightParen).then_some(value);
+        }
+
+        match self.tokens.get(self.pos)? {
+            Token::Ident(flag) => {
+                self.pos += 1;
+                Some(self.context.is_set(flag))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl MenuItemDef {
+    fn build_action(
+        action: &SharedString,
+        args: &Option<serde_json::Value>,
+        cx: &AppContext,
+    ) -> Result<Box<dyn Action>> {
+        cx.build_action(action.as_ref(), args.clone())
+            .with_context(|| format!("failed to build menu action: {}", action))
+    }
+
+    /// Build the native menu item, the disabled items are omitted,
+    /// because the native menus are enabled by the availability of the actions.
+    fn to_menu_item(&self, context: &MenuContext, cx: &AppContext) -> Result<Option<MenuItem>> {
+        Ok(match self {
+            MenuItemDef::Separator => Some(MenuItem::separator()),
+            MenuItemDef::Action {
+                label,
+                action,
+                args,
+                os_action,
+                enabled_when,
+            } => {
+                if !context.is_enabled(enabled_when) {
+                    return Ok(None);
+                }
+
+                Some(MenuItem::Action {
+                    name: label.clone(),
+                    action: Self::build_action(action, args, cx)?,
+                    os_action: os_action.map(Into::into),
+                })
+            }
+            MenuItemDef::Submenu { label, items } => Some(MenuItem::Submenu(
+                MenuDef {
+                    name: label.clone(),
+                    items: items.clone(),
+                }
+                .to_menu(context, cx)?,
+            )),
+        })
+    }
+}
+
+impl MenuDef {
+    /// Check all the actions of the menu can be built, e.g.: after loaded the definitions.
+    pub fn validate(&self, cx: &AppContext) -> Result<()> {
+        fn validate_items(items: &[MenuItemDef], cx: &AppContext) -> Result<()> {
+            for item in items {
+                match item {
+                    MenuItemDef::Separator => {}
+                    MenuItemDef::Action { action, args, .. } => {
+                        MenuItemDef::build_action(action, args, cx)?;
+                    }
+                    MenuItemDef::Submenu { items, .. } => validate_items(items, cx)?,
+                }
+            }
+            Ok(())
+        }
+
+        validate_items(&self.items, cx)
+    }
+
+    /// Build the native application menu, see also [`set_menus`].
+    pub fn to_menu(&self, context: &MenuContext, cx: &AppContext) -> Result<Menu> {
+        let mut items = vec![];
+        for item in self.items.iter() {
+            items.extend(item.to_menu_item(context, cx)?);
+        }
+
+        Ok(Menu {
+            name: self.name.clone(),
+            items,
+        })
+    }
+}
+
+/// Set the native application menus by the definitions.
+///
+/// Call it again to update the menus when the [`MenuContext`] has been changed.
+pub fn set_menus(menus: &[MenuDef], context: &MenuContext, cx: &mut AppContext) -> Result<()> {
+    let menus = menus
+        .iter()
+        .map(|menu| menu.to_menu(context, cx))
+        .collect::<Result<Vec<_>>>()?;
+    cx.set_menus(menus);
+    Ok(())
+}
+
+impl PopupMenu {
+    /// Add the menu items by the definitions.
+    ///
+    /// The items with the unknown actions are skipped, use [`MenuDef::validate`] to check them.
+    pub fn menu_items(
+        mut self,
+        items: &[MenuItemDef],
+        context: &MenuContext,
+        cx: &mut ViewContext<Self>,
+    ) -> Self {
+        for item in items {
+            self = match item {
+                MenuItemDef::Separator => self.separator(),
+                MenuItemDef::Action {
+                    label,
+                    action,
+                    args,
+                    enabled_when,
+                    ..
+                } => match MenuItemDef::build_action(action, args, cx) {
+                    Ok(action) => self.menu_with_disabled(
+                        label.clone(),
+                        action,
+                        !context.is_enabled(enabled_when),
+                    ),
+                    Err(_) => self,
+                },
+                MenuItemDef::Submenu { label, items } => {
+                    let items = items.clone();
+                    let context = context.clone();
+                    self.submenu(label.clone(), cx, move |menu, cx| {
+                        menu.menu_items(&items, &context, cx)
+                    })
+                }
+            };
+        }
+
+        self
+    }
+}
+
+/// An in-window menu bar built by the [`MenuDef`]s, e.g.: for the platforms without native menus.
+#[derive(IntoElement)]
+pub struct MenuBar {
+    id: ElementId,
+    menus: Rc<Vec<MenuDef>>,
+    context: Rc<MenuContext>,
+}
+
+impl MenuBar {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            menus: Rc::new(vec![]),
+            context: Rc::new(MenuContext::default()),
+        }
+    }
+
+    /// Set the menus of the menu bar.
+    pub fn menus(mut self, menus: impl IntoIterator<Item = MenuDef>) -> Self {
+        self.menus = Rc::new(menus.into_iter().collect());
+        self
+    }
+
+    /// Set the context to evaluate the `enabled_when` of the menu items.
+    pub fn context(mut self, context: MenuContext) -> Self {
+        self.context = Rc::new(context);
+        self
+    }
+}
+
+impl RenderOnce for MenuBar {
+    fn render(self, _: &mut WindowContext) -> impl IntoElement {
+        h_flex()
+            .id(self.id)
+            .gap_1()
+            .children(self.menus.iter().enumerate().map(|(ix, menu)| {
+                let menus = self.menus.clone();
+                let context = self.context.clone();
+
+                Button::new(("menu", ix))
+                    .ghost()
+                    .small()
+                    .label(menu.name.clone())
+                    .popup_menu(move |this, cx| this.menu_items(&menus[ix].items, &context, cx))
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MenuContext;
+
+    #[test]
+    fn test_eval() {
+        let context = MenuContext::new()
+            .set("editable", true)
+            .set("has_selection", false)
+            .set("empty", true);
+
+        assert!(context.eval("editable"));
+        assert!(!context.eval("has_selection"));
+        assert!(!context.eval("unknown"));
+        assert!(context.eval("!has_selection"));
+        assert!(context.eval("editable && !has_selection"));
+        assert!(!context.eval("editable && has_selection"));
+        assert!(context.eval("has_selection || editable"));
+        assert!(!context.eval("editable && (has_selection || !empty)"));
+        assert!(context.eval("!(has_selection && editable)"));
+
+        // Invalid expressions
+        assert!(!context.eval(""));
+        assert!(!context.eval("editable &&"));
+        assert!(!context.eval("(editable"));
+        assert!(!context.eval("editable & empty"));
+        assert!(!context.eval("editable empty"));
+    }
+}
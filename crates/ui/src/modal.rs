@@ -1,25 +1,21 @@
 use std::{rc::Rc, time::Duration};
 
 use gpui::{
-    actions, anchored, div, hsla, prelude::FluentBuilder, px, relative, Animation,
-    AnimationExt as _, AnyElement, AppContext, Bounds, ClickEvent, Div, FocusHandle, Hsla,
-    InteractiveElement, IntoElement, KeyBinding, MouseButton, ParentElement, Pixels, Point,
-    RenderOnce, SharedString, Styled, WindowContext,
+    anchored, div, hsla, prelude::FluentBuilder, px, relative, Animation, AnyElement, Bounds,
+    ClickEvent, Div, FocusHandle, Hsla, InteractiveElement, IntoElement, MouseButton,
+    ParentElement, Pixels, Point, RenderOnce, SharedString, Styled, WindowContext,
 };
 
 use crate::{
-    animation::cubic_bezier,
+    animation::{cubic_bezier, TransitionExt as _},
     button::{Button, ButtonVariants as _},
+    primitives::{DismissableExt as _, FocusTrapExt as _},
+    root::Root,
     theme::ActiveTheme as _,
-    v_flex, ContextModal, IconName, Sizable as _,
+    v_flex, IconName, Sizable as _,
 };
 
-actions!(modal, [Escape]);
-
 const CONTEXT: &str = "Modal";
-pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([KeyBinding::new("escape", Escape, Some(CONTEXT))])
-}
 
 #[derive(IntoElement)]
 pub struct Modal {
@@ -35,6 +31,7 @@ pub struct Modal {
     show_close: bool,
     overlay: bool,
     keyboard: bool,
+    focus_trap: Vec<FocusHandle>,
 
     /// This will be change when open the modal, the focus handle is create when open the modal.
     pub(crate) focus_handle: FocusHandle,
@@ -54,6 +51,11 @@ pub(crate) fn overlay_color(overlay: bool, cx: &WindowContext) -> Hsla {
     }
 }
 
+/// Close the modal at the `layer_ix`, it's safe to call if the modal has been closed in `on_close`.
+fn close_modal_at(layer_ix: usize, cx: &mut WindowContext) {
+    Root::update(cx, move |root, cx| root.close_modal_at(layer_ix, cx));
+}
+
 impl Modal {
     pub fn new(cx: &mut WindowContext) -> Self {
         let base = v_flex()
@@ -61,7 +63,7 @@ impl Modal {
             .border_1()
             .border_color(cx.theme().border)
             .rounded_lg()
-            .shadow_xl()
+            .when(cx.theme().has_shadow(), |this| this.shadow_xl())
             .min_h_48()
             .p_4()
             .gap_4();
@@ -77,6 +79,7 @@ impl Modal {
             max_width: None,
             overlay: true,
             keyboard: true,
+            focus_trap: vec![],
             layer_ix: 0,
             overlay_visible: true,
             on_close: Rc::new(|_, _| {}),
@@ -141,6 +144,12 @@ impl Modal {
         self
     }
 
+    /// Set the focus handles to cycle by `tab` and `shift-tab` in the modal, defaults to empty.
+    pub fn focus_trap(mut self, handles: Vec<FocusHandle>) -> Self {
+        self.focus_trap = handles;
+        self
+    }
+
     pub(crate) fn has_overlay(&self) -> bool {
         self.overlay
     }
@@ -184,7 +193,7 @@ impl RenderOnce for Modal {
                         let on_close = self.on_close.clone();
                         move |_, cx| {
                             on_close(&ClickEvent::default(), cx);
-                            cx.close_modal();
+                            close_modal_at(layer_ix, cx);
                         }
                     })
                 })
@@ -193,16 +202,17 @@ impl RenderOnce for Modal {
                         .id(SharedString::from(format!("modal-{layer_ix}")))
                         .key_context(CONTEXT)
                         .track_focus(&self.focus_handle)
+                        .when(!self.focus_trap.is_empty(), |this| {
+                            this.focus_trap(self.focus_trap)
+                        })
                         .when(self.keyboard, |this| {
-                            this.on_action({
+                            this.on_escape({
                                 let on_close = self.on_close.clone();
-                                move |_: &Escape, cx| {
-                                    // FIXME:
-                                    //
-                                    // Here some Modal have no focus_handle, so it will not work will Escape key.
-                                    // But by now, we `cx.close_modal()` going to close the last active model, so the Escape is unexpected to work.
+                                move |cx| {
+                                    // Only close this modal, the focus is trapped in the top modal,
+                                    // so the escape will not close the modals below.
                                     on_close(&ClickEvent::default(), cx);
-                                    cx.close_modal();
+                                    close_modal_at(layer_ix, cx);
                                 }
                             })
                         })
@@ -227,13 +237,13 @@ impl RenderOnce for Modal {
                                     .icon(IconName::Close)
                                     .on_click(move |_, cx| {
                                         on_close(&ClickEvent::default(), cx);
-                                        cx.close_modal();
+                                        close_modal_at(layer_ix, cx);
                                     }),
                             )
                         })
                         .child(self.content)
                         .children(self.footer)
-                        .with_animation(
+                        .with_transition(
                             "slide-down",
                             Animation::new(Duration::from_secs_f64(0.25))
                                 .with_easing(cubic_bezier(0.32, 0.72, 0., 1.)),
@@ -241,6 +251,7 @@ impl RenderOnce for Modal {
                                 let y_offset = px(0.) + delta * px(30.);
                                 this.top(y + y_offset).opacity(delta)
                             },
+                            cx,
                         ),
                 ),
         )
@@ -1,20 +1,32 @@
-use std::{any::TypeId, collections::VecDeque, sync::Arc, time::Duration};
+use std::{
+    any::TypeId,
+    collections::{HashSet, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
 
+use chrono::NaiveTime;
 use gpui::{
-    div, prelude::FluentBuilder, px, Animation, AnimationExt, ClickEvent, DismissEvent, ElementId,
-    EventEmitter, InteractiveElement as _, IntoElement, ParentElement as _, Render, SharedString,
-    StatefulInteractiveElement, Styled, View, ViewContext, VisualContext, WindowContext,
+    div, prelude::FluentBuilder, px, relative, Animation, AppContext, ClickEvent, DismissEvent,
+    ElementId, EventEmitter, Global, InteractiveElement as _, IntoElement, ParentElement as _,
+    Render, SharedString, StatefulInteractiveElement, Styled, View, ViewContext, VisualContext,
+    WindowContext,
 };
+use rust_i18n::t;
 use smol::Timer;
 
 use crate::{
-    animation::cubic_bezier,
+    animation::{cubic_bezier, TransitionExt as _},
     button::{Button, ButtonVariants as _},
     h_flex,
     theme::ActiveTheme as _,
-    v_flex, Icon, IconName, Sizable as _, StyledExt,
+    v_flex, FocusRestore, Icon, IconName, Sizable as _, StyledExt,
 };
 
+/// The default time to show the notification before auto hidden.
+const DEFAULT_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NotificationType {
     Info,
     Success,
@@ -40,19 +52,109 @@ impl From<(TypeId, ElementId)> for NotificationId {
     }
 }
 
+/// The do-not-disturb window in local time, e.g.: from 22:00 to 08:00.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoNotDisturb {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl DoNotDisturb {
+    pub fn new(start: NaiveTime, end: NaiveTime) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns true if the time is in the window, the window can cross the midnight.
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            time >= self.start && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// The settings of the notifications, includes the muted sources and the do-not-disturb window.
+#[derive(Default)]
+pub struct NotificationSettings {
+    do_not_disturb: Option<DoNotDisturb>,
+    muted_sources: HashSet<SharedString>,
+}
+
+impl Global for NotificationSettings {}
+
+impl NotificationSettings {
+    /// Set the do-not-disturb window, `None` to disable it.
+    ///
+    /// The notifications (except the [`NotificationType::Error`]) will not be shown in the window.
+    pub fn set_do_not_disturb(do_not_disturb: Option<DoNotDisturb>, cx: &mut AppContext) {
+        cx.default_global::<Self>().do_not_disturb = do_not_disturb;
+    }
+
+    /// Returns the do-not-disturb window.
+    pub fn do_not_disturb(cx: &AppContext) -> Option<DoNotDisturb> {
+        cx.try_global::<Self>()
+            .and_then(|settings| settings.do_not_disturb)
+    }
+
+    /// Mute or unmute the notifications from the source.
+    pub fn set_muted(source: impl Into<SharedString>, muted: bool, cx: &mut AppContext) {
+        let settings = cx.default_global::<Self>();
+        let source = source.into();
+        if muted {
+            settings.muted_sources.insert(source);
+        } else {
+            settings.muted_sources.remove(&source);
+        }
+    }
+
+    /// Returns true if the notifications from the source are muted.
+    pub fn is_muted(source: &str, cx: &AppContext) -> bool {
+        cx.try_global::<Self>()
+            .map_or(false, |settings| settings.muted_sources.contains(source))
+    }
+
+    /// Returns the muted sources.
+    pub fn muted_sources(cx: &AppContext) -> Vec<SharedString> {
+        cx.try_global::<Self>()
+            .map(|settings| settings.muted_sources.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns true if the notification should not be shown, muted or in the do-not-disturb window.
+    pub(crate) fn is_suppressed(notification: &Notification, cx: &AppContext) -> bool {
+        if let Some(source) = &notification.source {
+            if Self::is_muted(source, cx) {
+                return true;
+            }
+        }
+
+        notification.type_ != NotificationType::Error
+            && Self::do_not_disturb(cx)
+                .map_or(false, |dnd| dnd.contains(chrono::Local::now().time()))
+    }
+}
+
 /// A notification element.
 pub struct Notification {
     /// The id is used make the notification unique.
     /// Then you push a notification with the same id, the previous notification will be replaced.
     ///
     /// None means the notification will be added to the end of the list.
-    id: NotificationId,
+    pub(crate) id: NotificationId,
     type_: NotificationType,
     title: Option<SharedString>,
     message: SharedString,
+    /// The source (or category) of the notification, used to group and mute the notifications.
+    source: Option<SharedString>,
     icon: Option<Icon>,
     autohide: bool,
+    /// The time to show before auto hidden.
+    duration: Duration,
+    /// Show a bar to count down the time before auto hidden.
+    countdown: bool,
     on_click: Option<Arc<dyn Fn(&ClickEvent, &mut WindowContext)>>,
+    action: Option<(SharedString, Arc<dyn Fn(&mut WindowContext)>)>,
     closing: bool,
 }
 
@@ -99,10 +201,14 @@ impl Notification {
             id: id.into(),
             title: None,
             message: message.into(),
+            source: None,
             type_: NotificationType::Info,
             icon: None,
             autohide: true,
+            duration: DEFAULT_DURATION,
+            countdown: false,
             on_click: None,
+            action: None,
             closing: false,
         }
     }
@@ -148,6 +254,14 @@ impl Notification {
         self
     }
 
+    /// Set the source (or category) of the notification, e.g.: `Downloads`, default is None.
+    ///
+    /// The notifications from the same source will be grouped, and can be muted by the source.
+    pub fn source(mut self, source: impl Into<SharedString>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
     /// Set the icon of the notification.
     ///
     /// If icon is None, the notification will use the default icon of the type.
@@ -168,6 +282,28 @@ impl Notification {
         self
     }
 
+    /// Set the time to show before auto hidden, default is 5s.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Show a bar to count down the time before auto hidden, default is false.
+    pub fn countdown(mut self, countdown: bool) -> Self {
+        self.countdown = countdown;
+        self
+    }
+
+    /// Add an action button next to the message, e.g.: `Undo`, the notification is dismissed after clicked.
+    pub fn action(
+        mut self,
+        label: impl Into<SharedString>,
+        handler: impl Fn(&mut WindowContext) + 'static,
+    ) -> Self {
+        self.action = Some((label.into(), Arc::new(handler)));
+        self
+    }
+
     /// Set the click callback of the notification.
     pub fn on_click(
         mut self,
@@ -177,7 +313,7 @@ impl Notification {
         self
     }
 
-    fn dismiss(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
+    pub(crate) fn dismiss(&mut self, _: &ClickEvent, cx: &mut ViewContext<Self>) {
         self.closing = true;
         cx.notify();
 
@@ -206,13 +342,13 @@ impl Render for Notification {
             None => match self.type_ {
                 NotificationType::Info => Icon::new(IconName::Info).text_color(crate::blue_500()),
                 NotificationType::Success => {
-                    Icon::new(IconName::CircleCheck).text_color(crate::green_500())
+                    Icon::new(IconName::CircleCheck).text_color(cx.theme().positive)
                 }
                 NotificationType::Warning => {
-                    Icon::new(IconName::TriangleAlert).text_color(crate::yellow_500())
+                    Icon::new(IconName::TriangleAlert).text_color(cx.theme().warning)
                 }
                 NotificationType::Error => {
-                    Icon::new(IconName::CircleX).text_color(crate::red_500())
+                    Icon::new(IconName::CircleX).text_color(cx.theme().negative)
                 }
             },
         };
@@ -227,7 +363,7 @@ impl Render for Notification {
             .border_color(cx.theme().border)
             .bg(cx.theme().popover)
             .rounded_md()
-            .shadow_md()
+            .when(cx.theme().has_shadow(), |this| this.shadow_md())
             .py_2()
             .px_4()
             .gap_3()
@@ -240,13 +376,48 @@ impl Render for Notification {
                         this.child(div().text_sm().font_semibold().child(title))
                     })
                     .overflow_hidden()
-                    .child(div().text_sm().child(self.message.clone())),
+                    .child(
+                        h_flex()
+                            .gap_2()
+                            .justify_between()
+                            .child(div().text_sm().child(self.message.clone()))
+                            .when_some(self.action.clone(), |this, (label, handler)| {
+                                this.child(
+                                    Button::new("action").label(label).link().small().on_click(
+                                        cx.listener(move |view, event, cx| {
+                                            cx.stop_propagation();
+                                            view.dismiss(event, cx);
+                                            handler(cx);
+                                        }),
+                                    ),
+                                )
+                            }),
+                    ),
             )
+            .when(self.autohide && self.countdown, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .bottom_0()
+                        .left_0()
+                        .h(px(2.))
+                        .bg(cx.theme().primary)
+                        .with_transition(
+                            "countdown",
+                            Animation::new(self.duration),
+                            |this, delta| this.w(relative(1. - delta)),
+                            cx,
+                        ),
+                )
+            })
             .when_some(self.on_click.clone(), |this, on_click| {
                 this.cursor_pointer()
                     .on_click(cx.listener(move |view, event, cx| {
+                        let focused = cx.focused();
                         view.dismiss(event, cx);
                         on_click(event, cx);
+                        // The action may close the overlays or blur the focused element.
+                        FocusRestore::refocus(focused, cx);
                     }))
             })
             .when(!self.autohide, |this| {
@@ -266,7 +437,7 @@ impl Render for Notification {
                         ),
                 )
             })
-            .with_animation(
+            .with_transition(
                 ElementId::NamedInteger("slide-down".into(), closing as usize),
                 Animation::new(Duration::from_secs_f64(0.15))
                     .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
@@ -279,6 +450,7 @@ impl Render for Notification {
                         this.top(px(0.) + y_offset).opacity(delta)
                     }
                 },
+                cx,
             )
     }
 }
@@ -288,6 +460,8 @@ pub struct NotificationList {
     /// Notifications that will be auto hidden.
     pub(crate) notifications: VecDeque<View<Notification>>,
     expanded: bool,
+    /// The sources that the grouped notifications are expanded.
+    expanded_sources: HashSet<SharedString>,
 }
 
 impl NotificationList {
@@ -295,13 +469,20 @@ impl NotificationList {
         Self {
             notifications: VecDeque::new(),
             expanded: false,
+            expanded_sources: HashSet::new(),
         }
     }
 
+    /// Push a notification, it will be ignored if the source is muted or in the do-not-disturb window.
     pub fn push(&mut self, notification: impl Into<Notification>, cx: &mut ViewContext<Self>) {
         let notification = notification.into();
+        if NotificationSettings::is_suppressed(&notification, cx) {
+            return;
+        }
+
         let id = notification.id.clone();
         let autohide = notification.autohide;
+        let duration = notification.duration;
 
         // Remove the notification by id, for keep unique.
         self.notifications.retain(|note| note.read(cx).id != id);
@@ -314,9 +495,8 @@ impl NotificationList {
 
         self.notifications.push_back(notification.clone());
         if autohide {
-            // Sleep for 5 seconds to autohide the notification
             cx.spawn(|_, mut cx| async move {
-                Timer::after(Duration::from_secs(5)).await;
+                Timer::after(duration).await;
 
                 if let Err(err) = notification
                     .update(&mut cx, |note, cx| note.dismiss(&ClickEvent::default(), cx))
@@ -337,12 +517,124 @@ impl NotificationList {
     pub fn notifications(&self) -> Vec<View<Notification>> {
         self.notifications.iter().cloned().collect()
     }
+
+    /// Mute the notifications from the source, and remove the current notifications of the source.
+    pub fn mute_source(&mut self, source: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
+        let source = source.into();
+        NotificationSettings::set_muted(source.clone(), true, cx);
+        self.notifications
+            .retain(|note| note.read(cx).source.as_ref() != Some(&source));
+        self.expanded_sources.remove(&source);
+        cx.notify();
+    }
+
+    /// Expand or collapse the grouped notifications of the source.
+    pub fn toggle_source(&mut self, source: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
+        let source = source.into();
+        if !self.expanded_sources.remove(&source) {
+            self.expanded_sources.insert(source);
+        }
+        cx.notify();
+    }
+
+    /// Group the notifications by the source, the group is placed at the latest notification.
+    fn groups(&self, cx: &AppContext) -> Vec<(Option<SharedString>, Vec<View<Notification>>)> {
+        let mut groups: Vec<(Option<SharedString>, Vec<View<Notification>>)> = vec![];
+        for note in self.notifications.iter().rev().take(10).rev() {
+            let source = note.read(cx).source.clone();
+            if source.is_some() {
+                if let Some(ix) = groups.iter().position(|(s, _)| *s == source) {
+                    let (_, mut notes) = groups.remove(ix);
+                    notes.push(note.clone());
+                    groups.push((source, notes));
+                    continue;
+                }
+            }
+
+            groups.push((source, vec![note.clone()]));
+        }
+        groups
+    }
+
+    fn render_group_header(
+        &self,
+        source: SharedString,
+        count: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> impl IntoElement {
+        let expanded = self.expanded_sources.contains(&source);
+
+        h_flex()
+            .w_96()
+            .px_1()
+            .justify_between()
+            .text_xs()
+            .text_color(cx.theme().muted_foreground)
+            .child(t!("Notification.group", count = count, source = source).to_string())
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        Button::new(SharedString::from(format!("toggle-{}", source)))
+                            .icon(if expanded {
+                                IconName::ChevronUp
+                            } else {
+                                IconName::ChevronDown
+                            })
+                            .ghost()
+                            .xsmall()
+                            .tooltip(if expanded {
+                                t!("Notification.Collapse")
+                            } else {
+                                t!("Notification.Expand")
+                            })
+                            .on_click(cx.listener({
+                                let source = source.clone();
+                                move |this, _, cx| this.toggle_source(source.clone(), cx)
+                            })),
+                    )
+                    .child(
+                        Button::new(SharedString::from(format!("mute-{}", source)))
+                            .icon(IconName::BellOff)
+                            .ghost()
+                            .xsmall()
+                            .tooltip(t!("Notification.Mute"))
+                            .on_click(
+                                cx.listener(move |this, _, cx| {
+                                    this.mute_source(source.clone(), cx)
+                                }),
+                            ),
+                    ),
+            )
+    }
 }
 
 impl Render for NotificationList {
     fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
         let size = cx.viewport_size();
-        let items = self.notifications.iter().rev().take(10).rev().cloned();
+        let groups = self.groups(cx);
+        let items = groups
+            .into_iter()
+            .map(|(source, notes)| match source {
+                Some(source) if notes.len() > 1 => {
+                    let expanded = self.expanded_sources.contains(&source);
+                    let count = notes.len();
+                    // Only show the latest notification of the group when collapsed.
+                    let notes = if expanded {
+                        notes
+                    } else {
+                        notes.into_iter().last().into_iter().collect()
+                    };
+
+                    v_flex()
+                        .gap_1()
+                        .child(self.render_group_header(source, count, cx))
+                        .children(notes)
+                        .into_any_element()
+                }
+                _ => v_flex().gap_3().children(notes).into_any_element(),
+            })
+            .collect::<Vec<_>>();
 
         div()
             .absolute()
@@ -367,3 +659,28 @@ impl Render for NotificationList {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveTime;
+
+    use super::DoNotDisturb;
+
+    #[test]
+    fn test_do_not_disturb_contains() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+
+        let dnd = DoNotDisturb::new(time(12, 0), time(14, 0));
+        assert!(dnd.contains(time(12, 0)));
+        assert!(dnd.contains(time(13, 30)));
+        assert!(!dnd.contains(time(14, 0)));
+        assert!(!dnd.contains(time(8, 0)));
+
+        // Cross the midnight
+        let dnd = DoNotDisturb::new(time(22, 0), time(8, 0));
+        assert!(dnd.contains(time(23, 0)));
+        assert!(dnd.contains(time(2, 0)));
+        assert!(!dnd.contains(time(8, 0)));
+        assert!(!dnd.contains(time(12, 0)));
+    }
+}
@@ -1,14 +1,15 @@
 use gpui::{
     actions, AppContext, EventEmitter, FocusHandle, FocusableView, InteractiveElement, IntoElement,
-    KeyBinding, ParentElement, Render, SharedString, Styled, Subscription, View, ViewContext,
-    VisualContext,
+    ParentElement, Render, SharedString, Styled, Subscription, View, ViewContext, VisualContext,
 };
 use regex::Regex;
 
 use crate::{
     button::{Button, ButtonVariants as _},
+    format::{format_cjk_number, parse_cjk_number, parse_number, NumberFormat},
     h_flex,
     input::{InputEvent, TextInput},
+    keymap::{self, KeymapEntry},
     prelude::FluentBuilder,
     theme::ActiveTheme,
     IconName, Sizable, Size, StyledExt,
@@ -19,34 +20,98 @@ actions!(number_input, [Increment, Decrement]);
 const KEY_CONTENT: &str = "NumberInput";
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys(vec![
-        KeyBinding::new("up", Increment, Some(KEY_CONTENT)),
-        KeyBinding::new("down", Decrement, Some(KEY_CONTENT)),
-    ]);
+    keymap::bind_keys(
+        cx,
+        vec![
+            KeymapEntry::new("up", Increment, Some(KEY_CONTENT)),
+            KeymapEntry::new("down", Decrement, Some(KEY_CONTENT)),
+        ],
+    );
 }
 
 pub struct NumberInput {
     input: View<TextInput>,
+    cjk_units: bool,
+    /// The decimal point of the default pattern set to the input, `None` if not set yet.
+    pattern_decimal: Option<char>,
+    custom_pattern: bool,
     _subscriptions: Vec<Subscription>,
 }
 
 impl NumberInput {
     pub fn new(cx: &mut ViewContext<Self>) -> Self {
-        // Default pattern for the number input.
-        let pattern = Regex::new(r"^-?(\d+)?\.?(\d+)?$").unwrap();
+        let input = cx.new_view(|cx| TextInput::new(cx).appearance(false));
 
-        let input = cx.new_view(|cx| TextInput::new(cx).pattern(pattern).appearance(false));
-
-        let _subscriptions = vec![cx.subscribe(&input, |_, _, event: &InputEvent, cx| {
+        let _subscriptions = vec![cx.subscribe(&input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::Blur = event {
+                this.format_cjk_units(cx);
+            }
             cx.emit(NumberInputEvent::Input(event.clone()));
         })];
 
         Self {
             input,
+            cjk_units: false,
+            pattern_decimal: None,
+            custom_pattern: false,
             _subscriptions,
         }
     }
 
+    /// Set the default pattern with the decimal point of the current locale,
+    /// resolved when rendering, so it follows the locale changed after the input created.
+    fn update_locale_pattern(&mut self, cx: &mut ViewContext<Self>) {
+        if self.cjk_units || self.custom_pattern {
+            return;
+        }
+
+        let decimal = NumberFormat::current().decimal;
+        if self.pattern_decimal == Some(decimal) {
+            return;
+        }
+
+        let escaped = regex::escape(&decimal.to_string());
+        let pattern = Regex::new(&format!(r"^-?(\d+)?{}?(\d+)?$", escaped)).unwrap();
+        self.input.update(cx, |input, _| input.set_pattern(pattern));
+        self.pattern_decimal = Some(decimal);
+    }
+
+    /// Set to allow the Chinese numeral units `万` and `亿` in the input, default is `false`.
+    ///
+    /// When enabled, the value will be displayed with the units after blur, e.g.: `15000` -> `1.5万`,
+    /// and use [`NumberInput::value`] to get the parsed value.
+    pub fn cjk_units(mut self, cjk_units: bool, cx: &mut ViewContext<Self>) -> Self {
+        self.cjk_units = cjk_units;
+        if cjk_units {
+            let pattern = Regex::new(r"^-?[\d,.万亿]*$").unwrap();
+            self.input.update(cx, |input, _| input.set_pattern(pattern));
+            self.pattern_decimal = None;
+        }
+        self
+    }
+
+    /// Returns the parsed number value of the input, `None` if the input is empty or invalid.
+    pub fn value(&self, cx: &AppContext) -> Option<f64> {
+        let text = self.input.read(cx).text();
+        if self.cjk_units {
+            parse_cjk_number(&text)
+        } else {
+            parse_number(&text)
+        }
+    }
+
+    fn format_cjk_units(&mut self, cx: &mut ViewContext<Self>) {
+        if !self.cjk_units {
+            return;
+        }
+
+        if let Some(value) = self.value(cx) {
+            // Use the max precision of the units to keep the value lossless.
+            let text = format_cjk_number(value, 8);
+            self.input.update(cx, |input, cx| input.set_text(text, cx));
+        }
+    }
+
     pub fn placeholder(
         self,
         placeholder: impl Into<SharedString>,
@@ -63,7 +128,8 @@ impl NumberInput {
         });
     }
 
-    pub fn pattern(self, pattern: regex::Regex, cx: &mut ViewContext<Self>) -> Self {
+    pub fn pattern(mut self, pattern: regex::Regex, cx: &mut ViewContext<Self>) -> Self {
+        self.custom_pattern = true;
         self.input.update(cx, |input, _| input.set_pattern(pattern));
         self
     }
@@ -85,7 +151,22 @@ impl NumberInput {
         self.set_size(Size::Large, cx)
     }
 
+    pub fn xlarge(self, cx: &mut ViewContext<Self>) -> Self {
+        self.set_size(Size::XLarge, cx)
+    }
+
+    /// Set the value in the canonical format with `.` as the decimal point, e.g.: `1234.5`,
+    /// it's displayed with the decimal point of the current locale, e.g.: `1234,5` in `de`.
     pub fn set_value(&self, text: impl Into<SharedString>, cx: &mut ViewContext<Self>) {
+        let text: SharedString = text.into();
+        let decimal = NumberFormat::current().decimal;
+        // The `万` and `亿` units are always parsed with `.`.
+        let text = if self.cjk_units || decimal == '.' {
+            text
+        } else {
+            text.replace('.', &decimal.to_string()).into()
+        };
+
         self.input.update(cx, |input, cx| input.set_text(text, cx))
     }
 
@@ -135,6 +216,7 @@ impl EventEmitter<NumberInputEvent> for NumberInput {}
 
 impl Render for NumberInput {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        self.update_locale_pattern(cx);
         let focused = self.input.focus_handle(cx).is_focused(cx);
 
         h_flex()
@@ -0,0 +1,161 @@
+use gpui::{
+    canvas, fill, point, px, size, Bounds, Canvas, ContentMask, Hsla, Path, Pixels, WindowContext,
+};
+
+use crate::theme::ActiveTheme as _;
+
+/// The spacing between the lines or dots of the patterns.
+const PATTERN_SPACING: f32 = 6.;
+/// The width of the lines or the size of the dots of the patterns.
+const PATTERN_STROKE: f32 = 1.5;
+
+/// The fill pattern of the data visualization, e.g.: the bars of the charts, the cells of the heatmaps,
+/// used with the colors to keep the series distinguishable without relying on the colors alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pattern {
+    Solid,
+    Diagonal,
+    Dots,
+    Horizontal,
+    Vertical,
+    Cross,
+}
+
+impl Pattern {
+    pub const ALL: [Pattern; 6] = [
+        Self::Solid,
+        Self::Diagonal,
+        Self::Dots,
+        Self::Horizontal,
+        Self::Vertical,
+        Self::Cross,
+    ];
+
+    /// Returns the pattern of the series at the index, the patterns are repeated if the index is out of range.
+    pub fn for_series(ix: usize) -> Self {
+        Self::ALL[ix % Self::ALL.len()]
+    }
+}
+
+/// Returns the offsets from `start` to cover `start..end` with the `spacing`.
+fn stripe_offsets(start: f32, end: f32, spacing: f32) -> Vec<f32> {
+    if spacing <= 0. || end <= start {
+        return vec![];
+    }
+
+    let count = ((end - start) / spacing).ceil() as usize;
+    (0..count).map(|ix| start + ix as f32 * spacing).collect()
+}
+
+/// Paint the pattern with the color in the bounds.
+///
+/// The [`Pattern::Solid`] fills the bounds with the color, the others paint the pattern
+/// with the color over the translucent color.
+pub fn paint_pattern(
+    bounds: Bounds<Pixels>,
+    pattern: Pattern,
+    color: Hsla,
+    cx: &mut WindowContext,
+) {
+    if pattern == Pattern::Solid {
+        cx.paint_quad(fill(bounds, color));
+        return;
+    }
+
+    cx.paint_quad(fill(bounds, color.opacity(0.3)));
+    cx.with_content_mask(Some(ContentMask { bounds }), |cx| {
+        let left = bounds.left().0;
+        let top = bounds.top().0;
+        let width = bounds.size.width.0;
+        let height = bounds.size.height.0;
+
+        if matches!(pattern, Pattern::Horizontal | Pattern::Cross) {
+            for y in stripe_offsets(top, top + height, PATTERN_SPACING) {
+                let line = Bounds::new(
+                    point(bounds.left(), px(y)),
+                    size(bounds.size.width, px(PATTERN_STROKE)),
+                );
+                cx.paint_quad(fill(line, color));
+            }
+        }
+
+        if matches!(pattern, Pattern::Vertical | Pattern::Cross) {
+            for x in stripe_offsets(left, left + width, PATTERN_SPACING) {
+                let line = Bounds::new(
+                    point(px(x), bounds.top()),
+                    size(px(PATTERN_STROKE), bounds.size.height),
+                );
+                cx.paint_quad(fill(line, color));
+            }
+        }
+
+        if pattern == Pattern::Dots {
+            for y in stripe_offsets(top, top + height, PATTERN_SPACING) {
+                for x in stripe_offsets(left, left + width, PATTERN_SPACING) {
+                    let dot = Bounds::new(
+                        point(px(x), px(y)),
+                        size(px(PATTERN_STROKE), px(PATTERN_STROKE)),
+                    );
+                    cx.paint_quad(fill(dot, color));
+                }
+            }
+        }
+
+        if pattern == Pattern::Diagonal {
+            // The stripes go from the bottom left to the top right, start from the left of
+            // the bounds by the height to cover the bottom left corner.
+            let stroke = PATTERN_STROKE * std::f32::consts::SQRT_2;
+            for x in stripe_offsets(left - height, left + width, PATTERN_SPACING) {
+                let mut path = Path::new(point(px(x), px(top + height)));
+                path.line_to(point(px(x + stroke), px(top + height)));
+                path.line_to(point(px(x + stroke + height), px(top)));
+                path.line_to(point(px(x + height), px(top)));
+                cx.paint_path(path, color);
+            }
+        }
+    });
+}
+
+/// An element to fill the bounds with the pattern, the pattern is [`Pattern::Solid`]
+/// if the [`Theme::chart_patterns`](crate::theme::Theme::chart_patterns) is disabled.
+///
+/// ```ignore
+/// div()
+///     .relative()
+///     .w(px(120.))
+///     .h_4()
+///     .child(pattern_fill(Pattern::for_series(ix), cx.theme().chart_color(ix)).absolute().size_full())
+/// ```
+pub fn pattern_fill(pattern: Pattern, color: Hsla) -> Canvas<()> {
+    canvas(
+        |_, _| {},
+        move |bounds, _, cx| {
+            let pattern = if cx.theme().chart_patterns {
+                pattern
+            } else {
+                Pattern::Solid
+            };
+            paint_pattern(bounds, pattern, color, cx);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stripe_offsets, Pattern};
+
+    #[test]
+    fn test_stripe_offsets() {
+        assert_eq!(stripe_offsets(0., 12., 6.), vec![0., 6.]);
+        assert_eq!(stripe_offsets(10., 23., 6.), vec![10., 16., 22.]);
+        assert_eq!(stripe_offsets(10., 10., 6.), Vec::<f32>::new());
+        assert_eq!(stripe_offsets(0., 10., 0.), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_for_series() {
+        assert_eq!(Pattern::for_series(0), Pattern::Solid);
+        assert_eq!(Pattern::for_series(1), Pattern::Diagonal);
+        assert_eq!(Pattern::for_series(Pattern::ALL.len() + 2), Pattern::Dots);
+    }
+}
@@ -1,12 +1,18 @@
 use gpui::{
-    actions, anchored, deferred, div, prelude::FluentBuilder as _, px, AnyElement, AppContext,
-    Bounds, Corner, DismissEvent, DispatchPhase, Element, ElementId, EventEmitter, FocusHandle,
-    FocusableView, GlobalElementId, Hitbox, InteractiveElement as _, IntoElement, KeyBinding,
-    LayoutId, ManagedView, MouseButton, MouseDownEvent, ParentElement, Pixels, Point, Render,
-    Style, StyleRefinement, Styled, View, ViewContext, VisualContext, WindowContext,
+    actions, deferred, div, prelude::FluentBuilder as _, px, AnyElement, AppContext, Bounds,
+    Corner, DismissEvent, DispatchPhase, Element, ElementId, EventEmitter, FocusHandle,
+    FocusableView, GlobalElementId, Hitbox, InteractiveElement as _, IntoElement, LayoutId,
+    ManagedView, MouseButton, MouseDownEvent, ParentElement, Pixels, Render, Style,
+    StyleRefinement, Styled, View, ViewContext, VisualContext, WindowContext,
 };
 use std::{cell::RefCell, rc::Rc};
 
+use crate::keymap::{self, KeymapEntry};
+use crate::primitives::{
+    placement::{anchored_to, FloatingPlacement},
+    DismissableExt as _,
+};
+use crate::theme::ActiveTheme as _;
 use crate::{Selectable, StyledExt as _};
 
 const CONTEXT: &str = "Popover";
@@ -14,7 +20,7 @@ const CONTEXT: &str = "Popover";
 actions!(popover, [Escape]);
 
 pub fn init(cx: &mut AppContext) {
-    cx.bind_keys([KeyBinding::new("escape", Escape, Some(CONTEXT))])
+    keymap::bind_keys(cx, [KeymapEntry::new("escape", Escape, Some(CONTEXT))])
 }
 
 pub struct PopoverContent {
@@ -65,6 +71,9 @@ impl Render for PopoverContent {
 pub struct Popover<M: ManagedView> {
     id: ElementId,
     anchor: Corner,
+    offset: Pixels,
+    window_padding: Pixels,
+    arrow: bool,
     trigger: Option<Box<dyn FnOnce(bool, &WindowContext) -> AnyElement + 'static>>,
     content: Option<Rc<dyn Fn(&mut WindowContext) -> View<M> + 'static>>,
     /// Style for trigger element.
@@ -83,6 +92,9 @@ where
         Self {
             id: id.into(),
             anchor: Corner::TopLeft,
+            offset: px(6.),
+            window_padding: px(8.),
+            arrow: false,
             trigger: None,
             trigger_style: None,
             content: None,
@@ -91,11 +103,32 @@ where
         }
     }
 
+    /// Set the corner of the popover attached to the trigger, default is `Corner::TopLeft`.
+    ///
+    /// The popover is flipped to the other side of the trigger if there is not enough space in the window.
     pub fn anchor(mut self, anchor: Corner) -> Self {
         self.anchor = anchor;
         self
     }
 
+    /// Set the gap between the trigger and the popover, default: 6px
+    pub fn offset(mut self, offset: Pixels) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the min distance from the popover to the window edges, default: 8px
+    pub fn window_padding(mut self, padding: Pixels) -> Self {
+        self.window_padding = padding;
+        self
+    }
+
+    /// Set true to show an arrow pointing to the trigger, default: false
+    pub fn arrow(mut self, arrow: bool) -> Self {
+        self.arrow = arrow;
+        self
+    }
+
     /// Set the mouse button to trigger the popover, default is `MouseButton::Left`.
     pub fn mouse_button(mut self, mouse_button: MouseButton) -> Self {
         self.mouse_button = mouse_button;
@@ -147,15 +180,6 @@ where
         (trigger)(is_open, cx)
     }
 
-    fn resolved_corner(&self, bounds: Bounds<Pixels>) -> Point<Pixels> {
-        bounds.corner(match self.anchor {
-            Corner::TopLeft => Corner::BottomLeft,
-            Corner::TopRight => Corner::BottomRight,
-            Corner::BottomLeft => Corner::TopLeft,
-            Corner::BottomRight => Corner::TopRight,
-        })
-    }
-
     fn with_element_state<R>(
         &mut self,
         id: &GlobalElementId,
@@ -249,16 +273,17 @@ impl<M: ManagedView> Element for Popover<M> {
             if let Some(content_view) = element_state.content_view.borrow_mut().as_mut() {
                 is_open = true;
 
-                let mut anchored = anchored()
-                    .snap_to_window_with_margin(px(8.))
-                    .anchor(view.anchor);
-                if let Some(trigger_bounds) = element_state.trigger_bounds {
-                    anchored = anchored.position(view.resolved_corner(trigger_bounds));
-                }
+                let placement = FloatingPlacement::from_corner(view.anchor)
+                    .offset(view.offset)
+                    .window_padding(view.window_padding);
+                let anchored =
+                    anchored_to(element_state.trigger_bounds.unwrap_or_default(), placement)
+                        .when(view.arrow && !view.no_style, |this| {
+                            this.arrow(cx.theme().popover, cx.theme().border)
+                        });
 
                 let mut element = {
                     let content_view_mut = element_state.content_view.clone();
-                    let anchor = view.anchor;
                     let no_style = view.no_style;
                     deferred(
                         anchored.child(
@@ -266,13 +291,9 @@ impl<M: ManagedView> Element for Popover<M> {
                                 .size_full()
                                 .occlude()
                                 .when(!no_style, |this| this.popover_style(cx))
-                                .map(|this| match anchor {
-                                    Corner::TopLeft | Corner::TopRight => this.top_1p5(),
-                                    Corner::BottomLeft | Corner::BottomRight => this.bottom_1p5(),
-                                })
                                 .child(content_view.clone())
                                 .when(!no_style, |this| {
-                                    this.on_mouse_down_out(move |_, cx| {
+                                    this.on_dismiss(move |cx| {
                                         // Update the element_state.content_view to `None`,
                                         // so that the `paint`` method will not paint it.
                                         *content_view_mut.borrow_mut() = None;
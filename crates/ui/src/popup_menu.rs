@@ -4,31 +4,43 @@ use std::rc::Rc;
 
 use gpui::{
     actions, div, prelude::FluentBuilder, px, Action, AppContext, DismissEvent, EventEmitter,
-    FocusHandle, InteractiveElement, IntoElement, KeyBinding, ParentElement, Pixels, Render,
-    SharedString, View, ViewContext, VisualContext as _, WindowContext,
+    FocusHandle, InteractiveElement, IntoElement, ParentElement, Pixels, Render, SharedString,
+    View, ViewContext, VisualContext as _, WindowContext,
 };
 use gpui::{
-    anchored, canvas, rems, AnyElement, Bounds, Corner, Edges, FocusableView, Keystroke,
-    ScrollHandle, StatefulInteractiveElement, Styled, WeakView,
+    canvas, rems, AnyElement, Bounds, Corner, FocusableView, Keystroke, ScrollHandle,
+    StatefulInteractiveElement, Styled, WeakView,
 };
 
 use crate::scroll::{Scrollbar, ScrollbarState};
 use crate::StyledExt;
 use crate::{
-    button::Button, h_flex, list::ListItem, popover::Popover, theme::ActiveTheme, v_flex, Icon,
-    IconName, Selectable, Sizable as _,
+    button::Button,
+    h_flex,
+    keymap::{self, KeymapEntry},
+    list::ListItem,
+    popover::Popover,
+    primitives::{
+        listbox,
+        placement::{anchored_to, Align, FloatingPlacement, Side},
+    },
+    theme::ActiveTheme,
+    v_flex, Icon, IconName, Selectable, Sizable as _,
 };
 
 actions!(menu, [Confirm, Dismiss, SelectNext, SelectPrev]);
 
 pub fn init(cx: &mut AppContext) {
     let context = Some("PopupMenu");
-    cx.bind_keys([
-        KeyBinding::new("enter", Confirm, context),
-        KeyBinding::new("escape", Dismiss, context),
-        KeyBinding::new("up", SelectPrev, context),
-        KeyBinding::new("down", SelectNext, context),
-    ]);
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("enter", Confirm, context),
+            KeymapEntry::new("escape", Dismiss, context),
+            KeymapEntry::new("up", SelectPrev, context),
+            KeymapEntry::new("down", SelectNext, context),
+        ],
+    );
 }
 
 pub trait PopupMenuExt: Styled + Selectable + IntoElement + 'static {
@@ -65,6 +77,7 @@ enum PopupMenuItem {
         icon: Option<Icon>,
         label: SharedString,
         action: Option<Box<dyn Action>>,
+        disabled: bool,
         handler: Rc<dyn Fn(&mut WindowContext)>,
     },
     ElementItem {
@@ -80,7 +93,10 @@ enum PopupMenuItem {
 
 impl PopupMenuItem {
     fn is_clickable(&self) -> bool {
-        !matches!(self, PopupMenuItem::Separator)
+        !matches!(
+            self,
+            PopupMenuItem::Separator | PopupMenuItem::Item { disabled: true, .. }
+        )
     }
 
     fn is_separator(&self) -> bool {
@@ -103,6 +119,8 @@ pub struct PopupMenu {
     max_width: Pixels,
     hovered_menu_ix: Option<usize>,
     bounds: Bounds<Pixels>,
+    /// The bounds of the hovered submenu item to place the submenu.
+    submenu_anchor: Bounds<Pixels>,
 
     scrollable: bool,
     scroll_handle: ScrollHandle,
@@ -134,6 +152,7 @@ impl PopupMenu {
                 has_icon: false,
                 hovered_menu_ix: None,
                 bounds: Bounds::default(),
+                submenu_anchor: Bounds::default(),
                 scrollable: false,
                 scroll_handle: ScrollHandle::default(),
                 scroll_state: Rc::new(Cell::new(ScrollbarState::default())),
@@ -183,6 +202,7 @@ impl PopupMenu {
             icon: None,
             label: label.into(),
             action: None,
+            disabled: false,
             handler: Rc::new(move |cx| cx.open_url(&href)),
         });
         self
@@ -200,6 +220,7 @@ impl PopupMenu {
             icon: Some(icon.into()),
             label: label.into(),
             action: None,
+            disabled: false,
             handler: Rc::new(move |cx| cx.open_url(&href)),
         });
         self
@@ -232,6 +253,24 @@ impl PopupMenu {
         self
     }
 
+    /// Add Menu Item that can be disabled, the disabled item is shown but can't be clicked.
+    pub fn menu_with_disabled(
+        mut self,
+        label: impl Into<SharedString>,
+        action: Box<dyn Action>,
+        disabled: bool,
+    ) -> Self {
+        self.add_menu_item(label, None, action);
+        if let Some(PopupMenuItem::Item {
+            disabled: item_disabled,
+            ..
+        }) = self.menu_items.last_mut()
+        {
+            *item_disabled = disabled;
+        }
+        self
+    }
+
     /// Add Menu Item with custom element render.
     pub fn menu_with_element<F, E>(mut self, builder: F, action: Box<dyn Action>) -> Self
     where
@@ -285,6 +324,7 @@ impl PopupMenu {
             icon,
             label: label.into(),
             action: Some(action.boxed_clone()),
+            disabled: false,
             handler: self.wrap_handler(action),
         });
         self
@@ -352,11 +392,16 @@ impl PopupMenu {
         self.menu_items.is_empty()
     }
 
-    fn clickable_menu_items(&self) -> impl Iterator<Item = (usize, &PopupMenuItem)> {
-        self.menu_items
-            .iter()
-            .enumerate()
-            .filter(|(_, item)| item.is_clickable())
+    /// Returns true if the action can't be handled in the current focus, then the item will be disabled.
+    ///
+    /// The availability is unknown when the menu is tracking another focus handle by the `track_focus`,
+    /// the action will be dispatched to that focus handle.
+    fn is_action_unavailable(&self, action: Option<&dyn Action>, cx: &WindowContext) -> bool {
+        if self.action_focus_handle.is_some() {
+            return false;
+        }
+
+        action.map_or(false, |action| !cx.is_action_available(action))
     }
 
     fn on_click(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
@@ -371,7 +416,12 @@ impl PopupMenu {
             Some(index) => {
                 let item = self.menu_items.get(index);
                 match item {
-                    Some(PopupMenuItem::Item { handler, .. }) => {
+                    Some(PopupMenuItem::Item {
+                        handler,
+                        action,
+                        disabled: false,
+                        ..
+                    }) if !self.is_action_unavailable(action.as_deref(), cx) => {
                         handler(cx);
                         self.dismiss(&Dismiss, cx)
                     }
@@ -387,26 +437,27 @@ impl PopupMenu {
     }
 
     fn select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
-        let count = self.clickable_menu_items().count();
-        if count > 0 {
-            let ix = self
-                .selected_index
-                .map(|index| if index == count - 1 { 0 } else { index + 1 })
-                .unwrap_or(0);
-
-            self.selected_index = Some(ix);
+        let ix = listbox::next_option(
+            self.selected_index,
+            self.menu_items.len(),
+            |ix| !self.menu_items[ix].is_clickable(),
+            true,
+        );
+        if ix.is_some() {
+            self.selected_index = ix;
             cx.notify();
         }
     }
 
     fn select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
-        let count = self.clickable_menu_items().count();
-        if count > 0 {
-            let ix = self
-                .selected_index
-                .map(|index| if index == count - 1 { 0 } else { index - 1 })
-                .unwrap_or(count - 1);
-            self.selected_index = Some(ix);
+        let ix = listbox::prev_option(
+            self.selected_index,
+            self.menu_items.len(),
+            |ix| !self.menu_items[ix].is_clickable(),
+            true,
+        );
+        if ix.is_some() {
+            self.selected_index = ix;
             cx.notify();
         }
     }
@@ -488,8 +539,6 @@ impl Render for PopupMenu {
         let view = cx.view().clone();
         let has_icon = self.menu_items.iter().any(|item| item.has_icon());
         let items_count = self.menu_items.len();
-        let max_width = self.max_width;
-        let bounds = self.bounds;
 
         let window_haft_height = cx.window_bounds().get_bounds().size.height * 0.5;
         let max_height = window_haft_height.min(px(450.));
@@ -581,16 +630,31 @@ impl Render for PopupMenu {
                                                 icon,
                                                 label,
                                                 action,
+                                                disabled,
                                                 ..
                                             } => {
+                                                let disabled = *disabled
+                                                    || self.is_action_unavailable(
+                                                        action.as_deref(),
+                                                        cx,
+                                                    );
                                                 let action = action
                                                     .as_ref()
                                                     .map(|action| action.boxed_clone());
                                                 let key = Self::render_keybinding(action, cx);
 
-                                                this.on_click(cx.listener(move |this, _, cx| {
-                                                    this.on_click(ix, cx)
-                                                }))
+                                                this.map(|this| {
+                                                    if disabled {
+                                                        this.disabled(true)
+                                                            .text_color(cx.theme().muted_foreground)
+                                                    } else {
+                                                        this.on_click(cx.listener(
+                                                            move |this, _, cx| {
+                                                                this.on_click(ix, cx)
+                                                            },
+                                                        ))
+                                                    }
+                                                })
                                                 .child(
                                                     h_flex()
                                                         .h(ITEM_HEIGHT)
@@ -641,52 +705,43 @@ impl Render for PopupMenu {
                                                                         ),
                                                                 ),
                                                         )
-                                                        .when_some(
-                                                            self.hovered_menu_ix,
-                                                            |this, hovered_ix| {
-                                                                let (anchor, left) =
-                                                                    if cx.bounds().size.width
-                                                                        - bounds.origin.x
-                                                                        < max_width
-                                                                    {
-                                                                        (Corner::TopRight, -px(15.))
-                                                                    } else {
-                                                                        (
-                                                                            Corner::TopLeft,
-                                                                            bounds.size.width
-                                                                                - px(10.),
-                                                                        )
-                                                                    };
-
-                                                                let top = if bounds.origin.y
-                                                                    + bounds.size.height
-                                                                    > cx.bounds().size.height
-                                                                {
-                                                                    px(32.)
-                                                                } else {
-                                                                    -px(10.)
-                                                                };
-
-                                                                if hovered_ix == ix {
-                                                                    this.child(
-                                                                anchored()
-                                                                    .anchor(anchor)
+                                                        .when(self.hovered_menu_ix == Some(ix), |this| {
+                                                            let view = cx.view().clone();
+                                                            let placement = FloatingPlacement::new(
+                                                                Side::Right,
+                                                                Align::Start,
+                                                            )
+                                                            .offset(px(4.));
+
+                                                            this.relative()
+                                                                .child(
+                                                                    canvas(
+                                                                        // Place the submenu again if the item moved, e.g.: hover another item.
+                                                                        move |bounds, cx| {
+                                                                            view.update(cx, |r, cx| {
+                                                                                if r.submenu_anchor != bounds {
+                                                                                    r.submenu_anchor = bounds;
+                                                                                    cx.notify();
+                                                                                }
+                                                                            })
+                                                                        },
+                                                                        |_, _, _| {},
+                                                                    )
+                                                                    .absolute()
+                                                                    .size_full(),
+                                                                )
+                                                                .child(
+                                                                    anchored_to(
+                                                                        self.submenu_anchor,
+                                                                        placement,
+                                                                    )
                                                                     .child(
                                                                         div()
                                                                             .occlude()
-                                                                            .top(top)
-                                                                            .left(left)
                                                                             .child(menu.clone()),
-                                                                    )
-                                                                    .snap_to_window_with_margin(
-                                                                        Edges::all(px(8.)),
                                                                     ),
-                                                            )
-                                                                } else {
-                                                                    this
-                                                                }
-                                                            },
-                                                        ),
+                                                                )
+                                                        }),
                                                 ),
                                         }
                                     }),
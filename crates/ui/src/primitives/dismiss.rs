@@ -0,0 +1,34 @@
+use std::rc::Rc;
+
+use gpui::{InteractiveElement, WindowContext};
+
+/// Dismiss the element by pressing `escape` or clicking outside, e.g.: a popover, a menu.
+pub trait DismissableExt: InteractiveElement + Sized {
+    /// Set the handler to dismiss the element by pressing `escape` or clicking outside.
+    ///
+    /// The `escape` key is only handled when the element or its children are focused.
+    fn on_dismiss(self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        let handler = Rc::new(handler);
+
+        self.on_escape({
+            let handler = handler.clone();
+            move |cx| handler(cx)
+        })
+        .on_mouse_down_out(move |_, cx| handler(cx))
+    }
+
+    /// Set the handler to dismiss the element by pressing `escape` only, e.g.: a modal
+    /// that handles the click on its overlay by itself.
+    ///
+    /// The bound actions of the focused children (e.g.: closing a completion menu) run first.
+    fn on_escape(self, handler: impl Fn(&mut WindowContext) + 'static) -> Self {
+        self.on_key_down(move |event, cx| {
+            if event.keystroke.key == "escape" {
+                handler(cx);
+                cx.stop_propagation();
+            }
+        })
+    }
+}
+
+impl<E: InteractiveElement> DismissableExt for E {}
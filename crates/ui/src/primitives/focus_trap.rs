@@ -0,0 +1,44 @@
+use gpui::{FocusHandle, InteractiveElement};
+
+/// Keep the focus cycling in the element by the `tab` and `shift-tab` keys, e.g.: a dialog.
+pub trait FocusTrapExt: InteractiveElement + Sized {
+    /// Set the focus handles to cycle in order, the first one is focused if the focus is outside.
+    fn focus_trap(self, handles: Vec<FocusHandle>) -> Self {
+        self.capture_key_down(move |event, cx| {
+            if event.keystroke.key != "tab" || handles.is_empty() {
+                return;
+            }
+
+            let focused_ix = handles.iter().position(|handle| handle.is_focused(cx));
+            let ix = cycle_index(focused_ix, handles.len(), !event.keystroke.modifiers.shift);
+            cx.focus(&handles[ix]);
+            cx.stop_propagation();
+        })
+    }
+}
+
+impl<E: InteractiveElement> FocusTrapExt for E {}
+
+/// Returns the index to focus next, `len` must be greater than 0.
+pub(crate) fn cycle_index(current: Option<usize>, len: usize, forward: bool) -> usize {
+    match (current, forward) {
+        (None, true) => 0,
+        (None, false) => len - 1,
+        (Some(ix), true) => (ix + 1) % len,
+        (Some(ix), false) => ix.checked_sub(1).unwrap_or(len - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cycle_index;
+
+    #[test]
+    fn test_cycle_index() {
+        assert_eq!(cycle_index(None, 3, true), 0);
+        assert_eq!(cycle_index(None, 3, false), 2);
+        assert_eq!(cycle_index(Some(1), 3, true), 2);
+        assert_eq!(cycle_index(Some(2), 3, true), 0);
+        assert_eq!(cycle_index(Some(0), 3, false), 2);
+    }
+}
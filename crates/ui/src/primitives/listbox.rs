@@ -0,0 +1,134 @@
+//! The keyboard behavior of a list of options, e.g.: the menu items, the dropdown options.
+//!
+//! The functions return the index of the option to activate, the disabled options
+//! (e.g.: the separators, the disabled items) are skipped.
+
+/// Returns the next enabled option after the active option, or the first one if there is no active option.
+///
+/// If `looped` is true, move to the first option when the active option is the last one.
+pub fn next_option(
+    active: Option<usize>,
+    len: usize,
+    is_disabled: impl Fn(usize) -> bool,
+    looped: bool,
+) -> Option<usize> {
+    let Some(active) = active.filter(|ix| *ix < len) else {
+        return first_option(len, is_disabled);
+    };
+
+    (active + 1..len)
+        .find(|ix| !is_disabled(*ix))
+        .or_else(|| {
+            if looped {
+                (0..active).find(|ix| !is_disabled(*ix))
+            } else {
+                None
+            }
+        })
+        .or(Some(active))
+}
+
+/// Returns the previous enabled option before the active option, or the last one if there is no active option.
+///
+/// If `looped` is true, move to the last option when the active option is the first one.
+pub fn prev_option(
+    active: Option<usize>,
+    len: usize,
+    is_disabled: impl Fn(usize) -> bool,
+    looped: bool,
+) -> Option<usize> {
+    let Some(active) = active.filter(|ix| *ix < len) else {
+        return last_option(len, is_disabled);
+    };
+
+    (0..active)
+        .rev()
+        .find(|ix| !is_disabled(*ix))
+        .or_else(|| {
+            if looped {
+                (active + 1..len).rev().find(|ix| !is_disabled(*ix))
+            } else {
+                None
+            }
+        })
+        .or(Some(active))
+}
+
+/// Returns the first enabled option, e.g.: by pressing `home`.
+pub fn first_option(len: usize, is_disabled: impl Fn(usize) -> bool) -> Option<usize> {
+    (0..len).find(|ix| !is_disabled(*ix))
+}
+
+/// Returns the last enabled option, e.g.: by pressing `end`.
+pub fn last_option(len: usize, is_disabled: impl Fn(usize) -> bool) -> Option<usize> {
+    (0..len).rev().find(|ix| !is_disabled(*ix))
+}
+
+/// Returns the next enabled option that the label starts with the `query` (ignore case),
+/// searching from the option after the active one, e.g.: type `p` to jump to the "Paste".
+///
+/// The `label` returns None for the options can't be matched.
+pub fn typeahead_option<S: AsRef<str>>(
+    active: Option<usize>,
+    len: usize,
+    query: &str,
+    label: impl Fn(usize) -> Option<S>,
+    is_disabled: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    if query.is_empty() || len == 0 {
+        return None;
+    }
+
+    let query = query.to_lowercase();
+    let start = active.map_or(0, |ix| ix + 1);
+    (0..len).map(|i| (start + i) % len).find(|ix| {
+        !is_disabled(*ix)
+            && label(*ix).map_or(false, |label| {
+                label.as_ref().to_lowercase().starts_with(&query)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listbox_navigation() {
+        // The option 1 and 3 are disabled.
+        let disabled = |ix: usize| ix == 1 || ix == 3;
+
+        assert_eq!(next_option(None, 5, disabled, true), Some(0));
+        assert_eq!(next_option(Some(0), 5, disabled, true), Some(2));
+        assert_eq!(next_option(Some(2), 5, disabled, true), Some(4));
+        assert_eq!(next_option(Some(4), 5, disabled, true), Some(0));
+        assert_eq!(next_option(Some(4), 5, disabled, false), Some(4));
+
+        assert_eq!(prev_option(None, 5, disabled, true), Some(4));
+        assert_eq!(prev_option(Some(4), 5, disabled, true), Some(2));
+        assert_eq!(prev_option(Some(0), 5, disabled, true), Some(4));
+        assert_eq!(prev_option(Some(0), 5, disabled, false), Some(0));
+
+        assert_eq!(first_option(5, |ix| ix < 2), Some(2));
+        assert_eq!(last_option(5, |ix| ix > 2), Some(2));
+        assert_eq!(next_option(None, 0, disabled, true), None);
+        assert_eq!(prev_option(Some(3), 3, |_| true, true), None);
+    }
+
+    #[test]
+    fn test_typeahead_option() {
+        let labels = ["Copy", "Cut", "Paste", "Paste Special"];
+        let label = |ix: usize| labels.get(ix).copied();
+
+        assert_eq!(typeahead_option(None, 4, "c", label, |_| false), Some(0));
+        assert_eq!(typeahead_option(Some(0), 4, "c", label, |_| false), Some(1));
+        assert_eq!(typeahead_option(Some(1), 4, "C", label, |_| false), Some(0));
+        assert_eq!(
+            typeahead_option(Some(2), 4, "paste", label, |_| false),
+            Some(3)
+        );
+        assert_eq!(typeahead_option(None, 4, "p", label, |ix| ix == 2), Some(3));
+        assert_eq!(typeahead_option(None, 4, "x", label, |_| false), None);
+        assert_eq!(typeahead_option(None, 4, "", label, |_| false), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! The headless primitives, the interaction behaviors without any styling.
+//!
+//! The styled components are built on them, so a design system can render its own elements
+//! and keep the same keyboard and focus behaviors:
+//!
+//! - [`listbox`]: Move the active option by the keyboard, skip the disabled options and typeahead,
+//!   used by [`PopupMenu`](crate::popup_menu::PopupMenu) and the input completion menu.
+//! - [`DismissableExt`]: Dismiss the element by pressing `escape` or clicking outside,
+//!   used by [`Popover`](crate::popover::Popover) and [`Modal`](crate::modal::Modal).
+//! - [`FocusTrapExt`]: Keep the `tab` focus cycling in the element,
+//!   used by [`Modal::focus_trap`](crate::modal::Modal::focus_trap).
+//! - [`placement`]: Place the floating element next to the anchor, flip and shift it to keep in the window.
+//!
+//! ```ignore
+//! div()
+//!     .track_focus(&self.focus_handle)
+//!     .on_dismiss(cx.listener(|this, cx| this.close(cx)))
+//!     .focus_trap(vec![self.ok_handle.clone(), self.cancel_handle.clone()])
+//!     .on_key_down(cx.listener(|this, event: &KeyDownEvent, cx| {
+//!         let is_disabled = |ix| this.items[ix].disabled;
+//!         this.active_ix = match event.keystroke.key.as_str() {
+//!             "down" => listbox::next_option(this.active_ix, this.items.len(), is_disabled, true),
+//!             "up" => listbox::prev_option(this.active_ix, this.items.len(), is_disabled, true),
+//!             _ => return,
+//!         };
+//!         cx.notify();
+//!     }))
+//! ```
+mod dismiss;
+mod focus_trap;
+pub mod listbox;
+pub mod placement;
+
+pub use dismiss::*;
+pub use focus_trap::*;
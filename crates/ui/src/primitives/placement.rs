@@ -0,0 +1,440 @@
+//! Position a floating element (e.g.: a popover, a dropdown menu) next to the anchor bounds,
+//! flip it to the other side if there is not enough space, and shift it to keep it in the window.
+
+use gpui::{
+    point, px, AnyElement, Bounds, Corner, Display, Element, ElementId, GlobalElementId, Hsla,
+    IntoElement, LayoutId, ParentElement, Path, Pixels, Point, Position, Size, Style,
+    WindowContext,
+};
+use smallvec::SmallVec;
+
+/// The half width of the arrow base, also the height of the arrow.
+const ARROW_SIZE: Pixels = px(5.);
+/// The min distance from the arrow to the corners, to avoid the arrow on the rounded corners.
+const ARROW_MARGIN: f32 = 8.;
+
+/// The side of the anchor to place the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    fn is_vertical(&self) -> bool {
+        matches!(self, Self::Top | Self::Bottom)
+    }
+}
+
+/// The alignment of the element to the anchor, on the cross axis of the [`Side`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FloatingPlacement {
+    pub side: Side,
+    pub align: Align,
+    /// The gap between the anchor and the element.
+    pub offset: Pixels,
+    /// The min distance from the element to the window edges.
+    pub window_padding: Pixels,
+}
+
+impl FloatingPlacement {
+    pub fn new(side: Side, align: Align) -> Self {
+        Self {
+            side,
+            align,
+            offset: px(6.),
+            window_padding: px(8.),
+        }
+    }
+
+    /// Create a placement by the corner of the element attached to the anchor,
+    /// e.g.: `Corner::TopLeft` is below the anchor and aligned to the left.
+    pub fn from_corner(corner: Corner) -> Self {
+        match corner {
+            Corner::TopLeft => Self::new(Side::Bottom, Align::Start),
+            Corner::TopRight => Self::new(Side::Bottom, Align::End),
+            Corner::BottomLeft => Self::new(Side::Top, Align::Start),
+            Corner::BottomRight => Self::new(Side::Top, Align::End),
+        }
+    }
+
+    /// Set the gap between the anchor and the element, default: 6px
+    pub fn offset(mut self, offset: Pixels) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Set the min distance from the element to the window edges, default: 8px
+    pub fn window_padding(mut self, padding: Pixels) -> Self {
+        self.window_padding = padding;
+        self
+    }
+
+    /// Resolve the bounds of the element with the `size` next to the `anchor`, in the window of the `window_size`.
+    pub fn resolve(
+        &self,
+        anchor: Bounds<Pixels>,
+        size: Size<Pixels>,
+        window_size: Size<Pixels>,
+    ) -> ResolvedPlacement {
+        let vertical = self.side.is_vertical();
+        let (anchor_main, anchor_cross) = if vertical {
+            (
+                (anchor.top().0, anchor.bottom().0),
+                (anchor.left().0, anchor.right().0),
+            )
+        } else {
+            (
+                (anchor.left().0, anchor.right().0),
+                (anchor.top().0, anchor.bottom().0),
+            )
+        };
+        let (size_main, size_cross, window_main, window_cross) = if vertical {
+            (
+                size.height.0,
+                size.width.0,
+                window_size.height.0,
+                window_size.width.0,
+            )
+        } else {
+            (
+                size.width.0,
+                size.height.0,
+                window_size.width.0,
+                window_size.height.0,
+            )
+        };
+        let padding = self.window_padding.0;
+
+        let (main, after) = main_axis(
+            anchor_main,
+            size_main,
+            self.offset.0,
+            (padding, window_main - padding),
+            matches!(self.side, Side::Bottom | Side::Right),
+        );
+        let cross = cross_axis(
+            anchor_cross,
+            size_cross,
+            self.align,
+            (padding, window_cross - padding),
+        );
+
+        let (side, origin) = match (vertical, after) {
+            (true, true) => (Side::Bottom, point(px(cross), px(main))),
+            (true, false) => (Side::Top, point(px(cross), px(main))),
+            (false, true) => (Side::Right, point(px(main), px(cross))),
+            (false, false) => (Side::Left, point(px(main), px(cross))),
+        };
+
+        ResolvedPlacement {
+            bounds: Bounds { origin, size },
+            side,
+            arrow: px(arrow_center(anchor_cross, cross, size_cross)),
+        }
+    }
+}
+
+/// The result of [`FloatingPlacement::resolve`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedPlacement {
+    /// The bounds of the element in the window.
+    pub bounds: Bounds<Pixels>,
+    /// The side of the anchor the element placed, it's flipped if different from the [`FloatingPlacement::side`].
+    pub side: Side,
+    /// The distance from the start of the element edge facing the anchor to the arrow,
+    /// the arrow points to the center of the anchor.
+    pub arrow: Pixels,
+}
+
+/// Returns the start of the element on the main axis, and whether it's placed after the anchor.
+///
+/// Flip to the other side if the preferred side has not enough space and the other side has more,
+/// then shift into the `limit` if it still overflows.
+fn main_axis(
+    (anchor_start, anchor_end): (f32, f32),
+    size: f32,
+    offset: f32,
+    (min, max): (f32, f32),
+    after: bool,
+) -> (f32, bool) {
+    let space_after = max - anchor_end - offset;
+    let space_before = anchor_start - offset - min;
+    let after = if after {
+        space_after >= size || space_after >= space_before
+    } else {
+        space_before < size && space_after > space_before
+    };
+
+    let start = if after {
+        anchor_end + offset
+    } else {
+        anchor_start - offset - size
+    };
+
+    (start.min(max - size).max(min), after)
+}
+
+/// Returns the start of the element on the cross axis, shifted into the `limit`.
+fn cross_axis(
+    (anchor_start, anchor_end): (f32, f32),
+    size: f32,
+    align: Align,
+    (min, max): (f32, f32),
+) -> f32 {
+    let start = match align {
+        Align::Start => anchor_start,
+        Align::Center => (anchor_start + anchor_end - size) / 2.,
+        Align::End => anchor_end - size,
+    };
+
+    start.min(max - size).max(min)
+}
+
+/// Returns the distance from the element start to the arrow pointing to the anchor center,
+/// keep it away from the corners of the element.
+fn arrow_center((anchor_start, anchor_end): (f32, f32), start: f32, size: f32) -> f32 {
+    let margin = ARROW_MARGIN + ARROW_SIZE.0;
+    let center = (anchor_start + anchor_end) / 2. - start;
+
+    center.min(size - margin).max(margin.min(size / 2.))
+}
+
+/// Returns the points of the arrow triangle on the `side` edge of the `bounds`,
+/// the `inset` moves the arrow into the bounds.
+fn arrow_points(
+    bounds: Bounds<Pixels>,
+    side: Side,
+    center: Pixels,
+    inset: Pixels,
+) -> [Point<Pixels>; 3] {
+    let size = ARROW_SIZE;
+    match side {
+        Side::Bottom => {
+            let (x, y) = (bounds.left() + center, bounds.top() + inset);
+            [point(x - size, y), point(x, y - size), point(x + size, y)]
+        }
+        Side::Top => {
+            let (x, y) = (bounds.left() + center, bounds.bottom() - inset);
+            [point(x - size, y), point(x, y + size), point(x + size, y)]
+        }
+        Side::Right => {
+            let (x, y) = (bounds.left() + inset, bounds.top() + center);
+            [point(x, y - size), point(x - size, y), point(x, y + size)]
+        }
+        Side::Left => {
+            let (x, y) = (bounds.right() - inset, bounds.top() + center);
+            [point(x, y - size), point(x + size, y), point(x, y + size)]
+        }
+    }
+}
+
+fn paint_arrow(points: [Point<Pixels>; 3], color: Hsla, cx: &mut WindowContext) {
+    let mut path = Path::new(points[0]);
+    path.line_to(points[1]);
+    path.line_to(points[2]);
+    cx.paint_path(path, color);
+}
+
+/// Create an element to place the children next to the `anchor` bounds in the window, with the [`FloatingPlacement`].
+///
+/// Unlike the `anchored`, the children are flipped to the other side of the anchor instead of overlapping it,
+/// when there is not enough space.
+///
+/// ```ignore
+/// deferred(
+///     anchored_to(self.bounds, FloatingPlacement::new(Side::Bottom, Align::Start))
+///         .arrow(cx.theme().popover, cx.theme().border)
+///         .child(menu),
+/// )
+/// ```
+pub fn anchored_to(anchor: Bounds<Pixels>, placement: FloatingPlacement) -> AnchoredTo {
+    AnchoredTo {
+        anchor,
+        placement,
+        arrow: None,
+        children: SmallVec::new(),
+    }
+}
+
+pub struct AnchoredTo {
+    anchor: Bounds<Pixels>,
+    placement: FloatingPlacement,
+    /// The background and border colors of the arrow.
+    arrow: Option<(Hsla, Hsla)>,
+    children: SmallVec<[AnyElement; 2]>,
+}
+
+impl AnchoredTo {
+    /// Show an arrow pointing to the anchor, with the background and border colors of the children.
+    pub fn arrow(mut self, background: impl Into<Hsla>, border: impl Into<Hsla>) -> Self {
+        self.arrow = Some((background.into(), border.into()));
+        self
+    }
+}
+
+impl ParentElement for AnchoredTo {
+    fn extend(&mut self, elements: impl IntoIterator<Item = AnyElement>) {
+        self.children.extend(elements)
+    }
+}
+
+impl IntoElement for AnchoredTo {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for AnchoredTo {
+    type RequestLayoutState = SmallVec<[LayoutId; 2]>;
+    type PrepaintState = Option<ResolvedPlacement>;
+
+    fn id(&self) -> Option<ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (LayoutId, Self::RequestLayoutState) {
+        let child_layout_ids = self
+            .children
+            .iter_mut()
+            .map(|child| child.request_layout(cx))
+            .collect::<SmallVec<_>>();
+
+        let style = Style {
+            position: Position::Absolute,
+            display: Display::Flex,
+            ..Style::default()
+        };
+        let layout_id = cx.request_layout(style, child_layout_ids.iter().copied());
+
+        (layout_id, child_layout_ids)
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        child_layout_ids: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        if child_layout_ids.is_empty() {
+            return None;
+        }
+
+        let mut child_min = point(Pixels::MAX, Pixels::MAX);
+        let mut child_max = Point::default();
+        for child_layout_id in child_layout_ids.iter() {
+            let child_bounds = cx.layout_bounds(*child_layout_id);
+            child_min = child_min.min(&child_bounds.origin);
+            child_max = child_max.max(&child_bounds.bottom_right());
+        }
+        let size: Size<Pixels> = (child_max - child_min).into();
+
+        let mut resolved = self
+            .placement
+            .resolve(self.anchor, size, cx.viewport_size());
+        let offset = resolved.bounds.origin - bounds.origin;
+        let offset = point(offset.x.round(), offset.y.round());
+        resolved.bounds.origin = bounds.origin + offset;
+
+        cx.with_element_offset(offset, |cx| {
+            for child in &mut self.children {
+                child.prepaint(cx);
+            }
+        });
+
+        Some(resolved)
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&GlobalElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        resolved: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        for child in &mut self.children {
+            child.paint(cx);
+        }
+
+        if let (Some((background, border)), Some(resolved)) = (self.arrow, resolved.as_ref()) {
+            let ResolvedPlacement {
+                bounds,
+                side,
+                arrow,
+            } = *resolved;
+            paint_arrow(arrow_points(bounds, side, arrow, px(0.)), border, cx);
+            paint_arrow(arrow_points(bounds, side, arrow, px(1.)), background, cx);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{arrow_center, cross_axis, main_axis, Align};
+
+    #[test]
+    fn test_main_axis() {
+        let limit = (8., 592.);
+        // Enough space below the anchor.
+        assert_eq!(main_axis((100., 120.), 200., 6., limit, true), (126., true));
+        // Flip to above the anchor.
+        assert_eq!(
+            main_axis((450., 470.), 200., 6., limit, true),
+            (244., false)
+        );
+        // Keep above the anchor if there is enough space.
+        assert_eq!(
+            main_axis((450., 470.), 200., 6., limit, false),
+            (244., false)
+        );
+        // Flip to below the anchor.
+        assert_eq!(
+            main_axis((100., 120.), 200., 6., limit, false),
+            (126., true)
+        );
+        // No enough space on both sides, use the larger side and shift into the window.
+        assert_eq!(main_axis((250., 270.), 400., 6., limit, true), (192., true));
+        assert_eq!(main_axis((350., 370.), 400., 6., limit, true), (8., false));
+        // Larger than the window.
+        assert_eq!(main_axis((100., 120.), 800., 6., limit, true), (8., true));
+    }
+
+    #[test]
+    fn test_cross_axis() {
+        let limit = (8., 392.);
+        assert_eq!(cross_axis((100., 200.), 150., Align::Start, limit), 100.);
+        assert_eq!(cross_axis((100., 200.), 150., Align::Center, limit), 75.);
+        assert_eq!(cross_axis((100., 200.), 150., Align::End, limit), 50.);
+        // Shift into the window.
+        assert_eq!(cross_axis((300., 380.), 150., Align::Start, limit), 242.);
+        assert_eq!(cross_axis((0., 50.), 150., Align::End, limit), 8.);
+        assert_eq!(cross_axis((0., 50.), 500., Align::Start, limit), 8.);
+    }
+
+    #[test]
+    fn test_arrow_center() {
+        assert_eq!(arrow_center((100., 200.), 100., 300.), 50.);
+        // Keep away from the corners.
+        assert_eq!(arrow_center((100., 110.), 100., 300.), 13.);
+        assert_eq!(arrow_center((390., 420.), 100., 300.), 287.);
+        // Too small element, at the center.
+        assert_eq!(arrow_center((100., 110.), 100., 20.), 10.);
+    }
+}
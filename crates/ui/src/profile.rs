@@ -0,0 +1,369 @@
+use std::{collections::BTreeMap, path::Path};
+
+use anyhow::{anyhow, Result};
+use gpui::{AppContext, Global};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    dock::{write_atomic, DockAreaState},
+    keymap::Keymap,
+    table::TableState,
+    theme::{ChartPalette, Theme, ThemeMode, ThemeVariant},
+};
+
+/// The theme options saved in the [`Profile`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileTheme {
+    pub mode: ThemeMode,
+    pub variant: ThemeVariant,
+    pub font_size: f32,
+    pub radius: f32,
+    pub lite: bool,
+    pub chart_palette: ChartPalette,
+    pub chart_patterns: bool,
+}
+
+impl Default for ProfileTheme {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::default(),
+            variant: ThemeVariant::default(),
+            font_size: 16.,
+            radius: 4.,
+            lite: false,
+            chart_palette: ChartPalette::default(),
+            chart_patterns: false,
+        }
+    }
+}
+
+impl ProfileTheme {
+    /// Returns the options of the `theme`.
+    pub fn from_theme(theme: &Theme) -> Self {
+        Self {
+            mode: theme.mode,
+            variant: theme.variant,
+            font_size: theme.font_size,
+            radius: theme.radius,
+            lite: theme.lite,
+            chart_palette: theme.chart_palette,
+            chart_patterns: theme.chart_patterns,
+        }
+    }
+
+    /// Apply the options to the global [`Theme`].
+    pub fn apply(&self, cx: &mut AppContext) {
+        let theme = Theme::global_mut(cx);
+        theme.variant = self.variant;
+        theme.lite = self.lite;
+        theme.chart_palette = self.chart_palette;
+        theme.chart_patterns = self.chart_patterns;
+        Theme::change(self.mode, cx);
+
+        let theme = Theme::global_mut(cx);
+        theme.font_size = self.font_size;
+        theme.radius = self.radius;
+        cx.refresh();
+    }
+}
+
+/// A named set of the user settings: the theme, the keymap, the layouts of the DockAreas
+/// and the states of the components.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub name: String,
+    pub theme: ProfileTheme,
+    /// The content of the user keymap file, see [`Keymap::load_user_keymap`].
+    pub keymap: Option<String>,
+    /// The layouts of the DockAreas, keyed by the [`DockArea::id`](crate::dock::DockArea::id).
+    pub layouts: BTreeMap<String, DockAreaState>,
+    /// The states of the tables, see [`Table::dump`](crate::table::Table::dump).
+    pub tables: BTreeMap<String, TableState>,
+    /// The states of the other components and the settings of the app, keyed by the app.
+    pub state: BTreeMap<String, serde_json::Value>,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the saved layout of the DockArea.
+    pub fn layout(&self, dock_area_id: &str) -> Option<&DockAreaState> {
+        self.layouts.get(dock_area_id)
+    }
+
+    /// Save the layout of the DockArea, e.g.: on the [`DockEvent::LayoutChanged`](crate::dock::DockEvent::LayoutChanged).
+    pub fn set_layout(&mut self, dock_area_id: impl Into<String>, state: DockAreaState) {
+        self.layouts.insert(dock_area_id.into(), state);
+    }
+
+    /// Returns the state of the component, `None` if not saved or the type is mismatched.
+    pub fn state<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        serde_json::from_value(self.state.get(key)?.clone()).ok()
+    }
+
+    /// Save the state of the component.
+    pub fn set_state<T: Serialize>(&mut self, key: impl Into<String>, value: &T) -> Result<()> {
+        self.state.insert(key.into(), serde_json::to_value(value)?);
+        Ok(())
+    }
+}
+
+/// The profiles of the app, only one profile is active at a time.
+///
+/// Switching the profile applies the theme and the keymap immediately, the DockAreas should
+/// observe the [`Profiles`] global to load the layouts of the active profile.
+///
+/// ```ignore
+/// Profiles::load("profiles.json", cx)?;
+///
+/// cx.observe_global::<Profiles>(|this, cx| {
+///     if let Some(state) = Profiles::global(cx).active().layout("main") {
+///         _ = this.dock_area.update(cx, |dock_area, cx| dock_area.load(state.clone(), cx));
+///     }
+/// })
+/// .detach();
+///
+/// Profiles::switch("Presentation", cx)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profiles {
+    active: String,
+    profiles: Vec<Profile>,
+}
+
+impl Global for Profiles {}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.into(),
+            profiles: vec![Profile::new(DEFAULT_PROFILE)],
+        }
+    }
+}
+
+const DEFAULT_PROFILE: &str = "Default";
+
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.set_global(Profiles::default());
+}
+
+impl Profiles {
+    pub fn global(cx: &AppContext) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Returns the active profile.
+    pub fn active(&self) -> &Profile {
+        self.profile(&self.active)
+            .unwrap_or_else(|| &self.profiles[0])
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    /// Add the profile, the name is suffixed with a number if it's already used, e.g.: `Work (2)`.
+    ///
+    /// Returns the name of the added profile.
+    pub fn add(&mut self, mut profile: Profile) -> String {
+        profile.name = unique_name(&profile.name, |name| self.profile(name).is_some());
+        let name = profile.name.clone();
+        self.profiles.push(profile);
+        name
+    }
+
+    /// Remove the profile, the last profile can't be removed.
+    ///
+    /// If the active profile is removed, the first profile will be active, call [`Profiles::switch`] to apply it.
+    pub fn remove(&mut self, name: &str) -> bool {
+        if self.profiles.len() <= 1 {
+            return false;
+        }
+
+        let len = self.profiles.len();
+        self.profiles.retain(|profile| profile.name != name);
+        if self.active == name {
+            self.active = self.profiles[0].name.clone();
+        }
+        self.profiles.len() != len
+    }
+
+    pub fn rename(&mut self, name: &str, new_name: impl Into<String>) -> Result<()> {
+        let new_name = new_name.into();
+        if new_name.trim().is_empty() {
+            return Err(anyhow!("the profile name can't be empty"));
+        }
+        if new_name != name && self.profile(&new_name).is_some() {
+            return Err(anyhow!("the profile `{}` already exists", new_name));
+        }
+
+        let profile = self
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.name == name)
+            .ok_or_else(|| anyhow!("the profile `{}` is not found", name))?;
+        profile.name = new_name.clone();
+        if self.active == name {
+            self.active = new_name;
+        }
+        Ok(())
+    }
+
+    /// Update the active profile, e.g.: to save the layout or the state of a component.
+    pub fn update_active(cx: &mut AppContext, f: impl FnOnce(&mut Profile)) {
+        let profiles = cx.global_mut::<Self>();
+        let active = profiles.active.clone();
+        if let Some(profile) = profiles
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.name == active)
+        {
+            f(profile);
+        }
+    }
+
+    /// Save the current theme into the active profile.
+    pub fn capture_theme(cx: &mut AppContext) {
+        let theme = ProfileTheme::from_theme(Theme::global(cx));
+        Self::update_active(cx, |profile| profile.theme = theme);
+    }
+
+    /// Switch to the profile, and apply the theme and the keymap of it.
+    ///
+    /// The current theme is captured into the previous profile before switching.
+    pub fn switch(name: &str, cx: &mut AppContext) -> Result<()> {
+        let profile = Self::global(cx)
+            .profile(name)
+            .cloned()
+            .ok_or_else(|| anyhow!("the profile `{}` is not found", name))?;
+
+        Self::capture_theme(cx);
+        cx.global_mut::<Self>().active = profile.name.clone();
+        Self::apply(&profile, cx)
+    }
+
+    pub(crate) fn apply(profile: &Profile, cx: &mut AppContext) -> Result<()> {
+        profile.theme.apply(cx);
+        match &profile.keymap {
+            Some(keymap) => Keymap::load_user_keymap(keymap, cx)?,
+            None => Keymap::clear_user_keymap(cx),
+        }
+        Ok(())
+    }
+
+    /// Load the profiles from the file and apply the active one.
+    pub fn load(path: impl AsRef<Path>, cx: &mut AppContext) -> Result<()> {
+        let json = std::fs::read_to_string(path)?;
+        let profiles = serde_json::from_str::<Self>(&json)?;
+        if profiles.profiles.is_empty() {
+            return Err(anyhow!("no profile in the file"));
+        }
+
+        let active = profiles.active().clone();
+        cx.set_global(profiles);
+        Self::apply(&active, cx)
+    }
+
+    /// Save all the profiles into the file atomically.
+    pub fn save(path: impl AsRef<Path>, cx: &mut AppContext) -> Result<()> {
+        Self::capture_theme(cx);
+        let json = serde_json::to_string_pretty(Self::global(cx))?;
+        write_atomic(path, json)?;
+        Ok(())
+    }
+
+    /// Export the profile into a file, e.g.: to move to another machine.
+    pub fn export(&self, name: &str, path: impl AsRef<Path>) -> Result<()> {
+        let profile = self
+            .profile(name)
+            .ok_or_else(|| anyhow!("the profile `{}` is not found", name))?;
+        write_atomic(path, serde_json::to_string_pretty(profile)?)?;
+        Ok(())
+    }
+
+    /// Import the profile exported by [`Profiles::export`], returns the name of the imported profile.
+    pub fn import(path: impl AsRef<Path>, cx: &mut AppContext) -> Result<String> {
+        let json = std::fs::read_to_string(path)?;
+        let profile = serde_json::from_str::<Profile>(&json)?;
+        Ok(cx.global_mut::<Self>().add(profile))
+    }
+}
+
+/// Returns the `name` if it's not used, otherwise the name with the smallest number suffix, e.g.: `Work (2)`.
+fn unique_name(name: &str, is_used: impl Fn(&str) -> bool) -> String {
+    let name = match name.trim() {
+        "" => DEFAULT_PROFILE,
+        name => name,
+    };
+    if !is_used(name) {
+        return name.to_string();
+    }
+
+    (2..)
+        .map(|i| format!("{} ({})", name, i))
+        .find(|name| !is_used(name))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{unique_name, Profile, Profiles};
+
+    #[test]
+    fn test_unique_name() {
+        let used = ["Work", "Work (2)"];
+        let is_used = |name: &str| used.contains(&name);
+        assert_eq!(unique_name("Home", is_used), "Home");
+        assert_eq!(unique_name("Work", is_used), "Work (3)");
+        assert_eq!(unique_name(" ", is_used), "Default");
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut profiles = Profiles::default();
+        assert_eq!(profiles.active().name, "Default");
+        assert!(!profiles.remove("Default"));
+
+        let mut work = Profile::new("Work");
+        work.set_state("table.widths", &vec![100, 200]).unwrap();
+        assert_eq!(profiles.add(work.clone()), "Work");
+        assert_eq!(profiles.add(work), "Work (2)");
+        assert_eq!(
+            profiles
+                .profile("Work")
+                .unwrap()
+                .state::<Vec<u32>>("table.widths"),
+            Some(vec![100, 200])
+        );
+        assert_eq!(
+            profiles
+                .profile("Work")
+                .unwrap()
+                .state::<String>("table.widths"),
+            None
+        );
+
+        assert!(profiles.rename("Work (2)", "Work").is_err());
+        assert!(profiles.rename("Work (2)", "").is_err());
+        profiles.rename("Default", "Home").unwrap();
+        assert_eq!(profiles.active().name, "Home");
+
+        assert!(profiles.remove("Home"));
+        assert_eq!(profiles.active().name, "Work");
+
+        let json = serde_json::to_string(&profiles).unwrap();
+        assert_eq!(serde_json::from_str::<Profiles>(&json).unwrap(), profiles);
+    }
+}
@@ -1,7 +1,10 @@
+use std::{f32::consts::PI, time::Duration};
+
 use crate::theme::ActiveTheme;
 use gpui::{
-    div, prelude::FluentBuilder, px, relative, IntoElement, ParentElement, RenderOnce, Styled,
-    WindowContext,
+    canvas, div, ease_in_out, point, prelude::FluentBuilder, px, relative, Animation,
+    AnimationExt as _, AnyElement, Bounds, Hsla, IntoElement, ParentElement, Path, Pixels,
+    RenderOnce, Styled, WindowContext,
 };
 
 /// A Progress bar element.
@@ -9,6 +12,7 @@ use gpui::{
 pub struct Progress {
     value: f32,
     height: f32,
+    indeterminate: bool,
 }
 
 impl Progress {
@@ -16,6 +20,7 @@ impl Progress {
         Progress {
             value: Default::default(),
             height: 8.,
+            indeterminate: false,
         }
     }
 
@@ -23,6 +28,12 @@ impl Progress {
         self.value = value;
         self
     }
+
+    /// Set the progress is indeterminate, a looping sweep will be shown instead of the value.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
 }
 
 impl RenderOnce for Progress {
@@ -39,18 +50,233 @@ impl RenderOnce for Progress {
             .h(px(self.height))
             .rounded(rounded)
             .bg(cx.theme().progress_bar.opacity(0.2))
+            .map(|this| {
+                if self.indeterminate && cx.theme().lite {
+                    // Show a static bar in the middle in the lite mode.
+                    this.overflow_hidden().child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left(relative(0.35))
+                            .h_full()
+                            .w(relative(0.3))
+                            .rounded(rounded)
+                            .bg(cx.theme().progress_bar),
+                    )
+                } else if self.indeterminate {
+                    this.overflow_hidden().child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .h_full()
+                            .w(relative(0.3))
+                            .rounded(rounded)
+                            .bg(cx.theme().progress_bar)
+                            .with_animation(
+                                "progress-indeterminate",
+                                Animation::new(Duration::from_secs_f64(1.2))
+                                    .repeat()
+                                    .with_easing(ease_in_out),
+                                |this, delta| this.left(relative(-0.3 + delta * 1.3)),
+                            ),
+                    )
+                } else {
+                    this.child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .left_0()
+                            .h_full()
+                            .w(relative_w)
+                            .bg(cx.theme().progress_bar)
+                            .map(|this| match self.value {
+                                v if v >= 100. => this.rounded(rounded),
+                                _ => this.rounded_l(rounded),
+                            }),
+                    )
+                }
+            })
+    }
+}
+
+/// A circular Progress ring element, with an optional label in the center.
+#[derive(IntoElement)]
+pub struct ProgressCircle {
+    value: f32,
+    size: Pixels,
+    stroke_width: Pixels,
+    indeterminate: bool,
+    color: Option<Hsla>,
+    label: Option<AnyElement>,
+}
+
+impl ProgressCircle {
+    pub fn new() -> Self {
+        Self {
+            value: Default::default(),
+            size: px(48.),
+            stroke_width: px(4.),
+            indeterminate: false,
+            color: None,
+            label: None,
+        }
+    }
+
+    /// Set the value of the progress, range of 0..100
+    pub fn value(mut self, value: f32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set the size of the ring, default: 48px
+    pub fn size(mut self, size: Pixels) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the stroke width of the ring, default: 4px
+    pub fn stroke_width(mut self, stroke_width: Pixels) -> Self {
+        self.stroke_width = stroke_width;
+        self
+    }
+
+    /// Set the progress is indeterminate, a looping spinning arc will be shown instead of the value.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    /// Set the color of the ring, default is the `progress_bar` color of the theme.
+    pub fn color(mut self, color: Hsla) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the label in the center of the ring, e.g.: `50%`.
+    pub fn label(mut self, label: impl IntoElement) -> Self {
+        self.label = Some(label.into_any_element());
+        self
+    }
+}
+
+/// Build a path of the arc with the `stroke_width`, the angles are in radians.
+fn arc_path(
+    bounds: Bounds<Pixels>,
+    stroke_width: Pixels,
+    start_angle: f32,
+    sweep_angle: f32,
+) -> Option<Path<Pixels>> {
+    if sweep_angle <= 0. {
+        return None;
+    }
+
+    let center = bounds.center();
+    let outer = bounds.size.width.0.min(bounds.size.height.0) / 2.;
+    let inner = (outer - stroke_width.0).max(0.);
+    let segments = ((sweep_angle / (2. * PI)) * 64.).ceil().max(2.) as usize;
+
+    let point_at = |radius: f32, ix: usize| {
+        let angle = start_angle + sweep_angle * ix as f32 / segments as f32;
+        point(
+            center.x + px(radius * angle.cos()),
+            center.y + px(radius * angle.sin()),
+        )
+    };
+
+    let mut path = Path::new(point_at(outer, 0));
+    for ix in 1..=segments {
+        path.line_to(point_at(outer, ix));
+    }
+    for ix in (0..=segments).rev() {
+        path.line_to(point_at(inner, ix));
+    }
+    Some(path)
+}
+
+impl RenderOnce for ProgressCircle {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let color = self.color.unwrap_or(cx.theme().progress_bar);
+        let track_color = color.opacity(0.2);
+        let stroke_width = self.stroke_width;
+        let value = self.value.clamp(0., 100.) / 100.;
+        // Start from the top, clockwise.
+        let start_angle = -PI / 2.;
+
+        div()
+            .relative()
+            .flex_shrink_0()
+            .size(self.size)
+            .flex()
+            .items_center()
+            .justify_center()
+            .text_xs()
             .child(
-                div()
-                    .absolute()
-                    .top_0()
-                    .left_0()
-                    .h_full()
-                    .w(relative_w)
-                    .bg(cx.theme().progress_bar)
-                    .map(|this| match self.value {
-                        v if v >= 100. => this.rounded(rounded),
-                        _ => this.rounded_l(rounded),
-                    }),
+                canvas(
+                    |_, _| {},
+                    move |bounds, _, cx| {
+                        if let Some(path) = arc_path(bounds, stroke_width, 0., 2. * PI) {
+                            cx.paint_path(path, track_color);
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
             )
+            .map(|this| {
+                if self.indeterminate && cx.theme().lite {
+                    // Show a static quarter arc in the lite mode.
+                    this.child(
+                        canvas(
+                            |_, _| {},
+                            move |bounds, _, cx| {
+                                if let Some(path) =
+                                    arc_path(bounds, stroke_width, start_angle, PI / 2.)
+                                {
+                                    cx.paint_path(path, color);
+                                }
+                            },
+                        )
+                        .absolute()
+                        .size_full(),
+                    )
+                } else if self.indeterminate {
+                    this.child(div().absolute().size_full().with_animation(
+                        "progress-circle-indeterminate",
+                        Animation::new(Duration::from_secs_f64(1.)).repeat(),
+                        move |this, delta| {
+                            let start_angle = start_angle + delta * 2. * PI;
+                            this.child(
+                                canvas(
+                                    |_, _| {},
+                                    move |bounds, _, cx| {
+                                        if let Some(path) =
+                                            arc_path(bounds, stroke_width, start_angle, PI / 2.)
+                                        {
+                                            cx.paint_path(path, color);
+                                        }
+                                    },
+                                )
+                                .size_full(),
+                            )
+                        },
+                    ))
+                } else {
+                    this.child(
+                        canvas(
+                            |_, _| {},
+                            move |bounds, _, cx| {
+                                if let Some(path) =
+                                    arc_path(bounds, stroke_width, start_angle, value * 2. * PI)
+                                {
+                                    cx.paint_path(path, color);
+                                }
+                            },
+                        )
+                        .absolute()
+                        .size_full(),
+                    )
+                }
+            })
+            .children(self.label)
     }
 }
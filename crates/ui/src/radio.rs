@@ -1,18 +1,26 @@
-use crate::{h_flex, theme::ActiveTheme, IconName};
+use std::rc::Rc;
+
+use crate::{
+    h_flex, primitives::listbox, theme::ActiveTheme, AutomationExt as _, AutomationId, GroupLayout,
+    IconName, RovingFocus, StyledExt as _,
+};
 use gpui::{
-    div, prelude::FluentBuilder, relative, svg, ElementId, InteractiveElement, IntoElement,
-    ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, Styled, WindowContext,
+    div, prelude::FluentBuilder, relative, svg, ElementId, FocusHandle, InteractiveElement,
+    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement, Styled,
+    WindowContext,
 };
 
 /// A Radio element.
 ///
-/// This is not included the Radio group implementation, you can manage the group by yourself.
+/// Use the [`RadioGroup`] to manage the exclusive selection of the radios.
 #[derive(IntoElement)]
 pub struct Radio {
     id: ElementId,
     label: Option<SharedString>,
     checked: bool,
     disabled: bool,
+    automation_id: Option<SharedString>,
+    focus_handle: Option<FocusHandle>,
     on_click: Option<Box<dyn Fn(&bool, &mut WindowContext) + 'static>>,
 }
 
@@ -23,6 +31,8 @@ impl Radio {
             label: None,
             checked: false,
             disabled: false,
+            automation_id: None,
+            focus_handle: None,
             on_click: None,
         }
     }
@@ -46,6 +56,19 @@ impl Radio {
         self.on_click = Some(Box::new(handler));
         self
     }
+
+    /// Track the focus of the radio, the focus ring will be shown when the radio is focused.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+}
+
+impl AutomationId for Radio {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.automation_id = Some(id.into());
+        self
+    }
 }
 
 impl RenderOnce for Radio {
@@ -55,9 +78,17 @@ impl RenderOnce for Radio {
         } else {
             cx.theme().primary
         };
+        let is_focused = self
+            .focus_handle
+            .as_ref()
+            .map_or(false, |handle| handle.is_focused(cx));
 
         h_flex()
             .id(self.id)
+            .when_some(self.focus_handle.as_ref(), |this, focus_handle| {
+                this.track_focus(focus_handle)
+            })
+            .when_automation_id(self.automation_id.as_ref())
             .gap_x_2()
             .text_color(cx.theme().foreground)
             .items_center()
@@ -71,6 +102,7 @@ impl RenderOnce for Radio {
                     .border_1()
                     .border_color(color)
                     .when(self.checked, |this| this.bg(color))
+                    .focus_ring(is_focused, cx)
                     .child(
                         svg()
                             .absolute()
@@ -107,3 +139,238 @@ impl RenderOnce for Radio {
             )
     }
 }
+
+/// A group of the [`Radio`]s with the exclusive selection.
+///
+/// The `on_click` and the `checked` of the radios are managed by the group.
+///
+/// ```ignore
+/// RadioGroup::new("order-type")
+///     .layout(GroupLayout::Horizontal)
+///     .child(Radio::new("limit").label("Limit"))
+///     .child(Radio::new("market").label("Market"))
+///     .child(Radio::new("stop").label("Stop").disabled(true))
+///     .selected_index(Some(self.order_type_ix))
+///     .roving_focus(&self.order_type_focus)
+///     .on_change(cx.listener(|this, ix: &usize, cx| {
+///         this.order_type_ix = *ix;
+///         cx.notify();
+///     }))
+/// ```
+#[derive(IntoElement)]
+pub struct RadioGroup {
+    id: ElementId,
+    radios: Vec<Radio>,
+    layout: GroupLayout,
+    selected_ix: Option<usize>,
+    disabled: bool,
+    roving_focus: Option<RovingFocus>,
+    on_change: Option<Rc<dyn Fn(&usize, &mut WindowContext) + 'static>>,
+}
+
+impl RadioGroup {
+    pub fn new(id: impl Into<ElementId>) -> Self {
+        Self {
+            id: id.into(),
+            radios: Vec::new(),
+            layout: GroupLayout::default(),
+            selected_ix: None,
+            disabled: false,
+            roving_focus: None,
+            on_change: None,
+        }
+    }
+
+    pub fn child(mut self, radio: Radio) -> Self {
+        self.radios.push(radio);
+        self
+    }
+
+    pub fn children(mut self, radios: impl IntoIterator<Item = Radio>) -> Self {
+        self.radios.extend(radios);
+        self
+    }
+
+    /// Set the layout of the radios, default: [`GroupLayout::Vertical`]
+    pub fn layout(mut self, layout: GroupLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Set the index of the selected radio.
+    pub fn selected_index(mut self, ix: Option<usize>) -> Self {
+        self.selected_ix = ix;
+        self
+    }
+
+    /// Disable all the radios.
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Use the arrow keys, `home` and `end` to move the focus and the selection between the enabled radios,
+    /// `space` to select the focused radio, and `tab` to leave the group.
+    pub fn roving_focus(mut self, roving_focus: &RovingFocus) -> Self {
+        self.roving_focus = Some(roving_focus.clone());
+        self
+    }
+
+    /// Set the handler to call with the index of the radio when the selection is changed.
+    pub fn on_change(mut self, handler: impl Fn(&usize, &mut WindowContext) + 'static) -> Self {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl RenderOnce for RadioGroup {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let layout = self.layout;
+        let selected_ix = self.selected_ix;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let roving_focus = self.roving_focus;
+        if let Some(roving_focus) = roving_focus.as_ref() {
+            roving_focus.truncate(self.radios.len());
+        }
+        let disabled_ixs: Rc<Vec<bool>> = Rc::new(
+            self.radios
+                .iter()
+                .map(|radio| disabled || radio.disabled)
+                .collect(),
+        );
+
+        layout
+            .container()
+            .id(self.id)
+            .when_some(roving_focus.clone(), |this, roving_focus| {
+                let on_change = on_change.clone();
+                let disabled_ixs = disabled_ixs.clone();
+                this.on_key_down(move |event, cx| {
+                    let Some(ix) = roving_focus.focused_ix(cx) else {
+                        return;
+                    };
+                    let is_disabled = |ix: usize| disabled_ixs.get(ix).copied().unwrap_or(true);
+                    let key = event.keystroke.key.as_str();
+                    let target_ix = if key == "space" {
+                        ix
+                    } else {
+                        let Some(target_ix) =
+                            target_radio(ix, disabled_ixs.len(), key, layout, is_disabled)
+                        else {
+                            return;
+                        };
+                        target_ix
+                    };
+                    if is_disabled(target_ix) {
+                        return;
+                    }
+
+                    // Move the focus and the selection together, like the native radio group.
+                    roving_focus.focus(target_ix, cx);
+                    if selected_ix != Some(target_ix) {
+                        if let Some(on_change) = on_change.as_ref() {
+                            on_change(&target_ix, cx);
+                        }
+                    }
+                    cx.stop_propagation();
+                })
+            })
+            .children(self.radios.into_iter().enumerate().map(|(ix, radio)| {
+                let checked = selected_ix == Some(ix);
+                let disabled = disabled_ixs[ix];
+                let focus_handle = roving_focus
+                    .as_ref()
+                    .map(|roving_focus| roving_focus.focus_handle(ix, cx));
+                let radio = Radio {
+                    checked,
+                    disabled,
+                    focus_handle: focus_handle.or(radio.focus_handle),
+                    on_click: on_change.clone().map(|on_change| {
+                        Box::new(move |_: &bool, cx: &mut WindowContext| {
+                            if !checked {
+                                on_change(&ix, cx);
+                            }
+                        }) as Box<dyn Fn(&bool, &mut WindowContext)>
+                    }),
+                    ..radio
+                };
+                layout.item(radio)
+            }))
+    }
+}
+
+/// Returns the radio to move to from the focused radio `ix` by the key, the disabled radios are skipped.
+///
+/// The `up` and `down` keys move between the rows in the grid layout.
+fn target_radio(
+    ix: usize,
+    len: usize,
+    key: &str,
+    layout: GroupLayout,
+    is_disabled: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let (prev_key, next_key) = match layout {
+        GroupLayout::Vertical => ("up", "down"),
+        _ => ("left", "right"),
+    };
+
+    match key {
+        key if key == prev_key => listbox::prev_option(Some(ix), len, is_disabled, true),
+        key if key == next_key => listbox::next_option(Some(ix), len, is_disabled, true),
+        "up" | "down" => {
+            let GroupLayout::Grid(cols) = layout else {
+                return None;
+            };
+            let cols = cols.max(1);
+            if key == "up" {
+                (1..=ix / cols)
+                    .map(|row| ix - row * cols)
+                    .find(|ix| !is_disabled(*ix))
+            } else {
+                (1..)
+                    .map(|row| ix + row * cols)
+                    .take_while(|ix| *ix < len)
+                    .find(|ix| !is_disabled(*ix))
+            }
+        }
+        "home" => listbox::first_option(len, is_disabled),
+        "end" => listbox::last_option(len, is_disabled),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::target_radio;
+    use crate::GroupLayout;
+
+    #[test]
+    fn test_target_radio() {
+        // The radio 1 and 4 are disabled.
+        let disabled = |ix: usize| ix == 1 || ix == 4;
+        let vertical = GroupLayout::Vertical;
+
+        assert_eq!(target_radio(0, 6, "down", vertical, disabled), Some(2));
+        assert_eq!(target_radio(2, 6, "up", vertical, disabled), Some(0));
+        assert_eq!(target_radio(0, 6, "up", vertical, disabled), Some(5));
+        assert_eq!(target_radio(0, 6, "right", vertical, disabled), None);
+        assert_eq!(target_radio(0, 6, "tab", vertical, disabled), None);
+        assert_eq!(target_radio(0, 6, "a", vertical, disabled), None);
+        assert_eq!(target_radio(2, 6, "end", vertical, disabled), Some(5));
+        assert_eq!(
+            target_radio(5, 6, "home", GroupLayout::Horizontal, |ix| ix == 0),
+            Some(1)
+        );
+
+        // [0, 1, 2]
+        // [3, 4, 5]
+        let grid = GroupLayout::Grid(3);
+        assert_eq!(target_radio(0, 6, "right", grid, disabled), Some(2));
+        assert_eq!(target_radio(0, 6, "down", grid, disabled), Some(3));
+        assert_eq!(target_radio(5, 6, "up", grid, disabled), Some(2));
+        assert_eq!(target_radio(1, 6, "down", grid, disabled), None);
+        assert_eq!(target_radio(2, 6, "down", grid, disabled), Some(5));
+        assert_eq!(target_radio(3, 6, "down", grid, disabled), None);
+    }
+}
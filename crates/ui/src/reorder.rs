@@ -0,0 +1,160 @@
+//! Drag and drop to reorder the items in a vertical container, e.g.: the [`List`](crate::list::List).
+//!
+//! The item renders a drag handle to start dragging a [`DragReorder`], and the container
+//! uses the helpers to get the insertion position, the target index and the auto scroll distance.
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, Bounds, EntityId, IntoElement, ParentElement, Pixels,
+    Point, Render, SharedString, Styled, ViewContext,
+};
+
+use crate::{h_flex, theme::ActiveTheme, Icon, IconName, Sizable};
+
+/// The distance to the container edge to start auto scrolling while dragging.
+const AUTO_SCROLL_EDGE: Pixels = px(32.);
+/// The max scroll distance of each step while dragging at the container edge.
+const AUTO_SCROLL_STEP: Pixels = px(12.);
+
+/// The drag payload to reorder the item in a container.
+#[derive(Clone)]
+pub struct DragReorder {
+    /// The entity id of the container, the item can only be dropped in the same container.
+    pub entity_id: EntityId,
+    /// The index of the dragging item.
+    pub ix: usize,
+    label: Option<SharedString>,
+}
+
+impl DragReorder {
+    pub fn new(entity_id: EntityId, ix: usize) -> Self {
+        Self {
+            entity_id,
+            ix,
+            label: None,
+        }
+    }
+
+    /// Set the label to show in the drag preview.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+impl Render for DragReorder {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        h_flex()
+            .cursor_grabbing()
+            .gap_1()
+            .py_1()
+            .px_2()
+            .max_w_64()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .border_1()
+            .border_color(cx.theme().border)
+            .rounded_md()
+            .text_sm()
+            .text_color(cx.theme().popover_foreground)
+            .bg(cx.theme().popover)
+            .opacity(0.85)
+            .when(cx.theme().has_shadow(), |this| this.shadow_md())
+            .child(
+                Icon::new(IconName::GripVertical)
+                    .small()
+                    .text_color(cx.theme().muted_foreground),
+            )
+            .children(self.label.clone().map(|label| div().child(label)))
+    }
+}
+
+/// Return the insertion position when dragging over the item at `ix`,
+/// the item is split into the upper and lower halves to insert before or after it.
+pub fn insertion_index(ix: usize, bounds: Bounds<Pixels>, position: Point<Pixels>) -> usize {
+    if position.y < bounds.center().y {
+        ix
+    } else {
+        ix + 1
+    }
+}
+
+/// Return the final index of the item moved from `from` to the `insertion` position,
+/// or `None` if the item keeps the same place.
+pub fn target_index(from: usize, insertion: usize) -> Option<usize> {
+    let to = if insertion > from {
+        insertion - 1
+    } else {
+        insertion
+    };
+
+    (to != from).then_some(to)
+}
+
+/// Return the new index of the item at `ix` after the item moved from `from` to `to`.
+pub fn moved_index(ix: usize, from: usize, to: usize) -> usize {
+    if ix == from {
+        to
+    } else if from < ix && ix <= to {
+        ix - 1
+    } else if to <= ix && ix < from {
+        ix + 1
+    } else {
+        ix
+    }
+}
+
+/// Return the vertical scroll distance when dragging near the edge of the container,
+/// positive to scroll up (to show the previous items), the closer to the edge the faster.
+pub fn auto_scroll_delta(bounds: Bounds<Pixels>, position: Point<Pixels>) -> Pixels {
+    if !bounds.contains(&position) {
+        return px(0.);
+    }
+
+    let to_top = position.y - bounds.top();
+    let to_bottom = bounds.bottom() - position.y;
+    if to_top < AUTO_SCROLL_EDGE {
+        AUTO_SCROLL_STEP * ((AUTO_SCROLL_EDGE - to_top) / AUTO_SCROLL_EDGE)
+    } else if to_bottom < AUTO_SCROLL_EDGE {
+        -AUTO_SCROLL_STEP * ((AUTO_SCROLL_EDGE - to_bottom) / AUTO_SCROLL_EDGE)
+    } else {
+        px(0.)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::*;
+
+    #[test]
+    fn test_reorder_index() {
+        assert_eq!(target_index(2, 0), Some(0));
+        assert_eq!(target_index(2, 2), None);
+        assert_eq!(target_index(2, 3), None);
+        assert_eq!(target_index(2, 5), Some(4));
+
+        // Move 1 to 3: [a, b, c, d] -> [a, c, d, b]
+        assert_eq!(moved_index(0, 1, 3), 0);
+        assert_eq!(moved_index(1, 1, 3), 3);
+        assert_eq!(moved_index(2, 1, 3), 1);
+        assert_eq!(moved_index(3, 1, 3), 2);
+        // Move 3 to 1: [a, b, c, d] -> [a, d, b, c]
+        assert_eq!(moved_index(1, 3, 1), 2);
+        assert_eq!(moved_index(3, 3, 1), 1);
+    }
+
+    #[test]
+    fn test_auto_scroll_delta() {
+        let bounds = Bounds::new(point(px(0.), px(100.)), size(px(100.), px(200.)));
+        let row = Bounds::new(point(px(0.), px(100.)), size(px(100.), px(20.)));
+        assert_eq!(insertion_index(3, row, point(px(10.), px(105.))), 3);
+        assert_eq!(insertion_index(3, row, point(px(10.), px(115.))), 4);
+
+        assert_eq!(auto_scroll_delta(bounds, point(px(10.), px(200.))), px(0.));
+        assert_eq!(auto_scroll_delta(bounds, point(px(10.), px(50.))), px(0.));
+        assert_eq!(auto_scroll_delta(bounds, point(px(10.), px(100.))), px(12.));
+        assert_eq!(auto_scroll_delta(bounds, point(px(10.), px(116.))), px(6.));
+        assert_eq!(auto_scroll_delta(bounds, point(px(10.), px(284.))), px(-6.));
+    }
+}
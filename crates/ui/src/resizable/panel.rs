@@ -7,14 +7,18 @@ use gpui::{
     ViewContext, VisualContext as _, WeakView, WindowContext,
 };
 
-use crate::{h_flex, v_flex, AxisExt};
+use crate::{h_flex, theme::ActiveTheme as _, v_flex, AxisExt, InteractiveElementExt as _};
 
 use super::resize_handle;
 
 pub(crate) const PANEL_MIN_SIZE: Pixels = px(100.);
+/// The size of the thin strip to show when the panel is collapsed.
+pub(crate) const PANEL_COLLAPSED_SIZE: Pixels = px(6.);
 
 pub enum ResizablePanelEvent {
     Resized,
+    /// The panel at the index has been collapsed (true) or expanded (false).
+    Collapsed(usize, bool),
 }
 
 #[derive(Clone, Render)]
@@ -146,19 +150,175 @@ impl ResizablePanelGroup {
         cx.notify()
     }
 
+    /// Returns true if the panel at the index is collapsed.
+    pub fn is_collapsed(&self, ix: usize, cx: &WindowContext) -> bool {
+        self.panels
+            .get(ix)
+            .map_or(false, |panel| panel.read(cx).collapsed)
+    }
+
+    /// Collapse or expand the panel at the index, only works for the collapsible panel.
+    ///
+    /// The freed size is given to the siblings when collapsed, and taken back when expanded,
+    /// the panel will restore to the last expanded size.
+    pub fn set_collapsed(&mut self, ix: usize, collapsed: bool, cx: &mut ViewContext<Self>) {
+        let Some(panel) = self.panels.get(ix).cloned() else {
+            return;
+        };
+        let panel_state = panel.read(cx);
+        if !panel_state.collapsible || panel_state.collapsed == collapsed {
+            return;
+        }
+
+        // Before the first layout, there are no sizes to redistribute.
+        let has_layout = self.bounds.size.along(self.axis) > px(0.);
+        if has_layout {
+            self.sync_real_panel_sizes(cx);
+        }
+
+        panel.update(cx, |panel, cx| {
+            if collapsed {
+                panel.expanded_size = panel.size.or(panel.initial_size);
+            } else {
+                panel.size = panel.expanded_size.take();
+                panel.size_ratio = None;
+            }
+            panel.collapsed = collapsed;
+            cx.notify();
+        });
+
+        if has_layout {
+            let size = if collapsed {
+                PANEL_COLLAPSED_SIZE
+            } else {
+                panel
+                    .read(cx)
+                    .size
+                    .unwrap_or_else(|| self.panel_min_size(ix, cx))
+            };
+            let sizes = self.sizes.iter().map(|size| size.0).collect::<Vec<_>>();
+            let min_sizes = (0..self.panels.len())
+                .map(|i| self.panel_min_size(i, cx).0)
+                .collect::<Vec<_>>();
+            let new_sizes = redistribute_sizes(&sizes, &min_sizes, ix, size.0);
+            self.set_sizes(new_sizes.into_iter().map(px).collect(), cx);
+        }
+
+        cx.emit(ResizablePanelEvent::Collapsed(ix, collapsed));
+        cx.notify();
+    }
+
+    /// Toggle the collapsed state of the panel at the index.
+    pub fn toggle_collapsed(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let collapsed = self.is_collapsed(ix, cx);
+        self.set_collapsed(ix, !collapsed, cx);
+    }
+
+    /// Set the size of the panel at the index, the other panels are resized in proportion to fill the group.
+    pub fn set_panel_size(&mut self, ix: usize, size: Pixels, cx: &mut ViewContext<Self>) {
+        if ix >= self.panels.len() {
+            return;
+        }
+
+        self.sync_real_panel_sizes(cx);
+        let container_size = self.container_size();
+        if container_size <= px(0.) {
+            return;
+        }
+
+        let others_min_size = (0..self.panels.len())
+            .filter(|&i| i != ix)
+            .fold(px(0.), |acc, i| acc + self.panel_min_size(i, cx));
+        let max_size = (container_size - others_min_size).max(self.panel_min_size(ix, cx));
+        let size = size.max(self.panel_min_size(ix, cx)).min(max_size);
+
+        let sizes = self.sizes.iter().map(|size| size.0).collect::<Vec<_>>();
+        let ratios = resize_ratios(&sizes, ix, size.0, container_size.0);
+        self.apply_ratios(ratios, cx);
+    }
+
+    /// Set all the panels to the same size.
+    pub fn equalize_sizes(&mut self, cx: &mut ViewContext<Self>) {
+        self.set_ratios(&[], cx);
+    }
+
+    /// Set the sizes of the panels by the ratios of the group size, e.g.: `&[0.3, 0.7]`.
+    ///
+    /// The ratios are normalized to sum to 1, the missing ones share the rest equally.
+    pub fn set_ratios(&mut self, ratios: &[f32], cx: &mut ViewContext<Self>) {
+        let ratios = normalize_ratios(ratios, self.panels.len());
+        self.apply_ratios(ratios, cx);
+    }
+
+    fn container_size(&self) -> Pixels {
+        let size = self.bounds.size.along(self.axis);
+        if size > px(0.) {
+            size
+        } else {
+            self.total_size()
+        }
+    }
+
+    fn apply_ratios(&mut self, ratios: Vec<f32>, cx: &mut ViewContext<Self>) {
+        if ratios.is_empty() {
+            return;
+        }
+
+        let container_size = self.container_size();
+        for (i, panel) in self.panels.iter().enumerate() {
+            let ratio = ratios[i];
+            let size = (container_size * ratio).floor();
+            self.sizes[i] = size;
+            panel.update(cx, |panel, cx| {
+                if panel.collapsed {
+                    // Restore to the new size when expanded.
+                    panel.expanded_size = Some(size).filter(|size| *size > px(0.));
+                } else {
+                    panel.size = Some(size).filter(|size| *size > px(0.));
+                    panel.size_ratio = Some(ratio);
+                }
+                cx.notify();
+            });
+        }
+
+        cx.emit(ResizablePanelEvent::Resized);
+        cx.notify();
+    }
+
+    fn panel_min_size(&self, ix: usize, cx: &WindowContext) -> Pixels {
+        self.panels
+            .get(ix)
+            .map_or(PANEL_MIN_SIZE, |panel| panel.read(cx).current_min_size())
+    }
+
     fn render_resize_handle(&self, ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let view = cx.view().clone();
-        resize_handle(("resizable-handle", ix), self.axis).on_drag(
-            DragPanel((cx.entity_id(), ix, self.axis)),
-            move |drag_panel, _, cx| {
-                cx.stop_propagation();
-                // Set current resizing panel ix
-                view.update(cx, |view, _| {
-                    view.resizing_panel_ix = Some(ix);
-                });
-                cx.new_view(|_| drag_panel.clone())
-            },
-        )
+        resize_handle(("resizable-handle", ix), self.axis)
+            .on_drag(
+                DragPanel((cx.entity_id(), ix, self.axis)),
+                move |drag_panel, _, cx| {
+                    cx.stop_propagation();
+                    // Set current resizing panel ix
+                    view.update(cx, |view, _| {
+                        view.resizing_panel_ix = Some(ix);
+                    });
+                    cx.new_view(|_| drag_panel.clone())
+                },
+            )
+            .on_double_click({
+                let view = cx.view().clone();
+                move |_, cx| {
+                    view.update(cx, |view, cx| {
+                        // The handle is between the `ix` and `ix + 1` panels, prefer to collapse the left one.
+                        let ix = if view.panels[ix].read(cx).collapsible {
+                            ix
+                        } else {
+                            ix + 1
+                        };
+                        view.toggle_collapsed(ix, cx);
+                    })
+                }
+            })
     }
 
     fn done_resizing(&mut self, cx: &mut ViewContext<Self>) {
@@ -197,20 +357,21 @@ impl ResizablePanelGroup {
             // Now to expand logic is correct.
             while changed > px(0.) && ix < self.panels.len() - 1 {
                 ix += 1;
-                let available_size = (new_sizes[ix] - PANEL_MIN_SIZE).max(px(0.));
+                let available_size = (new_sizes[ix] - self.panel_min_size(ix, cx)).max(px(0.));
                 let to_reduce = changed.min(available_size);
                 new_sizes[ix] -= to_reduce;
                 changed -= to_reduce;
             }
         } else {
-            let new_size = size.max(PANEL_MIN_SIZE);
+            let min_size = self.panel_min_size(ix, cx);
+            let new_size = size.max(min_size);
             new_sizes[ix] = new_size;
-            changed = size - PANEL_MIN_SIZE;
+            changed = size - min_size;
             new_sizes[ix + 1] += self.sizes[ix] - new_size;
 
             while changed < px(0.) && ix > 0 {
                 ix -= 1;
-                let available_size = self.sizes[ix] - PANEL_MIN_SIZE;
+                let available_size = self.sizes[ix] - self.panel_min_size(ix, cx);
                 let to_increase = (changed).min(available_size);
                 new_sizes[ix] += to_increase;
                 changed += to_increase;
@@ -221,23 +382,141 @@ impl ResizablePanelGroup {
         let total_size: Pixels = new_sizes.iter().map(|s| s.0).sum::<f32>().into();
         if total_size > container_size {
             let overflow = total_size - container_size;
-            new_sizes[main_ix] = (new_sizes[main_ix] - overflow).max(PANEL_MIN_SIZE);
+            new_sizes[main_ix] =
+                (new_sizes[main_ix] - overflow).max(self.panel_min_size(main_ix, cx));
         }
 
-        let total_size = new_sizes.iter().fold(px(0.0), |acc, &size| acc + size);
-        self.sizes = new_sizes;
+        self.set_sizes(new_sizes, cx);
+    }
+
+    /// Update the sizes of the group and the expanded panels.
+    fn set_sizes(&mut self, sizes: Vec<Pixels>, cx: &mut ViewContext<Self>) {
+        let total_size = sizes.iter().fold(px(0.0), |acc, &size| acc + size);
+        self.sizes = sizes;
         for (i, panel) in self.panels.iter().enumerate() {
             let size = self.sizes[i];
-            if size > px(0.) {
-                panel.update(cx, |this, _| {
+            if size > px(0.) && !panel.read(cx).collapsed {
+                panel.update(cx, |this, cx| {
                     this.size = Some(size);
                     this.size_ratio = Some(size / total_size);
+                    cx.notify();
                 });
             }
         }
     }
 }
 impl EventEmitter<ResizablePanelEvent> for ResizablePanelGroup {}
+
+/// Returns the `len` ratios that sum to 1, the missing or invalid ratios share the rest equally,
+/// or the average of the given ones if no rest.
+fn normalize_ratios(ratios: &[f32], len: usize) -> Vec<f32> {
+    if len == 0 {
+        return vec![];
+    }
+
+    let mut ratios = (0..len)
+        .map(|i| {
+            ratios
+                .get(i)
+                .copied()
+                .filter(|ratio| ratio.is_finite() && *ratio >= 0.)
+        })
+        .collect::<Vec<_>>();
+
+    let given = ratios.iter().flatten().copied().collect::<Vec<_>>();
+    let missing = len - given.len();
+    if missing > 0 {
+        let sum: f32 = given.iter().sum();
+        let fill = if sum < 1. {
+            (1. - sum) / missing as f32
+        } else {
+            sum / given.len() as f32
+        };
+        for ratio in ratios.iter_mut().filter(|ratio| ratio.is_none()) {
+            *ratio = Some(fill);
+        }
+    }
+
+    let ratios = ratios.into_iter().flatten().collect::<Vec<_>>();
+    let sum: f32 = ratios.iter().sum();
+    if sum <= 0. {
+        return vec![1. / len as f32; len];
+    }
+    ratios.into_iter().map(|ratio| ratio / sum).collect()
+}
+
+/// Returns the ratios after resizing the panel at `ix` to the `size`,
+/// the other panels keep their proportions to fill the rest of the `container_size`.
+fn resize_ratios(sizes: &[f32], ix: usize, size: f32, container_size: f32) -> Vec<f32> {
+    if sizes.len() <= 1 || container_size <= 0. {
+        return normalize_ratios(&[], sizes.len());
+    }
+
+    let size = size.clamp(0., container_size);
+    let rest = container_size - size;
+    let others_total: f32 = sizes
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != ix)
+        .map(|(_, size)| size)
+        .sum();
+    let others_len = (sizes.len() - 1) as f32;
+
+    sizes
+        .iter()
+        .enumerate()
+        .map(|(i, other)| {
+            let size = if i == ix {
+                size
+            } else if others_total > 0. {
+                other / others_total * rest
+            } else {
+                rest / others_len
+            };
+            size / container_size
+        })
+        .collect()
+}
+
+/// Returns the sizes after setting the panel at `ix` to the `size`, like resizing by the handle.
+///
+/// The freed size is given to the next panel (or the previous one for the last panel),
+/// the needed size is taken from the next panels and then the previous ones, but not smaller
+/// than their `min_sizes`.
+fn redistribute_sizes(sizes: &[f32], min_sizes: &[f32], ix: usize, size: f32) -> Vec<f32> {
+    let mut new_sizes = sizes.to_vec();
+    if ix >= sizes.len() {
+        return new_sizes;
+    }
+
+    let mut changed = size - sizes[ix];
+    new_sizes[ix] = size;
+    if changed < 0. {
+        let sibling = if ix + 1 < sizes.len() {
+            Some(ix + 1)
+        } else {
+            ix.checked_sub(1)
+        };
+        if let Some(sibling) = sibling {
+            new_sizes[sibling] -= changed;
+        }
+        return new_sizes;
+    }
+
+    for i in (ix + 1..sizes.len()).chain((0..ix).rev()) {
+        if changed <= 0. {
+            break;
+        }
+        let available_size = (new_sizes[i] - min_sizes[i]).max(0.);
+        let to_reduce = changed.min(available_size);
+        new_sizes[i] -= to_reduce;
+        changed -= to_reduce;
+    }
+    // Not enough size from the siblings, shrink the panel itself.
+    new_sizes[ix] -= changed;
+    new_sizes
+}
+
 impl Render for ResizablePanelGroup {
     fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let view = cx.view().clone();
@@ -289,6 +568,11 @@ pub struct ResizablePanel {
     /// The bounds of the resizable panel, when render the bounds will be updated.
     bounds: Bounds<Pixels>,
     resize_handle: Option<AnyElement>,
+    min_size: Option<Pixels>,
+    collapsible: bool,
+    collapsed: bool,
+    /// The size before collapsed, used to restore when expanded.
+    expanded_size: Option<Pixels>,
 }
 
 impl ResizablePanel {
@@ -304,6 +588,10 @@ impl ResizablePanel {
             content_visible: Rc::new(Box::new(|_| true)),
             bounds: Bounds::default(),
             resize_handle: None,
+            min_size: None,
+            collapsible: false,
+            collapsed: false,
+            expanded_size: None,
         }
     }
 
@@ -334,6 +622,28 @@ impl ResizablePanel {
         self
     }
 
+    /// Set the minimum size of the panel, default is 100px.
+    pub fn min_size(mut self, min_size: Pixels) -> Self {
+        self.min_size = Some(min_size);
+        self
+    }
+
+    /// Set whether the panel can be collapsed by double-clicking the resize handle, default is `false`.
+    ///
+    /// A collapsed panel will show as a thin strip.
+    pub fn collapsible(mut self, collapsible: bool) -> Self {
+        self.collapsible = collapsible;
+        self
+    }
+
+    fn current_min_size(&self) -> Pixels {
+        if self.collapsed {
+            PANEL_COLLAPSED_SIZE
+        } else {
+            self.min_size.unwrap_or(PANEL_MIN_SIZE)
+        }
+    }
+
     /// Save the real panel size, and update group sizes
     fn update_size(&mut self, bounds: Bounds<Pixels>, cx: &mut ViewContext<Self>) {
         let new_size = bounds.size.along(self.axis);
@@ -371,14 +681,34 @@ impl Render for ResizablePanel {
             .and_then(|group| group.upgrade())
             .map(|group| group.read(cx).total_size());
 
+        if self.collapsed {
+            return div()
+                .flex()
+                .flex_shrink_0()
+                .size_full()
+                .relative()
+                .flex_basis(PANEL_COLLAPSED_SIZE)
+                .bg(cx.theme().muted)
+                .child({
+                    canvas(
+                        move |bounds, cx| view.update(cx, |r, _| r.bounds = bounds),
+                        |_, _, _| {},
+                    )
+                    .absolute()
+                    .size_full()
+                })
+                .when_some(self.resize_handle.take(), |this, c| this.child(c));
+        }
+
+        let min_size = self.current_min_size();
         div()
             .flex()
             .flex_grow()
             .size_full()
             .relative()
             .when(self.initial_size.is_none(), |this| this.flex_shrink())
-            .when(self.axis.is_vertical(), |this| this.min_h(PANEL_MIN_SIZE))
-            .when(self.axis.is_horizontal(), |this| this.min_w(PANEL_MIN_SIZE))
+            .when(self.axis.is_vertical(), |this| this.min_h(min_size))
+            .when(self.axis.is_horizontal(), |this| this.min_w(min_size))
             .when_some(self.initial_size, |this, size| {
                 if size.is_zero() {
                     this
@@ -499,3 +829,66 @@ impl Element for ResizePanelGroupElement {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_ratios, redistribute_sizes, resize_ratios};
+
+    #[test]
+    fn test_normalize_ratios() {
+        assert_eq!(normalize_ratios(&[], 0), Vec::<f32>::new());
+        assert_eq!(normalize_ratios(&[], 4), vec![0.25; 4]);
+        assert_eq!(normalize_ratios(&[0.3, 0.7], 2), vec![0.3, 0.7]);
+        assert_eq!(normalize_ratios(&[1., 3.], 2), vec![0.25, 0.75]);
+        assert_eq!(normalize_ratios(&[0.5], 3), vec![0.5, 0.25, 0.25]);
+        assert_eq!(normalize_ratios(&[0.5, 0.5, 0.5], 2), vec![0.5, 0.5]);
+        assert_eq!(normalize_ratios(&[1., 1.], 4), vec![0.25; 4]);
+        assert_eq!(normalize_ratios(&[f32::NAN, -1.], 2), vec![0.5, 0.5]);
+        assert_eq!(normalize_ratios(&[0., 0.], 2), vec![0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_resize_ratios() {
+        assert_eq!(
+            resize_ratios(&[100., 100.], 0, 150., 200.),
+            vec![0.75, 0.25]
+        );
+        assert_eq!(
+            resize_ratios(&[100., 100., 200.], 2, 100., 400.),
+            vec![0.375, 0.375, 0.25]
+        );
+        assert_eq!(resize_ratios(&[0., 0.], 1, 100., 400.), vec![0.75, 0.25]);
+        assert_eq!(resize_ratios(&[100.], 0, 50., 100.), vec![1.]);
+        assert_eq!(resize_ratios(&[100., 100.], 0, 500., 200.), vec![1., 0.]);
+    }
+
+    #[test]
+    fn test_redistribute_sizes() {
+        let min_sizes = [100., 100., 100.];
+        // Collapse gives the freed size to the next panel, or the previous one for the last.
+        assert_eq!(
+            redistribute_sizes(&[300., 200., 100.], &min_sizes, 0, 6.),
+            vec![6., 494., 100.]
+        );
+        assert_eq!(
+            redistribute_sizes(&[300., 200., 100.], &min_sizes, 2, 6.),
+            vec![300., 294., 6.]
+        );
+        // Expand takes it back to restore the sizes.
+        assert_eq!(
+            redistribute_sizes(&[6., 494., 100.], &min_sizes, 0, 300.),
+            vec![300., 200., 100.]
+        );
+        assert_eq!(
+            redistribute_sizes(&[300., 294., 6.], &min_sizes, 2, 100.),
+            vec![300., 200., 100.]
+        );
+        // Take from the next panels first, then the previous ones, not below the min sizes.
+        assert_eq!(
+            redistribute_sizes(&[200., 6., 150.], &min_sizes, 1, 200.),
+            vec![100., 156., 100.]
+        );
+        assert_eq!(redistribute_sizes(&[100.], &[100.], 0, 6.), vec![6.]);
+        assert_eq!(redistribute_sizes(&[100.], &[100.], 3, 6.), vec![100.]);
+    }
+}
@@ -4,7 +4,7 @@ use gpui::{
     WindowContext,
 };
 
-use crate::{theme::ActiveTheme as _, AxisExt as _};
+use crate::{theme::ActiveTheme as _, AxisExt as _, InteractiveElementExt};
 
 pub(crate) const HANDLE_PADDING: Pixels = px(4.);
 pub(crate) const HANDLE_SIZE: Pixels = px(1.);
@@ -35,6 +35,7 @@ impl InteractiveElement for ResizeHandle {
     }
 }
 impl StatefulInteractiveElement for ResizeHandle {}
+impl InteractiveElementExt for ResizeHandle {}
 
 impl RenderOnce for ResizeHandle {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
@@ -1,18 +1,26 @@
 use crate::{
     drawer::Drawer,
+    focus_restore::{FocusRestore, OverlayKind},
     modal::Modal,
     notification::{Notification, NotificationList},
     theme::ActiveTheme,
+    undo_toast,
+    unsaved::{confirm_unsaved, UnsavedItem, UnsavedProvider, UnsavedProviderHandle},
 };
 use gpui::{
-    div, AnyView, FocusHandle, InteractiveElement, IntoElement, ParentElement as _, Render, Styled,
-    View, ViewContext, VisualContext as _, WindowContext,
+    div, px, AnyView, FocusHandle, InteractiveElement, IntoElement, ParentElement as _, Pixels,
+    Render, Styled, View, ViewContext, VisualContext as _, WindowBackgroundAppearance,
+    WindowContext,
 };
 use std::{
+    cell::Cell,
     ops::{Deref, DerefMut},
-    rc::Rc,
+    rc::{Rc, Weak},
 };
 
+/// The offset of the drawer that pushed by each drawer above it.
+const DRAWER_STACK_OFFSET: Pixels = px(32.);
+
 /// Extension trait for [`WindowContext`] and [`ViewContext`] to add drawer functionality.
 pub trait ContextModal: Sized {
     /// Opens a Drawer.
@@ -23,7 +31,7 @@ pub trait ContextModal: Sized {
     /// Return true, if there is an active Drawer.
     fn has_active_drawer(&self) -> bool;
 
-    /// Closes the active Drawer.
+    /// Closes the top active Drawer, the Drawers are stacked, so the previous Drawer will be active.
     fn close_drawer(&mut self);
 
     /// Opens a Modal.
@@ -34,7 +42,7 @@ pub trait ContextModal: Sized {
     /// Return true, if there is an active Modal.
     fn has_active_modal(&self) -> bool;
 
-    /// Closes the last active Modal.
+    /// Closes the top active Modal, the focus will be restored to the element that focused before the Modal opened.
     fn close_modal(&mut self);
 
     /// Closes all active Modals.
@@ -53,15 +61,14 @@ impl ContextModal for WindowContext<'_> {
         F: Fn(Drawer, &mut WindowContext) -> Drawer + 'static,
     {
         Root::update(self, move |root, cx| {
-            if root.active_drawer.is_none() {
-                root.previous_focus_handle = cx.focused();
-            }
-
             let focus_handle = cx.focus_handle();
-            focus_handle.focus(cx);
+            root.focus_restore
+                .push(OverlayKind::Drawer, focus_handle.clone(), cx);
 
-            root.active_drawer = Some(ActiveDrawer {
+            // Opening a new drawer will push the previous drawers.
+            root.active_drawers.push(ActiveDrawer {
                 focus_handle,
+                size: Rc::new(Cell::new(None)),
                 builder: Rc::new(build),
             });
             cx.notify();
@@ -69,13 +76,14 @@ impl ContextModal for WindowContext<'_> {
     }
 
     fn has_active_drawer(&self) -> bool {
-        Root::read(&self).active_drawer.is_some()
+        !Root::read(&self).active_drawers.is_empty()
     }
 
     fn close_drawer(&mut self) {
         Root::update(self, |root, cx| {
-            root.active_drawer = None;
-            root.focus_back(cx);
+            if let Some(drawer) = root.active_drawers.pop() {
+                root.focus_restore.close(&[drawer.focus_handle], cx);
+            }
             cx.notify();
         })
     }
@@ -85,14 +93,10 @@ impl ContextModal for WindowContext<'_> {
         F: Fn(Modal, &mut WindowContext) -> Modal + 'static,
     {
         Root::update(self, move |root, cx| {
-            // Only save focus handle if there are no active modals.
-            // This is used to restore focus when all modals are closed.
-            if root.active_modals.len() == 0 {
-                root.previous_focus_handle = cx.focused();
-            }
-
+            // Keep the focused element for each modal, to restore focus when the modal is closed.
             let focus_handle = cx.focus_handle();
-            focus_handle.focus(cx);
+            root.focus_restore
+                .push(OverlayKind::Modal, focus_handle.clone(), cx);
 
             root.active_modals.push(ActiveModal {
                 focus_handle,
@@ -108,25 +112,14 @@ impl ContextModal for WindowContext<'_> {
 
     fn close_modal(&mut self) {
         Root::update(self, move |root, cx| {
-            root.active_modals.pop();
-
-            if let Some(top_modal) = root.active_modals.last() {
-                // Focus the next modal.
-                top_modal.focus_handle.focus(cx);
-            } else {
-                // Restore focus if there are no more modals.
-                root.focus_back(cx);
+            if let Some(layer_ix) = root.active_modals.len().checked_sub(1) {
+                root.close_modal_at(layer_ix, cx);
             }
-            cx.notify();
         })
     }
 
     fn close_all_modals(&mut self) {
-        Root::update(self, |root, cx| {
-            root.active_modals.clear();
-            root.focus_back(cx);
-            cx.notify();
-        })
+        Root::update(self, |root, cx| root.close_modal_at(0, cx))
     }
 
     fn push_notification(&mut self, note: impl Into<Notification>) {
@@ -202,18 +195,27 @@ impl<V> ContextModal for ViewContext<'_, V> {
 ///
 /// It is used to manage the Drawer, Modal, and Notification.
 pub struct Root {
-    /// Used to store the focus handle of the previous view.
-    /// When the Modal, Drawer closes, we will focus back to the previous view.
-    previous_focus_handle: Option<FocusHandle>,
-    active_drawer: Option<ActiveDrawer>,
+    /// Used to restore the focus to the previous view when the Modal, Drawer closes.
+    pub(crate) focus_restore: FocusRestore,
+    /// The background appearance of the window that has been applied.
+    window_background: WindowBackgroundAppearance,
+    active_drawers: Vec<ActiveDrawer>,
     active_modals: Vec<ActiveModal>,
     pub notification: View<NotificationList>,
+    /// The providers to collect the unsaved items before the window close.
+    unsaved_providers: Vec<Weak<UnsavedProvider>>,
+    /// The providers kept for the lifetime of the window by [`UnsavedProviderHandle::detach`].
+    pub(crate) detached_unsaved_providers: Vec<Rc<UnsavedProvider>>,
+    /// True if the confirm modal of the unsaved items is open.
+    pub(crate) confirming_unsaved: bool,
     view: AnyView,
 }
 
 #[derive(Clone)]
 struct ActiveDrawer {
     focus_handle: FocusHandle,
+    /// The size of the drawer after resized by the user.
+    size: Rc<Cell<Option<Pixels>>>,
     builder: Rc<dyn Fn(Drawer, &mut WindowContext) -> Drawer + 'static>,
 }
 
@@ -225,15 +227,83 @@ struct ActiveModal {
 
 impl Root {
     pub fn new(view: AnyView, cx: &mut ViewContext<Self>) -> Self {
+        cx.on_window_should_close(Self::confirm_window_close);
+
         Self {
-            previous_focus_handle: None,
-            active_drawer: None,
+            focus_restore: FocusRestore::new(),
+            window_background: WindowBackgroundAppearance::Opaque,
+            active_drawers: Vec::new(),
             active_modals: Vec::new(),
             notification: cx.new_view(NotificationList::new),
+            unsaved_providers: Vec::new(),
+            detached_unsaved_providers: Vec::new(),
+            confirming_unsaved: false,
             view,
         }
     }
 
+    /// Register a provider to collect the unsaved items of the window.
+    ///
+    /// Before the window close, if there are any unsaved items, a confirm modal
+    /// will be shown to let user choose to save or discard the changes.
+    ///
+    /// The provider is unregistered when the returned handle is dropped,
+    /// call [`UnsavedProviderHandle::detach`] to keep it for the lifetime of the window.
+    pub fn register_unsaved_provider<F>(
+        cx: &mut WindowContext,
+        provider: F,
+    ) -> UnsavedProviderHandle
+    where
+        F: Fn(&mut WindowContext) -> Vec<UnsavedItem> + 'static,
+    {
+        let handle = UnsavedProviderHandle::new(Rc::new(provider));
+        let provider = handle.downgrade();
+        Root::update(cx, move |root, _| {
+            root.unsaved_providers
+                .retain(|provider| provider.strong_count() > 0);
+            root.unsaved_providers.push(provider);
+        });
+        handle
+    }
+
+    /// Collect all the unsaved items from the registered providers.
+    pub fn unsaved_items(cx: &mut WindowContext) -> Vec<UnsavedItem> {
+        let providers = Root::read(cx)
+            .unsaved_providers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect::<Vec<_>>();
+        providers.iter().flat_map(|provider| provider(cx)).collect()
+    }
+
+    fn confirm_window_close(cx: &mut WindowContext) -> bool {
+        let items = Self::unsaved_items(cx);
+        if items.is_empty() {
+            undo_toast::commit_window(cx);
+            Self::release_unsaved_providers(cx);
+            return true;
+        }
+
+        confirm_unsaved(
+            items,
+            |cx| {
+                undo_toast::commit_window(cx);
+                Self::release_unsaved_providers(cx);
+                cx.remove_window();
+            },
+            cx,
+        );
+        false
+    }
+
+    /// Drop the detached unsaved providers, called when the window is going to close.
+    fn release_unsaved_providers(cx: &mut WindowContext) {
+        Root::update(cx, |root, _| {
+            root.detached_unsaved_providers.clear();
+            root.unsaved_providers.clear();
+        });
+    }
+
     pub fn update<F>(cx: &mut WindowContext, f: F)
     where
         F: FnOnce(&mut Self, &mut ViewContext<Self>) + 'static,
@@ -257,10 +327,22 @@ impl Root {
         root.read(cx)
     }
 
-    fn focus_back(&mut self, cx: &mut WindowContext) {
-        if let Some(handle) = self.previous_focus_handle.clone() {
-            cx.focus(&handle);
+    /// Close the modal at the `layer_ix` and the modals above it.
+    ///
+    /// Do nothing if the modal has been closed, e.g.: the `on_close` of the modal closed itself.
+    pub(crate) fn close_modal_at(&mut self, layer_ix: usize, cx: &mut ViewContext<Self>) {
+        if layer_ix >= self.active_modals.len() {
+            return;
         }
+
+        let closed_handles = self
+            .active_modals
+            .split_off(layer_ix)
+            .into_iter()
+            .map(|modal| modal.focus_handle)
+            .collect::<Vec<_>>();
+        self.focus_restore.close(&closed_handles, cx);
+        cx.notify();
     }
 
     // Render Notification layer.
@@ -282,15 +364,26 @@ impl Root {
             .and_then(|w| w.root_view(cx).ok())
             .expect("The window root view should be of type `ui::Root`.");
 
-        if let Some(active_drawer) = root.read(cx).active_drawer.clone() {
-            let mut drawer = Drawer::new(cx);
-            drawer = (active_drawer.builder)(drawer, cx);
-            drawer.focus_handle = active_drawer.focus_handle.clone();
-
-            return Some(div().child(drawer));
+        let active_drawers = root.read(cx).active_drawers.clone();
+        if active_drawers.is_empty() {
+            return None;
         }
 
-        None
+        let count = active_drawers.len();
+        Some(
+            div().children(active_drawers.iter().enumerate().map(|(i, active_drawer)| {
+                let mut drawer = Drawer::new(cx);
+                drawer = (active_drawer.builder)(drawer, cx);
+                drawer.layer_ix = i;
+                drawer.focus_handle = active_drawer.focus_handle.clone();
+                drawer.resized_size = active_drawer.size.clone();
+                // The previous drawers are pushed by the drawers above them,
+                // and only the top drawer has the overlay.
+                drawer.stack_offset = DRAWER_STACK_OFFSET * (count - 1 - i) as f32;
+                drawer.overlay_visible = i == count - 1;
+                drawer
+            })),
+        )
     }
 
     /// Render the Modal layer.
@@ -308,6 +401,14 @@ impl Root {
             return None;
         }
 
+        // Trap the focus inside the top modal, e.g.: the focus was lost by clicking the blank area.
+        if let Some(top_modal) = active_modals.last() {
+            if !top_modal.focus_handle.contains_focused(cx) {
+                let focus_handle = top_modal.focus_handle.clone();
+                cx.defer(move |cx| focus_handle.focus(cx));
+            }
+        }
+
         Some(
             div().children(active_modals.iter().enumerate().map(|(i, active_modal)| {
                 let mut modal = Modal::new(cx);
@@ -344,11 +445,17 @@ impl Render for Root {
         let base_font_size = cx.theme().font_size;
         cx.set_rem_size(base_font_size);
 
+        let window_background = cx.theme().window_background();
+        if self.window_background != window_background {
+            self.window_background = window_background;
+            cx.set_background_appearance(window_background);
+        }
+
         div()
             .id("root")
             .size_full()
             .font_family(".SystemUIFont")
-            .bg(cx.theme().background)
+            .bg(cx.theme().surface(cx.theme().background))
             .text_color(cx.theme().foreground)
             .child(self.view.clone())
     }
@@ -1,7 +1,9 @@
+mod overscroll;
 mod scrollable;
 mod scrollable_mask;
 mod scrollbar;
 
+pub use overscroll::*;
 pub use scrollable::*;
 pub use scrollable_mask::*;
 pub use scrollbar::*;
@@ -0,0 +1,462 @@
+use std::{
+    cell::Cell,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use gpui::{
+    fill, point, px, relative, size, AnyElement, Bounds, Edges, Element, EntityId, Hsla,
+    IntoElement, Pixels, Point, Position, ScrollWheelEvent, Size, Style, WindowContext,
+};
+
+use crate::{theme::ActiveTheme, Placement};
+
+use super::{ScrollHandleOffsetable, ScrollbarAxis};
+
+/// The duration of the overscroll feedback to fade out.
+const OVERSCROLL_DURATION: Duration = Duration::from_millis(400);
+/// The max distance to move the content for the [`Overscroll::Bounce`].
+const BOUNCE_DISTANCE: Pixels = px(24.);
+/// The size of the glow for the [`Overscroll::Glow`].
+const GLOW_SIZE: Pixels = px(24.);
+/// The size of the fade shadow at the edge that has more content.
+const FADE_SIZE: Pixels = px(16.);
+const FADE_STEPS: usize = 8;
+
+/// The visual feedback when scrolling beyond the edge of the scroll area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overscroll {
+    /// No feedback, default.
+    #[default]
+    None,
+    /// Move the content a bit and spring back, like macOS.
+    Bounce,
+    /// Show a glow at the edge, like Android.
+    Glow,
+}
+
+impl Overscroll {
+    /// Returns the overscroll feedback of the current platform,
+    /// [`Overscroll::Bounce`] on macOS, otherwise [`Overscroll::Glow`].
+    pub fn platform() -> Self {
+        if cfg!(target_os = "macos") {
+            Self::Bounce
+        } else {
+            Self::Glow
+        }
+    }
+}
+
+/// The state of the last overscroll, shared between the [`ScrollEdges`] and the [`OverscrollBounce`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OverscrollState {
+    edge: Option<Placement>,
+    time: Option<Instant>,
+}
+
+impl OverscrollState {
+    /// Returns the edge and the amount (1.0 to 0.0) of the overscroll feedback.
+    fn active(&self) -> Option<(Placement, f32)> {
+        let amount = overscroll_amount(self.time?.elapsed());
+        self.edge.filter(|_| amount > 0.).map(|edge| (edge, amount))
+    }
+}
+
+/// Returns the edges that have more content out of the viewport.
+pub(crate) fn scrollable_edges(
+    offset: Point<Pixels>,
+    viewport: Size<Pixels>,
+    content: Size<Pixels>,
+) -> Edges<bool> {
+    let threshold = px(0.5);
+    Edges {
+        top: offset.y < -threshold,
+        bottom: content.height + offset.y > viewport.height + threshold,
+        left: offset.x < -threshold,
+        right: content.width + offset.x > viewport.width + threshold,
+    }
+}
+
+/// Returns the edge to show the overscroll feedback, if scrolling beyond the edge by the `delta`.
+pub(crate) fn overscroll_edge(
+    offset: Point<Pixels>,
+    viewport: Size<Pixels>,
+    content: Size<Pixels>,
+    delta: Point<Pixels>,
+) -> Option<Placement> {
+    let edges = scrollable_edges(offset, viewport, content);
+    if delta.y.abs() >= delta.x.abs() {
+        if content.height <= viewport.height {
+            return None;
+        }
+
+        if delta.y > px(0.) && !edges.top {
+            Some(Placement::Top)
+        } else if delta.y < px(0.) && !edges.bottom {
+            Some(Placement::Bottom)
+        } else {
+            None
+        }
+    } else {
+        if content.width <= viewport.width {
+            return None;
+        }
+
+        if delta.x > px(0.) && !edges.left {
+            Some(Placement::Left)
+        } else if delta.x < px(0.) && !edges.right {
+            Some(Placement::Right)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the amount of the overscroll feedback, from 1.0 to 0.0 with an ease out.
+pub(crate) fn overscroll_amount(elapsed: Duration) -> f32 {
+    let t = (elapsed.as_secs_f32() / OVERSCROLL_DURATION.as_secs_f32()).min(1.);
+    (1. - t) * (1. - t)
+}
+
+/// Returns the bounds of the strip at the edge of the bounds.
+fn edge_bounds(
+    bounds: Bounds<Pixels>,
+    edge: Placement,
+    start: Pixels,
+    thickness: Pixels,
+) -> Bounds<Pixels> {
+    match edge {
+        Placement::Top => Bounds::new(
+            point(bounds.left(), bounds.top() + start),
+            size(bounds.size.width, thickness),
+        ),
+        Placement::Bottom => Bounds::new(
+            point(bounds.left(), bounds.bottom() - start - thickness),
+            size(bounds.size.width, thickness),
+        ),
+        Placement::Left => Bounds::new(
+            point(bounds.left() + start, bounds.top()),
+            size(thickness, bounds.size.height),
+        ),
+        Placement::Right => Bounds::new(
+            point(bounds.right() - start - thickness, bounds.top()),
+            size(thickness, bounds.size.height),
+        ),
+    }
+}
+
+/// Paint a gradient from the `color` at the edge to transparent.
+fn paint_edge_gradient(
+    bounds: Bounds<Pixels>,
+    edge: Placement,
+    color: Hsla,
+    thickness: Pixels,
+    cx: &mut WindowContext,
+) {
+    let step = thickness / FADE_STEPS as f32;
+    for i in 0..FADE_STEPS {
+        let opacity = 1. - i as f32 / FADE_STEPS as f32;
+        cx.paint_quad(fill(
+            edge_bounds(bounds, edge, step * i as f32, step),
+            color.opacity(opacity),
+        ));
+    }
+}
+
+/// The fade shadows at the edges that have more content, and the overscroll glow.
+///
+/// This is an overlay of the scroll area like the [`Scrollbar`](super::Scrollbar).
+pub struct ScrollEdges {
+    view_id: EntityId,
+    axis: ScrollbarAxis,
+    scroll_handle: Rc<Box<dyn ScrollHandleOffsetable>>,
+    scroll_size: Size<Pixels>,
+    state: Rc<Cell<OverscrollState>>,
+    overscroll: Overscroll,
+    fade: bool,
+}
+
+impl ScrollEdges {
+    pub fn new(
+        view_id: EntityId,
+        state: Rc<Cell<OverscrollState>>,
+        axis: ScrollbarAxis,
+        scroll_handle: impl ScrollHandleOffsetable + 'static,
+        scroll_size: Size<Pixels>,
+    ) -> Self {
+        Self {
+            view_id,
+            axis,
+            scroll_handle: Rc::new(Box::new(scroll_handle)),
+            scroll_size,
+            state,
+            overscroll: Overscroll::None,
+            fade: true,
+        }
+    }
+
+    /// Set the overscroll feedback, default is [`Overscroll::None`].
+    pub fn overscroll(mut self, overscroll: Overscroll) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    /// Set to show the fade shadows at the edges that have more content, default is true.
+    pub fn fade(mut self, fade: bool) -> Self {
+        self.fade = fade;
+        self
+    }
+}
+
+impl IntoElement for ScrollEdges {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for ScrollEdges {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<gpui::ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (gpui::LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.position = Position::Absolute;
+        style.size.width = relative(1.).into();
+        style.size.height = relative(1.).into();
+
+        (cx.request_layout(style, None), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut WindowContext,
+    ) -> Self::PrepaintState {
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        bounds: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        let offset = self.scroll_handle.offset();
+        let mut edges = scrollable_edges(offset, bounds.size, self.scroll_size);
+        if !self.axis.has_vertical() {
+            edges.top = false;
+            edges.bottom = false;
+        }
+        if !self.axis.has_horizontal() {
+            edges.left = false;
+            edges.right = false;
+        }
+
+        if self.fade {
+            let color = gpui::black().opacity(if cx.theme().mode.is_dark() { 0.3 } else { 0.08 });
+            for (edge, has_more) in [
+                (Placement::Top, edges.top),
+                (Placement::Bottom, edges.bottom),
+                (Placement::Left, edges.left),
+                (Placement::Right, edges.right),
+            ] {
+                if has_more {
+                    paint_edge_gradient(bounds, edge, color, FADE_SIZE, cx);
+                }
+            }
+        }
+
+        if self.overscroll == Overscroll::None {
+            return;
+        }
+
+        if let Some((edge, amount)) = self.state.get().active() {
+            if self.overscroll == Overscroll::Glow {
+                let color = cx.theme().primary.opacity(0.35 * amount);
+                paint_edge_gradient(bounds, edge, color, GLOW_SIZE * amount, cx);
+            }
+            cx.request_animation_frame();
+        }
+
+        cx.on_mouse_event({
+            let state = self.state.clone();
+            let scroll_handle = self.scroll_handle.clone();
+            let scroll_size = self.scroll_size;
+            let axis = self.axis;
+            let view_id = self.view_id;
+            move |event: &ScrollWheelEvent, phase, cx| {
+                if !phase.bubble() || !bounds.contains(&event.position) {
+                    return;
+                }
+
+                let mut delta = event.delta.pixel_delta(cx.line_height());
+                if !axis.has_vertical() {
+                    delta.y = px(0.);
+                }
+                if !axis.has_horizontal() {
+                    delta.x = px(0.);
+                }
+
+                if let Some(edge) =
+                    overscroll_edge(scroll_handle.offset(), bounds.size, scroll_size, delta)
+                {
+                    state.set(OverscrollState {
+                        edge: Some(edge),
+                        time: Some(Instant::now()),
+                    });
+                    cx.notify(Some(view_id));
+                }
+            }
+        });
+    }
+}
+
+/// Move the child element for the [`Overscroll::Bounce`] feedback.
+pub struct OverscrollBounce {
+    child: AnyElement,
+    state: Rc<Cell<OverscrollState>>,
+}
+
+impl OverscrollBounce {
+    pub fn new(state: Rc<Cell<OverscrollState>>, child: impl IntoElement) -> Self {
+        Self {
+            child: child.into_any_element(),
+            state,
+        }
+    }
+}
+
+impl IntoElement for OverscrollBounce {
+    type Element = Self;
+
+    fn into_element(self) -> Self::Element {
+        self
+    }
+}
+
+impl Element for OverscrollBounce {
+    type RequestLayoutState = ();
+    type PrepaintState = ();
+
+    fn id(&self) -> Option<gpui::ElementId> {
+        None
+    }
+
+    fn request_layout(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        cx: &mut WindowContext,
+    ) -> (gpui::LayoutId, Self::RequestLayoutState) {
+        let mut style = Style::default();
+        style.flex_grow = 1.0;
+        style.size.width = relative(1.).into();
+        style.size.height = relative(1.).into();
+
+        let child_id = self.child.request_layout(cx);
+        (cx.request_layout(style, vec![child_id]), ())
+    }
+
+    fn prepaint(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        cx: &mut WindowContext,
+    ) -> Self::PrepaintState {
+        let offset = match self.state.get().active() {
+            Some((edge, amount)) => {
+                let distance = BOUNCE_DISTANCE * amount;
+                match edge {
+                    Placement::Top => point(px(0.), distance),
+                    Placement::Bottom => point(px(0.), -distance),
+                    Placement::Left => point(distance, px(0.)),
+                    Placement::Right => point(-distance, px(0.)),
+                }
+            }
+            None => Point::default(),
+        };
+
+        cx.with_element_offset(offset, |cx| self.child.prepaint(cx));
+    }
+
+    fn paint(
+        &mut self,
+        _: Option<&gpui::GlobalElementId>,
+        _: Bounds<Pixels>,
+        _: &mut Self::RequestLayoutState,
+        _: &mut Self::PrepaintState,
+        cx: &mut WindowContext,
+    ) {
+        self.child.paint(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gpui::{point, px, size};
+
+    use super::{overscroll_amount, overscroll_edge, scrollable_edges};
+    use crate::Placement;
+
+    #[test]
+    fn test_scrollable_edges() {
+        let viewport = size(px(100.), px(100.));
+        let content = size(px(100.), px(300.));
+
+        let edges = scrollable_edges(point(px(0.), px(0.)), viewport, content);
+        assert!(!edges.top && edges.bottom && !edges.left && !edges.right);
+        let edges = scrollable_edges(point(px(0.), px(-100.)), viewport, content);
+        assert!(edges.top && edges.bottom);
+        let edges = scrollable_edges(point(px(0.), px(-200.)), viewport, content);
+        assert!(edges.top && !edges.bottom);
+    }
+
+    #[test]
+    fn test_overscroll_edge() {
+        let viewport = size(px(100.), px(100.));
+        let content = size(px(100.), px(300.));
+        let up = point(px(0.), px(10.));
+        let down = point(px(0.), px(-10.));
+
+        let top = point(px(0.), px(0.));
+        assert_eq!(
+            overscroll_edge(top, viewport, content, up),
+            Some(Placement::Top)
+        );
+        assert_eq!(overscroll_edge(top, viewport, content, down), None);
+
+        let bottom = point(px(0.), px(-200.));
+        assert_eq!(
+            overscroll_edge(bottom, viewport, content, down),
+            Some(Placement::Bottom)
+        );
+        assert_eq!(overscroll_edge(bottom, viewport, content, up), None);
+
+        // Not scrollable
+        assert_eq!(overscroll_edge(top, viewport, viewport, up), None);
+        // Horizontal not scrollable
+        assert_eq!(
+            overscroll_edge(top, viewport, content, point(px(10.), px(0.))),
+            None
+        );
+
+        assert_eq!(overscroll_amount(Duration::ZERO), 1.);
+        assert!((overscroll_amount(Duration::from_millis(200)) - 0.25).abs() < 1e-6);
+        assert_eq!(overscroll_amount(Duration::from_secs(1)), 0.);
+    }
+}
@@ -1,18 +1,85 @@
 use std::{cell::Cell, rc::Rc};
 
-use super::{Scrollbar, ScrollbarAxis, ScrollbarState};
+use super::{
+    Overscroll, OverscrollBounce, OverscrollState, ScrollEdges, Scrollbar, ScrollbarAxis,
+    ScrollbarState, SCROLLBAR_WIDTH,
+};
+use crate::theme::ActiveTheme as _;
 use gpui::{
-    canvas, div, relative, AnyElement, Div, Element, ElementId, EntityId, GlobalElementId,
-    InteractiveElement, IntoElement, ParentElement, Pixels, Position, ScrollHandle, SharedString,
-    Size, Stateful, StatefulInteractiveElement, Style, StyleRefinement, Styled, WindowContext,
+    canvas, div, point, prelude::FluentBuilder as _, px, relative, AnyElement, Bounds, Div,
+    Element, ElementId, EntityId, GlobalElementId, InteractiveElement, IntoElement, ParentElement,
+    Pixels, Position, ScrollHandle, SharedString, Size, Stateful, StatefulInteractiveElement,
+    Style, StyleRefinement, Styled, WindowContext,
 };
 
+/// Returns the scroll offset (negative) on one axis to reveal the child in the viewport,
+/// the child is aligned to the start if it is larger than the viewport.
+///
+/// The `view_start` and `child_start` are the positions in the window with the current `offset`.
+fn reveal_offset(
+    offset: f32,
+    view_start: f32,
+    view_len: f32,
+    child_start: f32,
+    child_len: f32,
+) -> f32 {
+    let before = child_start - view_start;
+    let after = child_start + child_len - (view_start + view_len);
+
+    if before < 0. {
+        (offset - before).min(0.)
+    } else if after > 0. {
+        offset - after.min(before)
+    } else {
+        offset
+    }
+}
+
+/// Scroll the scroll view tracked by the `handle` to reveal the child `bounds`,
+/// both the vertical and the horizontal offsets are adjusted independently.
+///
+/// The `bounds` is the bounds of the child in the window, e.g.: measured in the last frame by a `canvas`.
+///
+/// ```ignore
+/// div()
+///     .scrollable(view_id, ScrollbarAxis::Both)
+///     .track_scroll(&self.scroll_handle)
+///     .child(...);
+///
+/// scroll_into_view(&self.scroll_handle, self.target_bounds);
+/// cx.notify();
+/// ```
+pub fn scroll_into_view(handle: &ScrollHandle, bounds: Bounds<Pixels>) {
+    let view = handle.bounds();
+    let offset = handle.offset();
+
+    handle.set_offset(point(
+        px(reveal_offset(
+            offset.x.0,
+            view.origin.x.0,
+            view.size.width.0,
+            bounds.origin.x.0,
+            bounds.size.width.0,
+        )),
+        px(reveal_offset(
+            offset.y.0,
+            view.origin.y.0,
+            view.size.height.0,
+            bounds.origin.y.0,
+            bounds.size.height.0,
+        )),
+    ));
+}
+
 /// A scroll view is a container that allows the user to scroll through a large amount of content.
 pub struct Scrollable<E> {
     id: ElementId,
     element: Option<E>,
     view_id: EntityId,
     axis: ScrollbarAxis,
+    overscroll: Overscroll,
+    edge_fades: bool,
+    scroll_handle: Option<ScrollHandle>,
     /// This is a fake element to handle Styled, InteractiveElement, not used.
     _element: Stateful<Div>,
 }
@@ -34,9 +101,24 @@ where
             id,
             view_id,
             axis,
+            overscroll: Overscroll::None,
+            edge_fades: false,
+            scroll_handle: None,
         }
     }
 
+    /// Set the feedback when scrolling beyond the edge, default is [`Overscroll::None`].
+    pub fn overscroll(mut self, overscroll: Overscroll) -> Self {
+        self.overscroll = overscroll;
+        self
+    }
+
+    /// Set to show the fade shadows at the edges that have more content, default is false.
+    pub fn edge_fades(mut self, edge_fades: bool) -> Self {
+        self.edge_fades = edge_fades;
+        self
+    }
+
     /// Set only a vertical scrollbar.
     pub fn vertical(mut self) -> Self {
         self.set_axis(ScrollbarAxis::Vertical);
@@ -44,12 +126,24 @@ where
     }
 
     /// Set only a horizontal scrollbar.
-    /// In current implementation, this is not supported yet.
     pub fn horizontal(mut self) -> Self {
         self.set_axis(ScrollbarAxis::Horizontal);
         self
     }
 
+    /// Set both the vertical and horizontal scrollbars.
+    pub fn both(mut self) -> Self {
+        self.set_axis(ScrollbarAxis::Both);
+        self
+    }
+
+    /// Track the scroll offset with the handle, to read or change the offset programmatically,
+    /// e.g.: [`scroll_into_view`].
+    pub fn track_scroll(mut self, handle: &ScrollHandle) -> Self {
+        self.scroll_handle = Some(handle.clone());
+        self
+    }
+
     /// Set the axis of the scroll view.
     pub fn set_axis(&mut self, axis: ScrollbarAxis) {
         self.axis = axis;
@@ -71,7 +165,10 @@ where
 
 pub struct ScrollViewState {
     scroll_size: Rc<Cell<Size<Pixels>>>,
+    /// The size of the viewport, measured in the last frame.
+    view_size: Rc<Cell<Size<Pixels>>>,
     state: Rc<Cell<ScrollbarState>>,
+    overscroll: Rc<Cell<OverscrollState>>,
     handle: ScrollHandle,
 }
 
@@ -80,7 +177,9 @@ impl Default for ScrollViewState {
         Self {
             handle: ScrollHandle::new(),
             scroll_size: Rc::new(Cell::new(Size::default())),
+            view_size: Rc::new(Cell::new(Size::default())),
             state: Rc::new(Cell::new(ScrollbarState::default())),
+            overscroll: Rc::new(Cell::new(OverscrollState::default())),
         }
     }
 }
@@ -158,33 +257,72 @@ where
 
         let axis = self.axis;
         let view_id = self.view_id;
+        let overscroll = self.overscroll;
+        let edge_fades = self.edge_fades;
 
         let scroll_id = self.id.clone();
         let content = self.element.take().map(|c| c.into_any_element());
+        let tracked_handle = self.scroll_handle.clone();
 
         self.with_element_state(id.unwrap(), cx, |_, element_state, cx| {
+            if let Some(handle) = tracked_handle {
+                element_state.handle = handle;
+            }
+
             let handle = element_state.handle.clone();
             let state = element_state.state.clone();
             let scroll_size = element_state.scroll_size.clone();
+            let overscroll_state = element_state.overscroll.clone();
+            let view_size = element_state.view_size.clone();
+
+            // Only show the corner when both scrollbars are visible, the horizontal scrollbar
+            // leaves the corner space for the vertical one.
+            let content_size = scroll_size.get();
+            let has_corner = axis.is_both()
+                && content_size.width > view_size.get().width
+                && content_size.height > view_size.get().height;
+            let is_track_visible = cx.theme().scrollbar_show.is_always() || state.get().is_active();
+
+            let scroll_area = div()
+                .id(scroll_id)
+                .track_scroll(&handle)
+                .map(|this| match axis {
+                    ScrollbarAxis::Vertical => this.overflow_y_scroll(),
+                    ScrollbarAxis::Horizontal => this.overflow_x_scroll(),
+                    ScrollbarAxis::Both => this.overflow_scroll(),
+                })
+                .relative()
+                .size_full()
+                .child(div().children(content).child({
+                    let scroll_size = element_state.scroll_size.clone();
+                    canvas(move |b, _| scroll_size.set(b.size), |_, _, _| {})
+                        .absolute()
+                        .size_full()
+                }));
 
             let mut element = div()
                 .relative()
                 .size_full()
                 .overflow_hidden()
-                .child(
-                    div()
-                        .id(scroll_id)
-                        .track_scroll(&handle)
-                        .overflow_scroll()
-                        .relative()
-                        .size_full()
-                        .child(div().children(content).child({
-                            let scroll_size = element_state.scroll_size.clone();
-                            canvas(move |b, _| scroll_size.set(b.size), |_, _, _| {})
-                                .absolute()
-                                .size_full()
-                        })),
-                )
+                .map(|this| match overscroll {
+                    Overscroll::Bounce => {
+                        this.child(OverscrollBounce::new(overscroll_state.clone(), scroll_area))
+                    }
+                    _ => this.child(scroll_area),
+                })
+                .when(edge_fades || overscroll != Overscroll::None, |this| {
+                    this.child(
+                        ScrollEdges::new(
+                            view_id,
+                            overscroll_state,
+                            axis,
+                            handle.clone(),
+                            scroll_size.get(),
+                        )
+                        .overscroll(overscroll)
+                        .fade(edge_fades),
+                    )
+                })
                 .child(
                     div()
                         .absolute()
@@ -197,6 +335,21 @@ where
                                 .axis(axis),
                         ),
                 )
+                .when(has_corner, |this| {
+                    this.child(
+                        div()
+                            .absolute()
+                            .right_0()
+                            .bottom_0()
+                            .size(SCROLLBAR_WIDTH)
+                            .when(is_track_visible, |this| this.bg(cx.theme().scrollbar)),
+                    )
+                })
+                .child(
+                    canvas(move |bounds, _| view_size.set(bounds.size), |_, _, _| {})
+                        .absolute()
+                        .size_full(),
+                )
                 .into_any_element();
             let element_id = element.request_layout(cx);
 
@@ -229,3 +382,21 @@ where
         element.paint(cx)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::reveal_offset;
+
+    #[test]
+    fn test_reveal_offset() {
+        // Already visible.
+        assert_eq!(reveal_offset(0., 0., 100., 20., 30.), 0.);
+        // Below the viewport, scroll down to align the end.
+        assert_eq!(reveal_offset(0., 0., 100., 150., 30.), -80.);
+        // Above the viewport, scroll up to align the start.
+        assert_eq!(reveal_offset(-200., 0., 100., -50., 30.), -150.);
+        assert_eq!(reveal_offset(-20., 0., 100., -50., 30.), 0.);
+        // Larger than the viewport, align the start.
+        assert_eq!(reveal_offset(0., 0., 100., 150., 300.), -150.);
+    }
+}
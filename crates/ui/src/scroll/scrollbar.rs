@@ -11,21 +11,33 @@ use serde::{Deserialize, Serialize};
 /// Scrollbar show mode.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash, Default)]
 pub enum ScrollbarShow {
+    /// Show the scrollbar when scrolling, and fade out after inactivity.
     #[default]
     Scrolling,
+    /// Show the scrollbar when the mouse hovers it.
     Hover,
+    /// Always show the scrollbar, no auto-hide.
+    Always,
 }
 
 impl ScrollbarShow {
     fn is_hover(&self) -> bool {
         matches!(self, Self::Hover)
     }
+
+    pub(crate) fn is_always(&self) -> bool {
+        matches!(self, Self::Always)
+    }
 }
 
 const BORDER_WIDTH: Pixels = px(0.);
-const MIN_THUMB_SIZE: f32 = 80.;
+/// The width of the vertical scrollbar, and the height of the horizontal scrollbar.
+pub(crate) const SCROLLBAR_WIDTH: Pixels = px(12.);
 const THUMB_RADIUS: Pixels = Pixels(3.0);
 const THUMB_INSET: Pixels = Pixels(4.);
+/// The inset of the thumb when the scrollbar is hovered or dragging, to make it thicker.
+const THUMB_ACTIVE_INSET: Pixels = Pixels(2.);
+const THUMB_ACTIVE_RADIUS: Pixels = Pixels(4.);
 const FADE_OUT_DURATION: f32 = 3.0;
 const FADE_OUT_DELAY: f32 = 2.0;
 
@@ -61,6 +73,53 @@ impl ScrollHandleOffsetable for UniformListScrollHandle {
     }
 }
 
+/// Returns the opacity of the thumb after the last scroll, it keeps visible in the `FADE_OUT_DELAY`,
+/// and then fades out to 0 at the `FADE_OUT_DURATION`.
+fn fade_opacity(elapsed: f32) -> f32 {
+    if elapsed < FADE_OUT_DELAY {
+        1.
+    } else if elapsed < FADE_OUT_DURATION {
+        // opacity = 1 - (x - 2)^10
+        (1. - (elapsed - FADE_OUT_DELAY).powi(10)).max(0.)
+    } else {
+        0.
+    }
+}
+
+/// Returns the length of the thumb, it is not less than the `min_size` unless the track is too short.
+fn thumb_length(container_size: f32, scroll_size: f32, min_size: f32) -> f32 {
+    if scroll_size <= 0. {
+        return container_size;
+    }
+
+    (container_size / scroll_size * container_size)
+        .max(min_size)
+        .min(container_size)
+}
+
+/// Returns the scroll ratio (0 to 1) to move the thumb start to the `thumb_start` in the track,
+/// the `margin_end` at the end of the track is reserved for the other scrollbar.
+fn thumb_ratio(thumb_start: f32, track_length: f32, thumb_length: f32, margin_end: f32) -> f32 {
+    let range = track_length - thumb_length - margin_end;
+    if range <= 0. {
+        return 0.;
+    }
+
+    (thumb_start / range).clamp(0., 1.)
+}
+
+/// Returns the scroll offset (negative) after paging by the container size,
+/// to the end if `forward`, otherwise to the start.
+fn page_offset(offset: f32, container_size: f32, scroll_size: f32, forward: bool) -> f32 {
+    let offset = if forward {
+        offset - container_size
+    } else {
+        offset + container_size
+    };
+
+    offset.clamp((container_size - scroll_size).min(0.), 0.)
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct ScrollbarState {
     hovered_axis: Option<ScrollbarAxis>,
@@ -139,7 +198,12 @@ impl ScrollbarState {
         state
     }
 
-    fn is_scrollbar_visible(&self) -> bool {
+    /// Returns true if the scrollbar is hovered or dragging, the track is painted in this state.
+    pub(crate) fn is_active(&self) -> bool {
+        self.hovered_axis.is_some() || self.dragged_axis.is_some()
+    }
+
+    pub(crate) fn is_scrollbar_visible(&self) -> bool {
         if let Some(last_time) = self.last_scroll_time {
             let elapsed = Instant::now().duration_since(last_time).as_secs_f32();
             elapsed < FADE_OUT_DURATION
@@ -163,7 +227,7 @@ impl ScrollbarAxis {
     }
 
     #[inline]
-    fn is_both(&self) -> bool {
+    pub(crate) fn is_both(&self) -> bool {
         matches!(self, Self::Both)
     }
 
@@ -198,6 +262,7 @@ pub struct Scrollbar {
     scroll_handle: Rc<Box<dyn ScrollHandleOffsetable>>,
     scroll_size: gpui::Size<Pixels>,
     state: Rc<Cell<ScrollbarState>>,
+    jump_to_click: bool,
 }
 
 impl Scrollbar {
@@ -213,8 +278,9 @@ impl Scrollbar {
             state,
             axis,
             scroll_size,
-            width: px(12.),
+            width: SCROLLBAR_WIDTH,
             scroll_handle: Rc::new(Box::new(scroll_handle)),
+            jump_to_click: true,
         }
     }
 
@@ -294,13 +360,20 @@ impl Scrollbar {
         self
     }
 
+    /// Set to jump the thumb to the clicked position of the track and start dragging from there,
+    /// otherwise clicking on the track scrolls a page toward the clicked position, default: true
+    pub fn jump_to_click(mut self, jump_to_click: bool) -> Self {
+        self.jump_to_click = jump_to_click;
+        self
+    }
+
     fn style_for_active(cx: &AppContext) -> (Hsla, Hsla, Hsla, Pixels, Pixels) {
         (
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
             cx.theme().border,
-            THUMB_INSET - px(1.),
-            THUMB_RADIUS,
+            THUMB_ACTIVE_INSET,
+            THUMB_ACTIVE_RADIUS,
         )
     }
 
@@ -309,24 +382,28 @@ impl Scrollbar {
             cx.theme().scrollbar_thumb_hover,
             cx.theme().scrollbar,
             cx.theme().border,
-            THUMB_INSET - px(1.),
-            THUMB_RADIUS,
+            THUMB_ACTIVE_INSET,
+            THUMB_ACTIVE_RADIUS,
         )
     }
 
     fn style_for_hovered_bar(cx: &AppContext) -> (Hsla, Hsla, Hsla, Pixels, Pixels) {
-        let (inset, radius) = if cx.theme().scrollbar_show.is_hover() {
-            (THUMB_INSET, THUMB_RADIUS - px(1.))
-        } else {
-            (THUMB_INSET - px(1.), THUMB_RADIUS)
-        };
-
         (
             cx.theme().scrollbar_thumb,
             cx.theme().scrollbar,
             gpui::transparent_black(),
-            inset,
-            radius,
+            THUMB_ACTIVE_INSET,
+            THUMB_ACTIVE_RADIUS,
+        )
+    }
+
+    fn style_for_always(cx: &AppContext) -> (Hsla, Hsla, Hsla, Pixels, Pixels) {
+        (
+            cx.theme().scrollbar_thumb,
+            gpui::transparent_black(),
+            gpui::transparent_black(),
+            THUMB_INSET,
+            THUMB_RADIUS - px(1.),
         )
     }
 
@@ -439,8 +516,11 @@ impl Element for Scrollbar {
                 continue;
             }
 
-            let thumb_length =
-                (container_size / scroll_area_size * container_size).max(px(MIN_THUMB_SIZE));
+            let thumb_length = px(thumb_length(
+                (container_size - margin_end).0,
+                scroll_area_size.0,
+                cx.theme().scrollbar_min_thumb_size,
+            ));
             let thumb_start = -(scroll_position / (scroll_area_size - container_size)
                 * (container_size - margin_end - thumb_length));
             let thumb_end = (thumb_start + thumb_length).min(container_size - margin_end);
@@ -473,21 +553,24 @@ impl Element for Scrollbar {
 
             let state = self.state.clone();
             let is_hover_to_show = cx.theme().scrollbar_show.is_hover();
+            let is_always_show = cx.theme().scrollbar_show.is_always();
             let is_hovered_on_bar = state.get().hovered_axis == Some(axis);
             let is_hovered_on_thumb = state.get().hovered_on_thumb == Some(axis);
 
             let (thumb_bg, bar_bg, bar_border, inset, radius) =
                 if state.get().dragged_axis == Some(axis) {
                     Self::style_for_active(cx)
-                } else if is_hover_to_show && is_hovered_on_bar {
+                } else if (is_hover_to_show || is_always_show) && is_hovered_on_bar {
                     if is_hovered_on_thumb {
                         Self::style_for_hovered_thumb(cx)
                     } else {
                         Self::style_for_hovered_bar(cx)
                     }
+                } else if is_always_show {
+                    Self::style_for_always(cx)
                 } else {
                     let mut idle_state = Self::style_for_idle(cx);
-                    // Delay 2s to fade out the scrollbar thumb (in 1s)
+                    // Auto-hide after inactivity, delay 2s to fade out the scrollbar thumb (in 1s)
                     if let Some(last_time) = state.get().last_scroll_time {
                         let elapsed = Instant::now().duration_since(last_time).as_secs_f32();
                         if elapsed < FADE_OUT_DURATION {
@@ -499,14 +582,8 @@ impl Element for Scrollbar {
                                     Self::style_for_hovered_bar(cx)
                                 };
                             } else {
-                                if elapsed < FADE_OUT_DELAY {
-                                    idle_state.0 = cx.theme().scrollbar_thumb;
-                                } else {
-                                    // opacity = 1 - (x - 2)^10
-                                    let opacity = 1.0 - (elapsed - FADE_OUT_DELAY).powi(10);
-                                    idle_state.0 = cx.theme().scrollbar_thumb.opacity(opacity);
-                                };
-
+                                idle_state.0 =
+                                    cx.theme().scrollbar_thumb.opacity(fade_opacity(elapsed));
                                 cx.request_animation_frame();
                             }
                         }
@@ -585,6 +662,8 @@ impl Element for Scrollbar {
         let hitbox_bounds = prepaint.hitbox.bounds;
         let is_visible = self.state.get().is_scrollbar_visible();
         let is_hover_to_show = cx.theme().scrollbar_show.is_hover();
+        let is_always_show = cx.theme().scrollbar_show.is_always();
+        let jump_to_click = self.jump_to_click;
 
         for state in prepaint.states.iter() {
             let axis = state.axis;
@@ -648,7 +727,7 @@ impl Element for Scrollbar {
 
             let safe_range = (-scroll_area_size + container_size)..px(0.);
 
-            if is_hover_to_show || is_visible {
+            if is_hover_to_show || is_always_show || is_visible {
                 cx.on_mouse_event({
                     let state = self.state.clone();
                     let view_id = self.view_id;
@@ -665,32 +744,63 @@ impl Element for Scrollbar {
                                 state.set(state.get().with_drag_pos(axis, pos));
 
                                 cx.notify(Some(view_id));
-                            } else {
+                            } else if jump_to_click {
                                 // click on the scrollbar, jump to the position
-                                // Set the thumb bar center to the click position
+                                // Set the thumb bar center to the click position, and keep dragging from there
                                 let offset = scroll_handle.offset();
                                 let percentage = if is_vertical {
-                                    (event.position.y - thumb_size / 2. - bounds.origin.y)
-                                        / (bounds.size.height - thumb_size)
+                                    thumb_ratio(
+                                        (event.position.y - thumb_size / 2. - bounds.origin.y).0,
+                                        bounds.size.height.0,
+                                        thumb_size.0,
+                                        margin_end.0,
+                                    )
+                                } else {
+                                    thumb_ratio(
+                                        (event.position.x - thumb_size / 2. - bounds.origin.x).0,
+                                        bounds.size.width.0,
+                                        thumb_size.0,
+                                        margin_end.0,
+                                    )
+                                };
+
+                                let scroll_to = (-(scroll_area_size - container_size) * percentage)
+                                    .clamp(safe_range.start, safe_range.end);
+                                if is_vertical {
+                                    scroll_handle.set_offset(point(offset.x, scroll_to));
                                 } else {
-                                    (event.position.x - thumb_size / 2. - bounds.origin.x)
-                                        / (bounds.size.width - thumb_size)
+                                    scroll_handle.set_offset(point(scroll_to, offset.y));
                                 }
-                                .min(1.);
+
+                                let half = thumb_size / 2.;
+                                state.set(state.get().with_drag_pos(axis, point(half, half)));
+                                cx.notify(Some(view_id));
+                            } else {
+                                // click on the scrollbar, scroll a page toward the click position
+                                let offset = scroll_handle.offset();
+                                let forward = if is_vertical {
+                                    event.position.y > thumb_bounds.origin.y
+                                } else {
+                                    event.position.x > thumb_bounds.origin.x
+                                };
+                                let current = if is_vertical { offset.y } else { offset.x };
+                                let scroll_to = px(page_offset(
+                                    current.0,
+                                    container_size.0,
+                                    scroll_area_size.0,
+                                    forward,
+                                ));
 
                                 if is_vertical {
-                                    scroll_handle.set_offset(point(
-                                        offset.x,
-                                        (-scroll_area_size * percentage)
-                                            .clamp(safe_range.start, safe_range.end),
-                                    ));
+                                    scroll_handle.set_offset(point(offset.x, scroll_to));
                                 } else {
-                                    scroll_handle.set_offset(point(
-                                        (-scroll_area_size * percentage)
-                                            .clamp(safe_range.start, safe_range.end),
-                                        offset.y,
-                                    ));
+                                    scroll_handle.set_offset(point(scroll_to, offset.y));
                                 }
+                                state.set(state.get().with_last_scroll(
+                                    scroll_handle.offset(),
+                                    Some(Instant::now()),
+                                ));
+                                cx.notify(Some(view_id));
                             }
                         }
                     }
@@ -737,14 +847,21 @@ impl Element for Scrollbar {
                         // We need to keep the thumb bar still at the origin down position
                         let drag_pos = state.get().drag_pos;
 
-                        let percentage = (if is_vertical {
-                            (event.position.y - drag_pos.y - bounds.origin.y)
-                                / (bounds.size.height - thumb_size)
+                        let percentage = if is_vertical {
+                            thumb_ratio(
+                                (event.position.y - drag_pos.y - bounds.origin.y).0,
+                                bounds.size.height.0,
+                                thumb_size.0,
+                                margin_end.0,
+                            )
                         } else {
-                            (event.position.x - drag_pos.x - bounds.origin.x)
-                                / (bounds.size.width - thumb_size - margin_end)
-                        })
-                        .clamp(0., 1.);
+                            thumb_ratio(
+                                (event.position.x - drag_pos.x - bounds.origin.x).0,
+                                bounds.size.width.0,
+                                thumb_size.0,
+                                margin_end.0,
+                            )
+                        };
 
                         let offset = if is_vertical {
                             point(
@@ -780,3 +897,45 @@ impl Element for Scrollbar {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{fade_opacity, page_offset, thumb_length, thumb_ratio};
+
+    #[test]
+    fn test_fade_opacity() {
+        assert_eq!(fade_opacity(0.), 1.);
+        assert_eq!(fade_opacity(1.9), 1.);
+        assert!(fade_opacity(2.5) > 0. && fade_opacity(2.5) < 1.);
+        assert_eq!(fade_opacity(3.), 0.);
+        assert_eq!(fade_opacity(10.), 0.);
+    }
+
+    #[test]
+    fn test_thumb_length() {
+        assert_eq!(thumb_length(100., 1000., 20.), 20.);
+        assert_eq!(thumb_length(500., 1000., 20.), 250.);
+        assert_eq!(thumb_length(50., 10000., 80.), 50.);
+        assert_eq!(thumb_length(100., 0., 20.), 100.);
+    }
+
+    #[test]
+    fn test_thumb_ratio() {
+        assert_eq!(thumb_ratio(0., 100., 20., 0.), 0.);
+        assert_eq!(thumb_ratio(40., 100., 20., 0.), 0.5);
+        // The end of the track is reserved for the other scrollbar.
+        assert_eq!(thumb_ratio(35., 100., 20., 10.), 0.5);
+        assert_eq!(thumb_ratio(80., 100., 20., 10.), 1.);
+        assert_eq!(thumb_ratio(-5., 100., 20., 10.), 0.);
+        assert_eq!(thumb_ratio(10., 20., 20., 0.), 0.);
+    }
+
+    #[test]
+    fn test_page_offset() {
+        assert_eq!(page_offset(0., 100., 1000., true), -100.);
+        assert_eq!(page_offset(-100., 100., 1000., false), 0.);
+        assert_eq!(page_offset(-850., 100., 1000., true), -900.);
+        assert_eq!(page_offset(-50., 100., 1000., false), 0.);
+        assert_eq!(page_offset(0., 100., 50., true), 0.);
+    }
+}
@@ -0,0 +1,362 @@
+use std::{collections::HashSet, fmt};
+
+use gpui::{
+    div, px, Action, AppContext, Global, Keystroke, ParentElement as _, SharedString, Styled,
+    WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{
+    h_flex,
+    keymap::{Keymap, KeymapEntry},
+    popup_menu::key_shortcut,
+    theme::ActiveTheme as _,
+    v_flex, ContextModal as _, StyledExt as _,
+};
+
+/// The group of the shortcuts registered without [`Shortcut::group`].
+const DEFAULT_GROUP: &str = "General";
+
+/// A key binding with the description, registered to the [`Shortcuts`].
+#[derive(Clone)]
+pub struct Shortcut {
+    entry: KeymapEntry,
+    description: SharedString,
+    group: SharedString,
+}
+
+impl Shortcut {
+    /// Create a shortcut, the `keystrokes` is same as the [`KeymapEntry::new`], e.g.: `cmd-shift-p`.
+    pub fn new<A: Action>(
+        keystrokes: &str,
+        action: A,
+        description: impl Into<SharedString>,
+    ) -> Self {
+        Self {
+            entry: KeymapEntry::new(keystrokes, action, None),
+            description: description.into(),
+            group: DEFAULT_GROUP.into(),
+        }
+    }
+
+    /// Set the key context of the shortcut, e.g.: `Editor`, default is global.
+    pub fn context(mut self, context: impl Into<SharedString>) -> Self {
+        self.entry.context = Some(context.into());
+        self
+    }
+
+    /// Set the group of the shortcut, the group can be enabled or disabled together,
+    /// and the shortcuts are listed by the groups in the help dialog, default: `General`
+    pub fn group(mut self, group: impl Into<SharedString>) -> Self {
+        self.group = group.into();
+        self
+    }
+
+    pub fn keystrokes(&self) -> &SharedString {
+        &self.entry.keystrokes
+    }
+
+    pub fn description(&self) -> &SharedString {
+        &self.description
+    }
+
+    pub fn group_name(&self) -> &SharedString {
+        &self.group
+    }
+
+    pub fn key_context(&self) -> Option<&SharedString> {
+        self.entry.context.as_ref()
+    }
+
+    pub fn action(&self) -> &dyn Action {
+        self.entry.action.as_ref()
+    }
+
+    fn conflicts_with(&self, entry: &KeymapEntry) -> bool {
+        is_same_context(self.entry.context.as_deref(), entry.context.as_deref())
+            && normalize_keystrokes(&self.entry.keystrokes)
+                == normalize_keystrokes(&entry.keystrokes)
+    }
+}
+
+/// The error of [`Shortcuts::register`], the keystrokes are already used in the same context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortcutConflict {
+    pub keystrokes: SharedString,
+    pub context: Option<SharedString>,
+    /// The description of the registered shortcut, or the action name of the default key binding.
+    pub existing: SharedString,
+}
+
+impl fmt::Display for ShortcutConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the shortcut `{}`", self.keystrokes)?;
+        if let Some(context) = &self.context {
+            write!(f, " in `{}`", context)?;
+        }
+        write!(f, " is already used by `{}`", self.existing)
+    }
+}
+
+impl std::error::Error for ShortcutConflict {}
+
+/// The registry of the shortcuts with the descriptions, to detect the conflicts and to list
+/// them in the "Keyboard Shortcuts" help dialog, see [`open_shortcuts_help`].
+///
+/// The shortcuts are bound into the [`Keymap`], so the user keymap still takes precedence.
+///
+/// ```ignore
+/// Shortcuts::register(
+///     Shortcut::new("cmd-shift-o", OpenOrder, "Open order ticket").group("Trading"),
+///     cx,
+/// )?;
+///
+/// // Disable the trading shortcuts in the read-only mode.
+/// Shortcuts::set_group_enabled("Trading", false, cx);
+/// ```
+#[derive(Default)]
+pub struct Shortcuts {
+    shortcuts: Vec<Shortcut>,
+    disabled_groups: HashSet<SharedString>,
+}
+
+impl Global for Shortcuts {}
+
+pub(crate) fn init(cx: &mut AppContext) {
+    cx.set_global(Shortcuts::default());
+}
+
+impl Shortcuts {
+    pub fn global(cx: &AppContext) -> &Self {
+        cx.global::<Self>()
+    }
+
+    /// Register the shortcut, returns error if the keystrokes are already used in the same context,
+    /// by another shortcut or a default key binding of the components.
+    pub fn register(shortcut: Shortcut, cx: &mut AppContext) -> Result<(), ShortcutConflict> {
+        let conflict = |existing: SharedString| ShortcutConflict {
+            keystrokes: shortcut.entry.keystrokes.clone(),
+            context: shortcut.entry.context.clone(),
+            existing,
+        };
+
+        if let Some(existing) = Self::global(cx)
+            .shortcuts
+            .iter()
+            .find(|existing| shortcut.conflicts_with(&existing.entry))
+        {
+            return Err(conflict(existing.description.clone()));
+        }
+        if let Some(entry) = cx.try_global::<Keymap>().and_then(|keymap| {
+            keymap
+                .defaults()
+                .iter()
+                .find(|entry| shortcut.conflicts_with(entry))
+        }) {
+            return Err(conflict(entry.action.name().into()));
+        }
+
+        cx.global_mut::<Self>().shortcuts.push(shortcut);
+        Self::rebind(cx);
+        Ok(())
+    }
+
+    /// Register the shortcuts, returns the conflicts of the ones not registered.
+    pub fn register_all(
+        shortcuts: impl IntoIterator<Item = Shortcut>,
+        cx: &mut AppContext,
+    ) -> Vec<ShortcutConflict> {
+        shortcuts
+            .into_iter()
+            .filter_map(|shortcut| Self::register(shortcut, cx).err())
+            .collect()
+    }
+
+    /// Remove the shortcuts of the group, e.g.: when the module of the app is unloaded.
+    pub fn unregister_group(group: &str, cx: &mut AppContext) {
+        cx.global_mut::<Self>()
+            .shortcuts
+            .retain(|shortcut| shortcut.group.as_ref() != group);
+        Self::rebind(cx);
+    }
+
+    /// Enable or disable the shortcuts of the group, the disabled shortcuts are not bound.
+    pub fn set_group_enabled(group: impl Into<SharedString>, enabled: bool, cx: &mut AppContext) {
+        let group = group.into();
+        let this = cx.global_mut::<Self>();
+        let changed = if enabled {
+            this.disabled_groups.remove(&group)
+        } else {
+            this.disabled_groups.insert(group)
+        };
+
+        if changed {
+            Self::rebind(cx);
+        }
+    }
+
+    pub fn is_group_enabled(&self, group: &str) -> bool {
+        !self.disabled_groups.contains(group)
+    }
+
+    /// Returns all the registered shortcuts in the order of the registration.
+    pub fn shortcuts(&self) -> &[Shortcut] {
+        &self.shortcuts
+    }
+
+    /// Returns the names of the groups in the order of the first registration.
+    pub fn groups(&self) -> Vec<SharedString> {
+        let mut groups: Vec<SharedString> = vec![];
+        for shortcut in &self.shortcuts {
+            if !groups.contains(&shortcut.group) {
+                groups.push(shortcut.group.clone());
+            }
+        }
+        groups
+    }
+
+    /// Returns the shortcuts of the group.
+    pub fn group<'a>(&'a self, group: &'a str) -> impl Iterator<Item = &'a Shortcut> + 'a {
+        self.shortcuts
+            .iter()
+            .filter(move |shortcut| shortcut.group.as_ref() == group)
+    }
+
+    fn rebind(cx: &mut AppContext) {
+        let this = Self::global(cx);
+        let entries = this
+            .shortcuts
+            .iter()
+            .filter(|shortcut| this.is_group_enabled(&shortcut.group))
+            .map(|shortcut| shortcut.entry.clone())
+            .collect();
+        Keymap::set_shortcuts(entries, cx);
+    }
+}
+
+fn is_same_context(a: Option<&str>, b: Option<&str>) -> bool {
+    a.map(str::trim) == b.map(str::trim)
+}
+
+/// Returns the keystrokes in the canonical form to compare,
+/// e.g.: `Shift-Ctrl-A` and `ctrl-shift-a` are the same.
+fn normalize_keystrokes(keystrokes: &str) -> String {
+    const MODIFIERS: [&str; 5] = ["ctrl", "alt", "shift", "fn", "cmd"];
+
+    keystrokes
+        .split_whitespace()
+        .map(|keystroke| {
+            let keystroke = keystroke.to_lowercase();
+            // The `-` key, e.g.: `cmd--`.
+            let (modifiers, key) = match keystroke.strip_suffix('-') {
+                Some(modifiers) => (modifiers, "-"),
+                None => keystroke.rsplit_once('-').unwrap_or(("", &keystroke)),
+            };
+
+            let modifiers = modifiers
+                .split('-')
+                .filter(|modifier| !modifier.is_empty())
+                .map(|modifier| match modifier {
+                    "control" => "ctrl",
+                    "option" => "alt",
+                    "function" => "fn",
+                    "command" | "super" | "win" | "platform" => "cmd",
+                    modifier => modifier,
+                })
+                .collect::<HashSet<_>>();
+
+            MODIFIERS
+                .iter()
+                .filter(|modifier| modifiers.contains(*modifier))
+                .copied()
+                .chain([key])
+                .collect::<Vec<_>>()
+                .join("-")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the keystrokes in the platform style to display, e.g.: `Ctrl+Shift+P`.
+fn display_keystrokes(keystrokes: &str) -> SharedString {
+    keystrokes
+        .split_whitespace()
+        .map(|keystroke| match Keystroke::parse(keystroke) {
+            Ok(keystroke) => key_shortcut(keystroke),
+            Err(_) => keystroke.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+        .into()
+}
+
+/// Open a modal to list the enabled shortcuts of the [`Shortcuts`] by the groups.
+pub fn open_shortcuts_help(cx: &mut WindowContext) {
+    cx.open_modal(|modal, cx| {
+        let shortcuts = Shortcuts::global(cx);
+        let groups = shortcuts
+            .groups()
+            .into_iter()
+            .filter(|group| shortcuts.is_group_enabled(group))
+            .map(|group| {
+                v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .child(group.clone())
+                            .text_color(cx.theme().muted_foreground)
+                            .font_semibold(),
+                    )
+                    .children(shortcuts.group(&group).map(|shortcut| {
+                        h_flex()
+                            .justify_between()
+                            .gap_4()
+                            .child(shortcut.description.clone())
+                            .child(
+                                div()
+                                    .flex_shrink_0()
+                                    .child(display_keystrokes(&shortcut.entry.keystrokes))
+                                    .px_1p5()
+                                    .rounded(px(cx.theme().radius))
+                                    .border_1()
+                                    .border_color(cx.theme().border)
+                                    .bg(cx.theme().muted)
+                                    .text_xs(),
+                            )
+                    }))
+            })
+            .collect::<Vec<_>>();
+
+        modal
+            .width(px(480.))
+            .title(t!("Shortcuts.Title").to_string())
+            .child(v_flex().gap_4().text_sm().children(groups))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_same_context, normalize_keystrokes};
+
+    #[test]
+    fn test_normalize_keystrokes() {
+        assert_eq!(normalize_keystrokes("ctrl-shift-a"), "ctrl-shift-a");
+        assert_eq!(normalize_keystrokes("Shift-Ctrl-A"), "ctrl-shift-a");
+        assert_eq!(normalize_keystrokes("cmd-k  cmd-s"), "cmd-k cmd-s");
+        assert_eq!(normalize_keystrokes("super-k"), "cmd-k");
+        assert_eq!(normalize_keystrokes("cmd--"), "cmd--");
+        assert_eq!(normalize_keystrokes("-"), "-");
+        assert_eq!(normalize_keystrokes("escape"), "escape");
+        assert_ne!(
+            normalize_keystrokes("ctrl-a"),
+            normalize_keystrokes("alt-a")
+        );
+    }
+
+    #[test]
+    fn test_is_same_context() {
+        assert!(is_same_context(None, None));
+        assert!(is_same_context(Some("Editor"), Some(" Editor")));
+        assert!(!is_same_context(Some("Editor"), None));
+    }
+}
@@ -1,8 +1,21 @@
-use crate::{theme::ActiveTheme, v_flex, Collapsible};
+use std::{collections::BTreeSet, rc::Rc, time::Duration};
+
+use crate::{
+    animation::{cubic_bezier, TransitionExt as _},
+    badge::Badge,
+    h_flex,
+    theme::ActiveTheme,
+    v_flex, Collapsible, Icon, IconName, Sizable as _,
+};
 use gpui::{
-    div, prelude::FluentBuilder as _, Div, IntoElement, ParentElement, RenderOnce, SharedString,
+    div, prelude::FluentBuilder as _, px, Animation, Div, ElementId, InteractiveElement as _,
+    IntoElement, ParentElement, RenderOnce, SharedString, StatefulInteractiveElement as _,
     Styled as _, WindowContext,
 };
+use serde::{Deserialize, Serialize};
+
+/// The max height to animate the group content when expanding.
+const EXPAND_MAX_HEIGHT: f32 = 800.;
 
 /// A sidebar group
 #[derive(IntoElement)]
@@ -10,6 +23,9 @@ pub struct SidebarGroup<E: Collapsible + IntoElement + 'static> {
     base: Div,
     label: SharedString,
     is_collapsed: bool,
+    expanded: bool,
+    badge: usize,
+    on_toggle_click: Option<Rc<dyn Fn(&bool, &mut WindowContext)>>,
     children: Vec<E>,
 }
 
@@ -19,6 +35,9 @@ impl<E: Collapsible + IntoElement> SidebarGroup<E> {
             base: div().gap_2().flex_col(),
             label: label.into(),
             is_collapsed: false,
+            expanded: true,
+            badge: 0,
+            on_toggle_click: None,
             children: Vec::new(),
         }
     }
@@ -32,6 +51,31 @@ impl<E: Collapsible + IntoElement> SidebarGroup<E> {
         self.children.extend(children);
         self
     }
+
+    /// Set the group to be expanded, default is true.
+    ///
+    /// The children are always visible when the sidebar is collapsed.
+    pub fn expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Set the count to show in the group header, the badge is hidden if the count is 0.
+    pub fn badge(mut self, count: usize) -> Self {
+        self.badge = count;
+        self
+    }
+
+    /// Set the handler when the group header is clicked, the group can only be folded with the handler.
+    ///
+    /// The first argument `bool` is the new expanded state.
+    pub fn on_toggle_click(
+        mut self,
+        on_toggle_click: impl Fn(&bool, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_toggle_click = Some(Rc::new(on_toggle_click));
+        self
+    }
 }
 impl<E: Collapsible + IntoElement> Collapsible for SidebarGroup<E> {
     fn is_collapsed(&self) -> bool {
@@ -45,27 +89,142 @@ impl<E: Collapsible + IntoElement> Collapsible for SidebarGroup<E> {
 }
 impl<E: Collapsible + IntoElement> RenderOnce for SidebarGroup<E> {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let expanded = self.expanded || self.is_collapsed;
+        let on_toggle_click = self.on_toggle_click.clone();
+        let label = self.label.clone();
+
         v_flex()
             .relative()
             .p_2()
             .when(!self.is_collapsed, |this| {
                 this.child(
-                    div()
+                    h_flex()
+                        .id(ElementId::Name(format!("sidebar-group-{}", label).into()))
                         .flex_shrink_0()
                         .px_2()
+                        .gap_1()
                         .rounded_md()
                         .text_xs()
                         .text_color(cx.theme().sidebar_foreground.opacity(0.7))
                         .h_8()
-                        .child(self.label),
+                        .child(div().flex_1().overflow_hidden().child(self.label))
+                        .when(self.badge > 0, |this| {
+                            this.child(Badge::secondary().small().count(self.badge))
+                        })
+                        .when_some(on_toggle_click, |this, on_toggle_click| {
+                            let expanded = self.expanded;
+                            this.cursor_pointer()
+                                .hover(|this| this.bg(cx.theme().sidebar_accent))
+                                .child(
+                                    Icon::new(if expanded {
+                                        IconName::ChevronDown
+                                    } else {
+                                        IconName::ChevronRight
+                                    })
+                                    .xsmall(),
+                                )
+                                .on_click(move |_, cx| on_toggle_click(&!expanded, cx))
+                        }),
                 )
             })
-            .child(
-                self.base.children(
+            .when(expanded, |this| {
+                let content = self.base.children(
                     self.children
                         .into_iter()
                         .map(|child| child.collapsed(self.is_collapsed)),
-                ),
-            )
+                );
+
+                if self.is_collapsed {
+                    this.child(content)
+                } else {
+                    // The content is removed when folded, so the animation will replay on expanding.
+                    this.child(
+                        div().overflow_hidden().child(content).with_transition(
+                            ElementId::Name(format!("sidebar-group-content-{}", label).into()),
+                            Animation::new(Duration::from_secs_f64(0.2))
+                                .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                            |this, delta| {
+                                // Remove the max height after expanded, to not clip the tall groups.
+                                if delta >= 1. {
+                                    this
+                                } else {
+                                    this.max_h(px(EXPAND_MAX_HEIGHT * delta)).opacity(delta)
+                                }
+                            },
+                            cx,
+                        ),
+                    )
+                }
+            })
+    }
+}
+
+/// The expanded state of the sidebar groups, use to persist the sidebar configuration,
+/// the groups are identified by the label.
+///
+/// ```ignore
+/// SidebarGroup::new("Projects")
+///     .expanded(state.is_group_expanded("Projects"))
+///     .on_toggle_click(cx.listener(|this, expanded: &bool, cx| {
+///         this.sidebar_state.set_group_expanded("Projects", *expanded);
+///         cx.notify();
+///     }))
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SidebarState {
+    /// The labels of the folded groups, the groups are expanded by default.
+    #[serde(default)]
+    pub collapsed_groups: BTreeSet<String>,
+}
+
+impl SidebarState {
+    /// Returns true if the group is expanded.
+    pub fn is_group_expanded(&self, label: &str) -> bool {
+        !self.collapsed_groups.contains(label)
+    }
+
+    /// Set the expanded state of the group.
+    pub fn set_group_expanded(&mut self, label: &str, expanded: bool) {
+        if expanded {
+            self.collapsed_groups.remove(label);
+        } else {
+            self.collapsed_groups.insert(label.to_string());
+        }
+    }
+
+    /// Dump the state as JSON, to persist it across launches.
+    pub fn dump(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    /// Restore the state from the JSON dumped by [`SidebarState::dump`],
+    /// returns the default state if the JSON is invalid.
+    pub fn restore(json: &str) -> Self {
+        serde_json::from_str(json).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SidebarState;
+
+    #[test]
+    fn test_sidebar_state() {
+        let mut state = SidebarState::default();
+        assert!(state.is_group_expanded("Projects"));
+
+        state.set_group_expanded("Projects", false);
+        state.set_group_expanded("Platform", true);
+        assert!(!state.is_group_expanded("Projects"));
+        assert!(state.is_group_expanded("Platform"));
+
+        let json = state.dump();
+        assert_eq!(json, r#"{"collapsed_groups":["Projects"]}"#);
+        assert_eq!(SidebarState::restore(&json), state);
+        assert_eq!(SidebarState::restore("invalid"), SidebarState::default());
+        assert_eq!(SidebarState::restore("{}"), SidebarState::default());
+
+        state.set_group_expanded("Projects", true);
+        assert!(state.is_group_expanded("Projects"));
     }
 }
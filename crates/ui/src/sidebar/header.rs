@@ -64,9 +64,11 @@ impl RenderOnce for SidebarHeader {
             .justify_between()
             .cursor_pointer()
             .rounded_md()
-            .hover(|this| {
-                this.bg(cx.theme().sidebar_accent)
-                    .text_color(cx.theme().sidebar_accent_foreground)
+            .when(!cx.theme().lite, |this| {
+                this.hover(|this| {
+                    this.bg(cx.theme().sidebar_accent)
+                        .text_color(cx.theme().sidebar_accent_foreground)
+                })
             })
             .when(self.selected, |this| {
                 this.bg(cx.theme().sidebar_accent)
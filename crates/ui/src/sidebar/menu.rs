@@ -169,9 +169,11 @@ impl SidebarMenuItem {
             .rounded_md()
             .text_sm()
             .cursor_pointer()
-            .hover(|this| {
-                this.bg(cx.theme().sidebar_accent)
-                    .text_color(cx.theme().sidebar_accent_foreground)
+            .when(!cx.theme().lite, |this| {
+                this.hover(|this| {
+                    this.bg(cx.theme().sidebar_accent)
+                        .text_color(cx.theme().sidebar_accent_foreground)
+                })
             })
             .when(is_active, |this| {
                 this.font_medium()
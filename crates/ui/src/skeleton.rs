@@ -1,21 +1,85 @@
-use crate::theme::ActiveTheme;
+use crate::{animation::cubic_bezier, h_flex, theme::ActiveTheme, v_flex};
 use gpui::{
-    bounce, div, ease_in_out, Animation, AnimationExt, Div, IntoElement, ParentElement as _,
-    RenderOnce, Styled,
+    bounce, div, ease_in_out, prelude::FluentBuilder as _, relative, Animation, AnimationExt, Div,
+    IntoElement, ParentElement as _, RenderOnce, Styled,
 };
 use std::time::Duration;
 
+/// The loading animation of the [`Skeleton`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SkeletonAnimation {
+    /// Fade in and out, this is default.
+    #[default]
+    Pulse,
+    /// A highlight sweeping from left to right.
+    Shimmer,
+    /// No animation.
+    None,
+}
+
 #[derive(IntoElement)]
 pub struct Skeleton {
     base: Div,
+    animation: SkeletonAnimation,
 }
 
 impl Skeleton {
     pub fn new() -> Self {
         Self {
             base: div().w_full().h_4().rounded_md(),
+            animation: SkeletonAnimation::default(),
         }
     }
+
+    /// Set the loading animation, default is [`SkeletonAnimation::Pulse`].
+    pub fn animation(mut self, animation: SkeletonAnimation) -> Self {
+        self.animation = animation;
+        self
+    }
+
+    /// Use the shimmer animation.
+    pub fn shimmer(self) -> Self {
+        self.animation(SkeletonAnimation::Shimmer)
+    }
+
+    /// A paragraph of text with the given number of lines, the last line is shorter.
+    pub fn paragraph(lines: usize) -> Div {
+        v_flex().w_full().gap_2().children(
+            (0..lines)
+                .map(|ix| Skeleton::new().when(lines > 1 && ix == lines - 1, |this| this.w_3_5())),
+        )
+    }
+
+    /// A round avatar with two lines of text.
+    pub fn avatar_with_text() -> Div {
+        h_flex()
+            .w_full()
+            .gap_4()
+            .child(Skeleton::new().flex_shrink_0().size_12().rounded_full())
+            .child(
+                v_flex()
+                    .flex_1()
+                    .gap_2()
+                    .child(Skeleton::new().w_4_5())
+                    .child(Skeleton::new().w_3_5()),
+            )
+    }
+
+    /// A table with a header and the given number of rows and columns.
+    pub fn table(rows: usize, cols: usize) -> Div {
+        let row = |height: fn(Skeleton) -> Skeleton| {
+            h_flex()
+                .w_full()
+                .gap_4()
+                .children((0..cols).map(move |_| div().flex_1().child(height(Skeleton::new()))))
+        };
+
+        v_flex()
+            .w_full()
+            .gap_3()
+            .child(row(|this| this.h_5()))
+            .children((0..rows).map(move |_| row(|this| this.h_4())))
+    }
 }
 
 impl Styled for Skeleton {
@@ -26,17 +90,48 @@ impl Styled for Skeleton {
 
 impl RenderOnce for Skeleton {
     fn render(self, cx: &mut gpui::WindowContext) -> impl IntoElement {
-        div().child(
-            self.base.bg(cx.theme().skeleton).with_animation(
-                "skeleton",
-                Animation::new(Duration::from_secs(2))
-                    .repeat()
-                    .with_easing(bounce(ease_in_out)),
-                move |this, delta| {
-                    let v = 1.0 - delta * 0.5;
-                    this.opacity(v)
-                },
-            ),
-        )
+        let base = self.base.bg(cx.theme().skeleton);
+
+        // All the animations are disabled in the lite mode.
+        let animation = if cx.theme().lite {
+            SkeletonAnimation::None
+        } else {
+            self.animation
+        };
+
+        div().child(match animation {
+            SkeletonAnimation::Pulse => base
+                .with_animation(
+                    "skeleton",
+                    Animation::new(Duration::from_secs(2))
+                        .repeat()
+                        .with_easing(bounce(ease_in_out)),
+                    move |this, delta| {
+                        let v = 1.0 - delta * 0.5;
+                        this.opacity(v)
+                    },
+                )
+                .into_any_element(),
+            SkeletonAnimation::Shimmer => base
+                .relative()
+                .overflow_hidden()
+                .child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .w(relative(0.5))
+                        .bg(cx.theme().background.opacity(0.4))
+                        .with_animation(
+                            "skeleton-shimmer",
+                            Animation::new(Duration::from_secs_f64(1.5))
+                                .repeat()
+                                .with_easing(cubic_bezier(0.4, 0., 0.2, 1.)),
+                            move |this, delta| this.left(relative(-0.5 + delta * 1.5)),
+                        ),
+                )
+                .into_any_element(),
+            SkeletonAnimation::None => base.into_any_element(),
+        })
     }
 }
@@ -1,16 +1,40 @@
+use crate::keymap::{self, KeymapEntry};
 use crate::{theme::ActiveTheme, tooltip::Tooltip};
 use gpui::{
-    canvas, div, prelude::FluentBuilder as _, px, relative, Axis, Bounds, DragMoveEvent, EntityId,
-    EventEmitter, InteractiveElement, IntoElement, MouseButton, MouseDownEvent, ParentElement as _,
-    Pixels, Point, Render, StatefulInteractiveElement as _, Styled, ViewContext,
-    VisualContext as _,
+    actions, canvas, div, prelude::FluentBuilder as _, px, relative, AppContext, Axis, Bounds,
+    DragMoveEvent, EntityId, EventEmitter, FocusHandle, FocusableView, InteractiveElement,
+    IntoElement, MouseButton, MouseDownEvent, ParentElement as _, Pixels, Point, Render,
+    SharedString, StatefulInteractiveElement as _, Styled, ViewContext, VisualContext as _,
 };
 
+actions!(
+    range_slider,
+    [Decrease, Increase, PageDecrease, PageIncrease]
+);
+
+const RANGE_CONTEXT: &str = "RangeSlider";
+
+pub fn init(cx: &mut AppContext) {
+    keymap::bind_keys(
+        cx,
+        vec![
+            KeymapEntry::new("left", Decrease, Some(RANGE_CONTEXT)),
+            KeymapEntry::new("down", Decrease, Some(RANGE_CONTEXT)),
+            KeymapEntry::new("right", Increase, Some(RANGE_CONTEXT)),
+            KeymapEntry::new("up", Increase, Some(RANGE_CONTEXT)),
+            KeymapEntry::new("pagedown", PageDecrease, Some(RANGE_CONTEXT)),
+            KeymapEntry::new("pageup", PageIncrease, Some(RANGE_CONTEXT)),
+        ],
+    );
+}
+
 #[derive(Clone, Render)]
 pub struct DragThumb(EntityId);
 
 pub enum SliderEvent {
     Change(f32),
+    /// The range of the [`RangeSlider`] has been changed, (start, end).
+    RangeChange(f32, f32),
 }
 
 /// A Slider element.
@@ -143,7 +167,7 @@ impl Slider {
             .rounded_full()
             .border_1()
             .border_color(cx.theme().slider_bar.opacity(0.9))
-            .when(cx.theme().shadow, |this| this.shadow_md())
+            .when(cx.theme().has_shadow(), |this| this.shadow_md())
             .bg(cx.theme().slider_thumb)
             .tooltip(move |cx| Tooltip::new(format!("{}", value), cx))
     }
@@ -194,3 +218,292 @@ impl Render for Slider {
             )
     }
 }
+
+/// The thumb of the [`RangeSlider`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeThumb {
+    Start,
+    End,
+}
+
+#[derive(Clone, Render)]
+pub struct DragRangeThumb(EntityId, RangeThumb);
+
+/// A Slider with two thumbs to select a range.
+///
+/// Use the arrow keys to adjust the active thumb (the last dragged or clicked one) by a step,
+/// and page up/down to adjust by 10 steps.
+pub struct RangeSlider {
+    focus_handle: FocusHandle,
+    min: f32,
+    max: f32,
+    step: f32,
+    start: f32,
+    end: f32,
+    min_gap: f32,
+    marks: Vec<(f32, SharedString)>,
+    active_thumb: RangeThumb,
+    bounds: Bounds<Pixels>,
+}
+
+impl RangeSlider {
+    pub fn new(cx: &mut ViewContext<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            min: 0.0,
+            max: 100.0,
+            step: 1.0,
+            start: 0.0,
+            end: 100.0,
+            min_gap: 0.0,
+            marks: Vec::new(),
+            active_thumb: RangeThumb::Start,
+            bounds: Bounds::default(),
+        }
+    }
+
+    /// Set the minimum value of the slider, default: 0.0
+    pub fn min(mut self, min: f32) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the maximum value of the slider, default: 100.0
+    pub fn max(mut self, max: f32) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the step value of the slider, default: 1.0
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step;
+        self
+    }
+
+    /// Set the minimum gap between the start and end, default: 0.0
+    pub fn min_gap(mut self, min_gap: f32) -> Self {
+        self.min_gap = min_gap;
+        self
+    }
+
+    /// Set the default range of the slider, default: min..max
+    pub fn default_range(mut self, start: f32, end: f32) -> Self {
+        self.start = start;
+        self.end = end;
+        self
+    }
+
+    /// Set the tick marks of the slider, the label can be empty to only show the tick.
+    pub fn marks(
+        mut self,
+        marks: impl IntoIterator<Item = (f32, impl Into<SharedString>)>,
+    ) -> Self {
+        self.marks = marks
+            .into_iter()
+            .map(|(value, label)| (value, label.into()))
+            .collect();
+        self
+    }
+
+    /// Set the range of the slider.
+    pub fn set_range(&mut self, start: f32, end: f32, cx: &mut ViewContext<Self>) {
+        self.start = start;
+        self.end = end;
+        cx.notify();
+    }
+
+    /// Return the range of the slider, (start, end).
+    pub fn range(&self) -> (f32, f32) {
+        (self.start, self.end)
+    }
+
+    fn relative(&self, value: f32) -> f32 {
+        ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+    }
+
+    /// Update the value of the thumb, keep the `min_gap` between the thumbs.
+    fn update_thumb(&mut self, thumb: RangeThumb, value: f32, cx: &mut ViewContext<Self>) {
+        let value = ((value / self.step).round() * self.step).clamp(self.min, self.max);
+        match thumb {
+            RangeThumb::Start => self.start = value.min(self.end - self.min_gap).max(self.min),
+            RangeThumb::End => self.end = value.max(self.start + self.min_gap).min(self.max),
+        }
+
+        self.active_thumb = thumb;
+        cx.emit(SliderEvent::RangeChange(self.start, self.end));
+        cx.notify();
+    }
+
+    fn value_by_position(&self, position: Point<Pixels>) -> f32 {
+        let relative = (position.x - self.bounds.left()) / self.bounds.size.width;
+        self.min + (self.max - self.min) * relative
+    }
+
+    fn on_mouse_down(&mut self, event: &MouseDownEvent, cx: &mut ViewContext<Self>) {
+        self.focus_handle.focus(cx);
+
+        // Move the nearest thumb to the mouse position.
+        let value = self.value_by_position(event.position);
+        let thumb = if (value - self.start).abs() <= (value - self.end).abs() {
+            RangeThumb::Start
+        } else {
+            RangeThumb::End
+        };
+        self.update_thumb(thumb, value, cx);
+    }
+
+    fn adjust(&mut self, steps: f32, cx: &mut ViewContext<Self>) {
+        let thumb = self.active_thumb;
+        let value = match thumb {
+            RangeThumb::Start => self.start,
+            RangeThumb::End => self.end,
+        };
+        self.update_thumb(thumb, value + self.step * steps, cx);
+    }
+
+    fn decrease(&mut self, _: &Decrease, cx: &mut ViewContext<Self>) {
+        self.adjust(-1., cx);
+    }
+
+    fn increase(&mut self, _: &Increase, cx: &mut ViewContext<Self>) {
+        self.adjust(1., cx);
+    }
+
+    fn page_decrease(&mut self, _: &PageDecrease, cx: &mut ViewContext<Self>) {
+        self.adjust(-10., cx);
+    }
+
+    fn page_increase(&mut self, _: &PageIncrease, cx: &mut ViewContext<Self>) {
+        self.adjust(10., cx);
+    }
+
+    fn render_thumb(&self, thumb: RangeThumb, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let entity_id = cx.entity_id();
+        let value = match thumb {
+            RangeThumb::Start => self.start,
+            RangeThumb::End => self.end,
+        };
+        let is_active = self.active_thumb == thumb && self.focus_handle.is_focused(cx);
+
+        div()
+            .id(match thumb {
+                RangeThumb::Start => "slider-thumb-start",
+                RangeThumb::End => "slider-thumb-end",
+            })
+            .on_drag(DragRangeThumb(entity_id, thumb), |drag, _, cx| {
+                cx.stop_propagation();
+                cx.new_view(|_| drag.clone())
+            })
+            .on_drag_move(
+                cx.listener(move |view, e: &DragMoveEvent<DragRangeThumb>, cx| {
+                    let DragRangeThumb(id, drag_thumb) = e.drag(cx).clone();
+                    if id != entity_id || drag_thumb != thumb {
+                        return;
+                    }
+
+                    let value = view.value_by_position(e.event.position);
+                    view.update_thumb(thumb, value, cx)
+                }),
+            )
+            .absolute()
+            .top(px(-5.))
+            .left(relative(self.relative(value)))
+            .ml(-px(8.))
+            .size_4()
+            .rounded_full()
+            .border_1()
+            .border_color(cx.theme().slider_bar.opacity(0.9))
+            .when(is_active, |this| this.border_2())
+            .when(cx.theme().has_shadow(), |this| this.shadow_md())
+            .bg(cx.theme().slider_thumb)
+            .tooltip(move |cx| Tooltip::new(format!("{}", value), cx))
+    }
+}
+
+impl EventEmitter<SliderEvent> for RangeSlider {}
+
+impl FocusableView for RangeSlider {
+    fn focus_handle(&self, _: &AppContext) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for RangeSlider {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let start = self.relative(self.start);
+        let end = self.relative(self.end);
+        let has_labels = self.marks.iter().any(|(_, label)| !label.is_empty());
+
+        div()
+            .id("range-slider")
+            .key_context(RANGE_CONTEXT)
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::decrease))
+            .on_action(cx.listener(Self::increase))
+            .on_action(cx.listener(Self::page_decrease))
+            .on_action(cx.listener(Self::page_increase))
+            .on_mouse_down(MouseButton::Left, cx.listener(Self::on_mouse_down))
+            .h_5()
+            .when(has_labels, |this| this.mb_5())
+            .child(
+                div()
+                    .id("slider-bar")
+                    .relative()
+                    .w_full()
+                    .my_1p5()
+                    .h_1p5()
+                    .bg(cx.theme().slider_bar.opacity(0.2))
+                    .active(|this| this.bg(cx.theme().slider_bar.opacity(0.4)))
+                    .rounded(px(3.))
+                    .children(self.marks.iter().map(|(value, label)| {
+                        let value = self.relative(*value);
+
+                        div()
+                            .absolute()
+                            .top_full()
+                            .left(relative(value))
+                            .mt_1()
+                            .child(
+                                div()
+                                    .w(px(1.))
+                                    .h_1p5()
+                                    .bg(cx.theme().slider_bar.opacity(0.4)),
+                            )
+                            .when(!label.is_empty(), |this| {
+                                this.child(
+                                    div()
+                                        .absolute()
+                                        .top_2()
+                                        .w(px(60.))
+                                        .ml(px(-30.))
+                                        .text_center()
+                                        .text_xs()
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(label.clone()),
+                                )
+                            })
+                    }))
+                    .child(
+                        div()
+                            .absolute()
+                            .top_0()
+                            .h_full()
+                            .left(relative(start))
+                            .w(relative(end - start))
+                            .bg(cx.theme().slider_bar)
+                            .rounded(px(3.)),
+                    )
+                    .child(self.render_thumb(RangeThumb::Start, cx))
+                    .child(self.render_thumb(RangeThumb::End, cx))
+                    .child({
+                        let view = cx.view().clone();
+                        canvas(
+                            move |bounds, cx| view.update(cx, |r, _| r.bounds = bounds),
+                            |_, _, _| {},
+                        )
+                        .absolute()
+                        .size_full()
+                    }),
+            )
+    }
+}
@@ -2,13 +2,15 @@ use std::fmt::{self, Display, Formatter};
 
 use crate::{
     scroll::{Scrollable, ScrollbarAxis},
-    theme::ActiveTheme,
+    theme::{box_shadow, ActiveTheme},
 };
 use gpui::{
-    div, px, Axis, Div, Edges, Element, ElementId, EntityId, FocusHandle, Pixels, Styled,
-    WindowContext,
+    div, px, relative, AnyElement, Axis, Div, Edges, Element, ElementId, EntityId, FocusHandle,
+    Hsla, InteractiveElement, IntoElement, ParentElement as _, Pixels, SharedString, Styled,
+    TextRun, TextStyle, WindowContext,
 };
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
 /// Returns a `Div` as horizontal flex layout.
 pub fn h_flex() -> Div {
@@ -20,6 +22,18 @@ pub fn v_flex() -> Div {
     div().v_flex()
 }
 
+/// Returns the [`TextRun`] to shape `len` bytes of the text in the font of the `style`, without decorations.
+pub(crate) fn text_run(len: usize, style: &TextStyle, color: Hsla) -> TextRun {
+    TextRun {
+        len,
+        font: style.font(),
+        color,
+        background_color: None,
+        underline: None,
+        strikethrough: None,
+    }
+}
+
 macro_rules! font_weight {
     ($fn:ident, $const:ident) => {
         /// [docs](https://tailwindcss.com/docs/font-weight)
@@ -104,9 +118,16 @@ pub trait StyledExt: Styled + Sized {
         self.border_color(cx.theme().ring)
     }
 
-    /// Wraps the element in a ScrollView.
-    ///
-    /// Current this is only have a vertical scrollbar.
+    /// Render a 2px focus ring outside the element with the theme ring color, if `focused` is true.
+    fn focus_ring(self, focused: bool, cx: &WindowContext) -> Self {
+        if !focused {
+            return self;
+        }
+
+        self.shadow(smallvec![box_shadow(0., 0., 0., 2., cx.theme().ring)])
+    }
+
+    /// Wraps the element in a ScrollView, with the scrollbars of the `axis`.
     fn scrollable(self, view_id: EntityId, axis: ScrollbarAxis) -> Scrollable<Self>
     where
         Self: Element,
@@ -126,11 +147,17 @@ pub trait StyledExt: Styled + Sized {
 
     /// Set as Popover style
     fn popover_style(self, cx: &mut WindowContext) -> Self {
-        self.bg(cx.theme().popover)
+        let this = self
+            .bg(cx.theme().popover)
             .border_1()
             .border_color(cx.theme().border)
-            .shadow_lg()
-            .rounded(px(cx.theme().radius))
+            .rounded(px(cx.theme().radius));
+
+        if cx.theme().has_shadow() {
+            this.shadow_lg()
+        } else {
+            this
+        }
     }
 }
 
@@ -145,6 +172,7 @@ pub enum Size {
     #[default]
     Medium,
     Large,
+    XLarge,
 }
 
 impl Size {
@@ -154,6 +182,7 @@ impl Size {
             Size::XSmall => px(26.),
             Size::Small => px(30.),
             Size::Large => px(40.),
+            Size::XLarge => px(44.),
             _ => px(32.),
         }
     }
@@ -178,6 +207,12 @@ impl Size {
                 left: px(12.),
                 right: px(12.),
             },
+            Size::XLarge => Edges {
+                top: px(10.),
+                bottom: px(10.),
+                left: px(16.),
+                right: px(16.),
+            },
             _ => Edges {
                 top: px(4.),
                 bottom: px(4.),
@@ -186,6 +221,18 @@ impl Size {
             },
         }
     }
+
+    /// Returns the width and height of a calendar cell, e.g.: the day, the week label.
+    pub fn calendar_cell_size(&self) -> Pixels {
+        match self {
+            Size::XSmall => px(24.),
+            Size::Small => px(28.),
+            Size::Large => px(40.),
+            Size::XLarge => px(44.),
+            Size::Size(size) => *size,
+            _ => px(36.),
+        }
+    }
 }
 
 impl From<Pixels> for Size {
@@ -230,8 +277,40 @@ pub trait Sizable: Sized {
     fn large(self) -> Self {
         self.with_size(Size::Large)
     }
+
+    /// Set to Size::XLarge
+    fn xlarge(self) -> Self {
+        self.with_size(Size::XLarge)
+    }
 }
 
+/// A trait for setting a stable identifier to the component for the QA automation,
+/// so the controls can be targeted without relying on the text content.
+///
+/// The id is set as the debug selector of the element when the `test-support` feature is enabled,
+/// then it can be queried by `VisualTestContext::debug_bounds` in the tests.
+pub trait AutomationId: Sized {
+    /// Set the automation id of this element.
+    fn automation_id(self, id: impl Into<SharedString>) -> Self;
+}
+
+/// Apply the automation id to the interactive element.
+pub(crate) trait AutomationExt: InteractiveElement + Sized {
+    fn when_automation_id(self, id: Option<&SharedString>) -> Self {
+        #[cfg(feature = "test-support")]
+        if let Some(id) = id {
+            let id = id.to_string();
+            return self.debug_selector(move || id);
+        }
+
+        #[cfg(not(feature = "test-support"))]
+        let _ = id;
+        self
+    }
+}
+
+impl<E: InteractiveElement> AutomationExt for E {}
+
 #[allow(unused)]
 pub trait StyleSized<T: Styled> {
     fn input_text_size(self, size: Size) -> Self;
@@ -248,6 +327,8 @@ pub trait StyleSized<T: Styled> {
     fn size_with(self, size: Size) -> Self;
     /// Apply the table cell size (Font size, padding) with the given `Size`.
     fn table_cell_size(self, size: Size) -> Self;
+    /// Apply the calendar cell size (Width, height, font size, radius) with the given `Size`.
+    fn calendar_cell_size(self, size: Size) -> Self;
 }
 
 impl<T: Styled> StyleSized<T> for T {
@@ -257,6 +338,7 @@ impl<T: Styled> StyleSized<T> for T {
             Size::Small => self.text_sm(),
             Size::Medium => self.text_base(),
             Size::Large => self.text_lg(),
+            Size::XLarge => self.text_xl(),
             Size::Size(size) => self.text_size(size),
         }
     }
@@ -267,6 +349,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn input_pl(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.pl_6(),
             Size::Large => self.pl_5(),
             Size::Medium => self.pl_3(),
             _ => self.pl_2(),
@@ -275,6 +358,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn input_pr(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.pr_6(),
             Size::Large => self.pr_5(),
             Size::Medium => self.pr_3(),
             _ => self.pr_2(),
@@ -283,6 +367,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn input_px(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.px_6(),
             Size::Large => self.px_5(),
             Size::Medium => self.px_3(),
             _ => self.px_2(),
@@ -291,6 +376,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn input_py(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.py_6(),
             Size::Large => self.py_5(),
             Size::Medium => self.py_2(),
             _ => self.py_1(),
@@ -299,6 +385,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn input_h(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.h_12(),
             Size::Large => self.h_11(),
             Size::Medium => self.h_8(),
             _ => self.h(px(26.)),
@@ -319,6 +406,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn list_py(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.py_2p5(),
             Size::Large => self.py_2(),
             Size::Medium => self.py_1(),
             Size::Small => self.py_0p5(),
@@ -328,6 +416,7 @@ impl<T: Styled> StyleSized<T> for T {
 
     fn size_with(self, size: Size) -> Self {
         match size {
+            Size::XLarge => self.size_12(),
             Size::Large => self.size_11(),
             Size::Medium => self.size_8(),
             Size::Small => self.size_5(),
@@ -341,6 +430,7 @@ impl<T: Styled> StyleSized<T> for T {
         match size {
             Size::XSmall => self.text_sm(),
             Size::Small => self.text_sm(),
+            Size::XLarge => self.text_lg(),
             _ => self,
         }
         .pl(padding.left)
@@ -348,6 +438,17 @@ impl<T: Styled> StyleSized<T> for T {
         .pt(padding.top)
         .pb(padding.bottom)
     }
+
+    fn calendar_cell_size(self, size: Size) -> Self {
+        let this = self.size(size.calendar_cell_size());
+        match size {
+            Size::XSmall => this.text_xs().rounded_sm(),
+            Size::Small => this.text_sm().rounded_md(),
+            Size::Large => this.text_base().rounded_lg(),
+            Size::XLarge => this.text_lg().rounded_lg(),
+            _ => this.text_sm().rounded_lg(),
+        }
+    }
 }
 
 pub trait AxisExt {
@@ -420,6 +521,38 @@ impl Side {
     }
 }
 
+/// The layout of the items in a group, e.g.: the [`RadioGroup`](crate::radio::RadioGroup).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GroupLayout {
+    #[default]
+    Vertical,
+    Horizontal,
+    /// The grid with the number of the columns.
+    Grid(usize),
+}
+
+impl GroupLayout {
+    /// Returns the container of the items.
+    pub(crate) fn container(self) -> Div {
+        match self {
+            Self::Vertical => v_flex().gap_2(),
+            Self::Horizontal => h_flex().flex_wrap().gap_x_4().gap_y_2(),
+            Self::Grid(_) => h_flex().flex_wrap().gap_y_2(),
+        }
+    }
+
+    /// Wrap the item to fill the column of the grid.
+    pub(crate) fn item(self, item: impl IntoElement) -> AnyElement {
+        match self {
+            Self::Grid(cols) => div()
+                .w(relative(1. / cols.max(1) as f32))
+                .child(item)
+                .into_any_element(),
+            _ => item.into_any_element(),
+        }
+    }
+}
+
 /// A trait for defining element that can be collapsed.
 pub trait Collapsible {
     fn collapsed(self, collapsed: bool) -> Self;
@@ -1,19 +1,56 @@
-use crate::{h_flex, theme::ActiveTheme, Disableable, Side, Sizable, Size};
+use crate::{
+    h_flex, indicator::Indicator, theme::ActiveTheme, AutomationExt as _, AutomationId,
+    Disableable, IconName, Side, Sizable, Size,
+};
 use gpui::{
     div, prelude::FluentBuilder as _, px, Animation, AnimationExt as _, AnyElement, Element,
     ElementId, GlobalElementId, InteractiveElement, IntoElement, LayoutId, ParentElement as _,
-    SharedString, Styled as _, WindowContext,
+    SharedString, Styled as _, Task, WindowContext,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
 };
-use std::{cell::RefCell, rc::Rc, time::Duration};
 
+type AsyncHandler = Rc<dyn Fn(&bool, &mut WindowContext) -> Task<bool>>;
+
+/// A Switch element.
+///
+/// For the async toggles, e.g.: saving the setting to the server, use the [`Switch::on_change`]
+/// to show the loading state until the task is done, the switch goes back if the task fails.
+///
+/// ```ignore
+/// let view = cx.view().downgrade();
+/// Switch::new("notifications")
+///     .checked(self.notifications)
+///     .confirm(|checked, cx| confirm_disable(*checked, cx))
+///     .on_change(move |checked, cx| {
+///         let checked = *checked;
+///         let view = view.clone();
+///         cx.spawn(|mut cx| async move {
+///             save_notifications(checked).await.is_ok()
+///                 && view
+///                     .update(&mut cx, |this, cx| {
+///                         this.notifications = checked;
+///                         cx.notify();
+///                     })
+///                     .is_ok()
+///         })
+///     })
+/// ```
 pub struct Switch {
     id: ElementId,
     checked: bool,
     disabled: bool,
+    loading: bool,
     label: Option<SharedString>,
     label_side: Side,
     on_click: Option<Rc<dyn Fn(&bool, &mut WindowContext)>>,
+    on_change: Option<AsyncHandler>,
+    confirm: Option<AsyncHandler>,
     size: Size,
+    automation_id: Option<SharedString>,
 }
 
 impl Switch {
@@ -23,10 +60,14 @@ impl Switch {
             id: id.clone(),
             checked: false,
             disabled: false,
+            loading: false,
             label: None,
             on_click: None,
+            on_change: None,
+            confirm: None,
             label_side: Side::Right,
             size: Size::Medium,
+            automation_id: None,
         }
     }
 
@@ -48,12 +89,47 @@ impl Switch {
         self
     }
 
+    /// Set the async handler to toggle, the switch shows the new state with a spinner until
+    /// the task is done, and goes back if the task returns `false`.
+    ///
+    /// The new state is kept until the `checked` is updated, so update it before returning `true`.
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&bool, &mut WindowContext) -> Task<bool> + 'static,
+    {
+        self.on_change = Some(Rc::new(handler));
+        self
+    }
+
+    /// Set a hook to confirm before toggling, e.g.: open a modal for the destructive settings,
+    /// the switch is toggled only if the task returns `true`.
+    pub fn confirm<F>(mut self, confirm: F) -> Self
+    where
+        F: Fn(&bool, &mut WindowContext) -> Task<bool> + 'static,
+    {
+        self.confirm = Some(Rc::new(confirm));
+        self
+    }
+
+    /// Show the spinner in the toggle and ignore the clicks, default: false
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
     pub fn label_side(mut self, label_side: Side) -> Self {
         self.label_side = label_side;
         self
     }
 }
 
+impl AutomationId for Switch {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.automation_id = Some(id.into());
+        self
+    }
+}
+
 impl Sizable for Switch {
     fn with_size(mut self, size: impl Into<Size>) -> Self {
         self.size = size.into();
@@ -76,9 +152,69 @@ impl IntoElement for Switch {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct SwitchState {
     prev_checked: Rc<RefCell<Option<bool>>>,
+    /// The state to show instead of the `checked` after toggled by the [`Switch::on_change`].
+    pending: Rc<Cell<Option<bool>>>,
+    /// True while the task of the [`Switch::on_change`] is running.
+    changing: Rc<Cell<bool>>,
+}
+
+impl SwitchState {
+    /// Toggle to the `checked` state, the `confirm` is called first if any.
+    fn toggle(
+        &self,
+        checked: bool,
+        on_click: Option<Rc<dyn Fn(&bool, &mut WindowContext)>>,
+        on_change: Option<AsyncHandler>,
+        confirm: Option<AsyncHandler>,
+        cx: &mut WindowContext,
+    ) {
+        let prev_checked = self.prev_checked.clone();
+        let pending = self.pending.clone();
+        let changing = self.changing.clone();
+
+        let toggle = move |cx: &mut WindowContext| {
+            *prev_checked.borrow_mut() = Some(!checked);
+            if let Some(on_click) = on_click {
+                on_click(&checked, cx);
+            }
+
+            let Some(on_change) = on_change else {
+                return;
+            };
+            pending.set(Some(checked));
+            changing.set(true);
+            cx.refresh();
+
+            let task = on_change(&checked, cx);
+            cx.spawn(|mut cx| async move {
+                let ok = task.await;
+                changing.set(false);
+                if !ok {
+                    // Go back to the previous state.
+                    *prev_checked.borrow_mut() = Some(checked);
+                    pending.set(None);
+                }
+                _ = cx.update(|cx| cx.refresh());
+            })
+            .detach();
+        };
+
+        match confirm {
+            Some(confirm) => {
+                let confirmed = confirm(&checked, cx);
+                cx.spawn(|mut cx| async move {
+                    if confirmed.await {
+                        _ = cx.update(|cx| toggle(cx));
+                    }
+                })
+                .detach();
+            }
+            None => toggle(cx),
+        }
+    }
 }
 
 impl Element for Switch {
@@ -96,13 +232,19 @@ impl Element for Switch {
         cx: &mut WindowContext,
     ) -> (LayoutId, Self::RequestLayoutState) {
         cx.with_element_state::<SwitchState, _>(global_id.unwrap(), move |state, cx| {
-            let state = state.unwrap_or_default();
+            let state: SwitchState = state.unwrap_or_default();
 
+            // The `checked` has been updated after the `on_change` succeeded.
+            if !state.changing.get() && state.pending.get() == Some(self.checked) {
+                state.pending.set(None);
+            }
+            let loading = self.loading || state.changing.get();
+            let checked = state.pending.get().unwrap_or(self.checked);
             let theme = cx.theme();
-            let checked = self.checked;
-            let on_click = self.on_click.clone();
+            let has_handler =
+                self.on_click.is_some() || self.on_change.is_some() || self.confirm.is_some();
 
-            let (bg, toggle_bg) = match self.checked {
+            let (bg, toggle_bg) = match checked {
                 true => (theme.primary, theme.background),
                 false => (theme.input, theme.background),
             };
@@ -113,17 +255,19 @@ impl Element for Switch {
             };
 
             let (bg_width, bg_height) = match self.size {
-                Size::XSmall | Size::Small => (px(28.), px(16.)),
-                _ => (px(36.), px(20.)),
-            };
-            let bar_width = match self.size {
-                Size::XSmall | Size::Small => px(12.),
-                _ => px(16.),
+                Size::XSmall => (px(24.), px(14.)),
+                Size::Small => (px(28.), px(16.)),
+                Size::Medium => (px(36.), px(20.)),
+                Size::Large => (px(44.), px(24.)),
+                Size::XLarge => (px(52.), px(28.)),
+                Size::Size(size) => (size * 1.8, size),
             };
             let inset = px(2.);
+            let bar_width = bg_height - inset * 2;
 
             let mut element = h_flex()
                 .id(self.id.clone())
+                .when_automation_id(self.automation_id.as_ref())
                 .items_center()
                 .gap_2()
                 .when(self.label_side.is_left(), |this| this.flex_row_reverse())
@@ -139,16 +283,28 @@ impl Element for Switch {
                         .border(inset)
                         .border_color(theme.transparent)
                         .bg(bg)
-                        .when(!self.disabled, |this| this.cursor_pointer())
+                        .when(!self.disabled && !loading, |this| this.cursor_pointer())
                         .child(
                             // Switch Toggle
                             div()
+                                .flex()
+                                .items_center()
+                                .justify_center()
                                 .rounded_full()
                                 .bg(toggle_bg)
                                 .size(bar_width)
+                                .when(loading, |this| {
+                                    this.child(
+                                        Indicator::new()
+                                            .icon(IconName::LoaderCircle)
+                                            .color(bg)
+                                            .with_size(Size::Size(bar_width - inset * 2)),
+                                    )
+                                })
                                 .map(|this| {
                                     let prev_checked = state.prev_checked.clone();
                                     if !self.disabled
+                                        && !cx.theme().lite
                                         && prev_checked
                                             .borrow()
                                             .map_or(false, |prev| prev != checked)
@@ -187,24 +343,28 @@ impl Element for Switch {
                 )
                 .when_some(self.label.clone(), |this, label| {
                     this.child(div().child(label).map(|this| match self.size {
-                        Size::XSmall | Size::Small => this.text_sm(),
+                        Size::XSmall => this.text_xs(),
+                        Size::Small => this.text_sm(),
+                        Size::XLarge => this.text_lg(),
                         _ => this.text_base(),
                     }))
                 })
-                .when_some(
-                    on_click
-                        .as_ref()
-                        .map(|c| c.clone())
-                        .filter(|_| !self.disabled),
-                    |this, on_click| {
-                        let prev_checked = state.prev_checked.clone();
-                        this.on_mouse_down(gpui::MouseButton::Left, move |_, cx| {
-                            cx.stop_propagation();
-                            *prev_checked.borrow_mut() = Some(checked);
-                            on_click(&!checked, cx);
-                        })
-                    },
-                )
+                .when(has_handler && !self.disabled && !loading, |this| {
+                    let state = state.clone();
+                    let on_click = self.on_click.clone();
+                    let on_change = self.on_change.clone();
+                    let confirm = self.confirm.clone();
+                    this.on_mouse_down(gpui::MouseButton::Left, move |_, cx| {
+                        cx.stop_propagation();
+                        state.toggle(
+                            !checked,
+                            on_click.clone(),
+                            on_change.clone(),
+                            confirm.clone(),
+                            cx,
+                        );
+                    })
+                })
                 .into_any_element();
 
             ((element.request_layout(cx), element), state)
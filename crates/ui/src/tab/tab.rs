@@ -1,9 +1,10 @@
 use crate::theme::ActiveTheme;
-use crate::Selectable;
+use crate::{AutomationExt as _, AutomationId, Selectable, Sizable, Size, StyledExt as _};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    div, px, AnyElement, Div, ElementId, InteractiveElement, IntoElement, ParentElement as _,
-    RenderOnce, Stateful, StatefulInteractiveElement, Styled, WindowContext,
+    div, px, AnyElement, Div, ElementId, FocusHandle, InteractiveElement, IntoElement,
+    ParentElement as _, RenderOnce, SharedString, Stateful, StatefulInteractiveElement, Styled,
+    WindowContext,
 };
 
 #[derive(IntoElement)]
@@ -15,6 +16,7 @@ pub struct Tab {
     suffix: Option<AnyElement>,
     disabled: bool,
     selected: bool,
+    focus_handle: Option<FocusHandle>,
 }
 
 impl Tab {
@@ -22,12 +24,13 @@ impl Tab {
         let id: ElementId = id.into();
         Self {
             id: id.clone(),
-            base: div().id(id).gap_1().py_1p5().px_3().h(px(30.)),
+            base: div().id(id).gap_1().py_1p5().px_3().h(px(30.)).text_sm(),
             label: label.into_any_element(),
             disabled: false,
             selected: false,
             prefix: None,
             suffix: None,
+            focus_handle: None,
         }
     }
 
@@ -48,6 +51,33 @@ impl Tab {
         self.disabled = disabled;
         self
     }
+
+    /// Track the focus of the tab, the focus ring will be shown when the tab is focused.
+    pub fn track_focus(mut self, focus_handle: &FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle.clone());
+        self
+    }
+}
+
+impl Sizable for Tab {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.base = self.base.map(|this| match size.into() {
+            Size::XSmall => this.gap_1().py_0p5().px_2().h(px(22.)).text_xs(),
+            Size::Small => this.gap_1().py_1().px_2().h(px(26.)).text_sm(),
+            Size::Medium => this.gap_1().py_1p5().px_3().h(px(30.)).text_sm(),
+            Size::Large => this.gap_1p5().py_2().px_4().h(px(36.)).text_base(),
+            Size::XLarge => this.gap_2().py_2().px_5().h(px(40.)).text_lg(),
+            Size::Size(size) => this.h(size),
+        });
+        self
+    }
+}
+
+impl AutomationId for Tab {
+    fn automation_id(mut self, id: impl Into<SharedString>) -> Self {
+        self.base = self.base.when_automation_id(Some(&id.into()));
+        self
+    }
 }
 
 impl Selectable for Tab {
@@ -84,8 +114,13 @@ impl RenderOnce for Tab {
             (true, true) => (cx.theme().muted_foreground, cx.theme().tab_active),
             (false, true) => (cx.theme().muted_foreground, cx.theme().tab),
         };
+        let focus_handle = self.focus_handle.clone();
+        let is_focused = focus_handle
+            .as_ref()
+            .map_or(false, |handle| handle.is_focused(cx));
 
-        self.base
+        let tab = self
+            .base
             .flex()
             .items_center()
             .flex_shrink_0()
@@ -96,11 +131,16 @@ impl RenderOnce for Tab {
             .border_x_1()
             .border_color(cx.theme().transparent)
             .when(self.selected, |this| this.border_color(cx.theme().border))
-            .text_sm()
             .when_some(self.prefix, |this, prefix| {
                 this.child(prefix).text_color(text_color)
             })
             .child(div().text_ellipsis().child(self.label))
             .when_some(self.suffix, |this, suffix| this.child(suffix))
+            .focus_ring(is_focused, cx);
+
+        match focus_handle {
+            Some(focus_handle) => tab.track_focus(&focus_handle).into_any_element(),
+            None => tab.into_any_element(),
+        }
     }
 }
@@ -1,13 +1,18 @@
-use crate::h_flex;
+use crate::button::{Button, ButtonVariants as _};
+use crate::popup_menu::{PopupMenu, PopupMenuExt as _};
 use crate::theme::ActiveTheme;
+use crate::{h_flex, Disableable as _, IconName, RovingFocus, Sizable as _};
 use gpui::prelude::FluentBuilder as _;
 use gpui::{
-    div, AnyElement, Div, ElementId, IntoElement, ParentElement, RenderOnce, ScrollHandle,
-    StatefulInteractiveElement as _, Styled, WindowContext,
+    div, point, AnyElement, Axis, Corner, Div, ElementId, IntoElement, ParentElement, Pixels,
+    RenderOnce, ScrollHandle, StatefulInteractiveElement as _, Styled, ViewContext, WindowContext,
 };
 use gpui::{px, InteractiveElement};
 use smallvec::SmallVec;
 
+/// The scroll distance of the scroll buttons, in ratio of the visible width.
+const SCROLL_STEP_RATIO: f32 = 0.5;
+
 #[derive(IntoElement)]
 pub struct TabBar {
     base: Div,
@@ -16,6 +21,8 @@ pub struct TabBar {
     prefix: Option<AnyElement>,
     suffix: Option<AnyElement>,
     children: SmallVec<[AnyElement; 2]>,
+    menu: Option<Box<dyn Fn(PopupMenu, &mut ViewContext<PopupMenu>) -> PopupMenu + 'static>>,
+    roving_focus: Option<RovingFocus>,
 }
 
 impl TabBar {
@@ -27,6 +34,8 @@ impl TabBar {
             scroll_handle: ScrollHandle::new(),
             prefix: None,
             suffix: None,
+            menu: None,
+            roving_focus: None,
         }
     }
 
@@ -36,6 +45,14 @@ impl TabBar {
         self
     }
 
+    /// Use the arrow keys to move focus between the tabs, and `tab` to leave the TabBar.
+    ///
+    /// The tabs should track focus by [`RovingFocus::focus_handle`] with the same index.
+    pub fn roving_focus(mut self, roving_focus: &RovingFocus) -> Self {
+        self.roving_focus = Some(roving_focus.clone());
+        self
+    }
+
     /// Set the prefix element of the TabBar
     pub fn prefix(mut self, prefix: impl IntoElement) -> Self {
         self.prefix = Some(prefix.into_any_element());
@@ -47,6 +64,30 @@ impl TabBar {
         self.suffix = Some(suffix.into_any_element());
         self
     }
+
+    /// Set the builder of the "all tabs" dropdown menu.
+    ///
+    /// The menu button will be displayed together with the scroll buttons when the tabs are overflowed.
+    pub fn menu(
+        mut self,
+        f: impl Fn(PopupMenu, &mut ViewContext<PopupMenu>) -> PopupMenu + 'static,
+    ) -> Self {
+        self.menu = Some(Box::new(f));
+        self
+    }
+
+    /// Return the max scroll offset (positive) of the tabs by the last rendered bounds.
+    fn max_scroll_offset(&self) -> Pixels {
+        let bounds = self.scroll_handle.bounds();
+        let Some(last_bounds) = self
+            .scroll_handle
+            .bounds_for_item(self.children.len().saturating_sub(1))
+        else {
+            return px(0.);
+        };
+
+        (last_bounds.right() - bounds.right()).max(px(0.))
+    }
 }
 
 impl ParentElement for TabBar {
@@ -63,6 +104,11 @@ impl Styled for TabBar {
 
 impl RenderOnce for TabBar {
     fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let max_offset = self.max_scroll_offset();
+        let is_overflow = max_offset > px(0.);
+        let offset_x = self.scroll_handle.offset().x;
+        let step = self.scroll_handle.bounds().size.width * SCROLL_STEP_RATIO;
+
         self.base
             .id(self.id)
             .group("tab-bar")
@@ -88,8 +134,72 @@ impl RenderOnce for TabBar {
                     .flex_grow()
                     .overflow_x_scroll()
                     .track_scroll(&self.scroll_handle)
+                    .when_some(self.roving_focus, |this, roving_focus| {
+                        roving_focus.truncate(self.children.len());
+                        this.on_key_down(roving_focus.on_key_down(Axis::Horizontal))
+                    })
                     .children(self.children),
             )
+            .when(is_overflow, |this| {
+                this.child(
+                    h_flex()
+                        .id("tab-bar-overflow")
+                        .flex_shrink_0()
+                        .h_full()
+                        .px_1()
+                        .gap_0p5()
+                        .border_l_1()
+                        .border_b_1()
+                        .border_color(cx.theme().border)
+                        .bg(cx.theme().tab_bar)
+                        .child(
+                            Button::new("scroll-left")
+                                .icon(IconName::ChevronLeft)
+                                .xsmall()
+                                .ghost()
+                                .disabled(offset_x >= px(0.))
+                                .on_click({
+                                    let scroll_handle = self.scroll_handle.clone();
+                                    move |_, cx| {
+                                        let offset = scroll_handle.offset();
+                                        scroll_handle.set_offset(point(
+                                            (offset.x + step).min(px(0.)),
+                                            offset.y,
+                                        ));
+                                        cx.refresh();
+                                    }
+                                }),
+                        )
+                        .child(
+                            Button::new("scroll-right")
+                                .icon(IconName::ChevronRight)
+                                .xsmall()
+                                .ghost()
+                                .disabled(offset_x <= -max_offset)
+                                .on_click({
+                                    let scroll_handle = self.scroll_handle.clone();
+                                    move |_, cx| {
+                                        let offset = scroll_handle.offset();
+                                        scroll_handle.set_offset(point(
+                                            (offset.x - step).max(-max_offset),
+                                            offset.y,
+                                        ));
+                                        cx.refresh();
+                                    }
+                                }),
+                        )
+                        .when_some(self.menu, |this, menu| {
+                            this.child(
+                                Button::new("tabs-menu")
+                                    .icon(IconName::ChevronDown)
+                                    .xsmall()
+                                    .ghost()
+                                    .popup_menu(move |this, cx| menu(this, cx))
+                                    .anchor(Corner::TopRight),
+                            )
+                        }),
+                )
+            })
             .when_some(self.suffix, |this, suffix| this.child(suffix))
     }
 }
@@ -0,0 +1,162 @@
+use gpui::{
+    div, prelude::FluentBuilder as _, relative, Div, Hsla, IntoElement, ParentElement, RenderOnce,
+    SharedString, Styled, WindowContext,
+};
+
+use crate::{
+    format::{format_number, format_percent},
+    h_flex,
+    theme::ActiveTheme as _,
+};
+
+/// Returns the start and end of the bar in the fraction of the cell width (0.0..1.0).
+///
+/// The bar starts from the position of zero (or `min` if zero is out of the range) to the value,
+/// so the negative values grow to the left in the split mode (e.g.: `-1.0..1.0`).
+fn bar_span(value: f64, min: f64, max: f64) -> (f32, f32) {
+    if !value.is_finite() || max <= min {
+        return (0., 0.);
+    }
+
+    let position = |value: f64| ((value.clamp(min, max) - min) / (max - min)) as f32;
+    let zero = position(0.);
+    let value = position(value);
+
+    (zero.min(value), zero.max(value))
+}
+
+/// A cell to display the value as a bar filling the background proportionally, with the label on top,
+/// e.g.: the volume or the weight columns in the [`Table`](super::Table).
+///
+/// Set the `min` to a negative value to use the positive/negative split mode,
+/// the negative bars grow to the left from the zero position with the `negative_color`.
+///
+/// It only renders 2 divs for the bar and the label, so it is cheap to render thousands of cells.
+///
+/// ```ignore
+/// fn render_td(&self, row_ix: usize, col_ix: usize, cx: &mut ViewContext<Table<Self>>) -> impl IntoElement {
+///     let row = &self.rows[row_ix];
+///     BarCell::new(row.volume)
+///         .max(self.max_volume)
+///         .number_label(0)
+/// }
+/// ```
+#[derive(IntoElement)]
+pub struct BarCell {
+    base: Div,
+    value: f64,
+    min: f64,
+    max: f64,
+    label: Option<SharedString>,
+    color: Option<Hsla>,
+    negative_color: Option<Hsla>,
+}
+
+impl BarCell {
+    pub fn new(value: f64) -> Self {
+        Self {
+            base: h_flex(),
+            value,
+            min: 0.,
+            max: 1.,
+            label: None,
+            color: None,
+            negative_color: None,
+        }
+    }
+
+    /// Set the min value of the cell, default: 0.0
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    /// Set the max value of the cell, default: 1.0
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// Set the label to display over the bar.
+    pub fn label(mut self, label: impl Into<SharedString>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the label to the value formatted in the current locale, e.g.: `1,234.57` or `1.234,57`.
+    pub fn number_label(self, precision: usize) -> Self {
+        let label = format_number(self.value, precision);
+        self.label(label)
+    }
+
+    /// Set the label to the value as the ratio formatted in the current locale, e.g.: `12.5%`.
+    pub fn percent_label(self, precision: usize) -> Self {
+        let label = format_percent(self.value, precision);
+        self.label(label)
+    }
+
+    /// Set the color of the bar, default is the `progress_bar` color of the theme.
+    pub fn color(mut self, color: impl Into<Hsla>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// Set the color of the bar for the negative values, default is the `negative` color of the theme.
+    pub fn negative_color(mut self, color: impl Into<Hsla>) -> Self {
+        self.negative_color = Some(color.into());
+        self
+    }
+}
+
+impl Styled for BarCell {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for BarCell {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let (start, end) = bar_span(self.value, self.min, self.max);
+        let color = if self.value < 0. {
+            self.negative_color.unwrap_or(cx.theme().negative)
+        } else {
+            self.color.unwrap_or(cx.theme().progress_bar)
+        };
+
+        self.base
+            .relative()
+            .size_full()
+            .justify_end()
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .when(end > start, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .bottom_0()
+                        .left(relative(start))
+                        .w(relative(end - start))
+                        .bg(color.opacity(0.25)),
+                )
+            })
+            .children(self.label.map(|label| div().relative().child(label)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bar_span;
+
+    #[test]
+    fn test_bar_span() {
+        assert_eq!(bar_span(0.5, 0., 1.), (0., 0.5));
+        assert_eq!(bar_span(2., 0., 1.), (0., 1.));
+        assert_eq!(bar_span(-1., 0., 1.), (0., 0.));
+        assert_eq!(bar_span(0.5, -1., 1.), (0.5, 0.75));
+        assert_eq!(bar_span(-0.5, -1., 1.), (0.25, 0.5));
+        assert_eq!(bar_span(5., 1., 11.), (0., 0.4));
+        assert_eq!(bar_span(f64::NAN, 0., 1.), (0., 0.));
+        assert_eq!(bar_span(0.5, 1., 1.), (0., 0.));
+    }
+}
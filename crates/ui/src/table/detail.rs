@@ -0,0 +1,164 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gpui::{ParentElement as _, Render, Subscription, View, ViewContext, WindowContext};
+
+use super::{Table, TableDelegate, TableEvent};
+use crate::{drawer::Drawer, ContextModal as _};
+
+/// Where the detail view of the [`TableDetail`] is shown, e.g.: a drawer or a panel in the dock area.
+pub trait DetailHost<V: 'static>: 'static {
+    /// Show the detail view, this is called on every selection change,
+    /// so it must do nothing if the detail view is already shown.
+    fn show(&self, detail: &View<V>, cx: &mut WindowContext);
+
+    /// Hide the detail view, called when the selection of the table is cleared.
+    fn hide(&self, detail: &View<V>, cx: &mut WindowContext);
+}
+
+/// Show the detail view in a drawer, the drawer is built by the given function.
+pub struct DrawerHost {
+    build: Rc<dyn Fn(Drawer, &mut WindowContext) -> Drawer>,
+}
+
+impl DrawerHost {
+    pub fn new(build: impl Fn(Drawer, &mut WindowContext) -> Drawer + 'static) -> Self {
+        Self {
+            build: Rc::new(build),
+        }
+    }
+}
+
+impl Default for DrawerHost {
+    fn default() -> Self {
+        Self::new(|drawer, _| drawer)
+    }
+}
+
+impl<V: Render> DetailHost<V> for DrawerHost {
+    fn show(&self, detail: &View<V>, cx: &mut WindowContext) {
+        if cx.has_active_drawer() {
+            return;
+        }
+
+        let build = self.build.clone();
+        let detail = detail.clone();
+        cx.open_drawer(move |drawer, cx| build(drawer, cx).child(detail.clone()));
+    }
+
+    fn hide(&self, _: &View<V>, cx: &mut WindowContext) {
+        cx.close_drawer();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DetailAction {
+    /// Build the detail view for the row and show it.
+    Open(usize),
+    /// Update the existing detail view to the row.
+    Update(usize),
+    Close,
+}
+
+fn detail_action(has_detail: bool, event: &TableEvent) -> Option<DetailAction> {
+    match event {
+        TableEvent::SelectRow(row_ix) if has_detail => Some(DetailAction::Update(*row_ix)),
+        TableEvent::SelectRow(row_ix) => Some(DetailAction::Open(*row_ix)),
+        TableEvent::ClearSelection if has_detail => Some(DetailAction::Close),
+        _ => None,
+    }
+}
+
+/// Binds the row selection of a [`Table`] to a detail view, for the master-detail screens.
+///
+/// - The detail view is built and shown when a row is selected.
+/// - The detail view is updated to the new row when the selection changes.
+/// - The detail view is hidden and dropped when the selection is cleared.
+///
+/// Keep the `TableDetail` in the owner view, the binding is removed when it is dropped.
+///
+/// ```ignore
+/// self.detail = TableDetail::new(
+///     &self.table,
+///     |row_ix, cx| StockDetail::view(row_ix, cx),
+///     |detail, row_ix, cx| detail.set_row(row_ix, cx),
+///     DrawerHost::new(|drawer, _| drawer.title("Detail")),
+///     cx,
+/// );
+/// ```
+pub struct TableDetail<V: 'static> {
+    detail: Rc<RefCell<Option<View<V>>>>,
+    _subscription: Subscription,
+}
+
+impl<V: Render> TableDetail<V> {
+    pub fn new<D: TableDelegate, T: 'static>(
+        table: &View<Table<D>>,
+        build: impl Fn(usize, &mut WindowContext) -> View<V> + 'static,
+        update: impl Fn(&mut V, usize, &mut ViewContext<V>) + 'static,
+        host: impl DetailHost<V>,
+        cx: &mut ViewContext<T>,
+    ) -> Self {
+        let detail: Rc<RefCell<Option<View<V>>>> = Rc::new(RefCell::new(None));
+
+        let _subscription = cx.subscribe(table, {
+            let detail = detail.clone();
+            move |_, _, event: &TableEvent, cx| {
+                let current = detail.borrow().clone();
+                match detail_action(current.is_some(), event) {
+                    Some(DetailAction::Open(row_ix)) => {
+                        let view = build(row_ix, cx);
+                        host.show(&view, cx);
+                        *detail.borrow_mut() = Some(view);
+                    }
+                    Some(DetailAction::Update(row_ix)) => {
+                        let Some(view) = current else {
+                            return;
+                        };
+                        view.update(cx, |view, cx| update(view, row_ix, cx));
+                        host.show(&view, cx);
+                    }
+                    Some(DetailAction::Close) => {
+                        if let Some(view) = detail.borrow_mut().take() {
+                            host.hide(&view, cx);
+                        }
+                    }
+                    None => {}
+                }
+            }
+        });
+
+        Self {
+            detail,
+            _subscription,
+        }
+    }
+
+    /// Returns the current detail view, `None` if no row is selected.
+    pub fn detail(&self) -> Option<View<V>> {
+        self.detail.borrow().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detail_action, DetailAction};
+    use crate::table::TableEvent;
+
+    #[test]
+    fn test_detail_action() {
+        assert_eq!(
+            detail_action(false, &TableEvent::SelectRow(1)),
+            Some(DetailAction::Open(1))
+        );
+        assert_eq!(
+            detail_action(true, &TableEvent::SelectRow(2)),
+            Some(DetailAction::Update(2))
+        );
+        assert_eq!(
+            detail_action(true, &TableEvent::ClearSelection),
+            Some(DetailAction::Close)
+        );
+        assert_eq!(detail_action(false, &TableEvent::ClearSelection), None);
+        assert_eq!(detail_action(true, &TableEvent::SelectCol(0)), None);
+    }
+}
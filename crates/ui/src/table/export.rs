@@ -0,0 +1,88 @@
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
+
+use gpui::AppContext;
+
+use super::TableDelegate;
+
+/// Export all the rows of the table as CSV, the first line is the column names.
+///
+/// The cell values are converted by the [`TableDelegate::export_value`].
+///
+/// ```ignore
+/// let mut file = std::fs::File::create("stocks.csv")?;
+/// table::export_csv(table.read(cx).delegate(), &mut file, cx)?;
+/// ```
+pub fn export_csv<D: TableDelegate>(
+    delegate: &D,
+    writer: &mut impl Write,
+    cx: &AppContext,
+) -> io::Result<()> {
+    let cols_count = delegate.cols_count(cx);
+
+    let header = (0..cols_count).map(|col_ix| delegate.col_name(col_ix, cx));
+    writer.write_all(csv_record(header).as_bytes())?;
+    for row_ix in 0..delegate.rows_count(cx) {
+        let values = (0..cols_count).map(|col_ix| delegate.export_value(row_ix, col_ix, cx));
+        writer.write_all(csv_record(values).as_bytes())?;
+    }
+
+    writer.flush()
+}
+
+/// Returns a CSV line of the values, the values are quoted if needed.
+pub(crate) fn csv_record(values: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    let mut line = values
+        .into_iter()
+        .map(|value| csv_field(value.as_ref()).into_owned())
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+fn csv_field(value: &str) -> Cow<str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Returns a TSV line of the values, the tabs and line breaks in the values are replaced by spaces.
+pub(crate) fn tsv_record(values: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    values
+        .into_iter()
+        .map(|value| value.as_ref().replace(['\t', '\n', '\r'], " "))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{csv_record, tsv_record};
+
+    #[test]
+    fn test_csv_record() {
+        assert_eq!(
+            csv_record(["AAPL", "Apple Inc.", "1.5"]),
+            "AAPL,Apple Inc.,1.5\n"
+        );
+        assert_eq!(
+            csv_record(["a,b", "say \"hi\"", "line\nbreak", ""]),
+            "\"a,b\",\"say \"\"hi\"\"\",\"line\nbreak\",\n"
+        );
+        assert_eq!(csv_record(Vec::<String>::new()), "\n");
+    }
+
+    #[test]
+    fn test_tsv_record() {
+        assert_eq!(
+            tsv_record(["AAPL", "Apple Inc.", "1.5"]),
+            "AAPL\tApple Inc.\t1.5"
+        );
+        assert_eq!(tsv_record(["a\tb", "line\r\nbreak"]), "a b\tline  break");
+    }
+}
@@ -0,0 +1,305 @@
+use std::{collections::HashSet, ops::Range};
+
+use gpui::SharedString;
+
+/// A group of the adjacent rows with the same [`TableDelegate::group_key`](super::TableDelegate::group_key).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableGroup {
+    /// The key of the group, also used as the group label.
+    pub key: SharedString,
+    /// The row indexes in the group.
+    pub rows: Range<usize>,
+}
+
+impl TableGroup {
+    /// Returns the number of rows in the group.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns true if the group has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// An item in the table body, the table body is virtualized by the items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TableItem {
+    /// The header of the group at the given index.
+    Group(usize),
+    /// The row at the given index.
+    Row(usize),
+}
+
+/// The items to render in the table body, the rows of the collapsed groups are skipped.
+///
+/// If there is no group, the items are the rows.
+///
+/// The groups are built once by the rows, the collapsed groups are identified by the first row,
+/// so the non-adjacent groups with the same key are collapsed separately.
+#[derive(Debug, Default)]
+pub(crate) struct TableGroups {
+    rows_count: usize,
+    pub(crate) groups: Vec<TableGroup>,
+    /// The group index of each row.
+    row_groups: Vec<Option<usize>>,
+    items: Vec<TableItem>,
+    /// The group index of each item.
+    item_groups: Vec<Option<usize>>,
+    /// The item index of each row, `None` if the row is in a collapsed group.
+    row_items: Vec<Option<usize>>,
+}
+
+impl TableGroups {
+    pub(crate) fn new(
+        rows_count: usize,
+        group_key: impl Fn(usize) -> Option<SharedString>,
+        collapsed: &HashSet<usize>,
+    ) -> Self {
+        let mut groups: Vec<TableGroup> = vec![];
+        let mut row_groups = Vec::with_capacity(rows_count);
+        for row_ix in 0..rows_count {
+            let Some(key) = group_key(row_ix) else {
+                row_groups.push(None);
+                continue;
+            };
+
+            match groups.last_mut() {
+                Some(group) if group.key == key && group.rows.end == row_ix => {
+                    group.rows.end += 1;
+                }
+                _ => groups.push(TableGroup {
+                    key,
+                    rows: row_ix..row_ix + 1,
+                }),
+            }
+            row_groups.push(Some(groups.len() - 1));
+        }
+
+        if groups.is_empty() {
+            return Self {
+                rows_count,
+                ..Default::default()
+            };
+        }
+
+        let mut this = Self {
+            rows_count,
+            groups,
+            row_groups,
+            ..Default::default()
+        };
+        this.set_collapsed(collapsed);
+        this
+    }
+
+    /// Rebuild the items by the first rows of the collapsed groups, without the `group_key` calls.
+    pub(crate) fn set_collapsed(&mut self, collapsed: &HashSet<usize>) {
+        if !self.is_grouped() {
+            return;
+        }
+
+        let rows_count = self.rows_count;
+        let mut items = Vec::with_capacity(rows_count + self.groups.len());
+        let mut item_groups = Vec::with_capacity(rows_count + self.groups.len());
+        let mut row_items = vec![None; rows_count];
+        for (row_ix, group_ix) in self.row_groups.iter().copied().enumerate() {
+            if let Some(group_ix) = group_ix {
+                let group = &self.groups[group_ix];
+                if group.rows.start == row_ix {
+                    items.push(TableItem::Group(group_ix));
+                    item_groups.push(Some(group_ix));
+                }
+                if collapsed.contains(&group.rows.start) {
+                    continue;
+                }
+            }
+
+            row_items[row_ix] = Some(items.len());
+            items.push(TableItem::Row(row_ix));
+            item_groups.push(group_ix);
+        }
+
+        self.items = items;
+        self.item_groups = item_groups;
+        self.row_items = row_items;
+    }
+
+    /// Returns the number of the rows that the groups are built by.
+    pub(crate) fn rows_count(&self) -> usize {
+        self.rows_count
+    }
+
+    /// Returns the group that the row belongs to.
+    pub(crate) fn row_group(&self, row_ix: usize) -> Option<&TableGroup> {
+        self.row_groups
+            .get(row_ix)
+            .copied()
+            .flatten()
+            .and_then(|group_ix| self.groups.get(group_ix))
+    }
+
+    fn is_grouped(&self) -> bool {
+        !self.groups.is_empty()
+    }
+
+    /// Returns the number of the items to render.
+    pub(crate) fn items_count(&self) -> usize {
+        if self.is_grouped() {
+            self.items.len()
+        } else {
+            self.rows_count
+        }
+    }
+
+    pub(crate) fn item(&self, ix: usize) -> Option<TableItem> {
+        if self.is_grouped() {
+            self.items.get(ix).copied()
+        } else {
+            (ix < self.rows_count).then_some(TableItem::Row(ix))
+        }
+    }
+
+    /// Returns the item index of the row, or `None` if the row is in a collapsed group.
+    pub(crate) fn item_ix(&self, row_ix: usize) -> Option<usize> {
+        if self.is_grouped() {
+            self.row_items.get(row_ix).copied().flatten()
+        } else {
+            Some(row_ix)
+        }
+    }
+
+    /// Returns the index of the group that the item belongs to.
+    pub(crate) fn item_group(&self, ix: usize) -> Option<usize> {
+        self.item_groups.get(ix).copied().flatten()
+    }
+
+    /// Returns the previous or next visible row of the given row, for the keyboard selection.
+    pub(crate) fn adjacent_row(
+        &self,
+        row_ix: Option<usize>,
+        forward: bool,
+        loop_select: bool,
+    ) -> Option<usize> {
+        let rows = (0..self.items_count())
+            .filter_map(|ix| match self.item(ix) {
+                Some(TableItem::Row(row_ix)) => Some(row_ix),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if rows.is_empty() {
+            return None;
+        }
+
+        let pos = row_ix
+            .and_then(|row_ix| rows.iter().position(|ix| *ix == row_ix))
+            .unwrap_or(0);
+        let pos = if forward {
+            if pos + 1 < rows.len() {
+                pos + 1
+            } else if loop_select {
+                0
+            } else {
+                pos
+            }
+        } else if pos > 0 {
+            pos - 1
+        } else if loop_select {
+            rows.len() - 1
+        } else {
+            pos
+        };
+
+        Some(rows[pos])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use gpui::SharedString;
+
+    use super::{TableGroups, TableItem};
+
+    fn build(collapsed: &[usize]) -> TableGroups {
+        // [a, a, -, b, b, b, a]
+        let keys = [
+            Some("a"),
+            Some("a"),
+            None,
+            Some("b"),
+            Some("b"),
+            Some("b"),
+            Some("a"),
+        ];
+        let collapsed = collapsed.iter().copied().collect::<HashSet<_>>();
+
+        TableGroups::new(
+            keys.len(),
+            |row_ix| keys[row_ix].map(|key| SharedString::from(key.to_string())),
+            &collapsed,
+        )
+    }
+
+    #[test]
+    fn test_table_groups() {
+        let groups = build(&[]);
+        assert_eq!(groups.groups.len(), 3);
+        assert_eq!(groups.groups[0].rows, 0..2);
+        assert_eq!(groups.groups[1].rows, 3..6);
+        assert_eq!(groups.groups[2].rows, 6..7);
+        assert_eq!(groups.items_count(), 10);
+        assert_eq!(groups.item(0), Some(TableItem::Group(0)));
+        assert_eq!(groups.item(1), Some(TableItem::Row(0)));
+        assert_eq!(groups.item(3), Some(TableItem::Row(2)));
+        assert_eq!(groups.item(4), Some(TableItem::Group(1)));
+        assert_eq!(groups.item_group(3), None);
+        assert_eq!(groups.item_group(5), Some(1));
+        assert_eq!(groups.item_ix(3), Some(5));
+
+        assert_eq!(
+            groups.row_group(6).map(|group| group.rows.clone()),
+            Some(6..7)
+        );
+        assert_eq!(groups.row_group(2), None);
+
+        // Collapse the "b" group.
+        let groups = build(&[3]);
+        assert_eq!(groups.items_count(), 7);
+        assert_eq!(groups.item(4), Some(TableItem::Group(1)));
+        assert_eq!(groups.item(5), Some(TableItem::Group(2)));
+        assert_eq!(groups.item_ix(4), None);
+        assert_eq!(groups.item_ix(6), Some(6));
+        assert_eq!(groups.adjacent_row(Some(2), true, false), Some(6));
+        assert_eq!(groups.adjacent_row(Some(6), false, false), Some(2));
+        assert_eq!(groups.adjacent_row(Some(6), true, true), Some(0));
+        assert_eq!(groups.adjacent_row(Some(0), false, true), Some(6));
+
+        // The non-adjacent groups with the same key are collapsed separately.
+        let mut groups = build(&[6]);
+        assert_eq!(groups.items_count(), 9);
+        assert_eq!(groups.item_ix(0), Some(1));
+        assert_eq!(groups.item_ix(6), None);
+        groups.set_collapsed(&HashSet::from([0]));
+        assert_eq!(groups.items_count(), 8);
+        assert_eq!(groups.item_ix(0), None);
+        assert_eq!(groups.item_ix(6), Some(7));
+    }
+
+    #[test]
+    fn test_table_groups_without_group() {
+        let groups = TableGroups::new(3, |_| None, &HashSet::new());
+        assert_eq!(groups.items_count(), 3);
+        assert_eq!(groups.item(2), Some(TableItem::Row(2)));
+        assert_eq!(groups.item(3), None);
+        assert_eq!(groups.item_ix(1), Some(1));
+        assert_eq!(groups.item_group(1), None);
+        assert_eq!(groups.adjacent_row(None, true, true), Some(1));
+        assert_eq!(groups.adjacent_row(Some(2), true, false), Some(2));
+
+        let groups = TableGroups::new(0, |_| None, &HashSet::new());
+        assert_eq!(groups.adjacent_row(None, true, true), None);
+    }
+}
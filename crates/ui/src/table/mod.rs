@@ -0,0 +1,11 @@
+mod bar_cell;
+mod detail;
+mod export;
+mod group;
+mod table;
+
+pub use bar_cell::*;
+pub use detail::*;
+pub use export::*;
+pub use group::*;
+pub use table::*;
@@ -1,23 +1,37 @@
-use std::{cell::Cell, ops::Range, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    ops::Range,
+    rc::Rc,
+};
 
+use super::{
+    export::tsv_record,
+    group::{TableGroup, TableGroups, TableItem},
+};
 use crate::{
     context_menu::ContextMenuExt,
     h_flex,
+    keymap::{self, KeymapEntry},
     popup_menu::PopupMenu,
     scroll::{ScrollableAxis, ScrollableMask, Scrollbar, ScrollbarState},
+    styled::text_run,
     theme::ActiveTheme,
+    tooltip::Tooltip,
     v_flex,
     virtual_list::virtual_list,
-    Icon, IconName, Sizable, Size, StyleSized as _,
+    Icon, IconName, Sizable, Size, StyleSized as _, StyledExt as _,
 };
 use gpui::{
-    actions, canvas, div, prelude::FluentBuilder, px, uniform_list, AppContext, Axis, Bounds, Div,
-    DragMoveEvent, Edges, Entity, EntityId, EventEmitter, FocusHandle, FocusableView,
-    InteractiveElement, IntoElement, KeyBinding, ListSizingBehavior, MouseButton, ParentElement,
-    Pixels, Point, Render, ScrollHandle, ScrollStrategy, SharedString, Stateful,
-    StatefulInteractiveElement as _, Styled, UniformListScrollHandle, ViewContext,
-    VisualContext as _, WindowContext,
+    actions, canvas, div, impl_actions, prelude::FluentBuilder, px, rems, uniform_list, AnyElement,
+    AppContext, Axis, Bounds, ClipboardItem, Div, DragMoveEvent, Edges, ElementId, Entity,
+    EntityId, EventEmitter, FocusHandle, FocusableView, Font, InteractiveElement, IntoElement,
+    ListSizingBehavior, MouseButton, ParentElement, Pixels, Point, Render, ScrollHandle,
+    ScrollStrategy, SharedString, Stateful, StatefulInteractiveElement as _, Styled,
+    UniformListScrollHandle, ViewContext, VisualContext as _, WindowContext,
 };
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
 
 actions!(
     table,
@@ -26,19 +40,37 @@ actions!(
         SelectPrev,
         SelectNext,
         SelectPrevColumn,
-        SelectNextColumn
+        SelectNextColumn,
+        Copy
     ]
 );
 
+/// Toggle the visibility of the column at the given index.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct ToggleColumn(pub usize);
+
+/// Change the row height of the table.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+pub struct SetRowHeight(pub RowHeight);
+
+impl_actions!(table, [ToggleColumn, SetRowHeight]);
+
 pub fn init(cx: &mut AppContext) {
     let context = Some("Table");
-    cx.bind_keys([
-        KeyBinding::new("escape", Cancel, context),
-        KeyBinding::new("up", SelectPrev, context),
-        KeyBinding::new("down", SelectNext, context),
-        KeyBinding::new("left", SelectPrevColumn, context),
-        KeyBinding::new("right", SelectNextColumn, context),
-    ]);
+    keymap::bind_keys(
+        cx,
+        [
+            KeymapEntry::new("escape", Cancel, context),
+            KeymapEntry::new("up", SelectPrev, context),
+            KeymapEntry::new("down", SelectNext, context),
+            KeymapEntry::new("left", SelectPrevColumn, context),
+            KeymapEntry::new("right", SelectNextColumn, context),
+            #[cfg(target_os = "macos")]
+            KeymapEntry::new("cmd-c", Copy, context),
+            #[cfg(not(target_os = "macos"))]
+            KeymapEntry::new("ctrl-c", Copy, context),
+        ],
+    );
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -53,6 +85,7 @@ pub(crate) struct ColGroup {
     pub(crate) sort: Option<ColSort>,
     pub(crate) fixed: Option<ColFixed>,
     pub(crate) padding: Option<Edges<Pixels>>,
+    pub(crate) visible: bool,
 }
 
 #[derive(Clone)]
@@ -63,7 +96,7 @@ pub(crate) struct DragCol {
     pub(crate) col_ix: usize,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ColSort {
     /// No sorting.
     Default,
@@ -83,7 +116,7 @@ impl Render for DragCol {
             .opacity(0.9)
             .border_1()
             .border_color(cx.theme().border)
-            .shadow_md()
+            .when(cx.theme().has_shadow(), |this| this.shadow_md())
             .w(self.width)
             .min_w(px(100.))
             .max_w(px(450.))
@@ -104,8 +137,82 @@ enum SelectionState {
 pub enum TableEvent {
     SelectRow(usize),
     SelectCol(usize),
+    /// The selected row and column have been cleared.
+    ClearSelection,
     ColWidthsChanged(Vec<Pixels>),
     MoveCol(usize, usize),
+    /// The column at the given index has been shown or hidden.
+    ColVisibilityChanged(usize, bool),
+    /// The group has been collapsed or expanded.
+    GroupCollapsed(TableGroup, bool),
+    /// The row height has been changed by the header context menu.
+    RowHeightChanged(RowHeight),
+}
+
+/// The height of the rows in the [`Table`], all the rows have the same height.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RowHeight {
+    /// Single line rows, the cell content does not wrap.
+    #[default]
+    Normal,
+    /// Taller rows, the cell content wraps to 2 lines and the overflow is truncated with ellipsis.
+    Tall,
+}
+
+impl RowHeight {
+    /// Returns the height of the row with the given size.
+    pub fn height(&self, size: Size) -> Pixels {
+        match self {
+            Self::Normal => size.table_row_height(),
+            Self::Tall => size.table_row_height() * 1.75,
+        }
+    }
+
+    /// Returns the max lines of the cell content.
+    pub fn max_lines(&self) -> usize {
+        match self {
+            Self::Normal => 1,
+            Self::Tall => 2,
+        }
+    }
+}
+
+/// The widths of the cell texts of each column, so the texts are only shaped when the content changed,
+/// all are cleared when the font is changed or the table is refreshed.
+#[derive(Default)]
+struct TextWidths {
+    font: Option<(Font, Pixels)>,
+    cols: Vec<HashMap<SharedString, Pixels>>,
+}
+
+/// Returns true if the text is too long to fit in the lines of the cell, so it is truncated.
+fn is_text_truncated(text_width: Pixels, cell_width: Pixels, max_lines: usize) -> bool {
+    cell_width > px(0.) && text_width > cell_width * max_lines as f32
+}
+
+/// The column configuration of the [`Table`], used to persist the table state,
+/// e.g.: alongside the dock layout.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableState {
+    /// The columns in display order.
+    pub cols: Vec<ColState>,
+    #[serde(default)]
+    pub row_height: RowHeight,
+}
+
+/// The state of a column, the column is identified by the [`TableDelegate::col_name`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColState {
+    pub name: SharedString,
+    pub width: Pixels,
+    #[serde(default = "default_visible")]
+    pub visible: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<ColSort>,
+}
+
+fn default_visible() -> bool {
+    true
 }
 
 #[derive(Clone, Copy, Default)]
@@ -139,12 +246,22 @@ pub struct Table<D: TableDelegate> {
     /// The column index that is being resized.
     resizing_col: Option<usize>,
 
+    /// The rows grouped by the [`TableDelegate::group_key`].
+    groups: TableGroups,
+    /// The keys of the collapsed groups.
+    /// The first rows of the collapsed groups.
+    collapsed_groups: HashSet<usize>,
+
     /// Set stripe style of the table.
     stripe: bool,
     /// Set to use border style of the table.
     border: bool,
     /// The cell size of the table.
     size: Size,
+    row_height: RowHeight,
+    /// Show the full value of the truncated cells in the tooltip.
+    cell_tooltip: bool,
+    text_widths: RefCell<TextWidths>,
 }
 
 #[allow(unused)]
@@ -196,6 +313,13 @@ pub trait TableDelegate: Sized + 'static {
         None
     }
 
+    /// Returns whether the column at the given index can be hidden by the header context menu.
+    ///
+    /// Default: true
+    fn can_hide_col(&self, col_ix: usize, cx: &AppContext) -> bool {
+        true
+    }
+
     /// Perform sort on the column at the given index.
     fn perform_sort(&mut self, col_ix: usize, sort: ColSort, cx: &mut ViewContext<Table<Self>>) {}
 
@@ -209,11 +333,40 @@ pub trait TableDelegate: Sized + 'static {
         h_flex().id(("table-row", row_ix))
     }
 
+    /// Returns the group key of the row at the given index, return None to not group the row.
+    ///
+    /// The adjacent rows with the same key are grouped, so the rows should be sorted by the group key.
+    /// The groups are cached, call [`Table::refresh`] after the keys are changed.
+    ///
+    /// Default: None
+    fn group_key(&self, row_ix: usize, cx: &AppContext) -> Option<SharedString> {
+        None
+    }
+
+    /// Returns the aggregates to show in the group header, e.g.: `("Sum", "1,024.00")`.
+    ///
+    /// The rows count of the group is always shown.
+    fn group_aggregates(
+        &self,
+        group: &TableGroup,
+        cx: &AppContext,
+    ) -> Vec<(SharedString, SharedString)> {
+        vec![]
+    }
+
     /// Render the context menu for the row at the given row index.
     fn context_menu(&self, row_ix: usize, menu: PopupMenu, cx: &WindowContext) -> PopupMenu {
         menu
     }
 
+    /// Returns the plain text of the cell at the given row and column,
+    /// used to copy the selection, export the table and show the tooltip of the truncated cell.
+    ///
+    /// Default: empty string
+    fn export_value(&self, row_ix: usize, col_ix: usize, cx: &AppContext) -> SharedString {
+        SharedString::default()
+    }
+
     /// Render cell at the given row and column.
     fn render_td(
         &self,
@@ -298,15 +451,21 @@ where
             right_clicked_row: None,
             selected_col: None,
             resizing_col: None,
+            groups: TableGroups::default(),
+            collapsed_groups: HashSet::new(),
             bounds: Bounds::default(),
             fixed_head_cols_bounds: Bounds::default(),
             head_content_bounds: Bounds::default(),
             stripe: false,
             border: true,
             size: Size::default(),
+            row_height: RowHeight::default(),
+            cell_tooltip: true,
+            text_widths: RefCell::default(),
         };
 
         this.prepare_col_groups(cx);
+        this.prepare_groups(cx);
         this
     }
 
@@ -341,9 +500,86 @@ where
         cx.notify();
     }
 
+    /// Set the row height of the table, default to [`RowHeight::Normal`].
+    pub fn row_height(mut self, row_height: RowHeight) -> Self {
+        self.row_height = row_height;
+        self
+    }
+
+    /// Set the row height of the table, the [`RowHeight::Tall`] rows wrap the cell content to 2 lines.
+    pub fn set_row_height(&mut self, row_height: RowHeight, cx: &mut ViewContext<Self>) {
+        self.row_height = row_height;
+        cx.notify();
+    }
+
+    /// Set to show the full value of the truncated cells in the tooltip after hovering, default to true.
+    ///
+    /// The value is from the [`TableDelegate::export_value`], the cells with the empty value have no tooltip.
+    pub fn cell_tooltip(mut self, cell_tooltip: bool) -> Self {
+        self.cell_tooltip = cell_tooltip;
+        self
+    }
+
+    /// Returns the height of the items (the rows and the group headers) in the table body,
+    /// the items must have the same height for the virtualization.
+    pub fn item_height(&self) -> Pixels {
+        self.row_height.height(self.size)
+    }
+
     /// When we update columns or rows, we need to refresh the table.
     pub fn refresh(&mut self, cx: &mut ViewContext<Self>) {
         self.prepare_col_groups(cx);
+        self.prepare_groups(cx);
+        self.text_widths.take();
+    }
+
+    /// Rebuild the groups by the [`TableDelegate::group_key`] of the rows,
+    /// it's called by [`Table::refresh`] or when the rows count is changed.
+    fn prepare_groups(&mut self, cx: &AppContext) {
+        let delegate = &self.delegate;
+        let groups = TableGroups::new(
+            delegate.rows_count(cx),
+            |row_ix| delegate.group_key(row_ix, cx),
+            &self.collapsed_groups,
+        );
+        // Forget the collapsed groups that no longer exist.
+        self.collapsed_groups.retain(|row_ix| {
+            groups
+                .row_group(*row_ix)
+                .map_or(false, |group| group.rows.start == *row_ix)
+        });
+        self.groups = groups;
+    }
+
+    /// Returns true if the group of the row is collapsed.
+    pub fn is_group_collapsed(&self, row_ix: usize) -> bool {
+        self.groups.row_group(row_ix).map_or(false, |group| {
+            self.collapsed_groups.contains(&group.rows.start)
+        })
+    }
+
+    /// Collapse or expand the group of the row.
+    pub fn set_group_collapsed(
+        &mut self,
+        row_ix: usize,
+        collapsed: bool,
+        cx: &mut ViewContext<Self>,
+    ) {
+        let Some(group) = self.groups.row_group(row_ix).cloned() else {
+            return;
+        };
+        let changed = if collapsed {
+            self.collapsed_groups.insert(group.rows.start)
+        } else {
+            self.collapsed_groups.remove(&group.rows.start)
+        };
+        if !changed {
+            return;
+        }
+
+        self.groups.set_collapsed(&self.collapsed_groups);
+        cx.emit(TableEvent::GroupCollapsed(group, collapsed));
+        cx.notify();
     }
 
     fn prepare_col_groups(&mut self, cx: &mut ViewContext<Self>) {
@@ -354,6 +590,8 @@ where
                 bounds: Bounds::default(),
                 sort: self.delegate.col_sort(col_ix, cx),
                 fixed: self.delegate.col_fixed(col_ix, cx),
+                // Keep the visibility when refresh.
+                visible: self.col_groups.get(col_ix).map_or(true, |col| col.visible),
             })
             .collect();
         self.fixed_cols.left = self
@@ -364,9 +602,92 @@ where
         cx.notify();
     }
 
-    fn scroll_to_row(&mut self, row_ix: usize, cx: &mut ViewContext<Self>) {
+    /// Returns true if the column at the given index is visible.
+    pub fn is_col_visible(&self, col_ix: usize) -> bool {
+        self.col_groups.get(col_ix).map_or(false, |col| col.visible)
+    }
+
+    /// Show or hide the column at the given index.
+    pub fn set_col_visible(&mut self, col_ix: usize, visible: bool, cx: &mut ViewContext<Self>) {
+        let Some(col_group) = self.col_groups.get_mut(col_ix) else {
+            return;
+        };
+        if col_group.visible == visible {
+            return;
+        }
+
+        col_group.visible = visible;
+        if !visible && self.selected_col == Some(col_ix) {
+            self.selected_col = None;
+        }
+        cx.emit(TableEvent::ColVisibilityChanged(col_ix, visible));
+        cx.notify();
+    }
+
+    /// Dump the column configuration (widths, order, visibility and sort) and the row height of the table.
+    pub fn dump(&self, cx: &AppContext) -> TableState {
+        TableState {
+            cols: self
+                .col_groups
+                .iter()
+                .enumerate()
+                .map(|(col_ix, col)| ColState {
+                    name: self.delegate.col_name(col_ix, cx),
+                    width: col.width,
+                    visible: col.visible,
+                    sort: col.sort,
+                })
+                .collect(),
+            row_height: self.row_height,
+        }
+    }
+
+    /// Load the column configuration that dumped by [`Table::dump`].
+    ///
+    /// The columns are matched by name, the unknown columns in the state will be ignored.
+    pub fn load(&mut self, state: TableState, cx: &mut ViewContext<Self>) {
+        let mut to_ix = 0;
+        for col_state in state.cols {
+            let Some(col_ix) = (0..self.col_groups.len())
+                .find(|ix| self.delegate.col_name(*ix, cx) == col_state.name)
+            else {
+                continue;
+            };
+
+            // The matched columns are moved to the front in order.
+            let col_ix = if col_ix == to_ix {
+                to_ix += 1;
+                col_ix
+            } else if col_ix > to_ix && self.delegate.can_move_col(col_ix, cx) {
+                self.move_col(col_ix, to_ix, cx);
+                to_ix += 1;
+                to_ix - 1
+            } else {
+                col_ix
+            };
+
+            let col_group = &mut self.col_groups[col_ix];
+            col_group.width = col_state.width;
+            col_group.visible = col_state.visible || !self.delegate.can_hide_col(col_ix, cx);
+            if col_group.sort.is_some() {
+                if let Some(sort) = col_state.sort {
+                    col_group.sort = Some(sort);
+                    if sort != ColSort::Default {
+                        self.delegate.perform_sort(col_ix, sort, cx);
+                    }
+                }
+            }
+        }
+        self.prepare_groups(cx);
+
+        self.row_height = state.row_height;
+        cx.notify();
+    }
+
+    /// Scroll to the item (the row or the group header) at the given index.
+    fn scroll_to_item(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
         self.vertical_scroll_handle
-            .scroll_to_item(row_ix, ScrollStrategy::Top);
+            .scroll_to_item(ix, ScrollStrategy::Top);
         cx.notify();
     }
 
@@ -380,9 +701,9 @@ where
         self.selection_state = SelectionState::Row;
         self.right_clicked_row = None;
         self.selected_row = Some(row_ix);
-        if let Some(row_ix) = self.selected_row {
+        if let Some(ix) = self.groups.item_ix(row_ix) {
             self.vertical_scroll_handle
-                .scroll_to_item(row_ix, ScrollStrategy::Top);
+                .scroll_to_item(ix, ScrollStrategy::Top);
         }
         cx.emit(TableEvent::SelectRow(row_ix));
         cx.notify();
@@ -407,6 +728,40 @@ where
         cx.notify();
     }
 
+    /// Copy the selected row, or the selected column with the column name,
+    /// as TSV to the clipboard, the hidden columns are skipped.
+    pub fn copy_selection_to_clipboard(&self, cx: &mut ViewContext<Self>) {
+        let text = match self.selection_state {
+            SelectionState::Row => {
+                let Some(row_ix) = self.selected_row else {
+                    return;
+                };
+
+                tsv_record(
+                    (0..self.col_groups.len())
+                        .filter(|col_ix| self.is_col_visible(*col_ix))
+                        .map(|col_ix| self.delegate.export_value(row_ix, col_ix, cx)),
+                )
+            }
+            SelectionState::Column => {
+                let Some(col_ix) = self.selected_col.filter(|ix| self.is_col_visible(*ix)) else {
+                    return;
+                };
+
+                std::iter::once(self.delegate.col_name(col_ix, cx))
+                    .chain(
+                        (0..self.delegate.rows_count(cx))
+                            .map(|row_ix| self.delegate.export_value(row_ix, col_ix, cx)),
+                    )
+                    .map(|value| tsv_record([value]))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        };
+
+        cx.write_to_clipboard(ClipboardItem::new_string(text));
+    }
+
     fn on_row_click(
         &mut self,
         mouse_button: MouseButton,
@@ -428,38 +783,64 @@ where
         self.set_selected_col(col_ix, cx)
     }
 
-    fn action_cancel(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+    /// Clears the selected row and column.
+    pub fn clear_selection(&mut self, cx: &mut ViewContext<Self>) {
+        let had_selection = self.selected_row.is_some() || self.selected_col.is_some();
         self.selection_state = SelectionState::Row;
         self.selected_row = None;
         self.selected_col = None;
+        if had_selection {
+            cx.emit(TableEvent::ClearSelection);
+        }
         cx.notify();
     }
 
-    fn action_select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
-        let mut selected_row = self.selected_row.unwrap_or(0);
-        let rows_count = self.delegate.rows_count(cx);
-        if selected_row > 0 {
-            selected_row = selected_row - 1;
-        } else {
-            if self.delegate.can_loop_select(cx) {
-                selected_row = rows_count - 1;
-            }
+    fn action_cancel(&mut self, _: &Cancel, cx: &mut ViewContext<Self>) {
+        self.clear_selection(cx);
+    }
+
+    fn action_copy(&mut self, _: &Copy, cx: &mut ViewContext<Self>) {
+        self.copy_selection_to_clipboard(cx);
+    }
+
+    fn action_toggle_column(&mut self, action: &ToggleColumn, cx: &mut ViewContext<Self>) {
+        let col_ix = action.0;
+        if !self.delegate.can_hide_col(col_ix, cx) {
+            return;
         }
 
-        self.set_selected_row(selected_row, cx);
+        let visible = self.is_col_visible(col_ix);
+        self.set_col_visible(col_ix, !visible, cx);
     }
 
-    fn action_select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
-        let mut selected_row = self.selected_row.unwrap_or(0);
-        if selected_row < self.delegate.rows_count(cx) - 1 {
-            selected_row += 1;
-        } else {
-            if self.delegate.can_loop_select(cx) {
-                selected_row = 0;
-            }
+    fn action_set_row_height(&mut self, action: &SetRowHeight, cx: &mut ViewContext<Self>) {
+        if self.row_height == action.0 {
+            return;
         }
 
-        self.set_selected_row(selected_row, cx);
+        self.set_row_height(action.0, cx);
+        cx.emit(TableEvent::RowHeightChanged(action.0));
+    }
+
+    fn action_select_prev(&mut self, _: &SelectPrev, cx: &mut ViewContext<Self>) {
+        let loop_select = self.delegate.can_loop_select(cx);
+        // The rows in the collapsed groups are skipped.
+        if let Some(row_ix) = self
+            .groups
+            .adjacent_row(self.selected_row, false, loop_select)
+        {
+            self.set_selected_row(row_ix, cx);
+        }
+    }
+
+    fn action_select_next(&mut self, _: &SelectNext, cx: &mut ViewContext<Self>) {
+        let loop_select = self.delegate.can_loop_select(cx);
+        if let Some(row_ix) = self
+            .groups
+            .adjacent_row(self.selected_row, true, loop_select)
+        {
+            self.set_selected_row(row_ix, cx);
+        }
     }
 
     fn action_select_prev_col(&mut self, _: &SelectPrevColumn, cx: &mut ViewContext<Self>) {
@@ -563,6 +944,8 @@ where
         }
 
         self.delegate_mut().perform_sort(col_ix, sort, cx);
+        // The rows are reordered, so the groups are changed.
+        self.prepare_groups(cx);
 
         cx.notify();
     }
@@ -586,7 +969,7 @@ where
             return;
         }
 
-        let row_count = self.delegate.rows_count(cx);
+        let row_count = self.groups.items_count();
         let load_more_count = self.delegate.load_more_threshold();
 
         // Securely handle subtract logic to prevent attempt to subtract with overflow
@@ -624,12 +1007,22 @@ where
         let col_width = self.col_groups[col_ix].width;
         let col_padding = self.col_groups[col_ix].padding;
 
+        // Keep an empty cell for the hidden column, to keep the column indexes.
+        if !self.col_groups[col_ix].visible {
+            return div().w_0().h_full().flex_shrink_0().overflow_hidden();
+        }
+
         div()
             .w(col_width)
             .h_full()
             .flex_shrink_0()
             .overflow_hidden()
-            .whitespace_nowrap()
+            .map(|this| match self.row_height {
+                RowHeight::Normal => this.whitespace_nowrap(),
+                RowHeight::Tall => this
+                    .h(self.row_height.height(self.size))
+                    .line_clamp(self.row_height.max_lines()),
+            })
             .table_cell_size(self.size)
             .map(|this| match col_padding {
                 Some(padding) => this
@@ -641,6 +1034,76 @@ where
             })
     }
 
+    /// Render the cell with the content of the delegate, and the tooltip of the full value if it is truncated.
+    fn render_td_cell(
+        &self,
+        row_ix: usize,
+        col_ix: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> Stateful<Div> {
+        let cell = self
+            .render_cell(col_ix, cx)
+            .id(("table-cell", col_ix))
+            .child(self.delegate.render_td(row_ix, col_ix, cx));
+
+        if !self.cell_tooltip || !self.col_groups[col_ix].visible {
+            return cell;
+        }
+
+        let text = self.delegate.export_value(row_ix, col_ix, cx);
+        if text.is_empty() {
+            return cell;
+        }
+
+        let padding = self.col_groups[col_ix]
+            .padding
+            .unwrap_or_else(|| self.size.table_cell_padding());
+        let cell_width = self.col_groups[col_ix].width - padding.left - padding.right;
+        if !is_text_truncated(
+            self.text_width(col_ix, &text, cx),
+            cell_width,
+            self.row_height.max_lines(),
+        ) {
+            return cell;
+        }
+
+        cell.tooltip(move |cx| Tooltip::new(text.clone(), cx))
+    }
+
+    /// Returns the width of the text of the cell in the column, in the font of the cells.
+    fn text_width(&self, col_ix: usize, text: &SharedString, cx: &WindowContext) -> Pixels {
+        let text_style = cx.text_style();
+        let font_size = match self.size {
+            Size::XSmall | Size::Small => rems(0.875).to_pixels(cx.rem_size()),
+            Size::XLarge => rems(1.125).to_pixels(cx.rem_size()),
+            _ => text_style.font_size.to_pixels(cx.rem_size()),
+        };
+        let font = (text_style.font(), font_size);
+
+        let mut text_widths = self.text_widths.borrow_mut();
+        if text_widths.font.as_ref() != Some(&font) {
+            *text_widths = TextWidths {
+                font: Some(font),
+                cols: vec![],
+            };
+        }
+        if text_widths.cols.len() <= col_ix {
+            text_widths.cols.resize_with(col_ix + 1, HashMap::default);
+        }
+        if let Some(width) = text_widths.cols[col_ix].get(text) {
+            return *width;
+        }
+
+        let run = text_run(text.len(), &text_style, text_style.color);
+        let width = cx
+            .text_system()
+            .shape_line(text.clone(), font_size, &[run])
+            .map(|line| line.width)
+            .unwrap_or_default();
+        text_widths.cols[col_ix].insert(text.clone(), width);
+        width
+    }
+
     /// Show Column selection style, when the column is selected and the selection state is Column.
     fn render_col_wrap(&self, col_ix: usize, cx: &mut ViewContext<Self>) -> Div {
         let el = h_flex().h_full();
@@ -793,7 +1256,9 @@ where
                     true => this,
                     false => this.opacity(0.5),
                 })
-                .hover(|this| this.bg(cx.theme().secondary).opacity(7.))
+                .when(!cx.theme().lite, |this| {
+                    this.hover(|this| this.bg(cx.theme().secondary).opacity(7.))
+                })
                 .active(|this| this.bg(cx.theme().secondary_active).opacity(1.))
                 .on_click(cx.listener(move |table, _, cx| table.perform_sort(col_ix, cx)))
                 .child(
@@ -808,9 +1273,13 @@ where
     /// The children must be one by one items.
     /// Because the horizontal scroll handle will use the child_item_bounds to
     /// calculate the item position for itself's `scroll_to_item` method.
-    fn render_th(&self, col_ix: usize, cx: &mut ViewContext<Self>) -> impl IntoElement {
+    fn render_th(&self, col_ix: usize, cx: &mut ViewContext<Self>) -> AnyElement {
         let entity_id = cx.entity_id();
         let col_group = self.col_groups.get(col_ix).expect("BUG: invalid col index");
+        if !col_group.visible {
+            return div().into_any_element();
+        }
+
         let moveable = self.delegate.can_move_col(col_ix, cx);
         let paddings = self.delegate.col_padding(col_ix, cx);
         let name = self.delegate.col_name(col_ix, cx);
@@ -882,6 +1351,32 @@ where
                 .absolute()
                 .size_full()
             })
+            .into_any_element()
+    }
+
+    /// Render the header context menu to show or hide the columns and change the row height.
+    fn render_cols_menu(&self, menu: PopupMenu, cx: &AppContext) -> PopupMenu {
+        let menu = menu.track_focus(&self.focus_handle);
+        (0..self.col_groups.len())
+            .filter(|col_ix| self.delegate.can_hide_col(*col_ix, cx))
+            .fold(menu, |menu, col_ix| {
+                menu.menu_with_check(
+                    self.delegate.col_name(col_ix, cx),
+                    self.col_groups[col_ix].visible,
+                    Box::new(ToggleColumn(col_ix)),
+                )
+            })
+            .separator()
+            .menu_with_check(
+                t!("Table.NormalRows"),
+                self.row_height == RowHeight::Normal,
+                Box::new(SetRowHeight(RowHeight::Normal)),
+            )
+            .menu_with_check(
+                t!("Table.TallRows"),
+                self.row_height == RowHeight::Tall,
+                Box::new(SetRowHeight(RowHeight::Tall)),
+            )
     }
 
     fn render_table_head(
@@ -893,12 +1388,19 @@ where
         let horizontal_scroll_handle = self.horizontal_scroll_handle.clone();
 
         h_flex()
+            .id("table-header")
             .w_full()
             .h(self.size.table_row_height())
             .flex_shrink_0()
             .border_b_1()
             .border_color(cx.theme().border)
             .text_color(cx.theme().table_head_foreground)
+            .context_menu({
+                let view = view.clone();
+                move |menu, cx: &mut ViewContext<PopupMenu>| {
+                    view.read(cx).render_cols_menu(menu, cx)
+                }
+            })
             .when(left_cols_count > 0, |this| {
                 let view = view.clone();
                 // Render left fixed columns
@@ -964,6 +1466,84 @@ where
             )
     }
 
+    /// Render the item at the given index, the fake rows are rendered after the items
+    /// to fill the rest table space.
+    fn render_table_item(
+        &mut self,
+        ix: usize,
+        rows_count: usize,
+        left_cols_count: usize,
+        cols_count: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> AnyElement {
+        match self.groups.item(ix) {
+            Some(TableItem::Group(group_ix)) => self
+                .render_group_row(("table-group", group_ix), group_ix, cx)
+                .into_any_element(),
+            Some(TableItem::Row(row_ix)) => self
+                .render_table_row(row_ix, rows_count, left_cols_count, cols_count, cx)
+                .into_any_element(),
+            None => {
+                let row_ix = rows_count + ix - self.groups.items_count();
+                self.render_table_row(row_ix, rows_count, left_cols_count, cols_count, cx)
+                    .into_any_element()
+            }
+        }
+    }
+
+    /// Render the group header with the expand/collapse toggle, the rows count and the aggregates.
+    fn render_group_row(
+        &self,
+        id: impl Into<ElementId>,
+        group_ix: usize,
+        cx: &mut ViewContext<Self>,
+    ) -> Stateful<Div> {
+        let group = &self.groups.groups[group_ix];
+        let key = group.key.clone();
+        let first_row = group.rows.start;
+        let collapsed = self.collapsed_groups.contains(&first_row);
+
+        h_flex()
+            .id(id)
+            .w_full()
+            .h(self.item_height())
+            .gap_2()
+            .table_cell_size(self.size)
+            .overflow_hidden()
+            .whitespace_nowrap()
+            .cursor_pointer()
+            .border_b_1()
+            .border_color(cx.theme().table_row_border)
+            .bg(cx.theme().table_head)
+            .text_color(cx.theme().table_head_foreground)
+            .child(
+                Icon::new(if collapsed {
+                    IconName::ChevronRight
+                } else {
+                    IconName::ChevronDown
+                })
+                .xsmall()
+                .text_color(cx.theme().muted_foreground),
+            )
+            .child(div().font_semibold().child(key))
+            .child(
+                div()
+                    .text_color(cx.theme().muted_foreground)
+                    .child(format!("({})", group.len())),
+            )
+            .children(self.delegate.group_aggregates(group, cx).into_iter().map(
+                |(label, value)| {
+                    h_flex()
+                        .gap_1()
+                        .child(div().text_color(cx.theme().muted_foreground).child(label))
+                        .child(value)
+                },
+            ))
+            .on_click(cx.listener(move |table, _, cx| {
+                table.set_group_collapsed(first_row, !collapsed, cx);
+            }))
+    }
+
     fn render_table_row(
         &mut self,
         row_ix: usize,
@@ -980,7 +1560,13 @@ where
             self.col_groups
                 .iter()
                 .skip(left_cols_count)
-                .map(|col| col.bounds.size)
+                .map(|col| {
+                    if col.visible {
+                        col.bounds.size
+                    } else {
+                        gpui::Size::default()
+                    }
+                })
                 .collect(),
         );
 
@@ -994,19 +1580,21 @@ where
                     }
                 })
                 .w_full()
-                .h(self.size.table_row_height())
+                .h(self.item_height())
                 .border_b_1()
                 .when(row_ix == rows_count, |this| {
                     this.border_color(gpui::transparent_white())
                 })
                 .border_color(cx.theme().table_row_border)
                 .when(is_stripe_row, |this| this.bg(cx.theme().table_even))
-                .hover(|this| {
-                    if is_selected || self.right_clicked_row == Some(row_ix) {
-                        this
-                    } else {
-                        this.bg(cx.theme().table_hover)
-                    }
+                .when(!cx.theme().lite, |this| {
+                    this.hover(|this| {
+                        if is_selected || self.right_clicked_row == Some(row_ix) {
+                            this
+                        } else {
+                            this.bg(cx.theme().table_hover)
+                        }
+                    })
                 })
                 .children(if left_cols_count > 0 {
                     // Left fixed columns
@@ -1016,10 +1604,8 @@ where
                             .border_r_1()
                             .border_color(cx.theme().table_row_border)
                             .children((0..left_cols_count).map(|col_ix| {
-                                self.render_col_wrap(col_ix, cx).child(
-                                    self.render_cell(col_ix, cx)
-                                        .child(self.delegate.render_td(row_ix, col_ix, cx)),
-                                )
+                                self.render_col_wrap(col_ix, cx)
+                                    .child(self.render_td_cell(row_ix, col_ix, cx))
                             })),
                     )
                 } else {
@@ -1037,11 +1623,9 @@ where
                                     visible_range
                                         .map(|col_ix| {
                                             let col_ix = col_ix + left_cols_count;
-                                            table.render_col_wrap(col_ix, cx).child(
-                                                table.render_cell(col_ix, cx).child(
-                                                    table.delegate.render_td(row_ix, col_ix, cx),
-                                                ),
-                                            )
+                                            table
+                                                .render_col_wrap(col_ix, cx)
+                                                .child(table.render_td_cell(row_ix, col_ix, cx))
                                         })
                                         .collect::<Vec<_>>()
                                 }
@@ -1144,6 +1728,10 @@ where
         let cols_count: usize = self.delegate.cols_count(cx);
         let left_cols_count = self.fixed_cols.left;
         let rows_count = self.delegate.rows_count(cx);
+        if rows_count != self.groups.rows_count() {
+            self.prepare_groups(cx);
+        }
+        let items_count = self.groups.items_count();
 
         let row_height = self
             .vertical_scroll_handle
@@ -1164,7 +1752,7 @@ where
         let mut extra_rows_needed = 0;
         if let Some(row_height) = row_height {
             if row_height > px(0.) {
-                let actual_height = row_height * rows_count as f32;
+                let actual_height = row_height * items_count as f32;
                 let remaining_height = total_height - actual_height;
                 if remaining_height > px(0.) {
                     extra_rows_needed = (remaining_height / row_height).ceil() as usize;
@@ -1172,11 +1760,33 @@ where
             }
         }
 
+        // The header of the group at the top is sticky when scrolling in the group.
+        let sticky_group = row_height
+            .filter(|row_height| *row_height > px(0.))
+            .and_then(|row_height| {
+                let scroll_y = -self
+                    .vertical_scroll_handle
+                    .0
+                    .borrow()
+                    .base_handle
+                    .offset()
+                    .y;
+                if scroll_y <= px(0.) {
+                    return None;
+                }
+
+                self.groups
+                    .item_group((scroll_y / row_height).floor() as usize)
+            });
+
         let inner_table = v_flex()
             .key_context("Table")
             .id("table")
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::action_cancel))
+            .on_action(cx.listener(Self::action_toggle_column))
+            .on_action(cx.listener(Self::action_set_row_height))
+            .on_action(cx.listener(Self::action_copy))
             .on_action(cx.listener(Self::action_select_next))
             .on_action(cx.listener(Self::action_select_prev))
             .on_action(cx.listener(Self::action_select_next_col))
@@ -1193,24 +1803,22 @@ where
                             uniform_list(
                                 view,
                                 "table-uniform-list",
-                                rows_count + extra_rows_needed,
+                                items_count + extra_rows_needed,
                                 {
                                     move |table, visible_range, cx| {
                                         table.load_more(visible_range.clone(), cx);
 
-                                        if visible_range.end > rows_count {
-                                            table.scroll_to_row(
-                                                std::cmp::min(visible_range.start, rows_count - 1),
+                                        if visible_range.end > items_count {
+                                            table.scroll_to_item(
+                                                std::cmp::min(visible_range.start, items_count - 1),
                                                 cx,
                                             );
                                         }
 
-                                        // Render fake rows to fill the table
                                         visible_range
-                                            .map(|row_ix| {
-                                                // Render real rows for available data
-                                                table.render_table_row(
-                                                    row_ix,
+                                            .map(|ix| {
+                                                table.render_table_item(
+                                                    ix,
                                                     rows_count,
                                                     left_cols_count,
                                                     cols_count,
@@ -1239,6 +1847,16 @@ where
             })
             .bg(cx.theme().table)
             .child(inner_table)
+            .when_some(sticky_group, |this, group_ix| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top(self.size.table_row_height())
+                        .left_0()
+                        .right_0()
+                        .child(self.render_group_row("table-sticky-group", group_ix, cx)),
+                )
+            })
             .child(ScrollableMask::new(
                 cx.view().clone(),
                 ScrollableAxis::Horizontal,
@@ -1261,3 +1879,19 @@ where
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use gpui::px;
+
+    use super::is_text_truncated;
+
+    #[test]
+    fn test_is_text_truncated() {
+        assert!(!is_text_truncated(px(80.), px(100.), 1));
+        assert!(is_text_truncated(px(120.), px(100.), 1));
+        assert!(!is_text_truncated(px(180.), px(100.), 2));
+        assert!(is_text_truncated(px(220.), px(100.), 2));
+        assert!(!is_text_truncated(px(120.), px(0.), 1));
+    }
+}
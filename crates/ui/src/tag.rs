@@ -0,0 +1,167 @@
+use std::rc::Rc;
+
+use gpui::{
+    div, prelude::FluentBuilder as _, px, relative, ClickEvent, Div, ElementId, Hsla,
+    InteractiveElement as _, IntoElement, ParentElement, RenderOnce, SharedString,
+    StatefulInteractiveElement as _, Styled, WindowContext,
+};
+
+use crate::{h_flex, theme::ActiveTheme as _, Icon, IconName, Sizable, Size};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TagVariant {
+    #[default]
+    Secondary,
+    Primary,
+    Outline,
+    Destructive,
+    /// The tag with the color from the palette, e.g.: `crate::blue_500()`.
+    Color(Hsla),
+}
+
+impl TagVariant {
+    fn bg(&self, cx: &WindowContext) -> Hsla {
+        match self {
+            Self::Secondary => cx.theme().secondary,
+            Self::Primary => cx.theme().primary,
+            Self::Outline => gpui::transparent_black(),
+            Self::Destructive => cx.theme().destructive,
+            Self::Color(color) => color.opacity(0.15),
+        }
+    }
+
+    fn border(&self, cx: &WindowContext) -> Hsla {
+        match self {
+            Self::Secondary => cx.theme().border,
+            Self::Primary => cx.theme().primary,
+            Self::Outline => cx.theme().border,
+            Self::Destructive => cx.theme().destructive,
+            Self::Color(color) => color.opacity(0.4),
+        }
+    }
+
+    fn fg(&self, cx: &WindowContext) -> Hsla {
+        match self {
+            Self::Secondary => cx.theme().secondary_foreground,
+            Self::Primary => cx.theme().primary_foreground,
+            Self::Outline => cx.theme().foreground,
+            Self::Destructive => cx.theme().destructive_foreground,
+            Self::Color(color) => *color,
+        }
+    }
+}
+
+/// A tag to label the items, e.g.: the categories of an article,
+/// can be removable with a close button.
+///
+/// ```ignore
+/// Tag::new("rust", "Rust")
+///     .color(ui::orange_500())
+///     .on_remove(|_, cx| { /* remove the tag */ })
+/// ```
+#[derive(IntoElement)]
+pub struct Tag {
+    id: ElementId,
+    base: Div,
+    label: SharedString,
+    variant: TagVariant,
+    size: Size,
+    on_remove: Option<Rc<dyn Fn(&ClickEvent, &mut WindowContext)>>,
+}
+
+impl Tag {
+    pub fn new(id: impl Into<ElementId>, label: impl Into<SharedString>) -> Self {
+        Self {
+            id: id.into(),
+            base: h_flex(),
+            label: label.into(),
+            variant: TagVariant::default(),
+            size: Size::default(),
+            on_remove: None,
+        }
+    }
+
+    pub fn with_variant(mut self, variant: TagVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    pub fn primary(self) -> Self {
+        self.with_variant(TagVariant::Primary)
+    }
+
+    pub fn outline(self) -> Self {
+        self.with_variant(TagVariant::Outline)
+    }
+
+    pub fn destructive(self) -> Self {
+        self.with_variant(TagVariant::Destructive)
+    }
+
+    /// Set the color of the tag, the background and the border are derived from the color.
+    pub fn color(self, color: impl Into<Hsla>) -> Self {
+        self.with_variant(TagVariant::Color(color.into()))
+    }
+
+    /// Set the handler of the close button, the tag is removable if this is set.
+    pub fn on_remove(
+        mut self,
+        handler: impl Fn(&ClickEvent, &mut WindowContext) + 'static,
+    ) -> Self {
+        self.on_remove = Some(Rc::new(handler));
+        self
+    }
+}
+
+impl Sizable for Tag {
+    fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+}
+
+impl Styled for Tag {
+    fn style(&mut self) -> &mut gpui::StyleRefinement {
+        self.base.style()
+    }
+}
+
+impl RenderOnce for Tag {
+    fn render(self, cx: &mut WindowContext) -> impl IntoElement {
+        let fg = self.variant.fg(cx);
+
+        self.base
+            .id(self.id)
+            .flex_shrink_0()
+            .gap_1()
+            .rounded_md()
+            .border_1()
+            .line_height(relative(1.3))
+            .map(|this| match self.size {
+                Size::XSmall | Size::Small => this.text_xs().px_1p5().py_0(),
+                Size::XLarge => this.text_sm().px_3().py_0p5(),
+                _ => this.text_xs().px_2().py_0p5(),
+            })
+            .bg(self.variant.bg(cx))
+            .text_color(fg)
+            .border_color(self.variant.border(cx))
+            .child(self.label)
+            .when_some(self.on_remove, |this, on_remove| {
+                this.child(
+                    div()
+                        .id("remove")
+                        .flex()
+                        .items_center()
+                        .rounded_sm()
+                        .cursor_pointer()
+                        .text_color(fg.opacity(0.7))
+                        .hover(|this| this.text_color(fg))
+                        .child(Icon::new(IconName::Close).size(px(12.)))
+                        .on_click(move |event, cx| {
+                            cx.stop_propagation();
+                            on_remove(event, cx);
+                        }),
+                )
+            })
+    }
+}
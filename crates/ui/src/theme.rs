@@ -1,16 +1,31 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
 
 use gpui::{
-    hsla, point, AppContext, BoxShadow, Global, Hsla, ModelContext, Pixels, SharedString,
-    ViewContext, WindowAppearance, WindowContext,
+    hsla, point, rgb, AnyWindowHandle, AppContext, BoxShadow, Global, Hsla, ModelContext, Pixels,
+    SharedString, ViewContext, WindowAppearance, WindowBackgroundAppearance, WindowContext,
 };
+use serde::{Deserialize, Serialize};
+use smol::Timer;
+
+use crate::{animation::cubic_bezier, scroll::ScrollbarShow};
 
-use crate::scroll::ScrollbarShow;
+/// The duration of the color transition when the theme changed.
+const TRANSITION_DURATION: Duration = Duration::from_millis(250);
+const TRANSITION_INTERVAL: Duration = Duration::from_millis(16);
 
 pub fn init(cx: &mut AppContext) {
     Theme::sync_system_appearance(cx)
 }
 
+/// Change the variant of the theme, e.g.: the high contrast or the color-blind safe colors.
+pub fn set_variant(variant: ThemeVariant, cx: &mut AppContext) {
+    Theme::set_variant(variant, cx)
+}
+
 pub trait ActiveTheme {
     fn theme(&self) -> &Theme;
 }
@@ -35,7 +50,39 @@ impl<V> ActiveTheme for ModelContext<'_, V> {
 
 impl ActiveTheme for WindowContext<'_> {
     fn theme(&self) -> &Theme {
-        self.deref().theme()
+        Theme::window(self)
+    }
+}
+
+/// The themes to override the global theme in the windows.
+#[derive(Default)]
+struct WindowThemes(HashMap<AnyWindowHandle, Theme>);
+
+impl Global for WindowThemes {}
+
+/// Returns the color between `from` and `to` at `t` (0.0..=1.0), the hue goes the shortest way.
+fn mix_color(from: Hsla, to: Hsla, t: f32) -> Hsla {
+    let t = t.clamp(0., 1.);
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+    // The hue of the gray colors is meaningless, use the hue of the other color.
+    let (from_h, to_h) = match (from.s == 0., to.s == 0.) {
+        (true, false) => (to.h, to.h),
+        (false, true) => (from.h, from.h),
+        _ => (from.h, to.h),
+    };
+    let mut delta = to_h - from_h;
+    if delta > 0.5 {
+        delta -= 1.;
+    } else if delta < -0.5 {
+        delta += 1.;
+    }
+
+    Hsla {
+        h: (from_h + delta * t).rem_euclid(1.),
+        s: lerp(from.s, to.s),
+        l: lerp(from.l, to.l),
+        a: lerp(from.a, to.a),
     }
 }
 
@@ -176,9 +223,13 @@ pub struct ThemeColor {
     pub list_hover: Hsla,
     pub muted: Hsla,
     pub muted_foreground: Hsla,
+    /// The color of the negative values, e.g.: the falling prices, the losses.
+    pub negative: Hsla,
     pub panel: Hsla,
     pub popover: Hsla,
     pub popover_foreground: Hsla,
+    /// The color of the positive values, e.g.: the rising prices, the profits.
+    pub positive: Hsla,
     pub primary: Hsla,
     pub primary_active: Hsla,
     pub primary_foreground: Hsla,
@@ -209,6 +260,8 @@ pub struct ThemeColor {
     pub table_head_foreground: Hsla,
     pub table_hover: Hsla,
     pub table_row_border: Hsla,
+    /// The color of the warnings.
+    pub warning: Hsla,
     pub title_bar: Hsla,
     pub title_bar_border: Hsla,
     pub sidebar: Hsla,
@@ -221,6 +274,177 @@ pub struct ThemeColor {
 }
 
 impl ThemeColor {
+    /// Returns the colors between `self` and `to` at `t` (0.0..=1.0), for the theme transition.
+    pub fn mix(&self, to: &ThemeColor, t: f32) -> ThemeColor {
+        ThemeColor {
+            accent: mix_color(self.accent, to.accent, t),
+            accent_foreground: mix_color(self.accent_foreground, to.accent_foreground, t),
+            accordion: mix_color(self.accordion, to.accordion, t),
+            accordion_active: mix_color(self.accordion_active, to.accordion_active, t),
+            accordion_hover: mix_color(self.accordion_hover, to.accordion_hover, t),
+            background: mix_color(self.background, to.background, t),
+            border: mix_color(self.border, to.border, t),
+            card: mix_color(self.card, to.card, t),
+            card_foreground: mix_color(self.card_foreground, to.card_foreground, t),
+            destructive: mix_color(self.destructive, to.destructive, t),
+            destructive_active: mix_color(self.destructive_active, to.destructive_active, t),
+            destructive_foreground: mix_color(
+                self.destructive_foreground,
+                to.destructive_foreground,
+                t,
+            ),
+            destructive_hover: mix_color(self.destructive_hover, to.destructive_hover, t),
+            drag_border: mix_color(self.drag_border, to.drag_border, t),
+            drop_target: mix_color(self.drop_target, to.drop_target, t),
+            foreground: mix_color(self.foreground, to.foreground, t),
+            input: mix_color(self.input, to.input, t),
+            link: mix_color(self.link, to.link, t),
+            link_active: mix_color(self.link_active, to.link_active, t),
+            link_hover: mix_color(self.link_hover, to.link_hover, t),
+            list: mix_color(self.list, to.list, t),
+            list_active: mix_color(self.list_active, to.list_active, t),
+            list_active_border: mix_color(self.list_active_border, to.list_active_border, t),
+            list_even: mix_color(self.list_even, to.list_even, t),
+            list_head: mix_color(self.list_head, to.list_head, t),
+            list_hover: mix_color(self.list_hover, to.list_hover, t),
+            muted: mix_color(self.muted, to.muted, t),
+            muted_foreground: mix_color(self.muted_foreground, to.muted_foreground, t),
+            negative: mix_color(self.negative, to.negative, t),
+            panel: mix_color(self.panel, to.panel, t),
+            popover: mix_color(self.popover, to.popover, t),
+            popover_foreground: mix_color(self.popover_foreground, to.popover_foreground, t),
+            positive: mix_color(self.positive, to.positive, t),
+            primary: mix_color(self.primary, to.primary, t),
+            primary_active: mix_color(self.primary_active, to.primary_active, t),
+            primary_foreground: mix_color(self.primary_foreground, to.primary_foreground, t),
+            primary_hover: mix_color(self.primary_hover, to.primary_hover, t),
+            progress_bar: mix_color(self.progress_bar, to.progress_bar, t),
+            ring: mix_color(self.ring, to.ring, t),
+            scrollbar: mix_color(self.scrollbar, to.scrollbar, t),
+            scrollbar_thumb: mix_color(self.scrollbar_thumb, to.scrollbar_thumb, t),
+            scrollbar_thumb_hover: mix_color(
+                self.scrollbar_thumb_hover,
+                to.scrollbar_thumb_hover,
+                t,
+            ),
+            secondary: mix_color(self.secondary, to.secondary, t),
+            secondary_active: mix_color(self.secondary_active, to.secondary_active, t),
+            secondary_foreground: mix_color(self.secondary_foreground, to.secondary_foreground, t),
+            secondary_hover: mix_color(self.secondary_hover, to.secondary_hover, t),
+            selection: mix_color(self.selection, to.selection, t),
+            skeleton: mix_color(self.skeleton, to.skeleton, t),
+            slider_bar: mix_color(self.slider_bar, to.slider_bar, t),
+            slider_thumb: mix_color(self.slider_thumb, to.slider_thumb, t),
+            tab: mix_color(self.tab, to.tab, t),
+            tab_active: mix_color(self.tab_active, to.tab_active, t),
+            tab_active_foreground: mix_color(
+                self.tab_active_foreground,
+                to.tab_active_foreground,
+                t,
+            ),
+            tab_bar: mix_color(self.tab_bar, to.tab_bar, t),
+            tab_foreground: mix_color(self.tab_foreground, to.tab_foreground, t),
+            table: mix_color(self.table, to.table, t),
+            table_active: mix_color(self.table_active, to.table_active, t),
+            table_active_border: mix_color(self.table_active_border, to.table_active_border, t),
+            table_even: mix_color(self.table_even, to.table_even, t),
+            table_head: mix_color(self.table_head, to.table_head, t),
+            table_head_foreground: mix_color(
+                self.table_head_foreground,
+                to.table_head_foreground,
+                t,
+            ),
+            table_hover: mix_color(self.table_hover, to.table_hover, t),
+            table_row_border: mix_color(self.table_row_border, to.table_row_border, t),
+            warning: mix_color(self.warning, to.warning, t),
+            title_bar: mix_color(self.title_bar, to.title_bar, t),
+            title_bar_border: mix_color(self.title_bar_border, to.title_bar_border, t),
+            sidebar: mix_color(self.sidebar, to.sidebar, t),
+            sidebar_accent: mix_color(self.sidebar_accent, to.sidebar_accent, t),
+            sidebar_accent_foreground: mix_color(
+                self.sidebar_accent_foreground,
+                to.sidebar_accent_foreground,
+                t,
+            ),
+            sidebar_border: mix_color(self.sidebar_border, to.sidebar_border, t),
+            sidebar_foreground: mix_color(self.sidebar_foreground, to.sidebar_foreground, t),
+            sidebar_primary: mix_color(self.sidebar_primary, to.sidebar_primary, t),
+            sidebar_primary_foreground: mix_color(
+                self.sidebar_primary_foreground,
+                to.sidebar_primary_foreground,
+                t,
+            ),
+        }
+    }
+
+    /// Returns the colors of the mode and the variant.
+    pub fn new(mode: ThemeMode, variant: ThemeVariant) -> Self {
+        let colors = match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        };
+
+        match variant {
+            ThemeVariant::Default => colors,
+            ThemeVariant::HighContrast => colors.high_contrast(mode),
+            ThemeVariant::ColorBlind => colors.color_blind(mode),
+        }
+    }
+
+    /// Strengthen the text and border colors for the high contrast.
+    fn high_contrast(mut self, mode: ThemeMode) -> Self {
+        let (background, foreground, muted_foreground, border) = match mode {
+            ThemeMode::Light => (
+                hsl(0.0, 0.0, 100.0),
+                hsl(0.0, 0.0, 0.0),
+                hsl(240.0, 5.0, 25.0),
+                hsl(240.0, 5.0, 35.0),
+            ),
+            ThemeMode::Dark => (
+                hsl(0.0, 0.0, 0.0),
+                hsl(0.0, 0.0, 100.0),
+                hsl(240.0, 5.0, 80.0),
+                hsl(240.0, 5.0, 65.0),
+            ),
+        };
+
+        self.background = background;
+        self.foreground = foreground;
+        self.card_foreground = foreground;
+        self.popover_foreground = foreground;
+        self.accent_foreground = foreground;
+        self.tab_active_foreground = foreground;
+        self.table_head_foreground = foreground;
+        self.sidebar_foreground = foreground;
+        self.muted_foreground = muted_foreground;
+        self.tab_foreground = muted_foreground;
+        self.scrollbar_thumb = muted_foreground;
+        self.scrollbar_thumb_hover = foreground;
+        self.border = border;
+        self.input = border;
+        self.title_bar_border = border;
+        self.sidebar_border = border;
+        self.table_row_border = border;
+        self.list_active_border = foreground;
+        self.table_active_border = foreground;
+        self.ring = foreground;
+        self
+    }
+
+    /// Use the color-blind safe semantic colors (by Okabe & Ito), the positive and negative colors
+    /// are blue and vermillion instead of green and red.
+    fn color_blind(mut self, mode: ThemeMode) -> Self {
+        let (positive, negative, warning) = match mode {
+            ThemeMode::Light => (0x0072b2, 0xd55e00, 0xe69f00),
+            ThemeMode::Dark => (0x56b4e9, 0xe66c16, 0xf0e442),
+        };
+
+        self.positive = rgb(positive).into();
+        self.negative = rgb(negative).into();
+        self.warning = rgb(warning).into();
+        self
+    }
+
     pub fn light() -> Self {
         Self {
             accent: hsl(240.0, 5.0, 96.0),
@@ -251,9 +475,11 @@ impl ThemeColor {
             list_hover: hsl(240.0, 4.8, 95.0),
             muted: hsl(240.0, 4.8, 95.9),
             muted_foreground: hsl(240.0, 3.8, 46.1),
+            negative: hsl(0.0, 84.2, 60.2),
             panel: hsl(0.0, 0.0, 100.0),
             popover: hsl(0.0, 0.0, 100.0),
             popover_foreground: hsl(240.0, 10.0, 3.9),
+            positive: hsl(142.1, 76.2, 36.3),
             primary: hsl(223.0, 5.9, 10.0),
             primary_active: hsl(223.0, 1.9, 25.0),
             primary_foreground: hsl(223.0, 0.0, 98.0),
@@ -284,6 +510,7 @@ impl ThemeColor {
             table_head_foreground: hsl(240.0, 10., 3.9).opacity(0.7),
             table_hover: hsl(240.0, 4.8, 95.0),
             table_row_border: hsl(240.0, 7.7, 94.5),
+            warning: hsl(37.7, 92.1, 50.2),
             title_bar: hsl(0.0, 0.0, 100.),
             title_bar_border: hsl(240.0, 5.9, 90.0),
             sidebar: hsl(0.0, 0.0, 98.0),
@@ -326,9 +553,11 @@ impl ThemeColor {
             list_hover: hsl(240.0, 3.7, 15.9),
             muted: hsl(240.0, 3.7, 15.9),
             muted_foreground: hsl(240.0, 5.0, 64.9),
+            negative: hsl(0.0, 72.2, 50.6),
             panel: hsl(299.0, 2., 11.),
             popover: hsl(0.0, 0.0, 10.),
             popover_foreground: hsl(0.0, 0.0, 78.0),
+            positive: hsl(142.1, 70.6, 45.3),
             primary: hsl(223.0, 0.0, 98.0),
             primary_active: hsl(223.0, 0.0, 80.0),
             primary_foreground: hsl(223.0, 5.9, 10.0),
@@ -359,6 +588,7 @@ impl ThemeColor {
             table_head_foreground: hsl(0., 0., 78.).opacity(0.7),
             table_hover: hsl(240.0, 3.7, 15.9).opacity(0.5),
             table_row_border: hsl(240.0, 3.7, 16.9).opacity(0.5),
+            warning: hsl(47.9, 95.8, 53.1),
             title_bar: hsl(0., 0., 9.7),
             title_bar_border: hsl(240.0, 3.7, 15.9),
             sidebar: hsl(240.0, 0.0, 10.0),
@@ -384,6 +614,76 @@ pub struct Theme {
     pub transparent: Hsla,
     /// Show the scrollbar mode, default: Scrolling
     pub scrollbar_show: ScrollbarShow,
+    /// The minimum length of the scrollbar thumb in pixels, default: 80.0
+    pub scrollbar_min_thumb_size: f32,
+    /// Use the translucent window background (macOS vibrancy / Windows acrylic), default: false
+    ///
+    /// Fallback to the solid colors if the platform is not supported.
+    pub translucent: bool,
+    /// The lite rendering mode, disables the shadows, blurs, animations and hover effects
+    /// of the components, for the remote desktop (VDI) connections, default: false
+    pub lite: bool,
+    /// The categorical colors for the data visualization, default: Default
+    pub chart_palette: ChartPalette,
+    /// Fill the series with the patterns (e.g.: stripes, dots) in addition to the colors,
+    /// so they are distinguishable without relying on the colors alone, default: false
+    pub chart_patterns: bool,
+    /// The variant of the theme colors, default: Default
+    pub variant: ThemeVariant,
+    /// The number of the color transitions, to stop the previous transition when the theme changed again.
+    transition: usize,
+}
+
+/// The variants of the theme colors, for the accessibility.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ThemeVariant {
+    #[default]
+    Default,
+    /// The high contrast colors, with the stronger text and border colors.
+    HighContrast,
+    /// The color-blind safe semantic colors, the charts use the color-blind safe palette.
+    ColorBlind,
+}
+
+/// The categorical color palettes for the data visualization, e.g.: the series of the charts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ChartPalette {
+    #[default]
+    Default,
+    /// The color-blind safe palette by Okabe & Ito.
+    OkabeIto,
+    /// The color-blind safe bright palette by Paul Tol.
+    TolBright,
+}
+
+impl ChartPalette {
+    pub const ALL: [ChartPalette; 3] = [Self::Default, Self::OkabeIto, Self::TolBright];
+
+    /// Returns the hex colors of the palette.
+    pub fn hex_colors(&self) -> &'static [u32] {
+        match self {
+            Self::Default => &[
+                0x3b82f6, 0xf97316, 0x22c55e, 0x8b5cf6, 0xec4899, 0x14b8a6, 0xf59e0b, 0xef4444,
+            ],
+            Self::OkabeIto => &[
+                0x0072b2, 0xe69f00, 0x009e73, 0xcc79a7, 0x56b4e9, 0xd55e00, 0xf0e442, 0x999999,
+            ],
+            Self::TolBright => &[
+                0x4477aa, 0xee6677, 0x228833, 0xccbb44, 0x66ccee, 0xaa3377, 0xbbbbbb,
+            ],
+        }
+    }
+
+    /// Returns the color of the series at the index, the colors are repeated if the index is out of the palette.
+    pub fn color(&self, ix: usize) -> Hsla {
+        let colors = self.hex_colors();
+        rgb(colors[ix % colors.len()]).into()
+    }
+
+    /// Returns true if the palette is safe for the color vision deficiencies.
+    pub fn is_color_blind_safe(&self) -> bool {
+        !matches!(self, Self::Default)
+    }
 }
 
 impl Deref for Theme {
@@ -413,6 +713,98 @@ impl Theme {
         cx.global_mut::<Theme>()
     }
 
+    /// Returns the theme of the window, the override of the window if set, otherwise the global theme.
+    pub fn window<'a>(cx: &'a WindowContext) -> &'a Theme {
+        cx.try_global::<WindowThemes>()
+            .and_then(|themes| themes.0.get(&cx.window_handle()))
+            .unwrap_or_else(|| Self::global(cx))
+    }
+
+    /// Set the theme of the window to override the global theme, `None` to follow the global theme.
+    ///
+    /// e.g.: a detached panel window can be dark while the main window is light.
+    pub fn set_window_theme(theme: Option<Theme>, cx: &mut WindowContext) {
+        let window = cx.window_handle();
+        let windows = cx.windows();
+        let themes = cx.default_global::<WindowThemes>();
+        // Remove the overrides of the closed windows.
+        themes.0.retain(|handle, _| windows.contains(handle));
+        match theme {
+            Some(theme) => themes.0.insert(window, theme),
+            None => themes.0.remove(&window),
+        };
+        cx.refresh();
+    }
+
+    /// Set the mode of the window to override the global theme, `None` to follow the global theme.
+    pub fn set_window_mode(mode: Option<ThemeMode>, cx: &mut WindowContext) {
+        let theme = mode.map(|mode| Self::with_mode(mode, cx));
+        Self::set_window_theme(theme, cx);
+    }
+
+    /// Returns the colors of the theme.
+    pub fn colors(&self) -> &ThemeColor {
+        &self.colors
+    }
+
+    /// Animate the colors of the global theme from the `from` colors to the current colors,
+    /// call this after changing the theme (e.g.: [`Theme::change`], [`Theme::apply_color`]).
+    ///
+    /// The colors are changed instantly in the lite rendering mode.
+    ///
+    /// ```ignore
+    /// let from = *cx.theme().colors();
+    /// Theme::change(ThemeMode::Dark, cx);
+    /// Theme::animate_from(from, cx);
+    /// ```
+    pub fn animate_from(from: ThemeColor, cx: &mut AppContext) {
+        let theme = Self::global_mut(cx);
+        theme.transition += 1;
+        if theme.lite {
+            return;
+        }
+
+        let transition = theme.transition;
+        let to = theme.colors;
+        theme.colors = from;
+        cx.refresh();
+
+        let started_at = Instant::now();
+        let ease = cubic_bezier(0.4, 0., 0.2, 1.);
+        cx.spawn(|mut cx| async move {
+            loop {
+                Timer::after(TRANSITION_INTERVAL).await;
+                let progress =
+                    started_at.elapsed().as_secs_f32() / TRANSITION_DURATION.as_secs_f32();
+                let done = cx.update(|cx| {
+                    let theme = Self::global_mut(cx);
+                    // The theme has been changed again, the new transition takes over.
+                    if theme.transition != transition {
+                        return true;
+                    }
+
+                    theme.colors = from.mix(&to, ease(progress.min(1.)));
+                    cx.refresh();
+                    progress >= 1.
+                });
+
+                if done.unwrap_or(true) {
+                    break;
+                }
+            }
+        })
+        .detach();
+    }
+
+    /// Change the theme mode with the color transition.
+    pub fn change_animated(mode: ThemeMode, cx: &mut AppContext) {
+        let from = cx.try_global::<Theme>().map(|theme| theme.colors);
+        Self::change(mode, cx);
+        if let Some(from) = from {
+            Self::animate_from(from, cx);
+        }
+    }
+
     /// Apply a mask color to the theme.
     pub fn apply_color(&mut self, mask_color: Hsla) {
         self.title_bar = self.title_bar.apply(mask_color);
@@ -489,6 +881,85 @@ impl Theme {
         self.sidebar_primary_foreground = self.sidebar_primary_foreground.apply(mask_color);
     }
 
+    /// Returns true if the platform supports the translucent window background.
+    pub fn is_translucent_supported() -> bool {
+        cfg!(any(target_os = "macos", target_os = "windows"))
+    }
+
+    /// Returns true if the translucent window background is enabled and supported.
+    ///
+    /// Always false in the lite rendering mode.
+    pub fn is_translucent(&self) -> bool {
+        self.translucent && !self.lite && Self::is_translucent_supported()
+    }
+
+    /// Returns true if the components should render the shadows.
+    pub fn has_shadow(&self) -> bool {
+        self.shadow && !self.lite
+    }
+
+    /// Enable or disable the lite rendering mode at runtime.
+    pub fn set_lite(lite: bool, cx: &mut AppContext) {
+        Self::global_mut(cx).lite = lite;
+        cx.refresh();
+    }
+
+    /// Change the palette of the data visualization at runtime.
+    pub fn set_chart_palette(palette: ChartPalette, cx: &mut AppContext) {
+        Self::global_mut(cx).chart_palette = palette;
+        cx.refresh();
+    }
+
+    /// Enable or disable the pattern fills of the data visualization at runtime.
+    pub fn set_chart_patterns(patterns: bool, cx: &mut AppContext) {
+        Self::global_mut(cx).chart_patterns = patterns;
+        cx.refresh();
+    }
+
+    /// Returns the color of the series at the index in the current chart palette.
+    ///
+    /// The [`ChartPalette::OkabeIto`] is used instead of the not color-blind safe palette
+    /// in the [`ThemeVariant::ColorBlind`].
+    pub fn chart_color(&self, ix: usize) -> Hsla {
+        if self.variant == ThemeVariant::ColorBlind && !self.chart_palette.is_color_blind_safe() {
+            return ChartPalette::OkabeIto.color(ix);
+        }
+
+        self.chart_palette.color(ix)
+    }
+
+    /// Change the variant of the theme colors at runtime, the colors of the current mode are reset.
+    pub fn set_variant(variant: ThemeVariant, cx: &mut AppContext) {
+        let mode = Self::global(cx).mode;
+        Self::global_mut(cx).variant = variant;
+        Self::change(mode, cx);
+    }
+
+    /// Enable or disable the translucent window background.
+    pub fn set_translucent(translucent: bool, cx: &mut AppContext) {
+        Self::global_mut(cx).translucent = translucent;
+        cx.refresh();
+    }
+
+    /// Returns the background appearance of the window for the current theme.
+    pub fn window_background(&self) -> WindowBackgroundAppearance {
+        if self.is_translucent() {
+            WindowBackgroundAppearance::Blurred
+        } else {
+            WindowBackgroundAppearance::Opaque
+        }
+    }
+
+    /// Returns the surface color for the panels, semi-transparent in the translucent mode,
+    /// otherwise the solid color.
+    pub fn surface(&self, color: Hsla) -> Hsla {
+        if self.is_translucent() {
+            color.opacity(0.75)
+        } else {
+            color
+        }
+    }
+
     /// Sync the theme with the system appearance
     pub fn sync_system_appearance(cx: &mut AppContext) {
         match cx.window_appearance() {
@@ -502,16 +973,31 @@ impl Theme {
     }
 
     pub fn change(mode: ThemeMode, cx: &mut AppContext) {
-        let colors = match mode {
-            ThemeMode::Light => ThemeColor::light(),
-            ThemeMode::Dark => ThemeColor::dark(),
-        };
+        let theme = Self::with_mode(mode, cx);
+        cx.set_global(theme);
+        cx.refresh();
+    }
 
-        let mut theme = Theme::from(colors);
+    /// Returns a new theme of the mode, with the options of the global theme.
+    fn with_mode(mode: ThemeMode, cx: &AppContext) -> Theme {
+        let variant = cx
+            .try_global::<Theme>()
+            .map_or(ThemeVariant::default(), |theme| theme.variant);
+
+        let mut theme = Theme::from(ThemeColor::new(mode, variant));
         theme.mode = mode;
+        theme.variant = variant;
+        // Keep the translucent, lite, chart and scrollbar options of the window when the mode changed.
+        if let Some(prev_theme) = cx.try_global::<Theme>() {
+            theme.translucent = prev_theme.translucent;
+            theme.lite = prev_theme.lite;
+            theme.chart_palette = prev_theme.chart_palette;
+            theme.chart_patterns = prev_theme.chart_patterns;
+            theme.scrollbar_min_thumb_size = prev_theme.scrollbar_min_thumb_size;
+            theme.transition = prev_theme.transition;
+        }
 
-        cx.set_global(theme);
-        cx.refresh();
+        theme
     }
 }
 
@@ -531,12 +1017,19 @@ impl From<ThemeColor> for Theme {
             radius: 4.0,
             shadow: true,
             scrollbar_show: ScrollbarShow::default(),
+            scrollbar_min_thumb_size: 80.,
+            translucent: false,
+            lite: false,
+            chart_palette: ChartPalette::default(),
+            chart_patterns: false,
+            variant: ThemeVariant::default(),
+            transition: 0,
             colors,
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Eq, Serialize, Deserialize)]
 pub enum ThemeMode {
     Light,
     #[default]
@@ -551,7 +1044,28 @@ impl ThemeMode {
 
 #[cfg(test)]
 mod tests {
-    use crate::theme::Colorize as _;
+    use crate::theme::{mix_color, Colorize as _};
+
+    #[test]
+    fn test_mix_color() {
+        let from = super::hsl(0., 50., 20.);
+        let to = super::hsl(90., 100., 80.);
+        assert_eq!(mix_color(from, to, 0.), from);
+        assert_eq!(mix_color(from, to, 1.), to);
+
+        let color = mix_color(from, to, 0.5);
+        assert_eq!(color.h, 0.125);
+        assert_eq!(color.s, 0.75);
+        assert_eq!(color.l, 0.5);
+
+        // The hue goes the shortest way across 0.
+        let color = mix_color(super::hsl(350., 50., 50.), super::hsl(10., 50., 50.), 0.5);
+        assert!(color.h.abs() < 0.0001 || (color.h - 1.).abs() < 0.0001);
+
+        // Keep the hue of the colored one when mixing with the gray.
+        let color = mix_color(super::hsl(0., 0., 100.), super::hsl(200., 50., 50.), 0.5);
+        assert_eq!(color.h, 200. / 360.);
+    }
 
     #[test]
     fn test_lighten() {
@@ -572,4 +1086,41 @@ mod tests {
         let color = color.darken(0.5);
         assert_eq!(color.l, 0.24);
     }
+
+    #[test]
+    fn test_chart_palette_color() {
+        use super::ChartPalette;
+
+        for palette in ChartPalette::ALL {
+            let len = palette.hex_colors().len();
+            assert!(len >= 7);
+            assert_eq!(palette.color(1), palette.color(len + 1));
+        }
+        assert!(ChartPalette::OkabeIto.is_color_blind_safe());
+        assert!(!ChartPalette::Default.is_color_blind_safe());
+    }
+
+    #[test]
+    fn test_theme_variant() {
+        use super::{ChartPalette, Theme, ThemeColor, ThemeMode, ThemeVariant};
+
+        let light = ThemeColor::new(ThemeMode::Light, ThemeVariant::Default);
+        let high_contrast = ThemeColor::new(ThemeMode::Light, ThemeVariant::HighContrast);
+        assert!(high_contrast.foreground.l < light.foreground.l);
+        assert!(high_contrast.border.l < light.border.l);
+
+        let dark = ThemeColor::new(ThemeMode::Dark, ThemeVariant::Default);
+        let high_contrast = ThemeColor::new(ThemeMode::Dark, ThemeVariant::HighContrast);
+        assert!(high_contrast.foreground.l > dark.foreground.l);
+
+        let color_blind = ThemeColor::new(ThemeMode::Light, ThemeVariant::ColorBlind);
+        assert_ne!(color_blind.positive, light.positive);
+        assert_eq!(color_blind.foreground, light.foreground);
+
+        let mut theme = Theme::from(color_blind);
+        theme.variant = ThemeVariant::ColorBlind;
+        assert_eq!(theme.chart_color(0), ChartPalette::OkabeIto.color(0));
+        theme.chart_palette = ChartPalette::TolBright;
+        assert_eq!(theme.chart_color(0), ChartPalette::TolBright.color(0));
+    }
 }
@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, NaiveDate, Weekday};
 use gpui::{
     prelude::FluentBuilder as _, px, relative, ClickEvent, ElementId, EventEmitter, FocusHandle,
     InteractiveElement, IntoElement, ParentElement, Render, SharedString,
@@ -12,10 +12,10 @@ use crate::{
     button::{Button, ButtonVariants as _},
     h_flex,
     theme::ActiveTheme,
-    v_flex, Disableable as _, IconName, Selectable, Sizable, Size,
+    v_flex, Disableable as _, IconName, Selectable, Sizable, Size, StyleSized as _,
 };
 
-use super::utils::days_in_month;
+use super::utils::{days_from_first_day_of_week, days_in_month};
 
 pub enum CalendarEvent {
     /// The user selected a date.
@@ -160,9 +160,21 @@ pub struct Calendar {
     year_page: i32,
     /// Number of the months view to show.
     number_of_months: usize,
+    /// The first day of the week, `None` to use the default of the current locale.
+    first_day_of_week: Option<Weekday>,
+    /// Whether to show the ISO week number column.
+    show_week_number: bool,
     today: NaiveDate,
 }
 
+/// Returns the first day of the week of the current locale, default is Sunday.
+fn locale_first_day_of_week() -> Weekday {
+    t!("Calendar.first_day_of_week")
+        .parse::<u8>()
+        .map(|n| (0..n % 7).fold(Weekday::Sun, |weekday, _| weekday.succ()))
+        .unwrap_or(Weekday::Sun)
+}
+
 impl Calendar {
     pub fn new(cx: &mut ViewContext<Self>) -> Self {
         let today = Local::now().naive_local().date();
@@ -176,6 +188,8 @@ impl Calendar {
             years: vec![],
             year_page: 0,
             number_of_months: 1,
+            first_day_of_week: None,
+            show_week_number: false,
             today,
         }
         .year_range((today.year() - 50, today.year() + 50))
@@ -213,6 +227,35 @@ impl Calendar {
         self
     }
 
+    /// Set the first day of the week, default is the first day of the week of the current locale.
+    pub fn first_day_of_week(mut self, weekday: Weekday) -> Self {
+        self.first_day_of_week = Some(weekday);
+        self
+    }
+
+    /// Set the first day of the week, `None` to use the default of the current locale.
+    pub fn set_first_day_of_week(&mut self, weekday: Option<Weekday>, cx: &mut ViewContext<Self>) {
+        self.first_day_of_week = weekday;
+        cx.notify();
+    }
+
+    /// Set true to show the ISO week number column, default is false.
+    pub fn week_number(mut self, show: bool) -> Self {
+        self.show_week_number = show;
+        self
+    }
+
+    /// Set true to show the ISO week number column.
+    pub fn set_week_number(&mut self, show: bool, cx: &mut ViewContext<Self>) {
+        self.show_week_number = show;
+        cx.notify();
+    }
+
+    fn resolved_first_day_of_week(&self) -> Weekday {
+        self.first_day_of_week
+            .unwrap_or_else(locale_first_day_of_week)
+    }
+
     pub fn set_size(&mut self, size: Size, cx: &mut ViewContext<Self>) {
         self.size = size;
         cx.notify();
@@ -256,11 +299,17 @@ impl Calendar {
         (year, month as u32)
     }
 
-    /// Returns the days of the month in a 2D vector to render on calendar.
-    fn days(&self) -> Vec<Vec<NaiveDate>> {
+    /// Returns the weeks of each month to render on calendar.
+    fn days(&self) -> Vec<Vec<Vec<NaiveDate>>> {
+        let first_day_of_week = self.resolved_first_day_of_week();
+
         (0..self.number_of_months)
-            .flat_map(|offset| {
-                days_in_month(self.current_year, self.current_month as u32 + offset as u32)
+            .map(|offset| {
+                days_in_month(
+                    self.current_year,
+                    self.current_month as u32 + offset as u32,
+                    first_day_of_week,
+                )
             })
             .collect()
     }
@@ -345,14 +394,9 @@ impl Calendar {
         cx: &mut ViewContext<Self>,
     ) -> impl IntoElement {
         h_flex()
-            .map(|this| match self.size {
-                Size::Small => this.size_7().rounded_sm(),
-                Size::Large => this.size_10().rounded_md(),
-                _ => this.size_9().rounded_md(),
-            })
+            .calendar_cell_size(self.size)
             .justify_center()
             .text_color(cx.theme().muted_foreground)
-            .text_sm()
             .child(week.into())
     }
 
@@ -367,11 +411,7 @@ impl Calendar {
     ) -> impl IntoElement + Styled + StatefulInteractiveElement {
         h_flex()
             .id(id.into())
-            .map(|this| match self.size {
-                Size::Small => this.size_7().rounded_md(),
-                Size::Large => this.size_10().rounded_lg(),
-                _ => this.size_9().rounded_lg(),
-            })
+            .calendar_cell_size(self.size)
             .justify_center()
             .cursor_pointer()
             .when(muted, |this| {
@@ -385,7 +425,7 @@ impl Calendar {
                 })
                 .text_color(cx.theme().accent_foreground)
             })
-            .when(!active, |this| {
+            .when(!active && !cx.theme().lite, |this| {
                 this.hover(|this| {
                     this.bg(cx.theme().accent)
                         .text_color(cx.theme().accent_foreground)
@@ -482,8 +522,8 @@ impl Calendar {
         let disabled = self.view_mode.is_month();
         let multiple_months = self.number_of_months > 1;
         let icon_size = match self.size {
+            Size::XSmall => Size::XSmall,
             Size::Small => Size::Small,
-            Size::Large => Size::Medium,
             _ => Size::Medium,
         };
 
@@ -550,8 +590,8 @@ impl Calendar {
                         h_flex()
                             .justify_center()
                             .map(|this| match self.size {
-                                Size::Small => this.gap_2(),
-                                Size::Large => this.gap_4(),
+                                Size::XSmall | Size::Small => this.gap_2(),
+                                Size::Large | Size::XLarge => this.gap_4(),
                                 _ => this.gap_3(),
                             })
                             .child(self.month_name(n))
@@ -576,7 +616,7 @@ impl Calendar {
     }
 
     fn render_days(&self, cx: &mut ViewContext<Self>) -> impl IntoElement {
-        let weeks = [
+        let mut weeks = [
             t!("Calendar.week.0"),
             t!("Calendar.week.1"),
             t!("Calendar.week.2"),
@@ -585,47 +625,80 @@ impl Calendar {
             t!("Calendar.week.5"),
             t!("Calendar.week.6"),
         ];
+        // The weeks are start from Sunday, rotate to the first day of the week.
+        weeks.rotate_left(days_from_first_day_of_week(
+            self.resolved_first_day_of_week(),
+            Weekday::Sun,
+        ) as usize);
+        let show_week_number = self.show_week_number;
 
         h_flex()
             .map(|this| match self.size {
+                Size::XSmall => this.gap_2().text_xs(),
                 Size::Small => this.gap_3().text_sm(),
                 Size::Large => this.gap_5().text_base(),
+                Size::XLarge => this.gap_5().text_lg(),
                 _ => this.gap_4().text_sm(),
             })
             .justify_between()
             .children(
                 self.days()
-                    .chunks(5)
+                    .into_iter()
                     .enumerate()
                     .map(|(offset_month, days)| {
                         v_flex()
                             .gap_0p5()
-                            .child(h_flex().gap_0p5().justify_between().children(
-                                weeks.iter().map(|week| self.render_week(week.clone(), cx)),
-                            ))
+                            .child(
+                                h_flex()
+                                    .gap_0p5()
+                                    .justify_between()
+                                    .when(show_week_number, |this| {
+                                        this.child(self.render_week(t!("Calendar.week_number"), cx))
+                                    })
+                                    .children(
+                                        weeks.iter().map(|week| self.render_week(week.clone(), cx)),
+                                    ),
+                            )
                             .children(days.iter().map(|week| {
-                                h_flex().gap_0p5().justify_between().children(
-                                    week.iter()
-                                        .enumerate()
-                                        .map(|(ix, d)| self.render_day(ix, d, offset_month, cx)),
-                                )
+                                h_flex()
+                                    .gap_0p5()
+                                    .justify_between()
+                                    .when(show_week_number, |this| {
+                                        // Every week has a Monday, use it's ISO week number.
+                                        let week_number = week
+                                            .iter()
+                                            .find(|d| d.weekday() == Weekday::Mon)
+                                            .map(|d| d.iso_week().week().to_string())
+                                            .unwrap_or_default();
+                                        this.child(self.render_week(week_number, cx))
+                                    })
+                                    .children(
+                                        week.iter().enumerate().map(|(ix, d)| {
+                                            self.render_day(ix, d, offset_month, cx)
+                                        }),
+                                    )
                             }))
                     }),
             )
     }
 
+    /// The spacing of the months and years grid, keep the same width as the days of a month.
+    fn grid_spacing<E: Styled>(&self, this: E) -> E {
+        let width = self.size.calendar_cell_size() * 7. + px(2.) * 6.;
+        match self.size {
+            Size::XSmall | Size::Small => this.mt_2().gap_y_2(),
+            Size::Large | Size::XLarge => this.mt_4().gap_y_4(),
+            _ => this.mt_3().gap_y_3(),
+        }
+        .w(width)
+    }
+
     fn render_months(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
         let months = self.months();
 
         h_flex()
-            .mt_3()
             .gap_0p5()
-            .gap_y_3()
-            .map(|this| match self.size {
-                Size::Small => this.mt_2().gap_y_2().w(px(208.)),
-                Size::Large => this.mt_4().gap_y_4().w(px(292.)),
-                _ => this.mt_3().gap_y_3().w(px(264.)),
-            })
+            .map(|this| self.grid_spacing(this))
             .justify_between()
             .flex_wrap()
             .children(
@@ -637,7 +710,6 @@ impl Calendar {
 
                         self.item_button(ix, month.to_string(), active, false, false, cx)
                             .w(relative(0.3))
-                            .text_sm()
                             .on_click(cx.listener(move |view, _, cx| {
                                 view.current_month = (ix + 1) as u8;
                                 view.set_view_mode(ViewMode::Day, cx);
@@ -654,11 +726,7 @@ impl Calendar {
         h_flex()
             .id("years")
             .gap_0p5()
-            .map(|this| match self.size {
-                Size::Small => this.mt_2().gap_y_2().w(px(208.)),
-                Size::Large => this.mt_4().gap_y_4().w(px(292.)),
-                _ => this.mt_3().gap_y_3().w(px(264.)),
-            })
+            .map(|this| self.grid_spacing(this))
             .justify_between()
             .flex_wrap()
             .children(
@@ -1,9 +1,9 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, Weekday};
 use gpui::{
-    anchored, deferred, div, prelude::FluentBuilder as _, px, AppContext, ElementId, EventEmitter,
-    FocusHandle, FocusableView, InteractiveElement as _, KeyBinding, Length, MouseButton,
-    ParentElement as _, Render, SharedString, StatefulInteractiveElement as _, Styled, View,
-    ViewContext, VisualContext as _,
+    canvas, deferred, div, prelude::FluentBuilder as _, px, AppContext, Bounds, ElementId,
+    EventEmitter, FocusHandle, FocusableView, InteractiveElement as _, Length, MouseButton,
+    ParentElement as _, Pixels, Render, SharedString, StatefulInteractiveElement as _, Styled,
+    View, ViewContext, VisualContext as _,
 };
 use rust_i18n::t;
 
@@ -12,6 +12,8 @@ use crate::{
     dropdown::Escape,
     h_flex,
     input::ClearButton,
+    keymap::{self, KeymapEntry},
+    primitives::placement::{anchored_to, Align, FloatingPlacement, Side},
     theme::ActiveTheme,
     v_flex, Icon, IconName, Sizable, Size, StyleSized as _, StyledExt as _,
 };
@@ -20,7 +22,7 @@ use super::calendar::{Calendar, CalendarEvent, Date};
 
 pub fn init(cx: &mut AppContext) {
     let context = Some("DatePicker");
-    cx.bind_keys([KeyBinding::new("escape", Escape, context)])
+    keymap::bind_keys(cx, [KeymapEntry::new("escape", Escape, context)])
 }
 
 #[derive(Clone)]
@@ -63,11 +65,15 @@ pub struct DatePicker {
     cleanable: bool,
     placeholder: Option<SharedString>,
     open: bool,
+    /// The bounds of the input to place the calendar.
+    bounds: Bounds<Pixels>,
     size: Size,
     width: Length,
     date_format: SharedString,
     calendar: View<Calendar>,
     number_of_months: usize,
+    first_day_of_week: Option<Weekday>,
+    show_week_number: bool,
     presets: Option<Vec<DateRangePreset>>,
 }
 
@@ -110,11 +116,14 @@ impl DatePicker {
             date,
             calendar,
             open: false,
+            bounds: Bounds::default(),
             size: Size::default(),
             width: Length::Auto,
             date_format: "%Y/%m/%d".into(),
             cleanable: false,
             number_of_months: 1,
+            first_day_of_week: None,
+            show_week_number: false,
             placeholder: None,
             presets: None,
         }
@@ -150,6 +159,18 @@ impl DatePicker {
         self
     }
 
+    /// Set the first day of the week of the calendar, default is the first day of the week of the current locale.
+    pub fn first_day_of_week(mut self, weekday: Weekday) -> Self {
+        self.first_day_of_week = Some(weekday);
+        self
+    }
+
+    /// Set true to show the ISO week number column in the calendar, default is false.
+    pub fn week_number(mut self, show: bool) -> Self {
+        self.show_week_number = show;
+        self
+    }
+
     /// Set preset ranges for the date picker.
     pub fn presets(mut self, presets: Vec<DateRangePreset>) -> Self {
         self.presets = Some(presets);
@@ -244,10 +265,18 @@ impl Render for DatePicker {
             .format(&self.date_format)
             .unwrap_or(placeholder.clone());
 
+        let preset_size = match self.size {
+            Size::XSmall => Size::XSmall,
+            _ => Size::Small,
+        };
+
         self.calendar.update(cx, |view, cx| {
             view.set_size(self.size, cx);
             view.set_number_of_months(self.number_of_months, cx);
+            view.set_first_day_of_week(self.first_day_of_week, cx);
+            view.set_week_number(self.show_week_number, cx);
         });
+        let view = cx.view().clone();
 
         div()
             .id(self.id.clone())
@@ -272,7 +301,7 @@ impl Render for DatePicker {
                     .border_1()
                     .border_color(cx.theme().input)
                     .rounded(px(cx.theme().radius))
-                    .when(cx.theme().shadow, |this| this.shadow_sm())
+                    .when(cx.theme().has_shadow(), |this| this.shadow_sm())
                     .cursor_pointer()
                     .overflow_hidden()
                     .input_text_size(self.size)
@@ -298,21 +327,33 @@ impl Render for DatePicker {
                                         .text_color(cx.theme().muted_foreground),
                                 )
                             }),
+                    )
+                    .child(
+                        canvas(
+                            move |bounds, cx| view.update(cx, |r, _| r.bounds = bounds),
+                            |_, _, _| {},
+                        )
+                        .absolute()
+                        .size_full(),
                     ),
             )
             .when(self.open, |this| {
+                let placement = FloatingPlacement::new(Side::Bottom, Align::Start);
                 this.child(
                     deferred(
-                        anchored().snap_to_window_with_margin(px(8.)).child(
+                        anchored_to(self.bounds, placement).child(
                             div()
                                 .track_focus(&self.focus_handle)
                                 .occlude()
-                                .mt_1p5()
                                 .rounded_lg()
-                                .p_3()
+                                .map(|this| match self.size {
+                                    Size::XSmall | Size::Small => this.p_2(),
+                                    Size::Large | Size::XLarge => this.p_4(),
+                                    _ => this.p_3(),
+                                })
                                 .border_1()
                                 .border_color(cx.theme().border)
-                                .shadow_lg()
+                                .when(cx.theme().has_shadow(), |this| this.shadow_lg())
                                 .rounded_lg()
                                 .bg(cx.theme().background)
                                 .on_mouse_up_out(
@@ -330,7 +371,7 @@ impl Render for DatePicker {
                                                     presets.into_iter().enumerate().map(
                                                         |(i, preset)| {
                                                             Button::new(("preset", i))
-                                                                .small()
+                                                                .with_size(preset_size)
                                                                 .ghost()
                                                                 .label(preset.label.clone())
                                                                 .on_click(cx.listener(
@@ -1,4 +1,4 @@
-use chrono::{Datelike, Duration, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
 trait NaiveDateExt {
     fn days_in_month(&self) -> i32;
@@ -28,7 +28,16 @@ impl NaiveDateExt for chrono::NaiveDate {
     }
 }
 
-pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
+/// Returns the number of days from the `first_day_of_week` to the `weekday`, range of 0..7.
+pub(crate) fn days_from_first_day_of_week(weekday: Weekday, first_day_of_week: Weekday) -> u32 {
+    (weekday.num_days_from_monday() + 7 - first_day_of_week.num_days_from_monday()) % 7
+}
+
+pub(crate) fn days_in_month(
+    year: i32,
+    month: u32,
+    first_day_of_week: Weekday,
+) -> Vec<Vec<NaiveDate>> {
     let mut year = year;
     let mut month = month;
     if month > 12 {
@@ -42,7 +51,7 @@ pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
 
     let date = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
     let num_days = date.days_in_month();
-    let start_weekday = date.weekday().num_days_from_sunday();
+    let start_weekday = days_from_first_day_of_week(date.weekday(), first_day_of_week);
 
     // Get the days in the month, 2023-02 will returns
     // "29|30|31| 1| 2| 3| 4",
@@ -50,8 +59,11 @@ pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
     // "12|13|14|15|16|17|18",
     // "19|20|21|22|23|24|25",
     // "26|27|28| 1| 2| 3| 4",
+    // At least 5 weeks, and 6 weeks if the month can't fit in.
+    let num_weeks = ((start_weekday as i32 + num_days + 6) / 7).max(5);
+
     let mut days = vec![];
-    for n in 0..5 {
+    for n in 0..num_weeks {
         let mut week_days = vec![];
         for weekday in 0..7 {
             let (mut y, mut m) = (year, month);
@@ -87,9 +99,9 @@ pub(crate) fn days_in_month(year: i32, month: u32) -> Vec<Vec<NaiveDate>> {
 
 #[cfg(test)]
 mod tests {
-    use chrono::{Datelike, NaiveDate};
+    use chrono::{Datelike, NaiveDate, Weekday};
 
-    use super::{days_in_month, NaiveDateExt};
+    use super::{days_from_first_day_of_week, days_in_month, NaiveDateExt};
 
     #[test]
     fn test_days_in_month() {
@@ -115,7 +127,16 @@ mod tests {
     fn test_days() {
         #[track_caller]
         fn assert_case(date: NaiveDate, expected: Vec<&str>) {
-            let out = days_in_month(date.year(), date.month())
+            assert_case_with_first_day(date, Weekday::Sun, expected)
+        }
+
+        #[track_caller]
+        fn assert_case_with_first_day(
+            date: NaiveDate,
+            first_day_of_week: Weekday,
+            expected: Vec<&str>,
+        ) {
+            let out = days_in_month(date.year(), date.month(), first_day_of_week)
                 .iter()
                 .map(|week| {
                     week.iter()
@@ -177,5 +198,39 @@ mod tests {
                 "26|27|28|3-1|3-2|3-3|3-4",
             ],
         );
+
+        assert_case_with_first_day(
+            NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(),
+            Weekday::Mon,
+            vec![
+                "7-29|7-30|7-31| 1| 2| 3| 4",
+                " 5| 6| 7| 8| 9|10|11",
+                "12|13|14|15|16|17|18",
+                "19|20|21|22|23|24|25",
+                "26|27|28|29|30|31|9-1",
+            ],
+        );
+        assert_case_with_first_day(
+            NaiveDate::from_ymd_opt(2024, 9, 1).unwrap(),
+            Weekday::Mon,
+            vec![
+                "8-26|8-27|8-28|8-29|8-30|8-31| 1",
+                " 2| 3| 4| 5| 6| 7| 8",
+                " 9|10|11|12|13|14|15",
+                "16|17|18|19|20|21|22",
+                "23|24|25|26|27|28|29",
+                "30|10-1|10-2|10-3|10-4|10-5|10-6",
+            ],
+        );
+    }
+
+    #[test]
+    fn test_days_from_first_day_of_week() {
+        assert_eq!(days_from_first_day_of_week(Weekday::Sun, Weekday::Sun), 0);
+        assert_eq!(days_from_first_day_of_week(Weekday::Mon, Weekday::Sun), 1);
+        assert_eq!(days_from_first_day_of_week(Weekday::Sun, Weekday::Mon), 6);
+        assert_eq!(days_from_first_day_of_week(Weekday::Mon, Weekday::Mon), 0);
+        assert_eq!(days_from_first_day_of_week(Weekday::Sat, Weekday::Sat), 0);
+        assert_eq!(days_from_first_day_of_week(Weekday::Fri, Weekday::Sat), 6);
     }
 }
@@ -0,0 +1,156 @@
+use gpui::{
+    div, point, prelude::FluentBuilder as _, px, InteractiveElement as _, IntoElement,
+    ParentElement as _, Pixels, Render, ScrollHandle, SharedString,
+    StatefulInteractiveElement as _, Styled, ViewContext,
+};
+
+use crate::{theme::ActiveTheme as _, v_flex};
+
+/// The offset from the top of the scroll container to consider a section is in view.
+const ACTIVE_OFFSET: Pixels = px(8.);
+
+/// An item of the [`Toc`], linked to a section in the scroll container.
+#[derive(Clone)]
+pub struct TocItem {
+    label: SharedString,
+    /// The child index of the section in the scroll container.
+    ix: usize,
+    level: usize,
+}
+
+impl TocItem {
+    /// Create a toc item, the `ix` is the child index of the section in the scroll container.
+    pub fn new(label: impl Into<SharedString>, ix: usize) -> Self {
+        Self {
+            label: label.into(),
+            ix,
+            level: 0,
+        }
+    }
+
+    /// Set the heading level of the item for indentation, default is 0.
+    pub fn level(mut self, level: usize) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+/// A table of contents for the sections inside a scroll container.
+///
+/// The scroll container must track the same [`ScrollHandle`] and have the sections as the direct children,
+/// the Toc will highlight the section currently in view while scrolling, and scroll to the section on click.
+///
+/// ```ignore
+/// let scroll_handle = ScrollHandle::new();
+/// let toc = cx.new_view(|_| {
+///     Toc::new(&scroll_handle).items(vec![TocItem::new("General", 0), TocItem::new("Account", 1)])
+/// });
+///
+/// div()
+///     .id("settings")
+///     .overflow_y_scroll()
+///     .track_scroll(&scroll_handle)
+///     .child(general_section)
+///     .child(account_section)
+/// ```
+pub struct Toc {
+    scroll_handle: ScrollHandle,
+    items: Vec<TocItem>,
+}
+
+impl Toc {
+    pub fn new(scroll_handle: &ScrollHandle) -> Self {
+        Self {
+            scroll_handle: scroll_handle.clone(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Set the items of the Toc.
+    pub fn items(mut self, items: impl IntoIterator<Item = TocItem>) -> Self {
+        self.items = items.into_iter().collect();
+        self
+    }
+
+    /// Set the items of the Toc, e.g.: the sections has been changed.
+    pub fn set_items(
+        &mut self,
+        items: impl IntoIterator<Item = TocItem>,
+        cx: &mut ViewContext<Self>,
+    ) {
+        self.items = items.into_iter().collect();
+        cx.notify();
+    }
+
+    /// Returns the index of the item that the section is currently in view.
+    ///
+    /// The last section that has scrolled to the top of the scroll container is active.
+    pub fn active_ix(&self) -> Option<usize> {
+        let top = self.scroll_handle.bounds().top() + ACTIVE_OFFSET;
+
+        let mut active_ix = None;
+        for (ix, item) in self.items.iter().enumerate() {
+            let Some(bounds) = self.scroll_handle.bounds_for_item(item.ix) else {
+                continue;
+            };
+
+            if bounds.top() > top {
+                break;
+            }
+            active_ix = Some(ix);
+        }
+
+        active_ix.or(if self.items.is_empty() { None } else { Some(0) })
+    }
+
+    /// Scroll the section of the item to the top of the scroll container.
+    pub fn scroll_to(&mut self, ix: usize, cx: &mut ViewContext<Self>) {
+        let Some(item) = self.items.get(ix) else {
+            return;
+        };
+        let Some(bounds) = self.scroll_handle.bounds_for_item(item.ix) else {
+            return;
+        };
+
+        let offset = self.scroll_handle.offset();
+        let top = self.scroll_handle.bounds().top();
+        self.scroll_handle
+            .set_offset(point(offset.x, offset.y - (bounds.top() - top)));
+        cx.notify();
+    }
+}
+
+impl Render for Toc {
+    fn render(&mut self, cx: &mut ViewContext<Self>) -> impl IntoElement {
+        let active_ix = self.active_ix();
+
+        v_flex()
+            .id("toc")
+            .border_l_1()
+            .border_color(cx.theme().border)
+            .children(self.items.iter().enumerate().map(|(ix, item)| {
+                let active = active_ix == Some(ix);
+
+                div()
+                    .id(("toc-item", ix))
+                    .ml(px(-1.))
+                    .py_1()
+                    .pr_3()
+                    .pl(px(12.) + px(12.) * item.level as f32)
+                    .text_sm()
+                    .border_l_1()
+                    .border_color(gpui::transparent_black())
+                    .text_color(cx.theme().muted_foreground)
+                    .cursor_pointer()
+                    .when(!cx.theme().lite, |this| {
+                        this.hover(|this| this.text_color(cx.theme().foreground))
+                    })
+                    .when(active, |this| {
+                        this.border_color(cx.theme().primary)
+                            .text_color(cx.theme().foreground)
+                    })
+                    .child(item.label.clone())
+                    .on_click(cx.listener(move |this, _, cx| this.scroll_to(ix, cx)))
+            }))
+    }
+}
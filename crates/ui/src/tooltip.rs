@@ -1,17 +1,45 @@
 use gpui::{
-    div, px, AnyView, IntoElement, ParentElement, Render, SharedString, Styled, ViewContext,
-    VisualContext, WindowContext,
+    div, prelude::FluentBuilder as _, px, Action, AnyView, IntoElement, ParentElement, Render,
+    SharedString, Styled, ViewContext, VisualContext, WindowContext,
 };
 
-use crate::theme::ActiveTheme;
+use crate::{h_flex, keymap::Keymap, popup_menu::key_shortcut, theme::ActiveTheme};
 
 pub struct Tooltip {
     text: SharedString,
+    key: Option<SharedString>,
 }
 
 impl Tooltip {
     pub fn new(text: impl Into<SharedString>, cx: &mut WindowContext) -> AnyView {
-        cx.new_view(|_| Self { text: text.into() }).into()
+        cx.new_view(|_| Self {
+            text: text.into(),
+            key: None,
+        })
+        .into()
+    }
+
+    /// Create a tooltip with the current keystrokes of the action from the [`Keymap`].
+    pub fn with_action(
+        text: impl Into<SharedString>,
+        action: &dyn Action,
+        context: Option<&str>,
+        cx: &mut WindowContext,
+    ) -> AnyView {
+        let key = Keymap::keystrokes_for_action(action, context, cx).map(|keystrokes| {
+            keystrokes
+                .into_iter()
+                .map(key_shortcut)
+                .collect::<Vec<_>>()
+                .join(" ")
+                .into()
+        });
+
+        cx.new_view(|_| Self {
+            text: text.into(),
+            key,
+        })
+        .into()
     }
 }
 
@@ -27,12 +55,17 @@ impl Render for Tooltip {
                 .bg(cx.theme().popover)
                 .border_1()
                 .border_color(cx.theme().border)
-                .shadow_md()
+                .when(cx.theme().has_shadow(), |this| this.shadow_md())
                 .rounded(px(6.))
                 .py_0p5()
                 .px_2()
                 .text_sm()
-                .child(self.text.clone()),
+                .child(h_flex().gap_2().child(self.text.clone()).when_some(
+                    self.key.clone(),
+                    |this, key| {
+                        this.child(div().text_color(cx.theme().muted_foreground).child(key))
+                    },
+                )),
         )
     }
 }
@@ -0,0 +1,291 @@
+use std::{any::TypeId, cell::RefCell, rc::Rc, time::Duration};
+
+use gpui::{
+    AnyWindowHandle, ClickEvent, ElementId, Global, SharedString, View, ViewContext, WindowContext,
+};
+use rust_i18n::t;
+use smol::Timer;
+
+use crate::{
+    history::{History, HistoryItem},
+    notification::{Notification, NotificationId, NotificationSettings},
+    ContextModal as _,
+};
+
+/// The default time to wait before committing the action.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+type Callback = Box<dyn FnOnce(&mut WindowContext)>;
+
+struct Callbacks {
+    on_undo: Option<Callback>,
+    on_commit: Option<Callback>,
+}
+
+/// The pending actions of the shown [`UndoToast`]s, the last one is reverted by [`undo_last`].
+#[derive(Default)]
+struct PendingUndos {
+    next_id: usize,
+    pending: Vec<PendingUndo>,
+}
+
+impl Global for PendingUndos {}
+
+/// A notification with the `Undo` button and the countdown for the soft actions,
+/// e.g.: deleting a watchlist, the action is committed after the timeout, or reverted if
+/// the `Undo` is clicked.
+///
+/// Perform the action softly (e.g.: hide the item) before showing the toast, revert it in the
+/// `on_undo`, and do the real work in the `on_commit`.
+///
+/// ```ignore
+/// self.hide_watchlist(id, cx);
+/// let view = cx.view().downgrade();
+/// UndoToast::new("Watchlist deleted")
+///     .on_undo({
+///         let view = view.clone();
+///         move |cx| _ = view.update(cx, |this, cx| this.restore_watchlist(id, cx))
+///     })
+///     .on_commit(move |cx| _ = view.update(cx, |this, cx| this.delete_watchlist(id, cx)))
+///     .show(cx);
+/// ```
+///
+/// The pending actions of the window are committed before the window is closed.
+pub struct UndoToast {
+    message: SharedString,
+    timeout: Duration,
+    source: Option<SharedString>,
+    commit_if_suppressed: bool,
+    on_undo: Option<Callback>,
+    on_commit: Option<Callback>,
+}
+
+impl UndoToast {
+    pub fn new(message: impl Into<SharedString>) -> Self {
+        Self {
+            message: message.into(),
+            timeout: DEFAULT_TIMEOUT,
+            source: None,
+            commit_if_suppressed: true,
+            on_undo: None,
+            on_commit: None,
+        }
+    }
+
+    /// Create the toast to undo the last change pushed to the [`History`] of the view,
+    /// the undone items are passed to the `apply` to revert them.
+    ///
+    /// Nothing is undone if the change is no longer the last one of the history,
+    /// e.g.: it's undone by the shortcut, or followed by another change.
+    ///
+    /// ```ignore
+    /// self.history.push(Change::RemoveRow(ix, row));
+    /// UndoToast::history(
+    ///     "Row deleted",
+    ///     cx.view(),
+    ///     |this| &mut this.history,
+    ///     |this, changes, cx| this.revert_changes(changes, cx),
+    ///     cx,
+    /// )
+    /// .show(cx);
+    /// ```
+    pub fn history<V, I>(
+        message: impl Into<SharedString>,
+        view: &View<V>,
+        history: impl Fn(&mut V) -> &mut History<I> + 'static,
+        apply: impl FnOnce(&mut V, Vec<I>, &mut ViewContext<V>) + 'static,
+        cx: &mut WindowContext,
+    ) -> Self
+    where
+        V: 'static,
+        I: HistoryItem + 'static,
+    {
+        let version = view.update(cx, |view, _| history(view).undo_version());
+        let view = view.downgrade();
+
+        Self::new(message).on_undo(move |cx| {
+            _ = view.update(cx, |view, cx| {
+                let history = history(view);
+                if version.is_none() || history.undo_version() != version {
+                    return;
+                }
+                if let Some(changes) = history.undo() {
+                    apply(view, changes, cx);
+                }
+            });
+        })
+    }
+
+    /// Set the time to wait before committing the action, default is 5s.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the source of the notification, see [`Notification::source`].
+    pub fn source(mut self, source: impl Into<SharedString>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Set whether to commit the action immediately if the notification is suppressed
+    /// by the do-not-disturb or the muted source, default is `true`.
+    ///
+    /// If false, the action is committed after the timeout, and can be reverted by [`undo_last`].
+    pub fn commit_if_suppressed(mut self, commit_if_suppressed: bool) -> Self {
+        self.commit_if_suppressed = commit_if_suppressed;
+        self
+    }
+
+    /// Set the callback to revert the soft action.
+    pub fn on_undo(mut self, on_undo: impl FnOnce(&mut WindowContext) + 'static) -> Self {
+        self.on_undo = Some(Box::new(on_undo));
+        self
+    }
+
+    /// Set the callback to commit the action after the timeout.
+    pub fn on_commit(mut self, on_commit: impl FnOnce(&mut WindowContext) + 'static) -> Self {
+        self.on_commit = Some(Box::new(on_commit));
+        self
+    }
+
+    /// Show the notification, returns the [`PendingUndo`] to undo or commit it manually.
+    pub fn show(self, cx: &mut WindowContext) -> PendingUndo {
+        let window = cx.window_handle();
+        let undos = cx.default_global::<PendingUndos>();
+        let pending = PendingUndo {
+            id: undos.next_id,
+            window,
+            callbacks: Rc::new(RefCell::new(Some(Callbacks {
+                on_undo: self.on_undo,
+                on_commit: self.on_commit,
+            }))),
+        };
+        undos.next_id += 1;
+        undos.pending.push(pending.clone());
+
+        let mut note = Notification::new(self.message)
+            .id1::<UndoToast>(pending.id)
+            .duration(self.timeout)
+            .countdown(true)
+            .action(t!("Undo.Undo").to_string(), {
+                let pending = pending.clone();
+                move |cx| {
+                    // The notification is dismissed by itself.
+                    pending.settle(true, cx);
+                }
+            });
+        if let Some(source) = self.source {
+            note = note.source(source);
+        }
+
+        if NotificationSettings::is_suppressed(&note, cx) {
+            if self.commit_if_suppressed {
+                pending.settle(false, cx);
+                return pending;
+            }
+        } else {
+            cx.push_notification(note);
+        }
+
+        let timeout = self.timeout;
+        cx.spawn({
+            let pending = pending.clone();
+            |mut cx| async move {
+                Timer::after(timeout).await;
+                // It fails if the window is closed, the action has been committed before closing.
+                _ = cx.update(|cx| pending.settle(false, cx));
+            }
+        })
+        .detach();
+
+        pending
+    }
+}
+
+/// The handle of the action shown by the [`UndoToast`].
+#[derive(Clone)]
+pub struct PendingUndo {
+    id: usize,
+    window: AnyWindowHandle,
+    callbacks: Rc<RefCell<Option<Callbacks>>>,
+}
+
+impl PendingUndo {
+    /// Returns true if the action is neither undone nor committed.
+    pub fn is_pending(&self) -> bool {
+        self.callbacks.borrow().is_some()
+    }
+
+    /// Revert the action and close the notification, returns false if it's not pending.
+    pub fn undo(&self, cx: &mut WindowContext) -> bool {
+        self.settle(true, cx) && self.dismiss(cx)
+    }
+
+    /// Commit the action now and close the notification, returns false if it's not pending,
+    /// e.g.: before the window is closed.
+    pub fn commit(&self, cx: &mut WindowContext) -> bool {
+        self.settle(false, cx) && self.dismiss(cx)
+    }
+
+    /// Call the `on_undo` or the `on_commit` once, returns false if it's already settled.
+    fn settle(&self, undo: bool, cx: &mut WindowContext) -> bool {
+        let Some(callbacks) = self.callbacks.borrow_mut().take() else {
+            return false;
+        };
+        cx.default_global::<PendingUndos>()
+            .pending
+            .retain(|pending| pending.id != self.id);
+
+        let callback = if undo {
+            callbacks.on_undo
+        } else {
+            callbacks.on_commit
+        };
+        if let Some(callback) = callback {
+            callback(cx);
+        }
+        true
+    }
+
+    fn dismiss(&self, cx: &mut WindowContext) -> bool {
+        let id = NotificationId::from((TypeId::of::<UndoToast>(), ElementId::from(self.id)));
+        for note in cx.notifications().iter() {
+            if note.read(cx).id == id {
+                note.update(cx, |note, cx| note.dismiss(&ClickEvent::default(), cx));
+            }
+        }
+        true
+    }
+}
+
+/// Revert the last pending action of the [`UndoToast`]s, e.g.: on the `Undo` action of the app,
+/// returns false if there is no pending action.
+pub fn undo_last(cx: &mut WindowContext) -> bool {
+    let Some(pending) = cx
+        .try_global::<PendingUndos>()
+        .and_then(|undos| undos.pending.last().cloned())
+    else {
+        return false;
+    };
+    pending.undo(cx)
+}
+
+/// Commit the pending actions of the window, called before the window is closed.
+pub(crate) fn commit_window(cx: &mut WindowContext) {
+    let window = cx.window_handle();
+    let Some(pendings) = cx.try_global::<PendingUndos>().map(|undos| {
+        undos
+            .pending
+            .iter()
+            .filter(|pending| pending.window == window)
+            .cloned()
+            .collect::<Vec<_>>()
+    }) else {
+        return;
+    };
+
+    for pending in pendings {
+        pending.commit(cx);
+    }
+}
@@ -0,0 +1,189 @@
+use std::{
+    rc::{Rc, Weak},
+    sync::Arc,
+};
+
+use anyhow::Result;
+use gpui::{
+    px, AnyElement, IntoElement, ParentElement as _, SharedString, Styled, Task, WindowContext,
+};
+use rust_i18n::t;
+
+use crate::{
+    button::{Button, ButtonVariants as _},
+    dock::PanelView,
+    h_flex,
+    notification::Notification,
+    theme::ActiveTheme as _,
+    v_flex, ContextModal as _, Icon, IconName, Root, Sizable as _,
+};
+
+pub(crate) type UnsavedProvider = dyn Fn(&mut WindowContext) -> Vec<UnsavedItem>;
+
+/// The handle of the provider registered by [`Root::register_unsaved_provider`],
+/// the provider will be unregistered when the handle is dropped.
+#[must_use]
+pub struct UnsavedProviderHandle {
+    provider: Rc<UnsavedProvider>,
+}
+
+impl UnsavedProviderHandle {
+    pub(crate) fn new(provider: Rc<UnsavedProvider>) -> Self {
+        Self { provider }
+    }
+
+    pub(crate) fn downgrade(&self) -> Weak<UnsavedProvider> {
+        Rc::downgrade(&self.provider)
+    }
+
+    /// Keep the provider registered for the lifetime of the window.
+    ///
+    /// The provider is held by the [`Root`] of the window, and dropped when the window closes.
+    pub fn detach(self, cx: &mut WindowContext) {
+        let provider = self.provider;
+        Root::update(cx, move |root, _| {
+            root.detached_unsaved_providers.push(provider)
+        });
+    }
+}
+
+/// An item has unsaved changes, used to list in the confirm dialog before closing.
+#[derive(Clone)]
+pub struct UnsavedItem {
+    title: Rc<dyn Fn(&WindowContext) -> AnyElement>,
+    save: Rc<dyn Fn(&mut WindowContext) -> Task<Result<()>>>,
+}
+
+impl UnsavedItem {
+    /// Create a new unsaved item with the title and the save callback.
+    pub fn new(
+        title: impl Into<SharedString>,
+        save: impl Fn(&mut WindowContext) -> Task<Result<()>> + 'static,
+    ) -> Self {
+        let title: SharedString = title.into();
+        Self {
+            title: Rc::new(move |_| title.clone().into_any_element()),
+            save: Rc::new(save),
+        }
+    }
+
+    /// Create an unsaved item from a dirty [`PanelView`], the panel title will be used.
+    pub fn panel(panel: Arc<dyn PanelView>) -> Self {
+        Self {
+            title: Rc::new({
+                let panel = panel.clone();
+                move |cx| panel.title(cx)
+            }),
+            save: Rc::new(move |cx| panel.save(cx)),
+        }
+    }
+
+    /// Save the changes of this item.
+    pub fn save(&self, cx: &mut WindowContext) -> Task<Result<()>> {
+        (self.save)(cx)
+    }
+}
+
+/// Open a confirm modal to list the unsaved items with `Save`, `Discard` and `Cancel` actions.
+///
+/// The `on_close` will be called after all items are saved or the changes are discarded,
+/// if any item failed to save, the modal will keep open and show the error notification.
+///
+/// Do nothing if the confirm modal of the window is already open.
+pub fn confirm_unsaved(
+    items: Vec<UnsavedItem>,
+    on_close: impl Fn(&mut WindowContext) + 'static,
+    cx: &mut WindowContext,
+) {
+    // The flag may be stale if the modal was closed by `close_all_modals`.
+    if Root::read(cx).confirming_unsaved && cx.has_active_modal() {
+        return;
+    }
+    set_confirming(true, cx);
+
+    let on_close = Rc::new(move |cx: &mut WindowContext| {
+        set_confirming(false, cx);
+        on_close(cx);
+    });
+
+    cx.open_modal(move |modal, cx| {
+        modal
+            .width(px(420.))
+            .title(t!("Unsaved.Title").to_string())
+            .on_close(|_, cx| set_confirming(false, cx))
+            .child(
+                v_flex()
+                    .gap_3()
+                    .child(t!("Unsaved.Message").to_string())
+                    .child(v_flex().gap_1().children(items.iter().map(|item| {
+                        h_flex()
+                            .gap_2()
+                            .text_color(cx.theme().muted_foreground)
+                            .child(Icon::new(IconName::Dash).small())
+                            .child((item.title)(cx))
+                    }))),
+            )
+            .footer(
+                h_flex()
+                    .gap_2()
+                    .justify_end()
+                    .child(
+                        Button::new("unsaved-cancel")
+                            .label(t!("Unsaved.Cancel").to_string())
+                            .on_click(|_, cx| {
+                                set_confirming(false, cx);
+                                cx.close_modal();
+                            }),
+                    )
+                    .child(
+                        Button::new("unsaved-discard")
+                            .danger()
+                            .label(t!("Unsaved.Discard").to_string())
+                            .on_click({
+                                let on_close = on_close.clone();
+                                move |_, cx| {
+                                    cx.close_modal();
+                                    on_close(cx);
+                                }
+                            }),
+                    )
+                    .child(
+                        Button::new("unsaved-save")
+                            .primary()
+                            .label(t!("Unsaved.Save").to_string())
+                            .on_click({
+                                let items = items.clone();
+                                let on_close = on_close.clone();
+                                move |_, cx| {
+                                    let tasks =
+                                        items.iter().map(|item| item.save(cx)).collect::<Vec<_>>();
+                                    let on_close = on_close.clone();
+
+                                    cx.spawn(|mut cx| async move {
+                                        for task in tasks {
+                                            if let Err(err) = task.await {
+                                                _ = cx.update(|cx| {
+                                                    cx.push_notification(Notification::error(
+                                                        err.to_string(),
+                                                    ));
+                                                });
+                                                return;
+                                            }
+                                        }
+
+                                        _ = cx.update(|cx| {
+                                            cx.close_modal();
+                                            on_close(cx);
+                                        });
+                                    })
+                                    .detach();
+                                }
+                            }),
+                    ),
+            )
+    })
+}
+
+fn set_confirming(confirming: bool, cx: &mut WindowContext) {
+    Root::update(cx, move |root, _| root.confirming_unsaved = confirming);
+}
@@ -0,0 +1,350 @@
+use std::{marker::PhantomData, time::Duration};
+
+use async_tungstenite::{
+    async_tls::client_async_tls,
+    tungstenite::{client::IntoClientRequest as _, Message},
+};
+use gpui::{EventEmitter, ModelContext, SharedString, Task};
+use serde::{de::DeserializeOwned, Serialize};
+use smol::{
+    channel::{Receiver, Sender},
+    net::TcpStream,
+    stream::StreamExt as _,
+    Timer,
+};
+
+pub use crate::connection::ConnectionState;
+
+/// The interval to deliver the batched messages to the Model, about one frame.
+const BATCH_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The exponential backoff to reconnect, with the random jitter to avoid all the clients
+/// reconnecting at the same time after the server restarts.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    /// The delay of the first attempt.
+    pub min: Duration,
+    pub max: Duration,
+    pub factor: f64,
+    /// The ratio of the random jitter of the delay, `0.0..1.0`.
+    pub jitter: f64,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            min: Duration::from_millis(500),
+            max: Duration::from_secs(30),
+            factor: 2.,
+            jitter: 0.3,
+        }
+    }
+}
+
+impl Backoff {
+    /// Returns the delay before the `attempt` (starts from 1), the `random` is in `0.0..1.0`.
+    fn delay(&self, attempt: u32, random: f64) -> Duration {
+        let max = self.max.as_secs_f64();
+        let exp = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let base = (self.min.as_secs_f64() * self.factor.powi(exp)).min(max);
+        let jitter = base * self.jitter * (random * 2. - 1.);
+
+        Duration::from_secs_f64((base + jitter).clamp(0., max))
+    }
+}
+
+enum Incoming {
+    Message(String),
+    State(ConnectionState),
+}
+
+enum Event {
+    Send(Result<String, smol::channel::RecvError>),
+    Receive(Option<Result<Message, async_tungstenite::tungstenite::Error>>),
+}
+
+async fn connect(
+    url: &str,
+) -> anyhow::Result<
+    async_tungstenite::WebSocketStream<async_tungstenite::async_tls::ClientStream<TcpStream>>,
+> {
+    let request = url.into_client_request()?;
+    let uri = request.uri();
+    let host = uri
+        .host()
+        .ok_or_else(|| anyhow::anyhow!("missing host in {}", url))?;
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("wss") {
+            443
+        } else {
+            80
+        });
+
+    let stream = TcpStream::connect((host, port)).await?;
+    let (socket, _) = client_async_tls(request, stream).await?;
+    Ok(socket)
+}
+
+/// Keep the connection until the `outgoing` is closed, reconnect with the `backoff` if the connection is lost.
+///
+/// The `incoming` is bounded, so the reading pauses when it's full, to apply the backpressure to the server.
+async fn run(
+    url: String,
+    backoff: Backoff,
+    outgoing: Receiver<String>,
+    incoming: Sender<Incoming>,
+) {
+    let mut attempt = 0;
+    loop {
+        if attempt == 0 {
+            _ = incoming
+                .send(Incoming::State(ConnectionState::Connecting))
+                .await;
+        }
+
+        if let Ok(mut socket) = connect(&url).await {
+            attempt = 0;
+            _ = incoming
+                .send(Incoming::State(ConnectionState::Connected))
+                .await;
+
+            loop {
+                let event = smol::future::or(async { Event::Send(outgoing.recv().await) }, async {
+                    Event::Receive(socket.next().await)
+                })
+                .await;
+
+                match event {
+                    Event::Send(Ok(text)) => {
+                        if socket.send(Message::text(text)).await.is_err() {
+                            break;
+                        }
+                    }
+                    Event::Send(Err(_)) => {
+                        _ = socket.close(None).await;
+                        return;
+                    }
+                    Event::Receive(Some(Ok(Message::Text(text)))) => {
+                        let message = Incoming::Message(text.to_string());
+                        if incoming.send(message).await.is_err() {
+                            return;
+                        }
+                    }
+                    Event::Receive(Some(Ok(Message::Close(_)))) | Event::Receive(None) => break,
+                    Event::Receive(Some(Err(_))) => break,
+                    // The ping / pong are replied by the socket.
+                    Event::Receive(Some(Ok(_))) => {}
+                }
+            }
+        }
+
+        if outgoing.is_closed() || incoming.is_closed() {
+            return;
+        }
+
+        attempt += 1;
+        let delay = backoff.delay(attempt, fastrand::f64());
+        _ = incoming
+            .send(Incoming::State(ConnectionState::Reconnecting {
+                attempt,
+                delay,
+            }))
+            .await;
+        Timer::after(delay).await;
+    }
+}
+
+pub enum WebSocketEvent<T> {
+    /// The messages received since the last delivery, in the receiving order.
+    Messages(Vec<T>),
+    StateChanged(ConnectionState),
+    /// Failed to decode the message as JSON, the message is skipped.
+    InvalidMessage(SharedString),
+}
+
+/// A WebSocket data source that keeps the connection on the background, decodes the JSON text messages
+/// as `T`, and delivers them to the Model in batches, at most once per frame.
+///
+/// - The connection is reconnected with the [`Backoff`] after lost.
+/// - The received messages are buffered up to the `buffer_size`, the reading from the socket pauses
+///   when the buffer is full, so a slow UI applies the backpressure to the server instead of the memory.
+/// - The connection is closed when the Model is dropped.
+/// - Use the [`ConnectionIndicator`](crate::connection::ConnectionIndicator) to display the [`WebSocket::state`].
+///
+/// ```ignore
+/// let socket = cx.new_model(|cx| {
+///     let mut socket = WebSocket::<Quote>::new("wss://example.com/quotes").max_batch(500);
+///     socket.connect(cx);
+///     socket
+/// });
+/// cx.subscribe(&socket, |this, _, event: &WebSocketEvent<Quote>, cx| match event {
+///     WebSocketEvent::Messages(quotes) => this.apply_quotes(quotes, cx),
+///     _ => cx.notify(),
+/// })
+/// .detach();
+/// ```
+pub struct WebSocket<T> {
+    url: SharedString,
+    backoff: Backoff,
+    buffer_size: usize,
+    max_batch: usize,
+    state: ConnectionState,
+    outgoing: Option<Sender<String>>,
+    _tasks: Vec<Task<()>>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> EventEmitter<WebSocketEvent<T>> for WebSocket<T> where T: DeserializeOwned + 'static {}
+
+impl<T> WebSocket<T>
+where
+    T: DeserializeOwned + 'static,
+{
+    /// Create a WebSocket with the `ws://` or `wss://` url, call [`WebSocket::connect`] to start.
+    pub fn new(url: impl Into<SharedString>) -> Self {
+        Self {
+            url: url.into(),
+            backoff: Backoff::default(),
+            buffer_size: 4096,
+            max_batch: 1024,
+            state: ConnectionState::Disconnected,
+            outgoing: None,
+            _tasks: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Set the backoff to reconnect.
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Set the max number of the received messages waiting for delivering, default: 4096
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        self
+    }
+
+    /// Set the max number of the messages in a [`WebSocketEvent::Messages`], default: 1024
+    pub fn max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch.max(1);
+        self
+    }
+
+    pub fn url(&self) -> &SharedString {
+        &self.url
+    }
+
+    pub fn state(&self) -> &ConnectionState {
+        &self.state
+    }
+
+    /// Start the connection, the previous connection is closed if any.
+    pub fn connect(&mut self, cx: &mut ModelContext<Self>) {
+        let (outgoing_tx, outgoing_rx) = smol::channel::unbounded();
+        let (incoming_tx, incoming_rx) = smol::channel::bounded(self.buffer_size);
+        let url = self.url.to_string();
+        let backoff = self.backoff;
+        let max_batch = self.max_batch;
+
+        let connection =
+            cx.background_executor()
+                .spawn(run(url, backoff, outgoing_rx, incoming_tx));
+        let delivery = cx.spawn(|this, mut cx| async move {
+            while let Ok(first) = incoming_rx.recv().await {
+                let mut batch = vec![first];
+                while batch.len() < max_batch {
+                    match incoming_rx.try_recv() {
+                        Ok(incoming) => batch.push(incoming),
+                        Err(_) => break,
+                    }
+                }
+
+                let result = this.update(&mut cx, |this, cx| this.deliver(batch, cx));
+                if result.is_err() {
+                    break;
+                }
+                Timer::after(BATCH_INTERVAL).await;
+            }
+        });
+
+        self.outgoing = Some(outgoing_tx);
+        self._tasks = vec![connection, delivery];
+    }
+
+    /// Close the connection.
+    pub fn close(&mut self, cx: &mut ModelContext<Self>) {
+        self.outgoing = None;
+        self._tasks.clear();
+        self.set_state(ConnectionState::Disconnected, cx);
+    }
+
+    /// Send the text message, it's queued if the connection is not ready.
+    ///
+    /// Returns false if not connected by [`WebSocket::connect`].
+    pub fn send(&self, text: impl Into<String>) -> bool {
+        self.outgoing
+            .as_ref()
+            .map_or(false, |outgoing| outgoing.try_send(text.into()).is_ok())
+    }
+
+    /// Send the value as the JSON text message.
+    pub fn send_json(&self, value: &impl Serialize) -> anyhow::Result<bool> {
+        Ok(self.send(serde_json::to_string(value)?))
+    }
+
+    fn set_state(&mut self, state: ConnectionState, cx: &mut ModelContext<Self>) {
+        if self.state != state {
+            self.state = state;
+            cx.emit(WebSocketEvent::StateChanged(state));
+            cx.notify();
+        }
+    }
+
+    fn deliver(&mut self, batch: Vec<Incoming>, cx: &mut ModelContext<Self>) {
+        let mut messages = Vec::with_capacity(batch.len());
+        for incoming in batch {
+            match incoming {
+                Incoming::Message(text) => match serde_json::from_str(&text) {
+                    Ok(message) => messages.push(message),
+                    Err(_) => cx.emit(WebSocketEvent::InvalidMessage(text.into())),
+                },
+                Incoming::State(state) => {
+                    // Deliver the messages received before the state changed first.
+                    if !messages.is_empty() {
+                        cx.emit(WebSocketEvent::Messages(std::mem::take(&mut messages)));
+                    }
+                    self.set_state(state, cx);
+                }
+            }
+        }
+
+        if !messages.is_empty() {
+            cx.emit(WebSocketEvent::Messages(messages));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Backoff;
+
+    #[test]
+    fn test_backoff_delay() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(1, 0.5), Duration::from_millis(500));
+        assert_eq!(backoff.delay(2, 0.5), Duration::from_secs(1));
+        assert_eq!(backoff.delay(4, 0.5), Duration::from_secs(4));
+        assert_eq!(backoff.delay(20, 0.5), Duration::from_secs(30));
+        assert_eq!(backoff.delay(u32::MAX, 0.5), Duration::from_secs(30));
+
+        // The jitter is in the range of `delay * (1 ± jitter)`, and never exceeds the max.
+        assert_eq!(backoff.delay(1, 0.), Duration::from_millis(350));
+        assert_eq!(backoff.delay(1, 1.), Duration::from_millis(650));
+        assert_eq!(backoff.delay(20, 1.), Duration::from_secs(30));
+    }
+}
@@ -1,22 +1,44 @@
 use std::{ops::Deref, rc::Rc};
 
+use serde::Serialize;
+use smol::channel::Sender;
 use wry::{
     dpi::{self, LogicalSize},
-    Rect,
+    PageLoadEvent, Rect,
 };
 
 use gpui::{
     canvas, div, Bounds, ContentMask, DismissEvent, Element, ElementId, EventEmitter, FocusHandle,
     FocusableView, GlobalElementId, Hitbox, InteractiveElement, IntoElement, LayoutId,
-    MouseDownEvent, ParentElement as _, Pixels, Render, Size, Style, Styled as _, View,
-    WindowContext,
+    MouseDownEvent, ParentElement as _, Pixels, Render, SharedString, Size, Style, Styled as _,
+    View, ViewContext, WindowContext,
 };
 
+/// The events of the [`WebView`], the navigation events are only emitted when the webview is
+/// created by [`WebView::build`].
+#[derive(Debug, Clone)]
+pub enum WebViewEvent {
+    /// The page has been loaded, with the url.
+    Navigated(SharedString),
+    /// The document title has been changed.
+    TitleChanged(SharedString),
+    /// Failed to load the url, with the error message.
+    LoadError(SharedString),
+}
+
+/// Internal events sent from the wry handlers, they may be called outside of the gpui context.
+enum BridgeEvent {
+    Message(SharedString),
+    Event(WebViewEvent),
+}
+
 pub struct WebView {
     focus_handle: FocusHandle,
     webview: Rc<wry::WebView>,
     visible: bool,
     bounds: Bounds<Pixels>,
+    bridge: Option<Sender<BridgeEvent>>,
+    on_message: Option<Rc<dyn Fn(&SharedString, &mut ViewContext<Self>)>>,
 }
 
 impl Drop for WebView {
@@ -34,9 +56,86 @@ impl WebView {
             visible: true,
             bounds: Bounds::default(),
             webview: Rc::new(webview),
+            bridge: None,
+            on_message: None,
         }
     }
 
+    /// Build a webview from the wry builder with the message bridge and navigation events.
+    ///
+    /// - The page can send messages by `window.ipc.postMessage(string)`, see [`WebView::on_message`].
+    /// - The messages sent by [`WebView::post_message`] can be received in the page by
+    ///   `window.addEventListener("message", (e) => e.data)`.
+    /// - The [`WebViewEvent`] will be emitted when the page navigated or the title changed.
+    pub fn build(builder: wry::WebViewBuilder, cx: &mut ViewContext<Self>) -> anyhow::Result<Self> {
+        let (tx, rx) = smol::channel::unbounded();
+
+        let webview = builder
+            .with_ipc_handler({
+                let tx = tx.clone();
+                move |req| {
+                    _ = tx.try_send(BridgeEvent::Message(req.body().clone().into()));
+                }
+            })
+            .with_on_page_load_handler({
+                let tx = tx.clone();
+                move |event, url| {
+                    if let PageLoadEvent::Finished = event {
+                        _ = tx.try_send(BridgeEvent::Event(WebViewEvent::Navigated(url.into())));
+                    }
+                }
+            })
+            .with_document_title_changed_handler({
+                let tx = tx.clone();
+                move |title| {
+                    _ = tx.try_send(BridgeEvent::Event(WebViewEvent::TitleChanged(title.into())));
+                }
+            })
+            .build_as_child(&cx.raw_window_handle())?;
+
+        cx.spawn(|view, mut cx| async move {
+            while let Ok(event) = rx.recv().await {
+                let result = cx.update(|cx| {
+                    view.update(cx, |view, cx| match event {
+                        BridgeEvent::Message(message) => {
+                            if let Some(on_message) = view.on_message.clone() {
+                                on_message(&message, cx);
+                            }
+                        }
+                        BridgeEvent::Event(event) => cx.emit(event),
+                    })
+                });
+                if !matches!(result, Ok(Ok(_))) {
+                    break;
+                }
+            }
+        })
+        .detach();
+
+        let mut this = Self::new(cx, webview);
+        this.bridge = Some(tx);
+        Ok(this)
+    }
+
+    /// Set the handler to receive the messages sent by `window.ipc.postMessage` from the page.
+    pub fn on_message(
+        mut self,
+        handler: impl Fn(&SharedString, &mut ViewContext<Self>) + 'static,
+    ) -> Self {
+        self.on_message = Some(Rc::new(handler));
+        self
+    }
+
+    /// Post a message to the page, the message will be serialized as JSON, and dispatched
+    /// as a `message` event on the `window`.
+    pub fn post_message(&self, message: &impl Serialize) -> anyhow::Result<()> {
+        let data = serde_json::to_string(message)?;
+        Ok(self.webview.evaluate_script(&format!(
+            "window.dispatchEvent(new MessageEvent('message', {{ data: {} }}));",
+            data
+        ))?)
+    }
+
     pub fn show(&mut self) {
         let _ = self.webview.set_visible(true);
     }
@@ -54,13 +153,25 @@ impl WebView {
         self.bounds
     }
 
-    /// Go back in the webview history.
-    pub fn back(&mut self) -> anyhow::Result<()> {
-        Ok(self.webview.evaluate_script("history.back();")?)
+    /// Go back in the webview history, the [`WebViewEvent::LoadError`] will be emitted if failed.
+    pub fn back(&mut self, cx: &mut ViewContext<Self>) {
+        if let Err(err) = self.webview.evaluate_script("history.back();") {
+            Self::emit_load_error(err, cx);
+        }
+    }
+
+    /// Load the url, the [`WebViewEvent::LoadError`] will be emitted if failed.
+    pub fn load_url(&mut self, url: &str, cx: &mut ViewContext<Self>) {
+        if let Err(err) = self.webview.load_url(url) {
+            Self::emit_load_error(err, cx);
+        }
     }
 
-    pub fn load_url(&mut self, url: &str) {
-        self.webview.load_url(url).unwrap();
+    /// Emit the error after the current update, so the subscribers added right after
+    /// loading the url can receive it.
+    fn emit_load_error(err: wry::Error, cx: &mut ViewContext<Self>) {
+        let message: SharedString = err.to_string().into();
+        cx.defer(move |_, cx| cx.emit(WebViewEvent::LoadError(message)));
     }
 }
 
@@ -79,6 +190,7 @@ impl FocusableView for WebView {
 }
 
 impl EventEmitter<DismissEvent> for WebView {}
+impl EventEmitter<WebViewEvent> for WebView {}
 
 impl Render for WebView {
     fn render(&mut self, cx: &mut gpui::ViewContext<Self>) -> impl IntoElement {
@@ -0,0 +1,141 @@
+use gpui::{point, size, AppContext, Bounds, DisplayId, Pixels, WindowBounds, WindowContext};
+use serde::{Deserialize, Serialize};
+
+/// Used to serialize and deserialize the window bounds.
+///
+/// The window `id` is used to identify the window, so an application can persist
+/// the state for multiple windows, e.g.: `main`, `settings`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowState {
+    pub id: String,
+    /// The restore bounds of the window, when the window is maximized or fullscreen,
+    /// this is the bounds before the window was maximized or fullscreen.
+    pub bounds: Bounds<Pixels>,
+    #[serde(default)]
+    pub maximized: bool,
+    #[serde(default)]
+    pub fullscreen: bool,
+    /// The display of the window was on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_id: Option<u32>,
+}
+
+impl WindowState {
+    /// Dump the current window state with the given window id.
+    pub fn new(id: impl Into<String>, cx: &WindowContext) -> Self {
+        let (bounds, maximized, fullscreen) = match cx.window_bounds() {
+            WindowBounds::Windowed(bounds) => (bounds, false, false),
+            WindowBounds::Maximized(bounds) => (bounds, true, false),
+            WindowBounds::Fullscreen(bounds) => (bounds, false, true),
+        };
+
+        Self {
+            id: id.into(),
+            bounds,
+            maximized,
+            fullscreen,
+            display_id: cx.display().map(|display| display.id().into()),
+        }
+    }
+
+    /// Return the display to restore the window, if the display was disconnected, return `None`.
+    pub fn display_id(&self, cx: &AppContext) -> Option<DisplayId> {
+        let display_id = self.display_id?;
+        cx.displays()
+            .into_iter()
+            .map(|display| display.id())
+            .find(|id| u32::from(*id) == display_id)
+    }
+
+    /// Return the [`WindowBounds`] to restore the window.
+    ///
+    /// The bounds will be clamped into the visible area of the display (excluding the menu bar and dock),
+    /// to avoid the window opened out of the screen when the display has been changed.
+    pub fn window_bounds(&self, cx: &AppContext) -> WindowBounds {
+        let display = self
+            .display_id(cx)
+            .and_then(|id| cx.find_display(id))
+            .or_else(|| cx.primary_display());
+
+        let bounds = match display {
+            Some(display) => clamp_bounds(self.bounds, display.visible_bounds()),
+            None => self.bounds,
+        };
+
+        if self.fullscreen {
+            WindowBounds::Fullscreen(bounds)
+        } else if self.maximized {
+            WindowBounds::Maximized(bounds)
+        } else {
+            WindowBounds::Windowed(bounds)
+        }
+    }
+}
+
+/// Clamp the bounds into the visible bounds, the size will be limited to the visible size.
+pub(crate) fn clamp_bounds(bounds: Bounds<Pixels>, visible: Bounds<Pixels>) -> Bounds<Pixels> {
+    let size = size(
+        bounds.size.width.min(visible.size.width),
+        bounds.size.height.min(visible.size.height),
+    );
+
+    let origin = point(
+        bounds
+            .origin
+            .x
+            .max(visible.left())
+            .min(visible.right() - size.width),
+        bounds
+            .origin
+            .y
+            .max(visible.top())
+            .min(visible.bottom() - size.height),
+    );
+
+    Bounds { origin, size }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{point, px, size, Bounds};
+
+    use super::clamp_bounds;
+
+    #[test]
+    fn test_clamp_bounds() {
+        let visible = Bounds {
+            origin: point(px(0.), px(0.)),
+            size: size(px(1920.), px(1080.)),
+        };
+
+        let bounds = Bounds {
+            origin: point(px(100.), px(100.)),
+            size: size(px(800.), px(600.)),
+        };
+        assert_eq!(clamp_bounds(bounds, visible), bounds);
+
+        // Out of the right bottom
+        let bounds = Bounds {
+            origin: point(px(1800.), px(1000.)),
+            size: size(px(800.), px(600.)),
+        };
+        assert_eq!(
+            clamp_bounds(bounds, visible).origin,
+            point(px(1120.), px(480.))
+        );
+
+        // Out of the left top, on the other display
+        let bounds = Bounds {
+            origin: point(px(-2000.), px(-50.)),
+            size: size(px(800.), px(600.)),
+        };
+        assert_eq!(clamp_bounds(bounds, visible).origin, point(px(0.), px(0.)));
+
+        // Larger than the display
+        let bounds = Bounds {
+            origin: point(px(10.), px(10.)),
+            size: size(px(2560.), px(1440.)),
+        };
+        assert_eq!(clamp_bounds(bounds, visible), visible);
+    }
+}